@@ -40,7 +40,7 @@ Launch with:  uv run service.py
 """
 
 from flask import request, Response
-from starkbot_sdk import create_app, success, error
+from starkbot_sdk import create_app, internal_token, module_token, success, error
 import sqlite3
 import os
 import json
@@ -90,7 +90,6 @@ def ed25519_sign(secret_key_hex: str, message: str) -> str:
 
 DB_PATH = os.path.join(os.path.dirname(os.path.abspath(__file__)), "hyper_claw.db")
 BACKEND_URL = os.environ.get("STARKBOT_BACKEND_URL", "http://127.0.0.1:8080")
-INTERNAL_TOKEN = os.environ.get("STARKBOT_INTERNAL_TOKEN", "")
 ALCHEMY_API_KEY = os.environ.get("ALCHEMY_API_KEY", "")
 
 # Orderly Network
@@ -623,14 +622,14 @@ def poll_receipt(tx_hash: str, attempts: int = 12, delay: float = 5.0) -> dict |
 
 def fire_hook(event: str, data: dict | None = None):
     """Fire a custom persona hook via the backend internal API."""
-    if not INTERNAL_TOKEN:
+    if not internal_token():
         logging.warning("[HYPER_CLAW] No STARKBOT_INTERNAL_TOKEN — cannot fire hooks")
         return
     try:
         http_requests.post(
             f"{BACKEND_URL}/api/internal/hooks/fire",
             json={"event": event, "data": data or {}},
-            headers={"X-Internal-Token": INTERNAL_TOKEN},
+            headers={"X-Internal-Token": internal_token(), "X-Module-Token": module_token()},
             timeout=10,
         )
     except Exception as e:
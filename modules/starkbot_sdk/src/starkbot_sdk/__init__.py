@@ -1,13 +1,16 @@
 """StarkBot SDK — shared helpers for Python modules."""
 
-from starkbot_sdk.responses import success, error, status_response
-from starkbot_sdk.app import create_app
+from starkbot_sdk.responses import success, error, status_response, ErrorCode
+from starkbot_sdk.app import create_app, internal_token, module_token
 
 __all__ = [
     "success",
     "error",
     "status_response",
+    "ErrorCode",
     "create_app",
+    "internal_token",
+    "module_token",
 ]
 
 # TUI dashboard support available via: from starkbot_sdk.tui import StarkbotDashboard
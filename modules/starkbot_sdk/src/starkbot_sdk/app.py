@@ -1,10 +1,31 @@
 """Flask app factory for StarkBot modules."""
 
-from flask import Flask
+from flask import Flask, request
 from starkbot_sdk.responses import status_response
+import hmac
+import os
 import time
 
 
+def internal_token() -> str:
+    """The current token for calling the backend's internal API.
+
+    Read on every call: the backend may rotate it at runtime (see the
+    /rpc/internal/token route below), so don't cache it at import time.
+    """
+    return os.environ.get("STARKBOT_INTERNAL_TOKEN", "")
+
+
+def module_token() -> str:
+    """This service's own identity token, issued by the backend when it spawned us.
+
+    Send it as ``X-Module-Token`` on wallet proxy calls (/api/internal/wallet/*);
+    the backend rejects those without it. Empty when the service was started
+    outside the backend.
+    """
+    return os.environ.get("STARKBOT_MODULE_TOKEN", "")
+
+
 def create_app(module_name: str, *, status_extra_fn=None) -> Flask:
     """Create a Flask app with /rpc/status pre-wired.
 
@@ -24,6 +45,21 @@ def create_app(module_name: str, *, status_extra_fn=None) -> Flask:
         extra = status_extra_fn() if status_extra_fn else None
         return status_response(module_name, extra=extra, start_time=start_time)
 
+    @app.route("/rpc/internal/token", methods=["POST"])
+    def _rpc_internal_token():
+        # Pushed by the backend when it rotates the internal token; authenticated
+        # with the token being replaced
+        from starkbot_sdk.responses import error, success
+        current = internal_token()
+        provided = request.headers.get("X-Internal-Token", "")
+        if not current or not hmac.compare_digest(provided, current):
+            return error("Invalid internal token", 401)
+        new_token = (request.get_json(silent=True) or {}).get("token", "")
+        if not new_token:
+            return error("token is required", 400)
+        os.environ["STARKBOT_INTERNAL_TOKEN"] = new_token
+        return success({"updated": True})
+
     @app.errorhandler(404)
     def _not_found(e):
         from starkbot_sdk.responses import error
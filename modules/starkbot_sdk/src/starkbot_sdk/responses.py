@@ -2,21 +2,57 @@
 
 All module RPC endpoints should return JSON in the envelope format:
     {"success": true, "data": ...}
-    {"success": false, "error": "..."}
+    {"success": false, "error": "...", "error_code": "..."}
+
+`error_code` is one of the ErrorCode values. It was added after the envelope
+shipped, so clients must treat it as optional and fall back to `error`.
 """
 
+from enum import Enum
 from flask import jsonify
 import time
 
 
+class ErrorCode(str, Enum):
+    """Machine-readable error classes for RPC error responses."""
+
+    VALIDATION = "validation"
+    NOT_FOUND = "not_found"
+    CONFLICT = "conflict"
+    RATE_LIMITED = "rate_limited"
+    UPSTREAM = "upstream"
+    INTERNAL = "internal"
+
+    @property
+    def transient(self):
+        """Whether retrying the same request may succeed."""
+        return self in (ErrorCode.RATE_LIMITED, ErrorCode.UPSTREAM, ErrorCode.INTERNAL)
+
+
+_STATUS_CODES = {
+    404: ErrorCode.NOT_FOUND,
+    409: ErrorCode.CONFLICT,
+    429: ErrorCode.RATE_LIMITED,
+    502: ErrorCode.UPSTREAM,
+    503: ErrorCode.UPSTREAM,
+    504: ErrorCode.UPSTREAM,
+}
+
+
 def success(data):
     """Return a successful RPC response."""
     return jsonify({"success": True, "data": data})
 
 
-def error(msg, status=400):
-    """Return an error RPC response with the given HTTP status code."""
-    return jsonify({"success": False, "error": msg}), status
+def error(msg, status=400, code=None):
+    """Return an error RPC response with the given HTTP status code.
+
+    `code` is an ErrorCode; when omitted it is derived from the status
+    (4xx defaults to validation, 5xx to internal).
+    """
+    if code is None:
+        code = _STATUS_CODES.get(status, ErrorCode.INTERNAL if status >= 500 else ErrorCode.VALIDATION)
+    return jsonify({"success": False, "error": msg, "error_code": ErrorCode(code).value}), status
 
 
 def status_response(module_name, *, extra=None, start_time=None):
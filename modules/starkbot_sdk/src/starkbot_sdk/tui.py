@@ -85,6 +85,7 @@ def notify_tui_update(module_name: str) -> None:
     """Fire-and-forget POST to the backend to broadcast a TUI invalidation event."""
     backend_url = os.environ.get("STARKBOT_SELF_URL", "http://127.0.0.1:8080")
     token = os.environ.get("STARKBOT_INTERNAL_TOKEN", "")
+    module_token = os.environ.get("STARKBOT_MODULE_TOKEN", "")
     if not token:
         return
 
@@ -93,7 +94,7 @@ def notify_tui_update(module_name: str) -> None:
             httpx.post(
                 f"{backend_url}/api/internal/modules/tui-invalidate",
                 json={"module": module_name},
-                headers={"X-Internal-Token": token},
+                headers={"X-Internal-Token": token, "X-Module-Token": module_token},
                 timeout=2,
             )
         except Exception:
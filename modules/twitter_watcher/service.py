@@ -21,7 +21,7 @@ from datetime import datetime, timezone
 
 import tweepy
 from flask import request
-from starkbot_sdk import create_app, error, success
+from starkbot_sdk import create_app, internal_token, module_token, error, success
 
 log = logging.getLogger("twitter_watcher")
 
@@ -35,7 +35,6 @@ ACCESS_TOKEN = os.environ.get("TWITTER_ACCESS_TOKEN", "")
 ACCESS_TOKEN_SECRET = os.environ.get("TWITTER_ACCESS_TOKEN_SECRET", "")
 
 BACKEND_URL = os.environ.get("STARKBOT_SELF_URL", "http://127.0.0.1:8080")
-INTERNAL_TOKEN = os.environ.get("STARKBOT_INTERNAL_TOKEN", "")
 
 DEFAULT_POLL_INTERVAL = 120  # seconds
 MIN_POLL_INTERVAL = 30
@@ -96,7 +95,7 @@ def _resolve_user_id(client: tweepy.Client, username: str) -> str | None:
 
 def _fire_hook(payload: dict) -> None:
     """Fire twitter_watched_tweet hook via backend internal API."""
-    if not INTERNAL_TOKEN:
+    if not internal_token():
         log.warning("No STARKBOT_INTERNAL_TOKEN — cannot fire hook")
         return
 
@@ -109,7 +108,7 @@ def _fire_hook(payload: dict) -> None:
                 "event": "twitter_watched_tweet",
                 "data": payload,
             },
-            headers={"X-Internal-Token": INTERNAL_TOKEN},
+            headers={"X-Internal-Token": internal_token(), "X-Module-Token": module_token()},
             timeout=10,
         )
         log.info("Fired twitter_watched_tweet hook for @%s", payload.get("username"))
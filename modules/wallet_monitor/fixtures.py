@@ -0,0 +1,25 @@
+"""Shared alchemy_getAssetTransfers-shaped payloads for the wallet monitor tests."""
+
+WALLET = "0x1111111111111111111111111111111111111111"
+OTHER = "0x2222222222222222222222222222222222222222"
+USDC = "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+NFT_CONTRACT = "0xbc4ca0eda7647a8ab7c2061c2e118a18a936f13d"
+
+
+def transfer(category, frm, to, value=None, asset=None, contract=None, token_id=None):
+    t = {
+        "blockNum": "0x12a05f2",
+        "uniqueId": f"0xabc:{category}",
+        "hash": "0xabc",
+        "from": frm,
+        "to": to,
+        "value": value,
+        "asset": asset,
+        "category": category,
+        "rawContract": {"value": None, "address": contract, "decimal": None},
+        "metadata": {"blockTimestamp": "2024-05-01T12:00:00.000Z"},
+    }
+    if token_id is not None:
+        t["erc721TokenId"] = token_id
+        t["tokenId"] = token_id
+    return t
@@ -1,6 +1,6 @@
 # /// script
 # requires-python = ">=3.12"
-# dependencies = ["flask", "requests", "starkbot-sdk"]
+# dependencies = ["flask", "requests", "starkbot-sdk", "websocket-client"]
 #
 # [tool.uv.sources]
 # starkbot-sdk = { path = "../starkbot_sdk" }
@@ -10,13 +10,34 @@ Wallet Monitor module — monitors ETH wallets for on-chain activity and whale t
 
 Supports Ethereum Mainnet and Base chains via Alchemy Enhanced APIs.
 Background worker polls every 40s, detects swaps, estimates USD values,
-and flags large trades above configurable thresholds.
+and flags large trades above configurable thresholds. Watched addresses with
+an ENS primary name get it backfilled as `resolved_name` (kept separate from
+the user's `label`) and refreshed every WALLET_MONITOR_NAME_TTL_SECS.
+
+Entries with copy_trade_enabled have their swaps mirrored: the same pair is
+quoted via the 0x API, scaled by COPY_TRADE_SCALE and capped at the entry's
+copy_trade_max_usd, signed through the backend's internal wallet proxy and
+broadcast. COPY_TRADE_DRY_RUN (on by default) only logs the intended trade.
+
+With WALLET_MONITOR_MEMPOOL=true the service also subscribes to Alchemy's
+pending-transaction stream for watched addresses and records early activity
+with status "pending". The poller replaces those rows with the confirmed
+entries; pending rows that never confirm are dropped after
+WALLET_MONITOR_PENDING_TTL_MINS.
+
+Large-trade alerts go to ALERT_CALLBACK_URL as they happen, or with
+WALLET_MONITOR_ALERT_MODE=digest|both as a summary posted every
+WALLET_MONITOR_DIGEST_INTERVAL_SECS.
 
 RPC protocol endpoints:
   GET  /rpc/status             -> service health
   POST /rpc/tools/watchlist    -> manage watchlist (action-based)
   POST /rpc/tools/activity     -> query activity (action-based)
   POST /rpc/tools/control      -> worker control (action-based)
+  GET  /rpc/csv/export         -> download the watchlist as CSV
+  POST /rpc/csv/import         -> add watchlist entries from a CSV upload
+  POST /rpc/backfill           -> scan a watched wallet's history (background job)
+  GET  /rpc/backfill[/<id>]    -> backfill job progress
   POST /rpc/backup/export      -> export watchlist for backup
   POST /rpc/backup/restore     -> restore watchlist from backup
   GET  /                       -> HTML dashboard
@@ -24,31 +45,132 @@ RPC protocol endpoints:
 Launch with:  uv run service.py
 """
 
-from flask import request
-from starkbot_sdk import create_app, success, error
+from flask import request, Response
+from starkbot_sdk import create_app, internal_token, module_token, success, error, ErrorCode
 import sqlite3
+import csv
+import io
 import os
 import re
 import json
 import time
 import logging
+import queue
 import threading
 import requests as http_requests
 from datetime import datetime, timezone
+from enum import Enum
+from html import escape
 
 DB_PATH = os.path.join(os.path.dirname(os.path.abspath(__file__)), "wallet_monitor.db")
 POLL_INTERVAL = int(os.environ.get("WALLET_MONITOR_POLL_INTERVAL", "40"))
+# Floor for per-wallet poll intervals, and how often the worker checks which wallets are due
+MIN_POLL_INTERVAL = 5
 ALCHEMY_API_KEY = os.environ.get("ALCHEMY_API_KEY", "")
 ALERT_CALLBACK_URL = os.environ.get("ALERT_CALLBACK_URL")
+# Default per-wallet alert cooldown: after an alert, further alerts for the wallet are held back
+# this long and reported as a count (0 = no cooldown). Entries can override it.
+ALERT_COOLDOWN_SECS = int(os.environ.get("WALLET_MONITOR_ALERT_COOLDOWN_SECS", "0"))
+# "instant" sends each large trade as it happens, "digest" only a periodic summary, "both" does both
+ALERT_MODE = os.environ.get("WALLET_MONITOR_ALERT_MODE", "instant").lower()
+DIGEST_INTERVAL_SECS = int(os.environ.get("WALLET_MONITOR_DIGEST_INTERVAL_SECS", "3600"))
+# Largest trades listed individually in a digest; the totals cover all of them
+DIGEST_MAX_TRADES = 20
+BACKEND_URL = os.environ.get("STARKBOT_BACKEND_URL", "http://127.0.0.1:8080")
+ZEROX_API_KEY = os.environ.get("ZEROX_API_KEY", "")
 FIRST_RUN_LOOKBACK_BLOCKS = 500
-PRICE_CACHE_TTL = 60
+# Only blocks at least this deep are processed, so shallow reorgs never reach the database
+CONFIRMATIONS = int(os.environ.get("WALLET_MONITOR_CONFIRMATIONS", "3"))
+# Already-processed blocks re-scanned each poll to catch reorgs deeper than CONFIRMATIONS
+REORG_WINDOW = int(os.environ.get("WALLET_MONITOR_REORG_WINDOW", "12"))
+# Most new blocks scanned per wallet poll; a wallet further behind catches up over several polls
+MAX_BLOCK_RANGE = int(os.environ.get("WALLET_MONITOR_MAX_BLOCK_RANGE", "2000"))
+# Largest history a single backfill may scan, and how many blocks it fetches per request
+MAX_BACKFILL_BLOCKS = int(os.environ.get("WALLET_MONITOR_MAX_BACKFILL_BLOCKS", "200000"))
+BACKFILL_CHUNK_BLOCKS = 10_000
+# Backfills running at once; further ones wait their turn
+MAX_CONCURRENT_BACKFILLS = 2
+# Average block times, used to turn a backfill `since` timestamp into a block count
+BLOCK_TIME_SECS = {"mainnet": 12, "base": 2}
+PRICE_CACHE_TTL = int(os.environ.get("WALLET_MONITOR_PRICE_CACHE_TTL_SECS", "60"))
+# Expired entries are pruned once the cache reaches this size
+PRICE_CACHE_MAX_ENTRIES = 1000
+MAX_BATCH_SIZE = 500
+MAX_CSV_BYTES = 5 * 1024 * 1024  # 5 MB
+MAX_CSV_ROWS = 10_000
+SUPPORTED_CHAINS = ("mainnet", "base")
+# Comma-separated spender addresses that are known to be malicious
+SPENDER_DENYLIST = {a.strip().lower() for a in os.environ.get("WALLET_MONITOR_SPENDER_DENYLIST", "").split(",") if a.strip()}
+NAME_RESOLUTION_ENABLED = os.environ.get("WALLET_MONITOR_NAME_RESOLUTION", "true").lower() not in ("0", "false", "no")
+NAME_TTL_SECS = int(os.environ.get("WALLET_MONITOR_NAME_TTL_SECS", str(24 * 3600)))
+NAME_BATCH_SIZE = 50
+MEMPOOL_ENABLED = os.environ.get("WALLET_MONITOR_MEMPOOL", "false").lower() in ("1", "true", "yes")
+MEMPOOL_CHAINS = [c.strip() for c in os.environ.get("WALLET_MONITOR_MEMPOOL_CHAINS", "mainnet").split(",") if c.strip()]
+PENDING_TTL_MINS = int(os.environ.get("WALLET_MONITOR_PENDING_TTL_MINS", "30"))
+# alchemy_pendingTransactions accepts at most 1000 addresses per filter
+MEMPOOL_MAX_ADDRESSES = 1000
+
+
+def _env_set(name: str) -> set[str]:
+    return {v.strip().lower() for v in os.environ.get(name, "").split(",") if v.strip()}
+
+
+COPY_TRADE_DRY_RUN = os.environ.get("COPY_TRADE_DRY_RUN", "true").lower() not in ("0", "false", "no")
+COPY_TRADE_SCALE = float(os.environ.get("COPY_TRADE_SCALE", "0.1"))
+COPY_TRADE_MIN_USD = float(os.environ.get("COPY_TRADE_MIN_USD", "1"))
+COPY_TRADE_SLIPPAGE_BPS = int(os.environ.get("COPY_TRADE_SLIPPAGE_BPS", "100"))
+# Token symbols or addresses. An empty allowlist allows everything not denied.
+COPY_TRADE_ALLOWED_TOKENS = _env_set("COPY_TRADE_ALLOWED_TOKENS")
+COPY_TRADE_DENIED_TOKENS = _env_set("COPY_TRADE_DENIED_TOKENS")
+CHAIN_IDS = {"mainnet": 1, "base": 8453}
+ZEROX_QUOTE_URL = "https://api.0x.org/swap/allowance-holder/quote"
+# ENS ReverseRecords: getNames(address[]) returns forward-verified primary names
+ENS_REVERSE_RECORDS = "0x3671aE578E63FdF66ad4F3E12CC0c0d71Ac7510C"
+ENS_GET_NAMES_SELECTOR = "cbf8b66c"
 
 # Module-level state for worker
 _start_time = time.time()
 _last_tick_at = None
+_last_tick_ts = None
 _last_tick_lock = threading.Lock()
-_price_cache: dict[str, tuple[float, float]] = {}  # symbol -> (price, timestamp)
+# watchlist id -> monotonic time the wallet is next due for polling
+_next_poll_at: dict[int, float] = {}
+_next_poll_lock = threading.Lock()
+# job id -> backfill job progress (kept for the life of the process)
+_backfill_jobs: dict[int, dict] = {}
+_backfill_lock = threading.Lock()
+_backfill_slots = threading.BoundedSemaphore(MAX_CONCURRENT_BACKFILLS)
+# watchlist id -> alert cooldown state: last alert sent, and what was suppressed since
+_alert_throttle: dict[int, dict] = {}
+_alert_throttle_lock = threading.Lock()
+# Copy trades run one at a time on a single worker: they all sign from the bot wallet,
+# so running them concurrently would race for its nonce
+_copy_trade_queue: queue.Queue = queue.Queue()
+_copy_trade_worker: threading.Thread | None = None
+_copy_trade_worker_lock = threading.Lock()
+# created_at (sqlite UTC format) up to which large trades were covered by a digest
+_digest_covered_until = datetime.now(timezone.utc).strftime("%Y-%m-%d %H:%M:%S")
+_digest_last_sent = time.monotonic()
+# Counters since process start, exported by /metrics
+_metrics_lock = threading.Lock()
+_metrics = {
+    "transactions": {},       # chain -> new activity rows
+    "large_trades": {},       # chain -> large trade alerts
+    "alerts_sent": {},        # alert_type -> delivered callbacks
+    "alerts_failed": {},      # alert_type -> failed callbacks
+    "alerts_suppressed": {},  # alert_type -> alerts held back by a wallet's cooldown
+    "poll_errors": {},        # chain -> failed wallet polls
+    "poll_seconds_sum": {},   # chain -> total wallet poll time
+    "poll_seconds_count": {}, # chain -> wallet polls
+    "last_poll_seconds": {},  # chain -> time spent polling the chain in the last tick
+    "reorged": {},            # chain -> activity rows dropped or moved by a reorg
+}
+# (chain, symbol) -> (price or None if unpriced, timestamp)
+_price_cache: dict[tuple[str, str], tuple[float | None, float]] = {}
 _price_cache_lock = threading.Lock()
+_price_cache_stats = {"hits": 0, "misses": 0}
+# One lock per key, so concurrent misses on the same token share a single fetch
+_price_fetch_locks: dict[tuple[str, str], threading.Lock] = {}
 
 
 # ---------------------------------------------------------------------------
@@ -75,14 +197,32 @@ def init_db():
             large_trade_threshold_usd REAL NOT NULL DEFAULT 1000.0,
             copy_trade_enabled INTEGER NOT NULL DEFAULT 0,
             copy_trade_max_usd REAL,
+            approval_alerts_enabled INTEGER NOT NULL DEFAULT 0,
             last_checked_block INTEGER,
             last_checked_at TEXT,
+            poll_interval_secs INTEGER,
+            alert_cooldown_secs INTEGER,
             notes TEXT,
+            resolved_name TEXT,
+            resolved_name_source TEXT,
+            resolved_name_at TEXT,
             created_at TEXT NOT NULL DEFAULT (datetime('now')),
             updated_at TEXT NOT NULL DEFAULT (datetime('now')),
             UNIQUE(address, chain)
         )
     """)
+    # Columns added after the initial release
+    existing = {r["name"] for r in conn.execute("PRAGMA table_info(wallet_watchlist)").fetchall()}
+    for column, ddl in (
+        ("resolved_name", "TEXT"),
+        ("resolved_name_source", "TEXT"),
+        ("resolved_name_at", "TEXT"),
+        ("approval_alerts_enabled", "INTEGER NOT NULL DEFAULT 0"),
+        ("poll_interval_secs", "INTEGER"),
+        ("alert_cooldown_secs", "INTEGER"),
+    ):
+        if column not in existing:
+            conn.execute(f"ALTER TABLE wallet_watchlist ADD COLUMN {column} {ddl}")
     conn.execute("""
         CREATE TABLE IF NOT EXISTS wallet_activity (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -105,13 +245,40 @@ def init_db():
             swap_to_token TEXT,
             swap_to_amount TEXT,
             raw_data TEXT,
+            status TEXT NOT NULL DEFAULT 'confirmed',
             created_at TEXT NOT NULL DEFAULT (datetime('now')),
             FOREIGN KEY (watchlist_id) REFERENCES wallet_watchlist(id) ON DELETE CASCADE,
             UNIQUE(tx_hash, watchlist_id)
         )
     """)
+    existing = {r["name"] for r in conn.execute("PRAGMA table_info(wallet_activity)").fetchall()}
+    if "status" not in existing:
+        conn.execute("ALTER TABLE wallet_activity ADD COLUMN status TEXT NOT NULL DEFAULT 'confirmed'")
     conn.execute("CREATE INDEX IF NOT EXISTS idx_wallet_activity_watchlist ON wallet_activity(watchlist_id, block_number DESC)")
     conn.execute("CREATE INDEX IF NOT EXISTS idx_wallet_activity_large ON wallet_activity(is_large_trade, created_at DESC)")
+    conn.execute("""
+        CREATE TABLE IF NOT EXISTS copy_trades (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            watchlist_id INTEGER NOT NULL,
+            source_tx_hash TEXT NOT NULL,
+            chain TEXT NOT NULL,
+            sell_token TEXT NOT NULL,
+            sell_symbol TEXT,
+            buy_token TEXT NOT NULL,
+            buy_symbol TEXT,
+            leader_usd REAL,
+            copy_usd REAL,
+            sell_amount_raw TEXT,
+            min_buy_amount_raw TEXT,
+            status TEXT NOT NULL,
+            reason TEXT,
+            tx_hash TEXT,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (watchlist_id) REFERENCES wallet_watchlist(id) ON DELETE CASCADE,
+            UNIQUE(source_tx_hash, watchlist_id)
+        )
+    """)
     conn.execute("CREATE INDEX IF NOT EXISTS idx_wallet_activity_chain ON wallet_activity(chain, block_number DESC)")
     conn.commit()
     conn.close()
@@ -135,7 +302,16 @@ def is_valid_eth_address(addr: str) -> bool:
 # Watchlist operations
 # ---------------------------------------------------------------------------
 
-def watchlist_add(address: str, label: str | None, chain: str, threshold_usd: float):
+def parse_poll_interval(value) -> tuple[int | None, str | None]:
+    """Validate a per-wallet poll interval. None or 0 means "use the global interval"."""
+    if value is None or value == 0:
+        return None, None
+    if isinstance(value, bool) or not isinstance(value, (int, float)) or value < MIN_POLL_INTERVAL:
+        return None, f"poll_interval_secs must be at least {MIN_POLL_INTERVAL} (or 0 for the global {POLL_INTERVAL}s)"
+    return int(value), None
+
+
+def watchlist_add(address: str, label: str | None, chain: str, threshold_usd: float, poll_interval_secs: int | None = None):
     if not is_valid_eth_address(address):
         return None, "Invalid Ethereum address"
     conn = get_db()
@@ -143,8 +319,8 @@ def watchlist_add(address: str, label: str | None, chain: str, threshold_usd: fl
     addr = address.lower()
     try:
         conn.execute(
-            "INSERT INTO wallet_watchlist (address, label, chain, large_trade_threshold_usd, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
-            (addr, label, chain, threshold_usd, ts, ts),
+            "INSERT INTO wallet_watchlist (address, label, chain, large_trade_threshold_usd, poll_interval_secs, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+            (addr, label, chain, threshold_usd, poll_interval_secs, ts, ts),
         )
         conn.commit()
         entry_id = conn.execute("SELECT last_insert_rowid()").fetchone()[0]
@@ -170,10 +346,16 @@ def watchlist_list():
         "SELECT * FROM wallet_watchlist ORDER BY created_at ASC"
     ).fetchall()
     conn.close()
-    return [row_to_dict(r) for r in rows]
+    entries = [row_to_dict(r) for r in rows]
+    for e in entries:
+        e["display_name"] = e.get("label") or e.get("resolved_name")
+        e["effective_poll_interval_secs"] = entry_poll_interval(e)
+        e["effective_alert_cooldown_secs"] = entry_alert_cooldown(e)
+    return entries
 
 
-def watchlist_update(entry_id: int, label=None, threshold_usd=None, monitor_enabled=None, notes=None):
+def watchlist_update(entry_id: int, label=None, threshold_usd=None, monitor_enabled=None, notes=None, approval_alerts_enabled=None,
+                     copy_trade_enabled=None, copy_trade_max_usd=None, poll_interval_secs=None, alert_cooldown_secs=None):
     conn = get_db()
     ts = now_iso()
     updates = ["updated_at = ?"]
@@ -190,19 +372,96 @@ def watchlist_update(entry_id: int, label=None, threshold_usd=None, monitor_enab
     if notes is not None:
         updates.append("notes = ?")
         params.append(notes)
+    if approval_alerts_enabled is not None:
+        updates.append("approval_alerts_enabled = ?")
+        params.append(1 if approval_alerts_enabled else 0)
+    if copy_trade_enabled is not None:
+        updates.append("copy_trade_enabled = ?")
+        params.append(1 if copy_trade_enabled else 0)
+    if copy_trade_max_usd is not None:
+        updates.append("copy_trade_max_usd = ?")
+        params.append(copy_trade_max_usd)
+    if poll_interval_secs is not None:
+        # 0 clears the override, falling back to the global interval
+        updates.append("poll_interval_secs = ?")
+        params.append(poll_interval_secs or None)
+    if alert_cooldown_secs is not None:
+        updates.append("alert_cooldown_secs = ?")
+        params.append(alert_cooldown_secs)
     params.append(entry_id)
     sql = f"UPDATE wallet_watchlist SET {', '.join(updates)} WHERE id = ?"
     cursor = conn.execute(sql, params)
     conn.commit()
     conn.close()
+    if poll_interval_secs is not None:
+        # Reschedule on the next worker pass with the new interval
+        with _next_poll_lock:
+            _next_poll_at.pop(entry_id, None)
     return cursor.rowcount > 0
 
 
+def watchlist_add_batch(items: list) -> list:
+    """Add several wallets in one transaction. Returns a result per item, in order."""
+    conn = get_db()
+    ts = now_iso()
+    results = []
+    for item in items:
+        address = item.get("address") if isinstance(item, dict) else None
+        if not address or not is_valid_eth_address(address):
+            results.append({"address": address, "success": False, "error": "Invalid Ethereum address", "error_code": ErrorCode.VALIDATION.value})
+            continue
+        chain = item.get("chain", "mainnet")
+        poll_interval, err = parse_poll_interval(item.get("poll_interval_secs"))
+        if err:
+            results.append({"address": address, "success": False, "error": err, "error_code": ErrorCode.VALIDATION.value})
+            continue
+        try:
+            cursor = conn.execute(
+                "INSERT INTO wallet_watchlist (address, label, chain, large_trade_threshold_usd, monitor_enabled, copy_trade_enabled, poll_interval_secs, notes, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                (
+                    address.lower(), item.get("label"), chain, item.get("threshold_usd", 1000.0),
+                    1 if item.get("monitor_enabled", True) else 0,
+                    1 if item.get("copy_trade_enabled", False) else 0,
+                    poll_interval, item.get("notes"), ts, ts,
+                ),
+            )
+            results.append({"address": address, "success": True, "id": cursor.lastrowid})
+        except sqlite3.IntegrityError:
+            results.append({
+                "address": address, "success": False,
+                "error": f"Wallet {address} already on watchlist for chain {chain}",
+                "error_code": ErrorCode.CONFLICT.value,
+            })
+    conn.commit()
+    conn.close()
+    return results
+
+
+def watchlist_remove_batch(entry_ids: list) -> list:
+    """Remove several entries in one transaction. Returns a result per id, in order."""
+    conn = get_db()
+    results = []
+    for entry_id in entry_ids:
+        cursor = conn.execute("DELETE FROM wallet_watchlist WHERE id = ?", (entry_id,))
+        if cursor.rowcount > 0:
+            results.append({"id": entry_id, "success": True})
+        else:
+            results.append({"id": entry_id, "success": False, "error": f"Entry #{entry_id} not found", "error_code": ErrorCode.NOT_FOUND.value})
+    conn.commit()
+    conn.close()
+    return results
+
+
+def batch_summary(results: list) -> dict:
+    succeeded = sum(1 for r in results if r["success"])
+    return {"succeeded": succeeded, "failed": len(results) - succeeded, "results": results}
+
+
 # ---------------------------------------------------------------------------
 # Activity operations
 # ---------------------------------------------------------------------------
 
-def activity_query(watchlist_id=None, address=None, activity_type=None, chain=None, large_only=False, limit=50):
+def activity_query(watchlist_id=None, address=None, activity_type=None, chain=None, large_only=False, status=None, limit=50):
     conn = get_db()
     conditions = ["1=1"]
     params: list = []
@@ -220,11 +479,15 @@ def activity_query(watchlist_id=None, address=None, activity_type=None, chain=No
         params.append(chain)
     if large_only:
         conditions.append("a.is_large_trade = 1")
+    if status:
+        conditions.append("a.status = ?")
+        params.append(status)
     limit = min(limit or 50, 200)
+    # Pending rows have no block yet, so they sort ahead of confirmed ones
     sql = f"""
         SELECT a.* FROM wallet_activity a
         WHERE {' AND '.join(conditions)}
-        ORDER BY a.block_number DESC, a.id DESC
+        ORDER BY (a.status = 'pending') DESC, a.block_number DESC, a.id DESC
         LIMIT {limit}
     """
     rows = conn.execute(sql, params).fetchall()
@@ -254,7 +517,7 @@ def activity_stats():
 def backup_export():
     conn = get_db()
     rows = conn.execute(
-        "SELECT address, label, chain, monitor_enabled, large_trade_threshold_usd, copy_trade_enabled, copy_trade_max_usd, notes FROM wallet_watchlist ORDER BY created_at ASC"
+        "SELECT address, label, chain, monitor_enabled, large_trade_threshold_usd, copy_trade_enabled, copy_trade_max_usd, approval_alerts_enabled, poll_interval_secs, alert_cooldown_secs, notes FROM wallet_watchlist ORDER BY created_at ASC"
     ).fetchall()
     conn.close()
     return [row_to_dict(r) for r in rows]
@@ -271,12 +534,12 @@ def backup_restore(wallets: list) -> int:
         if not addr:
             continue
         conn.execute(
-            "INSERT OR IGNORE INTO wallet_watchlist (address, label, chain, monitor_enabled, large_trade_threshold_usd, copy_trade_enabled, copy_trade_max_usd, notes, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT OR IGNORE INTO wallet_watchlist (address, label, chain, monitor_enabled, large_trade_threshold_usd, copy_trade_enabled, copy_trade_max_usd, approval_alerts_enabled, poll_interval_secs, alert_cooldown_secs, notes, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
             (
                 addr, entry.get("label"), entry.get("chain", "mainnet"),
                 entry.get("monitor_enabled", 1), entry.get("large_trade_threshold_usd", 1000.0),
                 entry.get("copy_trade_enabled", 0), entry.get("copy_trade_max_usd"),
-                entry.get("notes"), ts, ts,
+                entry.get("approval_alerts_enabled", 0), entry.get("poll_interval_secs"), entry.get("alert_cooldown_secs"), entry.get("notes"), ts, ts,
             ),
         )
         count += 1
@@ -306,13 +569,22 @@ def alchemy_get_block_number(chain: str) -> int:
     return int(hex_str, 16)
 
 
-def alchemy_get_asset_transfers(chain: str, address: str, from_block: int | None, direction: str) -> list[dict]:
+# Most transfers fetched per call before paging stops and the result is reported truncated
+MAX_TRANSFERS_PER_FETCH = 5000
+
+
+def alchemy_get_asset_transfers(chain: str, address: str, from_block: int | None, direction: str,
+                                to_block: int | None = None) -> tuple[list[dict], bool]:
+    """Transfers in block order, and whether paging stopped at MAX_TRANSFERS_PER_FETCH
+    before the end of the range."""
     url = alchemy_base_url(chain)
     from_block_hex = f"0x{from_block:x}" if from_block is not None else "0x0"
-    categories = ["external", "erc20"] if chain == "base" else ["external", "internal", "erc20"]
+    categories = ["external", "erc20", "erc721", "erc1155"]
+    if chain != "base":
+        categories.append("internal")
     params = {
         "fromBlock": from_block_hex,
-        "toBlock": "latest",
+        "toBlock": f"0x{to_block:x}" if to_block is not None else "latest",
         "category": categories,
         "withMetadata": True,
         "maxCount": "0x3e8",
@@ -337,15 +609,283 @@ def alchemy_get_asset_transfers(chain: str, address: str, from_block: int | None
         transfers = result.get("transfers", [])
         all_transfers.extend(transfers)
         page_key = result.get("pageKey")
-        if not page_key or len(all_transfers) > 5000:
-            break
-    return all_transfers
+        if not page_key:
+            return all_transfers, False
+        if len(all_transfers) >= MAX_TRANSFERS_PER_FETCH:
+            return all_transfers, True
+
+
+def alchemy_get_tx_input(chain: str, tx_hash: str) -> str | None:
+    url = alchemy_base_url(chain)
+    body = {"id": 1, "jsonrpc": "2.0", "method": "eth_getTransactionByHash", "params": [tx_hash]}
+    resp = http_requests.post(url, json=body, timeout=15)
+    data = resp.json()
+    if "error" in data and data["error"]:
+        raise RuntimeError(f"eth_getTransactionByHash error: {data['error'].get('message', '')}")
+    return (data.get("result") or {}).get("input")
+
+
+def alchemy_get_token_metadata(chain: str, token_address: str) -> dict:
+    url = alchemy_base_url(chain)
+    body = {"id": 1, "jsonrpc": "2.0", "method": "alchemy_getTokenMetadata", "params": [token_address]}
+    resp = http_requests.post(url, json=body, timeout=15)
+    data = resp.json()
+    if "error" in data and data["error"]:
+        raise RuntimeError(f"alchemy_getTokenMetadata error: {data['error'].get('message', '')}")
+    return data.get("result") or {}
+
+
+def alchemy_eth_call(chain: str, to: str, data: str) -> str:
+    url = alchemy_base_url(chain)
+    body = {"id": 1, "jsonrpc": "2.0", "method": "eth_call", "params": [{"to": to, "data": data}, "latest"]}
+    resp = http_requests.post(url, json=body, timeout=15)
+    data = resp.json()
+    if "error" in data and data["error"]:
+        raise RuntimeError(f"eth_call error: {data['error'].get('message', '')}")
+    return data.get("result", "0x")
 
 
 def parse_block_number(hex_str: str) -> int:
     return int(hex_str.replace("0x", ""), 16) if hex_str else 0
 
 
+# ---------------------------------------------------------------------------
+# Activity Classification
+# ---------------------------------------------------------------------------
+
+class ActivityType(str, Enum):
+    """Values stored in wallet_activity.activity_type."""
+
+    ETH_TRANSFER = "eth_transfer"
+    ERC20_TRANSFER = "erc20_transfer"
+    INTERNAL = "internal"
+    SWAP = "swap"
+    NFT_MINT = "nft_mint"
+    NFT_SALE = "nft_sale"
+    NFT_TRANSFER = "nft_transfer"
+    APPROVAL = "approval"
+    CONTRACT_DEPLOY = "contract_deploy"
+    BRIDGE_DEPOSIT = "bridge_deposit"
+    BRIDGE_WITHDRAWAL = "bridge_withdrawal"
+
+
+PLAIN_TRANSFER_TYPES = {ActivityType.ETH_TRANSFER.value, ActivityType.ERC20_TRANSFER.value, ActivityType.INTERNAL.value}
+ZERO_ADDRESS = "0x0000000000000000000000000000000000000000"
+NFT_CATEGORIES = {"erc721", "erc1155", "specialnft"}
+VALUE_CATEGORIES = {"external", "internal", "erc20"}
+# approve(address,uint256), setApprovalForAll(address,bool), increaseAllowance(address,uint256)
+APPROVAL_SELECTORS = {"0x095ea7b3", "0xa22cb465", "0x39509351"}
+# Canonical L1<->L2 bridge contracts. Sending to one is a deposit on L1 and a
+# withdrawal on L2; receiving from one is the reverse.
+BRIDGE_CONTRACTS = {
+    "mainnet": {
+        "0x49048044d57e1c92a77f79988d21fa8faf74e97e",  # Base OptimismPortal
+        "0x3154cf16ccdb4c6d922629664174b904d80f2c35",  # Base L1StandardBridge
+        "0x99c9fc46f92e8a1c0dec1b1747d010903e884be1",  # Optimism L1StandardBridge
+        "0x4dbd4fc535ac27206064b68ffcf827b0a60bab3f",  # Arbitrum Delayed Inbox
+    },
+    "base": {
+        "0x4200000000000000000000000000000000000010",  # L2StandardBridge
+        "0x4200000000000000000000000000000000000016",  # L2ToL1MessagePasser
+    },
+}
+L1_CHAINS = {"mainnet"}
+
+
+def is_swap_tx(transfers: list[tuple[dict, str]]) -> bool:
+    """ERC-20 tokens both leave and arrive in the same transaction."""
+    directions = {d for t, d in transfers if t.get("category") == "erc20"}
+    return directions == {"outgoing", "incoming"}
+
+
+def needs_tx_input(transfer: dict, direction: str) -> bool:
+    """Zero-value outgoing calls may be approvals, which only the calldata reveals."""
+    return (
+        direction == "outgoing"
+        and transfer.get("category") == "external"
+        and transfer.get("to") is not None
+        and not transfer.get("value")
+    )
+
+
+def classify_transfer(transfer: dict, direction: str, tx_transfers: list[tuple[dict, str]], chain: str, tx_input: str | None = None) -> ActivityType:
+    """Classify one Alchemy asset transfer in the context of the other transfers in its transaction.
+
+    `direction` is "outgoing" or "incoming" relative to the watched wallet, and
+    `tx_input` is the transaction calldata when it was fetched (see needs_tx_input).
+    """
+    category = transfer.get("category", "")
+    if category == "external" and direction == "outgoing" and transfer.get("to") is None:
+        return ActivityType.CONTRACT_DEPLOY
+
+    counterparty = (transfer.get("to") if direction == "outgoing" else transfer.get("from")) or ""
+    if counterparty.lower() in BRIDGE_CONTRACTS.get(chain, set()):
+        to_l2 = (direction == "outgoing") == (chain in L1_CHAINS)
+        return ActivityType.BRIDGE_DEPOSIT if to_l2 else ActivityType.BRIDGE_WITHDRAWAL
+
+    def is_mint(t: dict, d: str) -> bool:
+        return t.get("category") in NFT_CATEGORIES and d == "incoming" and (t.get("from") or "").lower() == ZERO_ADDRESS
+
+    nft_directions = {d for t, d in tx_transfers if t.get("category") in NFT_CATEGORIES}
+    value_directions = {d for t, d in tx_transfers if t.get("category") in VALUE_CATEGORIES and t.get("value")}
+    # An NFT moving one way while value moves the other is a sale (or purchase)
+    is_nft_sale = any(d != v for d in nft_directions for v in value_directions)
+
+    if category in NFT_CATEGORIES:
+        if is_mint(transfer, direction):
+            return ActivityType.NFT_MINT
+        return ActivityType.NFT_SALE if is_nft_sale else ActivityType.NFT_TRANSFER
+    if is_nft_sale:
+        # The payment leg takes the NFT leg's type so the whole transaction reads the same
+        return ActivityType.NFT_MINT if any(is_mint(t, d) for t, d in tx_transfers) else ActivityType.NFT_SALE
+    if is_swap_tx(tx_transfers):
+        return ActivityType.SWAP
+    if tx_input and tx_input[:10].lower() in APPROVAL_SELECTORS:
+        return ActivityType.APPROVAL
+    return {
+        "external": ActivityType.ETH_TRANSFER,
+        "internal": ActivityType.INTERNAL,
+        "erc20": ActivityType.ERC20_TRANSFER,
+    }.get(category, ActivityType.ETH_TRANSFER)
+
+
+# Allowances at or above this are effectively unlimited (max uint256 and friends)
+UNLIMITED_ALLOWANCE = 2 ** 255
+
+
+def decode_approval(tx_input: str | None) -> dict | None:
+    """Spender and allowance from approve/increaseAllowance/setApprovalForAll calldata.
+    Returns None for other calls and for revocations (zero allowance, approved=false)."""
+    if not tx_input or len(tx_input) < 10 + 128:
+        return None
+    selector = tx_input[:10].lower()
+    if selector not in APPROVAL_SELECTORS:
+        return None
+    args = tx_input[10:]
+    spender = "0x" + args[24:64].lower()
+    value = int(args[64:128], 16)
+    if value == 0:
+        return None
+    if selector == "0xa22cb465":
+        # setApprovalForAll hands over every NFT in the collection
+        return {"spender": spender, "allowance_raw": None, "unlimited": True, "operator": True}
+    return {"spender": spender, "allowance_raw": str(value), "unlimited": value >= UNLIMITED_ALLOWANCE, "operator": False}
+
+
+def build_approval_alert(entry: dict, token_address: str, approval: dict, tx_hash: str, token_meta: dict | None = None) -> dict | None:
+    """Alert for an approval worth flagging: unlimited, denylisted spender, or an
+    allowance worth at least the entry's large-trade threshold. None otherwise."""
+    token_meta = token_meta or {}
+    symbol = token_meta.get("symbol")
+    decimals = token_meta.get("decimals")
+    spender_flagged = approval["spender"] in SPENDER_DENYLIST
+
+    allowance_formatted = None
+    usd_value = None
+    if approval["allowance_raw"] is not None and not approval["unlimited"] and decimals is not None:
+        amount = int(approval["allowance_raw"]) / (10 ** int(decimals))
+        allowance_formatted = f"{amount:g}"
+        usd_value = estimate_usd_value(symbol, amount, entry["chain"])
+
+    is_large = usd_value is not None and usd_value >= entry["large_trade_threshold_usd"]
+    if not (approval["unlimited"] or spender_flagged or is_large):
+        return None
+
+    label = entry.get("label") or entry.get("resolved_name") or entry["address"]
+    token_str = symbol or token_address
+    if approval["operator"]:
+        what = f"ALL {token_str} NFTs"
+    elif approval["unlimited"]:
+        what = f"UNLIMITED {token_str}"
+    else:
+        usd_str = f" (${usd_value:.0f})" if usd_value else ""
+        what = f"{allowance_formatted or approval['allowance_raw']} {token_str}{usd_str}"
+    warning = " — spender is on the denylist!" if spender_flagged else ""
+    message = f"**{label}** ({entry['address'][:10]}) approved {approval['spender']} to spend {what} on {entry['chain']}{warning} [tx: {tx_hash}]"
+    return {
+        "alert_type": "approval",
+        "watchlist_id": entry["id"], "address": entry["address"],
+        "label": entry.get("label"), "chain": entry["chain"],
+        "tx_hash": tx_hash, "activity_type": ActivityType.APPROVAL.value,
+        "token_address": token_address, "token_symbol": symbol,
+        "spender": approval["spender"], "spender_flagged": spender_flagged,
+        "allowance_raw": approval["allowance_raw"], "allowance_formatted": allowance_formatted,
+        "unlimited": approval["unlimited"], "usd_value": usd_value,
+        "message": message,
+    }
+
+
+# ---------------------------------------------------------------------------
+# Name Resolution (ENS)
+# ---------------------------------------------------------------------------
+
+def encode_get_names(addresses: list[str]) -> str:
+    words = [f"{0x20:064x}", f"{len(addresses):064x}"]
+    words += [a.lower().replace("0x", "").rjust(64, "0") for a in addresses]
+    return "0x" + ENS_GET_NAMES_SELECTOR + "".join(words)
+
+
+def decode_string_array(result: str) -> list[str]:
+    raw = bytes.fromhex(result.replace("0x", ""))
+    if not raw:
+        return []
+
+    def word(offset: int) -> int:
+        return int.from_bytes(raw[offset:offset + 32], "big")
+
+    base = word(0)
+    count = word(base)
+    items_start = base + 32
+    names = []
+    for i in range(count):
+        start = items_start + word(items_start + 32 * i)
+        length = word(start)
+        names.append(raw[start + 32:start + 32 + length].decode("utf-8", errors="replace"))
+    return names
+
+
+def resolve_ens_names(addresses: list[str]) -> dict[str, str | None]:
+    """Primary ENS names for the addresses (None where unset), keyed by address."""
+    names = decode_string_array(alchemy_eth_call("mainnet", ENS_REVERSE_RECORDS, encode_get_names(addresses)))
+    if len(names) != len(addresses):
+        raise RuntimeError(f"getNames returned {len(names)} names for {len(addresses)} addresses")
+    return {a: (n or None) for a, n in zip(addresses, names)}
+
+
+def refresh_resolved_names(logger) -> int:
+    """Resolve names for addresses never resolved or older than the TTL. Returns addresses refreshed.
+    ENS names belong to the address, so every chain's entry for it gets the same name."""
+    cutoff = datetime.fromtimestamp(time.time() - NAME_TTL_SECS, timezone.utc).strftime("%Y-%m-%dT%H:%M:%S+00:00")
+    conn = get_db()
+    rows = conn.execute(
+        "SELECT DISTINCT address FROM wallet_watchlist WHERE resolved_name_at IS NULL OR resolved_name_at < ?",
+        (cutoff,),
+    ).fetchall()
+    conn.close()
+    addresses = [r["address"] for r in rows]
+    refreshed = 0
+    for i in range(0, len(addresses), NAME_BATCH_SIZE):
+        chunk = addresses[i:i + NAME_BATCH_SIZE]
+        try:
+            names = resolve_ens_names(chunk)
+        except Exception as e:
+            logger.warning(f"[WALLET_MONITOR] ENS lookup failed: {e}")
+            break
+        conn = get_db()
+        ts = now_iso()
+        for address, name in names.items():
+            conn.execute(
+                "UPDATE wallet_watchlist SET resolved_name = ?, resolved_name_source = ?, resolved_name_at = ? WHERE address = ?",
+                (name, "ens" if name else None, ts, address),
+            )
+        conn.commit()
+        conn.close()
+        refreshed += len(chunk)
+    if refreshed:
+        logger.debug(f"[WALLET_MONITOR] Refreshed names for {refreshed} addresses")
+    return refreshed
+
+
 # ---------------------------------------------------------------------------
 # USD Price Estimation
 # ---------------------------------------------------------------------------
@@ -353,32 +893,78 @@ def parse_block_number(hex_str: str) -> int:
 STABLECOINS = {"USDC", "USDT", "DAI", "BUSD", "TUSD", "FRAX"}
 
 
-def estimate_usd_value(asset: str | None, value: float | None, chain: str) -> float | None:
-    if value is None or value == 0.0:
-        return 0.0 if value == 0.0 else None
-    symbol = (asset or "ETH").upper()
-    if symbol in STABLECOINS:
-        return value
-
+def _cached_price(key: tuple[str, str]) -> tuple[bool, float | None]:
     with _price_cache_lock:
-        if symbol in _price_cache:
-            price, ts = _price_cache[symbol]
-            if time.time() - ts < PRICE_CACHE_TTL:
-                return value * price
+        cached = _price_cache.get(key)
+        if cached and time.time() - cached[1] < PRICE_CACHE_TTL:
+            return True, cached[0]
+    return False, None
+
 
+def fetch_dexscreener_price(symbol: str, chain: str) -> float | None:
     dex_chain = "base" if chain == "base" else "ethereum"
     try:
         resp = http_requests.get(f"https://api.dexscreener.com/latest/dex/search?q={symbol}", timeout=10)
-        data = resp.json()
-        for pair in data.get("pairs", []):
+        for pair in resp.json().get("pairs", []):
             if pair.get("chainId") == dex_chain and (pair.get("baseToken", {}).get("symbol", "").upper() == symbol):
                 price = float(pair.get("priceUsd", 0))
                 if price > 0:
-                    with _price_cache_lock:
-                        _price_cache[symbol] = (price, time.time())
-                    return value * price
+                    return price
     except Exception:
         pass
+    return None
+
+
+def get_token_price(symbol: str, chain: str) -> float | None:
+    """USD price of a token, cached per (chain, symbol) for PRICE_CACHE_TTL seconds.
+    Failed lookups are cached too, so unpriced tokens don't cost a request per transfer."""
+    key = (chain, symbol)
+    hit, price = _cached_price(key)
+    if not hit:
+        with _price_cache_lock:
+            fetch_lock = _price_fetch_locks.setdefault(key, threading.Lock())
+        with fetch_lock:
+            # Another thread may have fetched it while we waited
+            hit, price = _cached_price(key)
+            if not hit:
+                price = fetch_dexscreener_price(symbol, chain)
+                with _price_cache_lock:
+                    now = time.time()
+                    if len(_price_cache) >= PRICE_CACHE_MAX_ENTRIES:
+                        for stale in [k for k, (_, ts) in _price_cache.items() if now - ts >= PRICE_CACHE_TTL]:
+                            del _price_cache[stale]
+                            _price_fetch_locks.pop(stale, None)
+                    _price_cache[key] = (price, now)
+    with _price_cache_lock:
+        _price_cache_stats["hits" if hit else "misses"] += 1
+    return price
+
+
+def price_cache_status() -> dict:
+    with _price_cache_lock:
+        hits, misses = _price_cache_stats["hits"], _price_cache_stats["misses"]
+        now = time.time()
+        fresh = sum(1 for _, ts in _price_cache.values() if now - ts < PRICE_CACHE_TTL)
+    lookups = hits + misses
+    return {
+        "ttl_secs": PRICE_CACHE_TTL,
+        "entries": fresh,
+        "hits": hits,
+        "misses": misses,
+        "hit_rate": round(hits / lookups, 3) if lookups else None,
+    }
+
+
+def estimate_usd_value(asset: str | None, value: float | None, chain: str) -> float | None:
+    if value is None or value == 0.0:
+        return 0.0 if value == 0.0 else None
+    symbol = (asset or "ETH").upper()
+    if symbol in STABLECOINS:
+        return value
+
+    price = get_token_price(symbol, chain)
+    if price is not None:
+        return value * price
 
     fallback = {"ETH": 2500.0, "WETH": 2500.0}
     if symbol in fallback:
@@ -390,108 +976,423 @@ def estimate_usd_value(asset: str | None, value: float | None, chain: str) -> fl
 # Background Worker
 # ---------------------------------------------------------------------------
 
+def entry_poll_interval(entry: dict) -> int:
+    """Seconds between polls of a wallet: its own interval, or the global one when unset."""
+    return max(MIN_POLL_INTERVAL, entry.get("poll_interval_secs") or POLL_INTERVAL)
+
+
+def due_entries(entries: list[dict], now: float) -> list[dict]:
+    """Wallets whose next poll time has passed (never-polled wallets are due immediately)."""
+    with _next_poll_lock:
+        return [e for e in entries if _next_poll_at.get(e["id"], 0) <= now]
+
+
+def seconds_until_next_poll(now: float) -> float:
+    """Sleep until the earliest scheduled wallet, checking at least every POLL_INTERVAL."""
+    with _next_poll_lock:
+        earliest = min(_next_poll_at.values(), default=None)
+    if earliest is None:
+        return POLL_INTERVAL
+    return min(POLL_INTERVAL, max(MIN_POLL_INTERVAL, earliest - now))
+
+
 def worker_loop():
-    global _last_tick_at
+    global _last_tick_at, _last_tick_ts
     logger = logging.getLogger("wallet_monitor.worker")
-    logger.info(f"[WALLET_MONITOR] Worker started (poll interval: {POLL_INTERVAL}s)")
-    first_run = True
+    logger.info(f"[WALLET_MONITOR] Worker started (default poll interval: {POLL_INTERVAL}s)")
+    time.sleep(5)
+    last_name_refresh = 0.0
     while True:
-        delay = 5 if first_run else POLL_INTERVAL
-        first_run = False
-        time.sleep(delay)
         try:
             wallet_monitor_tick(logger)
             with _last_tick_lock:
                 _last_tick_at = now_iso()
+                _last_tick_ts = time.time()
         except Exception as e:
             logger.error(f"[WALLET_MONITOR] Tick error: {e}")
+        if NAME_RESOLUTION_ENABLED and time.monotonic() - last_name_refresh >= POLL_INTERVAL:
+            last_name_refresh = time.monotonic()
+            try:
+                refresh_resolved_names(logger)
+            except Exception as e:
+                logger.warning(f"[WALLET_MONITOR] Name refresh error: {e}")
+        time.sleep(seconds_until_next_poll(time.monotonic()))
+
+
+def inc_metric(name: str, label: str, amount: float = 1):
+    with _metrics_lock:
+        _metrics[name][label] = _metrics[name].get(label, 0) + amount
+
+
+def entry_alert_cooldown(entry: dict) -> int:
+    """Seconds between alerts for a wallet: its own cooldown, or the global one when unset."""
+    cooldown = entry.get("alert_cooldown_secs")
+    return ALERT_COOLDOWN_SECS if cooldown is None else cooldown
+
+
+def alert_cooldowns(watchlist_ids: set[int]) -> dict[int, int]:
+    if not watchlist_ids:
+        return {}
+    conn = get_db()
+    rows = conn.execute(
+        f"SELECT id, alert_cooldown_secs FROM wallet_watchlist WHERE id IN ({','.join('?' * len(watchlist_ids))})",
+        list(watchlist_ids),
+    ).fetchall()
+    conn.close()
+    return {r["id"]: entry_alert_cooldown(row_to_dict(r)) for r in rows}
+
+
+def throttle_alerts(alerts: list[dict], cooldowns: dict[int, int], now: float) -> list[dict]:
+    """Apply per-wallet alert cooldowns. The first alert in a window goes out; later ones
+    are counted and reported by the wallet's next alert (or digest) as `suppressed_count`.
+    Approvals to a denylisted spender are never held back."""
+    out = []
+    with _alert_throttle_lock:
+        for alert in alerts:
+            wid = alert.get("watchlist_id")
+            cooldown = cooldowns.get(wid, ALERT_COOLDOWN_SECS)
+            if wid is None or cooldown <= 0 or alert.get("spender_flagged"):
+                out.append(alert)
+                continue
+            state = _alert_throttle.setdefault(wid, {"last_sent": None, "suppressed": {}, "latest": None})
+            state["cooldown"] = cooldown
+            if state["last_sent"] is not None and now - state["last_sent"] < cooldown:
+                state["suppressed"][alert["alert_type"]] = state["suppressed"].get(alert["alert_type"], 0) + 1
+                state["latest"] = alert
+                inc_metric("alerts_suppressed", alert["alert_type"])
+                continue
+            suppressed = sum(state["suppressed"].values())
+            if suppressed:
+                alert = {
+                    **alert, "suppressed_count": suppressed,
+                    "message": f"{alert['message']} (+{suppressed} more alerts during the {cooldown}s cooldown)",
+                }
+            state.update(last_sent=now, suppressed={}, latest=None)
+            out.append(alert)
+    return out
+
+
+def due_alert_digests(now: float) -> list[dict]:
+    """Digest alerts for wallets whose cooldown ended with suppressed alerts not yet reported."""
+    digests = []
+    with _alert_throttle_lock:
+        for wid, state in _alert_throttle.items():
+            if not state["suppressed"] or now - state["last_sent"] < state["cooldown"]:
+                continue
+            latest = state["latest"]
+            count = sum(state["suppressed"].values())
+            label = latest.get("label") or latest["address"]
+            digests.append({
+                "alert_type": "digest",
+                "watchlist_id": wid, "address": latest["address"],
+                "label": latest.get("label"), "chain": latest["chain"],
+                "suppressed_count": count, "suppressed_types": dict(state["suppressed"]),
+                "message": f"**{label}** ({latest['address'][:10]}) triggered {count} more alerts during the {state['cooldown']}s cooldown. Latest: {latest['message']}",
+            })
+            state.update(last_sent=now, suppressed={}, latest=None)
+    return digests
+
+
+def build_trade_digest(conn, since: str, until: str) -> dict | None:
+    """Summarize confirmed large trades recorded in (since, until]; None if there were none."""
+    rows = [row_to_dict(r) for r in conn.execute(
+        """SELECT a.watchlist_id, a.chain, a.tx_hash, a.activity_type, a.asset_symbol, a.amount_formatted,
+                  a.usd_value, a.swap_from_token, a.swap_to_token, a.created_at, w.address, w.label, w.resolved_name
+           FROM wallet_activity a JOIN wallet_watchlist w ON w.id = a.watchlist_id
+           WHERE a.is_large_trade = 1 AND a.status = 'confirmed' AND a.created_at > ? AND a.created_at <= ?
+           ORDER BY a.usd_value DESC""",
+        (since, until),
+    ).fetchall()]
+    if not rows:
+        return None
+    wallets: dict[int, dict] = {}
+    for r in rows:
+        w = wallets.setdefault(r["watchlist_id"], {
+            "watchlist_id": r["watchlist_id"], "address": r["address"], "label": r["label"],
+            "chain": r["chain"], "trade_count": 0, "total_usd": 0.0,
+            "display_name": r["label"] or r["resolved_name"] or r["address"][:10],
+        })
+        w["trade_count"] += 1
+        w["total_usd"] += r["usd_value"] or 0.0
+    total_usd = sum(r["usd_value"] or 0.0 for r in rows)
+    ranked = sorted(wallets.values(), key=lambda w: w["total_usd"], reverse=True)
+    top = ", ".join(f"**{w['display_name']}** ({w['trade_count']}, ${w['total_usd']:.0f})" for w in ranked[:5])
+    return {
+        "alert_type": "trade_digest",
+        "period_start": since, "period_end": until,
+        "trade_count": len(rows), "wallet_count": len(wallets), "total_usd": total_usd,
+        "wallets": ranked,
+        "trades": [{k: r[k] for k in ("watchlist_id", "chain", "tx_hash", "activity_type", "asset_symbol",
+                                      "amount_formatted", "usd_value", "swap_from_token", "swap_to_token", "created_at")}
+                   for r in rows[:DIGEST_MAX_TRADES]],
+        "message": f"Digest: {len(rows)} large trades (${total_usd:.0f}) across {len(wallets)} wallets since {since} UTC. Top: {top}",
+    }
+
+
+def send_trade_digest(logger, now: float):
+    """Post the large-trade digest once every DIGEST_INTERVAL_SECS in digest alert modes."""
+    global _digest_covered_until, _digest_last_sent
+    if ALERT_MODE not in ("digest", "both") or now - _digest_last_sent < DIGEST_INTERVAL_SECS:
+        return
+    until = datetime.now(timezone.utc).strftime("%Y-%m-%d %H:%M:%S")
+    conn = get_db()
+    digest = build_trade_digest(conn, _digest_covered_until, until)
+    conn.close()
+    _digest_covered_until, _digest_last_sent = until, now
+    if digest:
+        send_alerts([digest], logger, throttle=False)
+
+
+def send_alerts(alerts: list[dict], logger, throttle: bool = True):
+    if ALERT_MODE == "digest":
+        # Large trades are reported by the periodic digest instead
+        alerts = [a for a in alerts if a["alert_type"] != "large_trade"]
+    if alerts and throttle:
+        cooldowns = alert_cooldowns({a["watchlist_id"] for a in alerts if a.get("watchlist_id") is not None})
+        alerts = throttle_alerts(alerts, cooldowns, time.monotonic())
+    if alerts and ALERT_CALLBACK_URL:
+        for alert in alerts:
+            try:
+                http_requests.post(ALERT_CALLBACK_URL, json=alert, timeout=10).raise_for_status()
+                inc_metric("alerts_sent", alert["alert_type"])
+            except Exception as e:
+                inc_metric("alerts_failed", alert["alert_type"])
+                logger.warning(f"[WALLET_MONITOR] Failed to send alert callback: {e}")
+        logger.warning(f"[WALLET_MONITOR] ALERTS: {' | '.join(a['message'] for a in alerts)}")
+
+
+def prune_stale_pending() -> int:
+    """Drop pending rows that never confirmed (replaced or dropped transactions)."""
+    conn = get_db()
+    cursor = conn.execute(
+        "DELETE FROM wallet_activity WHERE status = 'pending' AND created_at < datetime('now', ?)",
+        (f"-{PENDING_TTL_MINS} minutes",),
+    )
+    conn.commit()
+    conn.close()
+    return cursor.rowcount
 
 
 def wallet_monitor_tick(logger):
+    if MEMPOOL_ENABLED:
+        pruned = prune_stale_pending()
+        if pruned:
+            logger.debug(f"[WALLET_MONITOR] Dropped {pruned} pending transactions that never confirmed")
     conn = get_db()
-    watchlist = conn.execute(
+    watchlist = [row_to_dict(r) for r in conn.execute(
         "SELECT * FROM wallet_watchlist WHERE monitor_enabled = 1 ORDER BY created_at ASC"
-    ).fetchall()
+    ).fetchall()]
     conn.close()
-    if not watchlist:
+    # Forget schedules of wallets that were removed or disabled
+    active_ids = {e["id"] for e in watchlist}
+    with _next_poll_lock:
+        for stale in [i for i in _next_poll_at if i not in active_ids]:
+            del _next_poll_at[stale]
+    now = time.monotonic()
+    # Report bursts whose cooldown has ended, even if no wallet is due this pass
+    send_alerts(due_alert_digests(now), logger, throttle=False)
+    send_trade_digest(logger, now)
+    due = due_entries(watchlist, now)
+    if not due:
         return
 
-    logger.debug(f"[WALLET_MONITOR] Tick: checking {len(watchlist)} wallets")
+    logger.debug(f"[WALLET_MONITOR] Tick: checking {len(due)} of {len(watchlist)} wallets")
     total_new = 0
     alerts = []
+    chain_seconds: dict[str, float] = {}
 
-    for entry in watchlist:
-        entry = row_to_dict(entry)
+    for entry in due:
+        with _next_poll_lock:
+            _next_poll_at[entry["id"]] = now + entry_poll_interval(entry)
+        chain = entry["chain"]
+        started = time.monotonic()
         try:
             new_count, entry_alerts = process_wallet(entry, logger)
             total_new += new_count
             alerts.extend(entry_alerts)
+            inc_metric("transactions", chain, new_count)
+            inc_metric("large_trades", chain, sum(1 for a in entry_alerts if a["alert_type"] == "large_trade"))
         except Exception as e:
+            inc_metric("poll_errors", chain)
             logger.warning(f"[WALLET_MONITOR] Error processing wallet {entry['address']} ({entry['chain']}): {e}")
+        elapsed = time.monotonic() - started
+        chain_seconds[chain] = chain_seconds.get(chain, 0.0) + elapsed
+        inc_metric("poll_seconds_sum", chain, elapsed)
+        inc_metric("poll_seconds_count", chain)
 
-    if alerts and ALERT_CALLBACK_URL:
-        for alert in alerts:
-            try:
-                http_requests.post(ALERT_CALLBACK_URL, json=alert, timeout=10)
-            except Exception as e:
-                logger.warning(f"[WALLET_MONITOR] Failed to send alert callback: {e}")
-        logger.warning(f"[WALLET_MONITOR] LARGE TRADE ALERTS: {' | '.join(a['message'] for a in alerts)}")
+    with _metrics_lock:
+        _metrics["last_poll_seconds"].update(chain_seconds)
+
+    send_alerts(alerts, logger)
 
     if total_new > 0:
-        logger.info(f"[WALLET_MONITOR] Tick complete: {total_new} new transactions, {len(alerts)} large trades")
+        approvals = sum(1 for a in alerts if a["alert_type"] == "approval")
+        logger.info(f"[WALLET_MONITOR] Tick complete: {total_new} new transactions, {len(alerts) - approvals} large trades, {approvals} risky approvals")
 
 
-def process_wallet(entry: dict, logger) -> tuple[int, list[dict]]:
-    from_block = None
-    if entry["last_checked_block"] is not None:
-        from_block = entry["last_checked_block"] + 1
+def approval_alert_for(entry: dict, transfer: dict, tx_input: str | None, tx_hash: str, logger) -> dict | None:
+    approval = decode_approval(tx_input)
+    if approval is None:
+        return None
+    token_address = transfer.get("to") or ""
+    token_meta = None
+    try:
+        token_meta = alchemy_get_token_metadata(entry["chain"], token_address)
+    except Exception as e:
+        logger.debug(f"[WALLET_MONITOR] Token metadata lookup failed for {token_address}: {e}")
+    return build_approval_alert(entry, token_address, approval, tx_hash, token_meta)
+
+
+def scan_range(last_checked_block: int | None, latest: int) -> tuple[int, int] | None:
+    """Blocks to fetch this poll as (from, to), or None when no new block is confirmed yet.
+
+    New blocks stop CONFIRMATIONS short of the head and at most MAX_BLOCK_RANGE
+    past the last checked block; the range also reaches REORG_WINDOW blocks back
+    into already-processed blocks so activity a reorg moved or dropped is corrected.
+    """
+    safe_head = latest - CONFIRMATIONS
+    if last_checked_block is None:
+        start = max(0, safe_head - FIRST_RUN_LOOKBACK_BLOCKS)
+        rescan_from = start
     else:
-        latest = alchemy_get_block_number(entry["chain"])
-        from_block = max(0, latest - FIRST_RUN_LOOKBACK_BLOCKS)
-        logger.info(f"[WALLET_MONITOR] First run for {entry['address']} on {entry['chain']}: starting from block {from_block} (latest: {latest})")
+        start = last_checked_block + 1
+        rescan_from = max(0, start - REORG_WINDOW)
+    end = min(safe_head, start + MAX_BLOCK_RANGE - 1)
+    if end < start:
+        return None
+    return rescan_from, end
 
-    outgoing = alchemy_get_asset_transfers(entry["chain"], entry["address"], from_block, "from")
-    incoming = alchemy_get_asset_transfers(entry["chain"], entry["address"], from_block, "to")
 
-    if not outgoing and not incoming:
-        try:
-            latest = alchemy_get_block_number(entry["chain"])
-            conn = get_db()
-            ts = now_iso()
-            conn.execute("UPDATE wallet_watchlist SET last_checked_block = ?, last_checked_at = ?, updated_at = ? WHERE id = ?", (latest, ts, ts, entry["id"]))
-            conn.commit()
-            conn.close()
-        except Exception:
-            pass
+def reconcile_reorged(conn, entry: dict, from_block: int, to_block: int, tx_groups: dict, logger) -> int:
+    """Fix confirmed activity in [from_block, to_block] against a fresh fetch of that range:
+    drop rows whose transaction is gone and move rows whose transaction landed in another block."""
+    rows = conn.execute(
+        "SELECT id, tx_hash, block_number FROM wallet_activity WHERE watchlist_id = ? AND status = 'confirmed' AND block_number BETWEEN ? AND ?",
+        (entry["id"], from_block, to_block),
+    ).fetchall()
+    fixed = 0
+    for row in rows:
+        transfers = tx_groups.get(row["tx_hash"])
+        if transfers is None:
+            conn.execute("DELETE FROM wallet_activity WHERE id = ?", (row["id"],))
+            fixed += 1
+            continue
+        block_number = parse_block_number(transfers[0][0].get("blockNum", "0x0"))
+        if block_number != row["block_number"]:
+            meta = transfers[0][0].get("metadata") or {}
+            conn.execute(
+                "UPDATE wallet_activity SET block_number = ?, block_timestamp = ? WHERE id = ?",
+                (block_number, meta.get("blockTimestamp"), row["id"]),
+            )
+            fixed += 1
+    if fixed:
+        inc_metric("reorged", entry["chain"], fixed)
+        logger.warning(f"[WALLET_MONITOR] Reorg on {entry['chain']}: corrected {fixed} activity entries for {entry['address']} in blocks {from_block}-{to_block}")
+    return fixed
+
+
+def fetch_wallet_transfers(entry: dict, from_block: int, to_block: int) -> tuple[dict, int]:
+    """A wallet's transfers in [from_block, to_block], grouped by transaction, and the last
+    block they fully cover. That is to_block unless a busy range was truncated; then later
+    blocks are left out, since the last fetched block may only be partially there."""
+    outgoing, out_truncated = alchemy_get_asset_transfers(entry["chain"], entry["address"], from_block, "from", to_block)
+    incoming, in_truncated = alchemy_get_asset_transfers(entry["chain"], entry["address"], from_block, "to", to_block)
+    fetched_to = to_block
+    for transfers, truncated in ((outgoing, out_truncated), (incoming, in_truncated)):
+        if truncated and transfers:
+            first = parse_block_number(transfers[0].get("blockNum", "0x0"))
+            last = parse_block_number(transfers[-1].get("blockNum", "0x0"))
+            # A single block with more transfers than one fetch is kept as-is so scanning progresses
+            fetched_to = min(fetched_to, last - 1 if last > first else last)
+    if fetched_to < to_block:
+        def covered(t):
+            return parse_block_number(t.get("blockNum", "0x0")) <= fetched_to
+        outgoing = [t for t in outgoing if covered(t)]
+        incoming = [t for t in incoming if covered(t)]
+    return group_transfers(outgoing, incoming), fetched_to
+
+
+def process_wallet(entry: dict, logger) -> tuple[int, list[dict]]:
+    latest = alchemy_get_block_number(entry["chain"])
+    scan = scan_range(entry["last_checked_block"], latest)
+    if scan is None:
         return 0, []
+    from_block, to_block = scan
+    if entry["last_checked_block"] is None:
+        logger.info(f"[WALLET_MONITOR] First run for {entry['address']} on {entry['chain']}: starting from block {from_block} (latest: {latest})")
 
-    # Group transfers by tx_hash for swap detection
+    tx_groups, fetched_to = fetch_wallet_transfers(entry, from_block, to_block)
+
+    conn = get_db()
+    if fetched_to < to_block:
+        # A partial fetch can't tell a reorged-out transaction from an unfetched one
+        logger.warning(f"[WALLET_MONITOR] {entry['address']} on {entry['chain']} has more transfers in blocks {from_block}-{to_block} than one poll fetches; scanned through {fetched_to}, skipping reorg checks")
+    else:
+        reconcile_reorged(conn, entry, from_block, to_block, tx_groups, logger)
+    # The first poll's lookback window is history: record it like a backfill, without alerts or copy trades
+    live = entry["last_checked_block"] is not None
+    new_count, alerts, copy_candidates = record_activity(conn, entry, tx_groups, logger, live=live)
+
+    # The next poll continues after the last fully fetched block, even if it held no activity
+    # (never moving back before blocks an earlier poll already covered)
+    checked_to = max(fetched_to, entry["last_checked_block"] if entry["last_checked_block"] is not None else fetched_to)
+    ts = now_iso()
+    conn.execute("UPDATE wallet_watchlist SET last_checked_block = ?, last_checked_at = ?, updated_at = ? WHERE id = ?", (checked_to, ts, ts, entry["id"]))
+    conn.commit()
+    conn.close()
+
+    for swap in copy_candidates:
+        enqueue_copy_trade(entry, swap, logger)
+    return new_count, alerts
+
+
+def group_transfers(outgoing: list[dict], incoming: list[dict]) -> dict[str, list[tuple[dict, str]]]:
+    """Group transfers by tx_hash for swap detection."""
     tx_groups: dict[str, list[tuple[dict, str]]] = {}
     for t in outgoing:
         tx_groups.setdefault(t["hash"], []).append((t, "outgoing"))
     for t in incoming:
         tx_groups.setdefault(t["hash"], []).append((t, "incoming"))
+    return tx_groups
+
 
+def record_activity(conn, entry: dict, tx_groups: dict, logger, live: bool = True) -> tuple[int, list[dict], list[dict]]:
+    """Insert activity for grouped transfers. Returns (new entries, alerts, swaps to copy).
+
+    Historical activity (`live=False`, from a backfill) is recorded without alerts or copy trades.
+    """
     new_count = 0
-    max_block = entry["last_checked_block"] or 0
     alerts = []
-    conn = get_db()
+    copy_candidates = []
 
     for tx_hash, transfers in tx_groups.items():
         block_number = parse_block_number(transfers[0][0].get("blockNum", "0x0"))
-        if block_number > max_block:
-            max_block = block_number
+
+        # The confirmed entry replaces any pending one seen in the mempool,
+        # without alerting twice for the same transaction
+        pending = conn.execute(
+            "SELECT activity_type, is_large_trade FROM wallet_activity WHERE tx_hash = ? AND watchlist_id = ? AND status = 'pending'",
+            (tx_hash, entry["id"]),
+        ).fetchone()
+        already_alerted = pending is not None and (pending["is_large_trade"] or pending["activity_type"] == ActivityType.APPROVAL.value)
+        if pending is not None:
+            conn.execute("DELETE FROM wallet_activity WHERE tx_hash = ? AND watchlist_id = ? AND status = 'pending'", (tx_hash, entry["id"]))
 
         block_timestamp = None
         meta = transfers[0][0].get("metadata")
         if meta:
             block_timestamp = meta.get("blockTimestamp")
 
-        has_outgoing_erc20 = any(t["category"] == "erc20" and d == "outgoing" for t, d in transfers)
-        has_incoming_erc20 = any(t["category"] == "erc20" and d == "incoming" for t, d in transfers)
-        is_swap = has_outgoing_erc20 and has_incoming_erc20
+        is_swap = is_swap_tx(transfers)
+        tx_input = None
+        if any(needs_tx_input(t, d) for t, d in transfers):
+            try:
+                tx_input = alchemy_get_tx_input(entry["chain"], tx_hash)
+            except Exception as e:
+                logger.debug(f"[WALLET_MONITOR] Could not fetch input for {tx_hash}: {e}")
 
         swap_from_token = swap_from_amount = swap_to_token = swap_to_amount = None
+        out_erc20 = in_erc20 = None
         if is_swap:
             out_erc20 = next(((t, d) for t, d in transfers if d == "outgoing" and t["category"] == "erc20"), None)
             in_erc20 = next(((t, d) for t, d in transfers if d == "incoming" and t["category"] == "erc20"), None)
@@ -502,19 +1403,16 @@ def process_wallet(entry: dict, logger) -> tuple[int, list[dict]]:
                 swap_to_token = in_erc20[0].get("asset")
                 swap_to_amount = str(in_erc20[0].get("value", "")) if in_erc20[0].get("value") is not None else None
 
+        inserted_before = new_count
         for transfer, direction in transfers:
-            if is_swap:
-                a_type = "swap"
-            else:
-                cat = transfer.get("category", "")
-                a_type = {"external": "eth_transfer", "internal": "internal", "erc20": "erc20_transfer"}.get(cat, cat)
+            a_type = classify_transfer(transfer, direction, transfers, entry["chain"], tx_input).value
 
             amount_formatted = str(transfer["value"]) if transfer.get("value") is not None else None
             usd_value = estimate_usd_value(transfer.get("asset"), transfer.get("value"), entry["chain"])
             is_large_trade = usd_value is not None and usd_value >= entry["large_trade_threshold_usd"]
 
             raw_contract = transfer.get("rawContract") or {}
-            raw_data = json.dumps(transfer) if (is_swap or is_large_trade) else None
+            raw_data = json.dumps(transfer) if (a_type not in PLAIN_TRANSFER_TYPES or is_large_trade) else None
 
             try:
                 conn.execute(
@@ -534,11 +1432,17 @@ def process_wallet(entry: dict, logger) -> tuple[int, list[dict]]:
                 )
                 if conn.execute("SELECT changes()").fetchone()[0] > 0:
                     new_count += 1
+                    if already_alerted or not live:
+                        continue
+                    if a_type == ActivityType.APPROVAL and entry.get("approval_alerts_enabled"):
+                        approval_alert = approval_alert_for(entry, transfer, tx_input, tx_hash, logger)
+                        if approval_alert:
+                            alerts.append({**approval_alert, "status": "confirmed"})
                     if is_large_trade:
-                        label = entry.get("label") or entry["address"]
+                        label = entry.get("label") or entry.get("resolved_name") or entry["address"]
                         usd_str = f"${usd_value:.0f}" if usd_value else "unknown"
                         addr_short = entry["address"][:10]
-                        if is_swap:
+                        if a_type == ActivityType.SWAP:
                             message = f"**{label}** ({addr_short}) swapped {swap_from_amount or '?'} {swap_from_token or '?'} -> {swap_to_amount or '?'} {swap_to_token or '?'} ({usd_str}) on {entry['chain']} [tx: {tx_hash}]"
                         else:
                             asset = transfer.get("asset") or "ETH"
@@ -546,6 +1450,7 @@ def process_wallet(entry: dict, logger) -> tuple[int, list[dict]]:
                             dir_str = "sent" if direction == "outgoing" else "received"
                             message = f"**{label}** ({addr_short}) {dir_str} {amt} {asset} ({usd_str}) on {entry['chain']} [tx: {tx_hash}]"
                         alerts.append({
+                            "alert_type": "large_trade", "status": "confirmed",
                             "watchlist_id": entry["id"], "address": entry["address"],
                             "label": entry.get("label"), "chain": entry["chain"],
                             "tx_hash": tx_hash, "activity_type": a_type,
@@ -558,15 +1463,499 @@ def process_wallet(entry: dict, logger) -> tuple[int, list[dict]]:
             except Exception:
                 pass
 
+        # Only copy swaps seen for the first time
+        if live and new_count > inserted_before and is_swap and entry.get("copy_trade_enabled") and out_erc20 and in_erc20:
+            copy_candidates.append(swap_from_transfers(tx_hash, out_erc20[0], in_erc20[0], entry["chain"]))
+
     conn.commit()
+    return new_count, alerts, copy_candidates
 
-    if max_block > (entry["last_checked_block"] or 0):
-        ts = now_iso()
-        conn.execute("UPDATE wallet_watchlist SET last_checked_block = ?, last_checked_at = ?, updated_at = ? WHERE id = ?", (max_block, ts, ts, entry["id"]))
-        conn.commit()
 
+# ---------------------------------------------------------------------------
+# Backfill
+# ---------------------------------------------------------------------------
+
+def backfill_range(entry: dict, latest: int, blocks: int) -> tuple[int, int] | None:
+    """Historical blocks to scan as (from, to): the `blocks` before the range the live worker covers."""
+    if entry["last_checked_block"] is not None:
+        end = entry["last_checked_block"] - REORG_WINDOW
+    else:
+        end = latest - CONFIRMATIONS - FIRST_RUN_LOOKBACK_BLOCKS - 1
+    start = max(0, end - blocks + 1)
+    if end < start:
+        return None
+    return start, end
+
+
+def blocks_since(chain: str, since: str) -> int:
+    """Approximate number of blocks produced since an ISO timestamp."""
+    ts = datetime.fromisoformat(since.replace("Z", "+00:00"))
+    if ts.tzinfo is None:
+        ts = ts.replace(tzinfo=timezone.utc)
+    elapsed = (datetime.now(timezone.utc) - ts).total_seconds()
+    return max(0, int(elapsed / BLOCK_TIME_SECS.get(chain, 12)))
+
+
+def backfill_job_view(job: dict) -> dict:
+    view = dict(job)
+    total = job["to_block"] - job["from_block"] + 1
+    view["progress"] = round(job["blocks_scanned"] / total, 3) if total > 0 else 1.0
+    return view
+
+
+def start_backfill(entry: dict, blocks: int, logger) -> tuple[dict | None, str | None]:
+    with _backfill_lock:
+        if any(j["watchlist_id"] == entry["id"] and j["status"] in ("queued", "running") for j in _backfill_jobs.values()):
+            return None, f"A backfill is already running for entry #{entry['id']}"
+    latest = alchemy_get_block_number(entry["chain"])
+    scan = backfill_range(entry, latest, blocks)
+    if scan is None:
+        return None, "No historical blocks to scan"
+    with _backfill_lock:
+        job_id = max(_backfill_jobs, default=0) + 1
+        job = {
+            "id": job_id, "watchlist_id": entry["id"], "address": entry["address"], "chain": entry["chain"],
+            "from_block": scan[0], "to_block": scan[1], "current_block": scan[1], "blocks_scanned": 0,
+            "inserted": 0, "status": "queued", "error": None,
+            "created_at": now_iso(), "finished_at": None,
+        }
+        _backfill_jobs[job_id] = job
+    threading.Thread(target=run_backfill, args=(job, entry, logger), daemon=True).start()
+    return backfill_job_view(job), None
+
+
+def run_backfill(job: dict, entry: dict, logger):
+    """Scan a job's range newest-first in chunks, recording activity without alerts."""
+    with _backfill_slots:
+        job["status"] = "running"
+        logger.info(f"[WALLET_MONITOR] Backfill #{job['id']} for {entry['address']} on {entry['chain']}: blocks {job['from_block']}-{job['to_block']}")
+        try:
+            chunk_end = job["to_block"]
+            while chunk_end >= job["from_block"]:
+                chunk_start = max(job["from_block"], chunk_end - BACKFILL_CHUNK_BLOCKS + 1)
+                # A busy chunk is fetched in several parts
+                fetch_from = chunk_start
+                while fetch_from <= chunk_end:
+                    tx_groups, fetched_to = fetch_wallet_transfers(entry, fetch_from, chunk_end)
+                    conn = get_db()
+                    try:
+                        inserted, _, _ = record_activity(conn, entry, tx_groups, logger, live=False)
+                    finally:
+                        conn.close()
+                    with _backfill_lock:
+                        job["inserted"] += inserted
+                        job["blocks_scanned"] += fetched_to - fetch_from + 1
+                    fetch_from = fetched_to + 1
+                with _backfill_lock:
+                    job["current_block"] = chunk_start
+                chunk_end = chunk_start - 1
+            job["status"] = "done"
+            logger.info(f"[WALLET_MONITOR] Backfill #{job['id']} done: {job['inserted']} historical entries")
+        except Exception as e:
+            job["status"] = "failed"
+            job["error"] = str(e)
+            logger.warning(f"[WALLET_MONITOR] Backfill #{job['id']} failed at block {job['current_block']}: {e}")
+        finally:
+            job["finished_at"] = now_iso()
+
+
+# ---------------------------------------------------------------------------
+# Copy Trading
+# ---------------------------------------------------------------------------
+
+def swap_from_transfers(tx_hash: str, sold: dict, bought: dict, chain: str) -> dict:
+    sold_contract = sold.get("rawContract") or {}
+    bought_contract = bought.get("rawContract") or {}
+    raw_value = sold_contract.get("value")
+    return {
+        "tx_hash": tx_hash,
+        "sell_token": (sold_contract.get("address") or "").lower(),
+        "sell_symbol": sold.get("asset"),
+        "sell_amount_raw": int(raw_value, 16) if raw_value else None,
+        "buy_token": (bought_contract.get("address") or "").lower(),
+        "buy_symbol": bought.get("asset"),
+        "leader_usd": estimate_usd_value(sold.get("asset"), sold.get("value"), chain),
+    }
+
+
+def token_listed(token_set: set[str], address: str, symbol: str | None) -> bool:
+    return address.lower() in token_set or (symbol or "").lower() in token_set
+
+
+def plan_copy_trade(entry: dict, swap: dict) -> tuple[dict | None, str | None]:
+    """Size a copy of the leader's swap. Returns (plan, None) or (None, reason to skip)."""
+    if entry["chain"] not in CHAIN_IDS:
+        return None, f"unsupported chain {entry['chain']}"
+    if not swap["sell_token"] or not swap["buy_token"] or not swap["sell_amount_raw"]:
+        return None, "swap legs are missing token addresses or amounts"
+    for address, symbol in ((swap["sell_token"], swap["sell_symbol"]), (swap["buy_token"], swap["buy_symbol"])):
+        if token_listed(COPY_TRADE_DENIED_TOKENS, address, symbol):
+            return None, f"{symbol or address} is on the deny list"
+    if COPY_TRADE_ALLOWED_TOKENS and not token_listed(COPY_TRADE_ALLOWED_TOKENS, swap["buy_token"], swap["buy_symbol"]):
+        return None, f"{swap['buy_symbol'] or swap['buy_token']} is not on the allow list"
+    leader_usd = swap["leader_usd"]
+    if not leader_usd:
+        return None, "could not price the leader's trade"
+
+    copy_usd = leader_usd * COPY_TRADE_SCALE
+    if entry.get("copy_trade_max_usd") is not None:
+        copy_usd = min(copy_usd, entry["copy_trade_max_usd"])
+    if copy_usd < COPY_TRADE_MIN_USD:
+        return None, f"scaled size ${copy_usd:.2f} is below the ${COPY_TRADE_MIN_USD:.2f} minimum"
+
+    sell_amount_raw = int(swap["sell_amount_raw"] * copy_usd / leader_usd)
+    if sell_amount_raw <= 0:
+        return None, "scaled sell amount rounds to zero"
+    return {"copy_usd": copy_usd, "sell_amount_raw": sell_amount_raw}, None
+
+
+def get_copy_quote(chain: str, sell_token: str, buy_token: str, sell_amount_raw: int, taker: str) -> dict:
+    headers = {"0x-api-key": ZEROX_API_KEY, "0x-version": "v2"}
+    params = {
+        "chainId": CHAIN_IDS[chain],
+        "sellToken": sell_token,
+        "buyToken": buy_token,
+        "sellAmount": str(sell_amount_raw),
+        "taker": taker,
+        "slippageBps": COPY_TRADE_SLIPPAGE_BPS,
+    }
+    resp = http_requests.get(ZEROX_QUOTE_URL, params=params, headers=headers, timeout=15)
+    if resp.status_code != 200:
+        raise RuntimeError(f"0x quote failed ({resp.status_code}): {resp.text[:200]}")
+    quote = resp.json()
+    if not quote.get("liquidityAvailable", True) or not quote.get("transaction"):
+        raise RuntimeError("0x found no route for this pair")
+    return quote
+
+
+def internal_wallet_request(method: str, path: str, body: dict | None = None) -> dict:
+    resp = http_requests.request(
+        method, f"{BACKEND_URL}/api/internal/wallet{path}", json=body,
+        headers={"Authorization": f"Bearer {internal_token()}", "X-Module-Token": module_token()},
+        timeout=60,
+    )
+    data = resp.json()
+    if resp.status_code != 200 or not data.get("success"):
+        raise RuntimeError(data.get("error") or f"wallet proxy returned HTTP {resp.status_code}")
+    return data
+
+
+def sign_and_send(chain: str, to: str, data: str, value: str = "0") -> str:
+    """Sign through the backend wallet proxy, broadcast, and wait for the receipt. Returns the tx hash."""
+    signed = internal_wallet_request("POST", "/sign-transaction", {"network": chain, "to": to, "data": data, "value": value})
+    body = {"id": 1, "jsonrpc": "2.0", "method": "eth_sendRawTransaction", "params": [signed["signed_tx"]]}
+    result = http_requests.post(alchemy_base_url(chain), json=body, timeout=30).json()
+    if result.get("error"):
+        raise RuntimeError(f"broadcast failed: {result['error'].get('message', '')}")
+    tx_hash = result["result"]
+
+    receipt_body = {"id": 1, "jsonrpc": "2.0", "method": "eth_getTransactionReceipt", "params": [tx_hash]}
+    for _ in range(24):
+        time.sleep(5)
+        receipt = http_requests.post(alchemy_base_url(chain), json=receipt_body, timeout=10).json().get("result")
+        if receipt:
+            if int(receipt.get("status", "0x0"), 16) != 1:
+                raise RuntimeError(f"transaction {tx_hash} reverted")
+            return tx_hash
+    raise RuntimeError(f"no receipt for {tx_hash} after 2 minutes")
+
+
+def encode_approve(spender: str, amount: int) -> str:
+    return "0x095ea7b3" + spender.lower().replace("0x", "").rjust(64, "0") + f"{amount:064x}"
+
+
+def record_copy_trade(entry: dict, swap: dict, status: str, reason: str | None = None, plan: dict | None = None,
+                      min_buy_amount_raw: str | None = None, tx_hash: str | None = None) -> bool:
+    """Insert or update the copy trade row for this source swap. Returns False if it already existed on insert."""
+    conn = get_db()
+    ts = now_iso()
+    cursor = conn.execute(
+        """INSERT INTO copy_trades
+               (watchlist_id, source_tx_hash, chain, sell_token, sell_symbol, buy_token, buy_symbol,
+                leader_usd, copy_usd, sell_amount_raw, min_buy_amount_raw, status, reason, tx_hash, created_at, updated_at)
+           VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+           ON CONFLICT(source_tx_hash, watchlist_id) DO UPDATE SET
+               copy_usd = COALESCE(excluded.copy_usd, copy_trades.copy_usd),
+               sell_amount_raw = COALESCE(excluded.sell_amount_raw, copy_trades.sell_amount_raw),
+               min_buy_amount_raw = COALESCE(excluded.min_buy_amount_raw, copy_trades.min_buy_amount_raw),
+               status = excluded.status, reason = excluded.reason,
+               tx_hash = COALESCE(excluded.tx_hash, copy_trades.tx_hash), updated_at = excluded.updated_at
+           WHERE ? = 0""",
+        (
+            entry["id"], swap["tx_hash"], entry["chain"], swap["sell_token"], swap["sell_symbol"],
+            swap["buy_token"], swap["buy_symbol"], swap["leader_usd"],
+            plan["copy_usd"] if plan else None, str(plan["sell_amount_raw"]) if plan else None,
+            min_buy_amount_raw, status, reason, tx_hash, ts, ts,
+            1 if status == "planned" else 0,
+        ),
+    )
+    conn.commit()
     conn.close()
-    return new_count, alerts
+    return cursor.rowcount > 0
+
+
+def enqueue_copy_trade(entry: dict, swap: dict, logger):
+    """Hand a swap to the copy-trade worker, starting it on first use."""
+    global _copy_trade_worker
+    with _copy_trade_worker_lock:
+        if _copy_trade_worker is None or not _copy_trade_worker.is_alive():
+            _copy_trade_worker = threading.Thread(target=copy_trade_worker, daemon=True)
+            _copy_trade_worker.start()
+    _copy_trade_queue.put((entry, swap, logger))
+
+
+def copy_trade_worker():
+    while True:
+        entry, swap, logger = _copy_trade_queue.get()
+        try:
+            execute_copy_trade(entry, swap, logger)
+        except Exception as e:
+            logger.warning(f"[WALLET_MONITOR] Copy trade of {swap['tx_hash'][:12]} errored: {e}")
+        finally:
+            _copy_trade_queue.task_done()
+
+
+def execute_copy_trade(entry: dict, swap: dict, logger):
+    """Mirror one of the leader's swaps. Every outcome, including skips, is recorded in copy_trades."""
+    tag = f"[WALLET_MONITOR] Copy trade of {swap['tx_hash'][:12]}"
+    plan, reason = plan_copy_trade(entry, swap)
+    if plan is None:
+        record_copy_trade(entry, swap, "skipped", reason)
+        logger.info(f"{tag} skipped: {reason}")
+        return
+    # "planned" never overwrites an existing row, so a swap seen twice is only copied once
+    if not record_copy_trade(entry, swap, "planned", plan=plan):
+        return
+
+    intent = f"sell {plan['sell_amount_raw']} (raw) {swap['sell_symbol']} for {swap['buy_symbol']} (~${plan['copy_usd']:.2f}, max slippage {COPY_TRADE_SLIPPAGE_BPS} bps)"
+    if COPY_TRADE_DRY_RUN:
+        record_copy_trade(entry, swap, "dry_run", intent, plan)
+        logger.info(f"{tag} (dry run): would {intent}")
+        return
+    if not ZEROX_API_KEY or not internal_token():
+        record_copy_trade(entry, swap, "failed", "ZEROX_API_KEY and STARKBOT_INTERNAL_TOKEN are required", plan)
+        return
+
+    try:
+        taker = internal_wallet_request("GET", "/address")["address"]
+        quote = get_copy_quote(entry["chain"], swap["sell_token"], swap["buy_token"], plan["sell_amount_raw"], taker)
+        issues = quote.get("issues") or {}
+        if issues.get("balance"):
+            raise RuntimeError(f"insufficient {swap['sell_symbol']} balance")
+        allowance = issues.get("allowance")
+        if allowance:
+            logger.info(f"{tag}: approving {allowance['spender']} for {swap['sell_symbol']}")
+            sign_and_send(entry["chain"], swap["sell_token"], encode_approve(allowance["spender"], plan["sell_amount_raw"]))
+            quote = get_copy_quote(entry["chain"], swap["sell_token"], swap["buy_token"], plan["sell_amount_raw"], taker)
+        tx = quote["transaction"]
+        record_copy_trade(entry, swap, "submitted", intent, plan, min_buy_amount_raw=quote.get("minBuyAmount"))
+        tx_hash = sign_and_send(entry["chain"], tx["to"], tx["data"], str(tx.get("value") or "0"))
+        record_copy_trade(entry, swap, "executed", intent, plan, tx_hash=tx_hash)
+        logger.info(f"{tag} executed: {intent} [tx: {tx_hash}]")
+    except Exception as e:
+        record_copy_trade(entry, swap, "failed", str(e), plan)
+        logger.warning(f"{tag} failed: {e}")
+
+
+def copy_trade_list(limit: int = 50) -> list[dict]:
+    conn = get_db()
+    rows = conn.execute("SELECT * FROM copy_trades ORDER BY id DESC LIMIT ?", (min(limit or 50, 200),)).fetchall()
+    conn.close()
+    return [row_to_dict(r) for r in rows]
+
+
+# ---------------------------------------------------------------------------
+# Mempool (pending transactions)
+# ---------------------------------------------------------------------------
+
+def alchemy_ws_url(chain: str) -> str:
+    return alchemy_base_url(chain).replace("https://", "wss://", 1)
+
+
+def mempool_watched_addresses(chain: str) -> list[str]:
+    conn = get_db()
+    rows = conn.execute(
+        "SELECT DISTINCT LOWER(address) AS address FROM wallet_watchlist WHERE chain = ? AND monitor_enabled = 1 ORDER BY address",
+        (chain,),
+    ).fetchall()
+    conn.close()
+    return [r["address"] for r in rows]
+
+
+def record_pending_tx(chain: str, tx: dict, logger) -> list[dict]:
+    """Store a pending transaction for every watched entry it touches. Returns alerts to send."""
+    tx_hash = tx.get("hash")
+    frm = (tx.get("from") or "").lower()
+    to = (tx.get("to") or "").lower()
+    if not tx_hash or not frm:
+        return []
+
+    conn = get_db()
+    entries = conn.execute(
+        "SELECT * FROM wallet_watchlist WHERE chain = ? AND monitor_enabled = 1 AND LOWER(address) IN (?, ?)",
+        (chain, frm, to),
+    ).fetchall()
+
+    value_eth = int(tx.get("value") or "0x0", 16) / 1e18
+    transfer = {"category": "external", "hash": tx_hash, "from": frm, "to": to or None, "value": value_eth, "asset": "ETH"}
+    alerts = []
+    for entry in entries:
+        entry = row_to_dict(entry)
+        direction = "outgoing" if entry["address"].lower() == frm else "incoming"
+        a_type = classify_transfer(transfer, direction, [(transfer, direction)], chain, tx.get("input"))
+        usd_value = estimate_usd_value("ETH", value_eth, chain)
+        is_large_trade = usd_value is not None and usd_value >= entry["large_trade_threshold_usd"]
+        conn.execute(
+            """INSERT OR IGNORE INTO wallet_activity
+               (watchlist_id, chain, tx_hash, block_number, from_address, to_address, activity_type,
+                asset_symbol, amount_formatted, usd_value, is_large_trade, raw_data, status)
+               VALUES (?, ?, ?, 0, ?, ?, ?, 'ETH', ?, ?, ?, ?, 'pending')""",
+            (
+                entry["id"], chain, tx_hash, frm, to or "0x0", a_type.value,
+                str(value_eth), usd_value, 1 if is_large_trade else 0, json.dumps(tx),
+            ),
+        )
+        if conn.execute("SELECT changes()").fetchone()[0] == 0:
+            continue
+        if a_type == ActivityType.APPROVAL and entry.get("approval_alerts_enabled"):
+            approval_alert = approval_alert_for(entry, transfer, tx.get("input"), tx_hash, logger)
+            if approval_alert:
+                alerts.append({**approval_alert, "status": "pending", "message": f"[pending] {approval_alert['message']}"})
+        if is_large_trade:
+            label = entry.get("label") or entry.get("resolved_name") or entry["address"]
+            dir_str = "sending" if direction == "outgoing" else "receiving"
+            alerts.append({
+                "alert_type": "large_trade", "status": "pending",
+                "watchlist_id": entry["id"], "address": entry["address"],
+                "label": entry.get("label"), "chain": chain,
+                "tx_hash": tx_hash, "activity_type": a_type.value,
+                "usd_value": usd_value, "asset_symbol": "ETH",
+                "amount_formatted": str(value_eth),
+                "message": f"[pending] **{label}** ({entry['address'][:10]}) {dir_str} {value_eth:g} ETH (${usd_value:.0f}) on {chain} [tx: {tx_hash}]",
+            })
+    conn.commit()
+    conn.close()
+    return alerts
+
+
+def mempool_loop(chain: str):
+    """Subscribe to pending transactions from/to watched addresses, resubscribing when the watchlist changes."""
+    import websocket
+
+    logger = logging.getLogger("wallet_monitor.mempool")
+    logger.info(f"[WALLET_MONITOR] Mempool watcher started for {chain}")
+    backoff = 5
+    while True:
+        addresses = mempool_watched_addresses(chain)
+        if not addresses:
+            time.sleep(POLL_INTERVAL)
+            continue
+        if len(addresses) > MEMPOOL_MAX_ADDRESSES:
+            logger.warning(f"[WALLET_MONITOR] {len(addresses)} watched addresses on {chain}; only the first {MEMPOOL_MAX_ADDRESSES} get mempool alerts")
+            addresses = addresses[:MEMPOOL_MAX_ADDRESSES]
+
+        ws = None
+        try:
+            ws = websocket.create_connection(alchemy_ws_url(chain), timeout=30)
+            ws.send(json.dumps({
+                "jsonrpc": "2.0", "id": 1, "method": "eth_subscribe",
+                "params": ["alchemy_pendingTransactions", {"fromAddress": addresses, "toAddress": addresses, "hashesOnly": False}],
+            }))
+            ack = json.loads(ws.recv())
+            if ack.get("error"):
+                raise RuntimeError(ack["error"].get("message", "subscription rejected"))
+            backoff = 5
+            ws.settimeout(POLL_INTERVAL)
+            last_check = time.time()
+            while True:
+                try:
+                    msg = json.loads(ws.recv())
+                    tx = (msg.get("params") or {}).get("result")
+                    if isinstance(tx, dict):
+                        send_alerts(record_pending_tx(chain, tx, logger), logger)
+                except websocket.WebSocketTimeoutException:
+                    pass
+                if time.time() - last_check >= POLL_INTERVAL:
+                    last_check = time.time()
+                    if mempool_watched_addresses(chain)[:MEMPOOL_MAX_ADDRESSES] != addresses:
+                        break
+        except Exception as e:
+            logger.warning(f"[WALLET_MONITOR] Mempool subscription on {chain} failed: {e}; retrying in {backoff}s")
+            time.sleep(backoff)
+            backoff = min(backoff * 2, 300)
+        finally:
+            if ws is not None:
+                try:
+                    ws.close()
+                except Exception:
+                    pass
+
+
+# ---------------------------------------------------------------------------
+# Prometheus metrics
+# ---------------------------------------------------------------------------
+
+# (metric name, type, help, _metrics key, label name)
+LABELED_METRICS = [
+    ("wallet_monitor_transactions_processed_total", "counter", "New activity entries recorded", "transactions", "chain"),
+    ("wallet_monitor_large_trades_total", "counter", "Large trades detected", "large_trades", "chain"),
+    ("wallet_monitor_alerts_sent_total", "counter", "Alert callbacks delivered", "alerts_sent", "alert_type"),
+    ("wallet_monitor_alerts_failed_total", "counter", "Alert callbacks that failed", "alerts_failed", "alert_type"),
+    ("wallet_monitor_alerts_suppressed_total", "counter", "Alerts held back by a wallet's alert cooldown", "alerts_suppressed", "alert_type"),
+    ("wallet_monitor_poll_errors_total", "counter", "Wallet polls that raised an error", "poll_errors", "chain"),
+    ("wallet_monitor_reorged_activity_total", "counter", "Activity entries dropped or moved by chain reorgs", "reorged", "chain"),
+    ("wallet_monitor_last_tick_poll_seconds", "gauge", "Time spent polling each chain during the last tick", "last_poll_seconds", "chain"),
+]
+
+
+def db_size_bytes() -> int:
+    return os.path.getsize(DB_PATH) if os.path.exists(DB_PATH) else 0
+
+
+def render_metrics() -> str:
+    """Prometheus text exposition of the worker's counters and current state."""
+    lines = []
+
+    def metric(name: str, kind: str, help_text: str, samples: list[tuple[str, float]]):
+        lines.append(f"# HELP {name} {help_text}")
+        lines.append(f"# TYPE {name} {kind}")
+        for labels, value in samples:
+            lines.append(f"{name}{labels} {value}")
+
+    with _metrics_lock:
+        snapshot = {key: dict(values) for key, values in _metrics.items()}
+    for name, kind, help_text, key, label in LABELED_METRICS:
+        samples = [(f'{{{label}="{value}"}}', count) for value, count in sorted(snapshot[key].items())]
+        metric(name, kind, help_text, samples)
+
+    lines.append("# HELP wallet_monitor_poll_duration_seconds Time spent polling a single wallet")
+    lines.append("# TYPE wallet_monitor_poll_duration_seconds summary")
+    for chain in sorted(snapshot["poll_seconds_count"]):
+        lines.append(f'wallet_monitor_poll_duration_seconds_sum{{chain="{chain}"}} {snapshot["poll_seconds_sum"].get(chain, 0.0)}')
+        lines.append(f'wallet_monitor_poll_duration_seconds_count{{chain="{chain}"}} {snapshot["poll_seconds_count"][chain]}')
+
+    with _last_tick_lock:
+        last_tick_ts = _last_tick_ts
+    now = time.time()
+    # Before the first tick, age counts from startup so a worker that never ticks still looks stalled
+    metric("wallet_monitor_last_tick_age_seconds", "gauge", "Seconds since the worker last completed a tick",
+           [("", round(now - (last_tick_ts or _start_time), 3))])
+    metric("wallet_monitor_last_tick_timestamp_seconds", "gauge", "Unix time of the last completed tick (0 if none)",
+           [("", round(last_tick_ts or 0, 3))])
+    metric("wallet_monitor_uptime_seconds", "gauge", "Seconds since the service started", [("", round(now - _start_time, 3))])
+    metric("wallet_monitor_poll_interval_seconds", "gauge", "Configured poll interval", [("", POLL_INTERVAL)])
+
+    stats = activity_stats()
+    metric("wallet_monitor_activity_entries", "gauge", "Activity entries stored", [("", stats["total_transactions"])])
+    metric("wallet_monitor_large_trade_entries", "gauge", "Large trade entries stored", [("", stats["large_trades"])])
+    metric("wallet_monitor_watched_wallets", "gauge", "Wallets on the watchlist", [("", stats["watched_wallets"])])
+    metric("wallet_monitor_active_wallets", "gauge", "Wallets with monitoring enabled", [("", stats["active_wallets"])])
+    metric("wallet_monitor_db_size_bytes", "gauge", "Size of the SQLite database file", [("", db_size_bytes())])
+
+    cache = price_cache_status()
+    metric("wallet_monitor_price_cache_hits_total", "counter", "Price lookups served from cache", [("", cache["hits"])])
+    metric("wallet_monitor_price_cache_misses_total", "counter", "Price lookups that fetched from DexScreener", [("", cache["misses"])])
+    return "\n".join(lines) + "\n"
 
 
 # ---------------------------------------------------------------------------
@@ -579,13 +1968,25 @@ def _status_extra():
         last_tick = _last_tick_at
     stats["last_tick_at"] = last_tick
     stats["poll_interval_secs"] = POLL_INTERVAL
+    stats["alert_mode"] = ALERT_MODE
+    if ALERT_MODE in ("digest", "both"):
+        stats["digest_interval_secs"] = DIGEST_INTERVAL_SECS
     stats["worker_enabled"] = bool(ALCHEMY_API_KEY)
+    stats["mempool_chains"] = MEMPOOL_CHAINS if (MEMPOOL_ENABLED and ALCHEMY_API_KEY) else []
+    stats["copy_trade_dry_run"] = COPY_TRADE_DRY_RUN
+    stats["copy_trades_queued"] = _copy_trade_queue.qsize()
+    stats["price_cache"] = price_cache_status()
     return stats
 
 
 app = create_app("wallet_monitor", status_extra_fn=_status_extra)
 
 
+@app.route("/metrics")
+def metrics():
+    return Response(render_metrics(), mimetype="text/plain; version=0.0.4")
+
+
 # ---------------------------------------------------------------------------
 # RPC: Watchlist tool
 # ---------------------------------------------------------------------------
@@ -601,9 +2002,13 @@ def rpc_watchlist():
                 return error("address is required")
             chain = body.get("chain", "mainnet")
             threshold = body.get("threshold_usd", 1000.0)
-            entry, err = watchlist_add(address, body.get("label"), chain, threshold)
+            poll_interval, err = parse_poll_interval(body.get("poll_interval_secs"))
             if err:
                 return error(err)
+            entry, err = watchlist_add(address, body.get("label"), chain, threshold, poll_interval)
+            if err:
+                code = ErrorCode.CONFLICT if is_valid_eth_address(address) else ErrorCode.VALIDATION
+                return error(err, code=code)
             return success(entry)
 
         elif action == "remove":
@@ -614,6 +2019,22 @@ def rpc_watchlist():
                 return success(True)
             return error(f"Entry #{entry_id} not found", 404)
 
+        elif action == "add_batch":
+            wallets = body.get("wallets")
+            if not isinstance(wallets, list) or not wallets:
+                return error("wallets must be a non-empty list")
+            if len(wallets) > MAX_BATCH_SIZE:
+                return error(f"At most {MAX_BATCH_SIZE} wallets per batch")
+            return success(batch_summary(watchlist_add_batch(wallets)))
+
+        elif action == "remove_batch":
+            ids = body.get("ids")
+            if not isinstance(ids, list) or not ids:
+                return error("ids must be a non-empty list")
+            if len(ids) > MAX_BATCH_SIZE:
+                return error(f"At most {MAX_BATCH_SIZE} ids per batch")
+            return success(batch_summary(watchlist_remove_batch(ids)))
+
         elif action == "list":
             return success(watchlist_list())
 
@@ -621,14 +2042,27 @@ def rpc_watchlist():
             entry_id = body.get("id")
             if entry_id is None:
                 return error("id is required")
-            if watchlist_update(entry_id, body.get("label"), body.get("threshold_usd"), body.get("monitor_enabled"), body.get("notes")):
+            poll_interval = body.get("poll_interval_secs")
+            if poll_interval is not None:
+                parsed, err = parse_poll_interval(poll_interval)
+                if err:
+                    return error(err)
+                poll_interval = parsed or 0
+            cooldown = body.get("alert_cooldown_secs")
+            if cooldown is not None and (isinstance(cooldown, bool) or not isinstance(cooldown, int) or cooldown < 0):
+                return error("alert_cooldown_secs must be a non-negative integer (0 = no cooldown)")
+            if watchlist_update(
+                entry_id, body.get("label"), body.get("threshold_usd"), body.get("monitor_enabled"),
+                body.get("notes"), body.get("approval_alerts_enabled"),
+                body.get("copy_trade_enabled"), body.get("copy_trade_max_usd"), poll_interval, cooldown,
+            ):
                 return success(True)
             return error(f"Entry #{entry_id} not found", 404)
 
         else:
-            return error(f"Unknown action: {action}. Valid: add, remove, list, update")
+            return error(f"Unknown action: {action}. Valid: add, remove, add_batch, remove_batch, list, update")
     except Exception as e:
-        return error(str(e))
+        return error(str(e), code=ErrorCode.INTERNAL)
 
 
 # ---------------------------------------------------------------------------
@@ -649,11 +2083,16 @@ def rpc_activity():
             return success(data)
 
         elif action == "search":
+            activity_type = body.get("activity_type")
+            if activity_type and activity_type not in {t.value for t in ActivityType}:
+                valid = ", ".join(t.value for t in ActivityType)
+                return error(f"Unknown activity_type: {activity_type}. Valid: {valid}")
             data = activity_query(
                 address=body.get("address"),
-                activity_type=body.get("activity_type"),
+                activity_type=activity_type,
                 chain=body.get("chain"),
                 large_only=body.get("large_only", False),
+                status=body.get("status"),
                 limit=body.get("limit", 25),
             )
             return success(data)
@@ -664,7 +2103,7 @@ def rpc_activity():
         else:
             return error(f"Unknown action: {action}. Valid: recent, large_trades, search, stats")
     except Exception as e:
-        return error(str(e))
+        return error(str(e), code=ErrorCode.INTERNAL)
 
 
 # ---------------------------------------------------------------------------
@@ -683,10 +2122,12 @@ def rpc_control():
             logger = logging.getLogger("wallet_monitor.worker")
             threading.Thread(target=wallet_monitor_tick, args=(logger,), daemon=True).start()
             return success("Poll triggered")
+        elif action == "copy_trades":
+            return success(copy_trade_list(body.get("limit", 50)))
         else:
-            return error(f"Unknown action: {action}. Valid: status, trigger")
+            return error(f"Unknown action: {action}. Valid: status, trigger, copy_trades")
     except Exception as e:
-        return error(str(e))
+        return error(str(e), code=ErrorCode.INTERNAL)
 
 
 # ---------------------------------------------------------------------------
@@ -698,7 +2139,7 @@ def rpc_backup_export():
     try:
         return success(backup_export())
     except Exception as e:
-        return error(str(e))
+        return error(str(e), code=ErrorCode.INTERNAL)
 
 
 @app.route("/rpc/backup/restore", methods=["POST"])
@@ -711,7 +2152,197 @@ def rpc_backup_restore():
         count = backup_restore(wallets)
         return success(count)
     except Exception as e:
+        return error(str(e), code=ErrorCode.INTERNAL)
+
+
+# ---------------------------------------------------------------------------
+# RPC: Backfill
+# ---------------------------------------------------------------------------
+
+@app.route("/rpc/backfill", methods=["POST"])
+def rpc_backfill():
+    body = request.get_json(silent=True) or {}
+    entry_id = body.get("id")
+    if entry_id is None:
+        return error("id is required")
+    blocks, since = body.get("blocks"), body.get("since")
+    if (blocks is None) == (since is None):
+        return error("Provide exactly one of blocks or since")
+
+    conn = get_db()
+    row = conn.execute("SELECT * FROM wallet_watchlist WHERE id = ?", (entry_id,)).fetchone()
+    conn.close()
+    if row is None:
+        return error(f"Entry #{entry_id} not found", 404)
+    entry = row_to_dict(row)
+
+    if since is not None:
+        try:
+            blocks = blocks_since(entry["chain"], since)
+        except (TypeError, ValueError):
+            return error("since must be an ISO 8601 timestamp")
+    if isinstance(blocks, bool) or not isinstance(blocks, int) or blocks <= 0:
+        return error("blocks must be a positive integer")
+    if blocks > MAX_BACKFILL_BLOCKS:
+        return error(f"At most {MAX_BACKFILL_BLOCKS} blocks per backfill (WALLET_MONITOR_MAX_BACKFILL_BLOCKS)")
+
+    try:
+        job, err = start_backfill(entry, blocks, logging.getLogger("wallet_monitor.backfill"))
+    except Exception as e:
+        return error(str(e), 502, code=ErrorCode.UPSTREAM)
+    if err:
+        return error(err, 409, code=ErrorCode.CONFLICT)
+    return success(job)
+
+
+@app.route("/rpc/backfill", methods=["GET"])
+def rpc_backfill_list():
+    with _backfill_lock:
+        return success([backfill_job_view(j) for j in sorted(_backfill_jobs.values(), key=lambda j: -j["id"])])
+
+
+@app.route("/rpc/backfill/<int:job_id>", methods=["GET"])
+def rpc_backfill_status(job_id: int):
+    with _backfill_lock:
+        job = _backfill_jobs.get(job_id)
+        if job is None:
+            return error(f"Backfill #{job_id} not found", 404)
+        return success(backfill_job_view(job))
+
+
+# ---------------------------------------------------------------------------
+# CSV Export / Import
+# ---------------------------------------------------------------------------
+
+CSV_COLUMNS = ["address", "label", "chain", "threshold_usd", "monitor_enabled", "copy_trade_enabled", "notes"]
+
+
+def parse_csv_bool(value: str, default: bool):
+    value = value.strip().lower()
+    if not value:
+        return default
+    if value in ("1", "true", "yes", "y"):
+        return True
+    if value in ("0", "false", "no", "n"):
+        return False
+    raise ValueError(f"invalid boolean '{value}'")
+
+
+def parse_watchlist_csv_row(row: dict) -> dict:
+    """Validate one CSV row into an add_batch item. Raises ValueError with a readable reason."""
+    address = (row.get("address") or "").strip()
+    if not is_valid_eth_address(address):
+        raise ValueError(f"invalid Ethereum address '{address}'")
+    chain = (row.get("chain") or "").strip().lower() or "mainnet"
+    if chain not in SUPPORTED_CHAINS:
+        raise ValueError(f"unsupported chain '{chain}' (expected mainnet or base)")
+    threshold = (row.get("threshold_usd") or "").strip()
+    try:
+        threshold_usd = float(threshold) if threshold else 1000.0
+    except ValueError:
+        raise ValueError(f"invalid threshold_usd '{threshold}'") from None
+    if threshold_usd < 0:
+        raise ValueError("threshold_usd must not be negative")
+    monitor_enabled = parse_csv_bool(row.get("monitor_enabled") or "", True)
+    copy_trade_enabled = parse_csv_bool(row.get("copy_trade_enabled") or "", False)
+    return {
+        "address": address,
+        "label": (row.get("label") or "").strip() or None,
+        "chain": chain,
+        "threshold_usd": threshold_usd,
+        "monitor_enabled": monitor_enabled,
+        "copy_trade_enabled": copy_trade_enabled,
+        "notes": (row.get("notes") or "").strip() or None,
+    }
+
+
+def watchlist_import_csv(raw: str) -> dict:
+    """Validate every row, then add the valid ones in one batch.
+    Errors are reported against CSV line numbers (the header is line 1)."""
+    reader = csv.DictReader(io.StringIO(raw))
+    if not reader.fieldnames or "address" not in [f.strip() for f in reader.fieldnames]:
+        raise ValueError("CSV must have a header row with an 'address' column")
+    reader.fieldnames = [f.strip() for f in reader.fieldnames]
+
+    errors = []
+    items = []
+    item_lines = []
+    seen = {}
+    rows = 0
+    for row in reader:
+        line = reader.line_num
+        if not any((v or "").strip() for v in row.values() if isinstance(v, str)):
+            continue
+        rows += 1
+        if rows > MAX_CSV_ROWS:
+            errors.append({"line": line, "error": f"Row limit reached (max {MAX_CSV_ROWS}); remaining rows ignored"})
+            break
+        try:
+            item = parse_watchlist_csv_row(row)
+        except ValueError as e:
+            errors.append({"line": line, "error": str(e)})
+            continue
+        key = (item["address"].lower(), item["chain"])
+        if key in seen:
+            errors.append({"line": line, "error": f"duplicate of line {seen[key]}"})
+            continue
+        seen[key] = line
+        items.append(item)
+        item_lines.append(line)
+
+    imported = 0
+    for line, result in zip(item_lines, watchlist_add_batch(items) if items else []):
+        if result["success"]:
+            imported += 1
+        else:
+            errors.append({"line": line, "error": result["error"]})
+    errors.sort(key=lambda e: e["line"])
+    return {"imported": imported, "failed": len(errors), "errors": errors}
+
+
+@app.route("/rpc/csv/export")
+def rpc_csv_export():
+    buf = io.StringIO()
+    writer = csv.DictWriter(buf, fieldnames=CSV_COLUMNS)
+    writer.writeheader()
+    for w in watchlist_list():
+        writer.writerow({
+            "address": w["address"],
+            "label": w.get("label") or "",
+            "chain": w["chain"],
+            "threshold_usd": w["large_trade_threshold_usd"],
+            "monitor_enabled": "true" if w["monitor_enabled"] else "false",
+            "copy_trade_enabled": "true" if w["copy_trade_enabled"] else "false",
+            "notes": w.get("notes") or "",
+        })
+    return Response(
+        buf.getvalue(),
+        mimetype="text/csv",
+        headers={"Content-Disposition": "attachment; filename=wallet_watchlist.csv"},
+    )
+
+
+@app.route("/rpc/csv/import", methods=["POST"])
+def rpc_csv_import():
+    if request.content_length and request.content_length > MAX_CSV_BYTES:
+        return error(f"CSV too large (max {MAX_CSV_BYTES // 1024 // 1024} MB)")
+
+    f = request.files.get("file")
+    if f is None:
+        raw = request.get_data(as_text=True, cache=False)
+        if not raw:
+            return error("No CSV file or body provided")
+    else:
+        raw = f.read(MAX_CSV_BYTES + 1).decode("utf-8-sig", errors="replace")
+        if len(raw) > MAX_CSV_BYTES:
+            return error(f"CSV too large (max {MAX_CSV_BYTES // 1024 // 1024} MB)")
+
+    try:
+        return success(watchlist_import_csv(raw.lstrip("\ufeff")))
+    except ValueError as e:
         return error(str(e))
+    except Exception as e:
+        return error(str(e), code=ErrorCode.INTERNAL)
 
 
 # ---------------------------------------------------------------------------
@@ -758,7 +2389,8 @@ def dashboard():
 
     watchlist_rows = ""
     for w in wl:
-        label = w.get("label") or "-"
+        # ENS names are set by third parties, so never render them unescaped
+        label = escape(w.get("label") or w.get("resolved_name") or "-")
         status_cls = "active" if w["monitor_enabled"] else "paused"
         status_label = "Active" if w["monitor_enabled"] else "Paused"
         last_block = f"#{w['last_checked_block']}" if w.get("last_checked_block") else "-"
@@ -983,6 +2615,9 @@ if __name__ == "__main__":
     if ALCHEMY_API_KEY:
         worker_thread = threading.Thread(target=worker_loop, daemon=True)
         worker_thread.start()
+        if MEMPOOL_ENABLED:
+            for mempool_chain in MEMPOOL_CHAINS:
+                threading.Thread(target=mempool_loop, args=(mempool_chain,), daemon=True).start()
     else:
         logging.warning("[WALLET_MONITOR] ALCHEMY_API_KEY not set — background worker disabled")
 
@@ -0,0 +1,56 @@
+"""Per-wallet alert cooldown and burst digest tests.
+
+Run with:  uv run --with flask --with requests --with ../starkbot_sdk python -m unittest test_alert_cooldown
+"""
+
+import unittest
+from unittest import mock
+
+import service
+from fixtures import WALLET
+
+
+class AlertCooldownTests(unittest.TestCase):
+    def alert(self, alert_type="large_trade", **extra):
+        return {"alert_type": alert_type, "watchlist_id": 1, "address": WALLET, "label": "whale",
+                "chain": "mainnet", "message": f"{alert_type} alert", **extra}
+
+    def test_alerts_within_the_cooldown_are_counted_and_reported(self):
+        with mock.patch.dict(service._alert_throttle, clear=True):
+            self.assertEqual(len(service.throttle_alerts([self.alert()], {1: 60}, 100.0)), 1)
+            held = service.throttle_alerts([self.alert(), self.alert("approval")], {1: 60}, 110.0)
+            self.assertEqual(held, [])
+            self.assertEqual(service.due_alert_digests(150.0), [])
+
+            sent = service.throttle_alerts([self.alert()], {1: 60}, 170.0)
+            self.assertEqual(sent[0]["suppressed_count"], 2)
+            self.assertIn("+2 more alerts", sent[0]["message"])
+            self.assertEqual(service.due_alert_digests(300.0), [])
+
+    def test_digest_reports_a_burst_once_the_window_ends(self):
+        with mock.patch.dict(service._alert_throttle, clear=True):
+            service.throttle_alerts([self.alert()], {1: 60}, 100.0)
+            service.throttle_alerts([self.alert(), self.alert("approval")], {1: 60}, 110.0)
+            digests = service.due_alert_digests(161.0)
+            self.assertEqual(len(digests), 1)
+            self.assertEqual(digests[0]["alert_type"], "digest")
+            self.assertEqual(digests[0]["suppressed_count"], 2)
+            self.assertEqual(digests[0]["suppressed_types"], {"large_trade": 1, "approval": 1})
+            self.assertEqual(service.due_alert_digests(300.0), [])
+
+    def test_no_cooldown_and_denylisted_spenders_always_alert(self):
+        with mock.patch.dict(service._alert_throttle, clear=True):
+            self.assertEqual(len(service.throttle_alerts([self.alert(), self.alert()], {1: 0}, 100.0)), 2)
+            service.throttle_alerts([self.alert()], {1: 60}, 100.0)
+            flagged = self.alert("approval", spender_flagged=True)
+            self.assertEqual(service.throttle_alerts([flagged], {1: 60}, 101.0), [flagged])
+
+    def test_entry_cooldown_falls_back_to_global(self):
+        with mock.patch.object(service, "ALERT_COOLDOWN_SECS", 300):
+            self.assertEqual(service.entry_alert_cooldown({"alert_cooldown_secs": None}), 300)
+            self.assertEqual(service.entry_alert_cooldown({"alert_cooldown_secs": 0}), 0)
+            self.assertEqual(service.entry_alert_cooldown({"alert_cooldown_secs": 30}), 30)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,66 @@
+"""Wallet history backfill tests, fed with alchemy_getAssetTransfers-shaped payloads.
+
+Run with:  uv run --with flask --with requests --with ../starkbot_sdk python -m unittest test_backfill
+"""
+
+import os
+import tempfile
+import unittest
+from unittest import mock
+
+import service
+from fixtures import OTHER, USDC, WALLET, transfer
+
+
+class BackfillTests(unittest.TestCase):
+    def test_backfill_range_ends_before_live_coverage(self):
+        with mock.patch.multiple(service, CONFIRMATIONS=3, REORG_WINDOW=10, FIRST_RUN_LOOKBACK_BLOCKS=50):
+            self.assertEqual(service.backfill_range({"last_checked_block": 1000}, 1005, 100), (891, 990))
+            self.assertEqual(service.backfill_range({"last_checked_block": None}, 1000, 100), (847, 946))
+            self.assertEqual(service.backfill_range({"last_checked_block": 40}, 50, 100), (0, 30))
+            self.assertIsNone(service.backfill_range({"last_checked_block": 5}, 10, 100))
+
+    def test_backfill_records_history_without_alerts(self):
+        swap = [
+            transfer("erc20", WALLET, OTHER, value=5000, asset="USDC", contract=USDC),
+            transfer("erc20", OTHER, WALLET, value=2, asset="WETH", contract="0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+        ]
+        swap_block = 0x12a05f2
+
+        def fake_transfers(chain, address, from_block, direction, to_block):
+            if not from_block <= swap_block <= to_block:
+                return [], False
+            return ([swap[0]] if direction == "from" else [swap[1]]), False
+
+        with tempfile.TemporaryDirectory() as tmp, \
+                mock.patch.object(service, "DB_PATH", os.path.join(tmp, "test.db")), \
+                mock.patch.object(service, "BACKFILL_CHUNK_BLOCKS", 60), \
+                mock.patch.object(service, "estimate_usd_value", return_value=5000.0), \
+                mock.patch.object(service, "execute_copy_trade") as copy_trade, \
+                mock.patch.object(service, "alchemy_get_asset_transfers", side_effect=fake_transfers) as fetch:
+            service.init_db()
+            conn = service.get_db()
+            conn.execute("INSERT INTO wallet_watchlist (id, address, chain, copy_trade_enabled, large_trade_threshold_usd) VALUES (1, ?, 'mainnet', 1, 100)", (WALLET,))
+            conn.commit()
+            entry = service.row_to_dict(conn.execute("SELECT * FROM wallet_watchlist WHERE id = 1").fetchone())
+            conn.close()
+
+            job = {"id": 1, "from_block": swap_block - 99, "to_block": swap_block + 20, "current_block": 0,
+                   "blocks_scanned": 0, "inserted": 0, "status": "queued", "error": None, "finished_at": None}
+            service.run_backfill(job, entry, mock.Mock())
+
+            conn = service.get_db()
+            rows = conn.execute("SELECT activity_type, is_large_trade FROM wallet_activity").fetchall()
+            conn.close()
+        self.assertEqual(job["status"], "done")
+        self.assertEqual(job["blocks_scanned"], 120)
+        self.assertEqual(service.backfill_job_view(job)["progress"], 1.0)
+        self.assertEqual(fetch.call_count, 4)  # two chunks, both directions
+        # One entry per transaction, flagged large but never alerted or copied
+        self.assertEqual(job["inserted"], 1)
+        self.assertEqual([(r["activity_type"], r["is_large_trade"]) for r in rows], [("swap", 1)])
+        copy_trade.assert_not_called()
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,122 @@
+"""Activity classification and approval alert tests, fed with alchemy_getAssetTransfers-shaped payloads.
+
+Run with:  uv run --with flask --with requests --with ../starkbot_sdk python -m unittest test_classification
+"""
+
+import unittest
+
+import service
+from fixtures import NFT_CONTRACT, OTHER, USDC, WALLET, transfer
+from service import ActivityType, build_approval_alert, classify_transfer, decode_approval, needs_tx_input
+
+
+def classify_all(transfers, chain="mainnet", tx_input=None):
+    return [classify_transfer(t, d, transfers, chain, tx_input) for t, d in transfers]
+
+
+class ClassificationTests(unittest.TestCase):
+    def test_plain_transfers(self):
+        eth = [(transfer("external", WALLET, OTHER, 1.5, "ETH"), "outgoing")]
+        self.assertEqual(classify_all(eth), [ActivityType.ETH_TRANSFER])
+        erc20 = [(transfer("erc20", OTHER, WALLET, 250.0, "USDC", USDC), "incoming")]
+        self.assertEqual(classify_all(erc20), [ActivityType.ERC20_TRANSFER])
+
+    def test_swap(self):
+        txs = [
+            (transfer("erc20", WALLET, OTHER, 1000.0, "USDC", USDC), "outgoing"),
+            (transfer("erc20", OTHER, WALLET, 0.4, "WETH"), "incoming"),
+        ]
+        self.assertEqual(classify_all(txs), [ActivityType.SWAP, ActivityType.SWAP])
+
+    def test_nft_mint(self):
+        txs = [
+            (transfer("external", WALLET, NFT_CONTRACT, 0.08, "ETH"), "outgoing"),
+            (transfer("erc721", "0x0000000000000000000000000000000000000000", WALLET, None, None, NFT_CONTRACT, "0x1f"), "incoming"),
+        ]
+        self.assertEqual(classify_all(txs), [ActivityType.NFT_MINT, ActivityType.NFT_MINT])
+
+    def test_nft_sale_and_plain_nft_transfer(self):
+        sale = [
+            (transfer("erc721", WALLET, OTHER, None, None, NFT_CONTRACT, "0x2a"), "outgoing"),
+            (transfer("internal", OTHER, WALLET, 12.0, "ETH"), "incoming"),
+        ]
+        self.assertEqual(classify_all(sale), [ActivityType.NFT_SALE, ActivityType.NFT_SALE])
+        gift = [(transfer("erc1155", WALLET, OTHER, None, None, NFT_CONTRACT, "0x01"), "outgoing")]
+        self.assertEqual(classify_all(gift), [ActivityType.NFT_TRANSFER])
+
+    def test_approval_needs_calldata(self):
+        t = transfer("external", WALLET, USDC, 0.0, "ETH")
+        self.assertTrue(needs_tx_input(t, "outgoing"))
+        approve = "0x095ea7b3" + "00" * 64
+        self.assertEqual(classify_all([(t, "outgoing")], tx_input=approve), [ActivityType.APPROVAL])
+        self.assertEqual(classify_all([(t, "outgoing")], tx_input="0xa9059cbb"), [ActivityType.ETH_TRANSFER])
+        self.assertFalse(needs_tx_input(transfer("external", WALLET, OTHER, 1.0, "ETH"), "outgoing"))
+
+    def test_contract_deploy(self):
+        t = transfer("external", WALLET, None, 0.0, "ETH")
+        self.assertFalse(needs_tx_input(t, "outgoing"))
+        self.assertEqual(classify_all([(t, "outgoing")]), [ActivityType.CONTRACT_DEPLOY])
+
+    def test_bridge_direction_depends_on_chain(self):
+        portal = "0x49048044D57e1C92A77f79988d21Fa8fAF74E97e"
+        l2_bridge = "0x4200000000000000000000000000000000000010"
+        self.assertEqual(
+            classify_all([(transfer("external", WALLET, portal, 2.0, "ETH"), "outgoing")]),
+            [ActivityType.BRIDGE_DEPOSIT],
+        )
+        self.assertEqual(
+            classify_all([(transfer("external", portal, WALLET, 2.0, "ETH"), "incoming")]),
+            [ActivityType.BRIDGE_WITHDRAWAL],
+        )
+        self.assertEqual(
+            classify_all([(transfer("external", WALLET, l2_bridge, 2.0, "ETH"), "outgoing")], chain="base"),
+            [ActivityType.BRIDGE_WITHDRAWAL],
+        )
+
+
+def approve_calldata(spender, amount, selector="0x095ea7b3"):
+    return selector + spender.replace("0x", "").rjust(64, "0") + f"{amount:064x}"
+
+
+class ApprovalAlertTests(unittest.TestCase):
+    entry = {"id": 1, "address": WALLET, "label": "Treasury", "chain": "mainnet", "large_trade_threshold_usd": 1000.0}
+
+    def test_decode_approval(self):
+        unlimited = decode_approval(approve_calldata(OTHER, 2 ** 256 - 1))
+        self.assertEqual(unlimited["spender"], OTHER)
+        self.assertTrue(unlimited["unlimited"])
+        self.assertEqual(decode_approval(approve_calldata(OTHER, 5_000_000))["allowance_raw"], "5000000")
+        # Revocations and unrelated calls aren't approvals to alert on
+        self.assertIsNone(decode_approval(approve_calldata(OTHER, 0)))
+        self.assertIsNone(decode_approval(approve_calldata(OTHER, 1, selector="0xa9059cbb")))
+        operator = decode_approval(approve_calldata(OTHER, 1, selector="0xa22cb465"))
+        self.assertTrue(operator["operator"] and operator["unlimited"])
+
+    def test_alert_only_for_risky_approvals(self):
+        usdc = {"symbol": "USDC", "decimals": 6}
+        small = decode_approval(approve_calldata(OTHER, 50 * 10 ** 6))
+        self.assertIsNone(build_approval_alert(self.entry, USDC, small, "0xabc", usdc))
+
+        large = decode_approval(approve_calldata(OTHER, 5000 * 10 ** 6))
+        alert = build_approval_alert(self.entry, USDC, large, "0xabc", usdc)
+        self.assertEqual((alert["alert_type"], alert["allowance_formatted"], alert["usd_value"]), ("approval", "5000", 5000.0))
+
+        unlimited = decode_approval(approve_calldata(OTHER, 2 ** 256 - 1))
+        alert = build_approval_alert(self.entry, USDC, unlimited, "0xabc")
+        self.assertIn("UNLIMITED", alert["message"])
+        self.assertFalse(alert["spender_flagged"])
+
+    def test_denylisted_spender_is_flagged(self):
+        original = service.SPENDER_DENYLIST
+        service.SPENDER_DENYLIST = {OTHER}
+        try:
+            small = decode_approval(approve_calldata(OTHER, 1))
+            alert = build_approval_alert(self.entry, USDC, small, "0xabc", {"symbol": "USDC", "decimals": 6})
+            self.assertTrue(alert["spender_flagged"])
+            self.assertIn("denylist", alert["message"])
+        finally:
+            service.SPENDER_DENYLIST = original
+
+
+if __name__ == "__main__":
+    unittest.main()
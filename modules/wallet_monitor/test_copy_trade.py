@@ -0,0 +1,103 @@
+"""Copy-trade sizing, allow/deny list, execution ordering and first-poll tests.
+
+Run with:  uv run --with flask --with requests --with ../starkbot_sdk python -m unittest test_copy_trade
+"""
+
+import os
+import tempfile
+import time
+import unittest
+from unittest import mock
+
+import service
+from fixtures import OTHER, USDC, WALLET, transfer
+from service import plan_copy_trade
+
+
+class CopyTradeTests(unittest.TestCase):
+    entry = {"id": 1, "address": WALLET, "chain": "base", "copy_trade_enabled": 1, "copy_trade_max_usd": 250.0}
+    swap = {
+        "tx_hash": "0xabc", "sell_token": USDC, "sell_symbol": "USDC", "sell_amount_raw": 10_000 * 10 ** 6,
+        "buy_token": "0x4200000000000000000000000000000000000006", "buy_symbol": "WETH", "leader_usd": 10_000.0,
+    }
+
+    def test_scaled_and_capped(self):
+        plan, reason = plan_copy_trade(self.entry, self.swap)
+        self.assertIsNone(reason)
+        # 10% of $10k is $1000, capped at the wallet's $250
+        self.assertEqual((plan["copy_usd"], plan["sell_amount_raw"]), (250.0, 250 * 10 ** 6))
+        plan, _ = plan_copy_trade({**self.entry, "copy_trade_max_usd": None}, self.swap)
+        self.assertEqual(plan["sell_amount_raw"], 1000 * 10 ** 6)
+
+    def test_skips(self):
+        self.assertIn("minimum", plan_copy_trade(self.entry, {**self.swap, "leader_usd": 5.0, "sell_amount_raw": 5 * 10 ** 6})[1])
+        self.assertIn("price", plan_copy_trade(self.entry, {**self.swap, "leader_usd": None})[1])
+        self.assertIn("unsupported", plan_copy_trade({**self.entry, "chain": "polygon"}, self.swap)[1])
+
+    def test_allow_and_deny_lists(self):
+        allowed, denied = service.COPY_TRADE_ALLOWED_TOKENS, service.COPY_TRADE_DENIED_TOKENS
+        try:
+            service.COPY_TRADE_DENIED_TOKENS = {USDC}
+            self.assertIn("deny list", plan_copy_trade(self.entry, self.swap)[1])
+            service.COPY_TRADE_DENIED_TOKENS = set()
+            service.COPY_TRADE_ALLOWED_TOKENS = {"pepe"}
+            self.assertIn("allow list", plan_copy_trade(self.entry, self.swap)[1])
+            service.COPY_TRADE_ALLOWED_TOKENS = {"weth"}
+            self.assertIsNone(plan_copy_trade(self.entry, self.swap)[1])
+        finally:
+            service.COPY_TRADE_ALLOWED_TOKENS, service.COPY_TRADE_DENIED_TOKENS = allowed, denied
+
+    def test_copy_trades_run_one_at_a_time_in_order(self):
+        running, overlaps, order = [], [], []
+
+        def fake_execute(entry, swap, logger):
+            running.append(swap["tx_hash"])
+            overlaps.append(len(running) > 1)
+            time.sleep(0.01)
+            order.append(swap["tx_hash"])
+            running.remove(swap["tx_hash"])
+
+        with mock.patch.object(service, "execute_copy_trade", side_effect=fake_execute):
+            for i in range(5):
+                service.enqueue_copy_trade({"id": 1}, {"tx_hash": f"0x{i:02d}"}, mock.Mock())
+            service._copy_trade_queue.join()
+        self.assertEqual(order, [f"0x{i:02d}" for i in range(5)])
+        self.assertFalse(any(overlaps), "copy trades overlapped")
+
+    def test_first_poll_history_is_not_copied(self):
+        swap = [
+            transfer("erc20", WALLET, OTHER, value=5000, asset="USDC", contract=USDC),
+            transfer("erc20", OTHER, WALLET, value=2, asset="WETH", contract="0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+        ]
+
+        def fake_transfers(chain, address, from_block, direction, to_block):
+            return ([swap[0]] if direction == "from" else [swap[1]]), False
+
+        with tempfile.TemporaryDirectory() as tmp, \
+                mock.patch.object(service, "DB_PATH", os.path.join(tmp, "test.db")), \
+                mock.patch.object(service, "alchemy_get_block_number", return_value=0x12a05f2 + 10), \
+                mock.patch.object(service, "estimate_usd_value", return_value=5000.0), \
+                mock.patch.object(service, "enqueue_copy_trade") as copy_trade, \
+                mock.patch.object(service, "alchemy_get_asset_transfers", side_effect=fake_transfers):
+            service.init_db()
+            conn = service.get_db()
+            conn.execute("INSERT INTO wallet_watchlist (id, address, chain, copy_trade_enabled, large_trade_threshold_usd) VALUES (1, ?, 'mainnet', 1, 100)", (WALLET,))
+            conn.commit()
+            entry = service.row_to_dict(conn.execute("SELECT * FROM wallet_watchlist WHERE id = 1").fetchone())
+            conn.close()
+            self.assertIsNone(entry["last_checked_block"])
+
+            new_count, alerts = service.process_wallet(entry, mock.Mock())
+
+            conn = service.get_db()
+            rows = conn.execute("SELECT activity_type, is_large_trade FROM wallet_activity").fetchall()
+            conn.close()
+        # The lookback swap is recorded, but it's history: no alert and no copy trade
+        self.assertEqual(new_count, 1)
+        self.assertEqual([(r["activity_type"], r["is_large_trade"]) for r in rows], [("swap", 1)])
+        self.assertEqual(alerts, [])
+        copy_trade.assert_not_called()
+
+
+if __name__ == "__main__":
+    unittest.main()
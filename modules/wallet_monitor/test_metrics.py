@@ -0,0 +1,31 @@
+"""Prometheus metrics tests.
+
+Run with:  uv run --with flask --with requests --with ../starkbot_sdk python -m unittest test_metrics
+"""
+
+import unittest
+from unittest import mock
+
+import service
+
+
+class MetricsTests(unittest.TestCase):
+    def test_alert_delivery_is_counted_and_exported(self):
+        response = mock.Mock()
+        response.raise_for_status.side_effect = [None, RuntimeError("502")]
+        alerts = [{"alert_type": "large_trade", "message": "a"}, {"alert_type": "approval", "message": "b"}]
+        with mock.patch.object(service, "ALERT_CALLBACK_URL", "http://alerts"), \
+                mock.patch.object(service.http_requests, "post", return_value=response), \
+                mock.patch.dict(service._metrics, alerts_sent={}, alerts_failed={}):
+            service.send_alerts(alerts, mock.Mock())
+            service.inc_metric("alerts_sent", "large_trade")
+            with mock.patch.object(service, "activity_stats", return_value=dict.fromkeys(
+                    ["total_transactions", "large_trades", "watched_wallets", "active_wallets"], 0)):
+                text = service.render_metrics()
+        self.assertIn('wallet_monitor_alerts_sent_total{alert_type="large_trade"} 2', text)
+        self.assertIn('wallet_monitor_alerts_failed_total{alert_type="approval"} 1', text)
+        self.assertIn("# TYPE wallet_monitor_last_tick_age_seconds gauge", text)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,35 @@
+"""Per-wallet poll interval tests.
+
+Run with:  uv run --with flask --with requests --with ../starkbot_sdk python -m unittest test_poll_schedule
+"""
+
+import unittest
+from unittest import mock
+
+import service
+
+
+class PollScheduleTests(unittest.TestCase):
+    def test_wallets_are_polled_on_their_own_interval(self):
+        fast = {"id": 1, "poll_interval_secs": 10}
+        default = {"id": 2, "poll_interval_secs": None}
+        too_fast = {"id": 3, "poll_interval_secs": 1}
+        self.assertEqual(service.entry_poll_interval(fast), 10)
+        self.assertEqual(service.entry_poll_interval(default), service.POLL_INTERVAL)
+        self.assertEqual(service.entry_poll_interval(too_fast), service.MIN_POLL_INTERVAL)
+
+        with mock.patch.dict(service._next_poll_at, {1: 110.0, 2: 140.0}, clear=True):
+            self.assertEqual(service.due_entries([fast, default, too_fast], 100.0), [too_fast])
+            self.assertEqual(service.due_entries([fast, default, too_fast], 120.0), [fast, too_fast])
+            self.assertEqual(service.seconds_until_next_poll(100.0), 10.0)
+
+    def test_poll_interval_validation(self):
+        self.assertEqual(service.parse_poll_interval(None), (None, None))
+        self.assertEqual(service.parse_poll_interval(0), (None, None))
+        self.assertEqual(service.parse_poll_interval(15), (15, None))
+        self.assertIsNotNone(service.parse_poll_interval(2)[1])
+        self.assertIsNotNone(service.parse_poll_interval("fast")[1])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,41 @@
+"""Token price cache tests.
+
+Run with:  uv run --with flask --with requests --with ../starkbot_sdk python -m unittest test_price_cache
+"""
+
+import unittest
+from unittest import mock
+
+import service
+
+
+class PriceCacheTests(unittest.TestCase):
+    def setUp(self):
+        service._price_cache.clear()
+        service._price_cache_stats.update(hits=0, misses=0)
+
+    def test_burst_reuses_one_fetch_per_chain_and_token(self):
+        with mock.patch.object(service, "fetch_dexscreener_price", return_value=2.0) as fetch:
+            values = [service.estimate_usd_value("PEPE", 10.0, "base") for _ in range(5)]
+            service.estimate_usd_value("PEPE", 1.0, "mainnet")
+        self.assertEqual(values, [20.0] * 5)
+        self.assertEqual(fetch.call_count, 2)
+        status = service.price_cache_status()
+        self.assertEqual((status["hits"], status["misses"], status["entries"]), (4, 2, 2))
+
+    def test_failed_lookups_are_cached(self):
+        with mock.patch.object(service, "fetch_dexscreener_price", return_value=None) as fetch:
+            self.assertIsNone(service.estimate_usd_value("SCAM", 1.0, "base"))
+            self.assertIsNone(service.estimate_usd_value("SCAM", 1.0, "base"))
+        self.assertEqual(fetch.call_count, 1)
+
+    def test_expired_entries_are_refetched(self):
+        with mock.patch.object(service, "fetch_dexscreener_price", return_value=3.0) as fetch:
+            service.get_token_price("ARB", "mainnet")
+            service._price_cache[("mainnet", "ARB")] = (3.0, 0.0)
+            service.get_token_price("ARB", "mainnet")
+        self.assertEqual(fetch.call_count, 2)
+
+
+if __name__ == "__main__":
+    unittest.main()
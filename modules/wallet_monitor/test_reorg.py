@@ -0,0 +1,92 @@
+"""Reorg handling and truncated fetch tests, fed with alchemy_getAssetTransfers-shaped payloads.
+
+Run with:  uv run --with flask --with requests --with ../starkbot_sdk python -m unittest test_reorg
+"""
+
+import os
+import tempfile
+import unittest
+from unittest import mock
+
+import service
+from fixtures import OTHER, WALLET, transfer
+
+
+class ReorgTests(unittest.TestCase):
+    def test_scan_range_trails_head_and_rescans_window(self):
+        with mock.patch.multiple(service, CONFIRMATIONS=3, REORG_WINDOW=10, MAX_BLOCK_RANGE=100, FIRST_RUN_LOOKBACK_BLOCKS=50):
+            self.assertEqual(service.scan_range(None, 1000), (947, 997))
+            self.assertEqual(service.scan_range(990, 1000), (981, 997))
+            # Nothing new is confirmed yet
+            self.assertIsNone(service.scan_range(997, 1000))
+            # Far behind: catch up one chunk at a time
+            self.assertEqual(service.scan_range(500, 1000), (491, 600))
+
+    def test_reorged_activity_is_dropped_or_moved(self):
+        with tempfile.TemporaryDirectory() as tmp, \
+                mock.patch.object(service, "DB_PATH", os.path.join(tmp, "test.db")), \
+                mock.patch.dict(service._metrics, reorged={}):
+            service.init_db()
+            conn = service.get_db()
+            conn.execute("INSERT INTO wallet_watchlist (id, address, chain) VALUES (1, ?, 'base')", (WALLET,))
+            for tx, block in (("0xgone", 100), ("0xmoved", 101), ("0xkept", 102), ("0xold", 50)):
+                conn.execute(
+                    "INSERT INTO wallet_activity (watchlist_id, chain, tx_hash, block_number, from_address, to_address, activity_type) VALUES (1, 'base', ?, ?, ?, ?, 'eth_transfer')",
+                    (tx, block, WALLET, OTHER),
+                )
+            moved = transfer("external", WALLET, OTHER, value=1, asset="ETH")
+            moved["blockNum"] = hex(103)
+            kept = transfer("external", WALLET, OTHER, value=1, asset="ETH")
+            kept["blockNum"] = hex(102)
+            tx_groups = {"0xmoved": [(moved, "outgoing")], "0xkept": [(kept, "outgoing")]}
+
+            fixed = service.reconcile_reorged(conn, {"id": 1, "chain": "base", "address": WALLET}, 95, 105, tx_groups, mock.Mock())
+
+            blocks = dict(conn.execute("SELECT tx_hash, block_number FROM wallet_activity").fetchall())
+            conn.close()
+            self.assertEqual(service._metrics["reorged"], {"base": 2})
+        self.assertEqual(fixed, 2)
+        self.assertEqual(blocks, {"0xmoved": 103, "0xkept": 102, "0xold": 50})
+
+    def test_truncated_fetch_skips_reconcile_and_resumes_after_last_full_block(self):
+        def at(block, tx_hash):
+            t = transfer("external", WALLET, OTHER, value=1, asset="ETH")
+            t["blockNum"], t["hash"], t["uniqueId"] = hex(block), tx_hash, f"{tx_hash}:external"
+            return t
+
+        def fake_transfers(chain, address, from_block, direction, to_block):
+            if direction == "to":
+                return [], False
+            # Paging stopped partway through block 995
+            return [at(990, "0xa"), at(994, "0xb"), at(995, "0xc")], True
+
+        with tempfile.TemporaryDirectory() as tmp, \
+                mock.patch.object(service, "DB_PATH", os.path.join(tmp, "test.db")), \
+                mock.patch.multiple(service, CONFIRMATIONS=3, REORG_WINDOW=10, MAX_BLOCK_RANGE=100), \
+                mock.patch.object(service, "alchemy_get_block_number", return_value=1100), \
+                mock.patch.object(service, "estimate_usd_value", return_value=None), \
+                mock.patch.object(service, "alchemy_get_asset_transfers", side_effect=fake_transfers):
+            service.init_db()
+            conn = service.get_db()
+            conn.execute("INSERT INTO wallet_watchlist (id, address, chain, last_checked_block) VALUES (1, ?, 'base', 985)", (WALLET,))
+            # In the rescan window but beyond what the truncated fetch returned
+            conn.execute(
+                "INSERT INTO wallet_activity (watchlist_id, chain, tx_hash, block_number, from_address, to_address, activity_type) VALUES (1, 'base', '0xearlier', 980, ?, ?, 'eth_transfer')",
+                (WALLET, OTHER),
+            )
+            conn.commit()
+            entry = service.row_to_dict(conn.execute("SELECT * FROM wallet_watchlist WHERE id = 1").fetchone())
+            conn.close()
+
+            service.process_wallet(entry, mock.Mock())
+
+            conn = service.get_db()
+            hashes = {r["tx_hash"] for r in conn.execute("SELECT tx_hash FROM wallet_activity").fetchall()}
+            checked = conn.execute("SELECT last_checked_block FROM wallet_watchlist WHERE id = 1").fetchone()[0]
+            conn.close()
+        self.assertEqual(hashes, {"0xearlier", "0xa", "0xb"})
+        self.assertEqual(checked, 994)
+
+
+if __name__ == "__main__":
+    unittest.main()
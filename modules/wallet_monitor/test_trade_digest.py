@@ -0,0 +1,56 @@
+"""Periodic large-trade digest tests.
+
+Run with:  uv run --with flask --with requests --with ../starkbot_sdk python -m unittest test_trade_digest
+"""
+
+import os
+import tempfile
+import unittest
+from unittest import mock
+
+import service
+from fixtures import OTHER, WALLET
+
+
+class TradeDigestTests(unittest.TestCase):
+    def test_digest_summarizes_confirmed_large_trades_in_the_period(self):
+        with tempfile.TemporaryDirectory() as tmp, mock.patch.object(service, "DB_PATH", os.path.join(tmp, "wm.db")):
+            service.init_db()
+            conn = service.get_db()
+            conn.execute("INSERT INTO wallet_watchlist (id, address, label, chain) VALUES (1, ?, 'whale', 'mainnet')", (WALLET,))
+            conn.execute("INSERT INTO wallet_watchlist (id, address, chain) VALUES (2, ?, 'base')", (OTHER,))
+            activity = "INSERT INTO wallet_activity (watchlist_id, chain, tx_hash, block_number, from_address, to_address, activity_type, usd_value, is_large_trade, status, created_at) VALUES (?, 'mainnet', ?, 1, ?, ?, 'swap', ?, ?, ?, ?)"
+            for row in (
+                (1, "0x01", WALLET, OTHER, 5000.0, 1, "confirmed", "2026-01-01 10:30:00"),
+                (1, "0x02", WALLET, OTHER, 2000.0, 1, "confirmed", "2026-01-01 10:45:00"),
+                (2, "0x03", OTHER, WALLET, 9000.0, 1, "confirmed", "2026-01-01 10:50:00"),
+                (1, "0x04", WALLET, OTHER, 10.0, 0, "confirmed", "2026-01-01 10:50:00"),     # not large
+                (1, "0x05", WALLET, OTHER, 8000.0, 1, "pending", "2026-01-01 10:50:00"),     # not confirmed
+                (1, "0x06", WALLET, OTHER, 8000.0, 1, "confirmed", "2026-01-01 09:00:00"),   # previous digest
+            ):
+                conn.execute(activity, row)
+            conn.commit()
+
+            digest = service.build_trade_digest(conn, "2026-01-01 10:00:00", "2026-01-01 11:00:00")
+            empty = service.build_trade_digest(conn, "2026-01-01 11:00:00", "2026-01-01 12:00:00")
+            conn.close()
+        self.assertIsNone(empty)
+        self.assertEqual(digest["alert_type"], "trade_digest")
+        self.assertEqual(digest["trade_count"], 3)
+        self.assertEqual(digest["total_usd"], 16000.0)
+        self.assertEqual([(w["watchlist_id"], w["trade_count"], w["total_usd"]) for w in digest["wallets"]],
+                         [(2, 1, 9000.0), (1, 2, 7000.0)])
+        self.assertEqual([t["tx_hash"] for t in digest["trades"]], ["0x03", "0x01", "0x02"])
+        self.assertIn("**whale** (2, $7000)", digest["message"])
+
+    def test_digest_mode_holds_back_instant_large_trade_alerts(self):
+        alerts = [{"alert_type": "large_trade", "message": "big"}, {"alert_type": "approval", "message": "risky"}]
+        with mock.patch.object(service, "ALERT_MODE", "digest"), \
+                mock.patch.object(service, "ALERT_CALLBACK_URL", "http://cb"), \
+                mock.patch.object(service, "http_requests") as http:
+            service.send_alerts(alerts, mock.Mock(), throttle=False)
+        self.assertEqual([c.kwargs["json"]["alert_type"] for c in http.post.call_args_list], ["approval"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -35,6 +35,7 @@ pub fn parse_agent_file(content: &str) -> Result<AgentSubtypeConfig, String> {
         enabled: true,
         max_iterations: 90,
         skip_task_planner: false,
+        require_plan_approval: false,
         aliases: Vec::new(),
         hidden: false,
         preferred_ai_model: None,
@@ -90,6 +91,7 @@ pub fn parse_agent_file(content: &str) -> Result<AgentSubtypeConfig, String> {
                 "enabled" => config.enabled = value == "true",
                 "max_iterations" => config.max_iterations = value.parse().unwrap_or(90),
                 "skip_task_planner" => config.skip_task_planner = value == "true",
+                "require_plan_approval" => config.require_plan_approval = value == "true",
                 "hidden" => config.hidden = value == "true",
                 "preferred_ai_model" => {
                     let v = unquote_yaml(value);
@@ -302,6 +304,9 @@ fn serialize_agent_md(config: &AgentSubtypeConfig) -> String {
     yaml.push_str(&format!("enabled: {}\n", config.enabled));
     yaml.push_str(&format!("max_iterations: {}\n", config.max_iterations));
     yaml.push_str(&format!("skip_task_planner: {}\n", config.skip_task_planner));
+    if config.require_plan_approval {
+        yaml.push_str("require_plan_approval: true\n");
+    }
     yaml.push_str(&format!("hidden: {}\n", config.hidden));
     if let Some(ref model) = config.preferred_ai_model {
         yaml.push_str(&format!("preferred_ai_model: {}\n", model));
@@ -505,6 +510,7 @@ Finance prompt body here.
             enabled: true,
             max_iterations: 50,
             skip_task_planner: true,
+            require_plan_approval: true,
             aliases: vec!["tester".to_string()],
             hidden: false,
             preferred_ai_model: Some("minimax".to_string()),
@@ -525,6 +531,7 @@ Finance prompt body here.
         assert_eq!(parsed.enabled, config.enabled);
         assert_eq!(parsed.max_iterations, config.max_iterations);
         assert_eq!(parsed.skip_task_planner, config.skip_task_planner);
+        assert_eq!(parsed.require_plan_approval, config.require_plan_approval);
         assert_eq!(parsed.aliases, config.aliases);
         assert_eq!(parsed.hidden, config.hidden);
         assert_eq!(parsed.preferred_ai_model, config.preferred_ai_model);
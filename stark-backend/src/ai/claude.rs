@@ -1,5 +1,5 @@
 use crate::ai::types::{
-    AiError, AiResponse, ClaudeContentBlock, ClaudeMessage as TypedClaudeMessage,
+    client_retry_delay, AiError, AiResponse, ClaudeContentBlock, ClaudeMessage as TypedClaudeMessage,
     ClaudeMessageContent, ClaudeTool, ThinkingLevel, ToolCall, ToolResponse,
 };
 use crate::ai::{Message, MessageRole};
@@ -421,10 +421,13 @@ impl ClaudeClient {
         let mut last_error: Option<(String, Option<u16>)> = None;
         let mut response_data_opt: Option<ClaudeCompletionResponse> = None;
 
+        // Provider's Retry-After from the last retryable response, if any
+        let mut retry_after: Option<Duration> = None;
+
         for attempt in 0..=MAX_RETRIES {
             if attempt > 0 {
-                let delay_ms = BASE_DELAY_MS * (1 << (attempt - 1));
-                let wait_secs = delay_ms / 1000;
+                let delay_ms = client_retry_delay(attempt, BASE_DELAY_MS, retry_after.take()).as_millis() as u64;
+                let wait_secs = delay_ms.div_ceil(1000);
                 log::warn!(
                     "[CLAUDE] Tool request retry attempt {}/{} after {}ms delay",
                     attempt,
@@ -470,6 +473,7 @@ impl ClaudeClient {
             let is_retryable = matches!(status_code, 429 | 502 | 503 | 504);
 
             if !status.is_success() {
+                retry_after = crate::ai::types::retry_after(response.headers());
                 let error_text = response.text().await.unwrap_or_default();
 
                 // Check if this is a transient 402 error (payment settlement network failure)
@@ -499,7 +503,7 @@ impl ClaudeClient {
                     format!("Claude API returned error status: {}, body: {}", status, error_text)
                 };
 
-                return Err(AiError::with_status(error_msg, status_code));
+                return Err(AiError::with_status(error_msg, status_code).with_retry_after(retry_after));
             }
 
             response_data_opt = Some(response
@@ -23,6 +23,7 @@ use crate::x402::X402PaymentInfo;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -64,56 +65,209 @@ pub struct TraceEntry {
     pub output_error: Option<String>,
 }
 
+/// One step of a scripted mock response, replayed in order with its timing.
+#[derive(Debug, Clone)]
+pub enum MockStreamStep {
+    /// A chunk of response text
+    Content(String),
+    /// A complete tool call, streamed as start / arguments / complete events
+    ToolCall(ToolCall),
+    /// Pause before the next step
+    Delay(Duration),
+}
+
+/// A queued mock reply and the script it is streamed with
+struct MockReply {
+    result: Result<AiResponse, AiError>,
+    script: Vec<MockStreamStep>,
+}
+
+impl MockReply {
+    /// Script for a plain response: its text as one chunk, then each tool call
+    fn unscripted(result: Result<AiResponse, AiError>) -> Self {
+        let script = match &result {
+            Ok(response) => {
+                let mut script = Vec::new();
+                if !response.content.is_empty() {
+                    script.push(MockStreamStep::Content(response.content.clone()));
+                }
+                script.extend(response.tool_calls.iter().cloned().map(MockStreamStep::ToolCall));
+                script
+            }
+            Err(_) => Vec::new(),
+        };
+        MockReply { result, script }
+    }
+
+    /// Response assembled from a script's text chunks and tool calls
+    fn scripted(script: Vec<MockStreamStep>) -> Self {
+        let mut content = String::new();
+        let mut tool_calls = Vec::new();
+        for step in &script {
+            match step {
+                MockStreamStep::Content(chunk) => content.push_str(chunk),
+                MockStreamStep::ToolCall(call) => tool_calls.push(call.clone()),
+                MockStreamStep::Delay(_) => {}
+            }
+        }
+        let response = if tool_calls.is_empty() {
+            AiResponse::text(content)
+        } else {
+            AiResponse::with_tools(content, tool_calls)
+        };
+        MockReply { result: Ok(response), script }
+    }
+
+    /// Total scripted delay, waited out by non-streaming calls
+    fn delay(&self) -> Duration {
+        self.script
+            .iter()
+            .map(|step| match step {
+                MockStreamStep::Delay(d) => *d,
+                _ => Duration::ZERO,
+            })
+            .sum()
+    }
+}
+
 /// Mock AI client for integration tests — returns pre-configured responses from a queue.
 /// Also captures a trace of INPUT/OUTPUT for each iteration for auditing.
+///
+/// Replies can be scripted as a sequence of text chunks, tool calls and delays
+/// (see `MockAiClient::scripted`), which the streaming path replays as `stream.*`
+/// events and the non-streaming paths wait out before returning.
 #[derive(Clone)]
 pub struct MockAiClient {
-    responses: Arc<Mutex<VecDeque<Result<AiResponse, AiError>>>>,
+    responses: Arc<Mutex<VecDeque<MockReply>>>,
     trace: Arc<Mutex<Vec<TraceEntry>>>,
 }
 
 impl MockAiClient {
     /// Create a new MockAiClient with a queue of responses to return.
     pub fn new(responses: Vec<Result<AiResponse, AiError>>) -> Self {
+        Self::from_replies(responses.into_iter().map(MockReply::unscripted).collect())
+    }
+
+    /// Create a MockAiClient whose replies are built from scripted streams.
+    /// Each script becomes one response: its text chunks joined, plus its tool calls.
+    pub fn scripted(scripts: Vec<Vec<MockStreamStep>>) -> Self {
+        Self::from_replies(scripts.into_iter().map(MockReply::scripted).collect())
+    }
+
+    fn from_replies(replies: VecDeque<MockReply>) -> Self {
         MockAiClient {
-            responses: Arc::new(Mutex::new(VecDeque::from(responses))),
+            responses: Arc::new(Mutex::new(replies)),
             trace: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
-    /// Pop the next response from the queue, or return a fallback if exhausted.
-    /// Also records the INPUT/OUTPUT trace entry.
-    fn next_response_traced(
+    /// Pop the next reply from the queue, or a fallback if exhausted.
+    fn next_reply(&self) -> MockReply {
+        let mut queue = self.responses.lock().unwrap();
+        queue
+            .pop_front()
+            .unwrap_or_else(|| MockReply::unscripted(Ok(AiResponse::text("(mock exhausted)".to_string()))))
+    }
+
+    /// Record the INPUT/OUTPUT trace entry for one iteration.
+    fn record_trace(
         &self,
         messages: Vec<Message>,
         tool_history: Vec<ToolHistoryEntry>,
-        tools: Vec<crate::tools::ToolDefinition>,
-    ) -> Result<AiResponse, AiError> {
-        let mut queue = self.responses.lock().unwrap();
-        let result = queue.pop_front().unwrap_or_else(|| Ok(AiResponse::text("(mock exhausted)".to_string())));
-
-        let iteration = {
-            let trace = self.trace.lock().unwrap();
-            trace.len() + 1
-        };
-
+        tools: &[ToolDefinition],
+        result: &Result<AiResponse, AiError>,
+    ) {
+        let mut trace = self.trace.lock().unwrap();
         let entry = TraceEntry {
-            iteration,
+            iteration: trace.len() + 1,
             input_messages: messages,
             input_tool_history: tool_history,
             input_tools: tools.iter().map(|t| t.name.clone()).collect(),
             output_response: result.as_ref().ok().cloned(),
             output_error: result.as_ref().err().map(|e| e.message.clone()),
         };
+        trace.push(entry);
+    }
 
-        self.trace.lock().unwrap().push(entry);
-        result
+    /// Pop the next response after its scripted delay.
+    /// Also records the INPUT/OUTPUT trace entry.
+    async fn next_response_traced(
+        &self,
+        messages: Vec<Message>,
+        tool_history: Vec<ToolHistoryEntry>,
+        tools: Vec<ToolDefinition>,
+    ) -> Result<AiResponse, AiError> {
+        let reply = self.next_reply();
+        tokio::time::sleep(reply.delay()).await;
+        self.record_trace(messages, tool_history, &tools, &reply.result);
+        reply.result
     }
 
     /// Pop the next response without tracing (for simple generate_text calls).
-    fn next_response(&self) -> Result<AiResponse, AiError> {
-        let mut queue = self.responses.lock().unwrap();
-        queue.pop_front().unwrap_or_else(|| Ok(AiResponse::text("(mock exhausted)".to_string())))
+    async fn next_response(&self) -> Result<AiResponse, AiError> {
+        let reply = self.next_reply();
+        tokio::time::sleep(reply.delay()).await;
+        reply.result
+    }
+
+    /// Pop the next response and replay its script on `stream_sender`, ending with
+    /// `Done` (or `Error` for a failed reply). Also records the trace entry.
+    async fn stream_response_traced(
+        &self,
+        messages: Vec<Message>,
+        tool_history: Vec<ToolHistoryEntry>,
+        tools: Vec<ToolDefinition>,
+        stream_sender: streaming::StreamSender,
+    ) -> Result<AiResponse, AiError> {
+        use streaming::StreamEvent;
+
+        let reply = self.next_reply();
+        let mut tool_index = 0;
+        for step in &reply.script {
+            let events = match step {
+                MockStreamStep::Content(chunk) => vec![StreamEvent::ContentDelta {
+                    content: chunk.clone(),
+                    index: 0,
+                }],
+                MockStreamStep::ToolCall(call) => {
+                    let index = tool_index;
+                    tool_index += 1;
+                    vec![
+                        StreamEvent::ToolCallStart { id: call.id.clone(), name: call.name.clone(), index },
+                        StreamEvent::ToolCallDelta {
+                            id: call.id.clone(),
+                            arguments_delta: call.arguments.to_string(),
+                            index,
+                        },
+                        StreamEvent::ToolCallComplete {
+                            id: call.id.clone(),
+                            name: call.name.clone(),
+                            arguments: call.arguments.clone(),
+                            index,
+                        },
+                    ]
+                }
+                MockStreamStep::Delay(delay) => {
+                    tokio::time::sleep(*delay).await;
+                    continue;
+                }
+            };
+            for event in events {
+                let _ = stream_sender.send(event).await;
+            }
+        }
+
+        let last = match &reply.result {
+            Ok(response) => StreamEvent::Done { stop_reason: response.stop_reason.clone(), usage: None },
+            Err(e) => StreamEvent::Error {
+                message: e.message.clone(),
+                code: e.status_code.map(|c| c.to_string()),
+            },
+        };
+        let _ = stream_sender.send(last).await;
+
+        self.record_trace(messages, tool_history, &tools, &reply.result);
+        reply.result
     }
 
     /// Get the captured trace entries for auditing.
@@ -248,7 +402,7 @@ impl AiClient {
             AiClient::Claude(client) => client.generate_text(messages).await,
             AiClient::OpenAI(client) => client.generate_text(messages).await,
             AiClient::Llama(client) => client.generate_text(messages).await,
-            AiClient::Mock(client) => client.next_response()
+            AiClient::Mock(client) => client.next_response().await
                 .map(|r| r.content)
                 .map_err(|e| e.message),
         }
@@ -281,7 +435,7 @@ impl AiClient {
             // Other providers don't support x402
             AiClient::Claude(client) => Ok((client.generate_text(messages).await?, None)),
             AiClient::Llama(client) => Ok((client.generate_text(messages).await?, None)),
-            AiClient::Mock(client) => client.next_response()
+            AiClient::Mock(client) => client.next_response().await
                 .map(|r| (r.content, None))
                 .map_err(|e| e.message),
         }
@@ -317,8 +471,86 @@ impl AiClient {
                     .await
                     .map_err(AiError::from)
             }
-            AiClient::Mock(client) => client.next_response_traced(messages, tool_history, tools),
+            AiClient::Mock(client) => client.next_response_traced(messages, tool_history, tools).await,
+        }
+    }
+
+    /// Check if the current provider can stream tokens (OpenAI-compatible endpoints without x402,
+    /// and the mock, which replays its scripts)
+    pub fn supports_streaming(&self) -> bool {
+        match self {
+            AiClient::OpenAI(client) => client.supports_streaming(),
+            AiClient::Mock(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Generate response with tool support, relaying incremental tokens to the
+    /// broadcaster as `stream.*` events while the response is generated.
+    /// Falls back to `generate_with_tools` for providers that can't stream.
+    pub async fn generate_with_tools_streamed(
+        &self,
+        messages: Vec<Message>,
+        tool_history: Vec<ToolHistoryEntry>,
+        tools: Vec<ToolDefinition>,
+        broadcaster: &Arc<EventBroadcaster>,
+        channel_id: i64,
+    ) -> Result<AiResponse, AiError> {
+        if !self.supports_streaming() {
+            return self.generate_with_tools(messages, tool_history, tools).await;
         }
+
+        let (stream_sender, mut stream_rx) = streaming::create_default_stream_channel();
+        let relay_broadcaster = broadcaster.clone();
+        let relay = tokio::spawn(async move {
+            while let Some(event) = stream_rx.recv().await {
+                let gateway_event = match event {
+                    streaming::StreamEvent::ContentDelta { content, index } => {
+                        GatewayEvent::stream_content_delta(channel_id, &content, index)
+                    }
+                    streaming::StreamEvent::ToolCallStart { id, name, index } => {
+                        GatewayEvent::stream_tool_start(channel_id, &id, &name, index)
+                    }
+                    streaming::StreamEvent::ToolCallDelta { id, arguments_delta, index } => {
+                        GatewayEvent::stream_tool_delta(channel_id, &id, &arguments_delta, index)
+                    }
+                    streaming::StreamEvent::ToolCallComplete { id, name, arguments, index } => {
+                        GatewayEvent::stream_tool_complete(channel_id, &id, &name, &arguments, index)
+                    }
+                    streaming::StreamEvent::ThinkingDelta { content } => {
+                        GatewayEvent::stream_thinking_delta(channel_id, &content)
+                    }
+                    streaming::StreamEvent::Done { stop_reason, usage } => GatewayEvent::stream_end(
+                        channel_id,
+                        stop_reason.as_deref(),
+                        usage.as_ref().map(|u| u.input_tokens),
+                        usage.as_ref().map(|u| u.output_tokens),
+                    ),
+                    streaming::StreamEvent::Error { message, code } => {
+                        GatewayEvent::stream_error(channel_id, &message, code.as_deref())
+                    }
+                };
+                relay_broadcaster.broadcast(gateway_event);
+            }
+        });
+
+        broadcaster.broadcast(GatewayEvent::stream_start(channel_id, None));
+        let result = match self {
+            AiClient::OpenAI(client) => {
+                let tool_messages = Self::tool_history_to_openai(&tool_history);
+                client
+                    .generate_with_tools_streaming(messages, tool_messages, tools, stream_sender)
+                    .await
+                    .map_err(AiError::from)
+            }
+            AiClient::Mock(client) => {
+                client.stream_response_traced(messages, tool_history, tools, stream_sender).await
+            }
+            _ => self.generate_with_tools(messages, tool_history, tools).await,
+        };
+        // The sender is dropped with the request, so the relay drains and exits
+        let _ = relay.await;
+        result
     }
 
     /// Check if the current provider supports tools
@@ -457,6 +457,10 @@ impl SubAgentManager {
                 let mem_identity: Option<&str> = None;
                 match db.search_memories_fts(&fts_query, mem_identity, 10) {
                     Ok(results) if !results.is_empty() => {
+                        let memory_ids: Vec<i64> = results.iter().map(|(m, _)| m.id).collect();
+                        if let Err(e) = db.record_memory_hits(&memory_ids) {
+                            log::warn!("[SUBAGENT] Failed to record memory hits: {}", e);
+                        }
                         let mut hint = String::from("\n\n## Relevant Memories\n");
                         for (i, (mem, _rank)) in results.iter().enumerate() {
                             let snippet: String = mem.content.chars().take(200).collect();
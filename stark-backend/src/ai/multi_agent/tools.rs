@@ -39,6 +39,7 @@ pub fn define_tasks_tool() -> ToolDefinition {
         },
         group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
     }
 }
 
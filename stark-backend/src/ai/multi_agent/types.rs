@@ -55,6 +55,9 @@ pub struct AgentSubtypeConfig {
     /// If true, skip TaskPlanner mode and go straight to Assistant mode
     #[serde(default)]
     pub skip_task_planner: bool,
+    /// If true, show the planned task list and wait for the user's approval before executing it
+    #[serde(default)]
+    pub require_plan_approval: bool,
     /// Alternative names that resolve to this subtype key (e.g. "crypto" → "finance")
     #[serde(default)]
     pub aliases: Vec<String>,
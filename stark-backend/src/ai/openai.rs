@@ -1,5 +1,5 @@
 use crate::ai::streaming::{StreamEvent, StreamSender};
-use crate::ai::types::{AiError, AiResponse, ToolCall};
+use crate::ai::types::{client_retry_delay, AiError, AiResponse, ToolCall};
 use crate::ai::Message;
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
@@ -329,6 +329,11 @@ impl OpenAIClient {
         })
     }
 
+    /// Whether responses can be streamed (x402 payments aren't supported on the streaming path)
+    pub fn supports_streaming(&self) -> bool {
+        self.x402_client.is_none()
+    }
+
     /// Set the broadcaster for emitting retry events
     pub fn with_broadcaster(mut self, broadcaster: Arc<EventBroadcaster>, channel_id: i64) -> Self {
         self.broadcaster = Some(broadcaster);
@@ -463,11 +468,14 @@ impl OpenAIClient {
         let mut x402_payment: Option<X402PaymentInfo> = None;
         let mut response_text: Option<String> = None;
 
+        // Provider's Retry-After from the last retryable response, if any
+        let mut retry_after: Option<Duration> = None;
+
         for attempt in 0..=MAX_RETRIES {
             if attempt > 0 {
-                // Exponential backoff: 2s, 4s, 8s
-                let delay_ms = BASE_DELAY_MS * (1 << (attempt - 1));
-                let wait_secs = delay_ms / 1000;
+                // Provider's Retry-After, else exponential backoff: 2s, 4s, 8s
+                let delay_ms = client_retry_delay(attempt, BASE_DELAY_MS, retry_after.take()).as_millis() as u64;
+                let wait_secs = delay_ms.div_ceil(1000);
                 log::warn!(
                     "[OPENAI] Retry attempt {}/{} after {}ms delay",
                     attempt,
@@ -523,6 +531,7 @@ impl OpenAIClient {
             let is_retryable = matches!(status_code, 429 | 502 | 503 | 504);
 
             if !status.is_success() {
+                retry_after = crate::ai::types::retry_after(response.headers());
                 let error_text = response.text().await.unwrap_or_default();
 
                 // Check if this is a transient 402 error (payment settlement network failure)
@@ -576,7 +585,7 @@ impl OpenAIClient {
                     }
                 };
 
-                return Err(AiError::with_status(error_msg, status_code));
+                return Err(AiError::with_status(error_msg, status_code).with_retry_after(retry_after));
             }
 
             // Success - read response body
@@ -646,6 +655,8 @@ impl OpenAIClient {
             tool_calls,
             stop_reason: if is_tool_use {
                 Some("tool_use".to_string())
+            } else if finish_reason.as_deref() == Some("length") {
+                Some("max_tokens".to_string())
             } else {
                 Some("end_turn".to_string())
             },
@@ -1001,6 +1012,8 @@ impl OpenAIClient {
             tool_calls,
             stop_reason: if is_tool_use {
                 Some("tool_use".to_string())
+            } else if finish_reason.as_deref() == Some("length") {
+                Some("max_tokens".to_string())
             } else {
                 Some("end_turn".to_string())
             },
@@ -1008,3 +1021,93 @@ impl OpenAIClient {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::MessageRole;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serve one canned HTTP response per connection, in order
+    async fn mock_server(responses: Vec<String>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1/chat/completions", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                // Read the whole request (headers + Content-Length body) before answering
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some(end) = text.find("\r\n\r\n") {
+                        let content_length = text[..end]
+                            .lines()
+                            .find_map(|l| l.to_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap_or(0)))
+                            .unwrap_or(0);
+                        if request.len() >= end + 4 + content_length {
+                            break;
+                        }
+                    }
+                    if n == 0 {
+                        break;
+                    }
+                }
+                socket.write_all(response.as_bytes()).await.unwrap();
+                socket.shutdown().await.ok();
+            }
+        });
+        url
+    }
+
+    fn too_many_requests(retry_after: &str) -> String {
+        let body = r#"{"error":{"message":"Rate limit reached"}}"#;
+        format!(
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            retry_after,
+            body.len(),
+            body
+        )
+    }
+
+    fn user_message() -> Vec<Message> {
+        vec![Message { role: MessageRole::User, content: "hi".to_string() }]
+    }
+
+    #[tokio::test]
+    async fn retries_after_the_servers_retry_after_instead_of_backoff() {
+        let body = r#"{"choices":[{"message":{"content":"hello"},"finish_reason":"stop"}]}"#;
+        let ok = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let url = mock_server(vec![too_many_requests("1"), ok]).await;
+        let client = OpenAIClient::new("", Some(&url), None).unwrap();
+
+        let started = std::time::Instant::now();
+        let response = client.generate_with_tools(user_message(), Vec::new(), Vec::new()).await.unwrap();
+        assert_eq!(response.content, "hello");
+        // Waited the 1s the server asked for, not the 2s base backoff
+        let waited = started.elapsed();
+        assert!(waited >= Duration::from_secs(1) && waited < Duration::from_secs(2), "waited {:?}", waited);
+    }
+
+    #[tokio::test]
+    async fn surfaces_retry_after_when_retries_run_out() {
+        let url = mock_server(vec![too_many_requests("0"); 4]).await;
+        let client = OpenAIClient::new("", Some(&url), None).unwrap();
+
+        let err = client.generate_with_tools(user_message(), Vec::new(), Vec::new()).await.unwrap_err();
+        assert_eq!(err.status_code, Some(429));
+        assert_eq!(err.retry_after, Some(Duration::from_secs(0)));
+        assert!(err.to_string().contains("(Retry after 0s)"));
+        assert!(matches!(
+            crate::telemetry::FailureReason::classify(&err.to_string()),
+            crate::telemetry::FailureReason::RateLimited(_)
+        ));
+    }
+}
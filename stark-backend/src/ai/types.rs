@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fmt;
+use std::time::Duration;
 use crate::x402::X402PaymentInfo;
 
 /// AI API error with status code information
@@ -10,6 +11,8 @@ pub struct AiError {
     pub message: String,
     /// HTTP status code if available
     pub status_code: Option<u16>,
+    /// How long the provider asked us to wait (its Retry-After header)
+    pub retry_after: Option<Duration>,
 }
 
 impl AiError {
@@ -17,6 +20,7 @@ impl AiError {
         AiError {
             message: message.into(),
             status_code: None,
+            retry_after: None,
         }
     }
 
@@ -24,9 +28,15 @@ impl AiError {
         AiError {
             message: message.into(),
             status_code: Some(status_code),
+            retry_after: None,
         }
     }
 
+    pub fn with_retry_after(mut self, retry_after: Option<Duration>) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+
     /// Check if this is a client error (4xx status code)
     /// These errors indicate something wrong with the request that the AI might be able to fix
     pub fn is_client_error(&self) -> bool {
@@ -48,13 +58,42 @@ impl AiError {
     }
 }
 
+/// Longest Retry-After the AI clients wait between their own retries
+pub const MAX_CLIENT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Parse a response's `Retry-After` header: delta-seconds or an HTTP date
+pub fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let secs = (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_seconds().max(0);
+    Some(Duration::from_secs(secs as u64))
+}
+
+/// Delay before a client-level retry: the provider's Retry-After when it sent
+/// one (capped at `MAX_CLIENT_RETRY_AFTER`), else exponential backoff
+/// (`base_delay_ms`, doubling per attempt).
+pub fn client_retry_delay(attempt: u32, base_delay_ms: u64, retry_after: Option<Duration>) -> Duration {
+    match retry_after {
+        Some(wait) => wait.min(MAX_CLIENT_RETRY_AFTER),
+        None => Duration::from_millis(base_delay_ms * (1 << attempt.saturating_sub(1))),
+    }
+}
+
 impl fmt::Display for AiError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(code) = self.status_code {
-            write!(f, "[HTTP {}] {}", code, self.message)
+            write!(f, "[HTTP {}] {}", code, self.message)?;
         } else {
-            write!(f, "{}", self.message)
+            write!(f, "{}", self.message)?;
         }
+        // Kept in the text so it survives conversion to String (see Attempt::fail)
+        if let Some(wait) = self.retry_after {
+            write!(f, " (Retry after {}s)", wait.as_secs_f64().ceil() as u64)?;
+        }
+        Ok(())
     }
 }
 
@@ -336,6 +375,12 @@ impl AiResponse {
     pub fn is_tool_use(&self) -> bool {
         self.stop_reason.as_deref() == Some("tool_use") || !self.tool_calls.is_empty()
     }
+
+    /// Check if generation was cut off by the max response tokens limit
+    /// ("max_tokens" from Claude, "length" from OpenAI-compatible and Ollama APIs)
+    pub fn is_truncated(&self) -> bool {
+        matches!(self.stop_reason.as_deref(), Some("max_tokens") | Some("length"))
+    }
 }
 
 /// Tool definition in Claude API format
@@ -430,6 +475,22 @@ impl ClaudeMessage {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_retry_after_header_and_client_delay() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(reqwest::header::RETRY_AFTER, "7".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+        // HTTP dates in the past mean "now"
+        headers.insert(reqwest::header::RETRY_AFTER, "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(0)));
+
+        assert_eq!(client_retry_delay(1, 2000, None), Duration::from_millis(2000));
+        assert_eq!(client_retry_delay(3, 2000, None), Duration::from_millis(8000));
+        assert_eq!(client_retry_delay(1, 2000, Some(Duration::from_secs(5))), Duration::from_secs(5));
+        assert_eq!(client_retry_delay(1, 2000, Some(Duration::from_secs(3600))), MAX_CLIENT_RETRY_AFTER);
+    }
+
     #[test]
     fn test_ai_response_text() {
         let response = AiResponse::text("Hello world".to_string());
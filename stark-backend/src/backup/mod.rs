@@ -11,6 +11,7 @@
 //! This means you can freely add/remove fields without breaking existing backups.
 
 pub mod restore;
+pub mod scheduled;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -19,6 +20,74 @@ use std::collections::HashMap;
 /// Current backup format version
 pub const BACKUP_VERSION: u32 = 1;
 
+/// `format` marker of a downloaded backup file
+pub const BACKUP_FILE_FORMAT: &str = "starkbot-backup";
+
+/// Encrypted backup saved as a file, for restoring without the keystore server.
+///
+/// `encrypted_data` is the same hex ECIES blob the keystore stores, so only the
+/// wallet that made the backup can decrypt it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncryptedBackupFile {
+    pub format: String,
+    /// BackupData version inside the encrypted payload
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    /// SHA-256 (hex) of `encrypted_data`
+    pub checksum: String,
+    pub encrypted_data: String,
+}
+
+impl Default for EncryptedBackupFile {
+    fn default() -> Self {
+        Self {
+            format: String::new(),
+            version: 0,
+            created_at: Utc::now(),
+            checksum: String::new(),
+            encrypted_data: String::new(),
+        }
+    }
+}
+
+impl EncryptedBackupFile {
+    pub fn new(encrypted_data: String) -> Self {
+        Self {
+            format: BACKUP_FILE_FORMAT.to_string(),
+            version: BACKUP_VERSION,
+            created_at: Utc::now(),
+            checksum: sha256_hex(&encrypted_data),
+            encrypted_data,
+        }
+    }
+
+    /// Check the file is a backup this build can read and wasn't truncated or altered
+    pub fn validate(&self) -> Result<(), String> {
+        if self.format != BACKUP_FILE_FORMAT {
+            return Err("Not a StarkBot backup file".to_string());
+        }
+        if self.version == 0 || self.version > BACKUP_VERSION {
+            return Err(format!(
+                "Unsupported backup version {} (this build reads up to version {})",
+                self.version, BACKUP_VERSION
+            ));
+        }
+        if self.encrypted_data.is_empty() || hex::decode(&self.encrypted_data).is_err() {
+            return Err("Backup file has no valid encrypted data".to_string());
+        }
+        if !self.checksum.eq_ignore_ascii_case(&sha256_hex(&self.encrypted_data)) {
+            return Err("Backup file checksum mismatch (file is corrupted or was modified)".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn sha256_hex(data: &str) -> String {
+    use sha2::{Digest, Sha256};
+    format!("{:x}", Sha256::digest(data.as_bytes()))
+}
+
 /// Complete backup data structure
 ///
 /// This is the encrypted payload stored on the keystore server.
@@ -260,6 +329,61 @@ pub struct MemoryEntry {
     pub agent_subtype: Option<String>,
 }
 
+impl MemoryEntry {
+    pub fn from_row(m: &crate::db::tables::memories::MemoryRow) -> Self {
+        Self {
+            memory_type: m.memory_type.clone(),
+            content: m.content.clone(),
+            category: m.category.clone(),
+            tags: m.tags.clone(),
+            importance: Some(m.importance as i32),
+            identity_id: m.identity_id.clone(),
+            entity_type: m.entity_type.clone(),
+            entity_name: m.entity_name.clone(),
+            source_type: m.source_type.clone(),
+            log_date: m.log_date.clone(),
+            created_at: m.created_at.clone(),
+            agent_subtype: m.agent_subtype.clone(),
+        }
+    }
+
+    /// Insert this entry as a new memory, keeping its original created_at when present
+    pub fn insert(&self, db: &crate::db::Database) -> Result<i64, rusqlite::Error> {
+        if !self.created_at.is_empty() {
+            db.insert_memory_with_created_at(
+                &self.memory_type,
+                &self.content,
+                self.category.as_deref(),
+                self.tags.as_deref(),
+                self.importance.unwrap_or(5) as i64,
+                self.identity_id.as_deref(),
+                None,
+                self.entity_type.as_deref(),
+                self.entity_name.as_deref(),
+                self.source_type.as_deref(),
+                self.log_date.as_deref(),
+                &self.created_at,
+                self.agent_subtype.as_deref(),
+            )
+        } else {
+            db.insert_memory(
+                &self.memory_type,
+                &self.content,
+                self.category.as_deref(),
+                self.tags.as_deref(),
+                self.importance.unwrap_or(5) as i64,
+                self.identity_id.as_deref(),
+                None,
+                self.entity_type.as_deref(),
+                self.entity_name.as_deref(),
+                self.source_type.as_deref(),
+                self.log_date.as_deref(),
+                self.agent_subtype.as_deref(),
+            )
+        }
+    }
+}
+
 /// Bot settings entry in backup
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
@@ -276,6 +400,15 @@ pub struct BotSettingsEntry {
     pub theme_accent: Option<String>,
     pub whisper_server_url: Option<String>,
     pub embeddings_server_url: Option<String>,
+    pub gas_strategy: Option<String>,
+    pub gas_max_fee_gwei: Option<f64>,
+    pub command_prefix: Option<String>,
+    pub command_aliases: Option<String>,
+    pub gateway_previous_messages: Option<i32>,
+    #[serde(default)]
+    pub memory_fts_tokenizer: Option<String>,
+    #[serde(default)]
+    pub tx_confirmation_timeout_secs: Option<i32>,
 }
 
 /// Channel setting entry in backup
@@ -463,6 +596,8 @@ pub struct AgentSubtypeEntry {
     pub enabled: bool,
     pub max_iterations: Option<u32>,
     pub skip_task_planner: Option<bool>,
+    #[serde(default)]
+    pub require_plan_approval: Option<bool>,
     pub aliases_json: String,
     #[serde(default)]
     pub hidden: Option<bool>,
@@ -491,6 +626,8 @@ pub struct SpecialRoleEntry {
     pub name: String,
     pub allowed_tools_json: String,
     pub allowed_skills_json: String,
+    /// Empty in backups made before roles could deny tools
+    pub denied_tools_json: String,
     pub description: Option<String>,
 }
 
@@ -641,6 +778,10 @@ pub async fn collect_backup_data(
             .custom_rpc_endpoints
             .as_ref()
             .and_then(|h| serde_json::to_string(h).ok());
+        let command_aliases_json = settings
+            .command_aliases
+            .as_ref()
+            .and_then(|h| serde_json::to_string(h).ok());
 
         backup.bot_settings = Some(BotSettingsEntry {
             bot_name: settings.bot_name.clone(),
@@ -655,6 +796,13 @@ pub async fn collect_backup_data(
             theme_accent: settings.theme_accent.clone(),
             whisper_server_url: settings.whisper_server_url.clone(),
             embeddings_server_url: settings.embeddings_server_url.clone(),
+            gas_strategy: Some(settings.gas_strategy.clone()),
+            gas_max_fee_gwei: settings.gas_max_fee_gwei,
+            command_prefix: Some(settings.command_prefix.clone()),
+            command_aliases: command_aliases_json,
+            gateway_previous_messages: Some(settings.gateway_previous_messages),
+            memory_fts_tokenizer: Some(settings.memory_fts_tokenizer.clone()),
+            tx_confirmation_timeout_secs: Some(settings.tx_confirmation_timeout_secs),
         });
     }
 
@@ -704,20 +852,7 @@ pub async fn collect_backup_data(
             backup.memories = Some(
                 memories
                     .iter()
-                    .map(|m| MemoryEntry {
-                        memory_type: m.memory_type.clone(),
-                        content: m.content.clone(),
-                        category: m.category.clone(),
-                        tags: m.tags.clone(),
-                        importance: Some(m.importance as i32),
-                        identity_id: m.identity_id.clone(),
-                        entity_type: m.entity_type.clone(),
-                        entity_name: m.entity_name.clone(),
-                        source_type: m.source_type.clone(),
-                        log_date: m.log_date.clone(),
-                        created_at: m.created_at.clone(),
-                        agent_subtype: m.agent_subtype.clone(),
-                    })
+                    .map(MemoryEntry::from_row)
                     .collect(),
             );
         }
@@ -955,6 +1090,7 @@ pub async fn collect_backup_data(
                 enabled: s.enabled,
                 max_iterations: Some(s.max_iterations),
                 skip_task_planner: Some(s.skip_task_planner),
+                require_plan_approval: Some(s.require_plan_approval),
                 aliases_json: serde_json::to_string(&s.aliases).unwrap_or_else(|_| "[]".to_string()),
                 hidden: Some(s.hidden),
                 preferred_ai_model: s.preferred_ai_model.clone(),
@@ -974,6 +1110,7 @@ pub async fn collect_backup_data(
                 name: r.name.clone(),
                 allowed_tools_json: serde_json::to_string(&r.allowed_tools).unwrap_or_else(|_| "[]".to_string()),
                 allowed_skills_json: serde_json::to_string(&r.allowed_skills).unwrap_or_else(|_| "[]".to_string()),
+                denied_tools_json: serde_json::to_string(&r.denied_tools).unwrap_or_else(|_| "[]".to_string()),
                 description: r.description.clone(),
             })
             .collect();
@@ -1025,8 +1162,6 @@ pub async fn collect_backup_data(
     backup
 }
 
-/// Collect config files from tool directories that need to persist across restarts.
-///
 /// Collect all files from a skill's disk folder as SkillFileEntry items.
 /// Returns empty vec if the folder doesn't exist or is unreadable.
 fn collect_skill_folder_files(skills_dir: &std::path::Path, skill_name: &str) -> Vec<SkillFileEntry> {
@@ -1097,22 +1232,40 @@ fn collect_module_folder_files(module_dir: &std::path::Path) -> Vec<ModuleFileEn
     files
 }
 
-/// Currently supports:
+/// Tool config directories (relative to `$HOME`) that persist across restarts:
 /// - `gogcli`: ~/.config/gogcli/ (Google Workspace CLI auth tokens)
-fn collect_tool_configs(backup: &mut BackupData) {
-    use base64::Engine;
-    let engine = base64::engine::general_purpose::STANDARD;
+pub(crate) const TOOL_CONFIG_DIRS: &[(&str, &str)] = &[("gogcli", ".config/gogcli")];
+
+/// Config directory for a backed-up tool under `home`, if the tool is known
+pub(crate) fn tool_config_dir(home: &std::path::Path, tool_name: &str) -> Option<std::path::PathBuf> {
+    TOOL_CONFIG_DIRS
+        .iter()
+        .find(|(name, _)| *name == tool_name)
+        .map(|(_, rel_dir)| home.join(rel_dir))
+}
 
+/// Collect config files from tool directories that need to persist across restarts.
+fn collect_tool_configs(backup: &mut BackupData) {
     let home = match std::env::var("HOME") {
         Ok(h) => std::path::PathBuf::from(h),
         Err(_) => return,
     };
+    backup.tool_configs = collect_tool_configs_from(&home);
+}
+
+/// Collect [`TOOL_CONFIG_DIRS`] files under `home`, keyed by tool name
+pub(crate) fn collect_tool_configs_from(home: &std::path::Path) -> HashMap<String, Vec<ToolConfigFileEntry>> {
+    use base64::Engine;
+    let engine = base64::engine::general_purpose::STANDARD;
 
-    // gogcli: ~/.config/gogcli/
-    let gogcli_dir = home.join(".config").join("gogcli");
-    if gogcli_dir.exists() {
+    let mut configs = HashMap::new();
+    for (tool_name, _) in TOOL_CONFIG_DIRS {
+        let Some(config_dir) = tool_config_dir(home, tool_name) else { continue };
+        if !config_dir.exists() {
+            continue;
+        }
         let mut files = Vec::new();
-        if let Ok(entries) = collect_dir_files_recursive(&gogcli_dir, &gogcli_dir) {
+        if let Ok(entries) = collect_dir_files_recursive(&config_dir, &config_dir) {
             for (rel_path, content) in entries {
                 files.push(ToolConfigFileEntry {
                     relative_path: rel_path,
@@ -1121,10 +1274,11 @@ fn collect_tool_configs(backup: &mut BackupData) {
             }
         }
         if !files.is_empty() {
-            log::info!("[Backup] Collected {} gogcli config files", files.len());
-            backup.tool_configs.insert("gogcli".to_string(), files);
+            log::info!("[Backup] Collected {} {} config files", files.len(), tool_name);
+            configs.insert(tool_name.to_string(), files);
         }
     }
+    configs
 }
 
 /// Recursively collect all files in a directory, returning (relative_path, contents) pairs.
@@ -1285,6 +1439,19 @@ mod tests {
         assert_eq!(backup.item_count(), 2);
     }
 
+    #[test]
+    fn encrypted_backup_file_validation() {
+        let file = EncryptedBackupFile::new("deadbeef".to_string());
+        assert!(file.validate().is_ok());
+
+        let tampered = EncryptedBackupFile { encrypted_data: "deadbeee".to_string(), ..file.clone() };
+        assert!(tampered.validate().unwrap_err().contains("checksum"));
+        let newer = EncryptedBackupFile { version: BACKUP_VERSION + 1, ..file.clone() };
+        assert!(newer.validate().unwrap_err().contains("version"));
+        let other: EncryptedBackupFile = serde_json::from_str(r#"{"memories": []}"#).unwrap();
+        assert!(other.validate().is_err());
+    }
+
     #[tokio::test]
     async fn collect_backup_data_includes_memories() {
         let db = temp_db();
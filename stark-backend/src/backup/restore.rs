@@ -4,12 +4,16 @@
 //! (`controllers/api_keys.rs`) call [`restore_all`] so every resource type
 //! is handled consistently.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 
-use crate::backup::BackupData;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Serialize;
+
+use crate::backup::{BackupData, ToolConfigFileEntry};
 use crate::channels::ChannelManager;
 use crate::db::Database;
+use crate::disk_quota::DiskQuotaManager;
 use crate::notes::store::NoteStore;
 use crate::skills::SkillRegistry;
 
@@ -32,10 +36,13 @@ pub struct RestoreResult {
     pub memories: usize,
     pub notes: usize,
     pub kanban_items: usize,
+    pub tool_configs: usize,
     pub bot_settings: bool,
     pub heartbeat_config: bool,
     pub soul_document: bool,
     pub agent_identity: bool,
+    /// Per-section counts of items that already existed locally
+    pub conflicts: BTreeMap<&'static str, ConflictCounts>,
 }
 
 impl RestoreResult {
@@ -57,32 +64,344 @@ impl RestoreResult {
         if self.memories > 0 { parts.push(format!("{} memories", self.memories)); }
         if self.notes > 0 { parts.push(format!("{} notes", self.notes)); }
         if self.kanban_items > 0 { parts.push(format!("{} kanban items", self.kanban_items)); }
+        if self.tool_configs > 0 { parts.push(format!("{} tool config files", self.tool_configs)); }
         if self.bot_settings { parts.push("bot settings".to_string()); }
         if self.heartbeat_config { parts.push("heartbeat config".to_string()); }
         if self.soul_document { parts.push("soul document".to_string()); }
         if self.agent_identity { parts.push("agent identity".to_string()); }
 
-        if parts.is_empty() {
+        let mut summary = if parts.is_empty() {
             "No data restored".to_string()
         } else {
             format!("Restored {}", parts.join(", "))
+        };
+        let conflicts: Vec<String> = self.conflicts.iter()
+            .map(|(section, counts)| format!("{} ({})", section, counts.describe()))
+            .collect();
+        if !conflicts.is_empty() {
+            summary.push_str(&format!("; existing items: {}", conflicts.join(", ")));
         }
+        summary
+    }
+
+    /// Decide what to do with a backup item, counting it under `section` when it
+    /// collides with a local item. `local` is `None` when nothing exists locally,
+    /// otherwise the local item's last-modified time (if known).
+    fn resolve(
+        &mut self,
+        section: &'static str,
+        policy: ConflictPolicy,
+        local: Option<Option<DateTime<Utc>>>,
+        incoming: DateTime<Utc>,
+    ) -> Resolution {
+        let Some(local_modified) = local else {
+            return Resolution::Write;
+        };
+        let resolution = match policy {
+            ConflictPolicy::Overwrite => Resolution::Write,
+            ConflictPolicy::Skip => Resolution::Skip,
+            ConflictPolicy::KeepBoth => Resolution::Rename,
+            // Local items with no known timestamp lose to the backup
+            ConflictPolicy::NewerWins => match local_modified {
+                Some(local_modified) if local_modified >= incoming => Resolution::Skip,
+                _ => Resolution::Write,
+            },
+        };
+        let counts = self.conflicts.entry(section).or_default();
+        match resolution {
+            Resolution::Write => counts.overwritten += 1,
+            Resolution::Skip => counts.skipped += 1,
+            Resolution::Rename => counts.renamed += 1,
+        }
+        resolution
+    }
+}
+
+/// How restore treats a backup item that already exists locally (same name/key).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// The backup item replaces the local one. Sections restored wholesale
+    /// (channels, cron jobs, kanban, memories) are cleared first.
+    #[default]
+    Overwrite,
+    /// The local item is kept and the backup item dropped
+    Skip,
+    /// The backup item is restored under a new name next to the local one.
+    /// API keys and memories can't be renamed and keep the local item.
+    KeepBoth,
+    /// Whichever side was modified last wins. Items without their own
+    /// timestamp use the backup's `created_at`.
+    NewerWins,
+}
+
+impl ConflictPolicy {
+    /// Parse a policy name: overwrite, skip, keep-both (or rename), newer-wins.
+    /// An empty string is the default (overwrite).
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().replace('_', "-").as_str() {
+            "" | "overwrite" => Ok(Self::Overwrite),
+            "skip" => Ok(Self::Skip),
+            "keep-both" | "rename" => Ok(Self::KeepBoth),
+            "newer-wins" => Ok(Self::NewerWins),
+            other => Err(format!(
+                "Unknown conflict policy '{}'. Valid policies: overwrite, skip, keep-both, newer-wins",
+                other
+            )),
+        }
+    }
+
+    /// Keep-both falls back to skip for items identified by their content or a
+    /// fixed name (API keys, memories), where a renamed copy is meaningless
+    fn without_rename(self) -> Self {
+        if self == Self::KeepBoth { Self::Skip } else { self }
+    }
+
+    /// Whether sections restored wholesale are cleared before restoring
+    fn clears_sections(&self) -> bool {
+        *self == Self::Overwrite
     }
 }
 
-/// Restore everything from a [`BackupData`] payload.
+/// Outcome of [`RestoreResult::resolve`] for one backup item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Resolution {
+    /// Restore the item (new, or replacing the local one)
+    Write,
+    /// Leave the local item as-is
+    Skip,
+    /// Restore the item under a new name
+    Rename,
+}
+
+/// What happened to backup items that collided with local ones in a section
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ConflictCounts {
+    pub overwritten: usize,
+    pub skipped: usize,
+    pub renamed: usize,
+}
+
+impl ConflictCounts {
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.overwritten > 0 { parts.push(format!("{} overwritten", self.overwritten)); }
+        if self.skipped > 0 { parts.push(format!("{} skipped", self.skipped)); }
+        if self.renamed > 0 { parts.push(format!("{} renamed", self.renamed)); }
+        parts.join(", ")
+    }
+}
+
+/// Parse an RFC 3339 or SQLite (`YYYY-MM-DD HH:MM:SS`) timestamp
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").ok().map(|dt| dt.and_utc()))
+}
+
+/// Last-modified time of a file or directory on disk
+fn modified_at(path: &std::path::Path) -> Option<DateTime<Utc>> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok().map(DateTime::<Utc>::from)
+}
+
+/// First name produced by `make(n)` for n = 1, 2, ... that `taken` rejects
+fn unique_name(make: impl Fn(usize) -> String, taken: impl Fn(&str) -> bool) -> String {
+    (1..)
+        .map(make)
+        .find(|name| !taken(name))
+        .expect("unbounded range always yields a free name")
+}
+
+/// Replace the `field:` line in a markdown file's YAML frontmatter
+fn set_frontmatter_field(content: &str, field: &str, value: &str) -> String {
+    let prefix = format!("{}:", field);
+    let mut in_frontmatter = false;
+    let mut replaced = false;
+    let mut out = String::with_capacity(content.len());
+    for (i, line) in content.split_inclusive('\n').enumerate() {
+        let trimmed = line.trim_end();
+        if trimmed == "---" {
+            in_frontmatter = i == 0;
+        } else if in_frontmatter && !replaced && trimmed.starts_with(&prefix) {
+            out.push_str(&format!("{}: {}", field, value));
+            out.push_str(&line[trimmed.len()..]);
+            replaced = true;
+            continue;
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+/// Name for a renamed copy: "name-restored", "name-restored-2", ...
+fn restored_name(base: &str, n: usize) -> String {
+    if n == 1 { format!("{}-restored", base) } else { format!("{}-restored-{}", base, n) }
+}
+
+/// Which [`BackupData`] sections a restore applies. Defaults to everything.
+///
+/// Channels cover their settings and cron jobs too, since both reference
+/// channels by ID. Modules cover module data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestoreSelection {
+    pub api_keys: bool,
+    pub impulse_map: bool,
+    pub bot_settings: bool,
+    pub channels: bool,
+    pub heartbeat: bool,
+    pub soul: bool,
+    pub identity: bool,
+    pub x402_limits: bool,
+    pub modules: bool,
+    pub skills: bool,
+    pub agent_subtypes: bool,
+    pub agent_settings: bool,
+    pub kanban: bool,
+    pub special_roles: bool,
+    pub notes: bool,
+    pub memories: bool,
+    pub tool_configs: bool,
+}
+
+impl Default for RestoreSelection {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl RestoreSelection {
+    pub fn all() -> Self {
+        Self::with_all(true)
+    }
+
+    pub fn none() -> Self {
+        Self::with_all(false)
+    }
+
+    fn with_all(value: bool) -> Self {
+        Self {
+            api_keys: value,
+            impulse_map: value,
+            bot_settings: value,
+            channels: value,
+            heartbeat: value,
+            soul: value,
+            identity: value,
+            x402_limits: value,
+            modules: value,
+            skills: value,
+            agent_subtypes: value,
+            agent_settings: value,
+            kanban: value,
+            special_roles: value,
+            notes: value,
+            memories: value,
+            tool_configs: value,
+        }
+    }
+
+    fn flag_mut(&mut self, section: &str) -> Option<&mut bool> {
+        Some(match section {
+            "api_keys" => &mut self.api_keys,
+            "impulse_map" => &mut self.impulse_map,
+            "bot_settings" => &mut self.bot_settings,
+            "channels" => &mut self.channels,
+            "heartbeat" => &mut self.heartbeat,
+            "soul" => &mut self.soul,
+            "identity" => &mut self.identity,
+            "x402_limits" => &mut self.x402_limits,
+            "modules" => &mut self.modules,
+            "skills" => &mut self.skills,
+            "agent_subtypes" => &mut self.agent_subtypes,
+            "agent_settings" => &mut self.agent_settings,
+            "kanban" => &mut self.kanban,
+            "special_roles" => &mut self.special_roles,
+            "notes" => &mut self.notes,
+            "memories" => &mut self.memories,
+            "tool_configs" => &mut self.tool_configs,
+            _ => return None,
+        })
+    }
+
+    /// Parse a comma-separated list of section names (e.g. "skills,channels").
+    /// An empty list or "all" selects everything.
+    pub fn parse(list: &str) -> Result<Self, String> {
+        let names: Vec<String> = list
+            .split(',')
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty())
+            .collect();
+        if names.is_empty() || names.iter().any(|n| n == "all") {
+            return Ok(Self::all());
+        }
+        let mut selection = Self::none();
+        for name in &names {
+            match selection.flag_mut(name) {
+                Some(flag) => *flag = true,
+                None => {
+                    return Err(format!(
+                        "Unknown backup section '{}'. Valid sections: {}",
+                        name,
+                        Self::all().sections().join(", ")
+                    ));
+                }
+            }
+        }
+        Ok(selection)
+    }
+
+    /// Names of the selected sections
+    pub fn sections(&self) -> Vec<&'static str> {
+        [
+            ("api_keys", self.api_keys),
+            ("impulse_map", self.impulse_map),
+            ("bot_settings", self.bot_settings),
+            ("channels", self.channels),
+            ("heartbeat", self.heartbeat),
+            ("soul", self.soul),
+            ("identity", self.identity),
+            ("x402_limits", self.x402_limits),
+            ("modules", self.modules),
+            ("skills", self.skills),
+            ("agent_subtypes", self.agent_subtypes),
+            ("agent_settings", self.agent_settings),
+            ("kanban", self.kanban),
+            ("special_roles", self.special_roles),
+            ("notes", self.notes),
+            ("memories", self.memories),
+            ("tool_configs", self.tool_configs),
+        ]
+        .into_iter()
+        .filter(|(_, selected)| *selected)
+        .map(|(name, _)| name)
+        .collect()
+    }
+
+    pub fn is_all(&self) -> bool {
+        *self == Self::all()
+    }
+}
+
+/// Restore the selected sections of a [`BackupData`] payload.
+/// Sections not in `selection` are left untouched. Backup items that collide
+/// with local ones are handled per `policy`; see [`ConflictPolicy`].
 ///
 /// Optional components (`skill_registry`, `channel_manager`, `notes_store`)
 /// control post-restore actions:
 /// - `skill_registry` → reload DB, set enabled state
 /// - `channel_manager` → auto-start channels with `auto_start_on_boot`
 /// - `notes_store` → FTS reindex after writing note files
+/// - `disk_quota` → tool config files that would exceed the quota are skipped
+#[allow(clippy::too_many_arguments)]
 pub async fn restore_all(
     db: &Arc<Database>,
     backup_data: &mut BackupData,
     skill_registry: Option<&Arc<SkillRegistry>>,
     channel_manager: Option<&Arc<ChannelManager>>,
     notes_store: Option<&Arc<NoteStore>>,
+    disk_quota: Option<&Arc<DiskQuotaManager>>,
+    selection: &RestoreSelection,
+    policy: ConflictPolicy,
 ) -> Result<RestoreResult, String> {
     let mut result = RestoreResult::default();
 
@@ -92,96 +411,114 @@ pub async fn restore_all(
         backup_data.item_count(),
         backup_data.created_at.format("%Y-%m-%d %H:%M:%S")
     );
+    if !selection.is_all() {
+        log::info!("[Restore] Restoring only: {}", selection.sections().join(", "));
+    }
+    if policy != ConflictPolicy::Overwrite {
+        log::info!("[Restore] Conflict policy: {:?}", policy);
+    }
+    let backup_created_at = backup_data.created_at;
 
     // ── 1. API keys ─────────────────────────────────────────────────────
-    for key in &backup_data.api_keys {
-        if let Err(e) = db.upsert_api_key(&key.key_name, &key.key_value) {
-            log::warn!("[Restore] Failed to restore key {}: {}", key.key_name, e);
-        } else {
-            result.api_keys += 1;
+    if selection.api_keys {
+        for key in &backup_data.api_keys {
+            let local = db.get_api_key(&key.key_name).ok().flatten().map(|k| Some(k.updated_at));
+            // Keys are looked up by service name, so a renamed copy would never be used
+            if result.resolve("api_keys", policy.without_rename(), local, backup_created_at) == Resolution::Skip {
+                continue;
+            }
+            if let Err(e) = db.upsert_api_key(&key.key_name, &key.key_value) {
+                log::warn!("[Restore] Failed to restore key {}: {}", key.key_name, e);
+            } else {
+                result.api_keys += 1;
+            }
+        }
+        if result.api_keys > 0 {
+            log::info!("[Restore] Restored {} API keys", result.api_keys);
         }
-    }
-    if result.api_keys > 0 {
-        log::info!("[Restore] Restored {} API keys", result.api_keys);
     }
 
     // ── 2. Impulse map ──────────────────────────────────────────────────
-    // Clear existing nodes/connections
-    match db.clear_impulse_nodes_for_restore() {
-        Ok((nodes_deleted, connections_deleted)) => {
-            if nodes_deleted > 0 || connections_deleted > 0 {
-                log::info!("[Restore] Cleared {} nodes and {} connections for restore", nodes_deleted, connections_deleted);
+    if selection.impulse_map {
+        // Clear existing nodes/connections
+        match db.clear_impulse_nodes_for_restore() {
+            Ok((nodes_deleted, connections_deleted)) => {
+                if nodes_deleted > 0 || connections_deleted > 0 {
+                    log::info!("[Restore] Cleared {} nodes and {} connections for restore", nodes_deleted, connections_deleted);
+                }
             }
+            Err(e) => log::warn!("[Restore] Failed to clear impulse nodes for restore: {}", e),
         }
-        Err(e) => log::warn!("[Restore] Failed to clear impulse nodes for restore: {}", e),
-    }
 
-    // ID mapping for connections
-    let mut old_to_new_id: HashMap<i64, i64> = HashMap::new();
+        // ID mapping for connections
+        let mut old_to_new_id: HashMap<i64, i64> = HashMap::new();
 
-    // Map trunk node
-    let current_trunk = db.get_or_create_trunk_node().ok();
-    if let Some(ref trunk) = current_trunk {
-        for node in &backup_data.impulse_map_nodes {
-            if node.is_trunk {
-                old_to_new_id.insert(node.id, trunk.id);
-                if !node.body.is_empty() {
-                    let _ = db.update_impulse_node(trunk.id, &crate::db::tables::impulse_nodes::UpdateImpulseNodeRequest {
-                        body: Some(node.body.clone()),
-                        position_x: node.position_x,
-                        position_y: node.position_y,
-                    });
+        // Map trunk node
+        let current_trunk = db.get_or_create_trunk_node().ok();
+        if let Some(ref trunk) = current_trunk {
+            for node in &backup_data.impulse_map_nodes {
+                if node.is_trunk {
+                    old_to_new_id.insert(node.id, trunk.id);
+                    if !node.body.is_empty() {
+                        let _ = db.update_impulse_node(trunk.id, &crate::db::tables::impulse_nodes::UpdateImpulseNodeRequest {
+                            body: Some(node.body.clone()),
+                            position_x: node.position_x,
+                            position_y: node.position_y,
+                        });
+                    }
+                    break;
                 }
-                break;
             }
         }
-    }
 
-    // Create non-trunk nodes
-    for node in &backup_data.impulse_map_nodes {
-        if node.is_trunk { continue; }
-        let request = crate::db::tables::impulse_nodes::CreateImpulseNodeRequest {
-            body: Some(node.body.clone()),
-            position_x: node.position_x,
-            position_y: node.position_y,
-            parent_id: None,
-        };
-        match db.create_impulse_node(&request) {
-            Ok(new_node) => {
-                old_to_new_id.insert(node.id, new_node.id);
-                result.impulse_nodes += 1;
+        // Create non-trunk nodes
+        for node in &backup_data.impulse_map_nodes {
+            if node.is_trunk { continue; }
+            let request = crate::db::tables::impulse_nodes::CreateImpulseNodeRequest {
+                body: Some(node.body.clone()),
+                position_x: node.position_x,
+                position_y: node.position_y,
+                parent_id: None,
+            };
+            match db.create_impulse_node(&request) {
+                Ok(new_node) => {
+                    old_to_new_id.insert(node.id, new_node.id);
+                    result.impulse_nodes += 1;
+                }
+                Err(e) => log::warn!("[Restore] Failed to restore impulse node: {}", e),
             }
-            Err(e) => log::warn!("[Restore] Failed to restore impulse node: {}", e),
         }
-    }
-    if result.impulse_nodes > 0 {
-        log::info!("[Restore] Restored {} impulse map nodes", result.impulse_nodes);
-    }
+        if result.impulse_nodes > 0 {
+            log::info!("[Restore] Restored {} impulse map nodes", result.impulse_nodes);
+        }
 
-    // Connections
-    for conn in &backup_data.impulse_map_connections {
-        if let (Some(&parent_id), Some(&child_id)) = (
-            old_to_new_id.get(&conn.parent_id),
-            old_to_new_id.get(&conn.child_id),
-        ) {
-            match db.create_impulse_node_connection(parent_id, child_id) {
-                Ok(_) => result.impulse_connections += 1,
-                Err(e) => {
-                    if !e.to_string().contains("UNIQUE constraint") {
-                        log::warn!("[Restore] Failed to restore connection: {}", e);
+        // Connections
+        for conn in &backup_data.impulse_map_connections {
+            if let (Some(&parent_id), Some(&child_id)) = (
+                old_to_new_id.get(&conn.parent_id),
+                old_to_new_id.get(&conn.child_id),
+            ) {
+                match db.create_impulse_node_connection(parent_id, child_id) {
+                    Ok(_) => result.impulse_connections += 1,
+                    Err(e) => {
+                        if !e.to_string().contains("UNIQUE constraint") {
+                            log::warn!("[Restore] Failed to restore connection: {}", e);
+                        }
                     }
                 }
             }
         }
-    }
-    if result.impulse_connections > 0 {
-        log::info!("[Restore] Restored {} impulse map connections", result.impulse_connections);
+        if result.impulse_connections > 0 {
+            log::info!("[Restore] Restored {} impulse map connections", result.impulse_connections);
+        }
     }
 
     // ── 3. Bot settings ─────────────────────────────────────────────────
-    if let Some(settings) = &backup_data.bot_settings {
+    if selection.bot_settings && let Some(settings) = &backup_data.bot_settings {
         let custom_rpc: Option<HashMap<String, String>> =
             settings.custom_rpc_endpoints.as_ref().and_then(|s| serde_json::from_str(s).ok());
+        let command_aliases: Option<HashMap<String, String>> =
+            settings.command_aliases.as_ref().and_then(|s| serde_json::from_str(s).ok());
 
         match db.update_bot_settings_full(
             Some(&settings.bot_name),
@@ -200,107 +537,185 @@ pub async fn restore_all(
             None, // Don't restore kanban_auto_execute - keep current setting
             settings.whisper_server_url.as_deref(),
             settings.embeddings_server_url.as_deref(),
+            settings.gas_strategy.as_deref(),
+            settings.gas_max_fee_gwei,
+            settings.command_prefix.as_deref(),
+            command_aliases.as_ref(),
+            settings.gateway_previous_messages,
+            settings.memory_fts_tokenizer.as_deref(),
+            settings.tx_confirmation_timeout_secs,
         ) {
-            Ok(_) => { result.bot_settings = true; log::info!("[Restore] Restored bot settings"); }
+            Ok(_) => {
+                result.bot_settings = true;
+                log::info!("[Restore] Restored bot settings");
+                // Recreate the memory FTS index if the restored tokenizer differs
+                if let Err(e) = db.rebuild_fts_index() {
+                    log::warn!("[Restore] Failed to rebuild memory FTS index: {}", e);
+                }
+            }
             Err(e) => log::warn!("[Restore] Failed to restore bot settings: {}", e),
         }
     }
 
     // ── 4. Channels ─────────────────────────────────────────────────────
-    // Clear existing channels/settings/cron jobs first (API endpoint path does this)
-    let _ = db.clear_channel_settings_for_restore();
-    let _ = db.clear_channels_for_restore();
-    let _ = db.clear_cron_jobs_for_restore();
-
+    // Channels, their settings and cron jobs are restored together: cron jobs
+    // and settings reference channels by ID.
     let mut old_channel_to_new_id: HashMap<i64, i64> = HashMap::new();
-    for channel in &backup_data.channels {
-        match db.create_channel(&channel.channel_type, &channel.name, &channel.bot_token, channel.app_token.as_deref()) {
-            Ok(new_channel) => {
-                old_channel_to_new_id.insert(channel.id, new_channel.id);
-                // Restore enabled state
-                if channel.enabled {
-                    let _ = db.set_channel_enabled(new_channel.id, true);
-                }
-                // Migrate legacy bot_token → channel setting
-                if !channel.bot_token.is_empty() {
-                    let setting_key = match channel.channel_type.as_str() {
-                        "discord" => Some("discord_bot_token"),
-                        "telegram" => Some("telegram_bot_token"),
-                        "slack" => Some("slack_bot_token"),
-                        _ => None,
-                    };
-                    if let Some(key) = setting_key {
-                        let _ = db.set_channel_setting(new_channel.id, key, &channel.bot_token);
+    // Backed-up channel IDs whose local channel was kept (their settings aren't restored)
+    let mut kept_channels: HashSet<i64> = HashSet::new();
+    if selection.channels {
+        let existing_channels = db.list_channels().unwrap_or_default();
+        let existing_cron_jobs = db.list_cron_jobs().unwrap_or_default();
+        if policy.clears_sections() {
+            // Clear existing channels/settings/cron jobs first (API endpoint path does this)
+            let _ = db.clear_channel_settings_for_restore();
+            let _ = db.clear_channels_for_restore();
+            let _ = db.clear_cron_jobs_for_restore();
+        }
+
+        for channel in &backup_data.channels {
+            let found = existing_channels.iter()
+                .find(|c| c.name == channel.name && c.channel_type == channel.channel_type);
+            let mut name = channel.name.clone();
+            match result.resolve("channels", policy, found.map(|c| Some(c.updated_at)), backup_created_at) {
+                Resolution::Skip => {
+                    if let Some(found) = found {
+                        old_channel_to_new_id.insert(channel.id, found.id);
+                        kept_channels.insert(channel.id);
                     }
+                    continue;
                 }
-                // Migrate legacy app_token → channel setting
-                if let Some(ref app_token) = channel.app_token {
-                    if !app_token.is_empty() && channel.channel_type == "slack" {
-                        let _ = db.set_channel_setting(new_channel.id, "slack_app_token", app_token);
+                Resolution::Rename => {
+                    name = unique_name(
+                        |n| restored_name(&channel.name, n),
+                        |candidate| existing_channels.iter().any(|c| c.name == candidate && c.channel_type == channel.channel_type),
+                    );
+                }
+                Resolution::Write => {
+                    if let Some(found) = found && !policy.clears_sections() {
+                        let _ = db.delete_channel(found.id);
                     }
                 }
-                result.channels += 1;
             }
-            Err(e) => {
-                // Channel might already exist — map to existing
-                if let Ok(existing) = db.list_channels() {
-                    if let Some(found) = existing.iter().find(|c| c.name == channel.name && c.channel_type == channel.channel_type) {
-                        old_channel_to_new_id.insert(channel.id, found.id);
-                        log::debug!("[Restore] Channel {} already exists, mapping to existing", channel.name);
-                    } else {
-                        log::warn!("[Restore] Failed to restore channel {}: {}", channel.name, e);
+            match db.create_channel(&channel.channel_type, &name, &channel.bot_token, channel.app_token.as_deref()) {
+                Ok(new_channel) => {
+                    old_channel_to_new_id.insert(channel.id, new_channel.id);
+                    // Restore enabled state
+                    if channel.enabled {
+                        let _ = db.set_channel_enabled(new_channel.id, true);
+                    }
+                    // Migrate legacy bot_token → channel setting
+                    if !channel.bot_token.is_empty() {
+                        let setting_key = match channel.channel_type.as_str() {
+                            "discord" => Some("discord_bot_token"),
+                            "telegram" => Some("telegram_bot_token"),
+                            "slack" => Some("slack_bot_token"),
+                            "matrix" => Some("matrix_access_token"),
+                            _ => None,
+                        };
+                        if let Some(key) = setting_key {
+                            let _ = db.set_channel_setting(new_channel.id, key, &channel.bot_token);
+                        }
+                    }
+                    // Migrate legacy app_token → channel setting
+                    if let Some(ref app_token) = channel.app_token
+                        && !app_token.is_empty() && channel.channel_type == "slack"
+                    {
+                        let _ = db.set_channel_setting(new_channel.id, "slack_app_token", app_token);
+                    }
+                    result.channels += 1;
+                }
+                Err(e) => {
+                    // Channel might already exist — map to existing
+                    if let Ok(existing) = db.list_channels() {
+                        if let Some(found) = existing.iter().find(|c| c.name == channel.name && c.channel_type == channel.channel_type) {
+                            old_channel_to_new_id.insert(channel.id, found.id);
+                            log::debug!("[Restore] Channel {} already exists, mapping to existing", channel.name);
+                        } else {
+                            log::warn!("[Restore] Failed to restore channel {}: {}", channel.name, e);
+                        }
                     }
                 }
             }
         }
-    }
-    if result.channels > 0 {
-        log::info!("[Restore] Restored {} channels", result.channels);
-    }
+        if result.channels > 0 {
+            log::info!("[Restore] Restored {} channels", result.channels);
+        }
 
-    // ── 5. Channel settings ─────────────────────────────────────────────
-    for setting in &backup_data.channel_settings {
-        if let Some(&new_channel_id) = old_channel_to_new_id.get(&setting.channel_id) {
-            match db.set_channel_setting(new_channel_id, &setting.setting_key, &setting.setting_value) {
-                Ok(_) => result.channel_settings += 1,
-                Err(e) => log::warn!("[Restore] Failed to restore channel setting: {}", e),
+        // ── 5. Channel settings ─────────────────────────────────────────
+        for setting in &backup_data.channel_settings {
+            if kept_channels.contains(&setting.channel_id) {
+                continue;
+            }
+            if let Some(&new_channel_id) = old_channel_to_new_id.get(&setting.channel_id) {
+                match db.set_channel_setting(new_channel_id, &setting.setting_key, &setting.setting_value) {
+                    Ok(_) => result.channel_settings += 1,
+                    Err(e) => log::warn!("[Restore] Failed to restore channel setting: {}", e),
+                }
             }
         }
-    }
-    if result.channel_settings > 0 {
-        log::info!("[Restore] Restored {} channel settings", result.channel_settings);
-    }
+        if result.channel_settings > 0 {
+            log::info!("[Restore] Restored {} channel settings", result.channel_settings);
+        }
 
-    // ── 6. Cron jobs ────────────────────────────────────────────────────
-    for job in &backup_data.cron_jobs {
-        let mapped_channel_id = job.channel_id.and_then(|old_id| old_channel_to_new_id.get(&old_id).copied());
-        match db.create_cron_job(
-            &job.name,
-            job.description.as_deref(),
-            &job.schedule_type,
-            &job.schedule_value,
-            job.timezone.as_deref(),
-            &job.session_mode,
-            job.message.as_deref(),
-            job.system_event.as_deref(),
-            mapped_channel_id,
-            job.deliver_to.as_deref(),
-            job.deliver,
-            job.model_override.as_deref(),
-            job.thinking_level.as_deref(),
-            job.timeout_seconds,
-            job.delete_after_run,
-        ) {
-            Ok(_) => result.cron_jobs += 1,
-            Err(e) => log::warn!("[Restore] Failed to restore cron job {}: {}", job.name, e),
+        // ── 6. Cron jobs ────────────────────────────────────────────────
+        for job in &backup_data.cron_jobs {
+            let found = existing_cron_jobs.iter().find(|j| j.name == job.name);
+            let local = found.map(|j| parse_timestamp(&j.updated_at));
+            let mut name = job.name.clone();
+            match result.resolve("cron_jobs", policy, local, backup_created_at) {
+                Resolution::Skip => continue,
+                Resolution::Rename => {
+                    name = unique_name(
+                        |n| restored_name(&job.name, n),
+                        |candidate| existing_cron_jobs.iter().any(|j| j.name == candidate),
+                    );
+                }
+                Resolution::Write => {
+                    if let Some(found) = found && !policy.clears_sections() {
+                        let _ = db.delete_cron_job(found.id);
+                    }
+                }
+            }
+            let mapped_channel_id = job.channel_id.and_then(|old_id| old_channel_to_new_id.get(&old_id).copied());
+            match db.create_cron_job(
+                &name,
+                job.description.as_deref(),
+                &job.schedule_type,
+                &job.schedule_value,
+                job.timezone.as_deref(),
+                &job.session_mode,
+                job.message.as_deref(),
+                job.system_event.as_deref(),
+                mapped_channel_id,
+                job.deliver_to.as_deref(),
+                job.deliver,
+                job.model_override.as_deref(),
+                job.thinking_level.as_deref(),
+                job.timeout_seconds,
+                job.delete_after_run,
+            ) {
+                Ok(_) => result.cron_jobs += 1,
+                Err(e) => log::warn!("[Restore] Failed to restore cron job {}: {}", job.name, e),
+            }
+        }
+        if result.cron_jobs > 0 {
+            log::info!("[Restore] Restored {} cron jobs", result.cron_jobs);
+        }
+    } else {
+        // Channels kept as-is: map backed-up channel IDs to matching local channels
+        // so a restored heartbeat config stays attached
+        if let Ok(existing) = db.list_channels() {
+            for channel in &backup_data.channels {
+                if let Some(found) = existing.iter().find(|c| c.name == channel.name && c.channel_type == channel.channel_type) {
+                    old_channel_to_new_id.insert(channel.id, found.id);
+                }
+            }
         }
-    }
-    if result.cron_jobs > 0 {
-        log::info!("[Restore] Restored {} cron jobs", result.cron_jobs);
     }
 
     // ── 7. Heartbeat config ─────────────────────────────────────────────
-    if let Some(hb_config) = &backup_data.heartbeat_config {
+    if selection.heartbeat && let Some(hb_config) = &backup_data.heartbeat_config {
         let mapped_channel_id = hb_config.channel_id.and_then(|old_id| old_channel_to_new_id.get(&old_id).copied());
         match db.get_or_create_heartbeat_config(mapped_channel_id) {
             Ok(existing) => {
@@ -324,7 +739,7 @@ pub async fn restore_all(
     }
 
     // ── 8. Soul document ────────────────────────────────────────────────
-    if let Some(soul_content) = &backup_data.soul_document {
+    if selection.soul && let Some(soul_content) = &backup_data.soul_document {
         let soul_path = crate::config::soul_document_path();
         if let Some(parent) = soul_path.parent() {
             let _ = std::fs::create_dir_all(parent);
@@ -336,7 +751,7 @@ pub async fn restore_all(
     }
 
     // ── 9. Agent identity ───────────────────────────────────────────────
-    if let Some(ref ai) = backup_data.agent_identity {
+    if selection.identity && let Some(ref ai) = backup_data.agent_identity {
         let conn = db.conn();
         let existing: i64 = conn
             .query_row("SELECT COUNT(*) FROM agent_identity", [], |r| r.get(0))
@@ -368,49 +783,51 @@ pub async fn restore_all(
     }
 
     // Legacy: identity_document → DB migration
-    if !result.agent_identity {
-        if let Some(identity_content) = &backup_data.identity_document {
-            let existing: i64 = db.conn()
-                .query_row("SELECT COUNT(*) FROM agent_identity", [], |r| r.get(0))
-                .unwrap_or(0);
-            if existing == 0 {
-                if let Ok(reg) = serde_json::from_str::<crate::eip8004::types::RegistrationFile>(identity_content) {
-                    let services_json = serde_json::to_string(&reg.services).unwrap_or_else(|_| "[]".to_string());
-                    let supported_trust_json = serde_json::to_string(&reg.supported_trust).unwrap_or_else(|_| "[]".to_string());
-                    match db.upsert_agent_identity(
-                        0, "", 0,
-                        Some(&reg.name), Some(&reg.description), reg.image.as_deref(),
-                        reg.x402_support, reg.active,
-                        &services_json, &supported_trust_json,
-                        None,
-                    ) {
-                        Ok(_) => {
-                            result.agent_identity = true;
-                            log::info!("[Restore] Migrated legacy identity_document to DB");
-                        }
-                        Err(e) => log::warn!("[Restore] Failed to migrate legacy identity_document: {}", e),
-                    }
+    if selection.identity && !result.agent_identity
+        && let Some(identity_content) = &backup_data.identity_document
+    {
+        let existing: i64 = db.conn()
+            .query_row("SELECT COUNT(*) FROM agent_identity", [], |r| r.get(0))
+            .unwrap_or(0);
+        if existing == 0
+            && let Ok(reg) = serde_json::from_str::<crate::eip8004::types::RegistrationFile>(identity_content)
+        {
+            let services_json = serde_json::to_string(&reg.services).unwrap_or_else(|_| "[]".to_string());
+            let supported_trust_json = serde_json::to_string(&reg.supported_trust).unwrap_or_else(|_| "[]".to_string());
+            match db.upsert_agent_identity(
+                0, "", 0,
+                Some(&reg.name), Some(&reg.description), reg.image.as_deref(),
+                reg.x402_support, reg.active,
+                &services_json, &supported_trust_json,
+                None,
+            ) {
+                Ok(_) => {
+                    result.agent_identity = true;
+                    log::info!("[Restore] Migrated legacy identity_document to DB");
                 }
+                Err(e) => log::warn!("[Restore] Failed to migrate legacy identity_document: {}", e),
             }
         }
     }
 
     // ── 10. x402 payment limits ─────────────────────────────────────────
-    for limit in &backup_data.x402_payment_limits {
-        match db.set_x402_payment_limit(&limit.asset, &limit.max_amount, limit.decimals, &limit.display_name, limit.address.as_deref()) {
-            Ok(_) => {
-                crate::x402::payment_limits::set_limit(&limit.asset, &limit.max_amount, limit.decimals, &limit.display_name, limit.address.as_deref());
-                result.x402_limits += 1;
+    if selection.x402_limits {
+        for limit in &backup_data.x402_payment_limits {
+            match db.set_x402_payment_limit(&limit.asset, &limit.max_amount, limit.decimals, &limit.display_name, limit.address.as_deref()) {
+                Ok(_) => {
+                    crate::x402::payment_limits::set_limit(&limit.asset, &limit.max_amount, limit.decimals, &limit.display_name, limit.address.as_deref());
+                    result.x402_limits += 1;
+                }
+                Err(e) => log::warn!("[Restore] Failed to restore x402 payment limit for {}: {}", limit.asset, e),
             }
-            Err(e) => log::warn!("[Restore] Failed to restore x402 payment limit for {}: {}", limit.asset, e),
         }
-    }
-    if result.x402_limits > 0 {
-        log::info!("[Restore] Restored {} x402 payment limits", result.x402_limits);
+        if result.x402_limits > 0 {
+            log::info!("[Restore] Restored {} x402 payment limits", result.x402_limits);
+        }
     }
 
     // ── 11. Module data (generic module.restore_data()) ─────────────────
-    {
+    if selection.modules {
         let module_registry = crate::modules::ModuleRegistry::new();
 
         // Backward-compat shim: legacy discord_registrations → module_data
@@ -449,7 +866,7 @@ pub async fn restore_all(
     }
 
     // ── 12. Modules (folder files → disk) ───────────────────────────────
-    if !backup_data.modules.is_empty() {
+    if selection.modules && !backup_data.modules.is_empty() {
         let runtime_modules_dir = crate::config::runtime_modules_dir();
         std::fs::create_dir_all(&runtime_modules_dir).ok();
 
@@ -523,14 +940,29 @@ pub async fn restore_all(
     }
 
     // ── 13. Skills (folder files → disk) ────────────────────────────────
-    {
+    if selection.skills {
         let runtime_skills_dir = std::path::PathBuf::from(crate::config::runtime_skills_dir());
         std::fs::create_dir_all(&runtime_skills_dir).ok();
 
+        // Skills actually written, with their on-disk name and backed-up enabled state
+        let mut restored_skills: Vec<(String, bool)> = Vec::new();
         for skill_entry in &backup_data.skills {
+            let existing_dir = runtime_skills_dir.join(&skill_entry.name);
+            let local = existing_dir.is_dir()
+                .then(|| modified_at(&existing_dir.join("SKILL.md")).or_else(|| modified_at(&existing_dir)));
+            let name = match result.resolve("skills", policy, local, backup_created_at) {
+                Resolution::Skip => continue,
+                Resolution::Rename => unique_name(
+                    |n| restored_name(&skill_entry.name, n),
+                    |candidate| runtime_skills_dir.join(candidate).exists(),
+                ),
+                Resolution::Write => skill_entry.name.clone(),
+            };
+            restored_skills.push((name.clone(), skill_entry.enabled));
+
             if !skill_entry.folder_files.is_empty() {
                 // New folder-based format
-                let skill_dir = runtime_skills_dir.join(&skill_entry.name);
+                let skill_dir = runtime_skills_dir.join(&name);
                 for file_entry in &skill_entry.folder_files {
                     if file_entry.relative_path.contains("..") || file_entry.relative_path.contains('\0')
                         || file_entry.relative_path.starts_with('/') || file_entry.relative_path.starts_with('\\') {
@@ -541,7 +973,12 @@ pub async fn restore_all(
                     if let Some(parent) = file_path.parent() {
                         std::fs::create_dir_all(parent).ok();
                     }
-                    if let Err(e) = std::fs::write(&file_path, &file_entry.content) {
+                    let content = if name != skill_entry.name && file_entry.relative_path == "SKILL.md" {
+                        set_frontmatter_field(&file_entry.content, "name", &name)
+                    } else {
+                        file_entry.content.clone()
+                    };
+                    if let Err(e) = std::fs::write(&file_path, content) {
                         log::warn!("[Restore] Failed to write skill file {}/{}: {}", skill_entry.name, file_entry.relative_path, e);
                     }
                 }
@@ -554,7 +991,7 @@ pub async fn restore_all(
                     serde_json::from_str(&skill_entry.requires_api_keys).unwrap_or_default();
 
                 let parsed = crate::skills::ParsedSkill {
-                    name: skill_entry.name.clone(),
+                    name: name.clone(),
                     description: skill_entry.description.clone(),
                     body: skill_entry.body.clone(),
                     version: skill_entry.version.clone(),
@@ -604,8 +1041,8 @@ pub async fn restore_all(
                 }
             }
             // Restore enabled/disabled state from backup
-            for skill_entry in &backup_data.skills {
-                registry.set_enabled(&skill_entry.name, skill_entry.enabled);
+            for (name, enabled) in &restored_skills {
+                registry.set_enabled(name, *enabled);
             }
         }
     }
@@ -614,19 +1051,36 @@ pub async fn restore_all(
     }
 
     // ── 14. Agent subtypes (folder files → disk) ────────────────────────
-    {
+    if selection.agent_subtypes {
         let agents_dir = crate::config::runtime_agents_dir();
         std::fs::create_dir_all(&agents_dir).ok();
         for entry in &backup_data.agent_subtypes {
+            let existing_folder = agents_dir.join(&entry.key);
+            let local = existing_folder.is_dir()
+                .then(|| modified_at(&existing_folder.join("agent.md")).or_else(|| modified_at(&existing_folder)));
+            let key = match result.resolve("agent_subtypes", policy, local, backup_created_at) {
+                Resolution::Skip => continue,
+                Resolution::Rename => unique_name(
+                    |n| restored_name(&entry.key, n),
+                    |candidate| agents_dir.join(candidate).exists(),
+                ),
+                Resolution::Write => entry.key.clone(),
+            };
+
             if !entry.folder_files.is_empty() {
-                let agent_folder = agents_dir.join(&entry.key);
+                let agent_folder = agents_dir.join(&key);
                 std::fs::create_dir_all(&agent_folder).ok();
                 for file in &entry.folder_files {
                     let file_path = agent_folder.join(&file.relative_path);
                     if let Some(parent) = file_path.parent() {
                         std::fs::create_dir_all(parent).ok();
                     }
-                    if let Err(e) = std::fs::write(&file_path, &file.content) {
+                    let content = if key != entry.key && file.relative_path == "agent.md" {
+                        set_frontmatter_field(&file.content, "key", &key)
+                    } else {
+                        file.content.clone()
+                    };
+                    if let Err(e) = std::fs::write(&file_path, content) {
                         log::warn!("[Restore] Failed to write agent file {}/{}: {}", entry.key, file.relative_path, e);
                     }
                 }
@@ -638,7 +1092,7 @@ pub async fn restore_all(
                 let additional_tools: Vec<String> = serde_json::from_str(&entry.additional_tools_json).unwrap_or_default();
                 let aliases: Vec<String> = serde_json::from_str(&entry.aliases_json).unwrap_or_default();
                 let config = crate::ai::multi_agent::types::AgentSubtypeConfig {
-                    key: entry.key.clone(),
+                    key: key.clone(),
                     version: String::new(),
                     label: entry.label.clone(),
                     emoji: entry.emoji.clone(),
@@ -651,6 +1105,7 @@ pub async fn restore_all(
                     enabled: entry.enabled,
                     max_iterations: entry.max_iterations.unwrap_or(90) as u32,
                     skip_task_planner: entry.skip_task_planner.unwrap_or(false),
+                    require_plan_approval: entry.require_plan_approval.unwrap_or(false),
                     aliases,
                     hidden: entry.hidden.unwrap_or(false),
                     preferred_ai_model: entry.preferred_ai_model.clone(),
@@ -669,7 +1124,7 @@ pub async fn restore_all(
     }
 
     // ── 15. Agent settings ──────────────────────────────────────────────
-    if !backup_data.agent_settings.is_empty() {
+    if selection.agent_settings && !backup_data.agent_settings.is_empty() {
         if let Err(e) = db.disable_agent_settings() {
             log::warn!("[Restore] Failed to disable existing agent settings for restore: {}", e);
         }
@@ -701,17 +1156,33 @@ pub async fn restore_all(
     }
 
     // ── 16. Kanban items ────────────────────────────────────────────────
-    if !backup_data.kanban_items.is_empty() {
-        // Clear existing kanban items
-        if let Ok(existing) = db.list_kanban_items() {
-            for item in existing {
+    if selection.kanban && !backup_data.kanban_items.is_empty() {
+        let existing_items = db.list_kanban_items().unwrap_or_default();
+        if policy.clears_sections() {
+            // Clear existing kanban items
+            for item in &existing_items {
                 let _ = db.delete_kanban_item(item.id);
             }
         }
 
         for item in &backup_data.kanban_items {
+            let found = existing_items.iter().find(|i| i.title == item.title);
+            let incoming = parse_timestamp(&item.updated_at).unwrap_or(backup_created_at);
+            let title = match result.resolve("kanban", policy, found.map(|i| Some(i.updated_at)), incoming) {
+                Resolution::Skip => continue,
+                Resolution::Rename => unique_name(
+                    |n| restored_name(&item.title, n),
+                    |candidate| existing_items.iter().any(|i| i.title == candidate),
+                ),
+                Resolution::Write => {
+                    if let Some(found) = found && !policy.clears_sections() {
+                        let _ = db.delete_kanban_item(found.id);
+                    }
+                    item.title.clone()
+                }
+            };
             let request = crate::db::tables::kanban::CreateKanbanItemRequest {
-                title: item.title.clone(),
+                title,
                 description: Some(item.description.clone()),
                 priority: Some(item.priority),
             };
@@ -734,40 +1205,52 @@ pub async fn restore_all(
     }
 
     // ── 17. Special roles ───────────────────────────────────────────────
-    for entry in &backup_data.special_roles {
-        let role = crate::models::SpecialRole {
-            name: entry.name.clone(),
-            allowed_tools: serde_json::from_str(&entry.allowed_tools_json).unwrap_or_default(),
-            allowed_skills: serde_json::from_str(&entry.allowed_skills_json).unwrap_or_default(),
-            description: entry.description.clone(),
-            created_at: String::new(),
-            updated_at: String::new(),
-        };
-        match db.upsert_special_role(&role) {
-            Ok(_) => result.special_roles += 1,
-            Err(e) => log::warn!("[Restore] Failed to restore special role '{}': {}", entry.name, e),
+    if selection.special_roles {
+        for entry in &backup_data.special_roles {
+            let local = db.get_special_role(&entry.name).ok().flatten().map(|r| parse_timestamp(&r.updated_at));
+            let name = match result.resolve("special_roles", policy, local, backup_created_at) {
+                Resolution::Skip => continue,
+                Resolution::Rename => unique_name(
+                    |n| restored_name(&entry.name, n),
+                    |candidate| db.get_special_role(candidate).ok().flatten().is_some(),
+                ),
+                Resolution::Write => entry.name.clone(),
+            };
+            let role = crate::models::SpecialRole {
+                name,
+                allowed_tools: serde_json::from_str(&entry.allowed_tools_json).unwrap_or_default(),
+                allowed_skills: serde_json::from_str(&entry.allowed_skills_json).unwrap_or_default(),
+                denied_tools: serde_json::from_str(&entry.denied_tools_json).unwrap_or_default(),
+                description: entry.description.clone(),
+                created_at: String::new(),
+                updated_at: String::new(),
+            };
+            match db.upsert_special_role(&role) {
+                Ok(_) => result.special_roles += 1,
+                Err(e) => log::warn!("[Restore] Failed to restore special role '{}': {}", entry.name, e),
+            }
+        }
+        if result.special_roles > 0 {
+            log::info!("[Restore] Restored {} special roles", result.special_roles);
         }
-    }
-    if result.special_roles > 0 {
-        log::info!("[Restore] Restored {} special roles", result.special_roles);
-    }
 
-    // ── 18. Special role assignments ────────────────────────────────────
-    for entry in &backup_data.special_role_assignments {
-        match db.create_special_role_assignment(&entry.channel_type, &entry.user_id, &entry.special_role_name, entry.label.as_deref()) {
-            Ok(_) => result.special_role_assignments += 1,
-            Err(e) => log::warn!(
-                "[Restore] Failed to restore special role assignment ({}/{} -> {}): {}",
-                entry.channel_type, entry.user_id, entry.special_role_name, e
-            ),
+        // ── 18. Special role assignments ────────────────────────────────────
+        for entry in &backup_data.special_role_assignments {
+            match db.create_special_role_assignment(&entry.channel_type, &entry.user_id, &entry.special_role_name, entry.label.as_deref()) {
+                Ok(_) => result.special_role_assignments += 1,
+                Err(e) => log::warn!(
+                    "[Restore] Failed to restore special role assignment ({}/{} -> {}): {}",
+                    entry.channel_type, entry.user_id, entry.special_role_name, e
+                ),
+            }
+        }
+        if result.special_role_assignments > 0 {
+            log::info!("[Restore] Restored {} special role assignments", result.special_role_assignments);
         }
-    }
-    if result.special_role_assignments > 0 {
-        log::info!("[Restore] Restored {} special role assignments", result.special_role_assignments);
     }
 
     // ── 19. Notes ───────────────────────────────────────────────────────
-    if !backup_data.notes.is_empty() {
+    if selection.notes && !backup_data.notes.is_empty() {
         let notes_dir = std::path::PathBuf::from(crate::config::notes_dir());
         std::fs::create_dir_all(&notes_dir).ok();
 
@@ -776,7 +1259,22 @@ pub async fn restore_all(
                 log::warn!("[Restore] Skipping suspicious note path: {}", note.relative_path);
                 continue;
             }
-            let target = notes_dir.join(&note.relative_path);
+            let mut target = notes_dir.join(&note.relative_path);
+            let local = target.is_file().then(|| modified_at(&target));
+            match result.resolve("notes", policy, local, backup_created_at) {
+                Resolution::Skip => continue,
+                Resolution::Rename => {
+                    let original = target.clone();
+                    let stem = original.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+                    let extension = original.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+                    let file_name = unique_name(
+                        |n| format!("{}{}", restored_name(&stem, n), extension),
+                        |candidate| original.with_file_name(candidate).exists(),
+                    );
+                    target = original.with_file_name(file_name);
+                }
+                Resolution::Write => {}
+            }
             if let Some(parent) = target.parent() {
                 let _ = std::fs::create_dir_all(parent);
             }
@@ -798,50 +1296,32 @@ pub async fn restore_all(
     }
 
     // ── 20. Memories ────────────────────────────────────────────────────
-    if let Some(ref memories) = backup_data.memories {
-        match db.clear_memories_for_restore() {
-            Ok(deleted) => {
-                if deleted > 0 {
-                    log::info!("[Restore] Cleared {} memories for restore", deleted);
+    if selection.memories && let Some(ref memories) = backup_data.memories {
+        let existing_memories = db.list_all_memories().unwrap_or_default();
+        if policy.clears_sections() {
+            match db.clear_memories_for_restore() {
+                Ok(deleted) => {
+                    if deleted > 0 {
+                        log::info!("[Restore] Cleared {} memories for restore", deleted);
+                    }
                 }
+                Err(e) => log::warn!("[Restore] Failed to clear memories for restore: {}", e),
             }
-            Err(e) => log::warn!("[Restore] Failed to clear memories for restore: {}", e),
         }
 
         for mem in memories {
-            let insert_result = if !mem.created_at.is_empty() {
-                db.insert_memory_with_created_at(
-                    &mem.memory_type,
-                    &mem.content,
-                    mem.category.as_deref(),
-                    mem.tags.as_deref(),
-                    mem.importance.unwrap_or(5) as i64,
-                    mem.identity_id.as_deref(),
-                    None,
-                    mem.entity_type.as_deref(),
-                    mem.entity_name.as_deref(),
-                    mem.source_type.as_deref(),
-                    mem.log_date.as_deref(),
-                    &mem.created_at,
-                    mem.agent_subtype.as_deref(),
-                )
-            } else {
-                db.insert_memory(
-                    &mem.memory_type,
-                    &mem.content,
-                    mem.category.as_deref(),
-                    mem.tags.as_deref(),
-                    mem.importance.unwrap_or(5) as i64,
-                    mem.identity_id.as_deref(),
-                    None,
-                    mem.entity_type.as_deref(),
-                    mem.entity_name.as_deref(),
-                    mem.source_type.as_deref(),
-                    mem.log_date.as_deref(),
-                    mem.agent_subtype.as_deref(),
-                )
-            };
-            match insert_result {
+            // A memory is identified by its content, so keep-both would only duplicate it
+            let found = existing_memories.iter()
+                .find(|m| m.memory_type == mem.memory_type && m.content == mem.content);
+            let incoming = parse_timestamp(&mem.created_at).unwrap_or(backup_created_at);
+            let local = found.map(|m| parse_timestamp(&m.updated_at));
+            if result.resolve("memories", policy.without_rename(), local, incoming) == Resolution::Skip {
+                continue;
+            }
+            if let Some(found) = found && !policy.clears_sections() {
+                let _ = db.delete_memory(found.id);
+            }
+            match mem.insert(db) {
                 Ok(_) => result.memories += 1,
                 Err(e) => log::warn!("[Restore] Failed to restore memory: {}", e),
             }
@@ -858,21 +1338,28 @@ pub async fn restore_all(
     }
 
     // ── 21. Tool configs (gogcli etc.) ──────────────────────────────────
-    restore_tool_configs(backup_data);
+    if selection.tool_configs {
+        result.tool_configs = restore_tool_configs(backup_data, disk_quota);
+    }
 
     // ── 23. Auto-start channels ─────────────────────────────────────────
-    if let Some(cm) = channel_manager {
-        let mut auto_started = 0;
-        for (_old_id, &new_id) in &old_channel_to_new_id {
-            let should_auto_start = db
-                .get_channel_setting(new_id, "auto_start_on_boot")
-                .ok()
-                .flatten()
-                .map(|v| v == "true")
-                .unwrap_or(false);
-
-            if should_auto_start {
-                if let Ok(Some(channel)) = db.get_channel(new_id) {
+    if selection.channels {
+        if let Some(cm) = channel_manager {
+            let mut auto_started = 0;
+            for (old_id, &new_id) in &old_channel_to_new_id {
+                if kept_channels.contains(old_id) {
+                    continue;
+                }
+                let should_auto_start = db
+                    .get_channel_setting(new_id, "auto_start_on_boot")
+                    .ok()
+                    .flatten()
+                    .map(|v| v == "true")
+                    .unwrap_or(false);
+
+                if should_auto_start
+                    && let Ok(Some(channel)) = db.get_channel(new_id)
+                {
                     match cm.start_channel(channel).await {
                         Ok(_) => {
                             auto_started += 1;
@@ -882,22 +1369,25 @@ pub async fn restore_all(
                     }
                 }
             }
-        }
-        if auto_started > 0 {
-            log::info!("[Restore] Auto-started {} channels after restore", auto_started);
-        }
-    } else {
-        // No channel manager — just enable channels with auto_start_on_boot so gateway.start_enabled_channels() picks them up
-        for &new_channel_id in old_channel_to_new_id.values() {
-            let should_auto_start = db
-                .get_channel_setting(new_channel_id, "auto_start_on_boot")
-                .ok()
-                .flatten()
-                .map(|v| v == "true")
-                .unwrap_or(false);
-
-            if should_auto_start {
-                if let Err(e) = db.set_channel_enabled(new_channel_id, true) {
+            if auto_started > 0 {
+                log::info!("[Restore] Auto-started {} channels after restore", auto_started);
+            }
+        } else {
+            // No channel manager — just enable channels with auto_start_on_boot so gateway.start_enabled_channels() picks them up
+            for (old_id, &new_channel_id) in &old_channel_to_new_id {
+                if kept_channels.contains(old_id) {
+                    continue;
+                }
+                let should_auto_start = db
+                    .get_channel_setting(new_channel_id, "auto_start_on_boot")
+                    .ok()
+                    .flatten()
+                    .map(|v| v == "true")
+                    .unwrap_or(false);
+
+                if should_auto_start
+                    && let Err(e) = db.set_channel_enabled(new_channel_id, true)
+                {
                     log::warn!("[Restore] Failed to enable auto-start channel {}: {}", new_channel_id, e);
                 }
             }
@@ -909,34 +1399,57 @@ pub async fn restore_all(
 }
 
 /// Restore tool config directories from backup (e.g. gogcli auth tokens).
-fn restore_tool_configs(backup_data: &BackupData) {
-    use base64::Engine;
-    let engine = base64::engine::general_purpose::STANDARD;
-
+/// Returns the number of files written.
+fn restore_tool_configs(backup_data: &BackupData, disk_quota: Option<&Arc<DiskQuotaManager>>) -> usize {
     let home = match std::env::var("HOME") {
         Ok(h) => std::path::PathBuf::from(h),
         Err(_) => {
             log::warn!("[Restore] HOME not set, skipping tool config restore");
-            return;
+            return 0;
         }
     };
+    restore_tool_configs_to(&home, &backup_data.tool_configs, disk_quota)
+}
 
-    for (tool_name, files) in &backup_data.tool_configs {
-        let base_dir = match tool_name.as_str() {
-            "gogcli" => home.join(".config").join("gogcli"),
-            other => {
-                log::warn!("[Restore] Unknown tool config '{}', skipping", other);
-                continue;
-            }
+/// Write backed-up tool config files into their directories under `home`,
+/// creating directories as needed. Files that would exceed the disk quota are skipped.
+fn restore_tool_configs_to(
+    home: &std::path::Path,
+    tool_configs: &HashMap<String, Vec<ToolConfigFileEntry>>,
+    disk_quota: Option<&Arc<DiskQuotaManager>>,
+) -> usize {
+    use base64::Engine;
+    let engine = base64::engine::general_purpose::STANDARD;
+
+    let mut total = 0;
+    for (tool_name, files) in tool_configs {
+        let Some(base_dir) = crate::backup::tool_config_dir(home, tool_name) else {
+            log::warn!("[Restore] Unknown tool config '{}', skipping", tool_name);
+            continue;
         };
 
         let mut restored = 0;
         for file_entry in files {
-            if file_entry.relative_path.contains("..") {
+            if file_entry.relative_path.contains("..") || file_entry.relative_path.contains('\0')
+                || file_entry.relative_path.starts_with('/') || file_entry.relative_path.starts_with('\\') {
                 log::warn!("[Restore] Skipping suspicious path in tool config: {}", file_entry.relative_path);
                 continue;
             }
 
+            let content = match engine.decode(&file_entry.content_b64) {
+                Ok(content) => content,
+                Err(e) => {
+                    log::warn!("[Restore] Failed to decode tool config {}: {}", file_entry.relative_path, e);
+                    continue;
+                }
+            };
+            if let Some(dq) = disk_quota
+                && let Err(e) = dq.check_quota(content.len() as u64)
+            {
+                log::warn!("[Restore] Skipping tool config {}: {}", file_entry.relative_path, e);
+                continue;
+            }
+
             let target = base_dir.join(&file_entry.relative_path);
             if let Some(parent) = target.parent() {
                 if let Err(e) = std::fs::create_dir_all(parent) {
@@ -944,20 +1457,261 @@ fn restore_tool_configs(backup_data: &BackupData) {
                     continue;
                 }
             }
-
-            match engine.decode(&file_entry.content_b64) {
-                Ok(content) => {
-                    match std::fs::write(&target, &content) {
-                        Ok(_) => restored += 1,
-                        Err(e) => log::warn!("[Restore] Failed to write tool config {}: {}", file_entry.relative_path, e),
+            match std::fs::write(&target, &content) {
+                Ok(_) => {
+                    if let Some(dq) = disk_quota {
+                        dq.record_write(content.len() as u64);
                     }
+                    restored += 1;
                 }
-                Err(e) => log::warn!("[Restore] Failed to decode tool config {}: {}", file_entry.relative_path, e),
+                Err(e) => log::warn!("[Restore] Failed to write tool config {}: {}", file_entry.relative_path, e),
             }
         }
 
         if restored > 0 {
             log::info!("[Restore] Restored {} config files for tool '{}'", restored, tool_name);
         }
+        total += restored;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backup::{ApiKeyEntry, ChannelEntry, KanbanItemEntry, MemoryEntry, SkillEntry, SkillFileEntry};
+
+    fn sample_backup() -> BackupData {
+        let mut backup = BackupData::new("0xtest".to_string());
+        backup.api_keys = vec![ApiKeyEntry { key_name: "GITHUB_TOKEN".to_string(), key_value: "ghp_test".to_string() }];
+        backup.channels = vec![ChannelEntry {
+            id: 7,
+            channel_type: "discord".to_string(),
+            name: "restored-channel".to_string(),
+            ..Default::default()
+        }];
+        backup.skills = vec![SkillEntry {
+            name: "restored-skill".to_string(),
+            enabled: true,
+            folder_files: vec![SkillFileEntry {
+                relative_path: "SKILL.md".to_string(),
+                content: "---\nname: restored-skill\n---\nbody".to_string(),
+            }],
+            ..Default::default()
+        }];
+        backup.memories = Some(vec![MemoryEntry {
+            memory_type: "long_term".to_string(),
+            content: "from backup".to_string(),
+            ..Default::default()
+        }]);
+        backup
+    }
+
+    fn seed_local_memory(db: &Database) {
+        db.insert_memory("long_term", "local memory", None, None, 5, None, None, None, None, None, None, None)
+            .unwrap();
+    }
+
+    #[test]
+    fn selection_parse() {
+        assert!(RestoreSelection::parse("").unwrap().is_all());
+        assert!(RestoreSelection::parse("all").unwrap().is_all());
+
+        let selection = RestoreSelection::parse(" Skills , channels ").unwrap();
+        assert_eq!(selection.sections(), vec!["channels", "skills"]);
+        assert!(RestoreSelection::parse("skills,bogus").unwrap_err().contains("bogus"));
+    }
+
+    #[tokio::test]
+    async fn channels_only_restore_leaves_other_sections_untouched() {
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        seed_local_memory(&db);
+
+        let mut backup = sample_backup();
+        let selection = RestoreSelection { channels: true, ..RestoreSelection::none() };
+        let result = restore_all(&db, &mut backup, None, None, None, None, &selection, ConflictPolicy::Overwrite).await.unwrap();
+
+        assert_eq!(result.channels, 1);
+        assert_eq!(result.skills, 0);
+        assert_eq!(result.memories, 0);
+        assert_eq!(result.api_keys, 0);
+
+        let channels = db.list_channels().unwrap();
+        assert!(channels.iter().any(|c| c.name == "restored-channel"));
+        let memories = db.list_all_memories().unwrap();
+        assert_eq!(memories.len(), 1);
+        assert_eq!(memories[0].content, "local memory");
+        assert!(db.get_api_key("GITHUB_TOKEN").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn skills_only_restore_leaves_other_sections_untouched() {
+        let skills_dir = tempfile::tempdir().unwrap();
+        // Only this test restores skills, so pointing the runtime dir at a temp dir is safe
+        unsafe { std::env::set_var(crate::config::env_vars::RUNTIME_SKILLS_DIR, skills_dir.path()); }
+
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        seed_local_memory(&db);
+
+        let mut backup = sample_backup();
+        let selection = RestoreSelection { skills: true, ..RestoreSelection::none() };
+        let result = restore_all(&db, &mut backup, None, None, None, None, &selection, ConflictPolicy::Overwrite).await.unwrap();
+
+        assert_eq!(result.skills, 1);
+        assert_eq!(result.channels, 0);
+        assert_eq!(result.memories, 0);
+        assert!(skills_dir.path().join("restored-skill").join("SKILL.md").exists());
+
+        assert!(db.list_channels().unwrap().is_empty());
+        let memories = db.list_all_memories().unwrap();
+        assert_eq!(memories.len(), 1);
+        assert_eq!(memories[0].content, "local memory");
+        assert!(db.get_api_key("GITHUB_TOKEN").unwrap().is_none());
+    }
+
+    fn channels_and_keys() -> RestoreSelection {
+        RestoreSelection { api_keys: true, channels: true, ..RestoreSelection::none() }
+    }
+
+    #[test]
+    fn conflict_policy_parse() {
+        assert_eq!(ConflictPolicy::parse("").unwrap(), ConflictPolicy::Overwrite);
+        assert_eq!(ConflictPolicy::parse("keep_both").unwrap(), ConflictPolicy::KeepBoth);
+        assert_eq!(ConflictPolicy::parse("rename").unwrap(), ConflictPolicy::KeepBoth);
+        assert_eq!(ConflictPolicy::parse("Newer-Wins").unwrap(), ConflictPolicy::NewerWins);
+        assert!(ConflictPolicy::parse("merge").is_err());
+    }
+
+    #[test]
+    fn frontmatter_field_is_replaced_only_in_frontmatter() {
+        let content = "---\nname: weather\ndescription: x\n---\nname: body text\n";
+        assert_eq!(
+            set_frontmatter_field(content, "name", "weather-restored"),
+            "---\nname: weather-restored\ndescription: x\n---\nname: body text\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn skip_policy_keeps_local_items() {
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        db.upsert_api_key("GITHUB_TOKEN", "local").unwrap();
+        db.create_channel("discord", "restored-channel", "", None).unwrap();
+
+        let mut backup = sample_backup();
+        let result = restore_all(&db, &mut backup, None, None, None, None, &channels_and_keys(), ConflictPolicy::Skip)
+            .await
+            .unwrap();
+
+        assert_eq!(db.get_api_key("GITHUB_TOKEN").unwrap().unwrap().api_key, "local");
+        assert_eq!(db.list_channels().unwrap().len(), 1);
+        assert_eq!(result.conflicts["api_keys"], ConflictCounts { skipped: 1, ..Default::default() });
+        assert_eq!(result.conflicts["channels"], ConflictCounts { skipped: 1, ..Default::default() });
+        assert!(result.summary().contains("channels (1 skipped)"));
+    }
+
+    #[tokio::test]
+    async fn keep_both_policy_renames_channels_and_keeps_unrelated_ones() {
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        db.upsert_api_key("GITHUB_TOKEN", "local").unwrap();
+        db.create_channel("discord", "restored-channel", "", None).unwrap();
+        db.create_channel("telegram", "other", "", None).unwrap();
+
+        let mut backup = sample_backup();
+        let result = restore_all(&db, &mut backup, None, None, None, None, &channels_and_keys(), ConflictPolicy::KeepBoth)
+            .await
+            .unwrap();
+
+        let mut names: Vec<String> = db.list_channels().unwrap().into_iter().map(|c| c.name).collect();
+        names.sort();
+        assert_eq!(names, vec!["other", "restored-channel", "restored-channel-restored"]);
+        assert_eq!(result.conflicts["channels"], ConflictCounts { renamed: 1, ..Default::default() });
+        // API keys can't be renamed, so keep-both leaves the local key
+        assert_eq!(db.get_api_key("GITHUB_TOKEN").unwrap().unwrap().api_key, "local");
+        assert_eq!(result.conflicts["api_keys"], ConflictCounts { skipped: 1, ..Default::default() });
+    }
+
+    #[tokio::test]
+    async fn newer_wins_policy_compares_timestamps() {
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        for title in ["Stale in backup", "Fresh in backup"] {
+            db.create_kanban_item(&crate::db::tables::kanban::CreateKanbanItemRequest {
+                title: title.to_string(),
+                description: Some("local".to_string()),
+                priority: None,
+            })
+            .unwrap();
+        }
+
+        let mut backup = BackupData::new("0xtest".to_string());
+        backup.kanban_items = vec![
+            KanbanItemEntry {
+                title: "Stale in backup".to_string(),
+                description: "backup".to_string(),
+                status: "todo".to_string(),
+                updated_at: "2020-01-01T00:00:00Z".to_string(),
+                ..Default::default()
+            },
+            KanbanItemEntry {
+                title: "Fresh in backup".to_string(),
+                description: "backup".to_string(),
+                status: "todo".to_string(),
+                updated_at: "2999-01-01T00:00:00Z".to_string(),
+                ..Default::default()
+            },
+        ];
+        let selection = RestoreSelection { kanban: true, ..RestoreSelection::none() };
+        let result = restore_all(&db, &mut backup, None, None, None, None, &selection, ConflictPolicy::NewerWins)
+            .await
+            .unwrap();
+
+        let items = db.list_kanban_items().unwrap();
+        assert_eq!(items.len(), 2);
+        let description = |title: &str| items.iter().find(|i| i.title == title).unwrap().description.clone();
+        assert_eq!(description("Stale in backup"), "local");
+        assert_eq!(description("Fresh in backup"), "backup");
+        assert_eq!(result.conflicts["kanban"], ConflictCounts { overwritten: 1, skipped: 1, renamed: 0 });
+    }
+
+    #[test]
+    fn tool_configs_round_trip() {
+        let source_home = tempfile::tempdir().unwrap();
+        let gogcli_dir = source_home.path().join(".config/gogcli");
+        std::fs::create_dir_all(gogcli_dir.join("keyring")).unwrap();
+        std::fs::write(gogcli_dir.join("config.json"), br#"{"account":"me@example.com"}"#).unwrap();
+        std::fs::write(gogcli_dir.join("keyring/token.bin"), [0u8, 159, 146, 150]).unwrap();
+
+        let configs = crate::backup::collect_tool_configs_from(source_home.path());
+        assert_eq!(configs["gogcli"].len(), 2);
+
+        let target_home = tempfile::tempdir().unwrap();
+        assert_eq!(restore_tool_configs_to(target_home.path(), &configs, None), 2);
+        let restored_dir = target_home.path().join(".config/gogcli");
+        assert_eq!(
+            std::fs::read(restored_dir.join("config.json")).unwrap(),
+            br#"{"account":"me@example.com"}"#
+        );
+        assert_eq!(std::fs::read(restored_dir.join("keyring/token.bin")).unwrap(), [0u8, 159, 146, 150]);
+    }
+
+    #[test]
+    fn tool_configs_restore_skips_unsafe_paths_and_respects_quota() {
+        use base64::Engine;
+        let encode = |bytes: &[u8]| base64::engine::general_purpose::STANDARD.encode(bytes);
+        let configs = HashMap::from([(
+            "gogcli".to_string(),
+            vec![
+                ToolConfigFileEntry { relative_path: "/tmp/escape.json".to_string(), content_b64: encode(b"{}") },
+                ToolConfigFileEntry { relative_path: "../escape.json".to_string(), content_b64: encode(b"{}") },
+                ToolConfigFileEntry { relative_path: "large.bin".to_string(), content_b64: encode(&vec![0u8; 2 * 1024 * 1024]) },
+                ToolConfigFileEntry { relative_path: "small.json".to_string(), content_b64: encode(b"{}") },
+            ],
+        )]);
+        let quota = Arc::new(DiskQuotaManager::new(Some(1), Vec::new()));
+
+        let home = tempfile::tempdir().unwrap();
+        assert_eq!(restore_tool_configs_to(home.path(), &configs, Some(&quota)), 1);
+        assert!(home.path().join(".config/gogcli/small.json").exists());
+        assert!(!home.path().join(".config/gogcli/large.bin").exists());
+        assert_eq!(quota.usage_bytes(), 2);
     }
 }
@@ -0,0 +1,150 @@
+//! Scheduled cloud backups
+//!
+//! The scheduler pushes an encrypted backup to the keystore every
+//! `STARK_AUTO_BACKUP_INTERVAL_HOURS`. Each upload stores a fingerprint of the
+//! backed-up data; when the next run produces the same fingerprint the upload
+//! is skipped, so an idle bot doesn't rewrite the same backup every interval.
+
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+use super::{collect_backup_data, encrypt_with_private_key, BackupData};
+use crate::db::Database;
+use crate::keystore_client::KEYSTORE_CLIENT;
+use crate::wallet::WalletProvider;
+
+/// What a scheduled backup run did
+#[derive(Debug, PartialEq, Eq)]
+pub enum ScheduledBackupOutcome {
+    Uploaded { item_count: usize },
+    /// Same data as the last scheduled backup
+    Unchanged,
+    /// Nothing to back up
+    Empty,
+}
+
+/// SHA-256 of the backup contents, ignoring its creation time.
+/// serde_json objects are key-sorted, so HashMap fields hash deterministically.
+pub fn backup_fingerprint(backup: &BackupData) -> String {
+    let mut value = serde_json::to_value(backup).unwrap_or_default();
+    if let Some(obj) = value.as_object_mut() {
+        obj.remove("created_at");
+    }
+    format!("{:x}", Sha256::digest(value.to_string().as_bytes()))
+}
+
+/// Back up to the keystore if the data changed since the last scheduled backup,
+/// recording the result as the wallet's auto-sync status.
+pub async fn run_scheduled_backup(
+    db: &Database,
+    wallet_provider: &Arc<dyn WalletProvider>,
+) -> Result<ScheduledBackupOutcome, String> {
+    let wallet_address = wallet_provider.get_address();
+    let result = upload_if_changed(db, wallet_provider, &wallet_address).await;
+
+    match &result {
+        Ok(ScheduledBackupOutcome::Uploaded { item_count }) => {
+            log::info!("[AutoBackup] Uploaded scheduled backup ({} items)", item_count);
+            let _ = db.record_auto_sync_result(
+                &wallet_address,
+                "backed_up",
+                &format!("Scheduled backup uploaded ({} items)", item_count),
+                None,
+                None,
+            );
+        }
+        Ok(ScheduledBackupOutcome::Unchanged) => {
+            log::debug!("[AutoBackup] No changes since the last backup, skipping upload");
+        }
+        Ok(ScheduledBackupOutcome::Empty) => {
+            log::debug!("[AutoBackup] Nothing to back up");
+        }
+        Err(e) => {
+            log::error!("[AutoBackup] Scheduled backup failed: {}", e);
+            let _ = db.record_auto_sync_result(
+                &wallet_address,
+                "backup_error",
+                &format!("Scheduled backup failed: {}", e),
+                None,
+                None,
+            );
+        }
+    }
+    result
+}
+
+async fn upload_if_changed(
+    db: &Database,
+    wallet_provider: &Arc<dyn WalletProvider>,
+    wallet_address: &str,
+) -> Result<ScheduledBackupOutcome, String> {
+    let backup = collect_backup_data(db, wallet_address.to_string()).await;
+    if backup.is_empty() {
+        return Ok(ScheduledBackupOutcome::Empty);
+    }
+
+    let fingerprint = backup_fingerprint(&backup);
+    let last = db
+        .get_last_keystore_backup_hash(wallet_address)
+        .map_err(|e| format!("Failed to read backup state: {}", e))?;
+    if last.as_deref() == Some(fingerprint.as_str()) {
+        return Ok(ScheduledBackupOutcome::Unchanged);
+    }
+
+    let item_count = backup.item_count();
+    let private_key = wallet_provider
+        .get_encryption_key()
+        .await
+        .map_err(|e| format!("Failed to get encryption key: {}", e))?;
+    let backup_json =
+        serde_json::to_string(&backup).map_err(|e| format!("Failed to serialize backup: {}", e))?;
+    let encrypted_data = encrypt_with_private_key(&private_key, &backup_json)
+        .map_err(|e| format!("Failed to encrypt backup: {}", e))?;
+
+    let resp = KEYSTORE_CLIENT
+        .store_keys_with_provider(wallet_provider, &encrypted_data, item_count)
+        .await?;
+    if !resp.success {
+        return Err(resp.error.unwrap_or_else(|| "Unknown error".to_string()));
+    }
+
+    db.record_keystore_backup(wallet_address, backup.version, item_count)
+        .and_then(|_| db.set_last_keystore_backup_hash(wallet_address, &fingerprint))
+        .map_err(|e| format!("Backup uploaded but failed to record it: {}", e))?;
+    Ok(ScheduledBackupOutcome::Uploaded { item_count })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn fingerprint_ignores_created_at_and_tracks_data() {
+        let db = Database::new(":memory:").unwrap();
+        db.insert_memory("long_term", "User prefers Rust", None, None, 5, None, None, None, None, None, None, None)
+            .unwrap();
+
+        let first = collect_backup_data(&db, "0xtest".to_string()).await;
+        let mut second = collect_backup_data(&db, "0xtest".to_string()).await;
+        second.created_at = first.created_at + chrono::Duration::hours(1);
+        assert_eq!(backup_fingerprint(&first), backup_fingerprint(&second));
+
+        db.insert_memory("long_term", "User lives in Lisbon", None, None, 5, None, None, None, None, None, None, None)
+            .unwrap();
+        let changed = collect_backup_data(&db, "0xtest".to_string()).await;
+        assert_ne!(backup_fingerprint(&first), backup_fingerprint(&changed));
+    }
+
+    #[test]
+    fn last_backup_hash_round_trips() {
+        let db = Database::new(":memory:").unwrap();
+        assert_eq!(db.get_last_keystore_backup_hash("0xAbC").unwrap(), None);
+        assert!(db.get_last_keystore_backup_at("0xabc").unwrap().is_none());
+
+        db.record_keystore_backup("0xAbC", 1, 3).unwrap();
+        db.set_last_keystore_backup_hash("0xAbC", "deadbeef").unwrap();
+        assert_eq!(db.get_last_keystore_backup_hash("0xAbC").unwrap().as_deref(), Some("deadbeef"));
+        // Manual backups may store a checksummed address; the timestamp lookup ignores case
+        assert!(db.get_last_keystore_backup_at("0xabc").unwrap().is_some());
+    }
+}
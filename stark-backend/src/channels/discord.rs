@@ -6,10 +6,11 @@ use crate::db::Database;
 use crate::discord_hooks;
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::GatewayEvent;
+use crate::models::channel_settings::ChannelSettingKey;
 use crate::models::{Channel, ToolOutputVerbosity};
 use serenity::all::{
-    Client, Context, CreateEmbed, CreateMessage, EditMessage, EventHandler, GatewayIntents,
-    GetMessages, Message, MessageId, Ready, UserId,
+    ChannelId, Client, Context, CreateEmbed, CreateMessage, CreateThread, EditMessage, EventHandler,
+    GatewayIntents, GetMessages, Message, MessageId, Ready, UserId,
 };
 use std::sync::Arc;
 use tokio::sync::oneshot;
@@ -182,7 +183,7 @@ impl EventHandler for DiscordHandler {
             Ok(result) => {
                 // If module handled it with a direct response, send it and return
                 if let Some(response) = result.response {
-                    let chunks = util::split_message(&response, 2000);
+                    let chunks = util::split_message(&response, ChannelType::Discord.max_message_len());
                     for chunk in chunks {
                         if let Err(e) = msg.channel_id.say(&ctx.http, &chunk).await {
                             log::error!("Discord: Failed to send hooks response: {}", e);
@@ -281,14 +282,24 @@ impl EventHandler for DiscordHandler {
                     let chat_context = if recent_context.is_empty() { None } else { Some(recent_context) };
 
                     // Get channel name for context
-                    let channel_name = msg.channel_id.to_channel(&ctx.http).await.ok().and_then(|ch| {
-                        ch.guild().map(|gc| gc.name().to_string())
-                    });
+                    let guild_channel = msg.channel_id.to_channel(&ctx.http).await.ok().and_then(|ch| ch.guild());
+                    let channel_name = guild_channel.as_ref().map(|gc| gc.name().to_string());
+
+                    // Messages inside a thread are answered there; otherwise optionally start one
+                    let thread_id = match guild_channel.as_ref() {
+                        Some(gc) if gc.thread_metadata.is_some() => Some(msg.channel_id),
+                        Some(_) if self.reply_in_thread() => {
+                            self.start_reply_thread(&ctx, &msg, &forward.text).await
+                        }
+                        _ => None,
+                    };
+                    // chat_id follows the channel replies go to, so event routing matches
+                    let reply_channel = thread_id.unwrap_or(msg.channel_id);
 
                     let normalized = NormalizedMessage {
                         channel_id: self.channel_id,
                         channel_type: ChannelType::Discord.to_string(),
-                        chat_id: msg.channel_id.to_string(),
+                        chat_id: reply_channel.to_string(),
                         chat_name: channel_name,
                         user_id,
                         user_name: user_name.clone(),
@@ -299,9 +310,10 @@ impl EventHandler for DiscordHandler {
                         force_safe_mode: forward.force_safe_mode,
                         platform_role_ids: forward.platform_role_ids,
                         chat_context,
+                        thread_id: thread_id.map(|id| id.to_string()),
                     };
 
-                    self.dispatch_and_respond(&ctx, &msg, normalized, &user_name).await;
+                    self.dispatch_and_respond(&ctx, &msg, reply_channel, normalized, &user_name).await;
                     return;
                 }
 
@@ -371,11 +383,47 @@ impl EventHandler for DiscordHandler {
 }
 
 impl DiscordHandler {
-    /// Dispatch a message to the AI and send the response
+    /// Whether the channel is configured to answer in a thread per message
+    fn reply_in_thread(&self) -> bool {
+        self.db
+            .get_channel_setting(self.channel_id, ChannelSettingKey::DiscordReplyInThread.as_ref())
+            .ok()
+            .flatten()
+            .map(|v| v == "true")
+            .unwrap_or(false)
+    }
+
+    /// Start a thread from the user's message. Returns None (reply in the
+    /// channel) if the thread can't be created, e.g. missing permissions or a DM.
+    async fn start_reply_thread(&self, ctx: &Context, msg: &Message, text: &str) -> Option<ChannelId> {
+        // Thread names are limited to 100 characters
+        let mut name: String = text.lines().next().unwrap_or("").chars().take(90).collect();
+        if name.trim().is_empty() {
+            name = format!("Reply to {}", msg.author.name);
+        }
+        match msg
+            .channel_id
+            .create_thread_from_message(&ctx.http, msg.id, CreateThread::new(name.trim()))
+            .await
+        {
+            Ok(thread) => {
+                log::info!("Discord: Started reply thread {} for message {}", thread.id, msg.id);
+                Some(thread.id)
+            }
+            Err(e) => {
+                log::warn!("Discord: Failed to start reply thread, replying in channel: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Dispatch a message to the AI and send the response to `reply_channel`
+    /// (the originating channel, or the thread the reply belongs in)
     async fn dispatch_and_respond(
         &self,
         ctx: &Context,
         msg: &Message,
+        reply_channel: ChannelId,
         normalized: NormalizedMessage,
         user_name: &str,
     ) {
@@ -387,7 +435,7 @@ impl DiscordHandler {
 
         // Clone context and channel info for the event forwarder task
         let http = ctx.http.clone();
-        let discord_channel_id = msg.channel_id;
+        let discord_channel_id = reply_channel;
         let channel_id_for_events = self.channel_id;
         // Convert Discord channel ID to string for event filtering
         let chat_id_for_events = discord_channel_id.to_string();
@@ -519,6 +567,7 @@ impl DiscordHandler {
                             Some(format!("{} Task {}", emoji, status))
                         }
                     }
+                    "plan.approval_required" => Some(util::format_plan_approval(&event.data)),
                     _ => None,
                 };
 
@@ -575,7 +624,25 @@ impl DiscordHandler {
 
         // Dispatch to AI
         log::info!("Discord: Dispatching message to AI for user {}", user_name);
+        let http_for_typing = ctx.http.clone();
+        let typing_channel = reply_channel;
+        let typing = util::TypingIndicator::start(
+            &self.broadcaster,
+            ChannelType::Discord,
+            self.channel_id,
+            &reply_channel.to_string(),
+            move |typing| {
+                let http = http_for_typing.clone();
+                async move {
+                    if !typing {
+                        return Ok(());
+                    }
+                    typing_channel.broadcast_typing(&http).await.map_err(|e| e.to_string())
+                }
+            },
+        );
         let result = self.dispatcher.dispatch_safe(normalized).await;
+        drop(typing);
         log::info!("Discord: Dispatch complete, error={:?}", result.error);
 
         // Unsubscribe from events
@@ -602,7 +669,7 @@ impl DiscordHandler {
         // Delete the status message now that we have the final response
         // This keeps the chat clean - users see only their message and the final answer
         if let Some(msg_id) = status_message_id {
-            if let Err(e) = msg.channel_id.delete_message(&ctx.http, msg_id).await {
+            if let Err(e) = reply_channel.delete_message(&ctx.http, msg_id).await {
                 log::warn!("Discord: Failed to delete status message: {}", e);
             } else {
                 log::info!("Discord: Deleted status message {}", msg_id);
@@ -613,28 +680,32 @@ impl DiscordHandler {
 
         // Send final response
         if result.error.is_none() && !result.response.is_empty() {
-            // Discord has a 2000 character limit per message
-            let response = &result.response;
-            let chunks = util::split_message(response, 2000);
-
-            for chunk in chunks {
-                if let Err(e) = msg.channel_id.say(&ctx.http, &chunk).await {
-                    log::error!("Failed to send Discord message: {}", e);
-                }
-            }
+            // Paced by the outbound limiter so long replies don't trip Discord's limits
+            let max_len = ChannelType::Discord.max_message_len();
+            let chunks = util::split_message(&result.response, max_len);
+            self.dispatcher
+                .outbound_limiter()
+                .deliver(&reply_channel.to_string(), chunks, max_len, |chunk| {
+                    let http = ctx.http.clone();
+                    let channel_id = reply_channel;
+                    async move {
+                        channel_id.say(&http, chunk).await.map(|_| ()).map_err(|e| e.to_string())
+                    }
+                })
+                .await;
 
             // Send image embeds for any image URLs found in the response
-            let image_urls = extract_image_urls(response);
+            let image_urls = extract_image_urls(&result.response);
             for url in image_urls.iter().take(4) {
                 let embed = CreateEmbed::new().image(url);
                 let builder = CreateMessage::new().embed(embed);
-                if let Err(e) = msg.channel_id.send_message(&ctx.http, builder).await {
+                if let Err(e) = reply_channel.send_message(&ctx.http, builder).await {
                     log::warn!("Discord: Failed to send image embed: {}", e);
                 }
             }
         } else if let Some(error) = result.error {
             let error_msg = format!("Sorry, I encountered an error: {}", error);
-            let _ = msg.channel_id.say(&ctx.http, &error_msg).await;
+            let _ = reply_channel.say(&ctx.http, &error_msg).await;
         } else if result.response.is_empty() {
             log::debug!("Discord: Empty final response for user {}", user_name);
         }
@@ -0,0 +1,187 @@
+//! Global cap on concurrent dispatches
+//!
+//! Every dispatch that reaches the AI holds a permit for its whole run, so at
+//! most `max_concurrent` conversations are processed at once no matter how many
+//! channels are busy. Dispatches beyond the cap either wait for a permit or are
+//! turned away immediately, depending on the overflow policy.
+
+use serde::Serialize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::config;
+
+/// What to do with a dispatch when every permit is taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverflowPolicy {
+    /// Wait for a running dispatch to finish
+    Queue,
+    /// Fail fast with a "busy" error
+    Reject,
+}
+
+impl OverflowPolicy {
+    pub fn from_str_or_default(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "reject" => Self::Reject,
+            _ => Self::Queue,
+        }
+    }
+}
+
+/// Snapshot of dispatch concurrency, reported by /api/system/info
+#[derive(Debug, Clone, Serialize)]
+pub struct DispatchStats {
+    /// Configured cap (None = unlimited)
+    pub max_concurrent: Option<usize>,
+    pub overflow_policy: OverflowPolicy,
+    /// Dispatches currently holding a permit
+    pub in_flight: usize,
+    /// Dispatches waiting for a permit
+    pub queued: usize,
+    /// Highest in-flight count seen since startup
+    pub peak_in_flight: usize,
+    /// Dispatches turned away since startup
+    pub rejected: u64,
+}
+
+/// Semaphore-backed concurrency limiter shared by all channels
+pub struct DispatchLimiter {
+    semaphore: Option<Arc<Semaphore>>,
+    max_concurrent: Option<usize>,
+    policy: OverflowPolicy,
+    in_flight: Arc<AtomicUsize>,
+    queued: AtomicUsize,
+    peak_in_flight: AtomicUsize,
+    rejected: AtomicU64,
+}
+
+impl Default for DispatchLimiter {
+    fn default() -> Self {
+        Self::new(None, OverflowPolicy::Queue)
+    }
+}
+
+impl DispatchLimiter {
+    /// `max_concurrent` of None (or 0) disables the cap; in-flight dispatches are still counted
+    pub fn new(max_concurrent: Option<usize>, policy: OverflowPolicy) -> Self {
+        let max_concurrent = max_concurrent.filter(|n| *n > 0);
+        Self {
+            semaphore: max_concurrent.map(|n| Arc::new(Semaphore::new(n))),
+            max_concurrent,
+            policy,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            queued: AtomicUsize::new(0),
+            peak_in_flight: AtomicUsize::new(0),
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    /// Limits from STARK_MAX_CONCURRENT_DISPATCHES / STARK_DISPATCH_OVERFLOW_POLICY
+    pub fn from_env() -> Self {
+        Self::new(config::max_concurrent_dispatches(), config::dispatch_overflow_policy())
+    }
+
+    /// Take a permit for one dispatch, waiting or failing per the overflow policy.
+    /// Returns None if the dispatch was rejected.
+    pub async fn acquire(&self) -> Option<DispatchPermit> {
+        let permit = match &self.semaphore {
+            None => None,
+            Some(semaphore) => match semaphore.clone().try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) if self.policy == OverflowPolicy::Reject => {
+                    self.rejected.fetch_add(1, Ordering::Relaxed);
+                    return None;
+                }
+                Err(_) => {
+                    self.queued.fetch_add(1, Ordering::Relaxed);
+                    let permit = semaphore.clone().acquire_owned().await;
+                    self.queued.fetch_sub(1, Ordering::Relaxed);
+                    // The semaphore is never closed
+                    Some(permit.expect("dispatch semaphore closed"))
+                }
+            },
+        };
+
+        let in_flight = self.in_flight.fetch_add(1, Ordering::Relaxed) + 1;
+        self.peak_in_flight.fetch_max(in_flight, Ordering::Relaxed);
+        Some(DispatchPermit {
+            _permit: permit,
+            in_flight: self.in_flight.clone(),
+        })
+    }
+
+    pub fn stats(&self) -> DispatchStats {
+        DispatchStats {
+            max_concurrent: self.max_concurrent,
+            overflow_policy: self.policy,
+            in_flight: self.in_flight.load(Ordering::Relaxed),
+            queued: self.queued.load(Ordering::Relaxed),
+            peak_in_flight: self.peak_in_flight.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Held for the duration of a dispatch; releases the slot on drop
+pub struct DispatchPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for DispatchPermit {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_reject_policy_fails_fast() {
+        let limiter = DispatchLimiter::new(Some(1), OverflowPolicy::Reject);
+        let permit = limiter.acquire().await.expect("first dispatch runs");
+        assert!(limiter.acquire().await.is_none());
+        assert_eq!(limiter.stats().rejected, 1);
+
+        drop(permit);
+        assert_eq!(limiter.stats().in_flight, 0);
+        assert!(limiter.acquire().await.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_queue_policy_waits_for_permit() {
+        let limiter = Arc::new(DispatchLimiter::new(Some(1), OverflowPolicy::Queue));
+        let permit = limiter.acquire().await.unwrap();
+
+        let waiter = {
+            let limiter = limiter.clone();
+            tokio::spawn(async move { limiter.acquire().await.is_some() })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let stats = limiter.stats();
+        assert_eq!((stats.in_flight, stats.queued), (1, 1));
+
+        drop(permit);
+        assert!(waiter.await.unwrap());
+        let stats = limiter.stats();
+        assert_eq!((stats.in_flight, stats.queued, stats.peak_in_flight), (0, 0, 1));
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_still_counts() {
+        let limiter = DispatchLimiter::default();
+        let a = limiter.acquire().await.unwrap();
+        let _b = limiter.acquire().await.unwrap();
+        assert_eq!(limiter.stats().in_flight, 2);
+        drop(a);
+        assert_eq!(limiter.stats().in_flight, 1);
+        assert_eq!(limiter.stats().max_concurrent, None);
+        assert_eq!(OverflowPolicy::from_str_or_default("REJECT"), OverflowPolicy::Reject);
+    }
+}
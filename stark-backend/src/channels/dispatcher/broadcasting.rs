@@ -6,6 +6,47 @@ use crate::tools::ToolDefinition;
 use super::MessageDispatcher;
 
 impl MessageDispatcher {
+    /// Pre-flight check for x402 AI endpoints: if the wallet's USDC balance is
+    /// below the configured buffer, warn the UI so the user can top up before a
+    /// payment fails mid-conversation. Runs in the background and never blocks
+    /// the dispatch; the balance is cached briefly to avoid an RPC per message.
+    pub(super) fn spawn_x402_balance_preflight(&self, endpoint: &str, channel_id: i64) {
+        if !crate::x402::is_x402_endpoint(endpoint) {
+            return;
+        }
+        let buffer = crate::config::x402_balance_buffer_raw();
+        if buffer == 0 {
+            return;
+        }
+        let wallet_addr = match self.wallet_provider {
+            Some(ref wp) => wp.get_address(),
+            None => return,
+        };
+        let broadcaster = self.broadcaster.clone();
+
+        tokio::spawn(crate::telemetry::trace::in_current_trace(async move {
+            match crate::x402::check_usdc_balance_cached(&wallet_addr).await {
+                Ok(balance) if balance < ethers::types::U256::from(buffer) => {
+                    let balance_formatted = format_usdc(balance.low_u128());
+                    let buffer_formatted = format_usdc(buffer as u128);
+                    log::warn!(
+                        "[X402] USDC balance {} is below the {} USDC buffer for {}",
+                        balance_formatted, buffer_formatted, wallet_addr
+                    );
+                    broadcaster.broadcast(GatewayEvent::x402_low_balance(
+                        channel_id,
+                        &wallet_addr,
+                        &balance.to_string(),
+                        &balance_formatted,
+                        &buffer_formatted,
+                    ));
+                }
+                Ok(_) => {}
+                Err(e) => log::debug!("[X402] Pre-flight USDC balance check failed: {}", e),
+            }
+        }));
+    }
+
     /// Broadcast the current toolset to the UI for debug panel visibility
     pub(super) fn broadcast_toolset_update(
         &self,
@@ -118,5 +159,27 @@ impl MessageDispatcher {
             channel_id,
             session_id,
         ));
+
+        // Dispatch SessionEnd hook (e.g. webhooks) without holding up finalization
+        if let Some(hook_manager) = self.hook_manager.clone() {
+            use crate::hooks::{HookContext, HookEvent};
+            let status = self.active_cache.get_completion_status(session_id);
+            tokio::spawn(async move {
+                let mut hook_ctx = HookContext::new(HookEvent::SessionEnd)
+                    .with_channel(channel_id, Some(session_id))
+                    .with_extra(serde_json::json!({
+                        "completion_status": status.map(|s| s.as_str()),
+                    }));
+                let _ = hook_manager.execute(HookEvent::SessionEnd, &mut hook_ctx).await;
+            });
+        }
     }
 }
+
+/// Format a raw USDC amount (6 decimals) for display.
+fn format_usdc(raw: u128) -> String {
+    format!("{}.{:06}", raw / 1_000_000, raw % 1_000_000)
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
@@ -3,10 +3,12 @@ use crate::tools::ToolDefinition;
 use crate::channels::types::{DispatchResult, NormalizedMessage};
 use crate::context;
 use crate::gateway::protocol::GatewayEvent;
-use crate::models::SessionScope;
+use crate::models::{BotSettings, SessionScope, DEFAULT_COMMAND_PREFIX};
 use crate::telemetry;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::time::interval;
 
@@ -15,23 +17,144 @@ use super::MessageDispatcher;
 /// How often to broadcast "still waiting" events during long AI calls
 const AI_PROGRESS_INTERVAL_SECS: u64 = 30;
 
-/// Compiled regex pattern for inline thinking (e.g., "/t:medium What is...")
-pub(super) static INLINE_THINKING_PATTERN: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?i)^/(?:t|think|thinking):(\w+)\s+(.+)$").unwrap()
-});
+/// Built-in dispatcher commands and the names each answers to by default
+const BUILTIN_COMMANDS: &[(&str, &[&str])] = &[
+    ("new", &["new"]),
+    ("reset", &["reset"]),
+    ("status", &["status"]),
+    ("think", &["think", "t", "thinking"]),
+];
+
+/// Longest allowed command prefix
+const MAX_COMMAND_PREFIX_LEN: usize = 3;
+
+/// Last command config built from bot settings, reused until the settings change
+static COMMAND_CONFIG_CACHE: Lazy<Mutex<Option<Arc<CommandConfig>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Build the thinking directive patterns for a prefix and command names:
+/// inline (e.g., "/t:medium What is...") and standalone (e.g., "/think:medium")
+fn thinking_patterns(prefix: &str, names: &[&str]) -> (Regex, Regex) {
+    let prefix = regex::escape(prefix);
+    let names = names.iter().map(|n| regex::escape(n)).collect::<Vec<_>>().join("|");
+    let inline = Regex::new(&format!(r"(?i)^{}(?:{}):(\w+)\s+(.+)$", prefix, names)).unwrap();
+    let directive = Regex::new(&format!(r"(?i)^{}(?:{})(?::(\w+))?$", prefix, names)).unwrap();
+    (inline, directive)
+}
+
+/// Chat command syntax: the prefix and the names each built-in command answers to.
+/// Configured via the `command_prefix` and `command_aliases` bot settings.
+pub struct CommandConfig {
+    prefix: String,
+    aliases: Option<HashMap<String, String>>,
+    /// Lowercase command name (built-in or alias) -> built-in command
+    names: HashMap<String, &'static str>,
+    inline_thinking: Regex,
+    thinking_directive: Regex,
+}
+
+impl Default for CommandConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_COMMAND_PREFIX, None)
+    }
+}
+
+impl CommandConfig {
+    /// Build from a prefix and extra aliases (alias -> built-in command name).
+    /// Aliases pointing at unknown commands are ignored.
+    pub fn new(prefix: &str, aliases: Option<&HashMap<String, String>>) -> Self {
+        let prefix = if prefix.is_empty() { DEFAULT_COMMAND_PREFIX } else { prefix };
+
+        let mut names: HashMap<String, &'static str> = BUILTIN_COMMANDS
+            .iter()
+            .flat_map(|(command, defaults)| defaults.iter().map(move |n| (n.to_string(), *command)))
+            .collect();
+        for (alias, target) in aliases.into_iter().flatten() {
+            let alias = alias.trim().to_lowercase();
+            if alias.is_empty() || names.contains_key(&alias) {
+                continue;
+            }
+            if let Some(command) = names.get(&target.trim().to_lowercase()).copied() {
+                names.insert(alias, command);
+            }
+        }
+
+        let think_names: Vec<&str> = names
+            .iter()
+            .filter(|(_, command)| **command == "think")
+            .map(|(name, _)| name.as_str())
+            .collect();
+        let (inline_thinking, thinking_directive) = thinking_patterns(prefix, &think_names);
+
+        Self {
+            prefix: prefix.to_string(),
+            aliases: aliases.cloned(),
+            names,
+            inline_thinking,
+            thinking_directive,
+        }
+    }
+
+    pub fn from_settings(settings: &BotSettings) -> Self {
+        Self::new(&settings.command_prefix, settings.command_aliases.as_ref())
+    }
+
+    /// Check a prefix/aliases update before it is saved.
+    /// An empty prefix resets to the default; aliases must be single words that
+    /// point at a built-in command and don't shadow one.
+    pub fn validate(prefix: Option<&str>, aliases: Option<&HashMap<String, String>>) -> Result<(), String> {
+        if let Some(prefix) = prefix
+            && !prefix.is_empty()
+            && (prefix.chars().count() > MAX_COMMAND_PREFIX_LEN
+                || prefix.chars().any(|c| c.is_whitespace() || c.is_alphanumeric()))
+        {
+            return Err(format!(
+                "Invalid command prefix '{}': use 1-{} symbol characters (e.g. / or !)",
+                prefix, MAX_COMMAND_PREFIX_LEN
+            ));
+        }
+
+        let builtin = Self::default();
+        for (alias, target) in aliases.into_iter().flatten() {
+            let alias = alias.trim().to_lowercase();
+            if alias.is_empty() || !alias.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+                return Err(format!("Invalid command alias '{}': use a single word", alias));
+            }
+            if builtin.names.contains_key(&alias) {
+                return Err(format!("Command alias '{}' conflicts with a built-in command", alias));
+            }
+            if !builtin.names.contains_key(&target.trim().to_lowercase()) {
+                return Err(format!(
+                    "Command alias '{}' points to unknown command '{}'. Valid commands: new, reset, status, think",
+                    alias, target
+                ));
+            }
+        }
+        Ok(())
+    }
 
-/// Compiled regex pattern for standalone thinking directives (e.g., "/think:medium")
-pub(super) static THINKING_DIRECTIVE_PATTERN: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?i)^/(?:t|think|thinking)(?::(\w+))?$").unwrap()
-});
+    /// Resolve a bare command message (e.g. "/reset", "!clear") to its built-in command name
+    pub fn command(&self, text: &str) -> Option<&'static str> {
+        let name = text.trim().strip_prefix(self.prefix.as_str())?;
+        self.names.get(&name.to_lowercase()).copied()
+    }
+
+    /// Pattern for inline thinking (e.g., "/t:medium What is...")
+    pub fn inline_thinking_pattern(&self) -> &Regex {
+        &self.inline_thinking
+    }
+
+    /// Pattern for standalone thinking directives (e.g., "/think:medium")
+    pub fn thinking_directive_pattern(&self) -> &Regex {
+        &self.thinking_directive
+    }
+}
 
 /// Parse inline thinking directive from message (e.g., "/think:high What is...")
 /// Returns the thinking level and the clean message text
-pub(super) fn parse_inline_thinking(text: &str) -> (Option<ThinkingLevel>, Option<String>) {
+pub(super) fn parse_inline_thinking(text: &str, commands: &CommandConfig) -> (Option<ThinkingLevel>, Option<String>) {
     let text = text.trim();
 
-    // Use static pattern to avoid recompiling on every call
-    if let Some(captures) = INLINE_THINKING_PATTERN.captures(text) {
+    if let Some(captures) = commands.inline_thinking_pattern().captures(text) {
         let level_str = captures.get(1).map(|m| m.as_str()).unwrap_or("");
         let clean_text = captures.get(2).map(|m| m.as_str().to_string());
 
@@ -45,12 +168,27 @@ pub(super) fn parse_inline_thinking(text: &str) -> (Option<ThinkingLevel>, Optio
 }
 
 impl MessageDispatcher {
+    /// Command syntax from bot settings; the compiled config is reused until the settings change
+    pub(super) fn command_config(&self) -> Arc<CommandConfig> {
+        let settings = self.db.get_bot_settings().unwrap_or_default();
+        let mut cache = COMMAND_CONFIG_CACHE.lock().unwrap();
+        if let Some(config) = cache.as_ref()
+            && config.prefix == settings.command_prefix
+            && config.aliases == settings.command_aliases
+        {
+            return config.clone();
+        }
+        let config = Arc::new(CommandConfig::from_settings(&settings));
+        *cache = Some(config.clone());
+        config
+    }
+
     /// Handle thinking directive messages (e.g., "/think:medium" sets session default)
-    pub(super) async fn handle_thinking_directive(&self, message: &NormalizedMessage) -> Option<DispatchResult> {
+    pub(super) async fn handle_thinking_directive(&self, message: &NormalizedMessage, commands: &CommandConfig) -> Option<DispatchResult> {
         let text = message.text.trim();
 
         // Check if this is a standalone thinking directive
-        if let Some(captures) = THINKING_DIRECTIVE_PATTERN.captures(text) {
+        if let Some(captures) = commands.thinking_directive_pattern().captures(text) {
             let level_str = captures.get(1).map(|m| m.as_str()).unwrap_or("low");
 
             if let Some(level) = ThinkingLevel::from_str(level_str) {
@@ -155,8 +293,14 @@ impl MessageDispatcher {
             &tool_history,
         ));
 
-        // Spawn the actual AI request
-        let ai_future = client.generate_with_tools(conversation, tool_history, tools.clone());
+        // Spawn the actual AI request (streamed token by token when an API client is consuming them)
+        let ai_future = async {
+            if self.is_token_streaming(channel_id) && client.supports_streaming() {
+                client.generate_with_tools_streamed(conversation, tool_history, tools.clone(), &broadcaster, channel_id).await
+            } else {
+                client.generate_with_tools(conversation, tool_history, tools.clone()).await
+            }
+        };
         tokio::pin!(ai_future);
 
         // Watchdog LLM timeout
@@ -292,7 +436,7 @@ impl MessageDispatcher {
         }
     }
 
-    /// Handle /new or /reset commands
+    /// Handle the new/reset commands (/new, /reset or their aliases)
     pub(super) async fn handle_reset_command(&self, message: &NormalizedMessage) -> DispatchResult {
         // Cancel any ongoing execution for this channel
         self.execution_tracker.cancel_execution(message.channel_id);
@@ -323,7 +467,7 @@ impl MessageDispatcher {
         match self.db.get_or_create_chat_session(
             &message.channel_type,
             message.channel_id,
-            &message.chat_id,
+            &message.session_chat_id(),
             scope,
             None,
         ) {
@@ -394,4 +538,90 @@ impl MessageDispatcher {
             }
         }
     }
+
+    /// Handle the status command (/status or its aliases): report the current session's context usage,
+    /// compaction state, active subtype and safe mode without calling the AI
+    pub(super) async fn handle_status_command(&self, message: &NormalizedMessage) -> DispatchResult {
+        let scope = if message.chat_id != message.user_id {
+            SessionScope::Group
+        } else {
+            SessionScope::Dm
+        };
+
+        // Gateway channels start a fresh session per message, so report the latest one
+        // instead of creating a new (empty) session just to describe it
+        let session = if super::uses_gateway_sessions(message) {
+            self.db.get_latest_session_for_channel(&message.channel_type, message.channel_id)
+        } else {
+            self.db.get_or_create_chat_session(
+                &message.channel_type,
+                message.channel_id,
+                &message.session_chat_id(),
+                scope,
+                None,
+            ).map(Some)
+        };
+
+        let channel_safe_mode = self.db.get_channel(message.channel_id)
+            .ok()
+            .flatten()
+            .map(|ch| ch.safe_mode)
+            .unwrap_or(false);
+
+        let response = match session {
+            Ok(Some(session)) => {
+                // Prefer the in-memory cache (reflects an in-flight run), fall back to the DB
+                let session = self.active_cache.get_session(session.id).unwrap_or(session);
+                let agent_context = self.active_cache.get_agent_context(session.id)
+                    .or_else(|| self.db.get_agent_context(session.id).ok().flatten());
+                let message_count = self.db.count_session_messages(session.id).unwrap_or(0);
+                let safe_mode = channel_safe_mode || message.force_safe_mode || session.safe_mode;
+
+                let usage_pct = if session.max_context_tokens > 0 {
+                    session.context_tokens as f64 * 100.0 / session.max_context_tokens as f64
+                } else {
+                    0.0
+                };
+                let compaction = match session.compaction_id {
+                    Some(_) => "summary present",
+                    None => "not compacted",
+                };
+
+                format!(
+                    "**Session status**\n\
+                     Session: #{} ({} messages, {})\n\
+                     Context: {} / {} tokens ({:.0}%), {} tokens of budget left\n\
+                     Compaction: {}, pressure {}\n\
+                     Subtype: {}\n\
+                     Safe mode: {}",
+                    session.id,
+                    message_count,
+                    session.completion_status.as_str(),
+                    session.context_tokens,
+                    session.max_context_tokens,
+                    usage_pct,
+                    self.context_manager.get_context_budget(session.id).max(0),
+                    compaction,
+                    self.context_manager.check_compaction_level(session.id),
+                    agent_context.and_then(|ctx| ctx.subtype).unwrap_or_else(|| "none".to_string()),
+                    if safe_mode { "on" } else { "off" },
+                )
+            }
+            Ok(None) => format!(
+                "**Session status**\nNo active session yet.\nSafe mode: {}",
+                if channel_safe_mode || message.force_safe_mode { "on" } else { "off" },
+            ),
+            Err(e) => {
+                log::error!("Failed to get session for status: {}", e);
+                return DispatchResult::error(format!("Session error: {}", e));
+            }
+        };
+
+        self.broadcaster.broadcast(GatewayEvent::agent_response(
+            message.channel_id,
+            &message.user_name,
+            &response,
+        ));
+        DispatchResult::success(response)
+    }
 }
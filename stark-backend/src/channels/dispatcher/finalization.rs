@@ -228,6 +228,9 @@ impl MessageDispatcher {
             Ok((last_say_to_user_content.to_string(), true, last_say_to_user_id.map(|s| s.to_string())))
         } else if orchestrator_complete {
             Ok((final_summary.to_string(), false, None))
+        } else if was_cancelled {
+            // Stopped by the user: the session is already Cancelled, so there is nothing to report
+            Ok((String::new(), false, None))
         } else if tool_call_log.is_empty() {
             // Mark session as Failed — hit max iterations with no work done
             self.active_cache.update_completion_status(session_id, CompletionStatus::Failed);
@@ -16,7 +16,7 @@ use crate::gateway::protocol::GatewayEvent;
 use crate::models::session_message::MessageRole as DbMessageRole;
 use crate::models::{AgentSettings, CompletionStatus, SessionScope, SpecialRoleGrants, DEFAULT_MAX_TOOL_ITERATIONS};
 use crate::telemetry::{
-    self, Rollout, RolloutConfig, RolloutManager, SpanCollector, SpanType,
+    self, FailureReason, Rollout, RolloutConfig, RolloutManager, SpanCollector, SpanType,
     RewardEmitter, TelemetryStore, Watchdog, WatchdogConfig, ResourceManager,
 };
 use crate::tools::{ToolConfig, ToolContext, ToolDefinition, ToolExecution, ToolRegistry};
@@ -27,10 +27,14 @@ use std::time::Duration;
 mod broadcasting;
 mod commands;
 mod finalization;
+mod replay;
+mod session_title;
 mod skills;
 mod tool_loop;
 mod tool_processing;
 
+pub use commands::CommandConfig;
+
 /// Fallback maximum tool iterations (used when db lookup fails)
 /// Actual value is configurable via bot settings
 pub(super) const FALLBACK_MAX_TOOL_ITERATIONS: usize = DEFAULT_MAX_TOOL_ITERATIONS as usize;
@@ -65,6 +69,10 @@ pub struct MessageDispatcher {
     validator_registry: Option<Arc<crate::tool_validators::ValidatorRegistry>>,
     /// Transaction queue manager for queued web3 transactions
     tx_queue: Option<Arc<crate::tx_queue::TxQueueManager>>,
+    /// Tool calls awaiting the user's approval of their estimated cost
+    pending_confirmations: Arc<crate::execution::PendingConfirmationManager>,
+    /// Planned task lists waiting for the user's approval
+    plan_approvals: Arc<crate::execution::PlanApprovalManager>,
     /// Disk quota manager for enforcing disk usage limits
     disk_quota: Option<Arc<crate::disk_quota::DiskQuotaManager>>,
     /// Telemetry store for persisting execution spans
@@ -79,8 +87,17 @@ pub struct MessageDispatcher {
     session_lanes: Arc<SessionLaneManager>,
     /// In-memory cache for active session metadata + agent context (reduces SQLite writes)
     active_cache: Arc<ActiveSessionCache>,
-    /// Mock AI client for integration tests (bypasses real AI API)
-    #[cfg(test)]
+    /// Paces outbound messages so replies stay under the platform's rate limits
+    outbound_limiter: crate::channels::OutboundRateLimiter,
+    /// Recently processed platform message IDs (skips redelivered messages)
+    message_dedup: crate::channels::MessageDeduplicator,
+    /// Channels with an API client consuming streamed tokens, with a count of active consumers
+    token_stream_channels: Arc<dashmap::DashMap<i64, usize>>,
+    /// Global cap on concurrent dispatches (shared across all channels)
+    dispatch_limiter: Arc<crate::channels::dispatch_limiter::DispatchLimiter>,
+    /// Sessions with a title generation job running
+    titles_in_progress: Arc<dashmap::DashSet<i64>>,
+    /// Mock AI client for integration tests and rollout replays (bypasses real AI API)
     mock_ai_client: Option<crate::ai::MockAiClient>,
 }
 
@@ -202,6 +219,8 @@ impl MessageDispatcher {
             hook_manager: None,
             validator_registry: None,
             tx_queue: None,
+            pending_confirmations: Arc::new(crate::execution::PendingConfirmationManager::new()),
+            plan_approvals: Arc::new(crate::execution::PlanApprovalManager::new()),
             disk_quota: None,
             telemetry_store,
             rollout_manager,
@@ -209,7 +228,11 @@ impl MessageDispatcher {
             watchdog_config: WatchdogConfig::default(),
             session_lanes: SessionLaneManager::new(),
             active_cache,
-            #[cfg(test)]
+            outbound_limiter: crate::channels::OutboundRateLimiter::default(),
+            message_dedup: crate::channels::MessageDeduplicator::default(),
+            token_stream_channels: Arc::new(dashmap::DashMap::new()),
+            dispatch_limiter: Arc::new(crate::channels::dispatch_limiter::DispatchLimiter::from_env()),
+            titles_in_progress: Arc::new(dashmap::DashSet::new()),
             mock_ai_client: None,
         }
     }
@@ -245,6 +268,18 @@ impl MessageDispatcher {
         self
     }
 
+    /// Set the outbound delivery rate limiter (per-channel, platform-aware)
+    pub fn with_outbound_limiter(mut self, limiter: crate::channels::OutboundRateLimiter) -> Self {
+        self.outbound_limiter = limiter;
+        self
+    }
+
+    /// Share a message deduplicator (e.g. across restarts of the same channel)
+    pub fn with_message_dedup(mut self, dedup: crate::channels::MessageDeduplicator) -> Self {
+        self.message_dedup = dedup;
+        self
+    }
+
     /// Set the hybrid search engine (shared with both tool context and context manager)
     pub fn with_hybrid_search(mut self, engine: Arc<crate::memory::HybridSearchEngine>) -> Self {
         self.context_manager.set_hybrid_search(engine.clone());
@@ -252,14 +287,12 @@ impl MessageDispatcher {
         self
     }
 
-    /// Set a mock AI client for integration tests (bypasses real AI API)
-    #[cfg(test)]
+    /// Set a mock AI client for integration tests and rollout replays (bypasses real AI API)
     pub fn with_mock_ai_client(mut self, client: crate::ai::MockAiClient) -> Self {
         self.mock_ai_client = Some(client);
         self
     }
 
-    #[cfg(test)]
     pub fn get_mock_trace(&self) -> Vec<crate::ai::TraceEntry> {
         self.mock_ai_client.as_ref().map(|m| m.get_trace()).unwrap_or_default()
     }
@@ -308,6 +341,8 @@ impl MessageDispatcher {
             hook_manager: None,     // No hooks without explicit setup
             validator_registry: None, // No validators without explicit setup
             tx_queue: None,         // No tx queue without explicit setup
+            pending_confirmations: Arc::new(crate::execution::PendingConfirmationManager::new()),
+            plan_approvals: Arc::new(crate::execution::PlanApprovalManager::new()),
             disk_quota: None,       // No disk quota without explicit setup
             telemetry_store,
             rollout_manager,
@@ -315,7 +350,11 @@ impl MessageDispatcher {
             watchdog_config: WatchdogConfig::default(),
             session_lanes: SessionLaneManager::new(),
             active_cache,
-            #[cfg(test)]
+            outbound_limiter: crate::channels::OutboundRateLimiter::default(),
+            message_dedup: crate::channels::MessageDeduplicator::default(),
+            token_stream_channels: Arc::new(dashmap::DashMap::new()),
+            dispatch_limiter: Arc::new(crate::channels::dispatch_limiter::DispatchLimiter::from_env()),
+            titles_in_progress: Arc::new(dashmap::DashSet::new()),
             mock_ai_client: None,
         }
     }
@@ -330,6 +369,83 @@ impl MessageDispatcher {
         self.subagent_manager.clone()
     }
 
+    /// Tool call awaiting confirmation on a channel (if not expired)
+    pub fn get_pending_confirmation(&self, channel_id: i64) -> Option<crate::execution::PendingConfirmation> {
+        self.pending_confirmations.get_pending(channel_id)
+    }
+
+    /// Approve a channel's pending tool call. It runs when the agent retries
+    /// the same call, so the user is told to let the agent continue.
+    pub fn api_confirm_pending(&self, channel_id: i64) -> Result<String, String> {
+        let confirmation = self
+            .pending_confirmations
+            .approve(channel_id)
+            .ok_or_else(|| "No pending confirmation for this channel (it may have expired)".to_string())?;
+        log::info!("[CONFIRMATION] Approved {} on channel {}", confirmation.tool_name, channel_id);
+        self.broadcaster.broadcast(GatewayEvent::confirmation_approved(
+            channel_id,
+            &confirmation.id,
+            &confirmation.tool_name,
+        ));
+        Ok(format!("Approved: {}. Tell the agent to continue to run it.", confirmation.description))
+    }
+
+    /// Cancel a channel's pending tool call, returning its description
+    pub fn api_cancel_pending(&self, channel_id: i64) -> Result<String, String> {
+        let confirmation = self
+            .pending_confirmations
+            .cancel(channel_id)
+            .ok_or_else(|| "No pending confirmation for this channel".to_string())?;
+        log::info!("[CONFIRMATION] Cancelled {} on channel {}", confirmation.tool_name, channel_id);
+        self.broadcaster.broadcast(GatewayEvent::confirmation_rejected(
+            channel_id,
+            &confirmation.id,
+            &confirmation.tool_name,
+        ));
+        Ok(confirmation.description)
+    }
+
+    /// Whether a channel has a planned task list waiting for approval
+    pub fn has_pending_plan_approval(&self, channel_id: i64) -> bool {
+        self.plan_approvals.has_pending(channel_id)
+    }
+
+    /// Approve or reject the plan waiting on a channel
+    pub fn api_resolve_plan_approval(&self, channel_id: i64, approved: bool) -> Result<String, String> {
+        if !self.plan_approvals.resolve(channel_id, approved) {
+            return Err("No plan is waiting for approval on this channel".to_string());
+        }
+        log::info!("[PLAN_APPROVAL] Plan on channel {} {} via API", channel_id, if approved { "approved" } else { "rejected" });
+        Ok(if approved { "Plan approved" } else { "Plan rejected" }.to_string())
+    }
+
+    /// Abort the dispatch running on a channel, along with its subagents. The
+    /// tool loop stops at its next check (or mid-generation), marks the session
+    /// Cancelled and broadcasts its completion. Returns false if nothing was running.
+    pub async fn cancel_dispatch(&self, channel_id: i64) -> bool {
+        let running = self.execution_tracker.get_execution_id(channel_id).is_some();
+        if running {
+            log::info!("[DISPATCH] Cancelling running dispatch on channel {}", channel_id);
+            self.execution_tracker.cancel_execution(channel_id);
+            self.execution_tracker.cancel_all_sessions_for_channel(channel_id);
+        }
+        let subagents = match self.subagent_manager {
+            Some(ref manager) => manager.cancel_all_for_channel_and_wait(channel_id, Duration::from_millis(100)).await,
+            None => 0,
+        };
+        running || subagents > 0
+    }
+
+    /// Get the outbound delivery rate limiter
+    pub fn outbound_limiter(&self) -> &crate::channels::OutboundRateLimiter {
+        &self.outbound_limiter
+    }
+
+    /// Get the global dispatch concurrency limiter
+    pub fn dispatch_limiter(&self) -> &Arc<crate::channels::dispatch_limiter::DispatchLimiter> {
+        &self.dispatch_limiter
+    }
+
     /// Get the TelemetryStore
     pub fn telemetry_store(&self) -> &Arc<TelemetryStore> {
         &self.telemetry_store
@@ -345,6 +461,65 @@ impl MessageDispatcher {
         &self.active_cache
     }
 
+    /// Stream AI output for `channel_id` as `stream.*` events while the returned guard is held.
+    /// Streaming is opt-in because it bypasses x402 payments and costs a broadcast per token.
+    pub fn stream_tokens_for_channel(&self, channel_id: i64) -> TokenStreamGuard {
+        *self.token_stream_channels.entry(channel_id).or_insert(0) += 1;
+        TokenStreamGuard {
+            channels: self.token_stream_channels.clone(),
+            channel_id,
+        }
+    }
+
+    /// Whether any API client is consuming streamed tokens for this channel
+    pub(super) fn is_token_streaming(&self, channel_id: i64) -> bool {
+        self.token_stream_channels.contains_key(&channel_id)
+    }
+
+    /// Save an x402 payment made for an AI call and add it to the session's spend ledger
+    pub(super) fn record_x402_payment(&self, channel_id: i64, session_id: i64, payment_info: &crate::x402::X402PaymentInfo) {
+        if let Err(e) = self.db.record_x402_payment(
+            Some(channel_id),
+            None,
+            payment_info.resource.as_deref(),
+            &payment_info.amount,
+            &payment_info.amount_formatted,
+            &payment_info.asset,
+            &payment_info.pay_to,
+            payment_info.tx_hash.as_deref(),
+            &payment_info.status.to_string(),
+        ) {
+            log::error!("[DISPATCH] Failed to record x402 payment: {}", e);
+        }
+        crate::execution::spend_ledger::record_x402(&self.db, Some(&self.broadcaster), channel_id, session_id, payment_info);
+    }
+
+    /// Add an AI call's estimated token usage to the session's spend ledger
+    pub(super) fn record_ai_usage(
+        &self,
+        channel_id: i64,
+        session_id: i64,
+        input: &[Message],
+        tool_history: &[crate::ai::ToolHistoryEntry],
+        output: &str,
+        paid_via_x402: bool,
+    ) {
+        let mut input_tokens: i64 = input.iter().map(|m| estimate_tokens(&m.content) as i64).sum();
+        if !tool_history.is_empty() {
+            input_tokens += estimate_tokens(&serde_json::to_string(tool_history).unwrap_or_default()) as i64;
+        }
+        let output_tokens = estimate_tokens(output) as i64;
+        crate::execution::spend_ledger::record_ai_usage(
+            &self.db,
+            Some(&self.broadcaster),
+            channel_id,
+            session_id,
+            input_tokens,
+            output_tokens,
+            paid_via_x402,
+        );
+    }
+
     /// Panic-safe dispatch wrapper.
     ///
     /// Catches any panic inside `dispatch()` and returns a `DispatchResult::error`
@@ -377,8 +552,36 @@ impl MessageDispatcher {
         }
     }
 
-    /// Dispatch a normalized message to the AI and return the response
+    /// Dispatch a normalized message to the AI and return the response.
+    ///
+    /// Runs under a fresh trace id (reused as the rollout id) so every log line
+    /// and gateway event for this dispatch can be correlated.
     pub async fn dispatch(&self, message: NormalizedMessage) -> DispatchResult {
+        let trace_id = telemetry::trace::new_trace_id();
+        telemetry::trace::with_trace_id(trace_id.clone(), self.dispatch_traced(message, trace_id)).await
+    }
+
+    async fn dispatch_traced(&self, message: NormalizedMessage, trace_id: String) -> DispatchResult {
+        // Skip messages the platform redelivered (e.g. after a reconnect) before any session work
+        if self.message_dedup.check_and_record(
+            &message.channel_type,
+            message.channel_id,
+            message.message_id.as_deref(),
+        ) {
+            log::info!(
+                "[DISPATCH] Skipping duplicate {} message {:?} on channel {}",
+                message.channel_type, message.message_id, message.channel_id
+            );
+            return DispatchResult::success(String::new());
+        }
+
+        // Log platform messages for moderation history exports (web/cron/system dispatches are skipped)
+        if crate::models::ChannelType::from_str(&message.channel_type).is_some()
+            && let Err(e) = self.db.store_channel_message(&message)
+        {
+            log::warn!("[DISPATCH] Failed to log message for channel {}: {}", message.channel_id, e);
+        }
+
         // Emit message received event
         self.broadcaster.broadcast(GatewayEvent::channel_message(
             message.channel_id,
@@ -387,25 +590,57 @@ impl MessageDispatcher {
             &message.text,
         ));
 
+        // A reply to a plan waiting for approval answers it instead of queueing behind
+        // the dispatch that is waiting on it
+        if self.plan_approvals.has_pending(message.channel_id)
+            && let Some(approved) = crate::execution::plan_approval::parse_reply(&message.text)
+            && self.plan_approvals.resolve_for_user(message.channel_id, &message.user_id, approved)
+        {
+            log::info!(
+                "[PLAN_APPROVAL] {} {} the plan on channel {}",
+                message.user_name,
+                if approved { "approved" } else { "rejected" },
+                message.channel_id
+            );
+            return DispatchResult::success(String::new());
+        }
+
         // Acquire session lane to serialize requests for the same channel/chat.
         // This prevents concurrent dispatches from racing on session creation,
         // context building, and tool execution for the same conversation.
-        let lane_key = format!("{}:{}:{}", message.channel_type, message.channel_id, message.chat_id);
+        let lane_key = format!("{}:{}:{}", message.channel_type, message.channel_id, message.session_chat_id());
         let _lane_guard = self.session_lanes.acquire(&lane_key).await;
 
-        // Check for reset commands
-        let text_lower = message.text.trim().to_lowercase();
-        if text_lower == "/new" || text_lower == "/reset" {
-            return self.handle_reset_command(&message).await;
+        // Check for reset and status commands (prefix and aliases come from bot settings).
+        // Status is answered from session state, no AI call.
+        let command_config = self.command_config();
+        match command_config.command(&message.text) {
+            Some("new") | Some("reset") => return self.handle_reset_command(&message).await,
+            Some("status") => return self.handle_status_command(&message).await,
+            _ => {}
         }
 
         // Check for thinking directives (session-level setting)
-        if let Some(thinking_response) = self.handle_thinking_directive(&message).await {
+        if let Some(thinking_response) = self.handle_thinking_directive(&message, &command_config).await {
             return thinking_response;
         }
 
         // Parse inline thinking directive and extract clean message
-        let (thinking_level, clean_text) = commands::parse_inline_thinking(&message.text);
+        let (thinking_level, clean_text) = commands::parse_inline_thinking(&message.text, &command_config);
+
+        // Hold a global concurrency slot for the rest of the dispatch (commands above don't need one)
+        let _dispatch_permit = match self.dispatch_limiter.acquire().await {
+            Some(permit) => permit,
+            None => {
+                let error_msg = "The bot is handling too many conversations right now. Please try again shortly.".to_string();
+                log::warn!(
+                    "[DISPATCH] Rejected {} message on channel {}: concurrent dispatch limit reached",
+                    message.channel_type, message.channel_id
+                );
+                self.broadcaster.broadcast(GatewayEvent::agent_error(message.channel_id, &error_msg));
+                return DispatchResult::error(error_msg);
+            }
+        };
 
         // Start execution tracking with user message for descriptive display
         let user_msg = clean_text.as_deref().unwrap_or(&message.text);
@@ -420,6 +655,7 @@ impl MessageDispatcher {
         // We use session_id=0 initially; it will be updated once the session is resolved
         let rollout_config = RolloutConfig::default();
         let (mut rollout, span_collector) = self.rollout_manager.start_rollout(
+            trace_id,
             0, // will be updated once we have the session
             message.channel_id,
             rollout_config,
@@ -451,6 +687,7 @@ impl MessageDispatcher {
             "user_name": message.user_name,
             "channel_type": message.channel_type,
             "rollout_id": rollout.rollout_id,
+            "text": user_msg,
         });
         rollout_span.succeed();
         span_collector.record(rollout_span);
@@ -505,25 +742,26 @@ impl MessageDispatcher {
             }
         };
 
-        // For gateway channels (Discord, Telegram), create a fresh session for each message
+        // For gateway channels (Discord, Telegram, Matrix), create a fresh session for each message
         // to prevent context from growing too large. Previous conversation context is
-        // preserved by including the last 10 messages in the system prompt.
+        // preserved by including the last `gateway_previous_messages` messages (bot setting,
+        // default 6) in the system prompt.
+        // Threaded messages instead keep one persistent session per thread (see below).
         let channel_type_lower = message.channel_type.to_lowercase();
-        let is_gateway_channel = channel_type_lower == "discord"
-            || channel_type_lower == "telegram"
-            || channel_type_lower == "web"
-            || channel_type_lower == "external_channel";
+        let is_gateway_channel = uses_gateway_sessions(&message);
 
-        // Collect previous session messages for gateway channels (max 10)
+        // Collect previous session messages for gateway channels
         let previous_gateway_messages: Vec<crate::models::SessionMessage> = if is_gateway_channel {
-            const MAX_PREVIOUS_MESSAGES: i32 = 6;
+            let max_previous_messages = self.db.get_bot_settings()
+                .map(|s| s.gateway_previous_messages)
+                .unwrap_or(crate::models::DEFAULT_GATEWAY_PREVIOUS_MESSAGES);
 
             // Get the current active session (if any) and its messages
             if let Ok(Some(prev_session)) = self.db.get_latest_session_for_channel(
                 &message.channel_type,
                 message.channel_id,
             ) {
-                let messages = self.db.get_recent_session_messages(prev_session.id, MAX_PREVIOUS_MESSAGES)
+                let messages = self.db.get_recent_session_messages(prev_session.id, max_previous_messages)
                     .unwrap_or_default();
 
                 // Deactivate the old session
@@ -583,11 +821,11 @@ impl MessageDispatcher {
                 }
             }
         } else {
-            // Standard session handling for other channels
+            // Standard session handling for other channels (and threads, keyed per thread)
             match self.db.get_or_create_chat_session(
                 &message.channel_type,
                 message.channel_id,
-                &message.chat_id,
+                &message.session_chat_id(),
                 scope,
                 None,
             ) {
@@ -610,7 +848,7 @@ impl MessageDispatcher {
         };
 
         // Now that session is resolved, update rollout and span collector with real session_id
-        rollout.session_id = session.id;
+        self.rollout_manager.set_session(&mut rollout, session.id);
         span_collector.set_session(session.id);
 
         // Load session into in-memory cache for fast access during this dispatch
@@ -728,8 +966,7 @@ impl MessageDispatcher {
         // Sync session's max_context_tokens with agent settings for dynamic compaction
         self.context_manager.sync_max_context_tokens(session.id, settings.max_context_tokens);
 
-        // Create AI client — use mock in tests if configured, otherwise create from settings
-        #[cfg(test)]
+        // Create AI client — use the mock if configured (tests, replays), otherwise create from settings
         let client = if let Some(ref mock) = self.mock_ai_client {
             AiClient::Mock(mock.clone())
         } else {
@@ -757,33 +994,9 @@ impl MessageDispatcher {
                 }
             }
         };
-        #[cfg(not(test))]
-        let client = match AiClient::from_settings_with_wallet_provider(&settings, self.wallet_provider.clone()) {
-            Ok(c) => c.with_broadcaster(Arc::clone(&self.broadcaster), message.channel_id),
-            Err(e) => {
-                let error = format!("Failed to create AI client: {}", e);
-                log::error!("{}", error);
-                // Store error as assistant message so it's visible in the session
-                let _ = self.db.add_session_message(
-                    session.id, DbMessageRole::Assistant,
-                    &format!("[Error] {}", error), None, None, None, None,
-                );
-                // Mark session as Failed so it doesn't stay stuck as Active
-                self.active_cache.update_completion_status(session.id, CompletionStatus::Failed);
-                self.active_cache.flush_and_evict(session.id, &self.db);
-                self.broadcast_session_complete(message.channel_id, session.id);
-                self.broadcaster.broadcast(GatewayEvent::agent_error(
-                    message.channel_id,
-                    &error,
-                ));
-                self.execution_tracker.complete_execution(message.channel_id);
-                self.rollout_manager.fail_attempt(&mut rollout, &error, &span_collector);
-                self.telemetry_store.persist_spans(&span_collector);
-                heartbeat_handle.abort();
-                telemetry::clear_active_collector();
-                return DispatchResult::error(error);
-            }
-        };
+
+        // Warn early if the x402 wallet is running low (non-blocking)
+        self.spawn_x402_balance_preflight(&settings.endpoint, message.channel_id);
 
         // Add thinking event before AI generation
         self.execution_tracker.add_thinking(message.channel_id, "Processing request...");
@@ -952,6 +1165,20 @@ impl MessageDispatcher {
                 }
                 Err(e) => log::warn!("[DISPATCH] Failed to check special role grants: {}", e),
             }
+
+            // Role denials apply after every grant, including tools a granted skill requires
+            if let Some(grants) = special_role_grants.as_ref().filter(|g| !g.denied_tools.is_empty()) {
+                log::info!(
+                    "[DISPATCH] Special role {:?} denies tools {:?} for user {}",
+                    grants.role_name, grants.denied_tools, message.user_id
+                );
+                tool_config.allow_list.retain(|t| !grants.denies_tool(t));
+                for tool_name in &grants.denied_tools {
+                    if !tool_config.deny_list.contains(tool_name) {
+                        tool_config.deny_list.push(tool_name.clone());
+                    }
+                }
+            }
         }
 
         // Twitter has no interactive session — ask_user can never work, so block it.
@@ -1006,7 +1233,7 @@ impl MessageDispatcher {
         }
 
         // Add previous gateway chat messages (for Discord/Telegram fresh sessions)
-        // These are the last 10 messages from the previous session, providing continuity
+        // These are the last few messages from the previous session, providing continuity
         if !previous_gateway_messages.is_empty() {
             let mut context_text = String::from("## Previous Conversation\nRecent messages from the previous chat session:\n\n");
             for msg in &previous_gateway_messages {
@@ -1236,6 +1463,22 @@ impl MessageDispatcher {
                 "rogue_mode_enabled".to_string(),
                 serde_json::json!(bot_settings.rogue_mode_enabled),
             );
+            tool_context.extra.insert(
+                "tx_confirmation_timeout_secs".to_string(),
+                serde_json::json!(bot_settings.tx_confirmation_timeout_secs),
+            );
+
+            // Add gas strategy for building queued transactions
+            tool_context.extra.insert(
+                "gas_strategy".to_string(),
+                serde_json::json!(bot_settings.gas_strategy),
+            );
+            if let Some(max_fee) = bot_settings.gas_max_fee_gwei {
+                tool_context.extra.insert(
+                    "gas_max_fee_gwei".to_string(),
+                    serde_json::json!(max_fee),
+                );
+            }
 
             // Configure HTTP proxy for tool requests if set
             if let Some(ref url) = bot_settings.proxy_url {
@@ -1280,20 +1523,9 @@ impl MessageDispatcher {
                     Ok((content, payment)) => {
                         // Save x402 payment if one was made
                         if let Some(ref payment_info) = payment {
-                            if let Err(e) = self.db.record_x402_payment(
-                                Some(message.channel_id),
-                                None,
-                                payment_info.resource.as_deref(),
-                                &payment_info.amount,
-                                &payment_info.amount_formatted,
-                                &payment_info.asset,
-                                &payment_info.pay_to,
-                                payment_info.tx_hash.as_deref(),
-                                &payment_info.status.to_string(),
-                            ) {
-                                log::error!("[DISPATCH] Failed to record x402 payment: {}", e);
-                            }
+                            self.record_x402_payment(message.channel_id, session.id, payment_info);
                         }
+                        self.record_ai_usage(message.channel_id, session.id, &messages, &[], &content, payment.is_some());
                         Ok((content, false, None))
                     }
                     Err(e) => Err(e),
@@ -1324,13 +1556,35 @@ impl MessageDispatcher {
                             "[DISPATCH] Retrying after {}ms (attempt {}/{}): {}",
                             delay_ms,
                             rollout.attempt_count(),
-                            rollout.config.max_attempts,
+                            rollout.max_attempts(),
                             error_msg,
                         );
                         self.broadcaster.broadcast(GatewayEvent::agent_error(
                             message.channel_id,
-                            &format!("Retrying... (attempt {}/{})", rollout.attempt_count(), rollout.config.max_attempts),
+                            &format!("Retrying... (attempt {}/{})", rollout.attempt_count(), rollout.max_attempts()),
                         ));
+                        // Context overflow: compact before retrying, and shrink this request's history
+                        if matches!(
+                            rollout.last_failure().and_then(|a| a.failure_reason.as_ref()),
+                            Some(FailureReason::ContextOverflow)
+                        ) {
+                            self.broadcaster.broadcast(GatewayEvent::context_compacting(
+                                message.channel_id,
+                                session.id,
+                                "full",
+                                "Context overflow, compacting before retry",
+                            ));
+                            if let Err(e) = self.context_manager.compact_session(
+                                session.id,
+                                &client,
+                                memory_identity,
+                                None,
+                            ).await {
+                                log::error!("[COMPACTION] Compaction before retry failed: {}", e);
+                            }
+                            let dropped = drop_oldest_history(&mut messages);
+                            log::info!("[DISPATCH] Dropped {} old messages before retrying after context overflow", dropped);
+                        }
                         self.broadcaster.broadcast(GatewayEvent::rollout_status_change(
                             message.channel_id, &rollout.rollout_id, "retrying", rollout.attempt_count(),
                         ));
@@ -1495,6 +1749,9 @@ impl MessageDispatcher {
                 // Flush cached state to SQLite and evict (dispatch complete)
                 self.active_cache.flush_and_evict(session.id, &self.db);
 
+                // Give the session a readable title (first exchange, or after a topic shift)
+                self.spawn_session_title_update(message.channel_id, session.id);
+
                 DispatchResult::success_with_message_id(response, message_id)
             }
             Err(e) => {
@@ -1768,23 +2025,12 @@ impl MessageDispatcher {
 
         if tools.is_empty() {
             log::warn!("[TOOL_LOOP] No tools available, falling back to text-only generation");
-            let (content, payment) = effective_client.generate_text_with_events(messages, &self.broadcaster, original_message.channel_id).await?;
+            let (content, payment) = effective_client.generate_text_with_events(messages.clone(), &self.broadcaster, original_message.channel_id).await?;
             // Save x402 payment if one was made
             if let Some(ref payment_info) = payment {
-                if let Err(e) = self.db.record_x402_payment(
-                    Some(original_message.channel_id),
-                    None,
-                    payment_info.resource.as_deref(),
-                    &payment_info.amount,
-                    &payment_info.amount_formatted,
-                    &payment_info.asset,
-                    &payment_info.pay_to,
-                    payment_info.tx_hash.as_deref(),
-                    &payment_info.status.to_string(),
-                ) {
-                    log::error!("[TOOL_LOOP] Failed to record x402 payment: {}", e);
-                }
+                self.record_x402_payment(original_message.channel_id, session_id, payment_info);
             }
+            self.record_ai_usage(original_message.channel_id, session_id, &messages, &[], &content, payment.is_some());
             return Ok((content, false, None));
         }
 
@@ -1823,6 +2069,56 @@ impl MessageDispatcher {
     }
 }
 
+/// Keeps token streaming enabled for a channel until dropped
+/// (see `MessageDispatcher::stream_tokens_for_channel`)
+pub struct TokenStreamGuard {
+    channels: Arc<dashmap::DashMap<i64, usize>>,
+    channel_id: i64,
+}
+
+impl Drop for TokenStreamGuard {
+    fn drop(&mut self) {
+        self.channels.remove_if_mut(&self.channel_id, |_, count| {
+            *count -= 1;
+            *count == 0
+        });
+    }
+}
+
+/// Whether the message's channel gets a fresh session per message (gateway channels:
+/// Discord, Telegram, Matrix, web, external) rather than one persistent session per chat.
+/// Threaded messages always keep a persistent session per thread.
+/// Drop the older half of the conversation history (everything but system
+/// messages and the current message) to retry after a context overflow.
+/// Returns how many messages were dropped.
+fn drop_oldest_history(messages: &mut Vec<Message>) -> usize {
+    let Some(last) = messages.len().checked_sub(1) else {
+        return 0;
+    };
+    let history = messages[..last].iter().filter(|m| m.role != MessageRole::System).count();
+    let dropped = history.div_ceil(2);
+    let mut remaining = dropped;
+    messages.retain(|m| {
+        if remaining > 0 && m.role != MessageRole::System {
+            remaining -= 1;
+            false
+        } else {
+            true
+        }
+    });
+    dropped
+}
+
+fn uses_gateway_sessions(message: &NormalizedMessage) -> bool {
+    let channel_type_lower = message.channel_type.to_lowercase();
+    message.thread_id.is_none()
+        && (channel_type_lower == "discord"
+            || channel_type_lower == "telegram"
+            || channel_type_lower == "matrix"
+            || channel_type_lower == "web"
+            || channel_type_lower == "external_channel")
+}
+
 #[cfg(test)]
 #[path = "../dispatcher_tests.rs"]
 mod dispatcher_tests;
@@ -1831,10 +2127,31 @@ mod dispatcher_tests;
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_drop_oldest_history_keeps_system_and_current_message() {
+        let msg = |role, content: &str| Message { role, content: content.to_string() };
+        let mut messages = vec![
+            msg(MessageRole::System, "prompt"),
+            msg(MessageRole::User, "old question"),
+            msg(MessageRole::Assistant, "old answer"),
+            msg(MessageRole::User, "recent question"),
+            msg(MessageRole::Assistant, "recent answer"),
+            msg(MessageRole::User, "current"),
+        ];
+        assert_eq!(drop_oldest_history(&mut messages), 2);
+        let contents: Vec<&str> = messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["prompt", "recent question", "recent answer", "current"]);
+
+        let mut only_current = vec![msg(MessageRole::System, "prompt"), msg(MessageRole::User, "current")];
+        assert_eq!(drop_oldest_history(&mut only_current), 0);
+        assert_eq!(only_current.len(), 2);
+    }
+
     #[test]
     fn test_thinking_directive_pattern() {
         // Test the thinking directive pattern
-        let pattern = &*commands::THINKING_DIRECTIVE_PATTERN;
+        let defaults = commands::CommandConfig::default();
+        let pattern = defaults.thinking_directive_pattern();
 
         // Basic thinking directive
         let text = "/think";
@@ -1853,11 +2170,138 @@ mod tests {
 
     #[test]
     fn test_inline_thinking_pattern() {
-        let pattern = &*commands::INLINE_THINKING_PATTERN;
+        let defaults = commands::CommandConfig::default();
+        let pattern = defaults.inline_thinking_pattern();
 
         let text = "/t:medium What is the meaning of life?";
         let caps = pattern.captures(text).unwrap();
         assert_eq!(caps.get(1).map(|m| m.as_str()), Some("medium"));
         assert_eq!(caps.get(2).map(|m| m.as_str()), Some("What is the meaning of life?"));
     }
+
+    #[test]
+    fn test_command_config_prefix_and_aliases() {
+        let defaults = commands::CommandConfig::default();
+        assert_eq!(defaults.command("/reset"), Some("reset"));
+        assert_eq!(defaults.command(" /NEW "), Some("new"));
+        assert_eq!(defaults.command("/status"), Some("status"));
+        assert_eq!(defaults.command("reset"), None);
+
+        let aliases: std::collections::HashMap<String, String> = [
+            ("clear".to_string(), "reset".to_string()),
+            ("th".to_string(), "think".to_string()),
+            ("bogus".to_string(), "nope".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let config = commands::CommandConfig::new("!", Some(&aliases));
+        assert_eq!(config.command("!clear"), Some("reset"));
+        assert_eq!(config.command("!reset"), Some("reset"));
+        assert_eq!(config.command("/reset"), None);
+        assert_eq!(config.command("!bogus"), None);
+
+        // Thinking patterns honor the prefix and aliases
+        assert!(config.thinking_directive_pattern().is_match("!think:high"));
+        assert!(config.thinking_directive_pattern().is_match("!th"));
+        assert!(!config.thinking_directive_pattern().is_match("/think:high"));
+        let (level, text) = commands::parse_inline_thinking("!th:medium hello there", &config);
+        assert_eq!(level, Some(ThinkingLevel::Medium));
+        assert_eq!(text.as_deref(), Some("hello there"));
+
+        // Validation rejects unknown targets, shadowed built-ins and word-like prefixes
+        assert!(commands::CommandConfig::validate(Some("!"), Some(&aliases)).is_err());
+        let ok: std::collections::HashMap<String, String> =
+            [("clear".to_string(), "reset".to_string())].into_iter().collect();
+        assert!(commands::CommandConfig::validate(Some("!"), Some(&ok)).is_ok());
+        assert!(commands::CommandConfig::validate(Some(""), None).is_ok());
+        assert!(commands::CommandConfig::validate(Some("a"), None).is_err());
+        assert!(commands::CommandConfig::validate(Some("! "), None).is_err());
+        let shadow: std::collections::HashMap<String, String> =
+            [("new".to_string(), "status".to_string())].into_iter().collect();
+        assert!(commands::CommandConfig::validate(None, Some(&shadow)).is_err());
+    }
+
+    /// The "Relevant Memories" block of a shared channel's prompt only holds the
+    /// requesting identity's memories (plus shared ones), via hybrid and FTS search.
+    #[tokio::test]
+    async fn test_system_prompt_memories_are_scoped_to_identity() {
+        use crate::memory::embeddings::NullEmbeddingGenerator;
+        use crate::memory::{HybridSearchEngine, SHARED_MEMORY_IDENTITY};
+
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        let memory = |content: &str, identity: &str| {
+            db.insert_memory("long_term", content, None, None, 7, Some(identity), None, None, None, None, None, None)
+                .unwrap()
+        };
+        memory("Alice keeps her hardware wallet in a bank vault", "alice");
+        memory("Bob keeps his hardware wallet in a sock drawer", "bob");
+        memory("Hardware wallet firmware should always be updated", SHARED_MEMORY_IDENTITY);
+
+        let message = NormalizedMessage {
+            channel_id: 1,
+            channel_type: "discord".to_string(),
+            chat_id: "shared-channel".to_string(),
+            chat_name: None,
+            user_id: "alice-user".to_string(),
+            user_name: "Alice".to_string(),
+            text: "where is my hardware wallet".to_string(),
+            message_id: None,
+            session_mode: None,
+            selected_network: None,
+            force_safe_mode: false,
+            platform_role_ids: vec![],
+            chat_context: None,
+            thread_id: None,
+        };
+
+        let fts_only = MessageDispatcher::new_without_tools(db.clone(), Arc::new(EventBroadcaster::new()));
+        let hybrid = MessageDispatcher::new_without_tools(db.clone(), Arc::new(EventBroadcaster::new()))
+            .with_hybrid_search(Arc::new(HybridSearchEngine::new(db.clone(), Arc::new(NullEmbeddingGenerator))));
+        for dispatcher in [&fts_only, &hybrid] {
+            let prompt = dispatcher
+                .build_system_prompt(&message, "alice", &ToolConfig::default(), false, None)
+                .await;
+            assert!(prompt.contains("## Relevant Memories"));
+            assert!(prompt.contains("bank vault"));
+            assert!(prompt.contains("firmware"), "shared memories are visible to everyone");
+            assert!(!prompt.contains("sock drawer"), "bob's memory leaked into alice's prompt: {}", prompt);
+        }
+    }
+
+    /// Memories without an owner (e.g. written before identity scoping) are still
+    /// retrieved in a single-user standard-mode session.
+    #[tokio::test]
+    async fn test_system_prompt_includes_unowned_memories() {
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        db.insert_memory("long_term", "The operator prefers Base over mainnet", None, None, 7, None, None, None, None, None, None, None)
+            .unwrap();
+        db.insert_memory("preference", "Swaps should use the operator's ledger wallet", None, None, 7, None, None, None, None, None, None, None)
+            .unwrap();
+
+        let message = NormalizedMessage {
+            channel_id: 0,
+            channel_type: "web".to_string(),
+            chat_id: "web".to_string(),
+            chat_name: None,
+            user_id: "web-user".to_string(),
+            user_name: "Operator".to_string(),
+            text: "which wallet should swaps use".to_string(),
+            message_id: None,
+            session_mode: None,
+            selected_network: None,
+            force_safe_mode: false,
+            platform_role_ids: vec![],
+            chat_context: None,
+            thread_id: None,
+        };
+
+        let dispatcher = MessageDispatcher::new_without_tools(db.clone(), Arc::new(EventBroadcaster::new()));
+        let prompt = dispatcher
+            .build_system_prompt(&message, "operator", &ToolConfig::default(), false, None)
+            .await;
+        assert!(prompt.contains("## Memory\n"), "long-term block missing: {}", prompt);
+        assert!(prompt.contains("prefers Base"));
+        assert!(prompt.contains("## Relevant Memories"));
+        assert!(prompt.contains("ledger wallet"));
+    }
 }
@@ -0,0 +1,144 @@
+//! Replaying recorded rollouts against another resource version.
+
+use std::sync::Arc;
+
+use crate::ai::{AiError, MockAiClient};
+use crate::channels::types::NormalizedMessage;
+use crate::db::Database;
+use crate::execution::ExecutionTracker;
+use crate::gateway::events::EventBroadcaster;
+use crate::telemetry::{RecordedDispatch, ReplayOutcome, ReplayReport};
+
+use super::MessageDispatcher;
+
+impl MessageDispatcher {
+    /// Re-dispatch a recorded rollout's message with prompts resolved from `version_id`,
+    /// feeding the AI responses it recorded back through a mock client, and compare
+    /// the two runs.
+    ///
+    /// The replay runs in a throwaway in-memory database without a wallet, so it
+    /// leaves the live sessions alone and can't sign transactions. Other tools run
+    /// for real.
+    pub async fn replay_rollout(&self, rollout_id: &str, version_id: &str) -> Result<ReplayReport, String> {
+        let rollout = self.db.get_rollout(rollout_id)
+            .map_err(|e| format!("Failed to load rollout: {}", e))?
+            .ok_or_else(|| format!("Rollout '{}' not found", rollout_id))?;
+        let replay_version = self.db.get_resource_bundle(version_id)
+            .map_err(|e| format!("Failed to load resource version: {}", e))?
+            .ok_or_else(|| format!("Resource version '{}' not found", version_id))?;
+        let original_version = match rollout.resources_id.as_deref() {
+            Some(id) => self.db.get_resource_bundle(id).ok().flatten(),
+            None => None,
+        };
+
+        let spans = self.telemetry_store.get_rollout_spans(rollout_id);
+        let recorded = RecordedDispatch::from_spans(&spans)?;
+        let original = ReplayOutcome::from_spans(&spans, rollout.result.clone(), rollout.error.clone());
+
+        let replay_db = Arc::new(Database::new(":memory:")
+            .map_err(|e| format!("Failed to create replay database: {}", e))?);
+        let channel_id = self.seed_replay_db(&replay_db, &recorded, rollout.channel_id, &replay_version)?;
+
+        let broadcaster = Arc::new(EventBroadcaster::new());
+        let mock = MockAiClient::new(recorded.responses.iter().cloned().map(Ok::<_, AiError>).collect());
+        let skills_dir = std::path::PathBuf::from(crate::config::runtime_skills_dir());
+        let replayer = MessageDispatcher::new_with_wallet_and_skills(
+            replay_db.clone(),
+            broadcaster.clone(),
+            self.tool_registry.clone(),
+            Arc::new(ExecutionTracker::new(broadcaster)),
+            None,
+            Some(Arc::new(crate::skills::SkillRegistry::new(replay_db.clone(), skills_dir))),
+        )
+        .with_mock_ai_client(mock);
+
+        log::info!(
+            "[REPLAY] Replaying rollout {} ({} recorded AI responses) against resource version {}",
+            rollout_id,
+            recorded.responses.len(),
+            replay_version.label
+        );
+        let result = replayer.dispatch(NormalizedMessage {
+            channel_id,
+            channel_type: recorded.channel_type.clone(),
+            chat_id: "replay".to_string(),
+            chat_name: None,
+            user_id: "replay".to_string(),
+            user_name: recorded.user_name.clone(),
+            text: recorded.text.clone(),
+            message_id: None,
+            session_mode: None,
+            selected_network: None,
+            force_safe_mode: false,
+            platform_role_ids: vec![],
+            chat_context: None,
+            thread_id: None,
+        })
+        .await;
+
+        let replay_spans = replay_db.query_spans(None, None, None, None)
+            .map_err(|e| format!("Failed to read replay spans: {}", e))?;
+        let replay = ReplayOutcome::from_spans(
+            &replay_spans,
+            result.error.is_none().then(|| result.response.clone()),
+            result.error.clone(),
+        );
+
+        Ok(ReplayReport::new(
+            rollout_id.to_string(),
+            original_version.as_ref(),
+            &replay_version,
+            original,
+            replay,
+            recorded.responses.len(),
+            replayer.get_mock_trace().len(),
+        ))
+    }
+
+    /// Give the replay database what a dispatch needs: agent settings (without the
+    /// API key), a channel like the original, the enabled skills and the resource
+    /// version under test. Returns the replay channel's id.
+    fn seed_replay_db(
+        &self,
+        replay_db: &Database,
+        recorded: &RecordedDispatch,
+        original_channel_id: i64,
+        version: &crate::telemetry::ResourceBundle,
+    ) -> Result<i64, String> {
+        let settings = self.db.get_active_agent_settings()
+            .map_err(|e| format!("Failed to load agent settings: {}", e))?
+            .unwrap_or_default();
+        replay_db.save_agent_settings(
+            settings.endpoint_name.as_deref(),
+            "http://replay.invalid/v1/chat/completions",
+            &settings.model_archetype,
+            settings.model.as_deref(),
+            settings.max_response_tokens,
+            settings.max_context_tokens,
+            None,
+            "none",
+        )
+        .map_err(|e| format!("Failed to seed replay settings: {}", e))?;
+
+        let safe_mode = self.db.get_channel(original_channel_id)
+            .ok()
+            .flatten()
+            .is_some_and(|c| c.safe_mode);
+        let channel = replay_db
+            .create_channel_with_safe_mode(&recorded.channel_type, "replay", "replay", None, safe_mode)
+            .map_err(|e| format!("Failed to seed replay channel: {}", e))?;
+
+        for skill in self.db.list_enabled_skills().unwrap_or_default() {
+            if let Err(e) = replay_db.create_skill(&skill) {
+                log::warn!("[REPLAY] Failed to copy skill '{}': {}", skill.name, e);
+            }
+        }
+
+        let mut version = version.clone();
+        version.is_active = true;
+        replay_db.create_resource_bundle(&version)
+            .map_err(|e| format!("Failed to seed resource version: {}", e))?;
+
+        Ok(channel.id)
+    }
+}
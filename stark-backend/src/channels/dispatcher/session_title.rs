@@ -0,0 +1,257 @@
+//! Background generation of human-readable session titles
+//!
+//! After a successful dispatch, a session without a title gets one generated
+//! from its first messages. Titled sessions are re-checked every
+//! `RETITLE_CHECK_INTERVAL` messages and only retitled when recent user
+//! messages no longer share any keywords with the title (a topic shift).
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::ai::{AiClient, Message, MessageRole};
+use crate::config;
+use crate::db::Database;
+use crate::gateway::protocol::GatewayEvent;
+use crate::gateway::EventBroadcaster;
+use crate::memory::fts_utils::simple_stem;
+use crate::models::session_message::{MessageRole as DbMessageRole, SessionMessage};
+use crate::models::AgentSettings;
+use crate::wallet::WalletProvider;
+
+use super::MessageDispatcher;
+
+/// A session needs this many messages (a user message and a reply) before it is titled
+const MIN_MESSAGES_FOR_TITLE: i64 = 2;
+/// New messages since the last check before a topic shift is looked for
+const RETITLE_CHECK_INTERVAL: i64 = 20;
+/// Messages shown to the model when (re)titling
+const TITLE_CONTEXT_MESSAGES: i32 = 8;
+/// Characters kept from each message in the title prompt
+const TITLE_CONTEXT_MESSAGE_CHARS: usize = 500;
+/// Longest title stored
+const MAX_TITLE_CHARS: usize = 60;
+
+const TITLE_SYSTEM_PROMPT: &str = "You write short titles for chat conversations. \
+    Reply with the title only: 3-6 words, no quotes, no trailing punctuation.";
+
+/// What to do with a session's title after a dispatch
+#[derive(Debug, PartialEq, Eq)]
+enum TitleAction {
+    Skip,
+    Generate,
+    /// Topic unchanged: just move the check point forward
+    Checkpoint,
+}
+
+fn decide_title_action(current: Option<&(String, i64)>, message_count: i64, recent_user_text: &str) -> TitleAction {
+    if message_count < MIN_MESSAGES_FOR_TITLE {
+        return TitleAction::Skip;
+    }
+    let Some((title, checked_at)) = current else {
+        return TitleAction::Generate;
+    };
+    if message_count - checked_at < RETITLE_CHECK_INTERVAL {
+        return TitleAction::Skip;
+    }
+    if topic_shifted(title, recent_user_text) {
+        TitleAction::Generate
+    } else {
+        TitleAction::Checkpoint
+    }
+}
+
+/// Stemmed, lowercased significant words (3+ chars, no stop words)
+fn keywords(text: &str) -> HashSet<String> {
+    let stop_words = stop_words::get(stop_words::LANGUAGE::English);
+    text.split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|w| w.chars().count() >= 3 && !stop_words.contains(&w.as_str()))
+        .map(|w| simple_stem(&w))
+        .collect()
+}
+
+/// The topic shifted if none of the title's keywords appear in recent user messages
+fn topic_shifted(title: &str, recent_user_text: &str) -> bool {
+    let title_words = keywords(title);
+    if title_words.is_empty() || recent_user_text.trim().is_empty() {
+        return false;
+    }
+    title_words.is_disjoint(&keywords(recent_user_text))
+}
+
+/// Reduce a model reply to a clean single-line title
+fn clean_title(raw: &str) -> Option<String> {
+    let line = raw.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let line = line
+        .strip_prefix("Title:")
+        .or_else(|| line.strip_prefix("title:"))
+        .unwrap_or(line)
+        .trim_start_matches('#')
+        .trim()
+        .trim_matches(|c: char| matches!(c, '"' | '\'' | '*' | '`' | '“' | '”'))
+        .trim_end_matches(['.', '!', ':', ';', ','])
+        .trim();
+    if line.is_empty() {
+        return None;
+    }
+    let mut title: String = line.chars().take(MAX_TITLE_CHARS).collect();
+    if line.chars().count() > MAX_TITLE_CHARS {
+        if let Some(cut) = title.rfind(' ') {
+            title.truncate(cut);
+        }
+        title.push('…');
+    }
+    Some(title)
+}
+
+/// Conversation excerpt for the title prompt (user/assistant messages only)
+fn title_prompt(messages: &[SessionMessage]) -> String {
+    let conversation = messages
+        .iter()
+        .filter_map(|m| {
+            let role = match m.role {
+                DbMessageRole::User => "User",
+                DbMessageRole::Assistant => "Assistant",
+                _ => return None,
+            };
+            let content: String = m.content.chars().take(TITLE_CONTEXT_MESSAGE_CHARS).collect();
+            Some(format!("{}: {}", role, content))
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    format!("Write a title for this conversation:\n\n{}", conversation)
+}
+
+/// Client for titling: the configured preset if any, otherwise the active model
+fn title_client(
+    db: &Database,
+    preset_key: &str,
+    wallet_provider: Option<Arc<dyn WalletProvider>>,
+) -> Result<AiClient, String> {
+    let settings = if preset_key.is_empty() {
+        db.get_active_agent_settings()
+            .map_err(|e| format!("Failed to load agent settings: {}", e))?
+            .ok_or_else(|| "No active agent settings".to_string())?
+    } else {
+        let preset = crate::ai_endpoint_config::get_ai_endpoint(preset_key)
+            .ok_or_else(|| format!("Unknown AI endpoint preset '{}'", preset_key))?;
+        AgentSettings {
+            endpoint_name: Some(preset_key.to_string()),
+            endpoint: preset.endpoint,
+            model_archetype: preset.model_archetype,
+            model: preset.model,
+            ..AgentSettings::default()
+        }
+    };
+    AiClient::from_settings_with_wallet_provider(&settings, wallet_provider)
+}
+
+/// Generate (or regenerate) a session's title if needed and broadcast it
+async fn update_session_title(
+    db: Arc<Database>,
+    broadcaster: Arc<EventBroadcaster>,
+    wallet_provider: Option<Arc<dyn WalletProvider>>,
+    preset_key: String,
+    channel_id: i64,
+    session_id: i64,
+) -> Result<(), String> {
+    let message_count = db.count_session_messages(session_id).map_err(|e| e.to_string())?;
+    let current = db.get_session_title(session_id).map_err(|e| e.to_string())?;
+    let recent = db
+        .get_recent_session_messages(session_id, TITLE_CONTEXT_MESSAGES)
+        .map_err(|e| e.to_string())?;
+    let recent_user_text = recent
+        .iter()
+        .filter(|m| m.role == DbMessageRole::User)
+        .map(|m| m.content.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    match decide_title_action(current.as_ref(), message_count, &recent_user_text) {
+        TitleAction::Skip => return Ok(()),
+        TitleAction::Checkpoint => {
+            return db.set_session_title(session_id, None, message_count).map_err(|e| e.to_string());
+        }
+        TitleAction::Generate => {}
+    }
+
+    let client = title_client(&db, &preset_key, wallet_provider)?;
+    let reply = client
+        .generate_text(vec![
+            Message { role: MessageRole::System, content: TITLE_SYSTEM_PROMPT.to_string() },
+            Message { role: MessageRole::User, content: title_prompt(&recent) },
+        ])
+        .await?;
+    let title = clean_title(&reply).ok_or_else(|| "Model returned an empty title".to_string())?;
+
+    db.set_session_title(session_id, Some(&title), message_count)
+        .map_err(|e| e.to_string())?;
+    log::info!(
+        "[SESSION_TITLE] Session {} {}titled: {}",
+        session_id,
+        if current.is_some() { "re" } else { "" },
+        title
+    );
+    broadcaster.broadcast(GatewayEvent::session_title_updated(channel_id, session_id, &title));
+    Ok(())
+}
+
+impl MessageDispatcher {
+    /// Title the session in the background if it has none yet or its topic has shifted.
+    /// At most one title job runs per session at a time.
+    pub(super) fn spawn_session_title_update(&self, channel_id: i64, session_id: i64) {
+        // Tests and replays script every AI response; don't let titling consume them
+        if self.mock_ai_client.is_some() {
+            return;
+        }
+        let Some(preset_key) = config::session_title_model() else {
+            return;
+        };
+        if !self.titles_in_progress.insert(session_id) {
+            return;
+        }
+
+        let db = self.db.clone();
+        let broadcaster = self.broadcaster.clone();
+        let wallet_provider = self.wallet_provider.clone();
+        let in_progress = self.titles_in_progress.clone();
+        tokio::spawn(crate::telemetry::trace::in_current_trace(async move {
+            if let Err(e) = update_session_title(db, broadcaster, wallet_provider, preset_key, channel_id, session_id).await {
+                log::warn!("[SESSION_TITLE] Failed to title session {}: {}", session_id, e);
+            }
+            in_progress.remove(&session_id);
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decide_title_action() {
+        let title = ("Deploying contracts to Base".to_string(), 2);
+        assert_eq!(decide_title_action(None, 1, "hi"), TitleAction::Skip);
+        assert_eq!(decide_title_action(None, 2, "hi"), TitleAction::Generate);
+        // Not enough new messages to look for a shift
+        assert_eq!(decide_title_action(Some(&title), 10, "what's the weather"), TitleAction::Skip);
+        // Same topic: keywords still overlap ("deployed" stems to match "deploying")
+        assert_eq!(
+            decide_title_action(Some(&title), 30, "the contracts deployed, now verify them"),
+            TitleAction::Checkpoint
+        );
+        assert_eq!(
+            decide_title_action(Some(&title), 30, "what's the weather in Boston tomorrow?"),
+            TitleAction::Generate
+        );
+    }
+
+    #[test]
+    fn test_clean_title() {
+        assert_eq!(clean_title("\n\"Deploying to Base.\"\n").as_deref(), Some("Deploying to Base"));
+        assert_eq!(clean_title("Title: **Weather in Boston**").as_deref(), Some("Weather in Boston"));
+        assert_eq!(clean_title("  \n "), None);
+        let long = clean_title(&"word ".repeat(30)).unwrap();
+        assert!(long.chars().count() <= MAX_TITLE_CHARS + 1 && long.ends_with('…'));
+    }
+}
@@ -147,6 +147,7 @@ impl MessageDispatcher {
             },
             group: ToolGroup::System,
             hidden: false,
+            ..Default::default()
         })
     }
 
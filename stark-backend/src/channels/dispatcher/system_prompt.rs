@@ -95,6 +95,8 @@ impl MessageDispatcher {
                     prompt.push_str("**Extra Skills:**\n");
                     for skill_name in &grants.extra_skills {
                         match self.db.get_enabled_skill_by_name(skill_name) {
+                            // A skill needing a denied tool can't run, so it isn't offered
+                            Ok(Some(skill)) if skill.requires_tools.iter().any(|t| grants.denies_tool(t)) => {}
                             Ok(Some(skill)) => {
                                 prompt.push_str(&format!(
                                     "- `{}` — {}\n  - *Use with:* `use_skill(name: \"{}\")`\n",
@@ -116,7 +118,7 @@ impl MessageDispatcher {
 
                 // Only show explicitly-granted tools (not skill dependency tools)
                 let explicit_tools: Vec<&String> = grants.extra_tools.iter()
-                    .filter(|t| !skill_auto_tools.contains(t))
+                    .filter(|t| !skill_auto_tools.contains(t) && !grants.denies_tool(t))
                     .collect();
                 if !explicit_tools.is_empty() {
                     prompt.push_str("**Extra Tools:**\n");
@@ -167,10 +169,9 @@ impl MessageDispatcher {
 
         // Memory System: Active retrieval from DB
         // In safe mode, only show curated safemode memories.
-        // In standard mode, show top long-term memories + today's log + query-relevant results.
+        // In standard mode, show top long-term memories + today's log + query-relevant results,
+        // limited to memories visible to the requesting identity.
         {
-            let mem_identity: Option<&str> = if is_safe_mode { Some("safemode") } else { None };
-
             if is_safe_mode {
                 // Safe mode: only inject curated safemode long-term memories
                 if let Ok(memories) = self.db.get_long_term_memories(Some("safemode"), 10) {
@@ -185,7 +186,20 @@ impl MessageDispatcher {
                     }
                 }
             } else {
-                // Standard mode: today's log + relevant memories via search
+                // Standard mode: long-term memories + today's log + relevant memories via search
+
+                // Top long-term memories
+                if let Ok(memories) = self.db.get_long_term_memories_visible_to(identity_id, 10)
+                    && !memories.is_empty()
+                {
+                    prompt.push_str("## Memory\n");
+                    let text: String = memories.iter()
+                        .map(|m| m.content.as_str())
+                        .collect::<Vec<_>>()
+                        .join("\n\n");
+                    prompt.push_str(&truncate_tail_chars(&text, 2000));
+                    prompt.push_str("\n\n");
+                }
 
                 // Today's activity log
                 if let Ok(entries) = self.db.get_today_daily_log(Some(identity_id), 20) {
@@ -205,32 +219,39 @@ impl MessageDispatcher {
                 if !user_query.trim().is_empty() {
                     // Prefer hybrid search (FTS + vector + graph) when available
                     let mut found_memories = false;
-                    if let Some(ref hybrid) = self.hybrid_search {
-                        if let Ok(results) = hybrid.search_fast(user_query, 15, None).await {
-                            if !results.is_empty() {
-                                found_memories = true;
-                                prompt.push_str("## Relevant Memories\n");
-                                prompt.push_str("The following memories may be relevant to this conversation.\n");
-                                prompt.push_str("Use memory_read/memory_search tools to dig deeper if needed.\n\n");
-                                for (i, result) in results.iter().enumerate() {
-                                    let snippet: String = result.content.chars().take(200).collect();
-                                    prompt.push_str(&format!(
-                                        "[{}] (#{}, {}, importance: {}) {}\n",
-                                        i + 1, result.memory_id, result.memory_type, result.importance,
-                                        snippet.replace('\n', " ")
-                                    ));
-                                }
-                                prompt.push('\n');
-                            }
+                    if let Some(ref hybrid) = self.hybrid_search
+                        && let Ok(results) = hybrid.search_fast(user_query, 15, None, Some(identity_id)).await
+                        && !results.is_empty()
+                    {
+                        found_memories = true;
+                        let memory_ids: Vec<i64> = results.iter().map(|r| r.memory_id).collect();
+                        if let Err(e) = self.db.record_memory_hits(&memory_ids) {
+                            log::warn!("[MEMORY] Failed to record memory hits: {}", e);
+                        }
+                        prompt.push_str("## Relevant Memories\n");
+                        prompt.push_str("The following memories may be relevant to this conversation.\n");
+                        prompt.push_str("Use memory_read/memory_search tools to dig deeper if needed.\n\n");
+                        for (i, result) in results.iter().enumerate() {
+                            let snippet: String = result.content.chars().take(200).collect();
+                            prompt.push_str(&format!(
+                                "[{}] (#{}, {}, importance: {}) {}\n",
+                                i + 1, result.memory_id, result.memory_type, result.importance,
+                                snippet.replace('\n', " ")
+                            ));
                         }
+                        prompt.push('\n');
                     }
 
                     // Fallback: FTS search with stemming if hybrid search unavailable or empty
                     if !found_memories {
                         let fts_query = crate::memory::fts_utils::normalize_fts_query(user_query);
                         if !fts_query.is_empty() {
-                            if let Ok(results) = self.db.search_memories_fts(&fts_query, mem_identity, 15) {
+                            if let Ok(results) = self.db.search_memories_fts(&fts_query, Some(identity_id), 15) {
                                 if !results.is_empty() {
+                                    let memory_ids: Vec<i64> = results.iter().map(|(m, _)| m.id).collect();
+                                    if let Err(e) = self.db.record_memory_hits(&memory_ids) {
+                                        log::warn!("[MEMORY] Failed to record memory hits: {}", e);
+                                    }
                                     prompt.push_str("## Relevant Memories\n");
                                     prompt.push_str("The following memories may be relevant to this conversation.\n");
                                     prompt.push_str("Use memory_read/memory_search tools to dig deeper if needed.\n\n");
@@ -256,26 +277,12 @@ impl MessageDispatcher {
         if !is_safe_mode {
             let user_query = &message.text;
             if !user_query.trim().is_empty() {
-                let mut skill_matches: Vec<(crate::skills::types::DbSkill, f32)> = Vec::new();
-
-                // Try vector search first (requires hybrid search engine + embeddings)
-                if let Some(ref hybrid) = self.hybrid_search {
-                    let emb_gen = hybrid.embedding_generator().clone();
-                    if let Ok(matches) = crate::skills::embeddings::search_skills(
-                        &self.db, &emb_gen, user_query, 5, 0.30,
-                    ).await {
-                        skill_matches = matches;
-                    }
-                }
-
-                // Fallback: text-based search with stemming if vector returned nothing
-                if skill_matches.is_empty() {
-                    if let Ok(matches) = crate::skills::embeddings::search_skills_text(
-                        &self.db, user_query, 5,
-                    ) {
-                        skill_matches = matches;
-                    }
-                }
+                let emb_gen = self.hybrid_search.as_ref().map(|h| h.embedding_generator());
+                let skill_matches = match self.skill_registry {
+                    Some(ref registry) => registry.find_relevant_skills(user_query, 5, 0.30, emb_gen).await,
+                    None => crate::skills::embeddings::search_skills_text(&self.db, user_query, 5)
+                        .unwrap_or_default(),
+                };
 
                 if !skill_matches.is_empty() {
                     prompt.push_str("## Relevant Skills\n");
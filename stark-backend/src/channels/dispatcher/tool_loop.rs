@@ -6,7 +6,7 @@ use crate::channels::types::NormalizedMessage;
 use crate::gateway::protocol::GatewayEvent;
 use crate::models::session_message::MessageRole as DbMessageRole;
 use crate::models::TaskType;
-use crate::telemetry::Watchdog;
+use crate::telemetry::{self, Watchdog};
 use crate::tools::{ToolConfig, ToolContext, ToolDefinition};
 use std::sync::Arc;
 
@@ -14,6 +14,30 @@ use super::finalization::TaskAdvanceResult;
 use super::tool_processing::BatchState;
 use super::{MessageDispatcher, FALLBACK_MAX_TOOL_ITERATIONS};
 
+/// Max times a reply cut off by max_response_tokens is automatically continued
+const MAX_AUTO_CONTINUATIONS: usize = 2;
+
+/// Sent to the AI after a truncated reply to resume generation at the cut point
+const CONTINUATION_PROMPT: &str = "[SYSTEM] Your previous response was cut off because it reached the maximum \
+response length. Continue exactly where it stopped. Do not repeat anything or add a preamble.";
+
+/// Shown between auto-continued segments so the join is visible
+const CONTINUATION_MARKER: &str = "\n\n*[continued]*\n\n";
+
+/// Shown at the end of a reply that was still truncated after the last continuation
+const TRUNCATION_NOTICE: &str = "\n\n*[response truncated: maximum response length reached]*";
+
+/// Join auto-continued segments of a reply, marking each join and a final cut-off
+fn join_truncated_segments(mut segments: Vec<String>, last: &str, still_truncated: bool) -> String {
+    segments.push(last.to_string());
+    segments.retain(|s| !s.trim().is_empty());
+    let mut joined = segments.join(CONTINUATION_MARKER);
+    if still_truncated {
+        joined.push_str(TRUNCATION_NOTICE);
+    }
+    joined
+}
+
 impl MessageDispatcher {
     /// Generate response using native API tool calling with multi-agent orchestration
     pub(super) async fn generate_with_native_tools_orchestrated(
@@ -50,7 +74,9 @@ impl MessageDispatcher {
         }
 
         // Some APIs (MiniMax, Kimi) reject conversations with multiple system messages.
-        // Merge all system messages into the first one.
+        // Merge all system messages into the first one. The extra context is kept so the
+        // per-iteration system prompt rebuild below doesn't drop it.
+        let mut merged_system_context = String::new();
         if archetype.requires_single_system_message() {
             let mut merged_content = String::new();
             let mut non_system: Vec<Message> = Vec::new();
@@ -58,6 +84,8 @@ impl MessageDispatcher {
                 if msg.role == MessageRole::System {
                     if !merged_content.is_empty() {
                         merged_content.push_str("\n\n---\n\n");
+                        merged_system_context.push_str("\n\n---\n\n");
+                        merged_system_context.push_str(&msg.content);
                     }
                     merged_content.push_str(&msg.content);
                 } else {
@@ -102,6 +130,9 @@ impl MessageDispatcher {
         let mut no_tool_pending_retries: u32 = 0;
         const MAX_NO_TOOL_PENDING_RETRIES: u32 = 3;
 
+        // Text segments of a reply that hit max_response_tokens and was auto-continued
+        let mut truncated_segments: Vec<String> = Vec::new();
+
         loop {
             iterations += 1;
             log::info!(
@@ -255,9 +286,10 @@ impl MessageDispatcher {
                         if system_msg.role == MessageRole::System {
                             let orchestrator_prompt = orchestrator.get_system_prompt_with_resource_manager_and_channel(&self.resource_manager, Some(&original_message.channel_type));
                             system_msg.content = format!(
-                                "{}\n\n---\n\n{}",
+                                "{}\n\n---\n\n{}{}",
                                 orchestrator_prompt,
-                                archetype.enhance_system_prompt(&messages[0].content, &tools)
+                                archetype.enhance_system_prompt(&messages[0].content, &tools),
+                                merged_system_context
                             );
                         }
                     }
@@ -322,9 +354,10 @@ impl MessageDispatcher {
                     if system_msg.role == MessageRole::System {
                         let orchestrator_prompt = orchestrator.get_system_prompt_with_resource_manager_and_channel(&self.resource_manager, Some(&original_message.channel_type));
                         system_msg.content = format!(
-                            "{}\n\n---\n\n{}",
+                            "{}\n\n---\n\n{}{}",
                             orchestrator_prompt,
-                            archetype.enhance_system_prompt(&messages[0].content, &tools)
+                            archetype.enhance_system_prompt(&messages[0].content, &tools),
+                            merged_system_context
                         );
                     }
                 }
@@ -336,9 +369,10 @@ impl MessageDispatcher {
                 if system_msg.role == MessageRole::System {
                     let orchestrator_prompt = orchestrator.get_system_prompt_with_resource_manager_and_channel(&self.resource_manager, Some(&original_message.channel_type));
                     system_msg.content = format!(
-                        "{}\n\n---\n\n{}",
+                        "{}\n\n---\n\n{}{}",
                         orchestrator_prompt,
-                        archetype.enhance_system_prompt(&messages[0].content, &current_tools)
+                        archetype.enhance_system_prompt(&messages[0].content, &current_tools),
+                        merged_system_context
                     );
                 }
             }
@@ -352,6 +386,7 @@ impl MessageDispatcher {
             );
 
             // Generate with native tool support and progress notifications
+            let llm_started = std::time::Instant::now();
             let mut ai_response = match self.generate_with_progress(
                 &client,
                 conversation.clone(),
@@ -361,10 +396,18 @@ impl MessageDispatcher {
                 session_id,
             ).await {
                 Ok(response) => response,
+                Err(_) if self.execution_tracker.is_cancelled(original_message.channel_id) => {
+                    // Stopped mid-generation: wind down as a cancelled run, not a failed one
+                    log::info!("[ORCHESTRATED_LOOP] Execution cancelled during AI generation, stopping loop");
+                    was_cancelled = true;
+                    break;
+                }
                 Err(e) => {
-                    // Payment/infrastructure errors (402, timeouts) should NOT be retried here —
-                    // the HTTP client already retried 3 times internally.
+                    // Payment/infrastructure errors (402, 429, timeouts) should NOT be retried here —
+                    // the HTTP client already retried 3 times internally, and a rate limit is
+                    // left to the rollout retry, which waits out the provider's Retry-After.
                     let is_payment_or_infra = e.status_code == Some(402)
+                        || e.status_code == Some(429)
                         || e.message.contains("timed out")
                         || e.message.contains("operation timed out")
                         || e.message.contains("request failed");
@@ -427,6 +470,9 @@ impl MessageDispatcher {
                 }
             };
 
+            // Recorded before cleanup so a replay feeds the dispatcher the same raw response
+            telemetry::replay::record_llm_response(watchdog.collector(), &ai_response, llm_started.elapsed());
+
             // Strip model-specific artifacts (e.g. MiniMax <think> blocks)
             ai_response.content = archetype.clean_content(&ai_response.content);
 
@@ -446,21 +492,47 @@ impl MessageDispatcher {
                     &payment_info.pay_to,
                     payment_info.resource.as_deref(),
                 ));
-                let _ = self.db.record_x402_payment(
-                    Some(original_message.channel_id),
-                    None,
-                    payment_info.resource.as_deref(),
-                    &payment_info.amount,
-                    &payment_info.amount_formatted,
-                    &payment_info.asset,
-                    &payment_info.pay_to,
-                    payment_info.tx_hash.as_deref(),
-                    &payment_info.status.to_string(),
-                );
+                self.record_x402_payment(original_message.channel_id, session_id, payment_info);
             }
+            let tool_calls_json = serde_json::to_string(&ai_response.tool_calls).unwrap_or_default();
+            self.record_ai_usage(
+                original_message.channel_id,
+                session_id,
+                &conversation,
+                &tool_history,
+                &format!("{}{}", ai_response.content, tool_calls_json),
+                ai_response.x402_payment.is_some(),
+            );
 
             // If no tool calls, check if this is allowed
             if ai_response.tool_calls.is_empty() {
+                // Reply cut off by max_response_tokens: ask the AI to pick up where it stopped
+                if ai_response.is_truncated() && truncated_segments.len() < MAX_AUTO_CONTINUATIONS {
+                    log::info!(
+                        "[ORCHESTRATED_LOOP] Response truncated at max tokens ({} chars), auto-continuing ({}/{})",
+                        ai_response.content.len(),
+                        truncated_segments.len() + 1,
+                        MAX_AUTO_CONTINUATIONS
+                    );
+                    conversation.push(Message {
+                        role: MessageRole::Assistant,
+                        content: ai_response.content.clone(),
+                    });
+                    conversation.push(Message {
+                        role: MessageRole::User,
+                        content: CONTINUATION_PROMPT.to_string(),
+                    });
+                    truncated_segments.push(std::mem::take(&mut ai_response.content));
+                    continue;
+                }
+                if !truncated_segments.is_empty() || ai_response.is_truncated() {
+                    ai_response.content = join_truncated_segments(
+                        std::mem::take(&mut truncated_segments),
+                        &ai_response.content,
+                        ai_response.is_truncated(),
+                    );
+                }
+
                 // Check if the agent should have called tools but didn't
                 if let Some((warning_msg, attempt)) = orchestrator.check_tool_call_required() {
                     log::warn!(
@@ -802,7 +874,9 @@ impl MessageDispatcher {
         }
 
         // Some APIs (MiniMax, Kimi) reject conversations with multiple system messages.
-        // Merge all system messages into the first one.
+        // Merge all system messages into the first one. The extra context is kept so the
+        // per-iteration system prompt rebuild below doesn't drop it.
+        let mut merged_system_context = String::new();
         if archetype.requires_single_system_message() {
             let mut merged_content = String::new();
             let mut non_system: Vec<Message> = Vec::new();
@@ -810,6 +884,8 @@ impl MessageDispatcher {
                 if msg.role == MessageRole::System {
                     if !merged_content.is_empty() {
                         merged_content.push_str("\n\n---\n\n");
+                        merged_system_context.push_str("\n\n---\n\n");
+                        merged_system_context.push_str(&msg.content);
                     }
                     merged_content.push_str(&msg.content);
                 } else {
@@ -898,9 +974,10 @@ impl MessageDispatcher {
                     if system_msg.role == MessageRole::System {
                         let orchestrator_prompt = orchestrator.get_system_prompt_with_resource_manager_and_channel(&self.resource_manager, Some(&original_message.channel_type));
                         system_msg.content = format!(
-                            "{}\n\n---\n\n{}",
+                            "{}\n\n---\n\n{}{}",
                             orchestrator_prompt,
-                            archetype.enhance_system_prompt(&messages[0].content, &tools)
+                            archetype.enhance_system_prompt(&messages[0].content, &tools),
+                            merged_system_context
                         );
                     }
                 }
@@ -911,9 +988,10 @@ impl MessageDispatcher {
                 if system_msg.role == MessageRole::System {
                     let orchestrator_prompt = orchestrator.get_system_prompt_with_resource_manager_and_channel(&self.resource_manager, Some(&original_message.channel_type));
                     system_msg.content = format!(
-                        "{}\n\n---\n\n{}",
+                        "{}\n\n---\n\n{}{}",
                         orchestrator_prompt,
-                        archetype.enhance_system_prompt(&messages[0].content, &tools)
+                        archetype.enhance_system_prompt(&messages[0].content, &tools),
+                        merged_system_context
                     );
                 }
             }
@@ -926,11 +1004,21 @@ impl MessageDispatcher {
                 tools.iter().map(|t| &t.name).collect::<Vec<_>>()
             );
 
-            let (ai_content, payment) = match client.generate_text_with_events(
-                conversation.clone(),
-                &self.broadcaster,
-                original_message.channel_id,
-            ).await {
+            let llm_started = std::time::Instant::now();
+            let cancel_token = self.execution_tracker.get_cancellation_token(original_message.channel_id);
+            let generation = tokio::select! {
+                result = client.generate_text_with_events(
+                    conversation.clone(),
+                    &self.broadcaster,
+                    original_message.channel_id,
+                ) => result,
+                _ = cancel_token.cancelled() => {
+                    log::info!("[TEXT_ORCHESTRATED] Execution cancelled during AI generation, stopping loop");
+                    was_cancelled = true;
+                    break;
+                }
+            };
+            let (ai_content, payment) = match generation {
                 Ok(result) => result,
                 Err(e) => {
                     // AI generation failed - save summary of work done so far
@@ -957,19 +1045,23 @@ impl MessageDispatcher {
                 }
             };
 
+            telemetry::replay::record_llm_response(
+                watchdog.collector(),
+                &AiResponse::text(ai_content.clone()),
+                llm_started.elapsed(),
+            );
+
             if let Some(ref payment_info) = payment {
-                let _ = self.db.record_x402_payment(
-                    Some(original_message.channel_id),
-                    None,
-                    payment_info.resource.as_deref(),
-                    &payment_info.amount,
-                    &payment_info.amount_formatted,
-                    &payment_info.asset,
-                    &payment_info.pay_to,
-                    payment_info.tx_hash.as_deref(),
-                    &payment_info.status.to_string(),
-                );
+                self.record_x402_payment(original_message.channel_id, session_id, payment_info);
             }
+            self.record_ai_usage(
+                original_message.channel_id,
+                session_id,
+                &conversation,
+                &[],
+                &ai_content,
+                payment.is_some(),
+            );
 
             let parsed = archetype.parse_response(&ai_content);
 
@@ -3,7 +3,8 @@ use crate::ai::multi_agent::{
     Orchestrator, ProcessResult as OrchestratorResult,
 };
 use crate::channels::types::NormalizedMessage;
-use crate::gateway::protocol::GatewayEvent;
+use crate::execution::{PlanApprovalOutcome, PlanApprovalPolicy};
+use crate::gateway::protocol::{GatewayEvent, ToolResultEvent};
 use crate::models::session_message::MessageRole as DbMessageRole;
 use crate::telemetry::{self, Watchdog};
 use crate::tools::{ToolConfig, ToolContext, ToolDefinition};
@@ -56,6 +57,169 @@ pub(super) struct ToolCallProcessed {
 }
 
 impl MessageDispatcher {
+    /// Outside rogue mode, a tool with a cost estimate waits for the user to
+    /// approve that cost in the web chat's confirmation prompt. Returns the
+    /// result to hand back instead of executing, or None to run the tool
+    /// (no estimate, or the user already approved this exact call).
+    fn cost_confirmation_gate(
+        &self,
+        tool_name: &str,
+        tool_arguments: &Value,
+        tool_context: &ToolContext,
+        original_message: &NormalizedMessage,
+        session_id: i64,
+    ) -> Option<crate::tools::ToolResult> {
+        // Only the web chat can show the confirmation prompt
+        if !original_message.channel_type.eq_ignore_ascii_case("web") {
+            return None;
+        }
+        let is_rogue_mode = tool_context.extra
+            .get("rogue_mode_enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if is_rogue_mode {
+            return None;
+        }
+        let estimate = self.tool_registry.get(tool_name)?.estimate_cost(tool_arguments, tool_context)?;
+        if estimate.amount <= 0.0 {
+            return None;
+        }
+
+        let channel_id = original_message.channel_id;
+        if self.pending_confirmations.take_approval(channel_id, tool_name, tool_arguments) {
+            log::info!("[CONFIRMATION] Running approved '{}' ({})", tool_name, estimate.display());
+            return None;
+        }
+
+        let pending = self.pending_confirmations.add_pending_cost(
+            channel_id,
+            session_id,
+            tool_name.to_string(),
+            String::new(),
+            tool_arguments.clone(),
+            original_message.user_id.clone(),
+            estimate.clone(),
+        );
+        log::info!("[CONFIRMATION] '{}' needs approval: {}", tool_name, pending.description);
+        telemetry::emit_annotation("tool_cost_confirmation_requested", serde_json::json!({
+            "tool_name": tool_name,
+            "amount": estimate.amount,
+            "currency": estimate.currency,
+        }));
+        self.broadcaster.broadcast(GatewayEvent::confirmation_required(
+            channel_id,
+            &pending.id,
+            tool_name,
+            &pending.description,
+            tool_arguments,
+            Some(&estimate),
+        ));
+
+        Some(crate::tools::ToolResult::success(format!(
+            "This step costs {} ({}). Confirm or cancel it in the prompt above, then tell me to continue.",
+            estimate.display(),
+            estimate.description
+        )).with_metadata(serde_json::json!({
+            "requires_user_response": true,
+            "instruction": format!(
+                "WAIT for the user. Once they confirm, call {} again with the same arguments; if they cancel, do not retry it.",
+                tool_name
+            ),
+            "confirmation_id": pending.id,
+        })))
+    }
+
+    /// Whether plans on this message's channel wait for approval: the channel's
+    /// Plan Approval setting, else the subtype's `require_plan_approval` flag
+    /// (which runs the plan if the approval times out).
+    fn plan_approval_policy(&self, original_message: &NormalizedMessage, orchestrator: &Orchestrator) -> PlanApprovalPolicy {
+        let channel_policy = self.db
+            .get_channel_setting(original_message.channel_id, crate::models::ChannelSettingKey::PlanApproval.as_ref())
+            .ok()
+            .flatten()
+            .map(|v| PlanApprovalPolicy::from_str_or_default(&v))
+            .unwrap_or_default();
+        if channel_policy != PlanApprovalPolicy::Off {
+            return channel_policy;
+        }
+        let subtype_requires = agent_types::get_subtype_config(orchestrator.current_subtype_key())
+            .is_some_and(|c| c.require_plan_approval);
+        if subtype_requires {
+            PlanApprovalPolicy::ProceedOnTimeout
+        } else {
+            PlanApprovalPolicy::Off
+        }
+    }
+
+    /// Show the freshly planned task queue and wait for the user to approve it.
+    /// Returns true to execute the plan. Stopping the execution while waiting
+    /// counts as a rejection.
+    async fn plan_approval_gate(
+        &self,
+        original_message: &NormalizedMessage,
+        session_id: i64,
+        orchestrator: &Orchestrator,
+    ) -> bool {
+        let policy = self.plan_approval_policy(original_message, orchestrator);
+        if policy == PlanApprovalPolicy::Off {
+            return true;
+        }
+
+        let channel_id = original_message.channel_id;
+        let timeout = crate::config::plan_approval_timeout();
+        let tasks = &orchestrator.task_queue().tasks;
+        let decision = self.plan_approvals.request(channel_id, &original_message.user_id);
+        log::info!(
+            "[PLAN_APPROVAL] Waiting up to {}s for approval of {} planned tasks on channel {}",
+            timeout.as_secs(),
+            tasks.len(),
+            channel_id
+        );
+        self.broadcaster.broadcast(GatewayEvent::plan_approval_required(
+            channel_id,
+            &original_message.chat_id,
+            session_id,
+            tasks,
+            timeout.as_secs(),
+            policy.proceeds_on_timeout(),
+        ));
+
+        let cancel_token = self.execution_tracker.get_cancellation_token(channel_id);
+        let outcome = tokio::select! {
+            _ = cancel_token.cancelled() => PlanApprovalOutcome::Rejected,
+            result = tokio::time::timeout(timeout, decision) => match result {
+                Ok(Ok(true)) => PlanApprovalOutcome::Approved,
+                Ok(Ok(false)) | Ok(Err(_)) => PlanApprovalOutcome::Rejected,
+                Err(_) => PlanApprovalOutcome::TimedOut,
+            },
+        };
+        self.plan_approvals.clear(channel_id);
+
+        let proceed = match outcome {
+            PlanApprovalOutcome::Approved => true,
+            PlanApprovalOutcome::Rejected => false,
+            PlanApprovalOutcome::TimedOut => policy.proceeds_on_timeout(),
+        };
+        log::info!(
+            "[PLAN_APPROVAL] Plan on channel {} {} ({})",
+            channel_id,
+            outcome.as_str(),
+            if proceed { "executing" } else { "dropped" }
+        );
+        telemetry::emit_annotation("plan_approval", serde_json::json!({
+            "outcome": outcome.as_str(),
+            "proceeding": proceed,
+            "tasks": tasks.len(),
+        }));
+        self.broadcaster.broadcast(GatewayEvent::plan_approval_resolved(
+            channel_id,
+            session_id,
+            outcome.as_str(),
+            proceed,
+        ));
+        proceed
+    }
+
     /// Processes a single tool call: logging, orchestrator dispatch, skill handling,
     /// subtype checks, validators, execution, metadata processing (define_tasks,
     /// task_fully_completed, say_to_user, auto-complete), hooks, and DB persistence.
@@ -325,12 +489,25 @@ impl MessageDispatcher {
                     tool_config
                 };
 
-                // Run tool validators before execution
-                if let Some(ref validator_registry) = self.validator_registry {
+                // Attribute the call to a skill for per-skill metrics. use_skill runs
+                // before the skill becomes active, so tag it with the requested skill.
+                let metrics_skill: Option<String> = if tool_name == "use_skill" {
+                    tool_arguments.get("skill_name").and_then(|v| v.as_str()).map(|s| s.to_string())
+                } else {
+                    orchestrator.context().active_skill.as_ref().map(|s| s.name.clone())
+                };
+
+                // Paid tools wait for the user to approve their estimated cost,
+                // then run validators before execution
+                if let Some(gated) = self.cost_confirmation_gate(tool_name, tool_arguments, tool_context, original_message, session_id) {
+                    gated
+                } else if let Some(ref validator_registry) = self.validator_registry {
                     let validation_ctx = crate::tool_validators::ValidationContext::new(
                         tool_name.to_string(),
                         tool_arguments.clone(),
                         Arc::new(tool_context.clone()),
+                    ).with_args_schema(
+                        self.tool_registry.get(tool_name).and_then(|t| t.definition().args_schema()),
                     );
                     let validation_result = validator_registry.validate(&validation_ctx).await;
                     if let Some(error_msg) = validation_result.to_error_message() {
@@ -356,7 +533,7 @@ impl MessageDispatcher {
                         if tool_result.success {
                             orchestrator.record_tool_call(tool_name);
                         }
-                        watchdog.reward_emitter().tool_completed(tool_name, tool_result.success, duration_ms);
+                        watchdog.reward_emitter().tool_completed(tool_name, tool_result.success, duration_ms, metrics_skill.as_deref());
                         tool_result
                     }
                 } else {
@@ -375,7 +552,7 @@ impl MessageDispatcher {
                     if tool_result.success {
                         orchestrator.record_tool_call(tool_name);
                     }
-                    watchdog.reward_emitter().tool_completed(tool_name, tool_result.success, duration_ms);
+                    watchdog.reward_emitter().tool_completed(tool_name, tool_result.success, duration_ms, metrics_skill.as_deref());
                     tool_result
                 }
             }
@@ -538,17 +715,29 @@ impl MessageDispatcher {
                             "[ORCHESTRATED_LOOP] define_tasks: replacing queue with {} tasks",
                             task_descriptions.len()
                         );
+                        // Only a fresh plan is gated, not a re-plan of one already executing
+                        let queue = orchestrator.task_queue();
+                        let replanning = !queue.is_empty() && !queue.all_complete();
                         let available_tool_names: Vec<String> = tools.iter().map(|t| t.name.clone()).collect();
-                        let ctx = orchestrator.context_mut();
-                        ctx.task_queue =
+                        orchestrator.context_mut().task_queue =
                             crate::ai::multi_agent::types::TaskQueue::from_descriptions_with_tool_matching(task_descriptions, &available_tool_names);
-                        ctx.planner_completed = true;
-                        ctx.mode = AgentMode::Assistant;
-                        self.advance_to_next_task_or_complete(
-                            original_message.channel_id,
-                            session_id,
-                            orchestrator,
-                        );
+                        if replanning || self.plan_approval_gate(original_message, session_id, orchestrator).await {
+                            let ctx = orchestrator.context_mut();
+                            ctx.planner_completed = true;
+                            ctx.mode = AgentMode::Assistant;
+                            self.advance_to_next_task_or_complete(
+                                original_message.channel_id,
+                                session_id,
+                                orchestrator,
+                            );
+                        } else {
+                            // Drop the plan so the user's next message can revise it
+                            orchestrator.context_mut().task_queue = Default::default();
+                            processed.waiting_for_user_response = true;
+                            processed.user_question_content = Some(
+                                "Okay, I won't run that plan. Tell me what to change and I'll plan it again.".to_string(),
+                            );
+                        }
                         self.broadcast_task_queue_update(
                             original_message.channel_id,
                             session_id,
@@ -797,16 +986,17 @@ impl MessageDispatcher {
         // Telegram, AgentChat) skip say_to_user in their event handlers and instead
         // receive the content via the final result.response.
         if !is_duplicate_say_to_user {
-            self.broadcaster.broadcast(GatewayEvent::tool_result(
-                original_message.channel_id,
-                Some(&original_message.chat_id),
+            self.broadcaster.broadcast(GatewayEvent::tool_result(ToolResultEvent {
+                channel_id: original_message.channel_id,
+                chat_id: Some(&original_message.chat_id),
                 tool_name,
-                result.success,
+                success: result.success,
                 duration_ms,
-                &result.content,
-                is_safe_mode,
-                say_to_user_msg_id.as_deref(),
-            ));
+                content: &result.content,
+                safe_mode: is_safe_mode,
+                message_id: say_to_user_msg_id.as_deref(),
+                structured: result.structured.as_deref(),
+            }));
         }
 
         // Execute AfterToolCall hooks
@@ -820,7 +1010,7 @@ impl MessageDispatcher {
                     "content": result.content,
                 }));
             let hook_result = hook_manager.execute(HookEvent::AfterToolCall, &mut hook_context).await;
-            if let HookResult::Error(e) = hook_result {
+            if let HookResult::Error(e) | HookResult::Cancel(e) = hook_result {
                 log::warn!("Hook execution failed for tool '{}': {}", tool_name, e);
             }
         }
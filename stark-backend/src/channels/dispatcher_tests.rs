@@ -4,7 +4,7 @@
 //! to complete a task (say_to_user, task_fully_completed, or both), the user
 //! sees exactly 1 message across all channel types and modes.
 
-use crate::ai::{AiResponse, MockAiClient, TraceEntry, ToolCall};
+use crate::ai::{AiResponse, MockAiClient, MockStreamStep, TraceEntry, ToolCall};
 use crate::ai::multi_agent::types as agent_types;
 use crate::channels::dispatcher::MessageDispatcher;
 use crate::channels::types::{DispatchResult, NormalizedMessage};
@@ -48,6 +48,12 @@ impl TestHarness {
         force_safe_mode: bool,
         mock_responses: Vec<AiResponse>,
     ) -> Self {
+        let mock = MockAiClient::new(mock_responses.into_iter().map(Ok).collect());
+        Self::with_mock(channel_type, safe_mode, mock)
+    }
+
+    /// Build a test harness around an already-configured mock client (e.g. a scripted stream).
+    fn with_mock(channel_type: &str, safe_mode: bool, mock: MockAiClient) -> Self {
         // Load subtype registry so build_tool_list returns the correct tools
         ensure_subtype_registry();
 
@@ -106,7 +112,6 @@ impl TestHarness {
         let tool_registry = Arc::new(tools::create_default_registry());
 
         // Build dispatcher with mock AI client (include skill_registry so use_skill works)
-        let mock = MockAiClient::new(mock_responses.into_iter().map(Ok).collect());
         let dispatcher = MessageDispatcher::new_with_wallet_and_skills(
             db.clone(),
             broadcaster.clone(),
@@ -217,6 +222,7 @@ impl TestHarness {
             force_safe_mode,
             platform_role_ids: vec![],
             chat_context: None,
+            thread_id: None,
         }
     }
 
@@ -919,6 +925,7 @@ async fn swap_flow_realistic() {
         force_safe_mode: false,
         platform_role_ids: vec![],
             chat_context: None,
+            thread_id: None,
     };
 
     eprintln!("  Dispatching: \"{}\"", msg.text);
@@ -1852,3 +1859,401 @@ async fn test_build_tool_list_consistent_across_subtypes() {
     let names2: Vec<&str> = tools2.iter().map(|t| t.name.as_str()).collect();
     assert_eq!(names1, names2, "Same inputs should always produce same tool list");
 }
+
+/// /status is answered from session state without reaching the AI.
+#[tokio::test]
+async fn status_command_reports_session_without_ai() {
+    let responses = vec![AiResponse::with_tools(
+        String::new(),
+        vec![tool_call(
+            "say_to_user",
+            json!({"message": "Here's your answer", "finished_task": true}),
+        )],
+    )];
+    let mut harness = TestHarness::new("web", true, false, responses);
+
+    // No conversation yet
+    let (result, _) = harness.dispatch("/status", false).await;
+    assert!(result.error.is_none(), "status should succeed: {:?}", result.error);
+    assert!(result.response.contains("No active session"), "got: {}", result.response);
+    assert!(result.response.contains("Safe mode: on"));
+    assert!(harness.get_trace().is_empty(), "/status must not call the AI");
+
+    // After one exchange, the latest session is described
+    harness.dispatch("hello", false).await;
+    let (result, _) = harness.dispatch("/STATUS", false).await;
+    assert!(result.error.is_none(), "status should succeed: {:?}", result.error);
+    assert!(result.response.contains("Session: #"), "got: {}", result.response);
+    assert!(result.response.contains("Context: "));
+    assert!(result.response.contains("Subtype: "));
+    assert!(result.response.contains("Safe mode: on"));
+    assert_eq!(harness.get_trace().len(), 1, "only the regular message should reach the AI");
+}
+
+/// A reply cut off by max_response_tokens is continued and the segments joined with a marker.
+#[tokio::test]
+async fn truncated_response_is_auto_continued() {
+    let mut truncated = AiResponse::text("The first half of a long answer".to_string());
+    truncated.stop_reason = Some("max_tokens".to_string());
+    let responses = vec![truncated, AiResponse::text("and the second half.".to_string())];
+
+    let mut harness = TestHarness::new("web", false, false, responses);
+    let (result, _) = harness.dispatch("explain everything", false).await;
+
+    assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
+    assert_eq!(harness.get_trace().len(), 2, "truncated reply should trigger one continuation");
+    assert!(
+        result.response.contains("The first half of a long answer\n\n*[continued]*\n\nand the second half."),
+        "got: {}",
+        result.response
+    );
+    assert!(!result.response.contains("response truncated"));
+}
+
+/// Token streaming stays enabled for a channel until every SSE subscriber's guard is dropped.
+#[tokio::test]
+async fn token_stream_guards_are_counted_per_channel() {
+    let harness = TestHarness::new("web", false, false, vec![]);
+    let dispatcher = &harness.dispatcher;
+    assert!(!dispatcher.is_token_streaming(1));
+
+    let first = dispatcher.stream_tokens_for_channel(1);
+    let second = dispatcher.stream_tokens_for_channel(1);
+    assert!(dispatcher.is_token_streaming(1));
+    assert!(!dispatcher.is_token_streaming(2), "other channels are unaffected");
+
+    drop(first);
+    assert!(dispatcher.is_token_streaming(1), "one subscriber is still streaming");
+    drop(second);
+    assert!(!dispatcher.is_token_streaming(1));
+}
+
+/// Fresh gateway sessions carry exactly `gateway_previous_messages` messages from the previous one.
+#[tokio::test]
+async fn gateway_previous_messages_respects_bot_setting() {
+    use crate::models::session_message::MessageRole as DbMessageRole;
+    use crate::models::SessionScope;
+
+    let say = || {
+        AiResponse::with_tools(
+            String::new(),
+            vec![tool_call("say_to_user", json!({"message": "ok", "finished_task": true}))],
+        )
+    };
+    let mut harness = TestHarness::new("web", false, false, vec![say(), say()]);
+    let db = harness.dispatcher.db.clone();
+
+    let seed_previous_session = |db: &Database, channel_id: i64| {
+        let session = db
+            .create_gateway_session("web", channel_id, SessionScope::Dm, None)
+            .expect("create session");
+        for i in 0..10 {
+            db.add_session_message(session.id, DbMessageRole::User, &format!("earlier message {}", i), None, None, None, None)
+                .expect("add message");
+        }
+    };
+    let carried_over = |harness: &TestHarness| -> usize {
+        // System messages may be merged into one depending on the archetype, so count across all of them
+        let trace = harness.get_trace();
+        let input = &trace.last().expect("AI was called").input_messages;
+        input.iter().map(|m| m.content.matches("earlier message").count()).sum()
+    };
+
+    // At the limit: only the most recent N messages come through
+    db.update_bot_settings_full(None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, Some(3), None, None)
+        .expect("update settings");
+    seed_previous_session(&db, harness.channel_id);
+    harness.dispatch("hello again", false).await;
+    assert_eq!(carried_over(&harness), 3);
+
+    // 0 disables carryover entirely
+    db.update_bot_settings_full(None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, Some(0), None, None)
+        .expect("update settings");
+    seed_previous_session(&db, harness.channel_id);
+    harness.dispatch("and again", false).await;
+    assert_eq!(carried_over(&harness), 0);
+}
+
+/// A scripted stream reaches the gateway as ordered `stream.*` events before the tool runs.
+#[tokio::test]
+async fn scripted_stream_emits_gateway_events_in_order() {
+    let say = tool_call("say_to_user", json!({"message": "All done.", "finished_task": true}));
+    let mock = MockAiClient::scripted(vec![vec![
+        MockStreamStep::Content("Let me ".to_string()),
+        MockStreamStep::Delay(Duration::from_millis(20)),
+        MockStreamStep::Content("reply.".to_string()),
+        MockStreamStep::ToolCall(say.clone()),
+    ]]);
+    let mut harness = TestHarness::with_mock("web", false, mock);
+    let _stream = harness.dispatcher.stream_tokens_for_channel(harness.channel_id);
+
+    let (result, events) = harness.dispatch("hello", false).await;
+    assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
+
+    let order: Vec<&str> = events
+        .iter()
+        .map(|e| e.event.as_str())
+        .filter(|name| name.starts_with("stream.") || *name == "tool.result")
+        .collect();
+    assert_eq!(
+        order,
+        vec![
+            "stream.start",
+            "stream.content_delta",
+            "stream.content_delta",
+            "stream.tool_start",
+            "stream.tool_delta",
+            "stream.tool_complete",
+            "stream.end",
+            "tool.result",
+        ]
+    );
+
+    let deltas: Vec<&str> = events
+        .iter()
+        .filter(|e| e.event == "stream.content_delta")
+        .filter_map(|e| e.data.get("content").and_then(|v| v.as_str()))
+        .collect();
+    assert_eq!(deltas, vec!["Let me ", "reply."]);
+    let complete = events.iter().find(|e| e.event == "stream.tool_complete").unwrap();
+    assert_eq!(complete.data.get("tool_name").and_then(|v| v.as_str()), Some("say_to_user"));
+
+    let trace = harness.get_trace();
+    assert_eq!(trace.len(), 1);
+    let response = trace[0].output_response.as_ref().expect("scripted response");
+    assert_eq!(response.content, "Let me reply.");
+    assert_eq!(response.tool_calls[0].id, say.id);
+}
+
+/// Without a token stream the scripted reply is returned whole, after its scripted delay.
+#[tokio::test]
+async fn scripted_reply_waits_out_its_delay_when_not_streaming() {
+    let mock = MockAiClient::scripted(vec![vec![
+        MockStreamStep::Delay(Duration::from_millis(100)),
+        MockStreamStep::ToolCall(tool_call("say_to_user", json!({"message": "late", "finished_task": true}))),
+    ]]);
+    let mut harness = TestHarness::with_mock("web", false, mock);
+
+    let started = std::time::Instant::now();
+    let (result, events) = harness.dispatch("hello", false).await;
+    assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
+    assert!(started.elapsed() >= Duration::from_millis(100));
+    assert!(!events.iter().any(|e| e.event.starts_with("stream.")));
+    assert_eq!(count_user_messages(&events, &result.response), 1);
+}
+
+/// A recorded rollout replays its AI responses against another prompt version and reports both runs.
+#[tokio::test]
+async fn rollout_replays_against_a_new_resource_version() {
+    use crate::telemetry::{Resource, ResourceType, SpanType};
+
+    let say = || {
+        AiResponse::with_tools(
+            String::new(),
+            vec![tool_call("say_to_user", json!({"message": "Hi there!", "finished_task": true}))],
+        )
+    };
+    let mut harness = TestHarness::new("web", false, false, vec![say()]);
+    let (result, _) = harness.dispatch("say hi", false).await;
+    assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
+
+    let db = harness.dispatcher.db.clone();
+    let start = db.query_spans(Some(SpanType::Rollout), None, None, None).expect("query spans");
+    let rollout_id = start[0].rollout_id.clone();
+    let rollout = db.get_rollout(&rollout_id).unwrap().expect("rollout persisted");
+    assert!(rollout.session_id > 0, "session is persisted once resolved");
+    assert!(rollout.resources_id.is_some());
+
+    let version = harness.dispatcher.resource_manager().create_version(
+        "v2-terse".to_string(),
+        vec![Resource {
+            name: "system_prompt.assistant_skilled".to_string(),
+            resource_type: ResourceType::PromptTemplate,
+            content: "Be terse.".to_string(),
+            metadata: serde_json::Value::Null,
+        }],
+        None,
+    ).expect("create version");
+
+    let report = harness.dispatcher.replay_rollout(&rollout_id, &version.version_id).await.expect("replay");
+    assert_eq!(report.original_version_id, rollout.resources_id);
+    assert_eq!(report.replay_version_id, version.version_id);
+    assert!(report.changed_resources.contains(&"system_prompt.assistant_skilled".to_string()));
+    assert_eq!((report.recorded_responses, report.replayed_responses), (1, 1));
+    assert!(report.original.succeeded && report.replay.succeeded, "{:?}", report.replay.error);
+    assert_eq!(report.replay.tool_sequence, vec!["say_to_user"]);
+    assert!(report.same_tool_sequence);
+
+    assert!(harness.dispatcher.replay_rollout("missing", &version.version_id).await.is_err());
+}
+
+// ============================================================================
+// Plan approval: with the channel's `plan_approval` setting on, the planned
+// tasks wait for the user's chat reply before any of them run.
+// ============================================================================
+
+async fn dispatch_with_plan_reply(harness: &mut TestHarness, reply: &str) -> (DispatchResult, Vec<GatewayEvent>) {
+    let msg = harness.make_message("check the ETH price", false);
+    let reply = harness.make_message(reply, false);
+    let dispatcher = &harness.dispatcher;
+    let channel_id = harness.channel_id;
+
+    let (result, _) = tokio::join!(dispatcher.dispatch(msg), async {
+        timeout(Duration::from_secs(5), async {
+            while !dispatcher.has_pending_plan_approval(channel_id) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("plan should wait for approval");
+        let intercepted = dispatcher.dispatch(reply).await;
+        assert!(intercepted.error.is_none() && intercepted.response.is_empty());
+    });
+
+    let mut events = Vec::new();
+    while let Ok(Some(event)) = timeout(Duration::from_millis(50), harness.event_rx.recv()).await {
+        events.push(event);
+    }
+    (result, events)
+}
+
+fn plan_responses() -> Vec<AiResponse> {
+    vec![
+        AiResponse::with_tools(
+            String::new(),
+            vec![tool_call("define_tasks", json!({"tasks": ["TASK 1 — Look up the ETH price and report it."]}))],
+        ),
+        AiResponse::with_tools(
+            String::new(),
+            vec![tool_call("say_to_user", json!({"message": "ETH is at $3,000.", "finished_task": true}))],
+        ),
+    ]
+}
+
+fn plan_outcome(events: &[GatewayEvent]) -> Option<String> {
+    events
+        .iter()
+        .find(|e| e.event == "plan.approval_resolved")
+        .and_then(|e| e.data.get("outcome").and_then(|v| v.as_str()).map(str::to_string))
+}
+
+#[tokio::test]
+async fn plan_approval_runs_plan_once_approved() {
+    let mut harness = TestHarness::new("web", false, false, plan_responses());
+    harness.dispatcher.db.set_channel_setting(harness.channel_id, "plan_approval", "cancel").unwrap();
+
+    let (result, events) = dispatch_with_plan_reply(&mut harness, "approve").await;
+
+    assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
+    assert!(result.response.contains("ETH is at $3,000."), "got: {}", result.response);
+    assert_eq!(harness.get_trace().len(), 2, "planner and the approved task both run");
+
+    let required = events.iter().find(|e| e.event == "plan.approval_required").expect("plan shown to user");
+    assert_eq!(required.data["tasks"].as_array().map(Vec::len), Some(1));
+    assert_eq!(required.data["on_timeout"], "cancel");
+    assert_eq!(plan_outcome(&events).as_deref(), Some("approved"));
+}
+
+#[tokio::test]
+async fn plan_approval_drops_rejected_plan() {
+    let mut harness = TestHarness::new("web", false, false, plan_responses());
+    harness.dispatcher.db.set_channel_setting(harness.channel_id, "plan_approval", "proceed").unwrap();
+
+    let (result, events) = dispatch_with_plan_reply(&mut harness, "no").await;
+
+    assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
+    assert!(result.response.contains("won't run that plan"), "got: {}", result.response);
+    assert_eq!(harness.get_trace().len(), 1, "only the planner runs");
+    assert_eq!(plan_outcome(&events).as_deref(), Some("rejected"));
+    assert!(!harness.dispatcher.has_pending_plan_approval(harness.channel_id));
+}
+
+// ============================================================================
+// Cancelling an in-flight dispatch: a cancel that lands mid-generation winds
+// the run down as Cancelled (not Failed) and tells the UI it stopped.
+// ============================================================================
+
+#[tokio::test]
+async fn cancel_dispatch_stops_generation_and_marks_session_cancelled() {
+    let say = tool_call("say_to_user", json!({"message": "Too late.", "finished_task": true}));
+    let mock = MockAiClient::scripted(vec![vec![
+        MockStreamStep::Delay(Duration::from_secs(30)),
+        MockStreamStep::ToolCall(say),
+    ]]);
+    let mut harness = TestHarness::with_mock("web", false, mock);
+    let channel_id = harness.channel_id;
+    assert!(!harness.dispatcher.cancel_dispatch(channel_id).await, "nothing is running yet");
+
+    let msg = harness.make_message("take your time", false);
+    let dispatcher = &harness.dispatcher;
+    let (result, cancelled) = timeout(Duration::from_secs(5), async {
+        tokio::join!(dispatcher.dispatch(msg), async {
+            while dispatcher.execution_tracker.get_execution_id(channel_id).is_none() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            dispatcher.cancel_dispatch(channel_id).await
+        })
+    })
+    .await
+    .expect("cancel should interrupt the 30s generation");
+
+    assert!(cancelled);
+    assert!(result.error.is_none(), "a cancelled run is not an error: {:?}", result.error);
+
+    let mut events = Vec::new();
+    while let Ok(Some(event)) = timeout(Duration::from_millis(50), harness.event_rx.recv()).await {
+        events.push(event);
+    }
+    assert!(events.iter().any(|e| e.event == "execution.stopped"));
+    assert!(!events.iter().any(|e| e.event == "agent.error"));
+
+    let session_id = events
+        .iter()
+        .find(|e| e.event == "session.created")
+        .and_then(|e| e.data["session_id"].as_i64())
+        .expect("session created");
+    let session = harness.dispatcher.db.get_chat_session(session_id).unwrap().expect("session");
+    assert_eq!(session.completion_status, crate::models::CompletionStatus::Cancelled);
+}
+
+// ============================================================================
+// Special role denials: a role can take tools away from safe mode, and the
+// denial wins over the role's own grants.
+// ============================================================================
+
+#[tokio::test]
+async fn special_role_denied_tools_are_removed_from_safe_mode() {
+    use crate::models::SpecialRole;
+
+    let say = || vec![AiResponse::with_tools(
+        String::new(),
+        vec![tool_call("say_to_user", json!({"message": "Hi.", "finished_task": true}))],
+    )];
+
+    let mut baseline = TestHarness::new("web", true, false, say());
+    baseline.dispatch("hello", false).await;
+    let baseline_tools = baseline.get_trace()[0].input_tools.clone();
+    assert!(baseline_tools.iter().any(|t| t == "task_fully_completed"), "tools: {:?}", baseline_tools);
+
+    let mut harness = TestHarness::new("web", true, false, say());
+    let db = &harness.dispatcher.db;
+    db.upsert_special_role(&SpecialRole {
+        name: "no_completion".to_string(),
+        allowed_tools: vec!["task_fully_completed".to_string()],
+        allowed_skills: vec![],
+        denied_tools: vec!["task_fully_completed".to_string()],
+        description: None,
+        created_at: String::new(),
+        updated_at: String::new(),
+    })
+    .unwrap();
+    db.create_special_role_assignment("web", "test-user", "no_completion", None).unwrap();
+
+    let (result, _events) = harness.dispatch("hello", false).await;
+
+    assert!(result.error.is_none(), "dispatch should succeed: {:?}", result.error);
+    let tools = &harness.get_trace()[0].input_tools;
+    assert!(!tools.iter().any(|t| t == "task_fully_completed"), "denied tool offered: {:?}", tools);
+    assert!(tools.iter().any(|t| t == "say_to_user"), "tools: {:?}", tools);
+}
@@ -0,0 +1,712 @@
+use crate::channels::dispatcher::MessageDispatcher;
+use crate::channels::safe_mode_rate_limiter::SafeModeChannelRateLimiter;
+use crate::channels::types::{ChannelType, NormalizedMessage};
+use crate::channels::util;
+use crate::db::Database;
+use crate::gateway::events::EventBroadcaster;
+use crate::gateway::protocol::GatewayEvent;
+use crate::models::channel_settings::ChannelSettingKey;
+use crate::models::{Channel, ToolOutputVerbosity};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::oneshot;
+
+/// How long each /sync long-poll waits for new events
+const SYNC_TIMEOUT_MS: u64 = 30_000;
+/// Delay before retrying after a failed /sync
+const SYNC_RETRY_SECS: u64 = 5;
+/// How long a typing notification lasts before it must be refreshed
+const TYPING_TIMEOUT_MS: u64 = 30_000;
+
+// ---------------------------------------------------------------------------
+// Client-server API
+// ---------------------------------------------------------------------------
+
+/// Minimal Matrix client-server API client (access-token auth)
+#[derive(Clone)]
+struct MatrixClient {
+    http: reqwest::Client,
+    homeserver: String,
+    access_token: String,
+}
+
+impl MatrixClient {
+    fn new(homeserver: &str, access_token: &str) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_millis(SYNC_TIMEOUT_MS + 30_000))
+            .build()
+            .unwrap_or_default();
+        Self {
+            http,
+            homeserver: homeserver.trim_end_matches('/').to_string(),
+            access_token: access_token.to_string(),
+        }
+    }
+
+    async fn request(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<Value>,
+    ) -> Result<Value, String> {
+        let url = format!("{}/_matrix/client/v3{}", self.homeserver, path);
+        let mut req = self.http.request(method, &url).bearer_auth(&self.access_token);
+        if let Some(body) = body {
+            req = req.json(&body);
+        }
+
+        let resp = req.send().await.map_err(|e| format!("request failed: {}", e))?;
+        let status = resp.status();
+        let data: Value = resp.json().await.unwrap_or(Value::Null);
+        if status.is_success() {
+            Ok(data)
+        } else {
+            Err(format_matrix_error(status.as_u16(), &data))
+        }
+    }
+
+    /// Resolve the user ID that owns the access token
+    async fn whoami(&self) -> Result<String, String> {
+        let data = self.request(reqwest::Method::GET, "/account/whoami", None).await?;
+        data.get("user_id")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| "whoami response has no user_id".to_string())
+    }
+
+    async fn sync(&self, since: Option<&str>, timeout_ms: u64, filter: Option<&str>) -> Result<Value, String> {
+        let mut path = format!("/sync?timeout={}", timeout_ms);
+        if let Some(since) = since {
+            path.push_str(&format!("&since={}", urlencoding::encode(since)));
+        }
+        if let Some(filter) = filter {
+            path.push_str(&format!("&filter={}", urlencoding::encode(filter)));
+        }
+        self.request(reqwest::Method::GET, &path, None).await
+    }
+
+    async fn join_room(&self, room_id: &str) -> Result<(), String> {
+        let path = format!("/join/{}", urlencoding::encode(room_id));
+        self.request(reqwest::Method::POST, &path, Some(json!({}))).await?;
+        Ok(())
+    }
+
+    async fn send_event(&self, room_id: &str, content: Value) -> Result<String, String> {
+        let path = format!(
+            "/rooms/{}/send/m.room.message/{}",
+            urlencoding::encode(room_id),
+            uuid::Uuid::new_v4()
+        );
+        let data = self.request(reqwest::Method::PUT, &path, Some(content)).await?;
+        Ok(data.get("event_id").and_then(|v| v.as_str()).unwrap_or_default().to_string())
+    }
+
+    /// Send a plain-text message, optionally as a reply. Returns the event ID.
+    async fn send_text(&self, room_id: &str, text: &str, reply_to: Option<&str>) -> Result<String, String> {
+        let mut content = json!({ "msgtype": "m.text", "body": text });
+        if let Some(event_id) = reply_to {
+            content["m.relates_to"] = json!({ "m.in_reply_to": { "event_id": event_id } });
+        }
+        self.send_event(room_id, content).await
+    }
+
+    /// Replace the text of a message we sent (used for the status message)
+    async fn edit_text(&self, room_id: &str, event_id: &str, text: &str) -> Result<(), String> {
+        let content = json!({
+            "msgtype": "m.notice",
+            "body": format!("* {}", text),
+            "m.new_content": { "msgtype": "m.notice", "body": text },
+            "m.relates_to": { "rel_type": "m.replace", "event_id": event_id },
+        });
+        self.send_event(room_id, content).await.map(|_| ())
+    }
+
+    /// Show or clear the typing notification (shown for up to `TYPING_TIMEOUT_MS`)
+    async fn set_typing(&self, room_id: &str, user_id: &str, typing: bool) -> Result<(), String> {
+        let path = format!(
+            "/rooms/{}/typing/{}",
+            urlencoding::encode(room_id),
+            urlencoding::encode(user_id)
+        );
+        let body = if typing {
+            json!({ "typing": true, "timeout": TYPING_TIMEOUT_MS })
+        } else {
+            json!({ "typing": false })
+        };
+        self.request(reqwest::Method::PUT, &path, Some(body)).await?;
+        Ok(())
+    }
+
+    async fn redact(&self, room_id: &str, event_id: &str) -> Result<(), String> {
+        let path = format!(
+            "/rooms/{}/redact/{}/{}",
+            urlencoding::encode(room_id),
+            urlencoding::encode(event_id),
+            uuid::Uuid::new_v4()
+        );
+        self.request(reqwest::Method::PUT, &path, Some(json!({}))).await?;
+        Ok(())
+    }
+
+    /// Display name for a user, falling back to the user ID
+    async fn display_name(&self, user_id: &str) -> String {
+        let path = format!("/profile/{}/displayname", urlencoding::encode(user_id));
+        self.request(reqwest::Method::GET, &path, None)
+            .await
+            .ok()
+            .and_then(|d| d.get("displayname").and_then(|v| v.as_str()).map(|s| s.to_string()))
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| user_id.to_string())
+    }
+}
+
+/// Format a Matrix error response. Rate limits are rendered as "Retry after Xs"
+/// so `util::StatusThrottler` can back off.
+fn format_matrix_error(status: u16, data: &Value) -> String {
+    let errcode = data.get("errcode").and_then(|v| v.as_str()).unwrap_or("M_UNKNOWN");
+    let error = data.get("error").and_then(|v| v.as_str()).unwrap_or("");
+    let mut msg = format!("HTTP {} {}: {}", status, errcode, error);
+    if let Some(ms) = data.get("retry_after_ms").and_then(|v| v.as_u64()) {
+        msg.push_str(&format!(" (Retry after {}s)", ms.div_ceil(1000).max(1)));
+    }
+    msg
+}
+
+// ---------------------------------------------------------------------------
+// Sync parsing
+// ---------------------------------------------------------------------------
+
+/// A text message received from a joined room
+#[derive(Debug, Clone)]
+struct MatrixIncoming {
+    room_id: String,
+    event_id: String,
+    sender: String,
+    body: String,
+    mentions_bot: bool,
+}
+
+/// Rooms the bot has been invited to
+fn invited_rooms(sync: &Value) -> Vec<String> {
+    sync.pointer("/rooms/invite")
+        .and_then(|v| v.as_object())
+        .map(|rooms| rooms.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Joined member counts reported in this sync (only present when they change)
+fn member_counts(sync: &Value) -> Vec<(String, u64)> {
+    sync.pointer("/rooms/join")
+        .and_then(|v| v.as_object())
+        .map(|rooms| {
+            rooms
+                .iter()
+                .filter_map(|(room_id, room)| {
+                    room.pointer("/summary/m.joined_member_count")
+                        .and_then(|v| v.as_u64())
+                        .map(|n| (room_id.clone(), n))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Extract text messages from other users in joined rooms
+fn extract_messages(sync: &Value, bot_user_id: &str) -> Vec<MatrixIncoming> {
+    let Some(rooms) = sync.pointer("/rooms/join").and_then(|v| v.as_object()) else {
+        return Vec::new();
+    };
+
+    let mut messages = Vec::new();
+    for (room_id, room) in rooms {
+        let events = room
+            .pointer("/timeline/events")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        for event in events {
+            if event.get("type").and_then(|v| v.as_str()) != Some("m.room.message") {
+                continue;
+            }
+            let sender = event.get("sender").and_then(|v| v.as_str()).unwrap_or("");
+            if sender.is_empty() || sender == bot_user_id {
+                continue;
+            }
+            let content = event.get("content").cloned().unwrap_or(Value::Null);
+            // Skip edits (they arrive as separate events) and non-text messages
+            if content.pointer("/m.relates_to/rel_type").and_then(|v| v.as_str()) == Some("m.replace") {
+                continue;
+            }
+            if content.get("msgtype").and_then(|v| v.as_str()) != Some("m.text") {
+                continue;
+            }
+            let body = content.get("body").and_then(|v| v.as_str()).unwrap_or("").to_string();
+            if body.trim().is_empty() {
+                continue;
+            }
+
+            let mentions_bot = content
+                .pointer("/m.mentions/user_ids")
+                .and_then(|v| v.as_array())
+                .is_some_and(|ids| ids.iter().any(|id| id.as_str() == Some(bot_user_id)))
+                || body.contains(bot_user_id)
+                || content
+                    .get("formatted_body")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|f| f.contains(bot_user_id));
+
+            messages.push(MatrixIncoming {
+                room_id: room_id.clone(),
+                event_id: event.get("event_id").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                sender: sender.to_string(),
+                body,
+                mentions_bot,
+            });
+        }
+    }
+    messages
+}
+
+/// Strip the bot's user ID and a leading "localpart:" pill from message text
+fn strip_bot_mention(text: &str, bot_user_id: &str) -> String {
+    let text = text.replace(bot_user_id, "");
+    let localpart = bot_user_id
+        .trim_start_matches('@')
+        .split(':')
+        .next()
+        .unwrap_or("");
+    let trimmed = text.trim();
+    let stripped = if !localpart.is_empty()
+        && trimmed.get(..localpart.len()).is_some_and(|p| p.eq_ignore_ascii_case(localpart))
+        && trimmed[localpart.len()..].starts_with(':')
+    {
+        &trimmed[localpart.len() + 1..]
+    } else {
+        trimmed
+    };
+    stripped.trim_start_matches(':').trim().to_string()
+}
+
+// ---------------------------------------------------------------------------
+// Tool event formatting (same as Slack)
+// ---------------------------------------------------------------------------
+
+fn format_tool_call(tool_name: &str, verbosity: ToolOutputVerbosity) -> Option<String> {
+    match verbosity {
+        ToolOutputVerbosity::None => None,
+        _ => Some(format!("🔧 Calling: {}", tool_name)),
+    }
+}
+
+fn format_tool_result(
+    tool_name: &str,
+    success: bool,
+    duration_ms: i64,
+    verbosity: ToolOutputVerbosity,
+) -> Option<String> {
+    let status = if success { "✅" } else { "❌" };
+    match verbosity {
+        ToolOutputVerbosity::None => None,
+        _ => Some(format!("{} Result: {} ({} ms)", status, tool_name, duration_ms)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Message processing
+// ---------------------------------------------------------------------------
+
+struct MatrixState {
+    channel_id: i64,
+    dispatcher: Arc<MessageDispatcher>,
+    broadcaster: Arc<EventBroadcaster>,
+    client: MatrixClient,
+    bot_user_id: String,
+    admin_user_ids: Option<String>,
+    safe_mode_rate_limiter: SafeModeChannelRateLimiter,
+}
+
+async fn process_matrix_message(state: Arc<MatrixState>, msg: MatrixIncoming) {
+    let channel_id = state.channel_id;
+    let client = &state.client;
+    let user_id = msg.sender.clone();
+    let user_name = client.display_name(&user_id).await;
+
+    let clean_text = strip_bot_mention(&msg.body, &state.bot_user_id);
+    let clean_text = if clean_text.is_empty() {
+        "hello".to_string()
+    } else {
+        clean_text
+    };
+
+    log::info!(
+        "Matrix: Message from {} ({}) in {}: {}",
+        user_name,
+        user_id,
+        msg.room_id,
+        if clean_text.len() > 50 {
+            format!("{}...", clean_text.chars().take(50).collect::<String>())
+        } else {
+            clean_text.clone()
+        }
+    );
+
+    // Determine safe mode from admin setting
+    let force_safe_mode = match &state.admin_user_ids {
+        Some(admin_ids) => {
+            let is_admin = admin_ids.split(',').map(|s| s.trim()).any(|id| id == user_id);
+            if is_admin {
+                log::info!("Matrix: User {} ({}) is admin — full access", user_name, user_id);
+                false
+            } else {
+                log::info!("Matrix: User {} ({}) is not admin — using safe mode", user_name, user_id);
+                true
+            }
+        }
+        None => false,
+    };
+
+    // Check safe mode rate limit for non-admin queries
+    if force_safe_mode
+        && let Err(rate_limit_msg) = state.safe_mode_rate_limiter.check_and_record_query(&user_id, "matrix")
+    {
+        log::info!("Matrix: Rate limiting user {} - {}", user_id, rate_limit_msg);
+        let _ = client
+            .send_text(&msg.room_id, &format!("\u{231b} {}", rate_limit_msg), Some(&msg.event_id))
+            .await;
+        return;
+    }
+
+    let normalized = NormalizedMessage {
+        channel_id,
+        channel_type: ChannelType::Matrix.to_string(),
+        chat_id: msg.room_id.clone(),
+        chat_name: None,
+        user_id: user_id.clone(),
+        user_name: user_name.clone(),
+        text: format!("[MATRIX MESSAGE]\n\n{}", clean_text),
+        message_id: Some(msg.event_id.clone()),
+        session_mode: None,
+        selected_network: None,
+        force_safe_mode,
+        platform_role_ids: vec![],
+        chat_context: None,
+        thread_id: None,
+    };
+
+    // Subscribe to events for real-time tool call forwarding
+    let (client_id, mut event_rx) = state.broadcaster.subscribe();
+    let client_for_events = client.clone();
+    let room_for_events = msg.room_id.clone();
+
+    let event_task = tokio::spawn(async move {
+        let mut status_event: Option<String> = None;
+        let verbosity = ToolOutputVerbosity::MinimalThrottled;
+        let mut throttler = util::StatusThrottler::default_for_gateway();
+
+        while let Some(event) = event_rx.recv().await {
+            if !util::event_matches_session(&event.data, channel_id, &room_for_events) {
+                continue;
+            }
+
+            let tool_name = event.data.get("tool_name").and_then(|v| v.as_str()).unwrap_or("unknown");
+            let text = match event.event.as_str() {
+                "agent.tool_call" => format_tool_call(tool_name, verbosity.display_verbosity()),
+                "tool.result" if tool_name != "say_to_user" => {
+                    let success = event.data.get("success").and_then(|v| v.as_bool()).unwrap_or(false);
+                    let duration_ms = event.data.get("duration_ms").and_then(|v| v.as_i64()).unwrap_or(0);
+                    format_tool_result(tool_name, success, duration_ms, verbosity.display_verbosity())
+                }
+                "plan.approval_required" => Some(util::format_plan_approval(&event.data)),
+                _ => None,
+            };
+
+            let Some(text) = text else { continue };
+            let is_first = status_event.is_none();
+            let is_plan = event.event == "plan.approval_required";
+            if verbosity.is_throttled() && !is_plan && !throttler.should_send(is_first) {
+                continue;
+            }
+
+            let result = match &status_event {
+                Some(event_id) => client_for_events.edit_text(&room_for_events, event_id, &text).await,
+                None => client_for_events
+                    .send_event(&room_for_events, json!({ "msgtype": "m.notice", "body": text }))
+                    .await
+                    .map(|event_id| status_event = Some(event_id)),
+            };
+            match result {
+                Ok(()) => throttler.record_success(),
+                Err(e) => {
+                    if !throttler.record_error(&e) {
+                        log::warn!("Matrix: Failed to update status message: {}", e);
+                    }
+                }
+            }
+        }
+
+        status_event
+    });
+
+    // Dispatch to AI
+    log::info!("Matrix: Dispatching message to AI for user {}", user_name);
+    let typing_client = client.clone();
+    let (typing_room, typing_user) = (msg.room_id.clone(), state.bot_user_id.clone());
+    let typing = util::TypingIndicator::start(
+        &state.broadcaster,
+        ChannelType::Matrix,
+        channel_id,
+        &msg.room_id,
+        move |typing| {
+            let client = typing_client.clone();
+            let (room_id, user_id) = (typing_room.clone(), typing_user.clone());
+            async move { client.set_typing(&room_id, &user_id, typing).await }
+        },
+    );
+    let result = state.dispatcher.dispatch_safe(normalized).await;
+    drop(typing);
+    // The indicator may still be showing if the completion event wasn't handled before drop
+    let _ = client.set_typing(&msg.room_id, &state.bot_user_id, false).await;
+    log::info!("Matrix: Dispatch complete, error={:?}", result.error);
+
+    state.broadcaster.unsubscribe(&client_id);
+
+    // Wait for the event task to finish, then remove the status message
+    let status_event = match tokio::time::timeout(std::time::Duration::from_millis(2000), event_task).await {
+        Ok(Ok(event_id)) => event_id,
+        Ok(Err(e)) => {
+            log::warn!("Matrix: Event task panicked: {}", e);
+            None
+        }
+        Err(_) => {
+            log::warn!("Matrix: Event task timed out — status message may not be removed");
+            None
+        }
+    };
+    if let Some(event_id) = status_event
+        && let Err(e) = client.redact(&msg.room_id, &event_id).await
+    {
+        log::warn!("Matrix: Failed to redact status message: {}", e);
+    }
+
+    // Send final response as a reply to the user's message
+    if result.error.is_none() && !result.response.is_empty() {
+        let max_len = ChannelType::Matrix.max_message_len();
+        let chunks = util::split_message(&result.response, max_len);
+        state
+            .dispatcher
+            .outbound_limiter()
+            .deliver(&msg.room_id, chunks, max_len, |chunk| {
+                let client = &client;
+                let (room_id, event_id) = (&msg.room_id, &msg.event_id);
+                async move { client.send_text(room_id, &chunk, Some(event_id)).await.map(|_| ()) }
+            })
+            .await;
+    } else if let Some(error) = result.error {
+        let error_msg = format!("Sorry, I encountered an error: {}", error);
+        let _ = client.send_text(&msg.room_id, &error_msg, Some(&msg.event_id)).await;
+    } else {
+        log::debug!("Matrix: Empty final response for user {}", user_name);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Public entry point
+// ---------------------------------------------------------------------------
+
+/// Start a Matrix listener that long-polls /sync and dispatches messages to the AI.
+/// Replies to every message in direct rooms (two members) and to mentions elsewhere.
+pub async fn start_matrix_listener(
+    channel: Channel,
+    dispatcher: Arc<MessageDispatcher>,
+    broadcaster: Arc<EventBroadcaster>,
+    db: Arc<Database>,
+    safe_mode_rate_limiter: SafeModeChannelRateLimiter,
+    mut shutdown_rx: oneshot::Receiver<()>,
+) -> Result<(), String> {
+    let channel_id = channel.id;
+    let channel_name = channel.name.clone();
+
+    let homeserver = db
+        .get_channel_setting(channel_id, ChannelSettingKey::MatrixHomeserverUrl.as_ref())
+        .ok()
+        .flatten()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| "Matrix channels require a homeserver URL".to_string())?;
+    if channel.bot_token.is_empty() {
+        return Err("Matrix channels require an access token".to_string());
+    }
+
+    log::info!("Starting Matrix listener for channel: {} ({})", channel_name, homeserver);
+
+    let client = MatrixClient::new(&homeserver, &channel.bot_token);
+    let bot_user_id = client
+        .whoami()
+        .await
+        .map_err(|e| format!("Matrix whoami failed — invalid access token: {}", e))?;
+    log::info!("Matrix: Logged in as {}", bot_user_id);
+
+    // Load admin user IDs setting
+    let admin_user_ids: Option<String> = db
+        .get_channel_setting(channel_id, ChannelSettingKey::MatrixAdminUserIds.as_ref())
+        .ok()
+        .flatten()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    if let Some(ref ids) = admin_user_ids {
+        log::info!(
+            "Matrix [{}]: Admin user IDs configured: {} — non-admin users will use safe mode",
+            channel_name,
+            ids
+        );
+    } else {
+        log::info!(
+            "Matrix [{}]: No admin user IDs configured — all users get full access",
+            channel_name
+        );
+    }
+
+    // Initial sync only fetches the sync token so the backlog isn't replayed
+    let initial_filter = r#"{"room":{"timeline":{"limit":1}}}"#;
+    let initial = client
+        .sync(None, 0, Some(initial_filter))
+        .await
+        .map_err(|e| format!("Matrix initial sync failed: {}", e))?;
+    let mut since = initial
+        .get("next_batch")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let mut joined_members: HashMap<String, u64> = member_counts(&initial).into_iter().collect();
+    for room_id in invited_rooms(&initial) {
+        match client.join_room(&room_id).await {
+            Ok(()) => log::info!("Matrix: Joined room {}", room_id),
+            Err(e) => log::warn!("Matrix: Failed to join room {}: {}", room_id, e),
+        }
+    }
+
+    broadcaster.broadcast(GatewayEvent::channel_started(
+        channel_id,
+        ChannelType::Matrix.as_str(),
+        &channel_name,
+    ));
+
+    let state = Arc::new(MatrixState {
+        channel_id,
+        dispatcher,
+        broadcaster: broadcaster.clone(),
+        client: client.clone(),
+        bot_user_id: bot_user_id.clone(),
+        admin_user_ids,
+        safe_mode_rate_limiter,
+    });
+
+    let mut result = Ok(());
+    loop {
+        let sync = tokio::select! {
+            _ = &mut shutdown_rx => {
+                log::info!("Matrix listener {} received shutdown signal", channel_name);
+                break;
+            }
+            sync = client.sync(Some(&since), SYNC_TIMEOUT_MS, None) => sync,
+        };
+
+        let sync = match sync {
+            Ok(s) => s,
+            Err(e) if e.contains("M_UNKNOWN_TOKEN") => {
+                result = Err(format!("Matrix access token rejected: {}", e));
+                break;
+            }
+            Err(e) => {
+                log::warn!("Matrix: Sync failed, retrying in {}s: {}", SYNC_RETRY_SECS, e);
+                tokio::time::sleep(std::time::Duration::from_secs(SYNC_RETRY_SECS)).await;
+                continue;
+            }
+        };
+
+        if let Some(next) = sync.get("next_batch").and_then(|v| v.as_str()) {
+            since = next.to_string();
+        }
+
+        for room_id in invited_rooms(&sync) {
+            match client.join_room(&room_id).await {
+                Ok(()) => log::info!("Matrix: Joined room {}", room_id),
+                Err(e) => log::warn!("Matrix: Failed to join room {}: {}", room_id, e),
+            }
+        }
+        joined_members.extend(member_counts(&sync));
+
+        for msg in extract_messages(&sync, &bot_user_id) {
+            let is_direct = joined_members.get(&msg.room_id).is_some_and(|n| *n <= 2);
+            if !is_direct && !msg.mentions_bot {
+                continue;
+            }
+            tokio::spawn(process_matrix_message(state.clone(), msg));
+        }
+    }
+
+    broadcaster.broadcast(GatewayEvent::channel_stopped(
+        channel_id,
+        ChannelType::Matrix.as_str(),
+        &channel_name,
+    ));
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_messages() {
+        let sync = json!({
+            "next_batch": "s2",
+            "rooms": {
+                "invite": { "!new:example.org": {} },
+                "join": {
+                    "!room:example.org": {
+                        "summary": { "m.joined_member_count": 2 },
+                        "timeline": { "events": [
+                            { "type": "m.room.message", "event_id": "$1", "sender": "@alice:example.org",
+                              "content": { "msgtype": "m.text", "body": "hello bot" } },
+                            { "type": "m.room.message", "event_id": "$2", "sender": "@stark:example.org",
+                              "content": { "msgtype": "m.text", "body": "my own reply" } },
+                            { "type": "m.room.message", "event_id": "$3", "sender": "@alice:example.org",
+                              "content": { "msgtype": "m.text", "body": "* edited",
+                                           "m.relates_to": { "rel_type": "m.replace", "event_id": "$1" } } },
+                            { "type": "m.room.message", "event_id": "$4", "sender": "@bob:example.org",
+                              "content": { "msgtype": "m.text", "body": "stark: what's gas?",
+                                           "m.mentions": { "user_ids": ["@stark:example.org"] } } },
+                            { "type": "m.room.member", "event_id": "$5", "sender": "@carol:example.org",
+                              "content": { "membership": "join" } }
+                        ] }
+                    }
+                }
+            }
+        });
+
+        let messages = extract_messages(&sync, "@stark:example.org");
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].event_id, "$1");
+        assert!(!messages[0].mentions_bot);
+        assert_eq!(messages[1].sender, "@bob:example.org");
+        assert!(messages[1].mentions_bot);
+
+        assert_eq!(invited_rooms(&sync), vec!["!new:example.org"]);
+        assert_eq!(member_counts(&sync), vec![("!room:example.org".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_strip_bot_mention_and_errors() {
+        assert_eq!(strip_bot_mention("stark: what's gas?", "@stark:example.org"), "what's gas?");
+        assert_eq!(strip_bot_mention("@stark:example.org: hi", "@stark:example.org"), "hi");
+        assert_eq!(strip_bot_mention("starknet is cool", "@stark:example.org"), "starknet is cool");
+
+        let err = format_matrix_error(429, &json!({ "errcode": "M_LIMIT_EXCEEDED", "retry_after_ms": 2500 }));
+        assert_eq!(util::parse_retry_after(&err), Some(3));
+    }
+}
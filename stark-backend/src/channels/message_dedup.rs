@@ -0,0 +1,82 @@
+//! Deduplication of inbound platform messages
+//!
+//! On reconnect, some platforms redeliver recent messages (Discord gateway
+//! resumes, Slack Socket Mode retries, Matrix sync replays). Message IDs seen
+//! within the TTL window are remembered so a redelivered message is skipped
+//! instead of producing a second reply.
+
+use moka::sync::Cache;
+use std::time::Duration;
+
+/// How long a processed message ID is remembered
+const DEDUP_TTL: Duration = Duration::from_secs(600); // 10 min
+/// Max message IDs remembered across all channels
+const DEDUP_CAPACITY: u64 = 10_000;
+
+/// Channel types whose listeners receive platform message IDs that can be redelivered.
+/// Internal sources (web, cron, hooks) generate their own IDs and are not deduplicated.
+const DEDUP_CHANNEL_TYPES: &[&str] = &["discord", "telegram", "slack", "matrix", "twitter"];
+
+/// Remembers recently processed `(channel_type, channel_id, message_id)` keys.
+/// Cheap to clone; clones share the same cache.
+#[derive(Clone)]
+pub struct MessageDeduplicator {
+    seen: Cache<String, ()>,
+}
+
+impl Default for MessageDeduplicator {
+    fn default() -> Self {
+        Self::new(DEDUP_TTL)
+    }
+}
+
+impl MessageDeduplicator {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            seen: Cache::builder()
+                .time_to_live(ttl)
+                .max_capacity(DEDUP_CAPACITY)
+                .build(),
+        }
+    }
+
+    /// Record a message and return true if it was already processed within the window.
+    /// Messages without an ID, or from channel types that aren't deduplicated, are never duplicates.
+    /// The channel ID is part of the key so two bots in the same server don't suppress each other.
+    pub fn check_and_record(&self, channel_type: &str, channel_id: i64, message_id: Option<&str>) -> bool {
+        let message_id = match message_id {
+            Some(id) if !id.is_empty() => id,
+            _ => return false,
+        };
+        let channel_type = channel_type.to_lowercase();
+        if !DEDUP_CHANNEL_TYPES.contains(&channel_type.as_str()) {
+            return false;
+        }
+
+        let key = format!("{}:{}:{}", channel_type, channel_id, message_id);
+        !self.seen.entry(key).or_insert(()).is_fresh()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_and_record() {
+        let dedup = MessageDeduplicator::default();
+
+        assert!(!dedup.check_and_record("discord", 1, Some("123")));
+        assert!(dedup.check_and_record("discord", 1, Some("123")));
+        assert!(dedup.check_and_record("Discord", 1, Some("123")));
+        // Same ID on another channel or platform is a different message
+        assert!(!dedup.check_and_record("discord", 2, Some("123")));
+        assert!(!dedup.check_and_record("telegram", 1, Some("123")));
+
+        // No ID, or an internal source, is never deduplicated
+        assert!(!dedup.check_and_record("discord", 1, None));
+        assert!(!dedup.check_and_record("discord", 1, None));
+        assert!(!dedup.check_and_record("cron", 0, Some("cron-run-1")));
+        assert!(!dedup.check_and_record("cron", 0, Some("cron-run-1")));
+    }
+}
@@ -1,5 +1,9 @@
 pub mod discord;
+pub mod dispatch_limiter;
 pub mod dispatcher;
+pub mod matrix;
+pub mod message_dedup;
+pub mod outbound_rate_limiter;
 pub mod safe_mode_rate_limiter;
 pub mod session_writer;
 pub mod slack;
@@ -9,6 +13,8 @@ pub mod types;
 pub mod util;
 
 pub use dispatcher::MessageDispatcher;
+pub use message_dedup::MessageDeduplicator;
+pub use outbound_rate_limiter::{OutboundLimits, OutboundRateLimiter};
 pub use safe_mode_rate_limiter::{SafeModeChannelRateLimiter, SafeModeQueryResult};
 pub use types::{ChannelHandle, ChannelType, NormalizedMessage};
 
@@ -35,6 +41,8 @@ pub struct ChannelManager {
     wallet_provider: Option<Arc<dyn crate::wallet::WalletProvider>>,
     tx_queue: Option<Arc<TxQueueManager>>,
     skill_registry: Option<Arc<crate::skills::SkillRegistry>>,
+    /// Processed message IDs, shared across listeners so restarts don't reprocess redelivered messages
+    message_dedup: MessageDeduplicator,
 }
 
 impl ChannelManager {
@@ -49,6 +57,7 @@ impl ChannelManager {
             wallet_provider: None,
             tx_queue: None,
             skill_registry: None,
+            message_dedup: MessageDeduplicator::default(),
         }
     }
 
@@ -79,6 +88,7 @@ impl ChannelManager {
             wallet_provider,
             tx_queue: None,
             skill_registry: None,
+            message_dedup: MessageDeduplicator::default(),
         }
     }
 
@@ -99,6 +109,19 @@ impl ChannelManager {
         self.running_channels.contains_key(&channel_id)
     }
 
+    /// Abort the dispatch a channel listener is running. Listeners track their
+    /// executions apart from the shared dispatcher, so they are cancelled here.
+    /// Returns false if the listener wasn't running a dispatch.
+    pub fn cancel_dispatch(&self, channel_id: i64) -> bool {
+        if self.execution_tracker.get_execution_id(channel_id).is_none() {
+            return false;
+        }
+        log::info!("[CHANNEL_MANAGER] Cancelling running dispatch on channel {}", channel_id);
+        self.execution_tracker.cancel_execution(channel_id);
+        self.execution_tracker.cancel_all_sessions_for_channel(channel_id);
+        true
+    }
+
     /// Get list of running channel IDs
     pub fn running_channel_ids(&self) -> Vec<i64> {
         self.running_channels.iter().map(|e| *e.key()).collect()
@@ -122,6 +145,7 @@ impl ChannelManager {
                 "discord" => "discord_bot_token",
                 "telegram" => "telegram_bot_token",
                 "slack" => "slack_bot_token",
+                "matrix" => "matrix_access_token",
                 _ => "", // Twitter and ExternalChannel don't use bot_token
             };
             if !setting_key.is_empty() {
@@ -145,6 +169,13 @@ impl ChannelManager {
         // Create shutdown channel
         let (shutdown_tx, shutdown_rx) = oneshot::channel();
 
+        // Pace outbound replies per platform (overridable via outbound_messages_per_minute)
+        let outbound_limiter = OutboundRateLimiter::new(OutboundLimits::from_settings(
+            &self.db,
+            channel_id,
+            &channel_type,
+        ));
+
         // Create dispatcher with or without tools (and wallet provider for x402 payment support)
         let dispatcher = if let Some(ref tool_registry) = self.tool_registry {
             let mut disp = MessageDispatcher::new_with_wallet_and_skills(
@@ -159,12 +190,16 @@ impl ChannelManager {
             if let Some(ref tx_queue) = self.tx_queue {
                 disp = disp.with_tx_queue(tx_queue.clone());
             }
-            Arc::new(disp)
+            Arc::new(
+                disp.with_outbound_limiter(outbound_limiter)
+                    .with_message_dedup(self.message_dedup.clone()),
+            )
         } else {
-            Arc::new(MessageDispatcher::new_without_tools(
-                self.db.clone(),
-                self.broadcaster.clone(),
-            ))
+            Arc::new(
+                MessageDispatcher::new_without_tools(self.db.clone(), self.broadcaster.clone())
+                    .with_outbound_limiter(outbound_limiter)
+                    .with_message_dedup(self.message_dedup.clone()),
+            )
         };
 
         // Store handle
@@ -278,6 +313,29 @@ impl ChannelManager {
                     running_channels.remove(&channel_id);
                 });
             }
+            types::ChannelType::Matrix => {
+                let db = self.db.clone();
+                let safe_mode_rate_limiter = SafeModeChannelRateLimiter::new(db.clone());
+                tokio::spawn(async move {
+                    let result = matrix::start_matrix_listener(
+                        channel,
+                        dispatcher,
+                        broadcaster.clone(),
+                        db,
+                        safe_mode_rate_limiter,
+                        shutdown_rx,
+                    )
+                    .await;
+
+                    if let Err(e) = result {
+                        log::error!("Matrix listener error: {}", e);
+                        broadcaster.broadcast(GatewayEvent::channel_error(channel_id, &e));
+                    }
+
+                    // Remove from running channels
+                    running_channels.remove(&channel_id);
+                });
+            }
             types::ChannelType::ExternalChannel => {
                 // No listener needed — HTTP request/response model.
                 // Channel being in running_channels is sufficient.
@@ -0,0 +1,314 @@
+//! Outbound delivery rate limiter for channel messages
+//!
+//! Paces messages the bot sends so long tool loops can't trip platform rate
+//! limits. Each send reserves the next free slot for its chat (and for the
+//! channel as a whole) and waits for it, so bursts are queued rather than
+//! rejected. Only when a message would wait longer than `max_queue_delay` are
+//! the remaining chunks coalesced into one final message.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::db::Database;
+use crate::models::channel_settings::ChannelSettingKey;
+
+/// Longest a message may sit in the queue before the rest of a reply is coalesced
+const DEFAULT_MAX_QUEUE_DELAY: Duration = Duration::from_secs(60);
+
+/// Notice appended when trailing chunks had to be cut
+const COALESCE_NOTICE: &str = "\n\n[...message truncated: outbound rate limit reached]";
+
+/// Pacing limits for one channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutboundLimits {
+    /// Minimum gap between two messages to the same chat/room
+    pub per_chat_interval: Duration,
+    /// Minimum gap between any two messages sent by the channel (bot-wide limit)
+    pub channel_interval: Duration,
+    /// Longest a message may wait for its slot before coalescing kicks in
+    pub max_queue_delay: Duration,
+}
+
+impl Default for OutboundLimits {
+    fn default() -> Self {
+        Self {
+            per_chat_interval: Duration::from_secs(1),
+            channel_interval: Duration::from_millis(100),
+            max_queue_delay: DEFAULT_MAX_QUEUE_DELAY,
+        }
+    }
+}
+
+impl OutboundLimits {
+    /// Defaults based on each platform's documented limits
+    pub fn for_channel_type(channel_type: &str) -> Self {
+        let (per_chat_ms, channel_ms) = match channel_type {
+            // ~1 msg/s per chat, 30 msg/s per bot
+            "telegram" => (1000, 35),
+            // 5 msgs per 5s per channel, 50 requests/s globally
+            "discord" => (1000, 20),
+            // chat.postMessage allows ~1 msg/s per channel
+            "slack" => (1000, 100),
+            // Synapse's default rc_message is conservative for non-exempt accounts
+            "matrix" => (1000, 250),
+            // Posting is heavily limited; replies are rare anyway
+            "twitter" => (5000, 5000),
+            _ => return Self::default(),
+        };
+        Self {
+            per_chat_interval: Duration::from_millis(per_chat_ms),
+            channel_interval: Duration::from_millis(channel_ms),
+            max_queue_delay: DEFAULT_MAX_QUEUE_DELAY,
+        }
+    }
+
+    /// Platform defaults, with the per-chat pace overridden by the channel's
+    /// `outbound_messages_per_minute` setting when set
+    pub fn from_settings(db: &Database, channel_id: i64, channel_type: &str) -> Self {
+        let mut limits = Self::for_channel_type(channel_type);
+        let per_minute = db
+            .get_channel_setting(channel_id, ChannelSettingKey::OutboundMessagesPerMinute.as_ref())
+            .ok()
+            .flatten()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|n| *n > 0);
+        if let Some(per_minute) = per_minute {
+            limits.per_chat_interval = Duration::from_millis(60_000 / per_minute.min(60_000));
+        }
+        limits
+    }
+}
+
+#[derive(Debug, Default)]
+struct OutboundState {
+    /// Next free slot for the channel as a whole
+    channel_next: Option<Instant>,
+    /// Next free slot per chat ID
+    chat_next: HashMap<String, Instant>,
+    /// Messages cut because the queue was saturated
+    coalesced: u64,
+}
+
+/// Per-channel outbound rate limiter. Cheap to clone; clones share state.
+#[derive(Clone)]
+pub struct OutboundRateLimiter {
+    limits: OutboundLimits,
+    state: Arc<Mutex<OutboundState>>,
+}
+
+impl Default for OutboundRateLimiter {
+    fn default() -> Self {
+        Self::new(OutboundLimits::default())
+    }
+}
+
+impl OutboundRateLimiter {
+    pub fn new(limits: OutboundLimits) -> Self {
+        Self {
+            limits,
+            state: Arc::new(Mutex::new(OutboundState::default())),
+        }
+    }
+
+    pub fn limits(&self) -> OutboundLimits {
+        self.limits
+    }
+
+    /// Number of chunks cut so far because the queue was saturated
+    pub fn coalesced_count(&self) -> u64 {
+        self.state.lock().unwrap().coalesced
+    }
+
+    /// Reserve the next slot for `chat_id` and return how long to wait for it.
+    /// Returns None (and reserves nothing) if the wait would exceed
+    /// `max_queue_delay`, unless `force` is set.
+    fn reserve(&self, chat_id: &str, force: bool) -> Option<Duration> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+
+        let chat_next = state.chat_next.get(chat_id).copied().unwrap_or(now);
+        let channel_next = state.channel_next.unwrap_or(now);
+        let slot = now.max(chat_next).max(channel_next);
+        let wait = slot - now;
+        if !force && wait > self.limits.max_queue_delay {
+            return None;
+        }
+
+        state.channel_next = Some(slot + self.limits.channel_interval);
+        state.chat_next.insert(chat_id.to_string(), slot + self.limits.per_chat_interval);
+        // Forget chats whose slots are long past
+        state.chat_next.retain(|_, next| *next + Duration::from_secs(60) > now);
+        Some(wait)
+    }
+
+    /// Wait for the next slot to send to `chat_id`.
+    /// Returns false if the queue is saturated and the message should not be sent.
+    pub async fn acquire(&self, chat_id: &str) -> bool {
+        match self.reserve(chat_id, false) {
+            Some(wait) => {
+                if !wait.is_zero() {
+                    log::debug!("Outbound: Pacing message to {} by {}ms", chat_id, wait.as_millis());
+                    tokio::time::sleep(wait).await;
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Back off after the platform reported a rate limit for `chat_id`
+    pub fn record_rate_limited(&self, chat_id: &str, retry_after: Duration) {
+        let until = Instant::now() + retry_after;
+        let mut state = self.state.lock().unwrap();
+        state.channel_next = Some(state.channel_next.map_or(until, |next| next.max(until)));
+        let next = state.chat_next.entry(chat_id.to_string()).or_insert(until);
+        *next = (*next).max(until);
+        log::warn!(
+            "Outbound: Platform rate limit hit for {}, pausing deliveries for {}s",
+            chat_id,
+            retry_after.as_secs()
+        );
+    }
+
+    /// Send `chunks` to `chat_id` through `send`, paced by the limiter.
+    ///
+    /// A chunk that fails with a "Retry after Xs" error is retried once after
+    /// backing off. If the queue saturates, the remaining chunks are coalesced
+    /// into a single truncated message (capped at `max_len`) as a last resort.
+    /// Returns the number of messages sent.
+    pub async fn deliver<F, Fut>(&self, chat_id: &str, chunks: Vec<String>, max_len: usize, mut send: F) -> usize
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = Result<(), String>>,
+    {
+        let mut sent = 0;
+        let mut pending = chunks.into_iter();
+
+        while let Some(chunk) = pending.next() {
+            let chunk = if self.acquire(chat_id).await {
+                chunk
+            } else {
+                let rest: Vec<String> = std::iter::once(chunk).chain(pending.by_ref()).collect();
+                let coalesced = coalesce_chunks(&rest, max_len);
+                self.state.lock().unwrap().coalesced += rest.len() as u64;
+                log::warn!(
+                    "Outbound: Queue saturated for {}, coalescing {} remaining chunk(s) into one message",
+                    chat_id,
+                    rest.len()
+                );
+                if let Some(wait) = self.reserve(chat_id, true) {
+                    tokio::time::sleep(wait).await;
+                }
+                coalesced
+            };
+
+            match send(chunk.clone()).await {
+                Ok(()) => sent += 1,
+                Err(e) => match super::util::parse_retry_after(&e) {
+                    Some(secs) => {
+                        self.record_rate_limited(chat_id, Duration::from_secs(secs.max(1)));
+                        if let Some(wait) = self.reserve(chat_id, true) {
+                            tokio::time::sleep(wait).await;
+                        }
+                        match send(chunk).await {
+                            Ok(()) => sent += 1,
+                            Err(e) => log::error!("Outbound: Failed to deliver message to {} after retry: {}", chat_id, e),
+                        }
+                    }
+                    None => log::error!("Outbound: Failed to deliver message to {}: {}", chat_id, e),
+                },
+            }
+        }
+
+        sent
+    }
+}
+
+/// Join chunks into one message no longer than `max_len`, marking the cut
+fn coalesce_chunks(chunks: &[String], max_len: usize) -> String {
+    let joined = chunks.join("\n");
+    if joined.len() <= max_len {
+        return joined;
+    }
+    let mut end = max_len.saturating_sub(COALESCE_NOTICE.len());
+    while end > 0 && !joined.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{}", &joined[..end], COALESCE_NOTICE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(per_chat_ms: u64, channel_ms: u64, max_delay_ms: u64) -> OutboundLimits {
+        OutboundLimits {
+            per_chat_interval: Duration::from_millis(per_chat_ms),
+            channel_interval: Duration::from_millis(channel_ms),
+            max_queue_delay: Duration::from_millis(max_delay_ms),
+        }
+    }
+
+    /// Real time elapses between calls, so compare waits with some slack
+    fn assert_wait(wait: Option<Duration>, expected_ms: u64) {
+        let wait = wait.expect("slot should be reserved").as_millis() as i64;
+        assert!((wait - expected_ms as i64).abs() < 50, "waited {}ms, expected ~{}ms", wait, expected_ms);
+    }
+
+    #[test]
+    fn test_reserve_paces_per_chat_and_channel() {
+        let limiter = OutboundRateLimiter::new(limits(1000, 100, 60_000));
+
+        assert_wait(limiter.reserve("a", false), 0);
+        assert_wait(limiter.reserve("a", false), 1000);
+        // Another chat only waits for the channel-wide gap
+        assert_wait(limiter.reserve("b", false), 1100);
+
+        // A platform rate limit pushes every slot back
+        limiter.record_rate_limited("a", Duration::from_secs(10));
+        assert_wait(limiter.reserve("c", false), 10_000);
+    }
+
+    #[tokio::test]
+    async fn test_deliver_coalesces_when_saturated() {
+        let limiter = OutboundRateLimiter::new(limits(100, 0, 150));
+        // Another sender already queued two messages for this room
+        limiter.reserve("room", false);
+        limiter.reserve("room", false);
+        let chunks: Vec<String> = (0..3).map(|i| format!("chunk {}", i)).collect();
+
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let log = sent.clone();
+        let count = limiter
+            .deliver("room", chunks, 100, move |text| {
+                log.lock().unwrap().push(text);
+                async { Ok(()) }
+            })
+            .await;
+
+        // The first chunk would wait 200ms (> 150ms), so the reply goes out as one message
+        assert_eq!(count, 1);
+        assert_eq!(sent.lock().unwrap().as_slice(), ["chunk 0\nchunk 1\nchunk 2"]);
+        assert_eq!(limiter.coalesced_count(), 3);
+
+        // Once the backlog drains, chunks are paced instead of coalesced
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let count = limiter
+            .deliver("room", vec!["x".into(), "y".into()], 100, |_| async { Ok(()) })
+            .await;
+        assert_eq!(count, 2);
+        assert_eq!(limiter.coalesced_count(), 3);
+    }
+
+    #[test]
+    fn test_coalesce_truncates() {
+        let chunks = vec!["a".repeat(80), "b".repeat(80)];
+        let out = coalesce_chunks(&chunks, 100);
+        assert!(out.len() <= 100);
+        assert!(out.ends_with(COALESCE_NOTICE));
+        assert_eq!(OutboundLimits::for_channel_type("discord").channel_interval, Duration::from_millis(20));
+    }
+}
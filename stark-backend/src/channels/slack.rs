@@ -503,6 +503,7 @@ async fn process_slack_message(
         force_safe_mode,
         platform_role_ids: vec![],
         chat_context: None,
+        thread_id: Some(reply_thread_ts.to_string()),
     };
 
     // Subscribe to events for real-time tool call forwarding
@@ -582,12 +583,14 @@ async fn process_slack_message(
                 "agent.mode_change"
                 | "execution.task_started"
                 | "execution.task_completed" => None,
+                "plan.approval_required" => Some(util::format_plan_approval(&event.data)),
                 _ => None,
             };
 
             if let Some(text) = message_text {
                 let is_first = status_ts.is_none();
-                if verbosity.is_throttled() && !throttler.should_send(is_first) {
+                let is_plan = event.event == "plan.approval_required";
+                if verbosity.is_throttled() && !is_plan && !throttler.should_send(is_first) {
                     continue;
                 }
 
@@ -728,20 +731,21 @@ async fn process_slack_message(
 
     // Send final response in thread
     if result.error.is_none() && !result.response.is_empty() {
-        let chunks = util::split_message(&result.response, 4000);
-        for chunk in chunks {
-            if let Err(e) = send_slack_message(
-                &client,
-                &state.bot_token,
-                &slack_channel,
-                &chunk,
-                Some(&reply_thread_ts),
-            )
-            .await
-            {
-                log::error!("Slack: Failed to send response: {}", e);
-            }
-        }
+        // Paced by the outbound limiter so long replies don't trip Slack's limits
+        let max_len = ChannelType::Slack.max_message_len();
+        let chunks = util::split_message(&result.response, max_len);
+        state
+            .dispatcher
+            .outbound_limiter()
+            .deliver(&slack_channel.0, chunks, max_len, |chunk| {
+                let (client, token, channel, thread_ts) = (&client, &state.bot_token, &slack_channel, &reply_thread_ts);
+                async move {
+                    send_slack_message(client, token, channel, &chunk, Some(thread_ts))
+                        .await
+                        .map(|_| ())
+                }
+            })
+            .await;
     } else if let Some(error) = result.error {
         let error_msg = format!("Sorry, I encountered an error: {}", error);
         let _ = send_slack_message(
@@ -11,7 +11,7 @@ use rand::seq::SliceRandom;
 use std::sync::Arc;
 use teloxide::prelude::*;
 use teloxide::requests::Requester;
-use teloxide::types::MessageId;
+use teloxide::types::{ChatAction, MessageId};
 use tokio::sync::oneshot;
 
 /// Format a tool call event for Telegram display based on verbosity
@@ -534,6 +534,7 @@ pub async fn start_telegram_listener(
                         force_safe_mode,
                         platform_role_ids: vec![],
                         chat_context: None,
+                        thread_id: None,
                     };
 
                     // Subscribe to events for real-time tool call forwarding
@@ -693,18 +694,25 @@ pub async fn start_telegram_listener(
                                 "agent.mode_change"
                                 | "execution.task_started"
                                 | "execution.task_completed" => None,
+                                "plan.approval_required" => Some(util::format_plan_approval(&event.data)),
                                 _ => None,
                             };
 
                             if let Some(text) = message_text {
                                 // Throttle: skip status updates if too frequent or rate-limited
+                                // (never the plan, which the agent is waiting on)
                                 let is_first = status_message_id.is_none();
-                                if verbosity.is_throttled() && !throttler.should_send(is_first) {
+                                let is_plan = event.event == "plan.approval_required";
+                                if verbosity.is_throttled() && !is_plan && !throttler.should_send(is_first) {
                                     continue;
                                 }
 
-                                let display_text = if text.len() > 4096 {
-                                    format!("{}...", &text[..4093])
+                                let display_text = if text.len() > ChannelType::Telegram.max_message_len() {
+                                    let mut end = ChannelType::Telegram.max_message_len() - 3;
+                                    while !text.is_char_boundary(end) {
+                                        end -= 1;
+                                    }
+                                    format!("{}...", &text[..end])
                                 } else {
                                     text
                                 };
@@ -791,7 +799,27 @@ pub async fn start_telegram_listener(
                         "Telegram: Dispatching message to AI for user {}",
                         user_name
                     );
+                    let bot_for_typing = bot.clone();
+                    let typing = util::TypingIndicator::start(
+                        &broadcaster,
+                        ChannelType::Telegram,
+                        channel_id,
+                        &telegram_chat_id.to_string(),
+                        move |typing| {
+                            let bot = bot_for_typing.clone();
+                            async move {
+                                if !typing {
+                                    return Ok(());
+                                }
+                                bot.send_chat_action(telegram_chat_id, ChatAction::Typing)
+                                    .await
+                                    .map(|_| ())
+                                    .map_err(|e| e.to_string())
+                            }
+                        },
+                    );
                     let result = dispatcher.dispatch_safe(normalized).await;
+                    drop(typing);
                     log::info!("Telegram: Dispatch complete, error={:?}", result.error);
 
                     // Unsubscribe from events
@@ -842,16 +870,22 @@ pub async fn start_telegram_listener(
                             true,
                         );
 
-                        let chunks = util::split_message(&result.response, 4096);
-                        for chunk in chunks {
-                            if let Err(e) = bot
-                                .send_message(msg.chat.id, &chunk)
-                                .reply_to_message_id(msg.id)
-                                .await
-                            {
-                                log::error!("Failed to send Telegram message: {}", e);
-                            }
-                        }
+                        // Paced by the outbound limiter so long replies don't trip Telegram's limits
+                        let max_len = ChannelType::Telegram.max_message_len();
+                        let chunks = util::split_message(&result.response, max_len);
+                        dispatcher
+                            .outbound_limiter()
+                            .deliver(&msg.chat.id.to_string(), chunks, max_len, |chunk| {
+                                let bot = bot.clone();
+                                async move {
+                                    bot.send_message(msg.chat.id, chunk)
+                                        .reply_to_message_id(msg.id)
+                                        .await
+                                        .map(|_| ())
+                                        .map_err(|e| e.to_string())
+                                }
+                            })
+                            .await;
                     } else if let Some(error) = result.error {
                         let error_msg =
                             format!("Sorry, I encountered an error: {}", error);
@@ -1049,6 +1049,7 @@ async fn process_mention(
         force_safe_mode,
         platform_role_ids: vec![],
         chat_context: None,
+        thread_id: None,
     };
 
     // Subscribe to events to capture say_to_user messages.
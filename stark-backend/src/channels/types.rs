@@ -9,6 +9,7 @@ pub enum ChannelType {
     Discord,
     Twitter,
     ExternalChannel,
+    Matrix,
 }
 
 impl ChannelType {
@@ -20,6 +21,7 @@ impl ChannelType {
             Self::Discord => "discord",
             Self::Twitter => "twitter",
             Self::ExternalChannel => "external_channel",
+            Self::Matrix => "matrix",
         }
     }
 
@@ -31,13 +33,14 @@ impl ChannelType {
             "discord" => Some(Self::Discord),
             "twitter" => Some(Self::Twitter),
             "external_channel" => Some(Self::ExternalChannel),
+            "matrix" => Some(Self::Matrix),
             _ => None,
         }
     }
 
     /// All supported channel types
     pub fn all() -> &'static [ChannelType] {
-        &[Self::Telegram, Self::Slack, Self::Discord, Self::Twitter, Self::ExternalChannel]
+        &[Self::Telegram, Self::Slack, Self::Discord, Self::Twitter, Self::ExternalChannel, Self::Matrix]
     }
 
     /// Display name for UI
@@ -48,6 +51,36 @@ impl ChannelType {
             Self::Discord => "Discord",
             Self::Twitter => "Twitter",
             Self::ExternalChannel => "External Channel",
+            Self::Matrix => "Matrix",
+        }
+    }
+
+    /// How often a typing indicator must be re-sent to stay visible.
+    /// None for platforms without bot typing indicators.
+    pub fn typing_refresh_interval(&self) -> Option<std::time::Duration> {
+        match self {
+            // Telegram chat actions last 5 seconds
+            Self::Telegram => Some(std::time::Duration::from_secs(4)),
+            // Discord typing lasts ~10 seconds
+            Self::Discord => Some(std::time::Duration::from_secs(8)),
+            // Sent with a 30 second timeout (see matrix.rs)
+            Self::Matrix => Some(std::time::Duration::from_secs(20)),
+            Self::Slack | Self::Twitter | Self::ExternalChannel => None,
+        }
+    }
+
+    /// Longest single message the platform accepts, in bytes.
+    /// Longer replies are split with `util::split_message`.
+    pub fn max_message_len(&self) -> usize {
+        match self {
+            Self::Telegram => 4096,
+            Self::Discord => 2000,
+            // chat.postMessage truncates text past 40k, but blocks render best under 4k
+            Self::Slack => 4000,
+            // Kept well under the 64KiB event size limit
+            Self::Matrix => 16_000,
+            Self::Twitter => 280,
+            Self::ExternalChannel => usize::MAX,
         }
     }
 }
@@ -97,6 +130,22 @@ pub struct NormalizedMessage {
     /// stored user message.
     #[serde(default)]
     pub chat_context: Option<String>,
+    /// Platform thread the message belongs to (Slack thread_ts, Discord thread channel ID).
+    /// When set, replies go to the thread and the session is scoped to it.
+    #[serde(default)]
+    pub thread_id: Option<String>,
+}
+
+impl NormalizedMessage {
+    /// Chat identifier used for session keys and lanes: the chat ID, narrowed
+    /// to the thread when the message is threaded so parallel threads keep
+    /// separate context.
+    pub fn session_chat_id(&self) -> String {
+        match self.thread_id.as_deref() {
+            Some(thread_id) if !thread_id.is_empty() => format!("{}:{}", self.chat_id, thread_id),
+            _ => self.chat_id.clone(),
+        }
+    }
 }
 
 /// Handle to a running channel listener
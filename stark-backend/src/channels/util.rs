@@ -1,7 +1,14 @@
 //! Shared utilities for channel implementations.
 
-/// Split a message into chunks respecting a platform's character limit.
-/// Splits on line boundaries; lines exceeding `max_len` are hard-split.
+/// Split a message into chunks respecting a platform's length limit
+/// (see `ChannelType::max_message_len`).
+///
+/// Breaks at paragraph boundaries first, then at line boundaries; only lines
+/// longer than `max_len` are hard-split (preferring whitespace, never inside a
+/// UTF-8 character). Fenced code blocks are kept whole when they fit in a
+/// chunk; a block too long for one message is split between lines and each
+/// part is closed and re-opened with the same fence, so every chunk renders
+/// as valid markdown.
 pub fn split_message(text: &str, max_len: usize) -> Vec<String> {
     if text.len() <= max_len {
         return vec![text.to_string()];
@@ -10,37 +17,163 @@ pub fn split_message(text: &str, max_len: usize) -> Vec<String> {
     let mut chunks = Vec::new();
     let mut current = String::new();
 
-    for line in text.lines() {
-        if current.len() + line.len() + 1 > max_len {
+    for block in split_blocks(text) {
+        for piece in fit_block(&block, max_len) {
+            if !current.is_empty() && current.len() + 2 + piece.len() > max_len {
+                chunks.push(std::mem::take(&mut current));
+            }
             if !current.is_empty() {
-                chunks.push(current);
-                current = String::new();
+                current.push_str("\n\n");
             }
-            if line.len() > max_len {
-                let mut remaining = line;
-                while remaining.len() > max_len {
-                    chunks.push(remaining[..max_len].to_string());
-                    remaining = &remaining[max_len..];
-                }
-                if !remaining.is_empty() {
-                    current = remaining.to_string();
-                }
+            current.push_str(&piece);
+        }
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
+/// A paragraph, or a fenced code block (`fence` is its opening line)
+struct MessageBlock {
+    lines: Vec<String>,
+    fence: Option<String>,
+}
+
+impl MessageBlock {
+    fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+
+/// The fence marker (``` or ~~~ run) a line opens or closes a code block with
+fn fence_marker(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let ch = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let run = trimmed.len() - trimmed.trim_start_matches(ch).len();
+    (run >= 3).then(|| &trimmed[..run])
+}
+
+/// Split text into paragraphs (separated by blank lines) and code blocks
+fn split_blocks(text: &str) -> Vec<MessageBlock> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut code: Option<(String, Vec<String>)> = None;
+
+    for line in text.lines() {
+        if let Some((open, mut lines)) = code.take() {
+            lines.push(line.to_string());
+            let closes = fence_marker(line).is_some_and(|m| {
+                fence_marker(&open).is_some_and(|o| m.starts_with(o)) && line.trim().len() == m.len()
+            });
+            if closes {
+                blocks.push(MessageBlock { lines, fence: Some(open) });
             } else {
-                current = line.to_string();
+                code = Some((open, lines));
+            }
+        } else if fence_marker(line).is_some() {
+            if !paragraph.is_empty() {
+                blocks.push(MessageBlock { lines: std::mem::take(&mut paragraph), fence: None });
+            }
+            code = Some((line.to_string(), vec![line.to_string()]));
+        } else if line.trim().is_empty() {
+            if !paragraph.is_empty() {
+                blocks.push(MessageBlock { lines: std::mem::take(&mut paragraph), fence: None });
             }
         } else {
+            paragraph.push(line.to_string());
+        }
+    }
+
+    if !paragraph.is_empty() {
+        blocks.push(MessageBlock { lines: paragraph, fence: None });
+    }
+    // Unterminated fence: keep it as a code block
+    if let Some((open, lines)) = code {
+        blocks.push(MessageBlock { lines, fence: Some(open) });
+    }
+    blocks
+}
+
+/// Break a block into pieces no longer than `max_len`
+fn fit_block(block: &MessageBlock, max_len: usize) -> Vec<String> {
+    let text = block.text();
+    if text.len() <= max_len {
+        return vec![text];
+    }
+
+    let Some(open) = block.fence.as_deref() else {
+        return pack_lines(&block.lines, max_len);
+    };
+
+    // Re-wrap each part of an oversized code block in its own fence
+    let close = fence_marker(open).unwrap_or("```");
+    let overhead = open.len() + close.len() + 2;
+    if overhead >= max_len / 2 {
+        return pack_lines(&block.lines, max_len);
+    }
+    let body_end = if block.lines.len() > 1 && fence_marker(&block.lines[block.lines.len() - 1]).is_some() {
+        block.lines.len() - 1
+    } else {
+        block.lines.len()
+    };
+    pack_lines(&block.lines[1..body_end], max_len - overhead)
+        .into_iter()
+        .map(|body| format!("{}\n{}\n{}", open, body, close))
+        .collect()
+}
+
+/// Join lines into pieces of at most `max_len`, hard-splitting overlong lines
+fn pack_lines(lines: &[String], max_len: usize) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+
+    for line in lines {
+        let parts = if line.len() > max_len { hard_split(line, max_len) } else { vec![line.clone()] };
+        for part in parts {
+            if !current.is_empty() && current.len() + 1 + part.len() > max_len {
+                pieces.push(std::mem::take(&mut current));
+            }
             if !current.is_empty() {
                 current.push('\n');
             }
-            current.push_str(line);
+            current.push_str(&part);
         }
     }
 
     if !current.is_empty() {
-        chunks.push(current);
+        pieces.push(current);
     }
+    pieces
+}
 
-    chunks
+/// Split a single overlong line, preferring whitespace in the second half of each window
+fn hard_split(line: &str, max_len: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut remaining = line;
+
+    while remaining.len() > max_len {
+        let mut end = max_len;
+        while end > 0 && !remaining.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == 0 {
+            // max_len is smaller than one character; emit it whole
+            end = remaining.chars().next().map(char::len_utf8).unwrap_or(remaining.len());
+        }
+        if let Some(ws) = remaining[..end].rfind(char::is_whitespace).filter(|&i| i > end / 2) {
+            end = ws;
+        }
+        parts.push(remaining[..end].to_string());
+        remaining = remaining[end..].trim_start();
+    }
+
+    if !remaining.is_empty() {
+        parts.push(remaining.to_string());
+    }
+    parts
 }
 
 /// Parse "Retry after Xs" from a platform API error string.
@@ -155,3 +288,158 @@ pub fn event_matches_session(
         _ => false,
     }
 }
+
+/// Chat text for a `plan.approval_required` event: the planned tasks and how to answer.
+/// Shown regardless of tool output verbosity, since the agent waits on the reply.
+pub fn format_plan_approval(data: &serde_json::Value) -> String {
+    let tasks = data.get("tasks").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+    let steps = tasks
+        .iter()
+        .filter_map(|t| {
+            let id = t.get("id").and_then(|v| v.as_u64())?;
+            let description = t.get("description").and_then(|v| v.as_str())?;
+            Some(format!("{}. {}", id, description))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let minutes = data.get("timeout_secs").and_then(|v| v.as_u64()).unwrap_or(0).div_ceil(60);
+    let fallback = match data.get("on_timeout").and_then(|v| v.as_str()) {
+        Some("proceed") => "it runs anyway",
+        _ => "it's dropped",
+    };
+    format!(
+        "📋 **Planned tasks:**\n{}\n\nReply **approve** to run this plan or **reject** to cancel it. \
+         If there's no answer within {} min, {}.",
+        steps, minutes, fallback
+    )
+}
+
+/// Shows a platform "bot is typing…" indicator while the agent works on a chat.
+///
+/// Driven by execution-tracker events: typing starts when an execution (or any
+/// of its tasks) starts for the chat, is re-sent every `refresh` interval so it
+/// stays visible during long tool loops, and stops when the execution completes
+/// or is stopped. `send_typing(true)` starts/refreshes the indicator;
+/// `send_typing(false)` clears it on platforms that support that.
+///
+/// A no-op for channel types without typing indicators. The indicator stops
+/// when dropped.
+pub struct TypingIndicator {
+    inner: Option<(String, std::sync::Arc<crate::gateway::events::EventBroadcaster>, tokio::task::JoinHandle<()>)>,
+}
+
+impl TypingIndicator {
+    pub fn start<F, Fut>(
+        broadcaster: &std::sync::Arc<crate::gateway::events::EventBroadcaster>,
+        channel_type: super::types::ChannelType,
+        channel_id: i64,
+        chat_id: &str,
+        mut send_typing: F,
+    ) -> Self
+    where
+        F: FnMut(bool) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = Result<(), String>> + Send,
+    {
+        let Some(refresh) = channel_type.typing_refresh_interval() else {
+            return Self { inner: None };
+        };
+
+        // Subscribe before returning so the execution.started event isn't missed
+        let (client_id, mut event_rx) = broadcaster.subscribe();
+        let chat_id = chat_id.to_string();
+        let task = tokio::spawn(async move {
+            let mut active = false;
+            let mut ticker = tokio::time::interval(refresh);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    event = event_rx.recv() => {
+                        let Some(event) = event else { break };
+                        if !event_matches_session(&event.data, channel_id, &chat_id) {
+                            continue;
+                        }
+                        match event.event.as_str() {
+                            "execution.started" | "execution.task_started" | "execution.thinking" if !active => {
+                                active = true;
+                                ticker.reset();
+                                if let Err(e) = send_typing(true).await {
+                                    log::debug!("Typing indicator failed for chat {}: {}", chat_id, e);
+                                }
+                            }
+                            "execution.completed" | "execution.stopped" if active => {
+                                active = false;
+                                let _ = send_typing(false).await;
+                            }
+                            _ => {}
+                        }
+                    }
+                    _ = ticker.tick(), if active => {
+                        if let Err(e) = send_typing(true).await {
+                            log::debug!("Typing indicator refresh failed for chat {}: {}", chat_id, e);
+                        }
+                    }
+                }
+            }
+        });
+
+        Self {
+            inner: Some((client_id, broadcaster.clone(), task)),
+        }
+    }
+}
+
+impl Drop for TypingIndicator {
+    fn drop(&mut self) {
+        if let Some((client_id, broadcaster, task)) = self.inner.take() {
+            task.abort();
+            broadcaster.unsubscribe(&client_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_message_paragraphs() {
+        assert_eq!(split_message("short", 100), vec!["short"]);
+
+        let text = format!("{}\n\n{}\n\n{}", "a".repeat(40), "b".repeat(40), "c".repeat(40));
+        let chunks = split_message(&text, 90);
+        assert_eq!(chunks, vec![format!("{}\n\n{}", "a".repeat(40), "b".repeat(40)), "c".repeat(40)]);
+
+        // Overlong lines are hard-split at whitespace and UTF-8 boundaries
+        let words = "héllo wörld ".repeat(20);
+        for chunk in split_message(&words, 30) {
+            assert!(chunk.len() <= 30);
+        }
+    }
+
+    #[test]
+    fn test_split_message_code_fences() {
+        let code = (0..10).map(|i| format!("let x{} = {};", i, i)).collect::<Vec<_>>().join("\n");
+        let text = format!("{}\n\n```rust\n{}\n```\n\ndone", "intro ".repeat(10), code);
+
+        // The code block fits in one chunk, so it moves to its own chunk whole
+        let chunks = split_message(&text, 160);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[0].starts_with("```rust\n") || chunks[1].starts_with("```rust\n"));
+        for chunk in &chunks {
+            assert_eq!(chunk.matches("```").count() % 2, 0, "unbalanced fence in {:?}", chunk);
+        }
+
+        // An oversized block is split between lines, each part re-fenced
+        let chunks = split_message(&text, 80);
+        let code_chunks: Vec<_> = chunks.iter().filter(|c| c.contains("let x")).collect();
+        assert!(code_chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.len() <= 80);
+            assert_eq!(chunk.matches("```").count() % 2, 0, "unbalanced fence in {:?}", chunk);
+        }
+        for chunk in code_chunks {
+            assert!(chunk.contains("```rust\nlet x") && chunk.contains(";\n```"));
+        }
+    }
+}
@@ -7,6 +7,8 @@ use std::path::{Path, PathBuf};
 pub mod env_vars {
     pub const LOGIN_ADMIN_PUBLIC_ADDRESS: &str = "LOGIN_ADMIN_PUBLIC_ADDRESS";
     pub const BURNER_WALLET_PRIVATE_KEY: &str = "BURNER_WALLET_BOT_PRIVATE_KEY";
+    // Additional labelled wallets for standard mode: "treasury:0xKEY,ops:0xKEY"
+    pub const WALLET_KEYS: &str = "STARK_WALLET_KEYS";
     pub const PORT: &str = "PORT";
     pub const DATABASE_URL: &str = "DATABASE_URL";
     pub const WORKSPACE_DIR: &str = "STARK_WORKSPACE_DIR";
@@ -27,6 +29,77 @@ pub mod env_vars {
     pub const MEMORY_ENABLE_PRE_COMPACTION_FLUSH: &str = "STARK_MEMORY_ENABLE_PRE_COMPACTION_FLUSH";
     pub const MEMORY_ENABLE_CROSS_SESSION: &str = "STARK_MEMORY_ENABLE_CROSS_SESSION";
     pub const MEMORY_CROSS_SESSION_LIMIT: &str = "STARK_MEMORY_CROSS_SESSION_LIMIT";
+    // Hybrid memory search: association hops followed from FTS/vector hits (0 = no graph signal)
+    pub const MEMORY_GRAPH_MAX_HOPS: &str = "STARK_MEMORY_GRAPH_MAX_HOPS";
+    // Hybrid memory search: weakest association strength (0.0-1.0) the graph traversal follows
+    pub const MEMORY_GRAPH_MIN_EDGE_STRENGTH: &str = "STARK_MEMORY_GRAPH_MIN_EDGE_STRENGTH";
+    // x402: warn before dispatch when USDC balance drops below this many USDC (0 = disabled)
+    pub const X402_BALANCE_BUFFER_USDC: &str = "STARK_X402_BALANCE_BUFFER_USDC";
+    // x402: preferred payment asset symbol when a 402 response accepts several
+    pub const X402_DEFAULT_ASSET: &str = "STARK_X402_DEFAULT_ASSET";
+    // Tx queue: seconds after broadcast before an unconfirmed tx is considered stuck
+    pub const TX_STUCK_TIMEOUT_SECS: &str = "STARK_TX_STUCK_TIMEOUT_SECS";
+    // Tx queue: automatically replace stuck txs with a gas-bumped copy (same nonce)
+    pub const TX_AUTO_REPLACE: &str = "STARK_TX_AUTO_REPLACE";
+    // Tx queue: maximum number of replacements per original transaction
+    pub const TX_MAX_REPLACEMENTS: &str = "STARK_TX_MAX_REPLACEMENTS";
+    // Broadcast history: prune finalized records older than this many days (0 = keep forever)
+    pub const TX_HISTORY_RETENTION_DAYS: &str = "STARK_TX_HISTORY_RETENTION_DAYS";
+    // Broadcast history: prune oldest finalized records beyond this many rows (0 = unlimited)
+    pub const TX_HISTORY_MAX_ROWS: &str = "STARK_TX_HISTORY_MAX_ROWS";
+    // Web login session lifetime in hours; clients refresh before it runs out
+    pub const SESSION_TTL_HOURS: &str = "STARK_SESSION_TTL_HOURS";
+    // Skills: refuse to create skills whose required binaries or API keys are missing
+    pub const SKILL_STRICT_DEPENDENCIES: &str = "STARK_SKILL_STRICT_DEPENDENCIES";
+    // Dispatcher: max conversations processed at once across all channels (0 = unlimited)
+    pub const MAX_CONCURRENT_DISPATCHES: &str = "STARK_MAX_CONCURRENT_DISPATCHES";
+    // Dispatcher: "queue" (wait for a slot) or "reject" (fail fast) when the cap is reached
+    pub const DISPATCH_OVERFLOW_POLICY: &str = "STARK_DISPATCH_OVERFLOW_POLICY";
+    // Hooks: "continue" (log and carry on) or "block" (cancel the operation) when a hook errors, times out or panics
+    pub const HOOK_FAILURE_POLICY: &str = "STARK_HOOK_FAILURE_POLICY";
+    // Modules: seconds the previous internal token stays valid after a rotation
+    pub const INTERNAL_TOKEN_GRACE_SECS: &str = "STARK_INTERNAL_TOKEN_GRACE_SECS";
+    // Modules: seconds between resource usage samples of module services
+    pub const MODULE_MONITOR_INTERVAL_SECS: &str = "STARK_MODULE_MONITOR_INTERVAL_SECS";
+    // HTTP: comma-separated origins allowed to call the API cross-origin ("*" = any origin)
+    pub const CORS_ALLOWED_ORIGINS: &str = "STARK_CORS_ALLOWED_ORIGINS";
+    // HTTP: largest request body accepted by default, in bytes (413 above it)
+    pub const MAX_BODY_BYTES: &str = "STARK_MAX_BODY_BYTES";
+    // HTTP: largest chat message body (web chat and external gateway chat), in bytes
+    pub const MAX_CHAT_BODY_BYTES: &str = "STARK_MAX_CHAT_BODY_BYTES";
+    // HTTP: largest backup file accepted by restore, in bytes
+    pub const MAX_RESTORE_BODY_BYTES: &str = "STARK_MAX_RESTORE_BODY_BYTES";
+    // Plan approval: seconds to wait for the user to approve a planned task list
+    pub const PLAN_APPROVAL_TIMEOUT_SECS: &str = "STARK_PLAN_APPROVAL_TIMEOUT_SECS";
+    // Session titles: AI endpoint preset used to title sessions ("off" disables, unset = active model)
+    pub const SESSION_TITLE_MODEL: &str = "STARK_SESSION_TITLE_MODEL";
+    // Spend ledger: USD per million input/output tokens for estimating AI costs (unset = tokens only)
+    pub const AI_INPUT_PRICE_PER_MTOK: &str = "STARK_AI_INPUT_PRICE_PER_MTOK";
+    pub const AI_OUTPUT_PRICE_PER_MTOK: &str = "STARK_AI_OUTPUT_PRICE_PER_MTOK";
+    // Cloud backup: hours between scheduled keystore backups (0 = disabled)
+    pub const AUTO_BACKUP_INTERVAL_HOURS: &str = "STARK_AUTO_BACKUP_INTERVAL_HOURS";
+    // Cloud backup: comma-separated sections restored on startup auto-retrieval (unset = all)
+    pub const AUTO_RESTORE_SECTIONS: &str = "STARK_AUTO_RESTORE_SECTIONS";
+    // http_get: comma-separated domains the tool may fetch, subdomains included (unset = any public host)
+    pub const HTTP_GET_ALLOWED_DOMAINS: &str = "STARK_HTTP_GET_ALLOWED_DOMAINS";
+    // http_get: largest response body in bytes before the fetch is aborted
+    pub const HTTP_GET_MAX_RESPONSE_BYTES: &str = "STARK_HTTP_GET_MAX_RESPONSE_BYTES";
+    // http_get: seconds before a request is abandoned
+    pub const HTTP_GET_TIMEOUT_SECS: &str = "STARK_HTTP_GET_TIMEOUT_SECS";
+    // External channel: workers processing async-accepted (?async=true) gateway chat messages
+    pub const GATEWAY_QUEUE_WORKERS: &str = "STARK_GATEWAY_QUEUE_WORKERS";
+    // External channel: async-accepted messages that may wait before new ones get a 503
+    pub const GATEWAY_QUEUE_MAX_PENDING: &str = "STARK_GATEWAY_QUEUE_MAX_PENDING";
+    // External channel: how far a signed request's timestamp may drift from now, in seconds
+    pub const GATEWAY_SIGNATURE_WINDOW_SECS: &str = "STARK_GATEWAY_SIGNATURE_WINDOW_SECS";
+    // Metrics: bearer token for scraping /metrics (unset = a logged-in session is required)
+    pub const METRICS_TOKEN: &str = "STARK_METRICS_TOKEN";
+    // Tracing: standard OpenTelemetry exporter variables (export is off unless an endpoint is set)
+    pub const OTLP_ENDPOINT: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+    pub const OTLP_TRACES_ENDPOINT: &str = "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT";
+    pub const OTLP_HEADERS: &str = "OTEL_EXPORTER_OTLP_HEADERS";
+    pub const OTLP_PROTOCOL: &str = "OTEL_EXPORTER_OTLP_PROTOCOL";
+    pub const OTEL_SERVICE_NAME: &str = "OTEL_SERVICE_NAME";
 }
 
 /// Default values
@@ -40,6 +113,24 @@ pub mod defaults {
     pub const PUBLIC_DIR: &str = "public";
     pub const MEMORY_DIR: &str = "memory";
     pub const DISK_QUOTA_MB: u64 = 1024;
+    pub const X402_BALANCE_BUFFER_USDC: &str = "1";
+    pub const X402_DEFAULT_ASSET: &str = "USDC";
+    pub const TX_STUCK_TIMEOUT_SECS: i64 = 300;
+    pub const TX_MAX_REPLACEMENTS: u32 = 3;
+    pub const TX_HISTORY_RETENTION_DAYS: i64 = 90;
+    pub const TX_HISTORY_MAX_ROWS: usize = 10_000;
+    pub const SESSION_TTL_HOURS: i64 = 24;
+    pub const PLAN_APPROVAL_TIMEOUT_SECS: u64 = 300;
+    pub const HTTP_GET_MAX_RESPONSE_BYTES: usize = 1024 * 1024;
+    pub const HTTP_GET_TIMEOUT_SECS: u64 = 15;
+    pub const GATEWAY_QUEUE_WORKERS: usize = 2;
+    pub const GATEWAY_QUEUE_MAX_PENDING: usize = 100;
+    pub const GATEWAY_SIGNATURE_WINDOW_SECS: i64 = 300;
+    pub const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+    pub const INTERNAL_TOKEN_GRACE_SECS: u64 = 300;
+    pub const MODULE_MONITOR_INTERVAL_SECS: u64 = 10;
+    pub const MAX_CHAT_BODY_BYTES: usize = 256 * 1024;
+    pub const MAX_RESTORE_BODY_BYTES: usize = 64 * 1024 * 1024;
 }
 
 /// Returns the absolute path to the stark-backend directory.
@@ -397,6 +488,263 @@ pub fn disk_quota_mb() -> u64 {
         .unwrap_or(defaults::DISK_QUOTA_MB)
 }
 
+/// Get the x402 low-balance warning buffer in raw USDC units (6 decimals, 0 = disabled)
+pub fn x402_balance_buffer_raw() -> u64 {
+    let value = env::var(env_vars::X402_BALANCE_BUFFER_USDC)
+        .unwrap_or_else(|_| defaults::X402_BALANCE_BUFFER_USDC.to_string());
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite() && *v >= 0.0)
+        .map(|v| (v * 1_000_000.0).round() as u64)
+        .unwrap_or(1_000_000)
+}
+
+/// Get the preferred x402 payment asset symbol (e.g. "USDC")
+pub fn x402_default_asset() -> String {
+    env::var(env_vars::X402_DEFAULT_ASSET)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .unwrap_or_else(|| defaults::X402_DEFAULT_ASSET.to_string())
+}
+
+/// Get the number of seconds after broadcast before an unconfirmed tx is considered stuck
+pub fn tx_stuck_timeout_secs() -> i64 {
+    env::var(env_vars::TX_STUCK_TIMEOUT_SECS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v: &i64| *v > 0)
+        .unwrap_or(defaults::TX_STUCK_TIMEOUT_SECS)
+}
+
+/// Whether stuck transactions are automatically rebroadcast with bumped gas
+pub fn tx_auto_replace() -> bool {
+    env::var(env_vars::TX_AUTO_REPLACE)
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false)
+}
+
+/// Get the maximum number of gas-bump replacements per transaction
+pub fn tx_max_replacements() -> u32 {
+    env::var(env_vars::TX_MAX_REPLACEMENTS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::TX_MAX_REPLACEMENTS)
+}
+
+/// Get the broadcast history retention period in days (None = keep forever)
+pub fn tx_history_retention_days() -> Option<i64> {
+    let days = env::var(env_vars::TX_HISTORY_RETENTION_DAYS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::TX_HISTORY_RETENTION_DAYS);
+    (days > 0).then_some(days)
+}
+
+/// Get the maximum number of broadcast history rows to keep (None = unlimited)
+pub fn tx_history_max_rows() -> Option<usize> {
+    let rows = env::var(env_vars::TX_HISTORY_MAX_ROWS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::TX_HISTORY_MAX_ROWS);
+    (rows > 0).then_some(rows)
+}
+
+/// Get the bearer token Prometheus uses to scrape /metrics, if configured
+pub fn metrics_token() -> Option<String> {
+    env::var(env_vars::METRICS_TOKEN).ok().filter(|t| !t.is_empty())
+}
+
+/// Get the web login session lifetime in hours
+pub fn session_ttl_hours() -> i64 {
+    env::var(env_vars::SESSION_TTL_HOURS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v: &i64| *v > 0)
+        .unwrap_or(defaults::SESSION_TTL_HOURS)
+}
+
+/// Whether missing skill binaries/API keys block skill creation (default: warn only)
+pub fn skill_strict_dependencies() -> bool {
+    env::var(env_vars::SKILL_STRICT_DEPENDENCIES)
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false)
+}
+
+/// Get the global cap on concurrent dispatches (None = unlimited)
+pub fn max_concurrent_dispatches() -> Option<usize> {
+    env::var(env_vars::MAX_CONCURRENT_DISPATCHES)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v: &usize| *v > 0)
+}
+
+/// What to do with dispatches over the concurrency cap (default: queue)
+pub fn dispatch_overflow_policy() -> crate::channels::dispatch_limiter::OverflowPolicy {
+    crate::channels::dispatch_limiter::OverflowPolicy::from_str_or_default(
+        &env::var(env_vars::DISPATCH_OVERFLOW_POLICY).unwrap_or_default(),
+    )
+}
+
+/// What a failing hook does to the operation it guards (default: continue)
+pub fn hook_failure_policy() -> crate::hooks::HookFailurePolicy {
+    crate::hooks::HookFailurePolicy::from_str_or_default(
+        &env::var(env_vars::HOOK_FAILURE_POLICY).unwrap_or_default(),
+    )
+}
+
+/// How long the previous internal token keeps working after a rotation
+pub fn internal_token_grace() -> std::time::Duration {
+    let secs = env::var(env_vars::INTERNAL_TOKEN_GRACE_SECS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(defaults::INTERNAL_TOKEN_GRACE_SECS);
+    std::time::Duration::from_secs(secs)
+}
+
+/// How often module service resource usage is sampled and limits enforced
+pub fn module_monitor_interval() -> std::time::Duration {
+    let secs = env::var(env_vars::MODULE_MONITOR_INTERVAL_SECS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&s: &u64| s > 0)
+        .unwrap_or(defaults::MODULE_MONITOR_INTERVAL_SECS);
+    std::time::Duration::from_secs(secs)
+}
+
+/// Which cross-origin callers the HTTP server answers (default: same origin only)
+pub fn cors_policy() -> crate::middleware::cors::CorsPolicy {
+    crate::middleware::cors::CorsPolicy::parse(
+        &env::var(env_vars::CORS_ALLOWED_ORIGINS).unwrap_or_default(),
+    )
+}
+
+fn body_limit(var: &str, default: usize) -> usize {
+    env::var(var)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v: &usize| *v > 0)
+        .unwrap_or(default)
+}
+
+/// Default request body cap for JSON and raw-body routes
+pub fn max_body_bytes() -> usize {
+    body_limit(env_vars::MAX_BODY_BYTES, defaults::MAX_BODY_BYTES)
+}
+
+/// Request body cap for chat message routes
+pub fn max_chat_body_bytes() -> usize {
+    body_limit(env_vars::MAX_CHAT_BODY_BYTES, defaults::MAX_CHAT_BODY_BYTES)
+}
+
+/// Request body cap for backup file restore
+pub fn max_restore_body_bytes() -> usize {
+    body_limit(env_vars::MAX_RESTORE_BODY_BYTES, defaults::MAX_RESTORE_BODY_BYTES)
+}
+
+/// How long a planned task list waits for the user's approval
+pub fn plan_approval_timeout() -> std::time::Duration {
+    let secs = env::var(env_vars::PLAN_APPROVAL_TIMEOUT_SECS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v: &u64| *v > 0)
+        .unwrap_or(defaults::PLAN_APPROVAL_TIMEOUT_SECS);
+    std::time::Duration::from_secs(secs)
+}
+
+/// AI endpoint preset for generating session titles.
+/// Returns Some("") to use the active model, None if titles are disabled.
+pub fn session_title_model() -> Option<String> {
+    let value = env::var(env_vars::SESSION_TITLE_MODEL).unwrap_or_default();
+    match value.trim().to_lowercase().as_str() {
+        "off" | "none" | "false" | "0" => None,
+        _ => Some(value.trim().to_string()),
+    }
+}
+
+/// USD per million (input, output) tokens used to estimate AI costs in the
+/// session spend ledger. Unset or invalid prices count as 0.
+pub fn ai_token_prices_per_mtok() -> (f64, f64) {
+    let price = |var: &str| {
+        env::var(var)
+            .ok()
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|v| v.is_finite() && *v >= 0.0)
+            .unwrap_or(0.0)
+    };
+    (price(env_vars::AI_INPUT_PRICE_PER_MTOK), price(env_vars::AI_OUTPUT_PRICE_PER_MTOK))
+}
+
+/// Hours between scheduled cloud backups (None = disabled, the default)
+pub fn auto_backup_interval_hours() -> Option<i64> {
+    env::var(env_vars::AUTO_BACKUP_INTERVAL_HOURS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v: &i64| *v > 0)
+}
+
+/// Backup sections restored by startup auto-retrieval, comma-separated (empty = all)
+pub fn auto_restore_sections() -> String {
+    env::var(env_vars::AUTO_RESTORE_SECTIONS).unwrap_or_default()
+}
+
+/// Domains http_get may fetch, lowercased (empty = any public host)
+pub fn http_get_allowed_domains() -> Vec<String> {
+    env::var(env_vars::HTTP_GET_ALLOWED_DOMAINS)
+        .unwrap_or_default()
+        .split(',')
+        .map(|d| d.trim().trim_start_matches("*.").trim_end_matches('.').to_lowercase())
+        .filter(|d| !d.is_empty())
+        .collect()
+}
+
+/// Get the http_get response size cap in bytes
+pub fn http_get_max_response_bytes() -> usize {
+    env::var(env_vars::HTTP_GET_MAX_RESPONSE_BYTES)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v: &usize| *v > 0)
+        .unwrap_or(defaults::HTTP_GET_MAX_RESPONSE_BYTES)
+}
+
+/// Get the http_get request timeout
+pub fn http_get_timeout() -> std::time::Duration {
+    let secs = env::var(env_vars::HTTP_GET_TIMEOUT_SECS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v: &u64| *v > 0)
+        .unwrap_or(defaults::HTTP_GET_TIMEOUT_SECS);
+    std::time::Duration::from_secs(secs)
+}
+
+/// Number of workers draining the gateway chat queue
+pub fn gateway_queue_workers() -> usize {
+    env::var(env_vars::GATEWAY_QUEUE_WORKERS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v: &usize| *v > 0)
+        .unwrap_or(defaults::GATEWAY_QUEUE_WORKERS)
+}
+
+/// Max gateway chat messages waiting for a worker
+pub fn gateway_queue_max_pending() -> usize {
+    env::var(env_vars::GATEWAY_QUEUE_MAX_PENDING)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v: &usize| *v > 0)
+        .unwrap_or(defaults::GATEWAY_QUEUE_MAX_PENDING)
+}
+
+/// Replay window for signed gateway requests
+pub fn gateway_signature_window_secs() -> i64 {
+    env::var(env_vars::GATEWAY_SIGNATURE_WINDOW_SECS)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v: &i64| *v > 0)
+        .unwrap_or(defaults::GATEWAY_SIGNATURE_WINDOW_SECS)
+}
+
 /// Get the burner wallet private key from environment (for tools)
 pub fn burner_wallet_private_key() -> Option<String> {
     env::var(env_vars::BURNER_WALLET_PRIVATE_KEY).ok()
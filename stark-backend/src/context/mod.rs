@@ -743,12 +743,16 @@ impl ContextManager {
         // Called directly as async — no thread spawning needed.
         if let Some(ref engine) = self.hybrid_search {
             log::debug!("[MEMORY_RETRIEVAL] Fast search: {}", &natural_query);
-            match engine.search_fast(&natural_query, limit, None).await {
+            match engine.search_fast(&natural_query, limit, None, identity_id).await {
                 Ok(results) if !results.is_empty() => {
                     log::info!(
                         "[MEMORY_RETRIEVAL] Fast search found {} memories for identity {:?}",
                         results.len(), identity_id
                     );
+                    let memory_ids: Vec<i64> = results.iter().map(|r| r.memory_id).collect();
+                    if let Err(e) = self.db.record_memory_hits(&memory_ids) {
+                        log::warn!("[MEMORY_RETRIEVAL] Failed to record memory hits: {}", e);
+                    }
                     let formatted = results
                         .iter()
                         .map(|r| {
@@ -788,6 +792,10 @@ impl ContextManager {
                     "[MEMORY_RETRIEVAL] FTS found {} relevant memories for identity {:?}",
                     results.len(), identity_id
                 );
+                let memory_ids: Vec<i64> = results.iter().map(|(m, _)| m.id).collect();
+                if let Err(e) = self.db.record_memory_hits(&memory_ids) {
+                    log::warn!("[MEMORY_RETRIEVAL] Failed to record memory hits: {}", e);
+                }
 
                 // Format as bullet points with content snippets
                 let formatted = results
@@ -1089,4 +1097,48 @@ mod tests {
         assert_eq!(title, "Discussion about Rust programming");
         assert!(summary.contains("ownership"));
     }
+
+    /// Memories of one identity must never surface for another, through FTS,
+    /// hybrid search, or graph edges pointing across identities.
+    #[tokio::test]
+    async fn test_retrieve_relevant_memories_isolates_identities() {
+        use crate::memory::embeddings::NullEmbeddingGenerator;
+        use crate::memory::{HybridSearchEngine, SHARED_MEMORY_IDENTITY};
+        use crate::models::SessionScope;
+
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        let memory = |content: &str, identity: &str| {
+            db.insert_memory("long_term", content, None, None, 7, Some(identity), None, None, None, None, None, None)
+                .unwrap()
+        };
+        let alice = memory("Alice stores her wallet backup in a bank vault", "alice");
+        let bob = memory("Bob stores his wallet backup on a USB stick", "bob");
+        memory("Wallet backups should never be stored in plain text", SHARED_MEMORY_IDENTITY);
+        // A graph edge from alice's memory to bob's must not pull bob's memory in
+        db.create_memory_association(alice, bob, "related", 0.9, None).unwrap();
+
+        let session = db.create_gateway_session("discord", 1, SessionScope::Group, None).unwrap();
+        db.add_session_message(session.id, DbMessageRole::User, "where is my wallet backup stored", None, None, None, None)
+            .unwrap();
+        let recent = db.get_recent_session_messages(session.id, 10).unwrap();
+
+        let memory_config = MemoryConfig { enable_cross_session_memory: true, ..MemoryConfig::default() };
+        let fts_only = ContextManager::new(db.clone()).with_memory_config(memory_config.clone());
+        let hybrid = ContextManager::new(db.clone())
+            .with_memory_config(memory_config)
+            .with_hybrid_search(Arc::new(HybridSearchEngine::new(db.clone(), Arc::new(NullEmbeddingGenerator))));
+
+        for manager in [&fts_only, &hybrid] {
+            let (alice_context, _) = manager.retrieve_relevant_memories(Some("alice"), &recent).await;
+            let alice_context = alice_context.expect("alice has relevant memories");
+            assert!(alice_context.contains("bank vault"));
+            assert!(alice_context.contains("plain text"), "shared memories are visible to everyone");
+            assert!(!alice_context.contains("USB stick"), "bob's memory leaked to alice: {}", alice_context);
+
+            let (bob_context, _) = manager.retrieve_relevant_memories(Some("bob"), &recent).await;
+            let bob_context = bob_context.expect("bob has relevant memories");
+            assert!(bob_context.contains("USB stick"));
+            assert!(!bob_context.contains("bank vault"), "alice's memory leaked to bob: {}", bob_context);
+        }
+    }
 }
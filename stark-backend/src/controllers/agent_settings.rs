@@ -277,6 +277,46 @@ pub async fn update_bot_settings(
         }
     }
 
+    // Validate gas_strategy if provided
+    if let Some(ref strategy) = request.gas_strategy
+        && crate::tx_queue::GasSpeed::parse(strategy).is_none()
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Invalid gas strategy: {}. Valid options: slow, standard, fast", strategy)
+        }));
+    }
+
+    // Validate command prefix and aliases if provided
+    if let Err(e) = crate::channels::dispatcher::CommandConfig::validate(
+        request.command_prefix.as_deref(),
+        request.command_aliases.as_ref(),
+    ) {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "error": e }));
+    }
+
+    // Validate gateway previous-message carryover if provided
+    if let Some(count) = request.gateway_previous_messages
+        && let Err(e) = crate::models::bot_settings::validate_gateway_previous_messages(count)
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "error": e }));
+    }
+
+    // Validate the queued transaction confirmation timeout if provided
+    if let Some(secs) = request.tx_confirmation_timeout_secs
+        && let Err(e) = crate::models::bot_settings::validate_tx_confirmation_timeout_secs(secs)
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({ "error": e }));
+    }
+
+    // Validate memory FTS tokenizer if provided
+    if let Some(ref tokenizer) = request.memory_fts_tokenizer
+        && crate::memory::fts_utils::FtsTokenizer::parse(tokenizer).is_none()
+    {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("Invalid memory FTS tokenizer: {}. Valid options: unicode61, porter, trigram", tokenizer)
+        }));
+    }
+
     // Update KEYSTORE_CLIENT URL if keystore_url is being changed
     if let Some(ref url) = request.keystore_url {
         let new_url = if url.is_empty() { DEFAULT_KEYSTORE_URL } else { url.as_str() };
@@ -310,6 +350,13 @@ pub async fn update_bot_settings(
         request.kanban_auto_execute,
         request.whisper_server_url.as_deref(),
         request.embeddings_server_url.as_deref(),
+        request.gas_strategy.as_deref(),
+        request.gas_max_fee_gwei,
+        request.command_prefix.as_deref(),
+        request.command_aliases.as_ref(),
+        request.gateway_previous_messages,
+        request.memory_fts_tokenizer.as_deref(),
+        request.tx_confirmation_timeout_secs,
     ) {
         Ok(settings) => {
             log::info!(
@@ -322,6 +369,17 @@ pub async fn update_bot_settings(
             if let Some(ref endpoints) = settings.custom_rpc_endpoints {
                 crate::tools::rpc_config::set_custom_rpc_endpoints(endpoints.clone());
             }
+            // Recreate the memory FTS index with the new tokenizer (no-op if unchanged)
+            if request.memory_fts_tokenizer.is_some() {
+                match state.db.rebuild_fts_index() {
+                    Ok(()) => {
+                        if let Some(ref engine) = state.hybrid_search {
+                            engine.invalidate_caches();
+                        }
+                    }
+                    Err(e) => log::error!("Failed to rebuild memory FTS index: {}", e),
+                }
+            }
             HttpResponse::Ok().json(settings)
         }
         Err(e) => {
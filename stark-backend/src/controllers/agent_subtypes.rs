@@ -93,6 +93,8 @@ struct CreateSubtypeRequest {
     #[serde(default)]
     skip_task_planner: bool,
     #[serde(default)]
+    require_plan_approval: bool,
+    #[serde(default)]
     aliases: Vec<String>,
     #[serde(default)]
     hidden: bool,
@@ -144,6 +146,7 @@ async fn create_subtype(
         enabled: body.enabled,
         max_iterations: body.max_iterations.unwrap_or(90),
         skip_task_planner: body.skip_task_planner,
+        require_plan_approval: body.require_plan_approval,
         aliases: body.aliases.clone(),
         hidden: body.hidden,
         preferred_ai_model: body.preferred_ai_model.as_ref().filter(|s| !s.is_empty()).cloned(),
@@ -190,6 +193,8 @@ struct UpdateSubtypeRequest {
     #[serde(default)]
     skip_task_planner: Option<bool>,
     #[serde(default)]
+    require_plan_approval: Option<bool>,
+    #[serde(default)]
     aliases: Option<Vec<String>>,
     #[serde(default)]
     hidden: Option<bool>,
@@ -235,6 +240,7 @@ async fn update_subtype(
         enabled: body.enabled.unwrap_or(existing.enabled),
         max_iterations: body.max_iterations.unwrap_or(existing.max_iterations),
         skip_task_planner: body.skip_task_planner.unwrap_or(existing.skip_task_planner),
+        require_plan_approval: body.require_plan_approval.unwrap_or(existing.require_plan_approval),
         aliases: body.aliases.clone().unwrap_or(existing.aliases),
         hidden: body.hidden.unwrap_or(existing.hidden),
         preferred_ai_model: match &body.preferred_ai_model {
@@ -1,9 +1,12 @@
+use std::collections::BTreeMap;
+
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use ethers::signers::{LocalWallet, Signer};
 use serde::{Deserialize, Serialize};
 use strum::{AsRefStr, EnumIter, EnumString, IntoEnumIterator};
 
-use crate::backup::{ApiKeyEntry, BackupData};
+use crate::backup::restore::{ConflictCounts, ConflictPolicy, RestoreSelection};
+use crate::backup::{ApiKeyEntry, BackupData, EncryptedBackupFile};
 use crate::keystore_client::KEYSTORE_CLIENT;
 use crate::models::ApiKeyResponse;
 use crate::AppState;
@@ -264,6 +267,29 @@ pub struct DeleteApiKeyRequest {
     pub key_name: String,
 }
 
+/// Query params for the restore endpoints
+#[derive(Debug, Deserialize)]
+pub struct RestoreQuery {
+    /// Comma-separated backup sections to restore (e.g. "skills,channels"); omitted = all
+    #[serde(default)]
+    pub sections: Option<String>,
+    /// How to treat items that already exist locally: overwrite (default), skip, keep-both, newer-wins
+    #[serde(default)]
+    pub conflict: Option<String>,
+}
+
+impl RestoreQuery {
+    fn selection(&self) -> Result<RestoreSelection, HttpResponse> {
+        RestoreSelection::parse(self.sections.as_deref().unwrap_or_default())
+            .map_err(|e| HttpResponse::BadRequest().json(backup_error(e)))
+    }
+
+    fn conflict_policy(&self) -> Result<ConflictPolicy, HttpResponse> {
+        ConflictPolicy::parse(self.conflict.as_deref().unwrap_or_default())
+            .map_err(|e| HttpResponse::BadRequest().json(backup_error(e)))
+    }
+}
+
 #[derive(Serialize)]
 pub struct ApiKeysListResponse {
     pub success: bool,
@@ -338,6 +364,9 @@ pub struct BackupResponse {
     pub module_count: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub backup_size_bytes: Option<usize>,
+    /// Per-section counts of backup items that already existed locally (restore only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflicts: Option<BTreeMap<&'static str, ConflictCounts>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -442,7 +471,15 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/value", web::get().to(get_api_key_value))
             .route("/cloud_backup", web::post().to(backup_to_cloud))
             .route("/cloud_restore", web::post().to(restore_from_cloud))
-            .route("/cloud_preview", web::get().to(preview_cloud_keys)),
+            .route("/cloud_preview", web::get().to(preview_cloud_keys))
+            .route("/backup_file", web::get().to(download_backup_file))
+            .service(
+                web::resource("/restore_file")
+                    .app_data(crate::middleware::body_limit::json_config(
+                        crate::config::max_restore_body_bytes(),
+                    ))
+                    .route(web::post().to(restore_from_file)),
+            ),
     );
 }
 
@@ -728,6 +765,7 @@ async fn backup_to_cloud(state: web::Data<AppState>, req: HttpRequest) -> impl R
                 note_count: None,
                 module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
                 message: None,
                 error: Some("No wallet configured".to_string()),
             });
@@ -760,6 +798,7 @@ async fn backup_to_cloud(state: web::Data<AppState>, req: HttpRequest) -> impl R
                 note_count: None,
                 module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
                 message: None,
                 error: Some(format!("Failed to get encryption key: {}", e)),
             });
@@ -795,6 +834,7 @@ async fn backup_to_cloud(state: web::Data<AppState>, req: HttpRequest) -> impl R
             note_count: None,
             module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
             message: None,
             error: Some("No data to backup".to_string()),
         });
@@ -843,6 +883,7 @@ async fn backup_to_cloud(state: web::Data<AppState>, req: HttpRequest) -> impl R
                 note_count: None,
                 module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
                 message: None,
                 error: Some("Failed to serialize backup".to_string()),
             });
@@ -875,6 +916,7 @@ async fn backup_to_cloud(state: web::Data<AppState>, req: HttpRequest) -> impl R
                 note_count: None,
                 module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
                 message: None,
                 error: Some("Failed to encrypt backup".to_string()),
             });
@@ -915,6 +957,7 @@ async fn backup_to_cloud(state: web::Data<AppState>, req: HttpRequest) -> impl R
                 note_count: Some(note_count),
                 module_count: Some(module_count),
                 backup_size_bytes: Some(encrypted_data.len()),
+                conflicts: None,
                 message: Some(format!(
                     "Backed up {} items ({} keys, {} nodes, {} connections, {} cron jobs, {} channels, {} channel settings, {} discord registrations, {} skills, {} AI models, {} memories, {} notes, {} modules{}{}{}{})",
                     item_count,
@@ -961,6 +1004,7 @@ async fn backup_to_cloud(state: web::Data<AppState>, req: HttpRequest) -> impl R
                 note_count: None,
                 module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
                 message: None,
                 error: resp.error.or(Some("Failed to upload to keystore".to_string())),
             })
@@ -988,6 +1032,7 @@ async fn backup_to_cloud(state: web::Data<AppState>, req: HttpRequest) -> impl R
                 note_count: None,
                 module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
                 message: None,
                 error: Some(format!("Keystore error: {}", e)),
             })
@@ -995,11 +1040,23 @@ async fn backup_to_cloud(state: web::Data<AppState>, req: HttpRequest) -> impl R
     }
 }
 
-/// Restore all user data from cloud backup
-async fn restore_from_cloud(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+/// Restore user data from cloud backup (all sections unless `?sections=` narrows it)
+async fn restore_from_cloud(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<RestoreQuery>,
+) -> impl Responder {
     if let Err(resp) = validate_session_from_request(&state, &req) {
         return resp;
     }
+    let selection = match query.selection() {
+        Ok(s) => s,
+        Err(resp) => return resp,
+    };
+    let policy = match query.conflict_policy() {
+        Ok(p) => p,
+        Err(resp) => return resp,
+    };
 
     // Wallet provider is the source of truth (Standard=EnvWalletProvider, Flash=FlashWalletProvider)
     let wallet_provider = match &state.wallet_provider {
@@ -1026,6 +1083,7 @@ async fn restore_from_cloud(state: web::Data<AppState>, req: HttpRequest) -> imp
                 note_count: None,
                 module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
                 message: None,
                 error: Some("No wallet configured".to_string()),
             });
@@ -1057,6 +1115,7 @@ async fn restore_from_cloud(state: web::Data<AppState>, req: HttpRequest) -> imp
                 note_count: None,
                 module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
                 message: None,
                 error: Some(format!("Failed to get encryption key: {}", e)),
             });
@@ -1092,6 +1151,7 @@ async fn restore_from_cloud(state: web::Data<AppState>, req: HttpRequest) -> imp
                 note_count: None,
                 module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
                 message: None,
                 error: Some(format!("Keystore error: {}", e)),
             });
@@ -1122,6 +1182,7 @@ async fn restore_from_cloud(state: web::Data<AppState>, req: HttpRequest) -> imp
                 note_count: None,
                 module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
                 message: None,
                 error: Some(error),
             });
@@ -1147,6 +1208,7 @@ async fn restore_from_cloud(state: web::Data<AppState>, req: HttpRequest) -> imp
             note_count: None,
             module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
             message: None,
             error: Some(error),
         });
@@ -1176,18 +1238,41 @@ async fn restore_from_cloud(state: web::Data<AppState>, req: HttpRequest) -> imp
                 note_count: None,
                 module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
                 message: None,
                 error: Some("No encrypted data in response".to_string()),
             });
         }
     };
 
+    let restore_result = match decrypt_and_restore(&state, &private_key, &encrypted_data, &selection, policy).await {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+
+    // Record retrieval in local state
+    if let Some(wallet_address) = get_wallet_address(&private_key) {
+        let _ = state.db.record_keystore_retrieval(&wallet_address);
+    }
+
+    restore_response(&restore_result, encrypted_data.len())
+}
+
+/// Decrypt a backup blob (current BackupData format or the legacy API key list) and restore it.
+/// Shared by the keystore and file restore endpoints.
+async fn decrypt_and_restore(
+    state: &web::Data<AppState>,
+    private_key: &str,
+    encrypted_data: &str,
+    selection: &RestoreSelection,
+    policy: ConflictPolicy,
+) -> Result<crate::backup::restore::RestoreResult, HttpResponse> {
     // Decrypt with ECIES using the burner wallet's private key
-    let decrypted_json = match crate::backup::decrypt_with_private_key(&private_key, &encrypted_data) {
+    let decrypted_json = match crate::backup::decrypt_with_private_key(private_key, encrypted_data) {
         Ok(data) => data,
         Err(e) => {
             log::error!("Failed to decrypt backup: {}", e);
-            return HttpResponse::BadRequest().json(BackupResponse {
+            return Err(HttpResponse::BadRequest().json(BackupResponse {
                 success: false,
                 key_count: None,
                 node_count: None,
@@ -1208,9 +1293,10 @@ async fn restore_from_cloud(state: web::Data<AppState>, req: HttpRequest) -> imp
                 note_count: None,
                 module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
                 message: None,
                 error: Some("Failed to decrypt backup (wrong wallet?)".to_string()),
-            });
+            }));
         }
     };
 
@@ -1223,7 +1309,7 @@ async fn restore_from_cloud(state: web::Data<AppState>, req: HttpRequest) -> imp
                 Ok(keys) => keys,
                 Err(e) => {
                     log::error!("Failed to parse backup: {}", e);
-                    return HttpResponse::BadRequest().json(BackupResponse {
+                    return Err(HttpResponse::BadRequest().json(BackupResponse {
                         success: false,
                         key_count: None,
                         node_count: None,
@@ -1244,13 +1330,14 @@ async fn restore_from_cloud(state: web::Data<AppState>, req: HttpRequest) -> imp
                         note_count: None,
                         module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
                         message: None,
                         error: Some("Invalid backup data format".to_string()),
-                    });
+                    }));
                 }
             };
             // Convert legacy format to BackupData
-            let wallet_address = get_wallet_address(&private_key).unwrap_or_default();
+            let wallet_address = get_wallet_address(private_key).unwrap_or_default();
             let mut backup = BackupData::new(wallet_address);
             backup.api_keys = legacy_keys
                 .into_iter()
@@ -1271,13 +1358,16 @@ async fn restore_from_cloud(state: web::Data<AppState>, req: HttpRequest) -> imp
         Some(&state.skill_registry),
         Some(&state.channel_manager),
         notes_store.as_ref(),
+        state.disk_quota.as_ref(),
+        selection,
+        policy,
     ).await;
 
     let restore_result = match restore_result {
         Ok(r) => r,
         Err(e) => {
             log::error!("Restore failed: {}", e);
-            return HttpResponse::InternalServerError().json(BackupResponse {
+            return Err(HttpResponse::InternalServerError().json(BackupResponse {
                 success: false,
                 key_count: None,
                 node_count: None,
@@ -1298,43 +1388,159 @@ async fn restore_from_cloud(state: web::Data<AppState>, req: HttpRequest) -> imp
                 note_count: None,
                 module_count: None,
                 backup_size_bytes: None,
+                conflicts: None,
                 message: None,
                 error: Some(format!("Restore failed: {}", e)),
-            });
+            }));
         }
     };
 
-    // Record retrieval in local state
-    if let Some(wallet_address) = get_wallet_address(&private_key) {
-        let _ = state.db.record_keystore_retrieval(&wallet_address);
-    }
+    Ok(restore_result)
+}
 
+/// Success response listing what a restore brought back
+fn restore_response(result: &crate::backup::restore::RestoreResult, backup_size_bytes: usize) -> HttpResponse {
     HttpResponse::Ok().json(BackupResponse {
         success: true,
-        key_count: Some(restore_result.api_keys),
-        node_count: Some(restore_result.impulse_nodes),
-        connection_count: Some(restore_result.impulse_connections),
-        cron_job_count: Some(restore_result.cron_jobs),
-        channel_count: Some(restore_result.channels),
-        channel_setting_count: Some(restore_result.channel_settings),
+        key_count: Some(result.api_keys),
+        node_count: Some(result.impulse_nodes),
+        connection_count: Some(result.impulse_connections),
+        cron_job_count: Some(result.cron_jobs),
+        channel_count: Some(result.channels),
+        channel_setting_count: Some(result.channel_settings),
         discord_registration_count: Some(0),
-        skill_count: Some(restore_result.skills),
-        agent_settings_count: Some(restore_result.agent_settings),
-        has_settings: Some(restore_result.bot_settings),
-        has_heartbeat: Some(restore_result.heartbeat_config),
-        has_soul: Some(restore_result.soul_document),
-        has_identity: Some(restore_result.agent_identity),
-        special_role_count: Some(restore_result.special_roles),
-        special_role_assignment_count: Some(restore_result.special_role_assignments),
-        memory_count: Some(restore_result.memories),
-        note_count: Some(restore_result.notes),
-        module_count: Some(restore_result.modules),
-        backup_size_bytes: Some(encrypted_data.len()),
-        message: Some(restore_result.summary()),
+        skill_count: Some(result.skills),
+        agent_settings_count: Some(result.agent_settings),
+        has_settings: Some(result.bot_settings),
+        has_heartbeat: Some(result.heartbeat_config),
+        has_soul: Some(result.soul_document),
+        has_identity: Some(result.agent_identity),
+        special_role_count: Some(result.special_roles),
+        special_role_assignment_count: Some(result.special_role_assignments),
+        memory_count: Some(result.memories),
+        note_count: Some(result.notes),
+        module_count: Some(result.modules),
+        backup_size_bytes: Some(backup_size_bytes),
+        conflicts: Some(result.conflicts.clone()),
+        message: Some(result.summary()),
         error: None,
     })
 }
 
+/// Failed backup/restore response carrying only an error message
+fn backup_error(error: impl Into<String>) -> BackupResponse {
+    BackupResponse {
+        success: false,
+        key_count: None,
+        node_count: None,
+        connection_count: None,
+        cron_job_count: None,
+        channel_count: None,
+        channel_setting_count: None,
+        discord_registration_count: None,
+        skill_count: None,
+        agent_settings_count: None,
+        has_settings: None,
+        has_heartbeat: None,
+        has_soul: None,
+        has_identity: None,
+        special_role_count: None,
+        special_role_assignment_count: None,
+        memory_count: None,
+        note_count: None,
+        module_count: None,
+        backup_size_bytes: None,
+        conflicts: None,
+        message: None,
+        error: Some(error.into()),
+    }
+}
+
+/// Download the current backup as an encrypted file (no keystore server involved)
+async fn download_backup_file(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+    let Some(wallet_provider) = state.wallet_provider.clone() else {
+        return HttpResponse::BadRequest().json(backup_error("No wallet configured"));
+    };
+    let private_key = match wallet_provider.get_encryption_key().await {
+        Ok(k) => k,
+        Err(e) => {
+            return HttpResponse::InternalServerError()
+                .json(backup_error(format!("Failed to get encryption key: {}", e)));
+        }
+    };
+
+    let backup = crate::backup::collect_backup_data(&state.db, wallet_provider.get_address()).await;
+    if backup.is_empty() {
+        return HttpResponse::BadRequest().json(backup_error("No data to backup"));
+    }
+    let backup_json = match serde_json::to_string(&backup) {
+        Ok(j) => j,
+        Err(e) => {
+            log::error!("Failed to serialize backup: {}", e);
+            return HttpResponse::InternalServerError().json(backup_error("Failed to serialize backup"));
+        }
+    };
+    let encrypted_data = match crate::backup::encrypt_with_private_key(&private_key, &backup_json) {
+        Ok(data) => data,
+        Err(e) => {
+            log::error!("Failed to encrypt backup: {}", e);
+            return HttpResponse::InternalServerError().json(backup_error("Failed to encrypt backup"));
+        }
+    };
+
+    log::info!("[Backup] Exported encrypted backup file ({} items)", backup.item_count());
+    let filename = format!("starkbot-backup-{}.json", chrono::Utc::now().format("%Y%m%d-%H%M%S"));
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", filename)))
+        .json(EncryptedBackupFile::new(encrypted_data))
+}
+
+/// Restore from an uploaded backup file (from /api/keys/backup_file), for
+/// migrations where the keystore server isn't reachable
+async fn restore_from_file(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<RestoreQuery>,
+    body: web::Json<EncryptedBackupFile>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+    let selection = match query.selection() {
+        Ok(s) => s,
+        Err(resp) => return resp,
+    };
+    let policy = match query.conflict_policy() {
+        Ok(p) => p,
+        Err(resp) => return resp,
+    };
+    let file = body.into_inner();
+    if let Err(e) = file.validate() {
+        return HttpResponse::BadRequest().json(backup_error(e));
+    }
+    let Some(wallet_provider) = state.wallet_provider.clone() else {
+        return HttpResponse::BadRequest().json(backup_error("No wallet configured"));
+    };
+    let private_key = match wallet_provider.get_encryption_key().await {
+        Ok(k) => k,
+        Err(e) => {
+            return HttpResponse::InternalServerError()
+                .json(backup_error(format!("Failed to get encryption key: {}", e)));
+        }
+    };
+
+    let restore_result = match decrypt_and_restore(&state, &private_key, &file.encrypted_data, &selection, policy).await {
+        Ok(r) => r,
+        Err(resp) => return resp,
+    };
+    log::info!("[Restore] Restored from backup file: {}", restore_result.summary());
+    restore_response(&restore_result, file.encrypted_data.len())
+}
+
 /// Create a preview string from an API key value (e.g., "sk-abc...xyz")
 fn create_key_preview(value: &str) -> String {
     if value.len() <= 8 {
@@ -4,6 +4,7 @@ use ethers::core::types::Signature;
 use ethers::utils::hash_message;
 use serde::{Deserialize, Serialize};
 
+use crate::models::{SessionCheck, SESSION_EXPIRED_CODE};
 use crate::AppState;
 
 const SERVICE_NAME: &str = "StarkBot";
@@ -53,6 +54,24 @@ pub struct LogoutResponse {
 #[derive(Serialize)]
 pub struct ValidateResponse {
     valid: bool,
+    /// Set to "session_expired" when the token was valid but has expired
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct RefreshResponse {
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
 }
 
 pub fn config(cfg: &mut web::ServiceConfig) {
@@ -61,7 +80,8 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/generate_challenge", web::post().to(generate_challenge))
             .route("/validate_auth", web::post().to(validate_auth))
             .route("/logout", web::post().to(logout))
-            .route("/validate", web::get().to(validate)),
+            .route("/validate", web::get().to(validate))
+            .route("/refresh", web::post().to(refresh)),
     );
     // Flash mode auth - separate from /api/auth scope to allow redirect
     cfg.route("/auth/flash", web::get().to(flash_login));
@@ -235,19 +255,86 @@ async fn validate(state: web::Data<AppState>, req: HttpRequest) -> impl Responde
         .and_then(|h| h.to_str().ok())
         .map(|s| s.trim_start_matches("Bearer ").to_string());
 
+    let invalid = ValidateResponse { valid: false, code: None, expires_at: None };
+
     let token = match token {
         Some(t) => t,
         None => {
-            return HttpResponse::Ok().json(ValidateResponse { valid: false });
+            return HttpResponse::Ok().json(invalid);
         }
     };
 
-    match state.db.validate_session(&token) {
-        Ok(Some(_)) => HttpResponse::Ok().json(ValidateResponse { valid: true }),
-        Ok(None) => HttpResponse::Ok().json(ValidateResponse { valid: false }),
+    match state.db.check_session(&token) {
+        Ok(SessionCheck::Valid(session)) => HttpResponse::Ok().json(ValidateResponse {
+            valid: true,
+            code: None,
+            expires_at: Some(session.expires_at.timestamp()),
+        }),
+        Ok(SessionCheck::Expired) => HttpResponse::Ok().json(ValidateResponse {
+            valid: false,
+            code: Some(SESSION_EXPIRED_CODE),
+            expires_at: None,
+        }),
+        Ok(SessionCheck::Invalid) => HttpResponse::Ok().json(invalid),
         Err(e) => {
             log::error!("Failed to validate session: {}", e);
-            HttpResponse::Ok().json(ValidateResponse { valid: false })
+            HttpResponse::Ok().json(invalid)
+        }
+    }
+}
+
+/// Exchange a valid, non-expired session token for a new one with a fresh TTL
+async fn refresh(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+
+    let token = match token {
+        Some(t) => t,
+        None => {
+            return HttpResponse::Unauthorized().json(RefreshResponse {
+                success: false,
+                token: None,
+                expires_at: None,
+                code: None,
+                error: Some("No authorization token provided".to_string()),
+            });
+        }
+    };
+
+    match state.db.refresh_session(&token) {
+        Ok(SessionCheck::Valid(session)) => HttpResponse::Ok().json(RefreshResponse {
+            success: true,
+            token: Some(session.token),
+            expires_at: Some(session.expires_at.timestamp()),
+            code: None,
+            error: None,
+        }),
+        Ok(SessionCheck::Expired) => HttpResponse::Unauthorized().json(RefreshResponse {
+            success: false,
+            token: None,
+            expires_at: None,
+            code: Some(SESSION_EXPIRED_CODE),
+            error: Some("Session expired. Please sign in again.".to_string()),
+        }),
+        Ok(SessionCheck::Invalid) => HttpResponse::Unauthorized().json(RefreshResponse {
+            success: false,
+            token: None,
+            expires_at: None,
+            code: None,
+            error: Some("Invalid session".to_string()),
+        }),
+        Err(e) => {
+            log::error!("Failed to refresh session: {}", e);
+            HttpResponse::InternalServerError().json(RefreshResponse {
+                success: false,
+                token: None,
+                expires_at: None,
+                code: None,
+                error: Some("Failed to refresh session".to_string()),
+            })
         }
     }
 }
@@ -45,7 +45,8 @@ fn validate_session(state: &web::Data<AppState>, req: &HttpRequest) -> Result<()
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api/broadcasted-transactions")
-            .route("", web::get().to(list_broadcasted_transactions)),
+            .route("", web::get().to(list_broadcasted_transactions))
+            .route("/stats", web::get().to(get_history_stats)),
     );
 }
 
@@ -101,3 +102,29 @@ async fn list_broadcasted_transactions(
         }
     }
 }
+
+/// Current broadcast history size and retention summary
+async fn get_history_stats(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+) -> impl Responder {
+    if let Err(resp) = validate_session(&state, &req) {
+        return resp;
+    }
+
+    match state.db.broadcast_history_stats() {
+        Ok(stats) => HttpResponse::Ok().json(serde_json::json!({
+            "success": true,
+            "stats": stats,
+            "retention_days": crate::config::tx_history_retention_days(),
+            "max_rows": crate::config::tx_history_max_rows(),
+        })),
+        Err(e) => {
+            log::error!("Failed to get broadcast history stats: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "success": false,
+                "error": "Failed to fetch history stats"
+            }))
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
-use serde::Serialize;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::db::tables::channel_messages::ChannelMessage;
 
 use crate::models::{
     get_settings_for_channel_type, ChannelResponse, ChannelSettingsResponse,
@@ -26,6 +29,56 @@ pub struct ChannelOperationResponse {
     pub error: Option<String>,
 }
 
+/// Response for cancelling a channel's running dispatch
+#[derive(Serialize)]
+pub struct CancelDispatchResponse {
+    pub success: bool,
+    pub channel_id: i64,
+    /// A dispatch (or its subagents) was running and has been told to stop
+    pub cancelled: bool,
+}
+
+/// Most messages a single history export returns
+const MAX_EXPORT_MESSAGES: usize = 10_000;
+
+/// Query params for the message history export
+#[derive(Debug, Deserialize)]
+pub struct ExportMessagesQuery {
+    /// Start of the range (RFC 3339 or YYYY-MM-DD), inclusive
+    pub from: Option<String>,
+    /// End of the range (RFC 3339, exclusive; or YYYY-MM-DD, inclusive of that day)
+    pub to: Option<String>,
+    /// Max messages to return (capped at MAX_EXPORT_MESSAGES)
+    pub limit: Option<usize>,
+}
+
+/// Response for the message history export
+#[derive(Serialize)]
+pub struct ChannelMessagesExportResponse {
+    pub success: bool,
+    pub channel_id: i64,
+    pub messages: Vec<ChannelMessage>,
+    /// More messages matched the range than the export returned
+    pub truncated: bool,
+    /// Content was withheld because the channel's history is private
+    pub content_redacted: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ChannelMessagesExportResponse {
+    fn error(channel_id: i64, error: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            channel_id,
+            messages: vec![],
+            truncated: false,
+            content_redacted: false,
+            error: Some(error.into()),
+        }
+    }
+}
+
 /// Response for safe mode channel creation with rate limit info
 #[derive(Serialize)]
 pub struct SafeModeChannelResponse {
@@ -53,8 +106,10 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/{id}", web::delete().to(delete_channel))
             .route("/{id}/start", web::post().to(start_channel))
             .route("/{id}/stop", web::post().to(stop_channel))
+            .route("/{id}/cancel", web::post().to(cancel_dispatch))
             .route("/{id}/settings", web::get().to(get_channel_settings))
-            .route("/{id}/settings", web::put().to(update_channel_settings)),
+            .route("/{id}/settings", web::put().to(update_channel_settings))
+            .route("/{id}/messages/export", web::get().to(export_channel_messages)),
     );
 }
 
@@ -183,7 +238,7 @@ async fn create_channel(
         return HttpResponse::BadRequest().json(ChannelOperationResponse {
             success: false,
             channel: None,
-            error: Some("Invalid channel type. Valid options: telegram, slack, discord, twitter, external_channel".to_string()),
+            error: Some("Invalid channel type. Valid options: telegram, slack, discord, twitter, external_channel, matrix".to_string()),
         });
     }
 
@@ -251,7 +306,7 @@ async fn create_safe_mode_channel(
         return HttpResponse::BadRequest().json(SafeModeChannelResponse {
             success: false,
             channel: None,
-            error: Some("Invalid channel type. Valid options: telegram, slack, discord, twitter".to_string()),
+            error: Some("Invalid channel type. Valid options: telegram, slack, discord, twitter, matrix".to_string()),
             queue_length: state.safe_mode_rate_limiter.queue_len(),
             next_slot_ms: state.safe_mode_rate_limiter.time_until_available_ms(),
         });
@@ -584,6 +639,27 @@ async fn stop_channel(
     }
 }
 
+/// Abort the dispatch running on a channel, leaving the listener running
+async fn cancel_dispatch(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    let id = path.into_inner();
+    let dispatch_cancelled = state.dispatcher.cancel_dispatch(id).await;
+    let listener_cancelled = state.channel_manager.cancel_dispatch(id);
+
+    HttpResponse::Ok().json(CancelDispatchResponse {
+        success: true,
+        channel_id: id,
+        cancelled: dispatch_cancelled || listener_cancelled,
+    })
+}
+
 /// Get available settings schema for a channel type
 async fn get_settings_schema(
     state: web::Data<AppState>,
@@ -724,3 +800,83 @@ async fn update_channel_settings(
         }
     }
 }
+
+/// Parse an export range bound into SQLite's `YYYY-MM-DD HH:MM:SS` format.
+/// A bare date as the end bound includes that whole day.
+fn parse_export_bound(value: &str, is_end: bool) -> Result<String, String> {
+    const SQLITE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.with_timezone(&Utc).format(SQLITE_FORMAT).to_string());
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}': use YYYY-MM-DD or RFC 3339", value))?;
+    let date = if is_end { date + Duration::days(1) } else { date };
+    Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().format(SQLITE_FORMAT).to_string())
+}
+
+/// Export the inbound message history of a channel over a date range (for moderation)
+async fn export_channel_messages(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+    query: web::Query<ExportMessagesQuery>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    let id = path.into_inner();
+
+    match state.db.get_channel(id) {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(ChannelMessagesExportResponse::error(id, "Channel not found"));
+        }
+        Err(e) => {
+            log::error!("Failed to get channel: {}", e);
+            return HttpResponse::InternalServerError()
+                .json(ChannelMessagesExportResponse::error(id, format!("Database error: {}", e)));
+        }
+    }
+
+    let bounds = (
+        query.from.as_deref().map(|v| parse_export_bound(v, false)).transpose(),
+        query.to.as_deref().map(|v| parse_export_bound(v, true)).transpose(),
+    );
+    let (from, to) = match bounds {
+        (Ok(from), Ok(to)) => (from, to),
+        (Err(e), _) | (_, Err(e)) => {
+            return HttpResponse::BadRequest().json(ChannelMessagesExportResponse::error(id, e));
+        }
+    };
+
+    let limit = query.limit.unwrap_or(MAX_EXPORT_MESSAGES).clamp(1, MAX_EXPORT_MESSAGES);
+    // Fetch one extra row to detect truncation
+    let mut messages = match state.db.list_channel_messages(id, from.as_deref(), to.as_deref(), limit + 1) {
+        Ok(messages) => messages,
+        Err(e) => {
+            log::error!("Failed to export channel messages: {}", e);
+            return HttpResponse::InternalServerError()
+                .json(ChannelMessagesExportResponse::error(id, format!("Database error: {}", e)));
+        }
+    };
+    let truncated = messages.len() > limit;
+    messages.truncate(limit);
+
+    // Also covers messages logged before the channel was flagged private
+    let content_redacted = state.db.is_channel_history_private(id);
+    if content_redacted {
+        for message in &mut messages {
+            message.content = None;
+        }
+    }
+
+    HttpResponse::Ok().json(ChannelMessagesExportResponse {
+        success: true,
+        channel_id: id,
+        messages,
+        truncated,
+        content_redacted,
+        error: None,
+    })
+}
@@ -129,7 +129,11 @@ pub struct GetPlannerTasksResponse {
 }
 
 pub fn config(cfg: &mut web::ServiceConfig) {
-    cfg.service(web::resource("/api/chat").route(web::post().to(chat)))
+    cfg.service(
+        web::resource("/api/chat")
+            .app_data(crate::middleware::body_limit::json_config(crate::config::max_chat_body_bytes()))
+            .route(web::post().to(chat)),
+    )
         .service(web::resource("/api/chat/stop").route(web::post().to(stop_execution)))
         .service(web::resource("/api/chat/execution-status").route(web::get().to(get_execution_status)))
         .service(web::resource("/api/chat/subagents").route(web::get().to(list_subagents)))
@@ -257,6 +261,7 @@ async fn chat(
         force_safe_mode: false,
         platform_role_ids: vec![],
         chat_context,
+        thread_id: None,
     };
 
     // Dispatch through the unified pipeline
@@ -1,6 +1,8 @@
-//! Transaction confirmation API endpoints
+//! Tool confirmation API endpoints
 //!
-//! Handles confirm/cancel requests from the frontend for pending blockchain transactions.
+//! Handles confirm/cancel requests from the frontend for tool calls awaiting
+//! the user's approval (e.g. of their estimated cost), and approve/reject
+//! requests for planned task lists waiting to run.
 
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
@@ -12,6 +14,12 @@ pub struct ConfirmationRequest {
     pub channel_id: i64,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct PlanApprovalRequest {
+    pub channel_id: i64,
+    pub approved: bool,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ConfirmationResponse {
     pub success: bool,
@@ -45,6 +53,7 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/pending/{channel_id}", web::get().to(get_pending))
             .route("/confirm", web::post().to(confirm))
             .route("/cancel", web::post().to(cancel))
+            .route("/plan", web::post().to(resolve_plan))
     );
 }
 
@@ -83,7 +92,7 @@ async fn get_pending(
     }
 }
 
-/// Confirm a pending transaction
+/// Approve a pending tool call
 async fn confirm(
     state: web::Data<AppState>,
     req: HttpRequest,
@@ -94,13 +103,13 @@ async fn confirm(
         return resp;
     }
 
-    match state.dispatcher.api_confirm_transaction(body.channel_id).await {
-        Ok(result) => {
+    match state.dispatcher.api_confirm_pending(body.channel_id) {
+        Ok(message) => {
             HttpResponse::Ok().json(ConfirmationResponse {
                 success: true,
-                message: Some("Transaction confirmed and executed".to_string()),
+                message: Some(message),
                 error: None,
-                result: Some(result),
+                result: None,
             })
         }
         Err(error) => {
@@ -114,7 +123,7 @@ async fn confirm(
     }
 }
 
-/// Cancel a pending transaction
+/// Cancel a pending tool call
 async fn cancel(
     state: web::Data<AppState>,
     req: HttpRequest,
@@ -125,11 +134,42 @@ async fn cancel(
         return resp;
     }
 
-    match state.dispatcher.api_cancel_transaction(body.channel_id) {
+    match state.dispatcher.api_cancel_pending(body.channel_id) {
         Ok(description) => {
             HttpResponse::Ok().json(ConfirmationResponse {
                 success: true,
-                message: Some(format!("Transaction cancelled: {}", description)),
+                message: Some(format!("Cancelled: {}", description)),
+                error: None,
+                result: None,
+            })
+        }
+        Err(error) => {
+            HttpResponse::BadRequest().json(ConfirmationResponse {
+                success: false,
+                message: None,
+                error: Some(error),
+                result: None,
+            })
+        }
+    }
+}
+
+/// Approve or reject a planned task list
+async fn resolve_plan(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<PlanApprovalRequest>,
+) -> impl Responder {
+    // Validate auth
+    if let Err(resp) = validate_auth(&state, &req) {
+        return resp;
+    }
+
+    match state.dispatcher.api_resolve_plan_approval(body.channel_id, body.approved) {
+        Ok(message) => {
+            HttpResponse::Ok().json(ConfirmationResponse {
+                success: true,
+                message: Some(message),
                 error: None,
                 result: None,
             })
@@ -0,0 +1,146 @@
+//! Dev-mode endpoints for scripted testing without auth
+//!
+//! Only registered when `STARKBOT_DEV` is set to `true`/`1`:
+//! - POST /api/dev/chat — dispatch a message through the normal pipeline
+//! - GET  /api/dev/ws   — WebSocket streaming a channel's gateway events
+//!
+//! A test can open the WebSocket, post to dev chat, and assert on the
+//! streamed events (tool calls, say_to_user, stream.* deltas, ...).
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use actix_ws::AggregatedMessage;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::channels::NormalizedMessage;
+use crate::gateway::protocol::GatewayEvent;
+use crate::AppState;
+
+/// Channel used by dev chat when none is given (same reserved ID as web chat)
+const DEV_CHANNEL_ID: i64 = 0;
+const DEV_CHANNEL_TYPE: &str = "web";
+
+/// Whether dev mode is on (`STARKBOT_DEV=true` or `1`)
+pub fn dev_mode_enabled() -> bool {
+    std::env::var("STARKBOT_DEV").map(|v| v == "true" || v == "1").unwrap_or(false)
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("/api/dev/chat").route(web::post().to(dev_chat)))
+        .service(web::resource("/api/dev/ws").route(web::get().to(dev_ws)));
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DevChatRequest {
+    pub message: String,
+    #[serde(default)]
+    pub channel_id: Option<i64>,
+    #[serde(default)]
+    pub user_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DevChatResponse {
+    pub success: bool,
+    pub response: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Query params for GET /api/dev/ws
+#[derive(Debug, Deserialize)]
+pub struct DevWsQuery {
+    /// Only forward events for this channel (defaults to the dev/web channel)
+    #[serde(default)]
+    pub channel_id: Option<i64>,
+}
+
+/// POST /api/dev/chat — dispatch a message without auth
+async fn dev_chat(state: web::Data<AppState>, body: web::Json<DevChatRequest>) -> impl Responder {
+    let body = body.into_inner();
+    let user_id = body.user_id.unwrap_or_else(|| "dev".to_string());
+
+    let normalized = NormalizedMessage {
+        channel_id: body.channel_id.unwrap_or(DEV_CHANNEL_ID),
+        channel_type: DEV_CHANNEL_TYPE.to_string(),
+        chat_id: user_id.clone(),
+        chat_name: None,
+        user_id: user_id.clone(),
+        user_name: format!("dev-{}", user_id),
+        text: body.message,
+        message_id: None,
+        session_mode: None,
+        selected_network: None,
+        force_safe_mode: false,
+        platform_role_ids: vec![],
+        chat_context: None,
+        thread_id: None,
+    };
+
+    let result = state.dispatcher.dispatch_safe(normalized).await;
+    let mut builder = if result.error.is_some() {
+        HttpResponse::InternalServerError()
+    } else {
+        HttpResponse::Ok()
+    };
+    builder.json(DevChatResponse {
+        success: result.error.is_none(),
+        response: result.response,
+        error: result.error,
+    })
+}
+
+/// GET /api/dev/ws — stream a channel's gateway events as JSON text frames.
+/// Read-only: incoming frames other than ping/close are ignored.
+async fn dev_ws(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    stream: web::Payload,
+    query: web::Query<DevWsQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let (response, mut session, msg_stream) = actix_ws::handle(&req, stream)?;
+    let channel_id = query.channel_id.unwrap_or(DEV_CHANNEL_ID);
+    let broadcaster = state.broadcaster.clone();
+
+    actix_web::rt::spawn(async move {
+        let (client_id, mut event_rx) = broadcaster.subscribe();
+        log::info!("[DEV_WS] Client {} watching channel {}", client_id, channel_id);
+
+        let mut msg_stream = msg_stream.aggregate_continuations().max_continuation_size(64 * 1024);
+        loop {
+            tokio::select! {
+                event = event_rx.recv() => {
+                    let Some(event) = event else { break };
+                    if !is_channel_event(&event, channel_id) {
+                        continue;
+                    }
+                    if let Ok(json) = serde_json::to_string(&event)
+                        && session.text(json).await.is_err()
+                    {
+                        break;
+                    }
+                }
+                msg = msg_stream.next() => match msg {
+                    Some(Ok(AggregatedMessage::Ping(data))) => {
+                        if session.pong(&data).await.is_err() {
+                            break;
+                        }
+                    }
+                    Some(Ok(AggregatedMessage::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                },
+            }
+        }
+
+        broadcaster.unsubscribe(&client_id);
+        let _ = session.close(None).await;
+        log::info!("[DEV_WS] Client {} disconnected", client_id);
+    });
+
+    Ok(response)
+}
+
+/// Events are scoped by the `channel_id` in their payload
+fn is_channel_event(event: &GatewayEvent, channel_id: i64) -> bool {
+    event.data.get("channel_id").and_then(|v| v.as_i64()) == Some(channel_id)
+}
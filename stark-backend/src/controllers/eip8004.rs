@@ -9,8 +9,10 @@ use crate::eip8004::{
     config::Eip8004Config,
     discovery::{AgentDiscovery, SearchCriteria},
     identity::{IdentityRegistry, RegistrationBuilder},
+    metadata_cache,
     reputation::ReputationRegistry,
     types::TrustLevel,
+    verification,
 };
 use crate::AppState;
 
@@ -85,6 +87,7 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             // Identity
             .route("/identity", web::get().to(get_our_identity))
             .route("/identity/registration", web::post().to(create_registration_json))
+            .route("/identity/verify", web::get().to(verify_our_identity))
             .route("/identity/{agent_id}", web::get().to(get_agent_identity))
             // Reputation
             .route("/reputation/{agent_id}", web::get().to(get_agent_reputation))
@@ -93,6 +96,10 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/agents", web::get().to(discover_agents))
             .route("/agents/search", web::get().to(search_agents))
             .route("/agents/{agent_id}", web::get().to(get_agent_details))
+            // Metadata cache
+            .route("/cache", web::get().to(get_cache_stats))
+            .route("/cache", web::delete().to(invalidate_cache))
+            .route("/cache/{agent_id}", web::delete().to(invalidate_agent_cache))
     );
 }
 
@@ -239,6 +246,40 @@ async fn get_our_identity(
     HttpResponse::Ok().json(resp)
 }
 
+/// Verify our registration's services and trust claims against the registry
+async fn verify_our_identity(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+) -> impl Responder {
+    if let Err(resp) = validate_auth(&state, &req) {
+        return resp;
+    }
+
+    let config = Eip8004Config::from_env();
+
+    let row = match state.db.get_agent_identity_full() {
+        Some(r) => r,
+        None => {
+            return HttpResponse::NotFound().json(ApiResponse::<()>::error(
+                "No registered identity found. Register or import an identity first.",
+            ));
+        }
+    };
+
+    if !config.is_identity_deployed() {
+        return HttpResponse::Ok().json(ApiResponse::success(verification::verify_local(&row, &config)));
+    }
+
+    let rpc = match config.build_rpc(state.wallet_provider.clone()) {
+        Ok(r) => r,
+        Err(e) => return HttpResponse::InternalServerError().json(ApiResponse::<()>::error(&e)),
+    };
+    let registry = IdentityRegistry::new(config.clone(), rpc);
+
+    let report = verification::verify_registration(&row, &config, &registry).await;
+    HttpResponse::Ok().json(ApiResponse::success(report))
+}
+
 /// Get agent identity by ID
 async fn get_agent_identity(
     state: web::Data<AppState>,
@@ -517,6 +558,50 @@ async fn get_agent_details(
     }
 }
 
+// =====================================================
+// Metadata Cache Endpoints
+// =====================================================
+
+/// Get registration metadata cache statistics
+async fn get_cache_stats(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_auth(&state, &req) {
+        return resp;
+    }
+
+    HttpResponse::Ok().json(ApiResponse::success(metadata_cache::stats()))
+}
+
+/// Drop all cached registration metadata
+async fn invalidate_cache(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_auth(&state, &req) {
+        return resp;
+    }
+
+    let removed = metadata_cache::invalidate(None, None);
+    log::info!("[eip8004] Invalidated {} cached registration(s)", removed);
+    HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({ "removed": removed })))
+}
+
+/// Drop cached registration metadata for one agent on the configured registry
+async fn invalidate_agent_cache(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<u64>,
+) -> impl Responder {
+    if let Err(resp) = validate_auth(&state, &req) {
+        return resp;
+    }
+
+    let agent_id = path.into_inner();
+    let registry = Eip8004Config::from_env().agent_registry_string();
+    let removed = metadata_cache::invalidate(Some(agent_id), Some(&registry));
+    HttpResponse::Ok().json(ApiResponse::success(serde_json::json!({
+        "agent_id": agent_id,
+        "agent_registry": registry,
+        "removed": removed
+    })))
+}
+
 // =====================================================
 // Auth Helper
 // =====================================================
@@ -8,6 +8,9 @@ use crate::models::chat_session::SessionScope;
 use crate::models::Channel;
 use crate::AppState;
 
+mod chat_queue;
+mod signing;
+
 const CHANNEL_TYPE: &str = "external_channel";
 
 // ── Request / Response types ────────────────────────────────────────────
@@ -21,7 +24,18 @@ pub struct GatewayChatRequest {
     pub user_name: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+/// Query params for POST /api/gateway/chat
+#[derive(Debug, Default, Deserialize)]
+pub struct GatewayChatQuery {
+    /// Respond with an SSE stream of tokens and tool events instead of a single JSON body
+    #[serde(default)]
+    pub stream: bool,
+    /// Queue the message and return a ticket immediately (202) instead of waiting
+    #[serde(default, rename = "async")]
+    pub async_accept: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct GatewayChatResponse {
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -30,6 +44,44 @@ pub struct GatewayChatResponse {
     pub session_id: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Typed results from tools that returned one, in the order they ran
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tool_results: Vec<GatewayToolResult>,
+}
+
+/// A tool's typed result in a gateway chat response
+#[derive(Debug, Clone, Serialize)]
+pub struct GatewayToolResult {
+    pub tool_name: String,
+    pub structured: Value,
+}
+
+/// Status of an async-accepted message (`?async=true`)
+#[derive(Debug, Serialize)]
+pub struct GatewayTicketResponse {
+    pub success: bool,
+    pub ticket_id: String,
+    pub status: chat_queue::TicketStatus,
+    pub created_at: String,
+    /// Messages ahead of this one, while queued
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_position: Option<usize>,
+    /// The chat response, once processing finished
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<GatewayChatResponse>,
+}
+
+impl GatewayTicketResponse {
+    fn from_ticket(ticket: &chat_queue::ChatTicket) -> Self {
+        Self {
+            success: true,
+            ticket_id: ticket.id.clone(),
+            status: ticket.status,
+            created_at: ticket.created_at.to_rfc3339(),
+            queue_position: ticket.queue_position,
+            result: ticket.result.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -99,7 +151,7 @@ pub struct GatewayModulesResponse {
 
 // ── SSE event types ─────────────────────────────────────────────────────
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 struct SseEvent {
     #[serde(rename = "type")]
     event_type: String,
@@ -121,6 +173,11 @@ struct SseEvent {
     error: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     task_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_id: Option<i64>,
+    /// Typed form of a tool result (`{"kind": ..., "data": ...}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    structured: Option<Value>,
 }
 
 // ── Route configuration ─────────────────────────────────────────────────
@@ -128,8 +185,18 @@ struct SseEvent {
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api/gateway")
-            .route("/chat", web::post().to(gateway_chat))
-            .route("/chat/stream", web::post().to(gateway_chat_stream))
+            .service(
+                web::resource("/chat")
+                    .app_data(chat_payload_config())
+                    .route(web::post().to(gateway_chat)),
+            )
+            .service(
+                web::resource("/chat/stream")
+                    .app_data(chat_payload_config())
+                    .route(web::post().to(gateway_chat_stream)),
+            )
+            .route("/chat/tickets/{id}", web::get().to(gateway_ticket_status))
+            .route("/chat/tickets/{id}/stream", web::get().to(gateway_ticket_stream))
             .route("/sessions", web::get().to(gateway_sessions))
             .route("/sessions/{id}/messages", web::get().to(gateway_session_messages))
             .route("/sessions/new", web::post().to(gateway_new_session))
@@ -140,6 +207,11 @@ pub fn config(cfg: &mut web::ServiceConfig) {
     );
 }
 
+/// Chat bodies are read raw (for signature checks), so cap the raw payload
+fn chat_payload_config() -> web::PayloadConfig {
+    crate::middleware::body_limit::payload_config(crate::config::max_chat_body_bytes())
+}
+
 // ── Auth helpers ────────────────────────────────────────────────────────
 
 /// Constant-time byte comparison to prevent timing attacks
@@ -219,6 +291,46 @@ fn validate_gateway_token(
     }))
 }
 
+/// Validate the gateway token and, when the channel has a signing secret,
+/// the request signature over `body` (the raw request body; empty for GETs).
+fn authenticate(
+    state: &web::Data<AppState>,
+    req: &HttpRequest,
+    body: &[u8],
+) -> Result<(i64, Channel), HttpResponse> {
+    let (channel_id, channel) = validate_gateway_token(state, req)?;
+
+    let secret = state
+        .db
+        .get_channel_setting(channel_id, "external_channel_signing_secret")
+        .ok()
+        .flatten()
+        .filter(|s| !s.is_empty());
+    if let Some(secret) = secret {
+        let now = chrono::Utc::now().timestamp();
+        let window = crate::config::gateway_signature_window_secs();
+        if let Err(e) = signing::verify(&secret, req, body, now, window) {
+            log::warn!("[EXT_CHANNEL] Rejected request on channel {}: {}", channel_id, e);
+            return Err(HttpResponse::Unauthorized().json(GatewayErrorResponse {
+                success: false,
+                error: e,
+            }));
+        }
+    }
+
+    Ok((channel_id, channel))
+}
+
+/// Parse a JSON request body that was read raw for signature verification
+fn parse_body<T: serde::de::DeserializeOwned>(body: &[u8]) -> Result<T, HttpResponse> {
+    serde_json::from_slice(body).map_err(|e| {
+        HttpResponse::BadRequest().json(GatewayErrorResponse {
+            success: false,
+            error: format!("Invalid JSON body: {}", e),
+        })
+    })
+}
+
 /// Validate web SIWE session (for admin actions like token generation)
 fn validate_web_session(
     state: &web::Data<AppState>,
@@ -259,16 +371,45 @@ fn validate_web_session(
 // ── Endpoint handlers ───────────────────────────────────────────────────
 
 /// POST /api/gateway/chat — send message, get full response
+/// (or an SSE stream with `?stream=true`, same as /chat/stream)
 async fn gateway_chat(
     state: web::Data<AppState>,
     req: HttpRequest,
-    body: web::Json<GatewayChatRequest>,
+    query: web::Query<GatewayChatQuery>,
+    body: web::Bytes,
 ) -> impl Responder {
-    let (channel_id, channel) = match validate_gateway_token(&state, &req) {
+    let (channel_id, channel) = match authenticate(&state, &req, &body) {
         Ok(v) => v,
         Err(resp) => return resp,
     };
+    let body: GatewayChatRequest = match parse_body(&body) {
+        Ok(b) => b,
+        Err(resp) => return resp,
+    };
+
+    if query.stream {
+        return stream_chat(&state, channel_id, &channel, body);
+    }
+    if query.async_accept {
+        return enqueue_chat(&state, channel_id, &channel, body);
+    }
+
+    let response = run_chat(&state, channel_id, &channel, body).await;
+    if response.success {
+        HttpResponse::Ok().json(response)
+    } else {
+        HttpResponse::InternalServerError().json(response)
+    }
+}
 
+/// Dispatch a message and wait for the full response. Shared by the
+/// synchronous endpoint and the async-accept queue workers.
+pub(crate) async fn run_chat(
+    state: &AppState,
+    channel_id: i64,
+    channel: &Channel,
+    body: GatewayChatRequest,
+) -> GatewayChatResponse {
     log::info!(
         "[EXT_CHANNEL] Chat on '{}' (id={}): {} chars",
         channel.name,
@@ -292,6 +433,9 @@ async fn gateway_chat(
     let collected: Arc<tokio::sync::Mutex<Vec<String>>> =
         Arc::new(tokio::sync::Mutex::new(Vec::new()));
     let collected_clone = collected.clone();
+    let tool_results: Arc<tokio::sync::Mutex<Vec<GatewayToolResult>>> =
+        Arc::new(tokio::sync::Mutex::new(Vec::new()));
+    let tool_results_clone = tool_results.clone();
     let cid = channel_id;
     let listener = tokio::spawn(async move {
         while let Some(event) = rx.recv().await {
@@ -318,6 +462,14 @@ async fn gateway_chat(
                         collected_clone.lock().await.push(content.to_string());
                     }
                 }
+                if ev_channel == Some(cid)
+                    && let Some(structured) = event.data.get("structured")
+                {
+                    tool_results_clone.lock().await.push(GatewayToolResult {
+                        tool_name: tool_name.to_string(),
+                        structured: structured.clone(),
+                    });
+                }
             }
             if event.event == "agent.response" {
                 let ev_channel = event.data.get("channel_id").and_then(|v| v.as_i64());
@@ -356,6 +508,7 @@ async fn gateway_chat(
         force_safe_mode: safe_mode,
         platform_role_ids: vec![],
         chat_context: None,
+        thread_id: None,
     };
 
     let result = state.dispatcher.dispatch_safe(normalized).await;
@@ -380,33 +533,132 @@ async fn gateway_chat(
 
     if let Some(error) = result.error {
         log::error!("[EXT_CHANNEL] Dispatch error: {}", error);
-        return HttpResponse::InternalServerError().json(GatewayChatResponse {
+        return GatewayChatResponse {
             success: false,
             response: None,
             session_id,
             error: Some(error),
-        });
+            tool_results: vec![],
+        };
     }
 
-    HttpResponse::Ok().json(GatewayChatResponse {
+    GatewayChatResponse {
         success: true,
         response: Some(response_text),
         session_id,
         error: None,
-    })
+        tool_results: std::mem::take(&mut *tool_results.lock().await),
+    }
+}
+
+/// Queue a message for background processing and return its ticket (202),
+/// or 503 when the queue is full.
+fn enqueue_chat(
+    state: &web::Data<AppState>,
+    channel_id: i64,
+    channel: &Channel,
+    body: GatewayChatRequest,
+) -> HttpResponse {
+    match chat_queue::enqueue(state, channel_id, channel.clone(), body) {
+        Ok(ticket) => HttpResponse::Accepted().json(GatewayTicketResponse::from_ticket(&ticket)),
+        Err(e) => HttpResponse::ServiceUnavailable()
+            .insert_header(("Retry-After", "5"))
+            .json(GatewayErrorResponse {
+                success: false,
+                error: e,
+            }),
+    }
+}
+
+/// GET /api/gateway/chat/tickets/{id} — poll an async-accepted message
+async fn gateway_ticket_status(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let (channel_id, _) = match authenticate(&state, &req, &[]) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    match chat_queue::get(&path, channel_id) {
+        Some(ticket) => HttpResponse::Ok().json(GatewayTicketResponse::from_ticket(&ticket)),
+        None => HttpResponse::NotFound().json(GatewayErrorResponse {
+            success: false,
+            error: "Ticket not found (unknown, expired, or from another channel)".to_string(),
+        }),
+    }
+}
+
+/// GET /api/gateway/chat/tickets/{id}/stream — SSE of the ticket's status
+/// changes, ending with a `done` event that carries the result
+async fn gateway_ticket_stream(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let (channel_id, _) = match authenticate(&state, &req, &[]) {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+
+    let Some(updates) = chat_queue::watch(&path, channel_id) else {
+        return HttpResponse::NotFound().json(GatewayErrorResponse {
+            success: false,
+            error: "Ticket not found (unknown, expired, or from another channel)".to_string(),
+        });
+    };
+
+    // Emit the current state, then one event per change until the ticket finishes
+    let stream = futures_util::stream::unfold(Some((updates, true)), |state| async move {
+        let (mut updates, first) = state?;
+        if !first && updates.changed().await.is_err() {
+            return None;
+        }
+        let ticket = updates.borrow_and_update().clone();
+        let finished = ticket.status.is_finished();
+        let mut payload =
+            serde_json::to_value(GatewayTicketResponse::from_ticket(&ticket)).unwrap_or_default();
+        payload["type"] = Value::from(if finished { "done" } else { "status" });
+        let bytes = web::Bytes::from(format!("data: {}\n\n", payload));
+        let next = (!finished).then_some((updates, false));
+        Some((Ok::<_, actix_web::Error>(bytes), next))
+    });
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(stream)
 }
 
 /// POST /api/gateway/chat/stream — send message, get SSE stream
 async fn gateway_chat_stream(
     state: web::Data<AppState>,
     req: HttpRequest,
-    body: web::Json<GatewayChatRequest>,
+    body: web::Bytes,
 ) -> impl Responder {
-    let (channel_id, channel) = match validate_gateway_token(&state, &req) {
+    let (channel_id, channel) = match authenticate(&state, &req, &body) {
         Ok(v) => v,
         Err(resp) => return resp,
     };
+    let body: GatewayChatRequest = match parse_body(&body) {
+        Ok(b) => b,
+        Err(resp) => return resp,
+    };
+
+    stream_chat(&state, channel_id, &channel, body)
+}
 
+/// Dispatch a message and stream tokens, tool activity and the final text as SSE.
+/// The stream ends with a `done` event (carrying the session ID, or the error)
+/// once the dispatch completes.
+fn stream_chat(
+    state: &web::Data<AppState>,
+    channel_id: i64,
+    channel: &Channel,
+    body: GatewayChatRequest,
+) -> HttpResponse {
     log::info!(
         "[EXT_CHANNEL] Stream on '{}' (id={}): {} chars",
         channel.name,
@@ -437,15 +689,19 @@ async fn gateway_chat_stream(
 
     // Dispatch in a background task
     let dispatcher = state.dispatcher.clone();
+    let db = state.db.clone();
     let msg_text = body.message.clone();
     let broadcaster_bg = broadcaster.clone();
     let client_id_bg = client_id.clone();
+    let (done_tx, done_rx) = tokio::sync::oneshot::channel::<SseEvent>();
 
     tokio::spawn(async move {
+        // Relay AI tokens as stream.* events for the duration of this dispatch
+        let _token_stream = dispatcher.stream_tokens_for_channel(cid);
         let normalized = NormalizedMessage {
             channel_id: cid,
             channel_type: CHANNEL_TYPE.to_string(),
-            chat_id,
+            chat_id: chat_id.clone(),
             chat_name: None,
             user_id: "gateway-user".to_string(),
             user_name,
@@ -456,8 +712,22 @@ async fn gateway_chat_stream(
             force_safe_mode: safe_mode,
             platform_role_ids: vec![],
         chat_context: None,
+        thread_id: None,
         };
-        let _ = dispatcher.dispatch_safe(normalized).await;
+        let result = dispatcher.dispatch_safe(normalized).await;
+        let session_id = db
+            .get_or_create_chat_session(CHANNEL_TYPE, cid, &chat_id, SessionScope::Api, None)
+            .ok()
+            .map(|s| s.id);
+        let _ = done_tx.send(SseEvent {
+            event_type: "done".to_string(),
+            success: Some(result.error.is_none()),
+            error: result.error,
+            session_id,
+            ..Default::default()
+        });
+        // Unsubscribing ends the event loop below once queued events are flushed,
+        // which closes the SSE connection
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
         broadcaster_bg.unsubscribe(&client_id_bg);
     });
@@ -473,6 +743,22 @@ async fn gateway_chat_stream(
             }
 
             let sse = match event.event.as_str() {
+                "stream.content_delta" => {
+                    let content = event.data.get("content").and_then(|v| v.as_str()).unwrap_or("");
+                    (!content.is_empty()).then(|| SseEvent {
+                        event_type: "token".to_string(),
+                        content: Some(content.to_string()),
+                        ..Default::default()
+                    })
+                }
+                "stream.thinking_delta" => {
+                    let content = event.data.get("content").and_then(|v| v.as_str()).unwrap_or("");
+                    (!content.is_empty()).then(|| SseEvent {
+                        event_type: "thinking_token".to_string(),
+                        content: Some(content.to_string()),
+                        ..Default::default()
+                    })
+                }
                 "tool.call" => {
                     let tool_name = event.data.get("tool_name").and_then(|v| v.as_str()).unwrap_or("unknown");
                     let parameters = event.data.get("parameters").cloned();
@@ -482,7 +768,7 @@ async fn gateway_chat_stream(
                         tool_name: Some(tool_name.to_string()),
                         parameters,
                         success: None, duration_ms: None,
-                        label: None, agent_subtype: None, error: None, task_name: None,
+                        label: None, agent_subtype: None, error: None, task_name: None, session_id: None, structured: None,
                     })
                 }
                 "tool.result" => {
@@ -490,6 +776,7 @@ async fn gateway_chat_stream(
                     let success = event.data.get("success").and_then(|v| v.as_bool()).unwrap_or(false);
                     let content = event.data.get("content").and_then(|v| v.as_str()).unwrap_or("");
                     let duration_ms = event.data.get("duration_ms").and_then(|v| v.as_i64());
+                    let structured = event.data.get("structured").cloned();
 
                     // For say_to_user / task_fully_completed, also emit text so the
                     // main response body is visible inline
@@ -500,7 +787,7 @@ async fn gateway_chat_stream(
                             content: Some(content.to_string()),
                             tool_name: None, parameters: None,
                             success: None, duration_ms: None,
-                            label: None, agent_subtype: None, error: None, task_name: None,
+                            label: None, agent_subtype: None, error: None, task_name: None, session_id: None, structured: None,
                         };
                         if let Ok(json) = serde_json::to_string(&text_event) {
                             let _ = tx.send(web::Bytes::from(format!("data: {}\n\n", json))).await;
@@ -516,7 +803,7 @@ async fn gateway_chat_stream(
                         parameters: None,
                         success: Some(success),
                         duration_ms,
-                        label: None, agent_subtype: None, error: None, task_name: None,
+                        label: None, agent_subtype: None, error: None, task_name: None, session_id: None, structured,
                     })
                 }
                 "agent.response" => {
@@ -527,7 +814,7 @@ async fn gateway_chat_stream(
                             content: Some(content.to_string()),
                             tool_name: None, parameters: None,
                             success: None, duration_ms: None,
-                            label: None, agent_subtype: None, error: None, task_name: None,
+                            label: None, agent_subtype: None, error: None, task_name: None, session_id: None, structured: None,
                         })
                     } else {
                         None
@@ -542,7 +829,7 @@ async fn gateway_chat_stream(
                         content: task,
                         tool_name: None, parameters: None,
                         success: None, duration_ms: None,
-                        label: Some(label), agent_subtype, error: None, task_name: None,
+                        label: Some(label), agent_subtype, error: None, task_name: None, session_id: None, structured: None,
                     })
                 }
                 "subagent.completed" => {
@@ -551,7 +838,7 @@ async fn gateway_chat_stream(
                         event_type: "subagent_completed".to_string(),
                         content: None, tool_name: None, parameters: None,
                         success: None, duration_ms: None,
-                        label: Some(label), agent_subtype: None, error: None, task_name: None,
+                        label: Some(label), agent_subtype: None, error: None, task_name: None, session_id: None, structured: None,
                     })
                 }
                 "subagent.failed" => {
@@ -561,7 +848,7 @@ async fn gateway_chat_stream(
                         event_type: "subagent_failed".to_string(),
                         content: None, tool_name: None, parameters: None,
                         success: None, duration_ms: None,
-                        label: Some(label), agent_subtype: None, error: Some(error), task_name: None,
+                        label: Some(label), agent_subtype: None, error: Some(error), task_name: None, session_id: None, structured: None,
                     })
                 }
                 "agent.subtype_change" => {
@@ -571,7 +858,7 @@ async fn gateway_chat_stream(
                         event_type: "subtype_change".to_string(),
                         content: None, tool_name: None, parameters: None,
                         success: None, duration_ms: None,
-                        label: Some(label), agent_subtype: Some(subtype), error: None, task_name: None,
+                        label: Some(label), agent_subtype: Some(subtype), error: None, task_name: None, session_id: None, structured: None,
                     })
                 }
                 "agent.thinking" => {
@@ -581,7 +868,7 @@ async fn gateway_chat_stream(
                         content: Some(message),
                         tool_name: None, parameters: None,
                         success: None, duration_ms: None,
-                        label: None, agent_subtype: None, error: None, task_name: None,
+                        label: None, agent_subtype: None, error: None, task_name: None, session_id: None, structured: None,
                     })
                 }
                 "execution.task_started" => {
@@ -594,7 +881,7 @@ async fn gateway_chat_stream(
                             event_type: "task_started".to_string(),
                             content: None, tool_name: None, parameters: None,
                             success: None, duration_ms: None,
-                            label: None, agent_subtype: None, error: None, task_name: Some(name),
+                            label: None, agent_subtype: None, error: None, task_name: Some(name), session_id: None, structured: None,
                         })
                     } else {
                         None
@@ -607,7 +894,7 @@ async fn gateway_chat_stream(
                         content: Some(status),
                         tool_name: None, parameters: None,
                         success: None, duration_ms: None,
-                        label: None, agent_subtype: None, error: None, task_name: None,
+                        label: None, agent_subtype: None, error: None, task_name: None, session_id: None, structured: None,
                     })
                 }
                 "dispatch.complete" => {
@@ -615,7 +902,7 @@ async fn gateway_chat_stream(
                         event_type: "done".to_string(),
                         content: None, tool_name: None, parameters: None,
                         success: None, duration_ms: None,
-                        label: None, agent_subtype: None, error: None, task_name: None,
+                        label: None, agent_subtype: None, error: None, task_name: None, session_id: None, structured: None,
                     };
                     if let Ok(json) = serde_json::to_string(&done) {
                         let _ = tx.send(web::Bytes::from(format!("data: {}\n\n", json))).await;
@@ -633,6 +920,13 @@ async fn gateway_chat_stream(
                 }
             }
         }
+
+        // Session complete: report the outcome, then drop `tx` to close the stream
+        if let Ok(done) = done_rx.await
+            && let Ok(json) = serde_json::to_string(&done)
+        {
+            let _ = tx.send(web::Bytes::from(format!("data: {}\n\n", json))).await;
+        }
     });
 
     let stream = futures_util::stream::unfold(sse_rx, |mut rx| async move {
@@ -651,7 +945,7 @@ async fn gateway_sessions(
     state: web::Data<AppState>,
     req: HttpRequest,
 ) -> impl Responder {
-    let (channel_id, _channel) = match validate_gateway_token(&state, &req) {
+    let (channel_id, _channel) = match authenticate(&state, &req, &[]) {
         Ok(v) => v,
         Err(resp) => return resp,
     };
@@ -684,7 +978,7 @@ async fn gateway_session_messages(
     req: HttpRequest,
     path: web::Path<i64>,
 ) -> impl Responder {
-    let (channel_id, _channel) = match validate_gateway_token(&state, &req) {
+    let (channel_id, _channel) = match authenticate(&state, &req, &[]) {
         Ok(v) => v,
         Err(resp) => return resp,
     };
@@ -727,8 +1021,9 @@ async fn gateway_session_messages(
 async fn gateway_new_session(
     state: web::Data<AppState>,
     req: HttpRequest,
+    body: web::Bytes,
 ) -> impl Responder {
-    let (channel_id, _channel) = match validate_gateway_token(&state, &req) {
+    let (channel_id, _channel) = match authenticate(&state, &req, &body) {
         Ok(v) => v,
         Err(resp) => return resp,
     };
@@ -825,7 +1120,7 @@ async fn gateway_modules_list(
     state: web::Data<AppState>,
     req: HttpRequest,
 ) -> impl Responder {
-    if let Err(resp) = validate_gateway_token(&state, &req) {
+    if let Err(resp) = authenticate(&state, &req, &[]) {
         return resp;
     }
 
@@ -928,7 +1223,7 @@ async fn gateway_tui_stream(
     path: web::Path<String>,
     query: web::Query<TuiStreamQuery>,
 ) -> impl Responder {
-    if let Err(resp) = validate_gateway_token(&state, &req) {
+    if let Err(resp) = authenticate(&state, &req, &[]) {
         return resp;
     }
 
@@ -1004,11 +1299,15 @@ async fn gateway_tui_action(
     state: web::Data<AppState>,
     req: HttpRequest,
     path: web::Path<String>,
-    body: web::Json<Value>,
+    body: web::Bytes,
 ) -> impl Responder {
-    if let Err(resp) = validate_gateway_token(&state, &req) {
+    if let Err(resp) = authenticate(&state, &req, &body) {
         return resp;
     }
+    let body: Value = match parse_body(&body) {
+        Ok(b) => b,
+        Err(resp) => return resp,
+    };
 
     let module_name = path.into_inner();
 
@@ -1028,7 +1327,7 @@ async fn gateway_tui_action(
     let client = reqwest::Client::new();
     match client
         .post(&url)
-        .json(&body.into_inner())
+        .json(&body)
         .timeout(std::time::Duration::from_secs(10))
         .send()
         .await
@@ -0,0 +1,207 @@
+//! Async-accept queue for `POST /api/gateway/chat?async=true`
+//!
+//! Accepted messages get a ticket and wait in a bounded queue; a fixed pool of
+//! workers dispatches them through the same path as the synchronous endpoint,
+//! so load spikes queue up instead of failing. Finished tickets are kept for
+//! `TICKET_TTL` for clients to collect. Tickets live in memory only.
+
+use actix_web::web;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+
+use super::{run_chat, GatewayChatRequest, GatewayChatResponse};
+use crate::models::Channel;
+use crate::AppState;
+
+/// How long finished tickets stay retrievable
+const TICKET_TTL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TicketStatus {
+    Queued,
+    Processing,
+    Complete,
+    Failed,
+}
+
+impl TicketStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, TicketStatus::Complete | TicketStatus::Failed)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChatTicket {
+    pub id: String,
+    pub channel_id: i64,
+    pub status: TicketStatus,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Messages ahead of this one (filled in on lookup while queued)
+    pub queue_position: Option<usize>,
+    pub result: Option<GatewayChatResponse>,
+}
+
+struct Job {
+    ticket_id: String,
+    channel_id: i64,
+    channel: Channel,
+    body: GatewayChatRequest,
+}
+
+struct ChatQueue {
+    jobs: mpsc::Sender<Job>,
+    max_pending: usize,
+    tickets: Mutex<HashMap<String, watch::Sender<ChatTicket>>>,
+    /// Ticket ids waiting for a worker, oldest first
+    pending: Mutex<VecDeque<String>>,
+}
+
+static QUEUE: OnceLock<ChatQueue> = OnceLock::new();
+
+/// The queue, starting its workers on first use
+fn queue(state: &web::Data<AppState>) -> &'static ChatQueue {
+    QUEUE.get_or_init(|| {
+        let workers = crate::config::gateway_queue_workers();
+        let max_pending = crate::config::gateway_queue_max_pending();
+        let (tx, rx) = mpsc::channel(max_pending);
+        let rx = Arc::new(tokio::sync::Mutex::new(rx));
+        for _ in 0..workers {
+            tokio::spawn(worker(state.clone(), rx.clone()));
+        }
+        log::info!(
+            "[EXT_CHANNEL] Chat queue started ({} workers, {} max pending)",
+            workers,
+            max_pending
+        );
+        ChatQueue {
+            jobs: tx,
+            max_pending,
+            tickets: Mutex::new(HashMap::new()),
+            pending: Mutex::new(VecDeque::new()),
+        }
+    })
+}
+
+impl ChatQueue {
+    fn update(&self, ticket_id: &str, f: impl FnOnce(&mut ChatTicket)) {
+        if let Some(tx) = self.tickets.lock().unwrap().get(ticket_id) {
+            tx.send_modify(f);
+        }
+    }
+
+    fn snapshot(&self, ticket_id: &str) -> Option<ChatTicket> {
+        let mut ticket = self.tickets.lock().unwrap().get(ticket_id)?.borrow().clone();
+        if ticket.status == TicketStatus::Queued {
+            ticket.queue_position = self.pending.lock().unwrap().iter().position(|id| id == ticket_id);
+        }
+        Some(ticket)
+    }
+
+    /// Drop finished tickets older than `TICKET_TTL`
+    fn prune(&self) {
+        let cutoff = Utc::now() - chrono::Duration::from_std(TICKET_TTL).unwrap_or_default();
+        self.tickets
+            .lock()
+            .unwrap()
+            .retain(|_, tx| tx.borrow().finished_at.is_none_or(|t| t > cutoff));
+    }
+
+    fn forget(&self, ticket_id: &str) {
+        self.tickets.lock().unwrap().remove(ticket_id);
+        self.pending.lock().unwrap().retain(|id| id != ticket_id);
+    }
+}
+
+/// Queue a message for processing. Errors when the queue is full.
+pub fn enqueue(
+    state: &web::Data<AppState>,
+    channel_id: i64,
+    channel: Channel,
+    body: GatewayChatRequest,
+) -> Result<ChatTicket, String> {
+    let queue = queue(state);
+    queue.prune();
+
+    let ticket_id = uuid::Uuid::new_v4().to_string();
+    let ticket = ChatTicket {
+        id: ticket_id.clone(),
+        channel_id,
+        status: TicketStatus::Queued,
+        created_at: Utc::now(),
+        finished_at: None,
+        queue_position: None,
+        result: None,
+    };
+
+    // Register before sending so a fast worker finds the ticket
+    queue
+        .tickets
+        .lock()
+        .unwrap()
+        .insert(ticket_id.clone(), watch::channel(ticket).0);
+    queue.pending.lock().unwrap().push_back(ticket_id.clone());
+
+    let job = Job {
+        ticket_id: ticket_id.clone(),
+        channel_id,
+        channel,
+        body,
+    };
+    if let Err(e) = queue.jobs.try_send(job) {
+        queue.forget(&ticket_id);
+        return Err(match e {
+            mpsc::error::TrySendError::Full(_) => format!(
+                "Chat queue is full ({} messages waiting), retry later",
+                queue.max_pending
+            ),
+            mpsc::error::TrySendError::Closed(_) => "Chat queue is not running".to_string(),
+        });
+    }
+
+    log::info!("[EXT_CHANNEL] Queued chat on channel {} as ticket {}", channel_id, ticket_id);
+    queue.snapshot(&ticket_id).ok_or_else(|| "Ticket vanished".to_string())
+}
+
+/// Current state of a ticket, if it exists and belongs to `channel_id`
+pub fn get(ticket_id: &str, channel_id: i64) -> Option<ChatTicket> {
+    QUEUE
+        .get()?
+        .snapshot(ticket_id)
+        .filter(|t| t.channel_id == channel_id)
+}
+
+/// Subscribe to a ticket's updates, if it exists and belongs to `channel_id`
+pub fn watch(ticket_id: &str, channel_id: i64) -> Option<watch::Receiver<ChatTicket>> {
+    let rx = QUEUE.get()?.tickets.lock().unwrap().get(ticket_id)?.subscribe();
+    let owned = rx.borrow().channel_id == channel_id;
+    owned.then_some(rx)
+}
+
+async fn worker(state: web::Data<AppState>, jobs: Arc<tokio::sync::Mutex<mpsc::Receiver<Job>>>) {
+    loop {
+        let job = jobs.lock().await.recv().await;
+        let Some(job) = job else { break };
+        let Some(queue) = QUEUE.get() else { continue };
+
+        queue.pending.lock().unwrap().retain(|id| id != &job.ticket_id);
+        queue.update(&job.ticket_id, |t| t.status = TicketStatus::Processing);
+
+        let result = run_chat(&state, job.channel_id, &job.channel, job.body).await;
+
+        queue.update(&job.ticket_id, |t| {
+            t.status = if result.success {
+                TicketStatus::Complete
+            } else {
+                TicketStatus::Failed
+            };
+            t.finished_at = Some(Utc::now());
+            t.result = Some(result);
+        });
+    }
+}
@@ -0,0 +1,134 @@
+//! Optional HMAC request signing for the gateway API
+//!
+//! When a channel has a signing secret, clients send two extra headers:
+//!
+//! ```text
+//! X-Stark-Timestamp: <unix seconds>
+//! X-Stark-Signature: sha256=<hex(hmac_sha256(secret, "<timestamp>.<METHOD>.<path>.<raw body>"))>
+//! ```
+//!
+//! `<path>` includes the query string, if any. Binding the timestamp, method and
+//! path into the signature means a captured request can't be altered or sent to
+//! another endpoint, and rejecting timestamps outside the replay window means it
+//! can't be resent later either. Bodyless requests sign an empty body.
+
+use actix_web::HttpRequest;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const TIMESTAMP_HEADER: &str = "X-Stark-Timestamp";
+pub const SIGNATURE_HEADER: &str = "X-Stark-Signature";
+
+/// Hex signature of a `method` request to `path` (with query) sent at `timestamp`
+pub fn sign(secret: &str, timestamp: i64, method: &str, path: &str, body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(method.to_ascii_uppercase().as_bytes());
+    mac.update(b".");
+    mac.update(path.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Check the signature headers of `req` against `body`.
+/// `now` and `window_secs` are unix seconds; the error is safe to return to the client.
+pub fn verify(
+    secret: &str,
+    req: &HttpRequest,
+    body: &[u8],
+    now: i64,
+    window_secs: i64,
+) -> Result<(), String> {
+    let header = |name: &str| {
+        req.headers()
+            .get(name)
+            .and_then(|h| h.to_str().ok())
+            .map(|s| s.trim().to_string())
+            .ok_or_else(|| format!("Missing {} header (this channel requires signed requests)", name))
+    };
+
+    let timestamp: i64 = header(TIMESTAMP_HEADER)?
+        .parse()
+        .map_err(|_| format!("{} must be unix seconds", TIMESTAMP_HEADER))?;
+    if now.abs_diff(timestamp) > window_secs.unsigned_abs() {
+        return Err(format!(
+            "Request timestamp is outside the {}s replay window",
+            window_secs
+        ));
+    }
+
+    let signature = header(SIGNATURE_HEADER)?.to_ascii_lowercase();
+    let signature = signature.strip_prefix("sha256=").unwrap_or(&signature);
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or_else(|| req.path());
+    let expected = sign(secret, timestamp, req.method().as_str(), path, body);
+    if !super::constant_time_eq(signature.as_bytes(), expected.as_bytes()) {
+        return Err("Invalid request signature".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    const SECRET: &str = "shh";
+    const NOW: i64 = 1_700_000_000;
+
+    const PATH: &str = "/api/gateway/chat";
+
+    fn signed(timestamp: i64, signature: &str) -> HttpRequest {
+        TestRequest::post()
+            .uri(PATH)
+            .insert_header((TIMESTAMP_HEADER, timestamp.to_string()))
+            .insert_header((SIGNATURE_HEADER, format!("sha256={}", signature)))
+            .to_http_request()
+    }
+
+    #[test]
+    fn test_valid_signature_is_accepted() {
+        let body = br#"{"message":"hi"}"#;
+        let req = signed(NOW - 10, &sign(SECRET, NOW - 10, "POST", PATH, body));
+        assert!(verify(SECRET, &req, body, NOW, 300).is_ok());
+    }
+
+    #[test]
+    fn test_tampered_or_stale_requests_are_rejected() {
+        let body = br#"{"message":"hi"}"#;
+
+        let req = signed(NOW, &sign(SECRET, NOW, "POST", PATH, body));
+        assert!(verify(SECRET, &req, br#"{"message":"bye"}"#, NOW, 300).is_err());
+        assert!(verify("other", &req, body, NOW, 300).is_err());
+
+        // Signed for a different method or endpoint
+        let req = signed(NOW, &sign(SECRET, NOW, "GET", PATH, body));
+        assert!(verify(SECRET, &req, body, NOW, 300).is_err());
+        let req = signed(NOW, &sign(SECRET, NOW, "POST", "/api/gateway/other", body));
+        assert!(verify(SECRET, &req, body, NOW, 300).is_err());
+
+        // Correctly signed, but replayed after the window
+        let old = NOW - 301;
+        let req = signed(old, &sign(SECRET, old, "POST", PATH, body));
+        assert!(verify(SECRET, &req, body, NOW, 300).unwrap_err().contains("replay window"));
+
+        let unsigned = TestRequest::default().to_http_request();
+        assert!(verify(SECRET, &unsigned, body, NOW, 300).unwrap_err().contains(TIMESTAMP_HEADER));
+    }
+
+    #[test]
+    fn test_extreme_timestamps_are_rejected_without_overflow() {
+        let body = b"";
+        for timestamp in [i64::MIN, i64::MAX] {
+            let req = signed(timestamp, &sign(SECRET, timestamp, "POST", PATH, body));
+            assert!(verify(SECRET, &req, body, NOW, 300).unwrap_err().contains("replay window"));
+        }
+    }
+}
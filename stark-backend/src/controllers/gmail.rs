@@ -235,6 +235,7 @@ async fn dispatch_email(
         force_safe_mode: false,
         platform_role_ids: vec![],
         chat_context: None,
+        thread_id: None,
     };
 
     // Broadcast event
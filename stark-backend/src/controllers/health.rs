@@ -1,12 +1,22 @@
 use actix_web::{web, HttpResponse, Responder};
+use futures_util::future::join_all;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
 
+use crate::keystore_client::KEYSTORE_CLIENT;
+use crate::models::DEFAULT_EMBEDDINGS_SERVER_URL;
 use crate::AppState;
 
 /// Version from Cargo.toml, available at compile time
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Timeout for each remote readiness check
+const READINESS_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
 pub fn config_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(web::resource("/api/health").route(web::get().to(health_check)));
+    cfg.service(web::resource("/api/health/ready").route(web::get().to(readiness_check)));
     cfg.service(web::resource("/api/version").route(web::get().to(get_version)));
     cfg.service(web::resource("/api/health/config").route(web::get().to(get_config_status)));
 }
@@ -18,6 +28,176 @@ async fn health_check() -> impl Responder {
     }))
 }
 
+/// Whether the instance can serve traffic without a subsystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Tier {
+    /// Down means unhealthy (503)
+    Critical,
+    /// Down means degraded; the instance keeps serving
+    Optional,
+}
+
+/// Overall readiness, from the worst failing subsystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Readiness {
+    Healthy,
+    Degraded,
+    Unhealthy,
+}
+
+/// Result of probing one subsystem
+#[derive(Debug, Serialize)]
+struct SubsystemCheck {
+    up: bool,
+    tier: Tier,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+    /// What to do about it (only when down)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remediation: Option<&'static str>,
+}
+
+impl SubsystemCheck {
+    fn from_result(result: Result<(), String>, tier: Tier, remediation: &'static str, started: Instant) -> Self {
+        let latency_ms = Some(started.elapsed().as_millis() as u64);
+        match result {
+            Ok(()) => Self { up: true, tier, latency_ms, detail: None, remediation: None },
+            Err(e) => Self { up: false, tier, latency_ms, detail: Some(e), remediation: Some(remediation) },
+        }
+    }
+}
+
+const DATABASE_REMEDIATION: &str =
+    "Check that the database file is readable and writable and the disk isn't full. If the connection pool is exhausted, restart the backend.";
+const WALLET_REMEDIATION: &str =
+    "Check that BURNER_WALLET_BOT_PRIVATE_KEY is a valid private key (standard mode) or that FLASH_KEYSTORE_URL is reachable (Flash mode), then restart.";
+const EMBEDDINGS_REMEDIATION: &str =
+    "Start the embeddings server or fix the embeddings server URL in Bot Settings. Until then, memory search uses full-text matching only.";
+const KEYSTORE_REMEDIATION: &str =
+    "Check the keystore URL in Bot Settings and network access. Until then, cloud backup and restore are unavailable.";
+const MODULE_REMEDIATION: &str =
+    "Start the module's service (see the command in its module.toml) or disable the module. Its tools fail until then.";
+
+fn overall_status<'a>(checks: impl IntoIterator<Item = &'a SubsystemCheck>) -> Readiness {
+    let mut status = Readiness::Healthy;
+    for check in checks {
+        if !check.up {
+            if check.tier == Tier::Critical {
+                return Readiness::Unhealthy;
+            }
+            status = Readiness::Degraded;
+        }
+    }
+    status
+}
+
+async fn probe_http(client: &reqwest::Client, url: String) -> Result<(), String> {
+    match client.get(&url).send().await {
+        Ok(resp) if resp.status().is_success() => Ok(()),
+        Ok(resp) => Err(format!("HTTP {}", resp.status())),
+        Err(e) if e.is_timeout() => Err("Timed out".to_string()),
+        Err(_) => Err("Unreachable".to_string()),
+    }
+}
+
+/// Readiness probe: reports each subsystem and returns 503 (unhealthy) when a
+/// critical one (the database, or a configured wallet that failed to initialize)
+/// is down. Embeddings, keystore and module services are optional: when only
+/// they fail the instance is degraded but still returns 200.
+async fn readiness_check(state: web::Data<AppState>) -> impl Responder {
+    let client = reqwest::Client::builder()
+        .timeout(READINESS_CHECK_TIMEOUT)
+        .build()
+        .unwrap_or_default();
+
+    let started = Instant::now();
+    let database = SubsystemCheck::from_result(state.db.ping(), Tier::Critical, DATABASE_REMEDIATION, started);
+
+    // A wallet is only required when one is configured
+    let wallet_expected = std::env::var("FLASH_KEYSTORE_URL").is_ok()
+        || crate::config::burner_wallet_private_key().is_some();
+    let wallet_up = state.wallet_provider.is_some() || !wallet_expected;
+    let wallet = SubsystemCheck {
+        up: wallet_up,
+        tier: if wallet_expected { Tier::Critical } else { Tier::Optional },
+        latency_ms: None,
+        detail: match (&state.wallet_provider, wallet_expected) {
+            (Some(wp), _) => Some(format!("{} mode", wp.mode_name())),
+            (None, true) => Some("Wallet configured but provider failed to initialize".to_string()),
+            (None, false) => Some("No wallet configured".to_string()),
+        },
+        remediation: (!wallet_up).then_some(WALLET_REMEDIATION),
+    };
+
+    let embeddings_url = state
+        .db
+        .get_bot_settings()
+        .ok()
+        .and_then(|s| s.embeddings_server_url)
+        .filter(|u| !u.is_empty())
+        .unwrap_or_else(|| DEFAULT_EMBEDDINGS_SERVER_URL.to_string());
+    let embeddings = async {
+        let started = Instant::now();
+        let result = probe_http(&client, format!("{}/health", embeddings_url.trim_end_matches('/'))).await;
+        SubsystemCheck::from_result(result, Tier::Optional, EMBEDDINGS_REMEDIATION, started)
+    };
+    let keystore = async {
+        let started = Instant::now();
+        let result = probe_http(&client, format!("{}/api/health", KEYSTORE_CLIENT.get_base_url().await)).await;
+        SubsystemCheck::from_result(result, Tier::Optional, KEYSTORE_REMEDIATION, started)
+    };
+
+    let registry = crate::modules::ModuleRegistry::new();
+    let enabled_modules: Vec<_> = state
+        .db
+        .list_installed_modules()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|m| m.enabled)
+        .filter_map(|m| registry.get(&m.module_name))
+        .collect();
+    let modules = join_all(enabled_modules.iter().map(|module| {
+        let client = &client;
+        async move {
+            let started = Instant::now();
+            let url = format!("{}{}", module.service_url(), module.health_endpoint());
+            let result = probe_http(client, url).await;
+            let check = SubsystemCheck::from_result(result, Tier::Optional, MODULE_REMEDIATION, started);
+            (module.name().to_string(), check)
+        }
+    }));
+
+    let (embeddings, keystore, modules) = tokio::join!(embeddings, keystore, modules);
+    let modules: BTreeMap<String, SubsystemCheck> = modules.into_iter().collect();
+
+    let status = overall_status(
+        [&database, &wallet, &embeddings, &keystore]
+            .into_iter()
+            .chain(modules.values()),
+    );
+    let body = serde_json::json!({
+        "status": status,
+        "version": VERSION,
+        "checks": {
+            "database": database,
+            "wallet_provider": wallet,
+            "embeddings": embeddings,
+            "keystore": keystore,
+            "modules": modules,
+        },
+    });
+
+    if status == Readiness::Unhealthy {
+        HttpResponse::ServiceUnavailable().json(body)
+    } else {
+        HttpResponse::Ok().json(body)
+    }
+}
+
 async fn get_version() -> impl Responder {
     HttpResponse::Ok().json(serde_json::json!({
         "version": VERSION
@@ -39,6 +219,35 @@ async fn get_config_status(state: web::Data<AppState>) -> impl Responder {
         "wallet_configured": state.wallet_provider.is_some(),
         "guest_dashboard_enabled": guest_dashboard,
         "wallet_address": wallet_address,
-        "wallet_mode": wallet_mode
+        "wallet_mode": wallet_mode,
+        "signing_frozen": crate::wallet::is_signing_frozen()
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(up: bool, tier: Tier) -> SubsystemCheck {
+        let result = if up { Ok(()) } else { Err("Unreachable".to_string()) };
+        SubsystemCheck::from_result(result, tier, KEYSTORE_REMEDIATION, Instant::now())
+    }
+
+    #[test]
+    fn only_critical_failures_make_the_instance_unhealthy() {
+        let healthy = [check(true, Tier::Critical), check(true, Tier::Optional)];
+        assert_eq!(overall_status(&healthy), Readiness::Healthy);
+        let degraded = [check(true, Tier::Critical), check(false, Tier::Optional)];
+        assert_eq!(overall_status(&degraded), Readiness::Degraded);
+        let unhealthy = [check(false, Tier::Critical), check(false, Tier::Optional)];
+        assert_eq!(overall_status(&unhealthy), Readiness::Unhealthy);
+    }
+
+    #[test]
+    fn remediation_only_for_failing_subsystems() {
+        assert!(check(true, Tier::Optional).remediation.is_none());
+        let down = serde_json::to_value(check(false, Tier::Optional)).unwrap();
+        assert_eq!(down["tier"], "optional");
+        assert_eq!(down["remediation"], KEYSTORE_REMEDIATION);
+    }
+}
@@ -2,11 +2,13 @@
 //!
 //! `POST /api/internal/hooks/fire` — fires a custom hook event.
 //! Authenticated via the `X-Internal-Token` header (same token modules use
-//! for other internal-only endpoints).
+//! for other internal-only endpoints) plus the module's `X-Module-Token`; the
+//! module must declare the `hooks.fire` permission.
 
 use actix_web::{web, HttpRequest, HttpResponse};
 use serde::Deserialize;
 
+use crate::modules::permissions::{self, Permission};
 use crate::persona_hooks;
 use crate::AppState;
 
@@ -29,18 +31,11 @@ async fn fire_hook(
     req: HttpRequest,
     body: web::Json<FireHookRequest>,
 ) -> HttpResponse {
-    // Authenticate via internal token
-    let token = req
-        .headers()
-        .get("X-Internal-Token")
-        .and_then(|h| h.to_str().ok())
-        .unwrap_or("");
-
-    if token.is_empty() || token != state.internal_token {
-        return HttpResponse::Unauthorized().json(serde_json::json!({
-            "error": "Invalid or missing X-Internal-Token"
-        }));
-    }
+    // Authenticate the calling module
+    let module = match permissions::authorize(&req, Permission::HooksFire) {
+        Ok(m) => m,
+        Err(resp) => return resp,
+    };
 
     let event = body.event.trim();
     if event.is_empty() {
@@ -49,7 +44,10 @@ async fn fire_hook(
         }));
     }
 
-    log::info!("[HOOKS_API] Firing custom hook event='{}' data={}", event, body.data);
+    log::info!(
+        "[HOOKS_API] {} firing custom hook event='{}' data={}",
+        module, event, body.data
+    );
 
     persona_hooks::fire_custom_hooks(event, body.data.clone(), &state.dispatcher).await;
 
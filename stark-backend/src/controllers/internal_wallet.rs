@@ -1,6 +1,12 @@
 use actix_web::{web, HttpRequest, HttpResponse};
+use ethers::types::Address;
 use serde::{Deserialize, Serialize};
 
+use crate::modules::permissions::{self, Permission};
+use crate::tools::builtin::cryptocurrency::web3_tx::parse_u256;
+use crate::tools::rpc_config::resolve_rpc;
+use crate::tx_queue::{GasStrategy, SimulationStatus};
+use crate::web3::sign_transaction_for_queue;
 use crate::AppState;
 
 // ── Request / Response types ────────────────────────────────────────────
@@ -28,44 +34,56 @@ pub struct SignMessageResponse {
     pub error: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct AddressResponse {
-    pub success: bool,
-    pub address: String,
+/// Networks modules may sign for
+const SIGNABLE_NETWORKS: &[&str] = &["base", "mainnet"];
+
+#[derive(Debug, Deserialize)]
+pub struct SignTransactionRequest {
+    #[serde(default = "default_network")]
+    pub network: String,
+    pub to: String,
+    #[serde(default = "default_data")]
+    pub data: String,
+    /// Wei, decimal or 0x-hex
+    #[serde(default = "default_value")]
+    pub value: String,
 }
 
-// ── Auth helper ─────────────────────────────────────────────────────────
+fn default_network() -> String {
+    "base".to_string()
+}
 
-fn validate_internal_token(state: &web::Data<AppState>, req: &HttpRequest) -> Result<(), HttpResponse> {
-    let token = req
-        .headers()
-        .get("Authorization")
-        .and_then(|h| h.to_str().ok())
-        .map(|s| s.trim_start_matches("Bearer ").to_string());
+fn default_data() -> String {
+    "0x".to_string()
+}
 
-    let token = match token {
-        Some(t) => t,
-        None => {
-            return Err(HttpResponse::Unauthorized().json(serde_json::json!({
-                "error": "No authorization token provided"
-            })));
-        }
-    };
+fn default_value() -> String {
+    "0".to_string()
+}
 
-    // Constant-time comparison to prevent timing attacks
-    let expected = state.internal_token.as_bytes();
-    let provided = token.as_bytes();
-    let mut diff = expected.len() ^ provided.len();
-    for (a, b) in expected.iter().zip(provided.iter()) {
-        diff |= (*a ^ *b) as usize;
-    }
-    if diff != 0 {
-        return Err(HttpResponse::Unauthorized().json(serde_json::json!({
-            "error": "Invalid internal token"
-        })));
+#[derive(Debug, Serialize)]
+pub struct SignTransactionResponse {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_tx: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SignTransactionResponse {
+    fn error(msg: impl Into<String>) -> Self {
+        Self { success: false, signed_tx: None, from: None, nonce: None, error: Some(msg.into()) }
     }
+}
 
-    Ok(())
+#[derive(Debug, Serialize)]
+pub struct AddressResponse {
+    pub success: bool,
+    pub address: String,
 }
 
 // ── Handlers ────────────────────────────────────────────────────────────
@@ -74,7 +92,7 @@ async fn get_address(
     state: web::Data<AppState>,
     req: HttpRequest,
 ) -> HttpResponse {
-    if let Err(resp) = validate_internal_token(&state, &req) {
+    if let Err(resp) = permissions::authorize(&req, Permission::WalletAddress) {
         return resp;
     }
 
@@ -95,9 +113,10 @@ async fn sign_message(
     req: HttpRequest,
     body: web::Json<SignMessageRequest>,
 ) -> HttpResponse {
-    if let Err(resp) = validate_internal_token(&state, &req) {
-        return resp;
-    }
+    let module = match permissions::authorize(&req, Permission::WalletSign) {
+        Ok(m) => m,
+        Err(resp) => return resp,
+    };
 
     let wp = match &state.wallet_provider {
         Some(wp) => wp,
@@ -141,7 +160,7 @@ async fn sign_message(
             })
         }
         Err(e) => {
-            log::error!("[INTERNAL_WALLET] sign_message failed: {}", e);
+            log::error!("[INTERNAL_WALLET] sign_message for {} failed: {}", module, e);
             HttpResponse::InternalServerError().json(SignMessageResponse {
                 success: false,
                 signature: None,
@@ -152,12 +171,112 @@ async fn sign_message(
     }
 }
 
+/// Sign (but don't broadcast) a transaction for a module acting autonomously.
+/// Nonce, gas and fees are filled in here; the nonce is reserved in the tx queue
+/// so concurrent module and user transactions never share one. The transaction
+/// is simulated first and refused if it would revert. Only allowed when rogue mode is on, since
+/// nobody reviews these transactions before they are signed.
+async fn sign_transaction(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<SignTransactionRequest>,
+) -> HttpResponse {
+    let module = match permissions::authorize(&req, Permission::WalletSign) {
+        Ok(m) => m,
+        Err(resp) => return resp,
+    };
+
+    let wp = match &state.wallet_provider {
+        Some(wp) => wp,
+        None => {
+            return HttpResponse::ServiceUnavailable()
+                .json(SignTransactionResponse::error("No wallet provider configured"));
+        }
+    };
+
+    let settings = state.db.get_bot_settings().unwrap_or_default();
+    if !settings.rogue_mode_enabled {
+        return HttpResponse::Forbidden().json(SignTransactionResponse::error(
+            "Autonomous transactions require rogue mode to be enabled in bot settings",
+        ));
+    }
+
+    if !SIGNABLE_NETWORKS.contains(&body.network.as_str()) {
+        return HttpResponse::BadRequest().json(SignTransactionResponse::error(format!(
+            "Unsupported network '{}'. Supported: {}",
+            body.network,
+            SIGNABLE_NETWORKS.join(", ")
+        )));
+    }
+    let to: Address = match body.to.parse() {
+        Ok(a) => a,
+        Err(_) => {
+            return HttpResponse::BadRequest()
+                .json(SignTransactionResponse::error(format!("Invalid 'to' address: {}", body.to)));
+        }
+    };
+    let calldata = match hex::decode(body.data.strip_prefix("0x").unwrap_or(&body.data)) {
+        Ok(d) => d,
+        Err(e) => {
+            return HttpResponse::BadRequest()
+                .json(SignTransactionResponse::error(format!("Invalid hex data: {}", e)));
+        }
+    };
+    let value = match parse_u256(&body.value) {
+        Ok(v) => v,
+        Err(e) => return HttpResponse::BadRequest().json(SignTransactionResponse::error(e)),
+    };
+
+    let gas_strategy = GasStrategy::from_settings(Some(&settings.gas_strategy), settings.gas_max_fee_gwei);
+    match sign_transaction_for_queue(
+        &body.network,
+        to,
+        calldata,
+        value,
+        &resolve_rpc(&body.network),
+        wp,
+        &gas_strategy,
+        &state.tx_queue,
+    )
+    .await
+    {
+        // Modules sign without a user confirming, so a reverting tx is refused outright
+        Ok(signed) if signed.simulation.status == SimulationStatus::Reverted => {
+            state.tx_queue.release_nonce(&signed.network, &signed.from, signed.nonce);
+            let e = format!(
+                "Transaction simulation reverted: {}. The transaction was refused.",
+                signed.simulation.error.as_deref().unwrap_or("unknown reason")
+            );
+            log::warn!("[INTERNAL_WALLET] sign_transaction for {} refused: {}", module, e);
+            HttpResponse::UnprocessableEntity().json(SignTransactionResponse::error(e))
+        }
+        Ok(signed) => {
+            log::info!(
+                "[INTERNAL_WALLET] Signed {} transaction to={} value={} nonce={} on {}",
+                module, signed.to, signed.value, signed.nonce, signed.network
+            );
+            HttpResponse::Ok().json(SignTransactionResponse {
+                success: true,
+                signed_tx: Some(signed.signed_tx_hex),
+                from: Some(signed.from),
+                nonce: Some(signed.nonce),
+                error: None,
+            })
+        }
+        Err(e) => {
+            log::warn!("[INTERNAL_WALLET] sign_transaction for {} failed: {}", module, e);
+            HttpResponse::UnprocessableEntity().json(SignTransactionResponse::error(e))
+        }
+    }
+}
+
 // ── Route config ────────────────────────────────────────────────────────
 
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api/internal/wallet")
             .route("/address", web::get().to(get_address))
-            .route("/sign-message", web::post().to(sign_message)),
+            .route("/sign-message", web::post().to(sign_message))
+            .route("/sign-transaction", web::post().to(sign_transaction)),
     );
 }
@@ -82,6 +82,7 @@ struct MemoryItem {
     created_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     agent_subtype: Option<String>,
+    access_count: i64,
 }
 
 #[derive(Debug, Serialize)]
@@ -307,7 +308,7 @@ async fn search(
 
             // Graph expansion: surface memories connected to FTS hits via edges
             let graph_limit = (limit / 2).max(3).min(10);
-            let graph_results = match data.db.graph_expand_from_seeds(&seed_ids, graph_limit) {
+            let graph_results = match data.db.graph_expand_from_seeds(&seed_ids, graph_limit, None) {
                 Ok(neighbors) => neighbors
                     .into_iter()
                     .filter_map(|(neighbor_id, strength)| {
@@ -366,6 +367,7 @@ fn rows_to_items(rows: Vec<crate::db::tables::memories::MemoryRow>) -> Vec<Memor
             source_type: m.source_type,
             created_at: m.created_at,
             agent_subtype: m.agent_subtype,
+            access_count: m.access_count,
         })
         .collect()
 }
@@ -1007,7 +1009,7 @@ async fn hybrid_search(
 
     let limit = query.limit.clamp(1, 50) as usize;
 
-    match engine.search(&query.query, limit, query.agent_subtype.as_deref()).await {
+    match engine.search(&query.query, limit, query.agent_subtype.as_deref(), None).await {
         Ok(results) => {
             let items: Vec<HybridSearchItem> = results
                 .into_iter()
@@ -1134,36 +1136,9 @@ async fn rebuild_associations(data: web::Data<AppState>, req: HttpRequest) -> im
     };
 
     let db = data.db.clone();
-    let db2 = data.db.clone();
     let embedding_generator = engine.embedding_generator().clone();
-    let config = crate::memory::association_loop::AssociationLoopConfig::default();
-
     tokio::spawn(async move {
-        // Step 0: Backfill missing entity_name / category metadata from content
-        match crate::memory::association_loop::backfill_memory_metadata(&db2) {
-            Ok(count) => {
-                if count > 0 {
-                    log::info!("[ASSOCIATIONS] Backfilled metadata for {} memories", count);
-                }
-            }
-            Err(e) => log::warn!("[ASSOCIATIONS] Metadata backfill failed: {}", e),
-        }
-
-        // Step 1: Reclassify existing "related" associations using metadata heuristics
-        match crate::memory::association_loop::reclassify_existing_associations(&db2) {
-            Ok(count) => {
-                if count > 0 {
-                    log::info!("[ASSOCIATIONS] Reclassified {} existing associations", count);
-                }
-            }
-            Err(e) => log::error!("[ASSOCIATIONS] Reclassification failed: {}", e),
-        }
-
-        // Step 2: Discover new associations with proper type classification
-        match crate::memory::association_loop::run_association_pass(&db, &embedding_generator, &config).await {
-            Ok(()) => log::info!("[ASSOCIATIONS] Rebuild pass complete"),
-            Err(e) => log::error!("[ASSOCIATIONS] Rebuild pass failed: {}", e),
-        }
+        run_association_rebuild(&db, &embedding_generator).await;
     });
 
     HttpResponse::Ok().json(BackfillResponse {
@@ -1173,6 +1148,39 @@ async fn rebuild_associations(data: web::Data<AppState>, req: HttpRequest) -> im
     })
 }
 
+/// Backfill missing metadata, reclassify existing associations, then discover new ones
+async fn run_association_rebuild(
+    db: &crate::db::Database,
+    embedding_generator: &std::sync::Arc<dyn crate::memory::EmbeddingGenerator + Send + Sync>,
+) {
+    // Step 0: Backfill missing entity_name / category metadata from content
+    match crate::memory::association_loop::backfill_memory_metadata(db) {
+        Ok(count) => {
+            if count > 0 {
+                log::info!("[ASSOCIATIONS] Backfilled metadata for {} memories", count);
+            }
+        }
+        Err(e) => log::warn!("[ASSOCIATIONS] Metadata backfill failed: {}", e),
+    }
+
+    // Step 1: Reclassify existing "related" associations using metadata heuristics
+    match crate::memory::association_loop::reclassify_existing_associations(db) {
+        Ok(count) => {
+            if count > 0 {
+                log::info!("[ASSOCIATIONS] Reclassified {} existing associations", count);
+            }
+        }
+        Err(e) => log::error!("[ASSOCIATIONS] Reclassification failed: {}", e),
+    }
+
+    // Step 2: Discover new associations with proper type classification
+    let config = crate::memory::association_loop::AssociationLoopConfig::default();
+    match crate::memory::association_loop::run_association_pass(db, embedding_generator, &config).await {
+        Ok(()) => log::info!("[ASSOCIATIONS] Rebuild pass complete"),
+        Err(e) => log::error!("[ASSOCIATIONS] Rebuild pass failed: {}", e),
+    }
+}
+
 /// DELETE /api/memory/all - Delete all memories
 async fn delete_all_memories(
     data: web::Data<AppState>,
@@ -1254,22 +1262,14 @@ struct MemoryExportData {
     associations: Vec<AssociationExportEntry>,
 }
 
+/// A memory in the same shape as full backups, plus its ID for remapping associations.
+/// Entries copied from a backup file (without `original_id`) import too.
 #[derive(Debug, Serialize, Deserialize)]
 struct MemoryExportEntry {
-    original_id: i64,
-    memory_type: String,
-    content: String,
-    category: Option<String>,
-    tags: Option<String>,
-    importance: i64,
-    identity_id: Option<String>,
-    entity_type: Option<String>,
-    entity_name: Option<String>,
-    source_type: Option<String>,
-    log_date: Option<String>,
-    created_at: String,
     #[serde(default)]
-    agent_subtype: Option<String>,
+    original_id: Option<i64>,
+    #[serde(flatten)]
+    memory: crate::backup::MemoryEntry,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -1315,6 +1315,9 @@ struct ImportResponse {
     skipped_duplicates: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     associations_created: Option<usize>,
+    /// Whether embedding backfill and association rebuild were started for the new memories
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reindex_started: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
 }
@@ -1425,19 +1428,8 @@ async fn export_memories(
     let export_entries: Vec<MemoryExportEntry> = memories
         .into_iter()
         .map(|m| MemoryExportEntry {
-            original_id: m.id,
-            memory_type: m.memory_type,
-            content: m.content,
-            category: m.category,
-            tags: m.tags,
-            importance: m.importance,
-            identity_id: m.identity_id,
-            entity_type: m.entity_type,
-            entity_name: m.entity_name,
-            source_type: m.source_type,
-            log_date: m.log_date,
-            created_at: m.created_at,
-            agent_subtype: m.agent_subtype,
+            original_id: Some(m.id),
+            memory: crate::backup::MemoryEntry::from_row(&m),
         })
         .collect();
 
@@ -1474,6 +1466,7 @@ async fn import_memories(
                 imported_count: None,
                 skipped_duplicates: None,
                 associations_created: None,
+                reindex_started: None,
                 error: Some(format!("Failed to clear memories for replace: {}", e)),
             });
         }
@@ -1484,46 +1477,45 @@ async fn import_memories(
     let mut id_mapping: std::collections::HashMap<i64, i64> = std::collections::HashMap::new();
 
     for entry in &body.data.memories {
-        // Merge strategy: check for duplicates via FTS
+        let mem = &entry.memory;
+        // Merge strategy: skip exact (content + type) duplicates, then near-duplicates via FTS
         if strategy == "merge" {
-            if let Ok(similar) = data.db.find_similar_memories_fts(
-                &entry.content,
-                Some(&entry.memory_type),
-                entry.identity_id.as_deref(),
-                3,
-            ) {
-                // BM25 returns negative scores; strong match = more negative (< -5)
-                if similar.iter().any(|(_, rank)| *rank < -5.0) {
-                    skipped += 1;
-                    // Map to the best match so associations can still reference it
-                    if let Some((best, _)) = similar.first() {
-                        id_mapping.insert(entry.original_id, best.id);
+            let existing = data
+                .db
+                .find_memory_by_content(&mem.memory_type, &mem.content, mem.identity_id.as_deref())
+                .ok()
+                .flatten()
+                .or_else(|| {
+                    let similar = data
+                        .db
+                        .find_similar_memories_fts(&mem.content, Some(&mem.memory_type), mem.identity_id.as_deref(), 3)
+                        .ok()?;
+                    // BM25 returns negative scores; strong match = more negative (< -5)
+                    if similar.iter().any(|(_, rank)| *rank < -5.0) {
+                        similar.first().map(|(best, _)| best.id)
+                    } else {
+                        None
                     }
-                    continue;
+                });
+            if let Some(existing_id) = existing {
+                skipped += 1;
+                // Map to the existing memory so associations can still reference it
+                if let Some(original_id) = entry.original_id {
+                    id_mapping.insert(original_id, existing_id);
                 }
+                continue;
             }
         }
 
-        match data.db.insert_memory(
-            &entry.memory_type,
-            &entry.content,
-            entry.category.as_deref(),
-            entry.tags.as_deref(),
-            entry.importance,
-            entry.identity_id.as_deref(),
-            None,
-            entry.entity_type.as_deref(),
-            entry.entity_name.as_deref(),
-            entry.source_type.as_deref(),
-            entry.log_date.as_deref(),
-            entry.agent_subtype.as_deref(),
-        ) {
+        match mem.insert(&data.db) {
             Ok(new_id) => {
-                id_mapping.insert(entry.original_id, new_id);
+                if let Some(original_id) = entry.original_id {
+                    id_mapping.insert(original_id, new_id);
+                }
                 imported += 1;
             }
             Err(e) => {
-                log::warn!("Failed to import memory {}: {}", entry.original_id, e);
+                log::warn!("Failed to import memory {:?}: {}", entry.original_id, e);
             }
         }
     }
@@ -1556,11 +1548,30 @@ async fn import_memories(
         }
     }
 
+    // New memories have no embeddings yet: backfill them, then rebuild associations
+    let reindex_started = match &data.hybrid_search {
+        Some(engine) if imported > 0 => {
+            engine.invalidate_caches();
+            let engine = engine.clone();
+            let db = data.db.clone();
+            tokio::spawn(async move {
+                match engine.backfill_embeddings().await {
+                    Ok(count) => log::info!("[EMBEDDINGS] Post-import backfill complete: {} embeddings generated", count),
+                    Err(e) => log::warn!("[EMBEDDINGS] Post-import backfill skipped: {}", e),
+                }
+                run_association_rebuild(&db, engine.embedding_generator()).await;
+            });
+            true
+        }
+        _ => false,
+    };
+
     HttpResponse::Ok().json(ImportResponse {
         success: true,
         imported_count: Some(imported),
         skipped_duplicates: Some(skipped),
         associations_created: Some(associations_created),
+        reindex_started: Some(reindex_started),
         error: None,
     })
 }
@@ -5,8 +5,10 @@ pub mod auth;
 pub mod broadcasted_transactions;
 pub mod channels;
 pub mod chat;
+pub mod confirmation;
 pub mod cron;
 pub mod dashboard;
+pub mod dev;
 pub mod heartbeat;
 pub mod eip8004;
 pub mod ext;
@@ -38,6 +40,7 @@ pub mod x402;
 pub mod x402_limits;
 
 use actix_web::{web, HttpRequest, HttpResponse};
+use crate::models::{SessionCheck, SESSION_EXPIRED_CODE};
 use crate::AppState;
 
 /// Shared session validation for controller handlers.
@@ -60,9 +63,13 @@ pub fn validate_session(
         }
     };
 
-    match state.db.validate_session(&token) {
-        Ok(Some(_)) => Ok(()),
-        Ok(None) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+    match state.db.check_session(&token) {
+        Ok(SessionCheck::Valid(_)) => Ok(()),
+        Ok(SessionCheck::Expired) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Session expired",
+            "code": SESSION_EXPIRED_CODE
+        }))),
+        Ok(SessionCheck::Invalid) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
             "error": "Invalid or expired session"
         }))),
         Err(e) => {
@@ -48,33 +48,51 @@ fn start_module_service(module_name: &str, port: u16, db: &crate::db::Database)
                     return;
                 }
             };
-            let mut cmd = std::process::Command::new("sh");
-            cmd.arg("-c").arg(&command);
-            cmd.current_dir(&module_dir);
-            cmd.stdout(std::process::Stdio::inherit())
-                .stderr(std::process::Stdio::inherit());
-            cmd.env("MODULE_PORT", port.to_string());
+            let mut envs = vec![("MODULE_PORT".to_string(), port.to_string())];
 
             // Also set the module-specific port env var (e.g. OPENAGENT_PORT, WALLET_MONITOR_PORT)
             if let Some(port_var) = module.manifest_port_env_var() {
-                cmd.env(&port_var, port.to_string());
+                envs.push((port_var, port.to_string()));
             }
 
             // Pass all declared env vars from DB api_keys, falling back to process env
             for env_key in module.manifest_env_var_keys() {
                 if let Ok(Some(key)) = db.get_api_key(&env_key) {
-                    cmd.env(&env_key, &key.api_key);
+                    envs.push((env_key, key.api_key));
                 } else if let Ok(val) = std::env::var(&env_key) {
                     if !val.is_empty() {
-                        cmd.env(&env_key, &val);
+                        envs.push((env_key, val));
                     }
                 }
             }
 
-            match cmd.spawn() {
-                Ok(_) => log::info!("[MODULE] Started {} via `{}` (port {})", module_name, command, port),
-                Err(e) => log::error!("[MODULE] Failed to start {} via `{}`: {}", module_name, command, e),
-            }
+            let name = module_name.to_string();
+            let permissions = module.permissions();
+            crate::modules::supervisor::spawn(module_name, module.resource_limits(), move || {
+                let mut cmd = std::process::Command::new("sh");
+                cmd.arg("-c").arg(&command);
+                cmd.current_dir(&module_dir);
+                cmd.stdout(std::process::Stdio::inherit())
+                    .stderr(std::process::Stdio::inherit());
+                cmd.envs(envs.iter().map(|(k, v)| (k, v)));
+                // The process env holds the startup token, which may since have been rotated
+                cmd.env("STARKBOT_INTERNAL_TOKEN", crate::modules::internal_token::current());
+                cmd.env(
+                    crate::modules::module_identity::ENV_VAR,
+                    crate::modules::module_identity::issue(&name, permissions.clone()),
+                );
+
+                match cmd.spawn() {
+                    Ok(child) => {
+                        log::info!("[MODULE] Started {} via `{}` (port {})", name, command, port);
+                        Some(child)
+                    }
+                    Err(e) => {
+                        log::error!("[MODULE] Failed to start {} via `{}`: {}", name, command, e);
+                        None
+                    }
+                }
+            });
             return;
         }
     }
@@ -90,15 +108,31 @@ fn start_module_service(module_name: &str, port: u16, db: &crate::db::Database)
         return;
     }
 
-    let mut cmd = std::process::Command::new(&exe_path);
-    cmd.stdout(std::process::Stdio::inherit())
-        .stderr(std::process::Stdio::inherit());
-    cmd.env("MODULE_PORT", port.to_string());
+    let limits = registry.get(module_name).map(|m| m.resource_limits()).unwrap_or_default();
+    let permissions = registry.get(module_name).map(|m| m.permissions()).unwrap_or_default();
+    let name = module_name.to_string();
+    crate::modules::supervisor::spawn(module_name, limits, move || {
+        let mut cmd = std::process::Command::new(&exe_path);
+        cmd.stdout(std::process::Stdio::inherit())
+            .stderr(std::process::Stdio::inherit());
+        cmd.env("MODULE_PORT", port.to_string());
+        cmd.env("STARKBOT_INTERNAL_TOKEN", crate::modules::internal_token::current());
+        cmd.env(
+            crate::modules::module_identity::ENV_VAR,
+            crate::modules::module_identity::issue(&name, permissions.clone()),
+        );
 
-    match cmd.spawn() {
-        Ok(_) => log::info!("[MODULE] Started {} (port {})", binary_name, port),
-        Err(e) => log::error!("[MODULE] Failed to start {}: {}", binary_name, e),
-    }
+        match cmd.spawn() {
+            Ok(child) => {
+                log::info!("[MODULE] Started {} (port {})", binary_name, port);
+                Some(child)
+            }
+            Err(e) => {
+                log::error!("[MODULE] Failed to start {}: {}", binary_name, e);
+                None
+            }
+        }
+    });
 }
 
 #[derive(Serialize)]
@@ -117,6 +151,10 @@ struct ModuleInfo {
     service_url: String,
     service_port: u16,
     installed_at: Option<String>,
+    /// Internal capabilities the module declared (and is granted)
+    permissions: Vec<crate::modules::permissions::Permission>,
+    /// Resource usage and limits of the service, if the backend spawned it
+    usage: Option<crate::modules::supervisor::ModuleUsage>,
 }
 
 #[derive(Deserialize)]
@@ -178,6 +216,9 @@ async fn deactivate_module(data: &web::Data<AppState>, module_name: &str) {
 async fn list_modules(data: web::Data<AppState>, _req: HttpRequest) -> HttpResponse {
     let registry = crate::modules::ModuleRegistry::new();
     let installed = data.db.list_installed_modules().unwrap_or_default();
+    let usage = tokio::task::spawn_blocking(crate::modules::supervisor::usage_all)
+        .await
+        .unwrap_or_default();
 
     let mut modules = Vec::new();
     for module in registry.available_modules() {
@@ -199,6 +240,8 @@ async fn list_modules(data: web::Data<AppState>, _req: HttpRequest) -> HttpRespo
             service_url: module.service_url(),
             service_port: module.default_port(),
             installed_at: installed_entry.map(|e| e.installed_at.to_rfc3339()),
+            permissions: module.permissions(),
+            usage: usage.get(module.name()).cloned(),
         });
     }
 
@@ -443,7 +486,7 @@ async fn module_status(
         })),
     };
 
-    let url = format!("{}/rpc/status", module.service_url());
+    let url = format!("{}{}", module.service_url(), module.health_endpoint());
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(3))
         .build()
@@ -467,6 +510,120 @@ async fn module_status(
     }
 }
 
+/// Per-module timeout for the aggregated status check
+const STATUS_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// How long an aggregated status snapshot is served before modules are polled again
+const STATUS_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(5);
+
+static STATUS_SNAPSHOT: std::sync::Mutex<Option<(std::time::Instant, serde_json::Value)>> =
+    std::sync::Mutex::new(None);
+
+/// Status of one module service in the aggregated health panel
+#[derive(Debug, Serialize)]
+struct ModuleStatusEntry {
+    name: String,
+    /// "healthy", "unhealthy", "timeout" or "offline"
+    status: &'static str,
+    latency_ms: u64,
+    /// The service's own status payload, when it answered with JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    usage: Option<crate::modules::supervisor::ModuleUsage>,
+}
+
+/// Poll one module's status endpoint, giving up after `timeout`
+async fn check_module_status(
+    client: &reqwest::Client,
+    name: String,
+    url: String,
+    timeout: std::time::Duration,
+) -> ModuleStatusEntry {
+    let started = std::time::Instant::now();
+    let result = tokio::time::timeout(timeout, async {
+        let resp = client.get(&url).send().await?;
+        let status = resp.status();
+        let body = resp.text().await?;
+        Ok::<_, reqwest::Error>((status, body))
+    })
+    .await;
+
+    let (status, data, error) = match result {
+        Err(_) => ("timeout", None, Some(format!("No response within {}s", timeout.as_secs()))),
+        Ok(Err(_)) => ("offline", None, Some("Service unreachable".to_string())),
+        Ok(Ok((code, body))) => {
+            let data = serde_json::from_str(&body).ok();
+            if code.is_success() {
+                ("healthy", data, None)
+            } else {
+                ("unhealthy", data, Some(format!("Service returned HTTP {}", code)))
+            }
+        }
+    };
+
+    let usage_name = name.clone();
+    let usage = tokio::task::spawn_blocking(move || crate::modules::supervisor::usage(&usage_name))
+        .await
+        .ok()
+        .flatten();
+
+    ModuleStatusEntry {
+        usage,
+        name,
+        status,
+        latency_ms: started.elapsed().as_millis() as u64,
+        data,
+        error,
+    }
+}
+
+/// GET /api/modules/status — status of every enabled module service, for the unified
+/// module health panel. Modules are polled concurrently with independent timeouts and
+/// the result is cached briefly.
+async fn aggregate_module_status(data: web::Data<AppState>, _req: HttpRequest) -> HttpResponse {
+    if let Some((at, snapshot)) = STATUS_SNAPSHOT.lock().unwrap().as_ref()
+        && at.elapsed() < STATUS_CACHE_TTL
+    {
+        return HttpResponse::Ok().json(snapshot);
+    }
+
+    let registry = crate::modules::ModuleRegistry::new();
+    let targets: Vec<(String, String)> = data
+        .db
+        .list_installed_modules()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|m| m.enabled)
+        .filter_map(|m| registry.get(&m.module_name))
+        .map(|m| {
+            let url = format!("{}{}", m.service_url(), m.health_endpoint());
+            (m.name().to_string(), url)
+        })
+        .collect();
+
+    let client = reqwest::Client::new();
+    let modules = futures_util::future::join_all(
+        targets
+            .into_iter()
+            .map(|(name, url)| check_module_status(&client, name, url, STATUS_CHECK_TIMEOUT)),
+    )
+    .await;
+
+    let healthy = modules.iter().filter(|m| m.status == "healthy").count();
+    let snapshot = serde_json::json!({
+        "checked_at": chrono::Utc::now().to_rfc3339(),
+        "healthy": healthy,
+        "total": modules.len(),
+        "modules": modules,
+    });
+    *STATUS_SNAPSHOT.lock().unwrap() = Some((std::time::Instant::now(), snapshot.clone()));
+
+    HttpResponse::Ok().json(snapshot)
+}
+
 /// POST /api/modules/reload — full resync of all module tools
 async fn reload_modules(data: web::Data<AppState>, _req: HttpRequest) -> HttpResponse {
     let module_registry = crate::modules::ModuleRegistry::new();
@@ -959,7 +1116,15 @@ async fn upload_module(
             Ok(mut field) => {
                 while let Some(chunk) = field.next().await {
                     match chunk {
-                        Ok(bytes) => file_data.extend_from_slice(&bytes),
+                        Ok(bytes) => {
+                            file_data.extend_from_slice(&bytes);
+                            // ZIP bomb protection: stop reading once over the limit
+                            if file_data.len() > crate::disk_quota::MAX_SKILL_ZIP_BYTES {
+                                return HttpResponse::PayloadTooLarge().json(serde_json::json!({
+                                    "error": "Upload rejected: file exceeds the 10MB limit."
+                                }));
+                            }
+                        }
                         Err(e) => {
                             return HttpResponse::BadRequest().json(serde_json::json!({
                                 "error": format!("Failed to read upload data: {}", e)
@@ -982,16 +1147,6 @@ async fn upload_module(
         }));
     }
 
-    // ZIP bomb protection
-    if file_data.len() > crate::disk_quota::MAX_SKILL_ZIP_BYTES {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": format!(
-                "Upload rejected: file size ({} bytes) exceeds the 10MB limit.",
-                file_data.len()
-            )
-        }));
-    }
-
     // Parse the module ZIP
     let parsed = match crate::modules::zip_parser::parse_module_zip(&file_data) {
         Ok(p) => p,
@@ -1114,6 +1269,101 @@ fn validate_session(
     }
 }
 
+#[derive(Deserialize, Default)]
+struct RotateTokenRequest {
+    /// Seconds the old token stays valid (defaults to STARK_INTERNAL_TOKEN_GRACE_SECS)
+    grace_secs: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct TokenPushResult {
+    module: String,
+    updated: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// POST /api/modules/internal-token/rotate — replace the internal module token
+///
+/// Running module services are sent the new token on `POST /rpc/internal/token`
+/// (authenticated with the old one). The old token keeps working for the grace
+/// period so modules that miss the push, or have requests in flight, aren't cut off.
+async fn rotate_internal_token(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    body: Option<web::Json<RotateTokenRequest>>,
+) -> HttpResponse {
+    if let Err(resp) = validate_session(&data, &req) {
+        return resp;
+    }
+
+    let grace = body
+        .and_then(|b| b.into_inner().grace_secs)
+        .map(std::time::Duration::from_secs)
+        .unwrap_or_else(crate::config::internal_token_grace);
+    let (new_token, old_token) = crate::modules::internal_token::rotate(grace);
+    log::info!(
+        "[MODULE] Rotated internal token; previous token valid for {}s",
+        grace.as_secs()
+    );
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(5))
+        .build()
+        .unwrap_or_default();
+    let mut modules = crate::modules::port_registry::all();
+    modules.sort();
+    let pushes = modules.into_iter().map(|(module, port)| {
+        let request = client
+            .post(format!("http://127.0.0.1:{}/rpc/internal/token", port))
+            .header("X-Internal-Token", &old_token)
+            .json(&serde_json::json!({ "token": new_token }));
+        async move {
+            let error = match request.send().await {
+                Ok(resp) if resp.status().is_success() => None,
+                Ok(resp) => Some(format!("HTTP {}", resp.status())),
+                Err(e) => Some(e.to_string()),
+            };
+            if let Some(ref e) = error {
+                log::warn!("[MODULE] Failed to push new internal token to {}: {}", module, e);
+            }
+            TokenPushResult { updated: error.is_none(), module, error }
+        }
+    });
+    let results = futures_util::future::join_all(pushes).await;
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "grace_secs": grace.as_secs(),
+        "modules": results,
+    }))
+}
+
+/// POST /api/modules/{name}/revoke-identity — cut a module off from the wallet proxy
+///
+/// The module keeps its other internal access; it gets a new identity the next
+/// time the backend starts its service.
+async fn revoke_module_identity(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    name: web::Path<String>,
+) -> HttpResponse {
+    if let Err(resp) = validate_session(&data, &req) {
+        return resp;
+    }
+
+    let name = name.into_inner();
+    let revoked = crate::modules::module_identity::revoke(&name);
+    if revoked {
+        log::warn!("[MODULE] Revoked identity of module '{}'", name);
+    }
+    HttpResponse::Ok().json(serde_json::json!({
+        "success": true,
+        "module": name,
+        "revoked": revoked,
+    }))
+}
+
 /// POST /api/modules/publish/{name} — publish a module to StarkHub (with file uploads)
 async fn publish_to_hub(
     data: web::Data<AppState>,
@@ -1298,13 +1548,16 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("", web::get().to(list_modules))
             .route("/upload", web::post().to(upload_module))
             .route("/reload", web::post().to(reload_modules))
+            .route("/status", web::get().to(aggregate_module_status))
             .route("/featured_remote", web::get().to(featured_remote))
             .route("/fetch_remote", web::post().to(fetch_remote))
+            .route("/internal-token/rotate", web::post().to(rotate_internal_token))
             .route("/publish/{name}", web::post().to(publish_to_hub))
             .route("/{name}/dashboard", web::get().to(module_dashboard))
             .route("/{name}/download", web::get().to(download_module))
             .route("/{name}/logs", web::get().to(module_logs))
             .route("/{name}/status", web::get().to(module_status))
+            .route("/{name}/revoke-identity", web::post().to(revoke_module_identity))
             .route("/{name}/proxy/{path:.*}", web::get().to(module_proxy))
             .route("/{name}/proxy/{path:.*}", web::post().to(module_proxy_post))
             .route("/{name}", web::post().to(module_action)),
@@ -1321,17 +1574,12 @@ async fn tui_invalidate(
     req: HttpRequest,
     body: web::Json<serde_json::Value>,
 ) -> HttpResponse {
-    // Authenticate via internal token
-    let token = req
-        .headers()
-        .get("X-Internal-Token")
-        .and_then(|h| h.to_str().ok())
-        .unwrap_or("");
-
-    if token.is_empty() || token != state.internal_token {
-        return HttpResponse::Unauthorized().json(serde_json::json!({
-            "error": "Invalid or missing X-Internal-Token"
-        }));
+    // Authenticate the calling module
+    if let Err(resp) = crate::modules::permissions::authorize(
+        &req,
+        crate::modules::permissions::Permission::TuiInvalidate,
+    ) {
+        return resp;
     }
 
     let module_name = match body.get("module").and_then(|v| v.as_str()) {
@@ -1350,3 +1598,38 @@ async fn tui_invalidate(
 
     HttpResponse::Ok().json(serde_json::json!({ "ok": true }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_slow_module_times_out_independently() {
+        // Accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let slow_url = format!("http://{}/rpc/status", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((sock, _)) = listener.accept().await {
+                held.push(sock);
+            }
+        });
+        // Nothing listens here
+        let free = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let offline_url = format!("http://{}/rpc/status", free.local_addr().unwrap());
+        drop(free);
+
+        let client = reqwest::Client::new();
+        let timeout = std::time::Duration::from_millis(300);
+        let started = std::time::Instant::now();
+        let (slow, offline) = tokio::join!(
+            check_module_status(&client, "slow".into(), slow_url, timeout),
+            check_module_status(&client, "offline".into(), offline_url, timeout),
+        );
+
+        assert_eq!(slow.status, "timeout");
+        assert_eq!(offline.status, "offline");
+        assert!(offline.latency_ms < 300);
+        assert!(started.elapsed() < std::time::Duration::from_secs(2));
+    }
+}
@@ -5,12 +5,16 @@
 
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io::{Cursor, Write as _};
 use std::path::Path;
 use tokio::fs;
 use zip::write::FileOptions;
 
 use crate::config::notes_dir;
+use crate::notes::links::NoteLinkGraph;
+use crate::notes::store::NoteFieldMatch;
+use crate::notes::NoteSearchFilter;
 use crate::AppState;
 
 /// Validate session token from request
@@ -400,16 +404,24 @@ struct SearchResultItem {
     file_path: String,
     title: String,
     tags: String,
+    /// Excerpt with matches wrapped in `>>>` / `<<<`
     snippet: String,
+    /// Relevance (higher is better)
+    score: f64,
 }
 
 #[derive(Debug, Deserialize)]
 struct SearchNotesQuery {
     q: String,
     limit: Option<i32>,
+    /// Only notes carrying this tag
+    tag: Option<String>,
+    /// Only notes with this frontmatter type
+    #[serde(rename = "type")]
+    note_type: Option<String>,
 }
 
-/// Full-text search across notes
+/// Full-text search across notes, ranked by relevance with highlighted snippets
 async fn search_notes(
     data: web::Data<AppState>,
     req: HttpRequest,
@@ -433,7 +445,12 @@ async fn search_notes(
 
     let limit = query.limit.unwrap_or(20).min(50).max(1);
 
-    match notes_store.search(&query.q, limit) {
+    let filter = NoteSearchFilter {
+        tag: query.tag.clone().filter(|t| !t.trim().is_empty()),
+        note_type: query.note_type.clone().filter(|t| !t.trim().is_empty()),
+    };
+
+    match notes_store.search_filtered(&query.q, &filter, limit) {
         Ok(results) => {
             let items: Vec<SearchResultItem> = results
                 .into_iter()
@@ -442,6 +459,8 @@ async fn search_notes(
                     title: r.title,
                     tags: r.tags,
                     snippet: r.snippet,
+                    // bm25 is lower-is-better; flip it for API consumers
+                    score: -r.score,
                 })
                 .collect();
 
@@ -546,6 +565,120 @@ async fn list_tags(data: web::Data<AppState>, req: HttpRequest) -> impl Responde
     }
 }
 
+// --- Frontmatter query ---
+
+#[derive(Debug, Serialize)]
+struct QueryNotesResponse {
+    success: bool,
+    notes: Vec<NoteFieldMatch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// List notes by frontmatter fields. Every query parameter except `limit` is a
+/// condition, e.g. `/api/notes/query?tag=blog&status=draft`.
+async fn query_notes(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let notes_store = match data.dispatcher.notes_store() {
+        Some(store) => store,
+        None => {
+            return HttpResponse::ServiceUnavailable().json(QueryNotesResponse {
+                success: false,
+                notes: vec![],
+                error: Some("Notes store not initialized".to_string()),
+            });
+        }
+    };
+
+    let limit = query
+        .get("limit")
+        .and_then(|l| l.parse::<i32>().ok())
+        .unwrap_or(100)
+        .clamp(1, 500);
+
+    let mut conditions: Vec<(String, String)> = query
+        .iter()
+        .filter(|(k, v)| k.as_str() != "limit" && !v.trim().is_empty())
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    conditions.sort();
+
+    match notes_store.query_fields(&conditions, limit) {
+        Ok(notes) => HttpResponse::Ok().json(QueryNotesResponse {
+            success: true,
+            notes,
+            error: None,
+        }),
+        Err(e) => HttpResponse::InternalServerError().json(QueryNotesResponse {
+            success: false,
+            notes: vec![],
+            error: Some(format!("Query failed: {}", e)),
+        }),
+    }
+}
+
+// --- Link graph ---
+
+#[derive(Debug, Serialize)]
+struct LinkGraphResponse {
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    graph: Option<NoteLinkGraph>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Inbound (backlinks) and outbound [[wikilinks]] for a note
+async fn note_links(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<ReadNoteQuery>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let notes_store = match data.dispatcher.notes_store() {
+        Some(store) => store,
+        None => {
+            return HttpResponse::ServiceUnavailable().json(LinkGraphResponse {
+                success: false,
+                graph: None,
+                error: Some("Notes store not initialized".to_string()),
+            });
+        }
+    };
+
+    let path = query.path.trim().trim_start_matches('/');
+    if path.split('/').any(|part| part == "..") || !notes_store.notes_dir().join(path).is_file() {
+        return HttpResponse::NotFound().json(LinkGraphResponse {
+            success: false,
+            graph: None,
+            error: Some(format!("Note not found: {}", path)),
+        });
+    }
+
+    match notes_store.link_graph(path) {
+        Ok(graph) => HttpResponse::Ok().json(LinkGraphResponse {
+            success: true,
+            graph: Some(graph),
+            error: None,
+        }),
+        Err(e) => HttpResponse::InternalServerError().json(LinkGraphResponse {
+            success: false,
+            graph: None,
+            error: Some(format!("Failed to build link graph: {}", e)),
+        }),
+    }
+}
+
 // --- Notes grouped by tag ---
 
 #[derive(Debug, Serialize)]
@@ -729,6 +862,8 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("", web::get().to(list_notes))
             .route("/read", web::get().to(read_note))
             .route("/search", web::get().to(search_notes))
+            .route("/query", web::get().to(query_notes))
+            .route("/links", web::get().to(note_links))
             .route("/info", web::get().to(notes_info))
             .route("/tags", web::get().to(list_tags))
             .route("/by-tag", web::get().to(notes_by_tag))
@@ -1,9 +1,11 @@
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::Deserialize;
 
 use crate::models::{
-    ChatSessionResponse, CompletionStatus, GetOrCreateSessionRequest, SessionScope,
-    SessionTranscriptResponse, UpdateResetPolicyRequest,
+    normalize_session_tag, ChatSessionResponse, CompletionStatus, GetOrCreateSessionRequest,
+    SessionScope, SessionSearchFilter, SessionSpendSummary, SessionTagsRequest, SessionTranscriptExport,
+    SessionTranscriptResponse, UpdateResetPolicyRequest, MAX_TAGS_PER_SESSION,
 };
 use crate::AppState;
 
@@ -61,6 +63,7 @@ async fn list_sessions(
                     if let Ok(count) = data.db.count_session_messages(session_id) {
                         response.message_count = Some(count);
                     }
+                    response.title = data.db.get_session_title(session_id).ok().flatten().map(|(t, _)| t);
                     // For web sessions, get the initial query (first user message)
                     if is_web {
                         if let Ok(Some(first_msg)) = data.db.get_first_user_message(session_id) {
@@ -138,6 +141,7 @@ async fn get_session(
             if let Ok(count) = data.db.count_session_messages(response.id) {
                 response.message_count = Some(count);
             }
+            response.title = data.db.get_session_title(response.id).ok().flatten().map(|(t, _)| t);
             HttpResponse::Ok().json(response)
         }
         Ok(None) => HttpResponse::NotFound().json(serde_json::json!({
@@ -385,9 +389,10 @@ async fn stop_session(
         0
     };
 
-    // Also cancel execution tracker
+    // Also cancel execution tracker (and the channel listener's, for platform channels)
     data.execution_tracker.cancel_execution(channel_id);
     data.execution_tracker.cancel_all_sessions_for_channel(channel_id);
+    data.channel_manager.cancel_dispatch(channel_id);
 
     // Clear any tasks associated with this session
     data.execution_tracker.clear_tasks_for_session(session_id);
@@ -526,18 +531,358 @@ async fn get_transcript(
     }
 }
 
+/// Export format and options for a full session transcript
+#[derive(Deserialize)]
+struct ExportQuery {
+    /// "markdown" (default) or "json"
+    #[serde(default)]
+    format: Option<String>,
+    /// Prepend the compaction summary of earlier, compacted-away messages
+    #[serde(default)]
+    include_summary: bool,
+}
+
+/// Export a session's full transcript as markdown (for sharing/archiving) or JSON
+async fn export_transcript(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+    query: web::Query<ExportQuery>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    let session_id = path.into_inner();
+
+    let as_json = match query.format.as_deref().map(str::to_lowercase).as_deref() {
+        None | Some("markdown") | Some("md") => false,
+        Some("json") => true,
+        Some(other) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": format!("Unsupported format '{}' (expected markdown or json)", other)
+            }));
+        }
+    };
+
+    let session = match data.db.get_chat_session(session_id) {
+        Ok(Some(session)) => session,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Session not found"
+            }));
+        }
+        Err(e) => {
+            log::error!("Failed to get session for export: {}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }));
+        }
+    };
+
+    let messages = match data.db.get_session_messages(session_id) {
+        Ok(msgs) => msgs,
+        Err(e) => {
+            log::error!("Failed to get messages for export: {}", e);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }));
+        }
+    };
+
+    let summary = if query.include_summary {
+        data.db.get_session_compaction_summary(session_id).ok().flatten()
+    } else {
+        None
+    };
+
+    let mut response: ChatSessionResponse = session.into();
+    response.message_count = Some(messages.len() as i64);
+    response.title = data.db.get_session_title(session_id).ok().flatten().map(|(t, _)| t);
+    let export = SessionTranscriptExport::new(response, messages, summary);
+
+    if as_json {
+        return HttpResponse::Ok().json(export);
+    }
+    HttpResponse::Ok()
+        .content_type("text/markdown; charset=utf-8")
+        .insert_header((
+            "Content-Disposition",
+            format!("attachment; filename=\"session-{}.md\"", session_id),
+        ))
+        .body(export.to_markdown())
+}
+
+/// Normalize and validate requested tags (deduplicated, order preserved)
+fn normalize_tags(tags: &[String]) -> Result<Vec<String>, HttpResponse> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        match normalize_session_tag(tag) {
+            Some(t) if !normalized.contains(&t) => normalized.push(t),
+            Some(_) => {}
+            None => {
+                return Err(HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": format!("Invalid tag '{}' (must be 1-64 characters)", tag)
+                })));
+            }
+        }
+    }
+    Ok(normalized)
+}
+
+/// Respond with a session's current tags, or 404 if the session doesn't exist
+fn session_tags_response(data: &web::Data<AppState>, session_id: i64) -> HttpResponse {
+    match data.db.get_chat_session(session_id) {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Session not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }));
+        }
+    }
+    match data.db.get_session_tags(session_id) {
+        Ok(tags) => HttpResponse::Ok().json(serde_json::json!({
+            "session_id": session_id,
+            "tags": tags
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+    }
+}
+
+/// Get a session's tags
+async fn get_session_tags(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    session_tags_response(&data, path.into_inner())
+}
+
+/// Replace a session's tags (PUT) or add to them (POST)
+async fn update_session_tags(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+    body: web::Json<SessionTagsRequest>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    let session_id = path.into_inner();
+    let tags = match normalize_tags(&body.tags) {
+        Ok(tags) => tags,
+        Err(resp) => return resp,
+    };
+    if matches!(data.db.get_chat_session(session_id), Ok(None)) {
+        return HttpResponse::NotFound().json(serde_json::json!({
+            "error": "Session not found"
+        }));
+    }
+
+    let replace = req.method() == actix_web::http::Method::PUT;
+    let existing = if replace {
+        Vec::new()
+    } else {
+        data.db.get_session_tags(session_id).unwrap_or_default()
+    };
+    let total = existing.len() + tags.iter().filter(|t| !existing.contains(t)).count();
+    if total > MAX_TAGS_PER_SESSION {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("A session can have at most {} tags", MAX_TAGS_PER_SESSION)
+        }));
+    }
+
+    let result = if replace {
+        data.db.set_session_tags(session_id, &tags)
+    } else {
+        data.db.add_session_tags(session_id, &tags)
+    };
+    if let Err(e) = result {
+        log::error!("Failed to update tags for session {}: {}", session_id, e);
+        return HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        }));
+    }
+    session_tags_response(&data, session_id)
+}
+
+/// Remove one tag from a session
+async fn delete_session_tag(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(i64, String)>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    let (session_id, tag) = path.into_inner();
+    let tag = normalize_session_tag(&tag).unwrap_or(tag);
+    if let Err(e) = data.db.remove_session_tag(session_id, &tag) {
+        return HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        }));
+    }
+    session_tags_response(&data, session_id)
+}
+
+/// Get a session's spend ledger: x402 payments, estimated AI token costs and
+/// gas, with totals per kind and currency
+async fn get_session_spend(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<i64>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    let session_id = path.into_inner();
+    match data.db.get_chat_session(session_id) {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(serde_json::json!({
+                "error": "Session not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }));
+        }
+    }
+    match (data.db.get_session_spend_totals(session_id), data.db.get_session_spend(session_id)) {
+        (Ok(totals), Ok(entries)) => HttpResponse::Ok().json(SessionSpendSummary { session_id, totals, entries }),
+        (Err(e), _) | (_, Err(e)) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+    }
+}
+
+/// List all tags in use with their session counts
+async fn list_tags(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    match data.db.list_session_tags() {
+        Ok(tags) => HttpResponse::Ok().json(
+            tags.into_iter()
+                .map(|(tag, count)| serde_json::json!({ "tag": tag, "count": count }))
+                .collect::<Vec<_>>(),
+        ),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        })),
+    }
+}
+
+/// Session search filters. `tag` may be comma-separated; all tags must match.
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: Option<String>,
+    tag: Option<String>,
+    channel_type: Option<String>,
+    /// RFC 3339 timestamp or YYYY-MM-DD (start of day, UTC)
+    from: Option<String>,
+    /// RFC 3339 timestamp or YYYY-MM-DD (end of day, UTC)
+    to: Option<String>,
+    limit: Option<usize>,
+}
+
+/// Parse a search date bound; bare dates cover the whole day
+fn parse_date_bound(value: &str, end_of_day: bool) -> Option<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    let time = if end_of_day {
+        date.and_hms_milli_opt(23, 59, 59, 999)?
+    } else {
+        date.and_hms_opt(0, 0, 0)?
+    };
+    Some(time.and_utc())
+}
+
+/// Search sessions by tag, channel type, date range and message content
+async fn search_sessions(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<SearchQuery>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    let query = query.into_inner();
+
+    let mut filter = SessionSearchFilter {
+        channel_type: query.channel_type.filter(|c| !c.is_empty()),
+        text: query.q.filter(|q| !q.trim().is_empty()),
+        limit: query.limit.unwrap_or(20).clamp(1, 100),
+        ..Default::default()
+    };
+    if let Some(tags) = query.tag {
+        let requested: Vec<String> = tags.split(',').filter(|t| !t.trim().is_empty()).map(str::to_string).collect();
+        filter.tags = match normalize_tags(&requested) {
+            Ok(tags) => tags,
+            Err(resp) => return resp,
+        };
+    }
+    for (value, end_of_day) in [(&query.from, false), (&query.to, true)] {
+        let Some(value) = value else { continue };
+        let Some(bound) = parse_date_bound(value, end_of_day) else {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": format!("Invalid date '{}' (expected RFC 3339 or YYYY-MM-DD)", value)
+            }));
+        };
+        if end_of_day {
+            filter.to = Some(bound);
+        } else {
+            filter.from = Some(bound);
+        }
+    }
+
+    match data.db.search_sessions(&filter) {
+        Ok(results) => HttpResponse::Ok().json(results),
+        Err(e) => {
+            log::error!("Session search failed: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Search failed: {}", e)
+            }))
+        }
+    }
+}
+
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api/sessions")
             .route("", web::get().to(list_sessions))
             .route("", web::post().to(get_or_create_session))
             .route("", web::delete().to(delete_all_sessions))
+            // Registered before /{id} so "search"/"tags" aren't parsed as IDs
+            .route("/search", web::get().to(search_sessions))
+            .route("/tags", web::get().to(list_tags))
             .route("/{id}", web::get().to(get_session))
             .route("/{id}", web::delete().to(delete_session))
             .route("/{id}/reset", web::post().to(reset_session))
             .route("/{id}/stop", web::post().to(stop_session))
             .route("/{id}/resume", web::post().to(resume_session))
             .route("/{id}/policy", web::put().to(update_reset_policy))
-            .route("/{id}/transcript", web::get().to(get_transcript)),
+            .route("/{id}/transcript", web::get().to(get_transcript))
+            .route("/{id}/export", web::get().to(export_transcript))
+            .route("/{id}/tags", web::get().to(get_session_tags))
+            .route("/{id}/tags", web::put().to(update_session_tags))
+            .route("/{id}/tags", web::post().to(update_session_tags))
+            .route("/{id}/tags/{tag}", web::delete().to(delete_session_tag))
+            .route("/{id}/spend", web::get().to(get_session_spend)),
     );
 }
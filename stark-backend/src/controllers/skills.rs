@@ -3,7 +3,8 @@ use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 
-use crate::skills::{DbSkillScript, Skill};
+use crate::skills::{DbSkillScript, Skill, SkillDependencyReport, SkillValidationReport};
+use crate::telemetry::SkillMetrics;
 use crate::AppState;
 
 #[derive(Serialize)]
@@ -68,6 +69,10 @@ pub struct SkillDetail {
     pub requires_tools: Vec<String>,
     pub requires_binaries: Vec<String>,
     pub missing_binaries: Vec<String>,
+    /// Required tools not present in the tool registry
+    pub missing_tools: Vec<String>,
+    /// Required API keys that haven't been configured
+    pub missing_api_keys: Vec<String>,
     pub tags: Vec<String>,
     pub arguments: Vec<ArgumentInfo>,
     pub prompt_template: String,
@@ -132,6 +137,8 @@ impl From<&Skill> for SkillDetail {
             requires_tools: skill.metadata.requires_tools.clone(),
             requires_binaries: skill.metadata.requires_binaries.clone(),
             missing_binaries,
+            missing_tools: Vec::new(),
+            missing_api_keys: Vec::new(),
             tags: skill.metadata.tags.clone(),
             arguments,
             prompt_template: skill.prompt_template.clone(),
@@ -168,6 +175,73 @@ pub struct UploadResponse {
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub skill: Option<SkillInfo>,
+    /// Unmet dependencies of the uploaded skill (installed with warnings)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<SkillDependencyReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SkillVersionInfo {
+    pub id: i64,
+    pub version: String,
+    pub description: String,
+    pub body: String,
+    pub arguments: Vec<String>,
+    pub scripts: Vec<String>,
+    /// When this version was replaced
+    pub created_at: String,
+}
+
+impl From<&crate::skills::DbSkillVersion> for SkillVersionInfo {
+    fn from(v: &crate::skills::DbSkillVersion) -> Self {
+        let mut arguments: Vec<String> = v.skill.arguments.keys().cloned().collect();
+        arguments.sort();
+        SkillVersionInfo {
+            id: v.id,
+            version: v.version.clone(),
+            description: v.skill.description.clone(),
+            body: v.skill.body.clone(),
+            arguments,
+            scripts: v.scripts.iter().map(|s| s.name.clone()).collect(),
+            created_at: v.created_at.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct SkillVersionsResponse {
+    pub success: bool,
+    pub versions: Vec<SkillVersionInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RollbackSkillRequest {
+    pub version_id: i64,
+}
+
+#[derive(Serialize)]
+pub struct SkillValidateResponse {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<SkillValidationReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SkillMetricsQuery {
+    /// Only count calls from the last N days (default: all retained telemetry)
+    pub since_days: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct SkillMetricsResponse {
+    pub success: bool,
+    pub metrics: Vec<SkillMetrics>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
 }
@@ -325,7 +399,8 @@ async fn install_from_hub(
                 let raw_markdown = reconstruct_skill_md(&parsed);
 
                 match state.skill_registry.create_skill_from_markdown(&raw_markdown) {
-                    Ok(_) => {
+                    Ok(db_skill) => {
+                        let dependencies = state.skill_registry.dependency_report(&db_skill.into_skill());
                         // Write auxiliary files (scripts, ABIs, presets) to disk
                         let mut downloaded_files = vec!["SKILL.md".to_string()];
                         let skills_dir = std::path::PathBuf::from(crate::config::runtime_skills_dir());
@@ -376,6 +451,7 @@ async fn install_from_hub(
                             "skill_name": skill_name,
                             "already_existed": already_exists,
                             "files": downloaded_files,
+                            "dependencies": dependencies,
                             "message": format!("{} skill '{}' from @{}/{}",
                                 if already_exists { "Updated" } else { "Installed" },
                                 skill_name, body.username, body.slug),
@@ -426,6 +502,7 @@ async fn install_from_hub(
     };
 
     let skill_name = db_skill.name.clone();
+    let dependencies = state.skill_registry.dependency_report(&db_skill.into_skill());
 
     let mut downloaded_files = Vec::new();
     if let Ok(files) = client
@@ -458,6 +535,7 @@ async fn install_from_hub(
         "skill_name": skill_name,
         "already_existed": already_exists,
         "files": downloaded_files,
+        "dependencies": dependencies,
         "message": format!("{} skill '{}' from @{}/{}",
             if already_exists { "Updated" } else { "Installed" },
             skill_name, body.username, body.slug),
@@ -595,6 +673,8 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("", web::get().to(list_skills))
             .route("/upload", web::post().to(upload_skill))
             .route("/reload", web::post().to(reload_skills))
+            .route("/metrics", web::get().to(get_skill_metrics))
+            .route("/search", web::get().to(search_skills_by_embedding))
             .route("/graph", web::get().to(get_skill_graph))
             .route("/graph/search", web::get().to(search_skills_by_embedding))
             .route("/embeddings/stats", web::get().to(get_skill_embedding_stats))
@@ -610,7 +690,10 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/{name}", web::put().to(update_skill))
             .route("/{name}", web::delete().to(delete_skill))
             .route("/{name}/enabled", web::put().to(set_enabled))
-            .route("/{name}/scripts", web::get().to(get_skill_scripts)),
+            .route("/{name}/scripts", web::get().to(get_skill_scripts))
+            .route("/{name}/validate", web::post().to(validate_skill))
+            .route("/{name}/versions", web::get().to(list_skill_versions))
+            .route("/{name}/rollback", web::post().to(rollback_skill)),
     );
 }
 
@@ -685,6 +768,9 @@ async fn get_skill(
     match state.skill_registry.get(&name) {
         Some(skill) => {
             let mut detail: SkillDetail = (&skill).into();
+            let dependencies = state.skill_registry.dependency_report(&skill);
+            detail.missing_tools = dependencies.missing_tools;
+            detail.missing_api_keys = dependencies.missing_api_keys;
 
             // Get associated scripts
             let scripts = state.skill_registry.get_skill_scripts(&name);
@@ -801,11 +887,26 @@ async fn upload_skill(
 
                 while let Some(chunk) = field.next().await {
                     match chunk {
-                        Ok(data) => file_data.extend_from_slice(&data),
+                        Ok(data) => {
+                            file_data.extend_from_slice(&data);
+                            // Reject oversized uploads (ZIP bomb protection) before buffering the rest
+                            if file_data.len() > crate::disk_quota::MAX_SKILL_ZIP_BYTES {
+                                return HttpResponse::PayloadTooLarge().json(UploadResponse {
+                                    success: false,
+                                    skill: None,
+                                    dependencies: None,
+                                    error: Some(
+                                        "Upload rejected: file exceeds the 10MB limit for skill uploads."
+                                            .to_string(),
+                                    ),
+                                });
+                            }
+                        }
                         Err(e) => {
                             return HttpResponse::BadRequest().json(UploadResponse {
                                 success: false,
                                 skill: None,
+                                dependencies: None,
                                 error: Some(format!("Failed to read upload data: {}", e)),
                             });
                         }
@@ -816,6 +917,7 @@ async fn upload_skill(
                 return HttpResponse::BadRequest().json(UploadResponse {
                     success: false,
                     skill: None,
+                    dependencies: None,
                     error: Some(format!("Failed to process upload: {}", e)),
                 });
             }
@@ -826,22 +928,11 @@ async fn upload_skill(
         return HttpResponse::BadRequest().json(UploadResponse {
             success: false,
             skill: None,
+            dependencies: None,
             error: Some("No file uploaded".to_string()),
         });
     }
 
-    // Reject uploads larger than 10MB (ZIP bomb protection)
-    if file_data.len() > crate::disk_quota::MAX_SKILL_ZIP_BYTES {
-        return HttpResponse::BadRequest().json(UploadResponse {
-            success: false,
-            skill: None,
-            error: Some(format!(
-                "Upload rejected: file size ({} bytes) exceeds the 10MB limit for skill uploads.",
-                file_data.len()
-            )),
-        });
-    }
-
     // Determine file type from filename or content
     let is_markdown = filename
         .as_ref()
@@ -899,9 +990,11 @@ async fn upload_skill(
             }
 
             let skill = db_skill.into_skill();
+            let dependencies = state.skill_registry.dependency_report(&skill);
             HttpResponse::Ok().json(UploadResponse {
                 success: true,
                 skill: Some((&skill).into()),
+                dependencies: (!dependencies.is_satisfied()).then_some(dependencies),
                 error: None,
             })
         }
@@ -910,6 +1003,7 @@ async fn upload_skill(
             HttpResponse::BadRequest().json(UploadResponse {
                 success: false,
                 skill: None,
+                dependencies: None,
                 error: Some(e),
             })
         }
@@ -1035,28 +1129,8 @@ async fn update_skill(
     }
 
     // Auto-regenerate embedding + rebuild associations for the updated skill
-    if let Some(ref engine) = state.hybrid_search {
-        if let Ok(Some(updated)) = state.db.get_skill(&name) {
-            if let Some(skill_id) = updated.id {
-                let emb_gen = engine.embedding_generator().clone();
-                let db = state.db.clone();
-                let skill_name = name.clone();
-                tokio::spawn(async move {
-                    let text = crate::skills::embeddings::build_skill_embedding_text(&updated);
-                    if let Ok(embedding) = emb_gen.generate(&text).await {
-                        let dims = embedding.len() as i32;
-                        if let Err(e) = db.upsert_skill_embedding(skill_id, &embedding, "remote", dims) {
-                            log::warn!("[SKILL-EMB] Failed to re-embed skill '{}': {}", skill_name, e);
-                        } else {
-                            // Rebuild associations for this skill
-                            if let Err(e) = crate::skills::embeddings::rebuild_associations_for_skill(&db, skill_id, 0.30).await {
-                                log::warn!("[SKILL-ASSOC] Failed to rebuild associations for '{}': {}", skill_name, e);
-                            }
-                        }
-                    }
-                });
-            }
-        }
+    if let Ok(Some(updated)) = state.db.get_skill(&name) {
+        reembed_skill(&state, updated);
     }
 
     // Re-fetch the updated skill
@@ -1196,9 +1270,11 @@ async fn restore_bundled_skill(
             }
 
             let skill = db_skill.into_skill();
+            let dependencies = state.skill_registry.dependency_report(&skill);
             HttpResponse::Ok().json(UploadResponse {
                 success: true,
                 skill: Some((&skill).into()),
+                dependencies: (!dependencies.is_satisfied()).then_some(dependencies),
                 error: None,
             })
         }
@@ -1207,12 +1283,166 @@ async fn restore_bundled_skill(
             HttpResponse::BadRequest().json(UploadResponse {
                 success: false,
                 skill: None,
+                dependencies: None,
                 error: Some(e),
             })
         }
     }
 }
 
+/// GET /api/skills/metrics — per-skill invocation counts, success/failure and
+/// mean duration. Installed skills with no recorded calls are listed with zeros.
+async fn get_skill_metrics(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<SkillMetricsQuery>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    let since = query.since_days.map(|days| {
+        chrono::Utc::now() - chrono::Duration::days(days as i64)
+    });
+    let mut metrics = state.telemetry_store.get_skill_metrics(since);
+
+    for skill in state.skill_registry.list() {
+        if !metrics.iter().any(|m| m.skill_name == skill.metadata.name) {
+            metrics.push(SkillMetrics {
+                skill_name: skill.metadata.name.clone(),
+                ..Default::default()
+            });
+        }
+    }
+
+    HttpResponse::Ok().json(SkillMetricsResponse {
+        success: true,
+        metrics,
+        error: None,
+    })
+}
+
+/// POST /api/skills/{name}/validate — dry-run a skill: parse its scripts and
+/// check the tools it uses, without running anything
+async fn validate_skill(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    let name = path.into_inner();
+    match state.skill_registry.validate_skill(&name).await {
+        Some(report) => HttpResponse::Ok().json(SkillValidateResponse {
+            success: true,
+            report: Some(report),
+            error: None,
+        }),
+        None => HttpResponse::NotFound().json(SkillValidateResponse {
+            success: false,
+            report: None,
+            error: Some(format!("Skill '{}' not found", name)),
+        }),
+    }
+}
+
+/// GET /api/skills/{name}/versions — prior versions of a skill, newest first
+async fn list_skill_versions(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    let name = path.into_inner();
+    match state.db.list_skill_versions(&name) {
+        Ok(versions) => HttpResponse::Ok().json(SkillVersionsResponse {
+            success: true,
+            versions: versions.iter().map(|v| v.into()).collect(),
+            error: None,
+        }),
+        Err(e) => HttpResponse::InternalServerError().json(SkillVersionsResponse {
+            success: false,
+            versions: vec![],
+            error: Some(format!("Failed to list skill versions: {}", e)),
+        }),
+    }
+}
+
+/// POST /api/skills/{name}/rollback — restore a prior version from history
+async fn rollback_skill(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<RollbackSkillRequest>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&state, &req) {
+        return resp;
+    }
+
+    let name = path.into_inner();
+    if !state.skill_registry.has_skill(&name) {
+        return HttpResponse::NotFound().json(SkillDetailResponse {
+            success: false,
+            skill: None,
+            error: Some(format!("Skill '{}' not found", name)),
+        });
+    }
+
+    let restored = match state.skill_registry.rollback_skill(&name, body.version_id) {
+        Ok(s) => s,
+        Err(e) => {
+            log::error!("Failed to roll back skill '{}': {}", name, e);
+            return HttpResponse::BadRequest().json(SkillDetailResponse {
+                success: false,
+                skill: None,
+                error: Some(e),
+            });
+        }
+    };
+
+    // Drop the stale embedding and re-run the backfill so search matches the restored text
+    if let (Some(engine), Some(skill_id)) = (state.hybrid_search.as_ref(), restored.id) {
+        let _ = state.db.delete_skill_embedding(skill_id);
+        let emb_gen = engine.embedding_generator().clone();
+        let db = state.db.clone();
+        tokio::spawn(async move {
+            match crate::skills::embeddings::backfill_skill_embeddings(&db, &emb_gen).await {
+                Ok(_) => {
+                    if let Err(e) = crate::skills::embeddings::rebuild_associations_for_skill(&db, skill_id, 0.30).await {
+                        log::warn!("[SKILL-ASSOC] Failed to rebuild associations after rollback: {}", e);
+                    }
+                }
+                Err(e) => log::warn!("[SKILL-EMB] Backfill after rollback failed: {}", e),
+            }
+        });
+    }
+
+    match state.skill_registry.get(&name) {
+        Some(skill) => {
+            let mut detail: SkillDetail = (&skill).into();
+            let scripts = state.skill_registry.get_skill_scripts(&name);
+            if !scripts.is_empty() {
+                detail.scripts = Some(scripts.iter().map(|s| s.into()).collect());
+            }
+            HttpResponse::Ok().json(SkillDetailResponse {
+                success: true,
+                skill: Some(detail),
+                error: None,
+            })
+        }
+        None => HttpResponse::InternalServerError().json(SkillDetailResponse {
+            success: false,
+            skill: None,
+            error: Some("Skill not found after rollback".to_string()),
+        }),
+    }
+}
+
 async fn get_skill_scripts(
     state: web::Data<AppState>,
     req: HttpRequest,
@@ -1307,64 +1537,51 @@ async fn search_skills_by_embedding(
         return resp;
     }
 
-    let limit = query.limit.unwrap_or(5);
+    let limit = query.limit.unwrap_or(5).clamp(1, 50);
+    let emb_gen = state.hybrid_search.as_ref().map(|engine| engine.embedding_generator());
 
-    // Try embedding search first if engine is available
-    if let Some(ref engine) = state.hybrid_search {
-        let emb_gen = engine.embedding_generator().clone();
-        // Check if any embeddings exist
-        let has_embeddings = state.db.count_skill_embeddings().unwrap_or(0) > 0;
-
-        if has_embeddings {
-            match crate::skills::embeddings::search_skills(&state.db, &emb_gen, &query.query, limit, 0.20).await {
-                Ok(matches) if !matches.is_empty() => {
-                    let results: Vec<SkillSearchResult> = matches
-                        .into_iter()
-                        .map(|(skill, sim)| SkillSearchResult {
-                            skill_id: skill.id.unwrap_or(0),
-                            name: skill.name,
-                            description: skill.description,
-                            similarity: sim,
-                        })
-                        .collect();
-                    return HttpResponse::Ok().json(SkillSearchResponse {
-                        success: true,
-                        results,
-                        error: None,
-                    });
-                }
-                Ok(_) => { /* empty results — fall through to text search */ }
-                Err(e) => {
-                    log::warn!("[SKILL-SEARCH] Embedding search failed, falling back to text: {}", e);
+    // Semantic match against skill embeddings, falling back to text search
+    let results: Vec<SkillSearchResult> = state
+        .skill_registry
+        .find_relevant_skills(&query.query, limit, 0.20, emb_gen)
+        .await
+        .into_iter()
+        .map(|(skill, sim)| SkillSearchResult {
+            skill_id: skill.id.unwrap_or(0),
+            name: skill.name,
+            description: skill.description,
+            similarity: sim,
+        })
+        .collect();
+
+    HttpResponse::Ok().json(SkillSearchResponse {
+        success: true,
+        results,
+        error: None,
+    })
+}
+
+/// Regenerate a skill's embedding and associations in the background
+fn reembed_skill(state: &web::Data<AppState>, skill: crate::skills::DbSkill) {
+    let (Some(engine), Some(skill_id)) = (state.hybrid_search.as_ref(), skill.id) else {
+        return;
+    };
+    let emb_gen = engine.embedding_generator().clone();
+    let db = state.db.clone();
+    tokio::spawn(async move {
+        let text = crate::skills::embeddings::build_skill_embedding_text(&skill);
+        if let Ok(embedding) = emb_gen.generate(&text).await {
+            let dims = embedding.len() as i32;
+            if let Err(e) = db.upsert_skill_embedding(skill_id, &embedding, "remote", dims) {
+                log::warn!("[SKILL-EMB] Failed to re-embed skill '{}': {}", skill.name, e);
+            } else {
+                // Rebuild associations for this skill
+                if let Err(e) = crate::skills::embeddings::rebuild_associations_for_skill(&db, skill_id, 0.30).await {
+                    log::warn!("[SKILL-ASSOC] Failed to rebuild associations for '{}': {}", skill.name, e);
                 }
             }
         }
-    }
-
-    // Fallback: text-based search (works without embeddings)
-    match crate::skills::embeddings::search_skills_text(&state.db, &query.query, limit) {
-        Ok(matches) => {
-            let results: Vec<SkillSearchResult> = matches
-                .into_iter()
-                .map(|(skill, sim)| SkillSearchResult {
-                    skill_id: skill.id.unwrap_or(0),
-                    name: skill.name,
-                    description: skill.description,
-                    similarity: sim,
-                })
-                .collect();
-            HttpResponse::Ok().json(SkillSearchResponse {
-                success: true,
-                results,
-                error: None,
-            })
-        }
-        Err(e) => HttpResponse::InternalServerError().json(SkillSearchResponse {
-            success: false,
-            results: vec![],
-            error: Some(e),
-        }),
-    }
+    });
 }
 
 async fn get_skill_embedding_stats(
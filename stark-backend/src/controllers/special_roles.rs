@@ -81,6 +81,20 @@ async fn get_role(
     }
 }
 
+/// Reject tool names that aren't in the registry
+fn validate_tool_names(data: &AppState, tools: &[String]) -> Result<(), HttpResponse> {
+    let unknown_tools: Vec<&str> = tools.iter()
+        .filter(|t| data.tool_registry.get(t).is_none())
+        .map(|t| t.as_str())
+        .collect();
+    if unknown_tools.is_empty() {
+        return Ok(());
+    }
+    Err(HttpResponse::BadRequest().json(serde_json::json!({
+        "error": format!("Unknown tool(s): {}. Use GET /api/tools to see available tools.", unknown_tools.join(", "))
+    })))
+}
+
 #[derive(Deserialize)]
 struct CreateRoleRequest {
     name: String,
@@ -88,6 +102,9 @@ struct CreateRoleRequest {
     allowed_tools: Vec<String>,
     #[serde(default)]
     allowed_skills: Vec<String>,
+    /// Tools the role never gets, applied after the grants
+    #[serde(default)]
+    denied_tools: Vec<String>,
     #[serde(default)]
     description: Option<String>,
 }
@@ -126,13 +143,10 @@ async fn create_role(
     }
 
     // Validate tool names exist in registry
-    let unknown_tools: Vec<&String> = body.allowed_tools.iter()
-        .filter(|t| data.tool_registry.get(t).is_none())
-        .collect();
-    if !unknown_tools.is_empty() {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": format!("Unknown tool(s): {}. Use GET /api/tools to see available tools.", unknown_tools.iter().map(|t| t.as_str()).collect::<Vec<_>>().join(", "))
-        }));
+    for tools in [&body.allowed_tools, &body.denied_tools] {
+        if let Err(resp) = validate_tool_names(&data, tools) {
+            return resp;
+        }
     }
 
     // Validate skill names exist in database
@@ -156,6 +170,7 @@ async fn create_role(
         name,
         allowed_tools: body.allowed_tools.clone(),
         allowed_skills: body.allowed_skills.clone(),
+        denied_tools: body.denied_tools.clone(),
         description: body.description.clone(),
         created_at: String::new(),
         updated_at: String::new(),
@@ -185,6 +200,8 @@ struct UpdateRoleRequest {
     #[serde(default)]
     allowed_skills: Option<Vec<String>>,
     #[serde(default)]
+    denied_tools: Option<Vec<String>>,
+    #[serde(default)]
     description: Option<Option<String>>,
 }
 
@@ -214,14 +231,9 @@ async fn update_role(
     };
 
     // Validate tool names if provided
-    if let Some(ref tools) = body.allowed_tools {
-        let unknown_tools: Vec<&String> = tools.iter()
-            .filter(|t| data.tool_registry.get(t).is_none())
-            .collect();
-        if !unknown_tools.is_empty() {
-            return HttpResponse::BadRequest().json(serde_json::json!({
-                "error": format!("Unknown tool(s): {}. Use GET /api/tools to see available tools.", unknown_tools.iter().map(|t| t.as_str()).collect::<Vec<_>>().join(", "))
-            }));
+    for tools in [&body.allowed_tools, &body.denied_tools].into_iter().flatten() {
+        if let Err(resp) = validate_tool_names(&data, tools) {
+            return resp;
         }
     }
 
@@ -246,6 +258,7 @@ async fn update_role(
         name: existing.name,
         allowed_tools: body.allowed_tools.clone().unwrap_or(existing.allowed_tools),
         allowed_skills: body.allowed_skills.clone().unwrap_or(existing.allowed_skills),
+        denied_tools: body.denied_tools.clone().unwrap_or(existing.denied_tools),
         description: body.description.clone().unwrap_or(existing.description),
         created_at: existing.created_at,
         updated_at: existing.updated_at,
@@ -1,12 +1,15 @@
-//! System controller — disk usage info and cleanup endpoints.
+//! System controller — disk usage info, cleanup, runtime log level and signing freeze endpoints.
 
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use walkdir::WalkDir;
 
+use crate::channels::dispatch_limiter::DispatchStats;
 use crate::config;
 use crate::controllers::health::VERSION;
+use crate::logging;
+use crate::wallet;
 use crate::AppState;
 
 /// Validate session token from request (same pattern as memory controller)
@@ -60,6 +63,10 @@ struct DiskInfo {
 #[derive(Debug, Serialize)]
 struct SystemInfoResponse {
     disk: DiskInfo,
+    /// Concurrent dispatch usage, for sizing STARK_MAX_CONCURRENT_DISPATCHES
+    dispatch: DispatchStats,
+    /// Whether transaction signing is frozen instance-wide
+    signing_frozen: bool,
     uptime_secs: u64,
     version: String,
 }
@@ -80,6 +87,24 @@ struct CleanupWorkspaceBody {
     confirm: bool,
 }
 
+#[derive(Debug, Deserialize)]
+struct SetLogLevelBody {
+    /// Module path prefix (`stark_backend::channels`) or log tag (`DISPATCH`)
+    target: String,
+    /// off/error/warn/info/debug/trace; null removes the override
+    level: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetSigningFreezeBody {
+    frozen: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct SigningFreezeResponse {
+    frozen: bool,
+}
+
 #[derive(Debug, Serialize)]
 struct CleanupResponse {
     success: bool,
@@ -170,6 +195,8 @@ async fn system_info(data: web::Data<AppState>, req: HttpRequest) -> impl Respon
             percentage,
             breakdown,
         },
+        dispatch: data.dispatcher.dispatch_limiter().stats(),
+        signing_frozen: wallet::is_signing_frozen(),
         uptime_secs,
         version: VERSION.to_string(),
     })
@@ -302,12 +329,110 @@ async fn cleanup_workspace(
     })
 }
 
+/// GET /api/system/log-levels
+async fn get_log_levels(data: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    HttpResponse::Ok().json(logging::current_levels())
+}
+
+/// PUT /api/system/log-levels
+///
+/// Override the log level for a target or tag until restart.
+async fn set_log_level(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<SetLogLevelBody>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    let Some(selector) = logging::Selector::parse(&body.target) else {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "target must be a module path (e.g. stark_backend::channels) or an upper-case log tag (e.g. DISPATCH)"
+        }));
+    };
+    let level = match body.level.as_deref().map(str::parse::<log::LevelFilter>) {
+        None => None,
+        Some(Ok(level)) => Some(level),
+        Some(Err(_)) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "level must be one of off, error, warn, info, debug, trace"
+            }));
+        }
+    };
+
+    log::info!("[SYSTEM] Log level for {} set to {:?}", body.target, level);
+    logging::set_level(selector, level);
+    HttpResponse::Ok().json(logging::current_levels())
+}
+
+/// DELETE /api/system/log-levels
+///
+/// Drop all overrides and return to the RUST_LOG levels.
+async fn reset_log_levels(data: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    logging::reset_levels();
+    log::info!("[SYSTEM] Log level overrides cleared");
+    HttpResponse::Ok().json(logging::current_levels())
+}
+
+/// GET /api/system/signing-freeze
+async fn get_signing_freeze(data: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+    HttpResponse::Ok().json(SigningFreezeResponse {
+        frozen: wallet::is_signing_frozen(),
+    })
+}
+
+/// PUT /api/system/signing-freeze
+///
+/// Freeze or unfreeze transaction signing. Takes effect immediately and
+/// persists across restarts.
+async fn set_signing_freeze(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<SetSigningFreezeBody>,
+) -> impl Responder {
+    if let Err(resp) = validate_session_from_request(&data, &req) {
+        return resp;
+    }
+
+    // Apply the runtime flag first so a failed write can't leave signing open
+    if body.frozen {
+        wallet::set_signing_frozen(true);
+    }
+    if let Err(e) = data.db.set_signing_frozen(body.frozen) {
+        log::error!("[SYSTEM] Failed to persist signing freeze: {}", e);
+        return HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Failed to save signing freeze: {}", e)
+        }));
+    }
+    wallet::set_signing_frozen(body.frozen);
+
+    log::warn!("[SYSTEM] Signing {} via API", if body.frozen { "frozen" } else { "unfrozen" });
+    HttpResponse::Ok().json(SigningFreezeResponse {
+        frozen: wallet::is_signing_frozen(),
+    })
+}
+
 /// Configure system routes
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api/system")
             .route("/info", web::get().to(system_info))
             .route("/cleanup/memories", web::post().to(cleanup_memories))
-            .route("/cleanup/workspace", web::post().to(cleanup_workspace)),
+            .route("/cleanup/workspace", web::post().to(cleanup_workspace))
+            .route("/log-levels", web::get().to(get_log_levels))
+            .route("/log-levels", web::put().to(set_log_level))
+            .route("/log-levels", web::delete().to(reset_log_levels))
+            .route("/signing-freeze", web::get().to(get_signing_freeze))
+            .route("/signing-freeze", web::put().to(set_signing_freeze)),
     );
 }
@@ -2,9 +2,17 @@
 
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::AppState;
-use crate::telemetry::{Resource, ResourceType};
+use crate::telemetry::{MetricKind, PrometheusWriter, Resource, ResourceType};
+use crate::tx_queue::QueuedTxStatus;
+
+/// How long a rendered `/metrics` snapshot is served before it is rebuilt
+const METRICS_REFRESH_INTERVAL: Duration = Duration::from_secs(15);
+
+static METRICS_SNAPSHOT: LazyLock<Mutex<Option<(Instant, String)>>> = LazyLock::new(|| Mutex::new(None));
 
 #[derive(Serialize)]
 struct ErrorResponse {
@@ -17,6 +25,7 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/session/{id}/timeline", web::get().to(get_session_timeline))
             .route("/rollout/{id}/summary", web::get().to(get_rollout_summary))
             .route("/rollout/{id}/triplets", web::get().to(get_rollout_triplets))
+            .route("/rollout/{id}/replay", web::post().to(replay_rollout))
             .route("/rewards/stats", web::get().to(get_reward_stats))
     );
     cfg.service(
@@ -25,6 +34,7 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("", web::post().to(create_resource))
             .route("/rollback/{version}", web::post().to(rollback_resource))
     );
+    cfg.service(web::resource("/metrics").route(web::get().to(get_metrics)));
 }
 
 async fn get_session_timeline(
@@ -74,6 +84,26 @@ async fn get_reward_stats(
     HttpResponse::Ok().json(stats)
 }
 
+#[derive(Deserialize)]
+struct ReplayRolloutRequest {
+    /// Resource version to resolve prompts from during the replay
+    version_id: String,
+}
+
+/// Replay a recorded rollout against another resource version and compare the outcomes
+async fn replay_rollout(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    body: web::Json<ReplayRolloutRequest>,
+    _req: HttpRequest,
+) -> impl Responder {
+    let rollout_id = path.into_inner();
+    match state.dispatcher.replay_rollout(&rollout_id, &body.version_id).await {
+        Ok(report) => HttpResponse::Ok().json(report),
+        Err(e) => HttpResponse::BadRequest().json(ErrorResponse { error: e }),
+    }
+}
+
 async fn list_resources(
     state: web::Data<AppState>,
     _req: HttpRequest,
@@ -147,3 +177,152 @@ async fn rollback_resource(
         }),
     }
 }
+
+/// Scrapers authenticate with `STARK_METRICS_TOKEN` when it is set;
+/// otherwise a logged-in dashboard session is required.
+fn authorize_metrics(state: &web::Data<AppState>, req: &HttpRequest) -> Result<(), HttpResponse> {
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim_start_matches("Bearer ").to_string());
+    let Some(token) = token else {
+        return Err(HttpResponse::Unauthorized().json(ErrorResponse {
+            error: "No authorization token provided".to_string(),
+        }));
+    };
+
+    let authorized = match crate::config::metrics_token() {
+        Some(expected) => crate::modules::internal_token::constant_time_eq(expected.as_bytes(), token.as_bytes()),
+        None => matches!(state.db.validate_session(&token), Ok(Some(_))),
+    };
+    if authorized {
+        Ok(())
+    } else {
+        Err(HttpResponse::Unauthorized().json(ErrorResponse {
+            error: "Invalid metrics token".to_string(),
+        }))
+    }
+}
+
+async fn get_metrics(state: web::Data<AppState>, req: HttpRequest) -> impl Responder {
+    if let Err(resp) = authorize_metrics(&state, &req) {
+        return resp;
+    }
+
+    let body = {
+        let mut snapshot = METRICS_SNAPSHOT.lock().unwrap();
+        match snapshot.as_ref() {
+            Some((rendered_at, body)) if rendered_at.elapsed() < METRICS_REFRESH_INTERVAL => body.clone(),
+            _ => {
+                let body = render_metrics(&state);
+                *snapshot = Some((Instant::now(), body.clone()));
+                body
+            }
+        }
+    };
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4; charset=utf-8")
+        .body(body)
+}
+
+fn render_metrics(state: &AppState) -> String {
+    let mut w = PrometheusWriter::new();
+
+    w.family(
+        "starkbot_build_info",
+        MetricKind::Gauge,
+        "Backend version",
+        [(vec![("version", crate::controllers::health::VERSION.to_string())], 1.0)],
+    );
+    w.gauge("starkbot_uptime_seconds", "Seconds since the backend started", state.started_at.elapsed().as_secs_f64());
+
+    let sessions = state.db.count_sessions_by_status().unwrap_or_else(|e| {
+        log::error!("[METRICS] Failed to count sessions: {}", e);
+        Vec::new()
+    });
+    w.family(
+        "starkbot_sessions",
+        MetricKind::Gauge,
+        "Chat sessions by completion status",
+        sessions.into_iter().map(|(status, count)| (vec![("status", status)], count as f64)),
+    );
+    w.gauge(
+        "starkbot_active_executions",
+        "Agent executions currently running",
+        state.execution_tracker.active_execution_count() as f64,
+    );
+
+    let tools = state.db.count_tool_executions().unwrap_or_else(|e| {
+        log::error!("[METRICS] Failed to count tool executions: {}", e);
+        Vec::new()
+    });
+    w.family(
+        "starkbot_tool_executions_total",
+        MetricKind::Counter,
+        "Logged tool executions by tool and outcome",
+        tools.into_iter().map(|(tool, success, count)| {
+            (vec![("tool", tool), ("success", success.to_string())], count as f64)
+        }),
+    );
+
+    w.family(
+        "starkbot_rollouts",
+        MetricKind::Gauge,
+        "Rollouts by status (within the telemetry retention window)",
+        state
+            .telemetry_store
+            .rollout_status_counts()
+            .into_iter()
+            .map(|(status, count)| (vec![("status", status)], count as f64)),
+    );
+
+    let tx_counts: Vec<(QueuedTxStatus, usize)> = QueuedTxStatus::ALL
+        .iter()
+        .map(|s| (*s, state.tx_queue.count_by_status(*s)))
+        .collect();
+    w.gauge(
+        "starkbot_tx_queue_depth",
+        "Queued transactions not yet confirmed, failed or replaced",
+        tx_counts.iter().filter(|(s, _)| s.is_in_flight()).map(|(_, n)| *n).sum::<usize>() as f64,
+    );
+    w.family(
+        "starkbot_tx_queue_transactions",
+        MetricKind::Gauge,
+        "Transactions in the in-memory queue by status",
+        tx_counts.iter().map(|(s, n)| (vec![("status", s.to_string())], *n as f64)),
+    );
+
+    let mut hook_stats: Vec<_> = state.hook_manager.get_all_stats().into_iter().collect();
+    hook_stats.sort_by(|a, b| a.0.cmp(&b.0));
+    w.family(
+        "starkbot_hook_executions_total",
+        MetricKind::Counter,
+        "Hook executions by hook and outcome (timeouts and panics are also counted as errors)",
+        hook_stats.iter().flat_map(|(hook, s)| {
+            [
+                ("success", s.successes),
+                ("error", s.failures),
+                ("timeout", s.timeouts),
+                ("panic", s.panics),
+                ("skip", s.skips),
+                ("cancel", s.cancellations),
+            ]
+            .into_iter()
+            .map(move |(outcome, n)| (vec![("hook", hook.clone()), ("outcome", outcome.to_string())], n as f64))
+        }),
+    );
+    w.family(
+        "starkbot_hook_duration_max_ms",
+        MetricKind::Gauge,
+        "Slowest hook execution in milliseconds",
+        hook_stats.iter().map(|(hook, s)| (vec![("hook", hook.clone())], s.max_execution_ms as f64)),
+    );
+
+    if let Some(quota) = state.disk_quota.as_ref().filter(|q| q.is_enabled()) {
+        w.gauge("starkbot_disk_usage_bytes", "Disk used by tracked directories", quota.usage_bytes() as f64);
+        w.gauge("starkbot_disk_quota_bytes", "Configured disk quota", quota.quota_bytes() as f64);
+    }
+
+    w.finish()
+}
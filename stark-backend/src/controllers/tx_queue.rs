@@ -6,7 +6,7 @@ use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
 
 use crate::AppState;
-use crate::tx_queue::{QueuedTxStatus, QueuedTxSummary};
+use crate::tx_queue::{self, GasStrategy, QueuedTxStatus, QueuedTxSummary, SubmissionClaim};
 
 /// Validate session token from request
 fn validate_session(state: &web::Data<AppState>, req: &HttpRequest) -> Result<(), HttpResponse> {
@@ -47,7 +47,8 @@ pub fn config(cfg: &mut web::ServiceConfig) {
         web::scope("/api/tx-queue")
             .route("", web::get().to(list_transactions))
             .route("/pending", web::get().to(list_pending))
-            .route("/{uuid}", web::get().to(get_transaction)),
+            .route("/{uuid}", web::get().to(get_transaction))
+            .route("/{uuid}/replace", web::post().to(replace_transaction)),
     );
 }
 
@@ -67,6 +68,7 @@ pub struct ListResponse {
     pending_count: usize,
     confirmed_count: usize,
     failed_count: usize,
+    stuck_count: usize,
 }
 
 /// Response for a single transaction
@@ -100,6 +102,8 @@ async fn list_transactions(
             "confirmed" => Some(QueuedTxStatus::Confirmed),
             "failed" => Some(QueuedTxStatus::Failed),
             "expired" => Some(QueuedTxStatus::Expired),
+            "stuck" => Some(QueuedTxStatus::Stuck),
+            "replaced" => Some(QueuedTxStatus::Replaced),
             _ => None,
         }
     });
@@ -119,6 +123,7 @@ async fn list_transactions(
     let pending_count = tx_queue.count_by_status(QueuedTxStatus::Pending);
     let confirmed_count = tx_queue.count_by_status(QueuedTxStatus::Confirmed);
     let failed_count = tx_queue.count_by_status(QueuedTxStatus::Failed);
+    let stuck_count = tx_queue.count_by_status(QueuedTxStatus::Stuck);
 
     HttpResponse::Ok().json(ListResponse {
         success: true,
@@ -127,6 +132,7 @@ async fn list_transactions(
         pending_count,
         confirmed_count,
         failed_count,
+        stuck_count,
     })
 }
 
@@ -144,6 +150,7 @@ async fn list_pending(state: web::Data<AppState>, req: HttpRequest) -> impl Resp
     let pending_count = total;
     let confirmed_count = tx_queue.count_by_status(QueuedTxStatus::Confirmed);
     let failed_count = tx_queue.count_by_status(QueuedTxStatus::Failed);
+    let stuck_count = tx_queue.count_by_status(QueuedTxStatus::Stuck);
 
     HttpResponse::Ok().json(ListResponse {
         success: true,
@@ -152,6 +159,7 @@ async fn list_pending(state: web::Data<AppState>, req: HttpRequest) -> impl Resp
         pending_count,
         confirmed_count,
         failed_count,
+        stuck_count,
     })
 }
 
@@ -181,3 +189,92 @@ async fn get_transaction(
         }),
     }
 }
+
+/// Replace a stuck transaction with a gas-bumped copy (same nonce)
+///
+/// An `Idempotency-Key` header makes retries safe: a repeat within the window
+/// returns the replacement already sent instead of sending another.
+async fn replace_transaction(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if let Err(resp) = validate_session(&state, &req) {
+        return resp;
+    }
+
+    let uuid = path.into_inner();
+    let idempotency_key = req
+        .headers()
+        .get("Idempotency-Key")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    if let Some(ref key) = idempotency_key {
+        match state.tx_queue.claim_submission(key, &uuid) {
+            Ok(SubmissionClaim::New) => {}
+            Ok(SubmissionClaim::InProgress) => {
+                return HttpResponse::Conflict().json(TransactionResponse {
+                    success: false,
+                    transaction: None,
+                    error: Some(format!("A replacement for {} is already being sent", uuid)),
+                });
+            }
+            Ok(SubmissionClaim::Completed(replacement_uuid)) => {
+                return HttpResponse::Ok().json(TransactionResponse {
+                    success: true,
+                    transaction: state.tx_queue.get_summary(&replacement_uuid),
+                    error: None,
+                });
+            }
+            Err(e) => {
+                return HttpResponse::Conflict().json(TransactionResponse {
+                    success: false,
+                    transaction: None,
+                    error: Some(e),
+                });
+            }
+        }
+    }
+
+    let wallet_provider = match &state.wallet_provider {
+        Some(wp) => wp.clone(),
+        None => {
+            if let Some(ref key) = idempotency_key {
+                state.tx_queue.release_submission(key);
+            }
+            return HttpResponse::ServiceUnavailable().json(TransactionResponse {
+                success: false,
+                transaction: None,
+                error: Some("Wallet not configured".to_string()),
+            });
+        }
+    };
+
+    let settings = state.db.get_bot_settings().unwrap_or_default();
+    let gas_strategy = GasStrategy::from_settings(Some(&settings.gas_strategy), settings.gas_max_fee_gwei);
+    match tx_queue::replace_transaction(&state.tx_queue, &uuid, wallet_provider, &gas_strategy, "partner").await {
+        Ok(replacement) => {
+            if let Some(ref key) = idempotency_key {
+                state.tx_queue.complete_submission(key, &replacement.uuid);
+            }
+            HttpResponse::Ok().json(TransactionResponse {
+                success: true,
+                transaction: Some(QueuedTxSummary::from(&replacement)),
+                error: None,
+            })
+        }
+        Err(e) => {
+            if let Some(ref key) = idempotency_key {
+                state.tx_queue.release_submission(key);
+            }
+            log::warn!("Failed to replace transaction {}: {}", uuid, e);
+            HttpResponse::BadRequest().json(TransactionResponse {
+                success: false,
+                transaction: None,
+                error: Some(e),
+            })
+        }
+    }
+}
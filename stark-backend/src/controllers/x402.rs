@@ -1,12 +1,14 @@
 //! x402 RPC endpoints for module-side payment verification.
 //!
-//! Provides two endpoints:
+//! Provides three endpoints:
 //! - `POST /rpc/x402/verify`           — verify an x402 payment signature
 //! - `POST /rpc/x402/payment-required` — generate a 402 response payload
+//! - `GET  /rpc/x402/budget`           — remaining rolling-window spend budgets
 
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpRequest, HttpResponse};
 use serde::Deserialize;
 
+use crate::x402::payment_limits;
 use crate::x402::verify::{self, VerifyRequirements};
 
 /// Request body for POST /rpc/x402/verify
@@ -40,6 +42,14 @@ struct PaymentRequiredRequest {
     extra: Option<serde_json::Value>,
 }
 
+/// Query for GET /rpc/x402/budget
+#[derive(Debug, Deserialize)]
+struct BudgetQuery {
+    /// Restrict the response to a single asset (symbol or contract address)
+    #[serde(default)]
+    asset: Option<String>,
+}
+
 fn default_currency() -> String { "USDC".to_string() }
 fn default_network() -> String { "base".to_string() }
 fn default_scheme() -> String { "exact".to_string() }
@@ -120,10 +130,39 @@ async fn generate_payment_required(body: web::Json<PaymentRequiredRequest>) -> H
     }))
 }
 
+/// GET /rpc/x402/budget — report spent/remaining amounts for each rolling-window budget.
+///
+/// Restricted to modules with the `x402.budget` permission since spend data is private.
+async fn get_budget(
+    req: HttpRequest,
+    query: web::Query<BudgetQuery>,
+) -> HttpResponse {
+    if let Err(resp) = crate::modules::permissions::authorize(
+        &req,
+        crate::modules::permissions::Permission::X402Budget,
+    ) {
+        return resp;
+    }
+
+    match query.asset.as_deref() {
+        Some(asset) => match payment_limits::budget_status(asset) {
+            Some(status) => HttpResponse::Ok().json(serde_json::json!({ "budgets": [status] })),
+            None => HttpResponse::Ok().json(serde_json::json!({
+                "budgets": [],
+                "message": format!("No window budget configured for {}", asset),
+            })),
+        },
+        None => HttpResponse::Ok().json(serde_json::json!({
+            "budgets": payment_limits::all_budget_statuses(),
+        })),
+    }
+}
+
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/rpc/x402")
             .route("/verify", web::post().to(verify_payment))
-            .route("/payment-required", web::post().to(generate_payment_required)),
+            .route("/payment-required", web::post().to(generate_payment_required))
+            .route("/budget", web::get().to(get_budget)),
     );
 }
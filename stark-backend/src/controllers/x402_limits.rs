@@ -60,7 +60,10 @@ pub async fn get_x402_limits(
         })
         .collect();
 
-    HttpResponse::Ok().json(serde_json::json!({ "limits": entries }))
+    HttpResponse::Ok().json(serde_json::json!({
+        "limits": entries,
+        "budgets": payment_limits::all_budget_statuses(),
+    }))
 }
 
 #[derive(Debug, Deserialize)]
@@ -79,6 +82,19 @@ fn default_decimals() -> u8 {
     6
 }
 
+#[derive(Debug, Deserialize)]
+pub struct UpdateBudgetRequest {
+    pub asset: String,
+    /// Maximum raw-unit amount spendable within the window
+    pub max_amount: String,
+    #[serde(default = "default_window_hours")]
+    pub window_hours: u32,
+}
+
+fn default_window_hours() -> u32 {
+    payment_limits::DEFAULT_WINDOW_HOURS
+}
+
 /// PUT /api/x402-limits — update a single payment limit
 pub async fn update_x402_limit(
     state: web::Data<AppState>,
@@ -125,11 +141,79 @@ pub async fn update_x402_limit(
     }))
 }
 
+/// PUT /api/x402-limits/budget — set the rolling-window spend budget for an asset
+pub async fn update_x402_budget(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    body: web::Json<UpdateBudgetRequest>,
+) -> impl Responder {
+    if let Err(resp) = validate_session(&state, &req) {
+        return resp;
+    }
+
+    let r = body.into_inner();
+    let asset = r.asset.to_uppercase();
+
+    if r.max_amount.parse::<u128>().is_err() {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "max_amount must be a valid non-negative integer string"
+        }));
+    }
+    if r.window_hours == 0 || r.window_hours > payment_limits::MAX_WINDOW_HOURS {
+        return HttpResponse::BadRequest().json(serde_json::json!({
+            "error": format!("window_hours must be between 1 and {}", payment_limits::MAX_WINDOW_HOURS)
+        }));
+    }
+
+    if let Err(e) = state.db.set_x402_payment_budget(&asset, &r.max_amount, r.window_hours) {
+        log::error!("Failed to save x402 payment budget: {}", e);
+        return HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": format!("Database error: {}", e)
+        }));
+    }
+
+    payment_limits::set_budget(&asset, &r.max_amount, r.window_hours);
+
+    log::info!(
+        "[x402_limits] Updated budget: {} max_amount={} window={}h",
+        asset, r.max_amount, r.window_hours
+    );
+
+    HttpResponse::Ok().json(payment_limits::budget_status(&asset))
+}
+
+/// DELETE /api/x402-limits/budget/{asset} — remove the rolling-window budget for an asset
+pub async fn delete_x402_budget(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    if let Err(resp) = validate_session(&state, &req) {
+        return resp;
+    }
+
+    let asset = path.into_inner().to_uppercase();
+    match state.db.delete_x402_payment_budget(&asset) {
+        Ok(deleted) => {
+            payment_limits::remove_budget(&asset);
+            HttpResponse::Ok().json(serde_json::json!({ "asset": asset, "deleted": deleted }))
+        }
+        Err(e) => {
+            log::error!("Failed to delete x402 payment budget: {}", e);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": format!("Database error: {}", e)
+            }))
+        }
+    }
+}
+
 /// Configure routes
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/api/x402-limits")
             .route("", web::get().to(get_x402_limits))
             .route("", web::put().to(update_x402_limit))
+            .route("/budget", web::put().to(update_x402_budget))
+            .route("/budget/{asset}", web::delete().to(delete_x402_budget))
     );
 }
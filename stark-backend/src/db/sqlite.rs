@@ -79,6 +79,17 @@ impl Database {
             .expect("Failed to get database connection from pool (timeout after 5s)")
     }
 
+    /// Check that a connection can be checked out and queried, without panicking
+    /// when the pool is exhausted (used by the readiness probe)
+    pub fn ping(&self) -> Result<(), String> {
+        let conn = self.pool
+            .get_timeout(std::time::Duration::from_secs(2))
+            .map_err(|e| format!("No database connection available: {}", e))?;
+        conn.query_row("SELECT 1", [], |row| row.get::<_, i64>(0))
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
     /// Initialize all database tables and run migrations
     fn init(&self) -> SqliteResult<()> {
         let conn = self.conn();
@@ -109,6 +120,9 @@ impl Database {
             [],
         )?;
 
+        // Migration: track session activity for expiry/refresh
+        let _ = conn.execute("ALTER TABLE auth_sessions ADD COLUMN last_active_at TEXT", []);
+
         // Auth challenges table for SIWE
         conn.execute(
             "CREATE TABLE IF NOT EXISTS auth_challenges (
@@ -464,6 +478,26 @@ impl Database {
         let _ = conn.execute("ALTER TABLE bot_settings ADD COLUMN whisper_server_url TEXT", []);
         let _ = conn.execute("ALTER TABLE bot_settings ADD COLUMN embeddings_server_url TEXT", []);
 
+        // Migration: Add gas strategy columns for queued transactions
+        let _ = conn.execute("ALTER TABLE bot_settings ADD COLUMN gas_strategy TEXT NOT NULL DEFAULT 'standard'", []);
+        let _ = conn.execute("ALTER TABLE bot_settings ADD COLUMN gas_max_fee_gwei REAL", []);
+
+        // Migration: Add chat command prefix and aliases
+        let _ = conn.execute("ALTER TABLE bot_settings ADD COLUMN command_prefix TEXT NOT NULL DEFAULT '/'", []);
+        let _ = conn.execute("ALTER TABLE bot_settings ADD COLUMN command_aliases TEXT", []);
+
+        // Migration: Add previous-session message carryover for gateway channels
+        let _ = conn.execute("ALTER TABLE bot_settings ADD COLUMN gateway_previous_messages INTEGER NOT NULL DEFAULT 6", []);
+
+        // Migration: Add tokenizer choice for the memory full-text index
+        let _ = conn.execute("ALTER TABLE bot_settings ADD COLUMN memory_fts_tokenizer TEXT NOT NULL DEFAULT 'unicode61'", []);
+
+        // Migration: Add the instance-wide signing freeze (web3 kill switch)
+        let _ = conn.execute("ALTER TABLE bot_settings ADD COLUMN signing_frozen INTEGER NOT NULL DEFAULT 0", []);
+
+        // Migration: Add the confirmation timeout for queued transactions
+        let _ = conn.execute("ALTER TABLE bot_settings ADD COLUMN tx_confirmation_timeout_secs INTEGER NOT NULL DEFAULT 300", []);
+
         // Initialize bot_settings with defaults if empty
         let bot_settings_count: i64 = conn
             .query_row("SELECT COUNT(*) FROM bot_settings", [], |row| row.get(0))
@@ -512,6 +546,9 @@ impl Database {
         let _ = conn.execute("ALTER TABLE chat_sessions ADD COLUMN completion_status TEXT NOT NULL DEFAULT 'active'", []);
         // QMD Memory: Add compaction_summary to store summary text directly
         let _ = conn.execute("ALTER TABLE chat_sessions ADD COLUMN compaction_summary TEXT", []);
+        // Session titles: generated display title + message count it was based on
+        let _ = conn.execute("ALTER TABLE chat_sessions ADD COLUMN title TEXT", []);
+        let _ = conn.execute("ALTER TABLE chat_sessions ADD COLUMN title_message_count INTEGER NOT NULL DEFAULT 0", []);
         // Sliding window compaction: Add generation counter and timestamp
         let _ = conn.execute("ALTER TABLE chat_sessions ADD COLUMN compaction_generation INTEGER NOT NULL DEFAULT 0", []);
         let _ = conn.execute("ALTER TABLE chat_sessions ADD COLUMN last_compaction_at TEXT", []);
@@ -537,6 +574,85 @@ impl Database {
             [],
         )?;
 
+        // FTS5 index over session message content (session search)
+        let session_fts_exists: bool = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'session_messages_fts'",
+                [],
+                |row| row.get::<_, i64>(0),
+            )
+            .map(|n| n > 0)
+            .unwrap_or(false);
+        conn.execute(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS session_messages_fts USING fts5(
+                content,
+                content=session_messages,
+                content_rowid=id
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TRIGGER IF NOT EXISTS session_messages_ai AFTER INSERT ON session_messages BEGIN
+                INSERT INTO session_messages_fts(rowid, content) VALUES (new.id, new.content);
+            END",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TRIGGER IF NOT EXISTS session_messages_ad AFTER DELETE ON session_messages BEGIN
+                INSERT INTO session_messages_fts(session_messages_fts, rowid, content)
+                VALUES ('delete', old.id, old.content);
+            END",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TRIGGER IF NOT EXISTS session_messages_au AFTER UPDATE ON session_messages BEGIN
+                INSERT INTO session_messages_fts(session_messages_fts, rowid, content)
+                VALUES ('delete', old.id, old.content);
+                INSERT INTO session_messages_fts(rowid, content) VALUES (new.id, new.content);
+            END",
+            [],
+        )?;
+        // Index messages stored before the FTS table existed
+        if !session_fts_exists {
+            conn.execute("INSERT INTO session_messages_fts(session_messages_fts) VALUES ('rebuild')", [])?;
+        }
+
+        // Session tags - free-form labels for finding past conversations
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS session_tags (
+                session_id INTEGER NOT NULL,
+                tag TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (session_id, tag),
+                FOREIGN KEY (session_id) REFERENCES chat_sessions(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_session_tags_tag ON session_tags(tag)",
+            [],
+        )?;
+
+        // Session spend ledger - x402 payments, estimated AI token costs and gas per session
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS session_spend (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                amount REAL NOT NULL DEFAULT 0,
+                currency TEXT NOT NULL,
+                tokens INTEGER,
+                detail TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (session_id) REFERENCES chat_sessions(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_session_spend_session ON session_spend(session_id)",
+            [],
+        )?;
+
         // Telegram chat messages - passive log of ALL messages in Telegram chats
         // Independent of session system, used by telegram_read readHistory
         conn.execute(
@@ -558,6 +674,26 @@ impl Database {
             [],
         )?;
 
+        // Channel messages - inbound normalized messages per channel, exported for moderation
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS channel_messages (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                channel_id INTEGER NOT NULL,
+                chat_id TEXT NOT NULL,
+                chat_name TEXT,
+                user_id TEXT NOT NULL,
+                user_name TEXT NOT NULL,
+                content TEXT,
+                platform_message_id TEXT,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_channel_messages_channel ON channel_messages(channel_id, created_at)",
+            [],
+        )?;
+
         // Identity links table - cross-channel user mapping
         conn.execute(
             "CREATE TABLE IF NOT EXISTS identity_links (
@@ -609,6 +745,9 @@ impl Database {
         let _ = conn.execute("ALTER TABLE memories ADD COLUMN valid_from TEXT", []);
         let _ = conn.execute("ALTER TABLE memories ADD COLUMN valid_until TEXT", []);
         let _ = conn.execute("ALTER TABLE memories ADD COLUMN agent_subtype TEXT", []);
+        // Retrieval hits, and how many of them the decay pass has already turned into importance
+        let _ = conn.execute("ALTER TABLE memories ADD COLUMN access_count INTEGER NOT NULL DEFAULT 0", []);
+        let _ = conn.execute("ALTER TABLE memories ADD COLUMN access_count_reinforced INTEGER NOT NULL DEFAULT 0", []);
 
         // FTS5 virtual table for full-text search on memories
         conn.execute(
@@ -813,6 +952,23 @@ impl Database {
             [],
         )?;
 
+        // Skill version history (snapshot of the previous definition on each update)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS skill_versions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                skill_name TEXT NOT NULL,
+                version TEXT NOT NULL,
+                skill_json TEXT NOT NULL,
+                scripts_json TEXT NOT NULL DEFAULT '[]',
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_skill_versions_name ON skill_versions(skill_name, id)",
+            [],
+        )?;
+
         // Skill associations (knowledge graph for skill relationships)
         conn.execute(
             "CREATE TABLE IF NOT EXISTS skill_associations (
@@ -855,6 +1011,9 @@ impl Database {
             [],
         )?;
 
+        // Migration: typed tool results alongside the text result
+        let _ = conn.execute("ALTER TABLE tool_executions ADD COLUMN structured_result TEXT", []);
+
         // Create index for tool executions lookup
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_tool_executions_channel ON tool_executions(channel_id, executed_at)",
@@ -1043,6 +1202,24 @@ impl Database {
             [],
         );
 
+        // x402 payment budgets — cumulative spend caps per rolling window
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS x402_payment_budgets (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                asset TEXT NOT NULL UNIQUE,
+                max_amount TEXT NOT NULL,
+                window_hours INTEGER NOT NULL DEFAULT 24,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_x402_payments_created_at ON x402_payments(created_at)",
+            [],
+        )?;
+
         // Migration: drop old agent_identity table if it has the legacy wallet_address column
         {
             let has_wallet_col: bool = conn
@@ -1326,6 +1503,20 @@ impl Database {
             [],
         )?;
 
+        // Migration: nonce tracking and replace-by-fee history
+        let _ = conn.execute("ALTER TABLE broadcasted_transactions ADD COLUMN nonce INTEGER", []);
+        let _ = conn.execute("ALTER TABLE broadcasted_transactions ADD COLUMN replaced_by TEXT", []);
+
+        // Running total of broadcast records removed by the retention policy (single row)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS broadcast_history_pruned (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                pruned_count INTEGER NOT NULL DEFAULT 0,
+                last_pruned_at TEXT
+            )",
+            [],
+        )?;
+
         // Channel settings table - per-channel configuration
         conn.execute(
             "CREATE TABLE IF NOT EXISTS channel_settings (
@@ -1528,6 +1719,12 @@ impl Database {
             [],
         );
 
+        // Migration: add require_plan_approval column (wait for the user to approve planned tasks)
+        let _ = conn.execute(
+            "ALTER TABLE agent_subtypes ADD COLUMN require_plan_approval INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+
         // Keystore state - track backup/retrieval status per wallet
         conn.execute(
             "CREATE TABLE IF NOT EXISTS keystore_state (
@@ -1565,6 +1762,11 @@ impl Database {
             "ALTER TABLE keystore_state ADD COLUMN auto_sync_node_count INTEGER",
             [],
         );
+        // Migration: Fingerprint of the last scheduled backup, to skip uploads when nothing changed
+        let _ = conn.execute(
+            "ALTER TABLE keystore_state ADD COLUMN last_backup_hash TEXT",
+            [],
+        );
 
         // =====================================================
         // Telemetry tables (agent-lightning philosophy)
@@ -1704,6 +1906,12 @@ impl Database {
             CREATE INDEX IF NOT EXISTS idx_sra_lookup ON special_role_assignments(channel_type, user_id);",
         )?;
 
+        // Migration: Add denied_tools column to special_roles (tools a role never gets)
+        let _ = conn.execute(
+            "ALTER TABLE special_roles ADD COLUMN denied_tools TEXT NOT NULL DEFAULT '[]'",
+            [],
+        );
+
         // Migration: Add label column to special_role_assignments if it doesn't exist
         let _ = conn.execute(
             "ALTER TABLE special_role_assignments ADD COLUMN label TEXT",
@@ -2063,6 +2271,42 @@ impl Database {
         Ok(())
     }
 
+    /// When the last backup to keystore was made for a wallet (manual or scheduled)
+    pub fn get_last_keystore_backup_at(&self, wallet_address: &str) -> Result<Option<chrono::DateTime<chrono::Utc>>, rusqlite::Error> {
+        let conn = self.conn();
+        let last: Option<String> = conn.query_row(
+            "SELECT MAX(last_backup_at) FROM keystore_state WHERE LOWER(wallet_address) = ?1",
+            [wallet_address.to_lowercase()],
+            |row| row.get(0),
+        )?;
+        Ok(last
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok())
+            .map(|dt| dt.with_timezone(&chrono::Utc)))
+    }
+
+    /// Fingerprint of the data in the last scheduled backup for a wallet
+    pub fn get_last_keystore_backup_hash(&self, wallet_address: &str) -> Result<Option<String>, rusqlite::Error> {
+        use rusqlite::OptionalExtension;
+        let conn = self.conn();
+        conn.query_row(
+            "SELECT last_backup_hash FROM keystore_state
+             WHERE wallet_address = ?1 AND last_backup_hash IS NOT NULL",
+            [wallet_address],
+            |row| row.get(0),
+        )
+        .optional()
+    }
+
+    /// Store the fingerprint of a backup that was just uploaded
+    pub fn set_last_keystore_backup_hash(&self, wallet_address: &str, hash: &str) -> Result<(), rusqlite::Error> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE keystore_state SET last_backup_hash = ?2 WHERE wallet_address = ?1",
+            rusqlite::params![wallet_address, hash],
+        )?;
+        Ok(())
+    }
+
     /// Record a successful retrieval from keystore
     pub fn record_keystore_retrieval(&self, wallet_address: &str) -> Result<(), rusqlite::Error> {
         let conn = self.conn();
@@ -11,7 +11,7 @@ impl Database {
     pub fn list_agent_subtypes(&self) -> SqliteResult<Vec<AgentSubtypeConfig>> {
         let conn = self.conn();
         let mut stmt = conn.prepare(
-            "SELECT key, label, emoji, description, tool_groups_json, skill_tags_json, prompt, sort_order, enabled, max_iterations, additional_tools_json, skip_task_planner, aliases_json, hidden, preferred_ai_model
+            "SELECT key, label, emoji, description, tool_groups_json, skill_tags_json, prompt, sort_order, enabled, max_iterations, additional_tools_json, skip_task_planner, aliases_json, hidden, preferred_ai_model, require_plan_approval
              FROM agent_subtypes ORDER BY sort_order, key"
         )?;
 
@@ -38,6 +38,7 @@ impl Database {
                     aliases: serde_json::from_str(&aliases_str).unwrap_or_default(),
                     hidden: row.get::<_, i32>(13).unwrap_or(0) != 0,
                     preferred_ai_model: row.get::<_, Option<String>>(14).unwrap_or(None),
+                    require_plan_approval: row.get::<_, i32>(15).unwrap_or(0) != 0,
                     hooks: Vec::new(),
                 })
             })?
@@ -51,7 +52,7 @@ impl Database {
     pub fn get_agent_subtype(&self, key: &str) -> SqliteResult<Option<AgentSubtypeConfig>> {
         let conn = self.conn();
         let result = conn.query_row(
-            "SELECT key, label, emoji, description, tool_groups_json, skill_tags_json, prompt, sort_order, enabled, max_iterations, additional_tools_json, skip_task_planner, aliases_json, hidden, preferred_ai_model
+            "SELECT key, label, emoji, description, tool_groups_json, skill_tags_json, prompt, sort_order, enabled, max_iterations, additional_tools_json, skip_task_planner, aliases_json, hidden, preferred_ai_model, require_plan_approval
              FROM agent_subtypes WHERE key = ?1",
             [key],
             |row| {
@@ -76,6 +77,7 @@ impl Database {
                     aliases: serde_json::from_str(&aliases_str).unwrap_or_default(),
                     hidden: row.get::<_, i32>(13).unwrap_or(0) != 0,
                     preferred_ai_model: row.get::<_, Option<String>>(14).unwrap_or(None),
+                    require_plan_approval: row.get::<_, i32>(15).unwrap_or(0) != 0,
                     hooks: Vec::new(),
                 })
             },
@@ -97,8 +99,8 @@ impl Database {
         let aliases_json = serde_json::to_string(&config.aliases).unwrap_or_else(|_| "[]".to_string());
 
         conn.execute(
-            "INSERT INTO agent_subtypes (key, label, emoji, description, tool_groups_json, skill_tags_json, additional_tools_json, prompt, sort_order, enabled, max_iterations, skip_task_planner, aliases_json, hidden, preferred_ai_model, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?16)
+            "INSERT INTO agent_subtypes (key, label, emoji, description, tool_groups_json, skill_tags_json, additional_tools_json, prompt, sort_order, enabled, max_iterations, skip_task_planner, aliases_json, hidden, preferred_ai_model, require_plan_approval, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?17)
              ON CONFLICT(key) DO UPDATE SET
                 label = excluded.label,
                 emoji = excluded.emoji,
@@ -114,6 +116,7 @@ impl Database {
                 aliases_json = excluded.aliases_json,
                 hidden = excluded.hidden,
                 preferred_ai_model = excluded.preferred_ai_model,
+                require_plan_approval = excluded.require_plan_approval,
                 updated_at = excluded.updated_at",
             rusqlite::params![
                 config.key,
@@ -131,6 +134,7 @@ impl Database {
                 aliases_json,
                 config.hidden as i32,
                 config.preferred_ai_model,
+                config.require_plan_approval as i32,
                 now,
             ],
         )?;
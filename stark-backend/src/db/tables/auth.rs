@@ -3,7 +3,7 @@
 use chrono::{DateTime, Duration, Utc};
 use rusqlite::Result as SqliteResult;
 
-use crate::models::Session;
+use crate::models::{Session, SessionCheck};
 use super::super::Database;
 
 impl Database {
@@ -19,10 +19,11 @@ impl Database {
         let conn = self.conn();
         let token = Self::generate_session_token();
         let created_at = Utc::now();
-        let expires_at = created_at + Duration::hours(24);
+        let expires_at = created_at + Duration::hours(crate::config::session_ttl_hours());
 
         conn.execute(
-            "INSERT INTO auth_sessions (token, public_address, created_at, expires_at) VALUES (?1, ?2, ?3, ?4)",
+            "INSERT INTO auth_sessions (token, public_address, created_at, expires_at, last_active_at)
+             VALUES (?1, ?2, ?3, ?4, ?3)",
             rusqlite::params![
                 &token,
                 public_address,
@@ -38,6 +39,7 @@ impl Database {
             token,
             created_at,
             expires_at,
+            last_active_at: Some(created_at),
         })
     }
 
@@ -49,19 +51,22 @@ impl Database {
             .collect()
     }
 
-    pub fn validate_session(&self, token: &str) -> SqliteResult<Option<Session>> {
+    /// Look up a session token, distinguishing expired from unknown tokens.
+    /// A valid session has its `last_active_at` updated; expiry is not extended
+    /// (clients call `refresh_session` to get a new token before it runs out).
+    pub fn check_session(&self, token: &str) -> SqliteResult<SessionCheck> {
         let conn = self.conn();
         let now = Utc::now();
-        let now_str = now.to_rfc3339();
 
         let mut stmt = conn.prepare(
-            "SELECT id, token, created_at, expires_at FROM auth_sessions WHERE token = ?1 AND expires_at > ?2",
+            "SELECT id, token, created_at, expires_at, last_active_at FROM auth_sessions WHERE token = ?1",
         )?;
 
         let session = stmt
-            .query_row([token, &now_str], |row| {
+            .query_row([token], |row| {
                 let created_at_str: String = row.get(2)?;
                 let expires_at_str: String = row.get(3)?;
+                let last_active_at_str: Option<String> = row.get(4)?;
 
                 Ok(Session {
                     id: row.get(0)?,
@@ -72,20 +77,57 @@ impl Database {
                     expires_at: DateTime::parse_from_rfc3339(&expires_at_str)
                         .unwrap()
                         .with_timezone(&Utc),
+                    last_active_at: last_active_at_str.and_then(|s| {
+                        DateTime::parse_from_rfc3339(&s)
+                            .ok()
+                            .map(|dt| dt.with_timezone(&Utc))
+                    }),
                 })
             })
             .ok();
 
-        // Extend session expiry on successful validation (keep active sessions alive)
-        if session.is_some() {
-            let new_expires = (now + Duration::hours(24)).to_rfc3339();
-            let _ = conn.execute(
-                "UPDATE auth_sessions SET expires_at = ?1 WHERE token = ?2",
-                [&new_expires, token],
-            );
+        let Some(mut session) = session else {
+            return Ok(SessionCheck::Invalid);
+        };
+
+        if session.expires_at <= now {
+            return Ok(SessionCheck::Expired);
         }
 
-        Ok(session)
+        let _ = conn.execute(
+            "UPDATE auth_sessions SET last_active_at = ?1 WHERE token = ?2",
+            [&now.to_rfc3339(), token],
+        );
+        session.last_active_at = Some(now);
+
+        Ok(SessionCheck::Valid(session))
+    }
+
+    pub fn validate_session(&self, token: &str) -> SqliteResult<Option<Session>> {
+        match self.check_session(token)? {
+            SessionCheck::Valid(session) => Ok(Some(session)),
+            SessionCheck::Expired | SessionCheck::Invalid => Ok(None),
+        }
+    }
+
+    /// Re-issue a session: a valid, non-expired token is exchanged for a new token
+    /// with a fresh TTL. The old token is revoked.
+    pub fn refresh_session(&self, token: &str) -> SqliteResult<SessionCheck> {
+        let old = match self.check_session(token)? {
+            SessionCheck::Valid(session) => session,
+            other => return Ok(other),
+        };
+
+        let public_address: Option<String> = self.conn().query_row(
+            "SELECT public_address FROM auth_sessions WHERE id = ?1",
+            [old.id],
+            |row| row.get(0),
+        )?;
+
+        let new_session = self.create_session_for_address(public_address.as_deref())?;
+        self.delete_session(token)?;
+
+        Ok(SessionCheck::Valid(new_session))
     }
 
     pub fn delete_session(&self, token: &str) -> SqliteResult<bool> {
@@ -94,6 +136,14 @@ impl Database {
         Ok(rows_affected > 0)
     }
 
+    /// Delete sessions that expired more than `grace_hours` ago. Recently expired
+    /// sessions are kept so requests with them can still be told apart from bad tokens.
+    pub fn delete_expired_sessions(&self, grace_hours: i64) -> SqliteResult<usize> {
+        let conn = self.conn();
+        let cutoff = (Utc::now() - Duration::hours(grace_hours)).to_rfc3339();
+        conn.execute("DELETE FROM auth_sessions WHERE expires_at < ?1", [&cutoff])
+    }
+
     // ============================================
     // Auth Challenge methods (for SIWE)
     // ============================================
@@ -148,3 +198,38 @@ impl Database {
         Ok(rows_affected > 0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_expiry_and_refresh() {
+        let db = Database::new(":memory:").expect("in-memory db");
+        let session = db.create_session_for_address(Some("0xabc")).unwrap();
+        assert!(matches!(db.check_session(&session.token).unwrap(), SessionCheck::Valid(_)));
+        assert!(matches!(db.check_session("nope").unwrap(), SessionCheck::Invalid));
+
+        // Refresh swaps the token and revokes the old one
+        let refreshed = match db.refresh_session(&session.token).unwrap() {
+            SessionCheck::Valid(s) => s,
+            other => panic!("expected refreshed session, got {:?}", other),
+        };
+        assert_ne!(refreshed.token, session.token);
+        assert!(db.validate_session(&session.token).unwrap().is_none());
+        assert!(db.validate_session(&refreshed.token).unwrap().is_some());
+
+        // Expired sessions are reported as such and cannot be refreshed
+        db.conn()
+            .execute(
+                "UPDATE auth_sessions SET expires_at = ?1 WHERE token = ?2",
+                [&(Utc::now() - Duration::minutes(1)).to_rfc3339(), &refreshed.token],
+            )
+            .unwrap();
+        assert!(matches!(db.check_session(&refreshed.token).unwrap(), SessionCheck::Expired));
+        assert!(matches!(db.refresh_session(&refreshed.token).unwrap(), SessionCheck::Expired));
+
+        assert_eq!(db.delete_expired_sessions(0).unwrap(), 1);
+        assert!(matches!(db.check_session(&refreshed.token).unwrap(), SessionCheck::Invalid));
+    }
+}
@@ -4,7 +4,7 @@ use chrono::{DateTime, Utc};
 use rusqlite::Result as SqliteResult;
 use std::collections::HashMap;
 
-use crate::models::{BotSettings, DEFAULT_MAX_TOOL_ITERATIONS, DEFAULT_SAFE_MODE_MAX_QUERIES_PER_10MIN};
+use crate::models::{BotSettings, DEFAULT_COMMAND_PREFIX, DEFAULT_GAS_STRATEGY, DEFAULT_GATEWAY_PREVIOUS_MESSAGES, MAX_GATEWAY_PREVIOUS_MESSAGES, DEFAULT_MEMORY_FTS_TOKENIZER, DEFAULT_MAX_TOOL_ITERATIONS, DEFAULT_TX_CONFIRMATION_TIMEOUT_SECS, MAX_TX_CONFIRMATION_TIMEOUT_SECS, DEFAULT_SAFE_MODE_MAX_QUERIES_PER_10MIN};
 use super::super::Database;
 
 impl Database {
@@ -17,7 +17,7 @@ impl Database {
         let conn = self.conn();
 
         let result = conn.query_row(
-            "SELECT id, bot_name, bot_email, web3_tx_requires_confirmation, rpc_provider, custom_rpc_endpoints, max_tool_iterations, rogue_mode_enabled, safe_mode_max_queries_per_10min, keystore_url, chat_session_memory_generation, guest_dashboard_enabled, theme_accent, proxy_url, kanban_auto_execute, created_at, updated_at, coalescing_enabled, coalescing_debounce_ms, coalescing_max_wait_ms, compaction_background_threshold, compaction_aggressive_threshold, compaction_emergency_threshold, whisper_server_url, embeddings_server_url FROM bot_settings LIMIT 1",
+            "SELECT id, bot_name, bot_email, web3_tx_requires_confirmation, rpc_provider, custom_rpc_endpoints, max_tool_iterations, rogue_mode_enabled, safe_mode_max_queries_per_10min, keystore_url, chat_session_memory_generation, guest_dashboard_enabled, theme_accent, proxy_url, kanban_auto_execute, created_at, updated_at, coalescing_enabled, coalescing_debounce_ms, coalescing_max_wait_ms, compaction_background_threshold, compaction_aggressive_threshold, compaction_emergency_threshold, whisper_server_url, embeddings_server_url, gas_strategy, gas_max_fee_gwei, command_prefix, command_aliases, gateway_previous_messages, memory_fts_tokenizer, signing_frozen, tx_confirmation_timeout_secs FROM bot_settings LIMIT 1",
             [],
             |row| {
                 let web3_tx_confirmation: i64 = row.get(3)?;
@@ -42,9 +42,26 @@ impl Database {
                 let compaction_emergency_threshold: f64 = row.get::<_, Option<f64>>(22)?.unwrap_or(0.95);
                 let whisper_server_url: Option<String> = row.get(23)?;
                 let embeddings_server_url: Option<String> = row.get(24)?;
+                let gas_strategy: String = row.get::<_, Option<String>>(25)?.unwrap_or_else(|| DEFAULT_GAS_STRATEGY.to_string());
+                let gas_max_fee_gwei: Option<f64> = row.get(26)?;
+                let command_prefix: String = row.get::<_, Option<String>>(27)?
+                    .filter(|p| !p.is_empty())
+                    .unwrap_or_else(|| DEFAULT_COMMAND_PREFIX.to_string());
+                let command_aliases_json: Option<String> = row.get(28)?;
+                let gateway_previous_messages: i32 = row.get::<_, Option<i32>>(29)?
+                    .unwrap_or(DEFAULT_GATEWAY_PREVIOUS_MESSAGES)
+                    .clamp(0, MAX_GATEWAY_PREVIOUS_MESSAGES);
+                let memory_fts_tokenizer: String = row.get::<_, Option<String>>(30)?
+                    .unwrap_or_else(|| DEFAULT_MEMORY_FTS_TOKENIZER.to_string());
+                let signing_frozen: i64 = row.get::<_, Option<i64>>(31)?.unwrap_or(0);
+                let tx_confirmation_timeout_secs: i32 = row.get::<_, Option<i32>>(32)?
+                    .unwrap_or(DEFAULT_TX_CONFIRMATION_TIMEOUT_SECS)
+                    .clamp(0, MAX_TX_CONFIRMATION_TIMEOUT_SECS);
 
                 let custom_rpc_endpoints: Option<HashMap<String, String>> = custom_rpc_endpoints_json
                     .and_then(|json| serde_json::from_str(&json).ok());
+                let command_aliases: Option<HashMap<String, String>> = command_aliases_json
+                    .and_then(|json| serde_json::from_str(&json).ok());
 
                 Ok(BotSettings {
                     id: row.get(0)?,
@@ -70,6 +87,14 @@ impl Database {
                     compaction_background_threshold,
                     compaction_aggressive_threshold,
                     compaction_emergency_threshold,
+                    gas_strategy,
+                    gas_max_fee_gwei,
+                    command_prefix,
+                    command_aliases,
+                    gateway_previous_messages,
+                    memory_fts_tokenizer,
+                    signing_frozen: signing_frozen != 0,
+                    tx_confirmation_timeout_secs,
                     created_at: DateTime::parse_from_rfc3339(&created_at_str)
                         .unwrap()
                         .with_timezone(&Utc),
@@ -95,7 +120,7 @@ impl Database {
         bot_email: Option<&str>,
         web3_tx_requires_confirmation: Option<bool>,
     ) -> SqliteResult<BotSettings> {
-        self.update_bot_settings_full(bot_name, bot_email, web3_tx_requires_confirmation, None, None, None, None, None, None, None, None, None, None, None, None, None)
+        self.update_bot_settings_full(bot_name, bot_email, web3_tx_requires_confirmation, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None, None)
     }
 
     /// Update bot settings with all fields including RPC config and keystore URL
@@ -117,6 +142,13 @@ impl Database {
         kanban_auto_execute: Option<bool>,
         whisper_server_url: Option<&str>,
         embeddings_server_url: Option<&str>,
+        gas_strategy: Option<&str>,
+        gas_max_fee_gwei: Option<f64>,
+        command_prefix: Option<&str>,
+        command_aliases: Option<&HashMap<String, String>>,
+        gateway_previous_messages: Option<i32>,
+        memory_fts_tokenizer: Option<&str>,
+        tx_confirmation_timeout_secs: Option<i32>,
     ) -> SqliteResult<BotSettings> {
         let conn = self.conn();
         let now = Utc::now().to_rfc3339();
@@ -234,6 +266,58 @@ impl Database {
                     rusqlite::params![url_value, &now],
                 )?;
             }
+            if let Some(strategy) = gas_strategy {
+                conn.execute(
+                    "UPDATE bot_settings SET gas_strategy = ?1, updated_at = ?2",
+                    [strategy, &now],
+                )?;
+            }
+            if let Some(max_fee) = gas_max_fee_gwei {
+                // Zero or negative means remove the ceiling (NULL)
+                let max_fee_value: Option<f64> = if max_fee > 0.0 { Some(max_fee) } else { None };
+                conn.execute(
+                    "UPDATE bot_settings SET gas_max_fee_gwei = ?1, updated_at = ?2",
+                    rusqlite::params![max_fee_value, &now],
+                )?;
+            }
+            if let Some(prefix) = command_prefix {
+                // Empty string means reset to the default prefix
+                let prefix_value = if prefix.is_empty() { DEFAULT_COMMAND_PREFIX } else { prefix };
+                conn.execute(
+                    "UPDATE bot_settings SET command_prefix = ?1, updated_at = ?2",
+                    [prefix_value, &now],
+                )?;
+            }
+            if let Some(aliases) = command_aliases {
+                // Empty map means no aliases (NULL)
+                let aliases_json: Option<String> = if aliases.is_empty() {
+                    None
+                } else {
+                    Some(serde_json::to_string(aliases).unwrap_or_else(|_| "{}".to_string()))
+                };
+                conn.execute(
+                    "UPDATE bot_settings SET command_aliases = ?1, updated_at = ?2",
+                    rusqlite::params![aliases_json, &now],
+                )?;
+            }
+            if let Some(count) = gateway_previous_messages {
+                conn.execute(
+                    "UPDATE bot_settings SET gateway_previous_messages = ?1, updated_at = ?2",
+                    rusqlite::params![count.clamp(0, MAX_GATEWAY_PREVIOUS_MESSAGES), &now],
+                )?;
+            }
+            if let Some(tokenizer) = memory_fts_tokenizer {
+                conn.execute(
+                    "UPDATE bot_settings SET memory_fts_tokenizer = ?1, updated_at = ?2",
+                    [tokenizer, &now],
+                )?;
+            }
+            if let Some(secs) = tx_confirmation_timeout_secs {
+                conn.execute(
+                    "UPDATE bot_settings SET tx_confirmation_timeout_secs = ?1, updated_at = ?2",
+                    rusqlite::params![secs.clamp(0, MAX_TX_CONFIRMATION_TIMEOUT_SECS), &now],
+                )?;
+            }
         } else {
             // Insert new
             let name = bot_name.unwrap_or("StarkBot");
@@ -254,9 +338,22 @@ impl Database {
             let kanban_auto = kanban_auto_execute.unwrap_or(true);
             let whisper_url_value: Option<&str> = whisper_server_url.filter(|u| !u.is_empty());
             let embeddings_url_value: Option<&str> = embeddings_server_url.filter(|u| !u.is_empty());
+            let gas_strategy_value = gas_strategy.unwrap_or(DEFAULT_GAS_STRATEGY);
+            let gas_max_fee_value: Option<f64> = gas_max_fee_gwei.filter(|f| *f > 0.0);
+            let command_prefix_value = command_prefix.filter(|p| !p.is_empty()).unwrap_or(DEFAULT_COMMAND_PREFIX);
+            let command_aliases_json = command_aliases
+                .filter(|a| !a.is_empty())
+                .map(|a| serde_json::to_string(a).unwrap_or_else(|_| "{}".to_string()));
+            let gateway_previous_value = gateway_previous_messages
+                .unwrap_or(DEFAULT_GATEWAY_PREVIOUS_MESSAGES)
+                .clamp(0, MAX_GATEWAY_PREVIOUS_MESSAGES);
+            let memory_fts_tokenizer_value = memory_fts_tokenizer.unwrap_or(DEFAULT_MEMORY_FTS_TOKENIZER);
+            let tx_confirmation_timeout_value = tx_confirmation_timeout_secs
+                .unwrap_or(DEFAULT_TX_CONFIRMATION_TIMEOUT_SECS)
+                .clamp(0, MAX_TX_CONFIRMATION_TIMEOUT_SECS);
             conn.execute(
-                "INSERT INTO bot_settings (bot_name, bot_email, web3_tx_requires_confirmation, rpc_provider, custom_rpc_endpoints, max_tool_iterations, rogue_mode_enabled, safe_mode_max_queries_per_10min, keystore_url, chat_session_memory_generation, guest_dashboard_enabled, theme_accent, proxy_url, kanban_auto_execute, whisper_server_url, embeddings_server_url, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
-                rusqlite::params![name, email, if confirmation { 1 } else { 0 }, provider, endpoints_json, max_iterations, if rogue_mode { 1 } else { 0 }, safe_mode_queries, keystore_url_value, if session_memory { 1 } else { 0 }, if guest_dashboard { 1 } else { 0 }, theme_accent_value, proxy_url_value, if kanban_auto { 1 } else { 0 }, whisper_url_value, embeddings_url_value, &now, &now],
+                "INSERT INTO bot_settings (bot_name, bot_email, web3_tx_requires_confirmation, rpc_provider, custom_rpc_endpoints, max_tool_iterations, rogue_mode_enabled, safe_mode_max_queries_per_10min, keystore_url, chat_session_memory_generation, guest_dashboard_enabled, theme_accent, proxy_url, kanban_auto_execute, whisper_server_url, embeddings_server_url, gas_strategy, gas_max_fee_gwei, command_prefix, command_aliases, gateway_previous_messages, memory_fts_tokenizer, tx_confirmation_timeout_secs, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25)",
+                rusqlite::params![name, email, if confirmation { 1 } else { 0 }, provider, endpoints_json, max_iterations, if rogue_mode { 1 } else { 0 }, safe_mode_queries, keystore_url_value, if session_memory { 1 } else { 0 }, if guest_dashboard { 1 } else { 0 }, theme_accent_value, proxy_url_value, if kanban_auto { 1 } else { 0 }, whisper_url_value, embeddings_url_value, gas_strategy_value, gas_max_fee_value, command_prefix_value, command_aliases_json, gateway_previous_value, memory_fts_tokenizer_value, tx_confirmation_timeout_value, &now, &now],
             )?;
         }
 
@@ -264,4 +361,36 @@ impl Database {
         self.cache.invalidate_bot_settings();
         self.get_bot_settings()
     }
+
+    /// Persist the signing freeze flag (the runtime flag lives in `wallet::signing_freeze`)
+    pub fn set_signing_frozen(&self, frozen: bool) -> SqliteResult<BotSettings> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE bot_settings SET signing_frozen = ?1, updated_at = ?2",
+            rusqlite::params![if frozen { 1 } else { 0 }, Utc::now().to_rfc3339()],
+        )?;
+        drop(conn);
+        self.cache.invalidate_bot_settings();
+        self.get_bot_settings()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::Database;
+
+    #[test]
+    fn test_signing_frozen_persists() {
+        let db = Database::new(":memory:").expect("in-memory db");
+        assert!(!db.get_bot_settings().unwrap().signing_frozen);
+
+        assert!(db.set_signing_frozen(true).unwrap().signing_frozen);
+        assert!(db.get_bot_settings().unwrap().signing_frozen);
+
+        // Other settings updates leave the freeze alone
+        db.update_bot_settings(Some("Renamed"), None, None).unwrap();
+        assert!(db.get_bot_settings().unwrap().signing_frozen);
+
+        assert!(!db.set_signing_frozen(false).unwrap().signing_frozen);
+    }
 }
@@ -2,7 +2,7 @@
 //!
 //! Persistent history of all crypto transaction broadcasts.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use rusqlite::Result as SqliteResult;
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +18,10 @@ pub enum BroadcastedTxStatus {
     Confirmed,
     /// Broadcast or confirmation failed
     Failed,
+    /// Not confirmed within the stuck timeout
+    Stuck,
+    /// Superseded by a gas-bumped replacement with the same nonce
+    Replaced,
 }
 
 impl std::fmt::Display for BroadcastedTxStatus {
@@ -26,6 +30,8 @@ impl std::fmt::Display for BroadcastedTxStatus {
             BroadcastedTxStatus::Broadcast => write!(f, "broadcast"),
             BroadcastedTxStatus::Confirmed => write!(f, "confirmed"),
             BroadcastedTxStatus::Failed => write!(f, "failed"),
+            BroadcastedTxStatus::Stuck => write!(f, "stuck"),
+            BroadcastedTxStatus::Replaced => write!(f, "replaced"),
         }
     }
 }
@@ -38,6 +44,8 @@ impl std::str::FromStr for BroadcastedTxStatus {
             "broadcast" => Ok(BroadcastedTxStatus::Broadcast),
             "confirmed" => Ok(BroadcastedTxStatus::Confirmed),
             "failed" => Ok(BroadcastedTxStatus::Failed),
+            "stuck" => Ok(BroadcastedTxStatus::Stuck),
+            "replaced" => Ok(BroadcastedTxStatus::Replaced),
             _ => Err(format!("Unknown status: {}", s)),
         }
     }
@@ -92,6 +100,25 @@ pub struct BroadcastedTransaction {
     pub broadcast_at: DateTime<Utc>,
     pub confirmed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
+    pub nonce: Option<i64>,
+    /// UUID of the replacement transaction (when status is replaced)
+    pub replaced_by: Option<String>,
+}
+
+/// Statuses that are final and safe to prune. Broadcast and stuck transactions
+/// are still in flight and are never pruned, regardless of age.
+const PRUNABLE_STATUSES: &str = "'confirmed', 'failed', 'replaced'";
+
+/// Size of the broadcast history and what the retention policy has removed
+#[derive(Debug, Clone, Serialize)]
+pub struct BroadcastHistoryStats {
+    /// Records currently stored
+    pub total: i64,
+    /// Stored records that are still unconfirmed (broadcast or stuck)
+    pub in_flight: i64,
+    /// Records removed by the retention policy since the table was created
+    pub pruned_count: i64,
+    pub last_pruned_at: Option<DateTime<Utc>>,
 }
 
 /// Data needed to record a new broadcast
@@ -105,6 +132,7 @@ pub struct RecordBroadcastRequest {
     pub tx_hash: Option<String>,
     pub explorer_url: Option<String>,
     pub broadcast_mode: BroadcastMode,
+    pub nonce: Option<i64>,
 }
 
 impl Database {
@@ -116,8 +144,8 @@ impl Database {
         conn.execute(
             "INSERT INTO broadcasted_transactions
              (uuid, network, from_address, to_address, value, value_formatted,
-              tx_hash, explorer_url, status, broadcast_mode, broadcast_at, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'broadcast', ?9, ?10, ?10)",
+              tx_hash, explorer_url, status, broadcast_mode, broadcast_at, created_at, nonce)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, 'broadcast', ?9, ?10, ?10, ?11)",
             rusqlite::params![
                 req.uuid,
                 req.network,
@@ -129,6 +157,7 @@ impl Database {
                 req.explorer_url,
                 req.broadcast_mode.to_string(),
                 now,
+                req.nonce,
             ],
         )?;
 
@@ -160,6 +189,16 @@ impl Database {
         Ok(rows > 0)
     }
 
+    /// Mark a broadcasted transaction as replaced by a gas-bumped copy
+    pub fn mark_broadcast_replaced(&self, uuid: &str, replaced_by: &str) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows = conn.execute(
+            "UPDATE broadcasted_transactions SET status = 'replaced', replaced_by = ?1 WHERE uuid = ?2",
+            rusqlite::params![replaced_by, uuid],
+        )?;
+        Ok(rows > 0)
+    }
+
     /// List broadcasted transactions with optional filters
     pub fn list_broadcasted_transactions(
         &self,
@@ -173,7 +212,7 @@ impl Database {
         let mut sql = String::from(
             "SELECT id, uuid, network, from_address, to_address, value, value_formatted,
                     tx_hash, explorer_url, status, broadcast_mode, error,
-                    broadcast_at, confirmed_at, created_at
+                    broadcast_at, confirmed_at, created_at, nonce, replaced_by
              FROM broadcasted_transactions WHERE 1=1",
         );
 
@@ -234,12 +273,102 @@ impl Database {
                 created_at: DateTime::parse_from_rfc3339(&created_at_str)
                     .unwrap()
                     .with_timezone(&Utc),
+                nonce: row.get(15)?,
+                replaced_by: row.get(16)?,
             })
         })?;
 
         Ok(rows.filter_map(|r| r.ok()).collect())
     }
 
+    /// Prune finalized broadcast records by age and/or row count.
+    /// In-flight records (broadcast, stuck) are never pruned. Returns the number deleted.
+    pub fn prune_broadcast_history(
+        &self,
+        retention_days: Option<i64>,
+        max_rows: Option<usize>,
+    ) -> SqliteResult<usize> {
+        let conn = self.conn();
+        let tx = conn.unchecked_transaction()?;
+        let mut deleted = 0;
+
+        if let Some(days) = retention_days {
+            let cutoff = (Utc::now() - Duration::days(days)).to_rfc3339();
+            deleted += tx.execute(
+                &format!(
+                    "DELETE FROM broadcasted_transactions
+                     WHERE status IN ({}) AND broadcast_at < ?1",
+                    PRUNABLE_STATUSES
+                ),
+                [&cutoff],
+            )?;
+        }
+
+        if let Some(max) = max_rows {
+            let total: i64 =
+                tx.query_row("SELECT COUNT(*) FROM broadcasted_transactions", [], |r| r.get(0))?;
+            let excess = total - max as i64;
+            if excess > 0 {
+                deleted += tx.execute(
+                    &format!(
+                        "DELETE FROM broadcasted_transactions WHERE id IN (
+                             SELECT id FROM broadcasted_transactions
+                             WHERE status IN ({})
+                             ORDER BY broadcast_at ASC, id ASC LIMIT ?1
+                         )",
+                        PRUNABLE_STATUSES
+                    ),
+                    [excess],
+                )?;
+            }
+        }
+
+        if deleted > 0 {
+            tx.execute(
+                "INSERT INTO broadcast_history_pruned (id, pruned_count, last_pruned_at)
+                 VALUES (1, ?1, ?2)
+                 ON CONFLICT(id) DO UPDATE SET
+                     pruned_count = pruned_count + excluded.pruned_count,
+                     last_pruned_at = excluded.last_pruned_at",
+                rusqlite::params![deleted as i64, Utc::now().to_rfc3339()],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(deleted)
+    }
+
+    /// Current broadcast history size and pruning summary
+    pub fn broadcast_history_stats(&self) -> SqliteResult<BroadcastHistoryStats> {
+        let conn = self.conn();
+
+        let (total, in_flight): (i64, i64) = conn.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(status IN ('broadcast', 'stuck')), 0)
+             FROM broadcasted_transactions",
+            [],
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )?;
+
+        let (pruned_count, last_pruned_at): (i64, Option<String>) = conn
+            .query_row(
+                "SELECT pruned_count, last_pruned_at FROM broadcast_history_pruned WHERE id = 1",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap_or((0, None));
+
+        Ok(BroadcastHistoryStats {
+            total,
+            in_flight,
+            pruned_count,
+            last_pruned_at: last_pruned_at.and_then(|s| {
+                DateTime::parse_from_rfc3339(&s)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&Utc))
+            }),
+        })
+    }
+
     /// Get a single broadcasted transaction by UUID
     pub fn get_broadcasted_transaction(&self, uuid: &str) -> SqliteResult<Option<BroadcastedTransaction>> {
         let txs = self.list_broadcasted_transactions(None, None, None, Some(1))?;
@@ -248,7 +377,7 @@ impl Database {
         let mut stmt = conn.prepare(
             "SELECT id, uuid, network, from_address, to_address, value, value_formatted,
                     tx_hash, explorer_url, status, broadcast_mode, error,
-                    broadcast_at, confirmed_at, created_at
+                    broadcast_at, confirmed_at, created_at, nonce, replaced_by
              FROM broadcasted_transactions WHERE uuid = ?1",
         )?;
 
@@ -284,6 +413,8 @@ impl Database {
                     created_at: DateTime::parse_from_rfc3339(&created_at_str)
                         .unwrap()
                         .with_timezone(&Utc),
+                    nonce: row.get(15)?,
+                    replaced_by: row.get(16)?,
                 })
             })
             .ok();
@@ -292,3 +423,62 @@ impl Database {
         Ok(tx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    fn record(db: &Database, uuid: &str, status: Option<BroadcastedTxStatus>) {
+        db.record_broadcast(RecordBroadcastRequest {
+            uuid: uuid.to_string(),
+            network: "base".to_string(),
+            from_address: "0xfrom".to_string(),
+            to_address: "0xto".to_string(),
+            value: "0".to_string(),
+            value_formatted: "0 ETH".to_string(),
+            tx_hash: None,
+            explorer_url: None,
+            broadcast_mode: BroadcastMode::Partner,
+            nonce: Some(0),
+        })
+        .unwrap();
+        if let Some(status) = status {
+            db.update_broadcast_status(uuid, status, None).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_prune_keeps_in_flight() {
+        let db = Database::new(":memory:").expect("in-memory db");
+        record(&db, "a", Some(BroadcastedTxStatus::Confirmed));
+        record(&db, "b", Some(BroadcastedTxStatus::Failed));
+        record(&db, "c", None);
+        record(&db, "d", Some(BroadcastedTxStatus::Stuck));
+
+        // Everything is older than a negative retention window, but only finalized rows go
+        assert_eq!(db.prune_broadcast_history(Some(-1), None).unwrap(), 2);
+
+        let stats = db.broadcast_history_stats().unwrap();
+        assert_eq!(stats.total, 2);
+        assert_eq!(stats.in_flight, 2);
+        assert_eq!(stats.pruned_count, 2);
+        assert!(stats.last_pruned_at.is_some());
+    }
+
+    #[test]
+    fn test_prune_max_rows() {
+        let db = Database::new(":memory:").expect("in-memory db");
+        record(&db, "a", Some(BroadcastedTxStatus::Confirmed));
+        record(&db, "b", None);
+        record(&db, "c", Some(BroadcastedTxStatus::Confirmed));
+
+        assert_eq!(db.prune_broadcast_history(None, Some(2)).unwrap(), 1);
+        assert!(db.get_broadcasted_transaction("a").unwrap().is_none());
+        assert!(db.get_broadcasted_transaction("c").unwrap().is_some());
+
+        // Cannot go below the in-flight count
+        assert_eq!(db.prune_broadcast_history(None, Some(0)).unwrap(), 1);
+        assert_eq!(db.broadcast_history_stats().unwrap().total, 1);
+    }
+}
@@ -0,0 +1,146 @@
+//! Channel message log - inbound normalized messages kept for moderation exports
+//!
+//! Independent of the session system. Stores every message the dispatcher
+//! receives from a platform channel, keyed by channel. Channels flagged
+//! private (`private_message_history` setting) are logged without content.
+
+use crate::channels::NormalizedMessage;
+use crate::db::Database;
+use crate::models::ChannelSettingKey;
+use rusqlite::Result as SqliteResult;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelMessage {
+    pub id: i64,
+    pub channel_id: i64,
+    pub chat_id: String,
+    pub chat_name: Option<String>,
+    pub user_id: String,
+    pub user_name: String,
+    /// Message text, `None` for channels flagged private
+    pub content: Option<String>,
+    pub platform_message_id: Option<String>,
+    pub created_at: String,
+}
+
+impl Database {
+    /// Whether a channel's message history is private (content is never logged or exported)
+    pub fn is_channel_history_private(&self, channel_id: i64) -> bool {
+        self.get_channel_setting(channel_id, ChannelSettingKey::PrivateMessageHistory.as_ref())
+            .ok()
+            .flatten()
+            .map(|v| v == "true")
+            .unwrap_or(false)
+    }
+
+    /// Log an inbound message, dropping its content if the channel is private
+    pub fn store_channel_message(&self, message: &NormalizedMessage) -> SqliteResult<()> {
+        let content = if self.is_channel_history_private(message.channel_id) {
+            None
+        } else {
+            Some(message.text.as_str())
+        };
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO channel_messages
+             (channel_id, chat_id, chat_name, user_id, user_name, content, platform_message_id, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, datetime('now'))",
+            rusqlite::params![
+                message.channel_id,
+                message.chat_id,
+                message.chat_name,
+                message.user_id,
+                message.user_name,
+                content,
+                message.message_id,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// List a channel's logged messages in `[from, to)`, oldest first.
+    /// Bounds use SQLite's `YYYY-MM-DD HH:MM:SS` format; `None` leaves that side open.
+    pub fn list_channel_messages(
+        &self,
+        channel_id: i64,
+        from: Option<&str>,
+        to: Option<&str>,
+        limit: usize,
+    ) -> SqliteResult<Vec<ChannelMessage>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, channel_id, chat_id, chat_name, user_id, user_name, content,
+                    platform_message_id, created_at
+             FROM channel_messages
+             WHERE channel_id = ?1
+               AND (?2 IS NULL OR created_at >= ?2)
+               AND (?3 IS NULL OR created_at < ?3)
+             ORDER BY created_at ASC, id ASC
+             LIMIT ?4",
+        )?;
+
+        let rows = stmt.query_map(rusqlite::params![channel_id, from, to, limit as i64], |row| {
+            Ok(ChannelMessage {
+                id: row.get(0)?,
+                channel_id: row.get(1)?,
+                chat_id: row.get(2)?,
+                chat_name: row.get(3)?,
+                user_id: row.get(4)?,
+                user_name: row.get(5)?,
+                content: row.get(6)?,
+                platform_message_id: row.get(7)?,
+                created_at: row.get(8)?,
+            })
+        })?;
+
+        rows.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::channels::NormalizedMessage;
+    use crate::db::Database;
+    use crate::models::ChannelSettingKey;
+
+    fn message(channel_id: i64, text: &str) -> NormalizedMessage {
+        serde_json::from_value(serde_json::json!({
+            "channel_id": channel_id,
+            "channel_type": "discord",
+            "chat_id": "general",
+            "user_id": "42",
+            "user_name": "alice",
+            "text": text,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_store_and_export_channel_messages() {
+        let db = Database::new(":memory:").expect("in-memory db");
+        let public = db.create_channel("discord", "public", "", None).unwrap();
+        let private = db.create_channel("discord", "private", "", None).unwrap();
+        db.set_channel_setting(private.id, ChannelSettingKey::PrivateMessageHistory.as_ref(), "true").unwrap();
+
+        db.store_channel_message(&message(public.id, "gm")).unwrap();
+        db.store_channel_message(&message(public.id, "wen token")).unwrap();
+        db.store_channel_message(&message(private.id, "secret")).unwrap();
+
+        let messages = db.list_channel_messages(public.id, None, None, 10).unwrap();
+        let contents: Vec<_> = messages.iter().map(|m| m.content.as_deref()).collect();
+        assert_eq!(contents, vec![Some("gm"), Some("wen token")]);
+        assert_eq!(messages[0].user_name, "alice");
+        assert_eq!(db.list_channel_messages(public.id, None, None, 1).unwrap().len(), 1);
+
+        // Private channels keep who/when but never the text
+        let private_messages = db.list_channel_messages(private.id, None, None, 10).unwrap();
+        assert_eq!(private_messages.len(), 1);
+        assert!(private_messages[0].content.is_none());
+
+        // Date range bounds
+        assert!(db.list_channel_messages(public.id, Some("2999-01-01 00:00:00"), None, 10).unwrap().is_empty());
+        assert!(db.list_channel_messages(public.id, None, Some("2000-01-01 00:00:00"), 10).unwrap().is_empty());
+        assert_eq!(db.list_channel_messages(public.id, Some("2000-01-01 00:00:00"), Some("2999-01-01 00:00:00"), 10).unwrap().len(), 2);
+    }
+}
@@ -785,6 +785,27 @@ impl Database {
         Ok(())
     }
 
+    /// Get a session's generated title and the message count it was last checked at
+    pub fn get_session_title(&self, session_id: i64) -> SqliteResult<Option<(String, i64)>> {
+        let conn = self.conn();
+        let title: Option<(Option<String>, i64)> = conn.query_row(
+            "SELECT title, title_message_count FROM chat_sessions WHERE id = ?1",
+            [session_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).ok();
+        Ok(title.and_then(|(title, count)| title.map(|t| (t, count))))
+    }
+
+    /// Set a session's title (None keeps the current one) and the message count it reflects
+    pub fn set_session_title(&self, session_id: i64, title: Option<&str>, message_count: i64) -> SqliteResult<()> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE chat_sessions SET title = COALESCE(?1, title), title_message_count = ?2 WHERE id = ?3",
+            rusqlite::params![title, message_count, session_id],
+        )?;
+        Ok(())
+    }
+
     /// Update the last_flush_at timestamp for a session (Phase 1: pre-compaction flush)
     pub fn update_session_last_flush(&self, session_id: i64) -> SqliteResult<()> {
         let conn = self.conn();
@@ -921,6 +942,16 @@ impl Database {
         Ok(count)
     }
 
+    /// Number of chat sessions per completion status
+    pub fn count_sessions_by_status(&self) -> SqliteResult<Vec<(String, i64)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT completion_status, COUNT(*) FROM chat_sessions GROUP BY completion_status ORDER BY completion_status",
+        )?;
+        let counts = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        counts.collect()
+    }
+
     /// Delete the oldest chat sessions when total count exceeds `max_sessions`.
     ///
     /// Deletes inactive sessions (not currently active) ordered by last_activity_at ASC,
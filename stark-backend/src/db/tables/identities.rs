@@ -374,7 +374,7 @@ impl Database {
             .collect();
 
         let query = format!(
-            "SELECT id, channel_id, tool_name, parameters, success, result, duration_ms, executed_at
+            "SELECT id, channel_id, tool_name, parameters, success, result, duration_ms, executed_at, structured_result
              FROM tool_executions
              WHERE session_id IN ({})
              ORDER BY executed_at DESC
@@ -401,6 +401,8 @@ impl Database {
                     parameters: serde_json::from_str(&params_str).unwrap_or_default(),
                     success: row.get::<_, i32>(4)? != 0,
                     result: row.get(5)?,
+                    structured_result: row.get::<_, Option<String>>(8)?
+                        .and_then(|s| serde_json::from_str(&s).ok()),
                     duration_ms: row.get(6)?,
                     executed_at: row.get(7)?,
                 })
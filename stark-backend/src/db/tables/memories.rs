@@ -1,19 +1,24 @@
 //! Database operations for the `memories` table
 //! Core CRUD for the structured memory system (SQL-backed).
 
+use rusqlite::OptionalExtension;
+use std::collections::HashSet;
+
 use crate::db::Database;
+use crate::memory::fts_utils::FtsTokenizer;
+use crate::memory::{SAFE_MODE_IDENTITY, SHARED_MEMORY_IDENTITY};
 
 /// Standard SELECT columns for memory queries
 const MEMORY_SELECT_COLS: &str =
     "id, memory_type, content, category, tags, importance,
      identity_id, session_id, entity_type, entity_name,
-     source_type, log_date, created_at, updated_at, last_accessed, agent_subtype";
+     source_type, log_date, created_at, updated_at, last_accessed, agent_subtype, access_count";
 
 /// Table-qualified SELECT columns for JOIN queries (avoids ambiguous column names with FTS)
 const MEMORY_SELECT_COLS_QUALIFIED: &str =
     "memories.id, memories.memory_type, memories.content, memories.category, memories.tags, memories.importance,
      memories.identity_id, memories.session_id, memories.entity_type, memories.entity_name,
-     memories.source_type, memories.log_date, memories.created_at, memories.updated_at, memories.last_accessed, memories.agent_subtype";
+     memories.source_type, memories.log_date, memories.created_at, memories.updated_at, memories.last_accessed, memories.agent_subtype, memories.access_count";
 
 /// A row from the `memories` table
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -34,6 +39,9 @@ pub struct MemoryRow {
     pub updated_at: String,
     pub last_accessed: Option<String>,
     pub agent_subtype: Option<String>,
+    /// Times this memory was surfaced by search or retrieval
+    #[serde(default)]
+    pub access_count: i64,
 }
 
 /// Parse a MemoryRow from a rusqlite::Row using the standard column order.
@@ -55,6 +63,7 @@ fn row_to_memory(row: &rusqlite::Row) -> rusqlite::Result<MemoryRow> {
         updated_at: row.get(13)?,
         last_accessed: row.get(14)?,
         agent_subtype: row.get(15)?,
+        access_count: row.get::<_, Option<i64>>(16)?.unwrap_or(0),
     })
 }
 
@@ -112,8 +121,8 @@ impl Database {
         Ok(id)
     }
 
-    /// Insert a memory with a specific created_at timestamp (for restore).
-    /// Preserves the original creation date from the backup.
+    /// Insert a memory with a specific created_at timestamp (for restore and import).
+    /// Preserves the original creation date from the backup. Content is redacted like `insert_memory`.
     pub fn insert_memory_with_created_at(
         &self,
         memory_type: &str,
@@ -130,6 +139,7 @@ impl Database {
         created_at: &str,
         agent_subtype: Option<&str>,
     ) -> Result<i64, rusqlite::Error> {
+        let content = &crate::memory::redaction::redact_content(content).content;
         let conn = self.conn();
         conn.execute(
             "INSERT INTO memories (
@@ -213,6 +223,63 @@ impl Database {
         Ok(())
     }
 
+    /// Record that memories were surfaced by search or retrieval: bumps their
+    /// access count (used by the decay pass to reinforce importance) and last_accessed.
+    pub fn record_memory_hits(&self, memory_ids: &[i64]) -> Result<usize, rusqlite::Error> {
+        if memory_ids.is_empty() {
+            return Ok(0);
+        }
+        let placeholders = vec!["?"; memory_ids.len()].join(", ");
+        let conn = self.conn();
+        conn.execute(
+            &format!(
+                "UPDATE memories SET access_count = access_count + 1, last_accessed = datetime('now')
+                 WHERE id IN ({})",
+                placeholders
+            ),
+            rusqlite::params_from_iter(memory_ids),
+        )
+    }
+
+    /// Delete a memory together with its embedding and associations.
+    /// Returns false if no memory had this ID.
+    pub fn delete_memory(&self, memory_id: i64) -> Result<bool, rusqlite::Error> {
+        let conn = self.conn();
+        let tx = conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM memory_embeddings WHERE memory_id = ?1", [memory_id])?;
+        tx.execute(
+            "DELETE FROM memory_associations WHERE source_memory_id = ?1 OR target_memory_id = ?1",
+            [memory_id],
+        )?;
+        tx.execute("UPDATE memories SET superseded_by = NULL WHERE superseded_by = ?1", [memory_id])?;
+        let deleted = tx.execute("DELETE FROM memories WHERE id = ?1", [memory_id])?;
+        tx.commit()?;
+        Ok(deleted > 0)
+    }
+
+    /// Active memories whose content contains `text` (case-insensitive), newest first.
+    /// `identity_id` of Some restricts the match to memories owned by that identity.
+    pub fn find_memories_containing(
+        &self,
+        text: &str,
+        identity_id: Option<&str>,
+        limit: i32,
+    ) -> Result<Vec<MemoryRow>, rusqlite::Error> {
+        let escaped = text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM memories
+             WHERE content LIKE '%' || ?1 || '%' ESCAPE '\\'
+               AND superseded_by IS NULL
+               AND (?2 IS NULL OR identity_id = ?2)
+             ORDER BY id DESC
+             LIMIT ?3",
+            MEMORY_SELECT_COLS
+        ))?;
+        let rows = stmt.query_map(rusqlite::params![escaped, identity_id, limit], row_to_memory)?;
+        rows.collect()
+    }
+
     /// Get a single memory by ID.
     pub fn get_memory(&self, memory_id: i64) -> Result<Option<MemoryRow>, rusqlite::Error> {
         let conn = self.conn();
@@ -268,6 +335,29 @@ impl Database {
         rows.collect()
     }
 
+    /// Fetch recent long_term memories (non-superseded) visible to `identity_id`
+    /// (see `memory_visible_to`), ordered by created_at DESC.
+    pub fn get_long_term_memories_visible_to(
+        &self,
+        identity_id: &str,
+        limit: i32,
+    ) -> Result<Vec<MemoryRow>, rusqlite::Error> {
+        let conn = self.conn();
+        let sql = format!(
+            "SELECT {} FROM memories
+             WHERE memory_type = 'long_term' AND superseded_by IS NULL
+               AND (identity_id IN (?1, ?2) OR (identity_id IS NULL AND ?1 <> ?3))
+             ORDER BY created_at DESC LIMIT ?4",
+            MEMORY_SELECT_COLS
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(
+            rusqlite::params![identity_id, SHARED_MEMORY_IDENTITY, SAFE_MODE_IDENTITY, limit],
+            row_to_memory,
+        )?;
+        rows.collect()
+    }
+
     /// Fetch daily_log entries for a specific date (YYYY-MM-DD).
     pub fn get_daily_log_memories(
         &self,
@@ -339,6 +429,7 @@ impl Database {
 
     /// FTS5 full-text search against the existing `memories_fts` virtual table.
     /// Returns matching memories with BM25 rank score (lower = better match).
+    /// With an `identity_id`, only memories visible to it match (see `memory_visible_to`).
     /// The query is passed directly to FTS5 MATCH — callers must ensure it is
     /// valid FTS5 syntax. For user-facing search, use `search_memories_fts_user`.
     pub fn search_memories_fts(
@@ -358,7 +449,9 @@ impl Database {
                     "SELECT {cols}, bm25(memories_fts) as rank
                      FROM memories
                      JOIN memories_fts ON memories.id = memories_fts.rowid
-                     WHERE memories_fts MATCH ?1 AND memories.identity_id = ?2
+                     WHERE memories_fts MATCH ?1
+                       AND (memories.identity_id IN (?2, ?4)
+                            OR (memories.identity_id IS NULL AND ?2 <> ?5))
                      ORDER BY rank
                      LIMIT ?3",
                     cols = MEMORY_SELECT_COLS_QUALIFIED
@@ -367,6 +460,8 @@ impl Database {
                     Box::new(query.to_string()) as Box<dyn rusqlite::types::ToSql>,
                     Box::new(id.to_string()),
                     Box::new(limit),
+                    Box::new(SHARED_MEMORY_IDENTITY),
+                    Box::new(SAFE_MODE_IDENTITY),
                 ],
             ),
             None => (
@@ -389,12 +484,27 @@ impl Database {
         let param_refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
         let rows = stmt.query_map(param_refs.as_slice(), |row| {
             let memory = row_to_memory(row)?;
-            let rank: f64 = row.get(16)?; // rank is after the 16 standard columns
+            let rank: f64 = row.get(17)?; // rank is after the 17 standard columns
             Ok((memory, rank))
         })?;
         rows.collect()
     }
 
+    /// IDs of every memory visible to `identity_id` (see `memory_visible_to`).
+    /// Used to scope search signals that don't filter by identity themselves (vector, graph).
+    pub fn list_memory_ids_visible_to(&self, identity_id: &str) -> Result<HashSet<i64>, rusqlite::Error> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id FROM memories
+             WHERE identity_id IN (?1, ?2) OR (identity_id IS NULL AND ?1 <> ?3)",
+        )?;
+        let rows = stmt.query_map(
+            rusqlite::params![identity_id, SHARED_MEMORY_IDENTITY, SAFE_MODE_IDENTITY],
+            |row| row.get::<_, i64>(0),
+        )?;
+        rows.collect()
+    }
+
     /// List distinct dates that have daily_log entries (for calendar display).
     pub fn list_memory_dates(
         &self,
@@ -499,12 +609,33 @@ impl Database {
         let param_refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
         let rows = stmt.query_map(param_refs.as_slice(), |row| {
             let memory = row_to_memory(row)?;
-            let rank: f64 = row.get(16)?;
+            let rank: f64 = row.get(17)?;
             Ok((memory, rank))
         })?;
         rows.collect()
     }
 
+    /// Find an active memory with exactly this type, content and identity.
+    /// Content is compared after redaction and trimming, as it would be stored.
+    pub fn find_memory_by_content(
+        &self,
+        memory_type: &str,
+        content: &str,
+        identity_id: Option<&str>,
+    ) -> Result<Option<i64>, rusqlite::Error> {
+        let content = crate::memory::redaction::redact_content(content).content;
+        let conn = self.conn();
+        conn.query_row(
+            "SELECT id FROM memories
+             WHERE memory_type = ?1 AND TRIM(content) = ?2 AND identity_id IS ?3
+               AND superseded_by IS NULL
+             ORDER BY id LIMIT 1",
+            rusqlite::params![memory_type, content.trim(), identity_id],
+            |row| row.get(0),
+        )
+        .optional()
+    }
+
     /// Merge two memories into a new one.
     ///
     /// 1. Fetch both memories
@@ -660,7 +791,13 @@ impl Database {
     /// Rebuild the FTS5 index from the external content table.
     /// Use this when the FTS index gets out of sync (e.g., after restore,
     /// or if the FTS table was created after memories already existed).
+    /// Recreates the index first if it doesn't use the tokenizer from bot settings.
     pub fn rebuild_fts_index(&self) -> Result<(), rusqlite::Error> {
+        let tokenizer = FtsTokenizer::parse(&self.get_bot_settings()?.memory_fts_tokenizer)
+            .unwrap_or_default();
+        if self.set_memory_fts_tokenizer(tokenizer)? {
+            return Ok(());
+        }
         let conn = self.conn();
         conn.execute(
             "INSERT INTO memories_fts(memories_fts) VALUES('rebuild')",
@@ -669,6 +806,51 @@ impl Database {
         Ok(())
     }
 
+    /// Tokenizer the `memories_fts` table was created with
+    pub fn memory_fts_tokenizer(&self) -> Result<FtsTokenizer, rusqlite::Error> {
+        let conn = self.conn();
+        let sql: String = conn.query_row(
+            "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'memories_fts'",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(FtsTokenizer::from_table_sql(&sql))
+    }
+
+    /// Recreate `memories_fts` with a different tokenizer and reindex all memories.
+    /// Returns false without touching the index if it already uses `tokenizer`.
+    /// The sync triggers reference the table by name, so they keep working.
+    pub fn set_memory_fts_tokenizer(&self, tokenizer: FtsTokenizer) -> Result<bool, rusqlite::Error> {
+        if self.memory_fts_tokenizer()? == tokenizer {
+            return Ok(false);
+        }
+        let tokenize = tokenizer
+            .fts5_option()
+            .map(|t| format!(", tokenize='{}'", t))
+            .unwrap_or_default();
+
+        let conn = self.conn();
+        let tx = conn.unchecked_transaction()?;
+        tx.execute("DROP TABLE memories_fts", [])?;
+        tx.execute(
+            &format!(
+                "CREATE VIRTUAL TABLE memories_fts USING fts5(
+                    content,
+                    category,
+                    tags,
+                    content=memories,
+                    content_rowid=id{}
+                )",
+                tokenize
+            ),
+            [],
+        )?;
+        tx.execute("INSERT INTO memories_fts(memories_fts) VALUES('rebuild')", [])?;
+        tx.commit()?;
+        log::info!("[FTS] Recreated memory FTS index with the {} tokenizer", tokenizer.as_str());
+        Ok(true)
+    }
+
     /// Get memory statistics aggregated from the DB.
     pub fn get_memory_stats(
         &self,
@@ -748,7 +930,7 @@ mod tests {
     }
 
     #[test]
-    fn test_fts_identity_filter_excludes_null_identity_memories() {
+    fn test_fts_identity_filter_treats_null_identity_memories_as_shared() {
         let db = setup_db();
         // Insert a memory with NULL identity
         db.insert_memory(
@@ -757,9 +939,13 @@ mod tests {
             Some("session_completion"), Some("2026-02-23"), None,
         ).unwrap();
 
-        // Search with a specific identity_id should NOT find NULL-identity memories
+        // Search with a specific identity_id still finds NULL-identity memories
         let results = db.search_memories_fts("excalidraw", Some("some-uuid"), 10).unwrap();
-        assert_eq!(results.len(), 0, "identity-filtered search should not match NULL-identity memories");
+        assert_eq!(results.len(), 1, "identity-filtered search should match NULL-identity memories");
+
+        // ...but safe mode never does
+        let results = db.search_memories_fts("excalidraw", Some(crate::memory::SAFE_MODE_IDENTITY), 10).unwrap();
+        assert_eq!(results.len(), 0, "safe mode should not match NULL-identity memories");
     }
 
     #[test]
@@ -881,4 +1067,110 @@ mod tests {
         assert_eq!(results.len(), 1, "should find guitar memory after rebuild");
         assert!(results[0].0.content.contains("guitar"));
     }
+
+    #[test]
+    fn test_trigram_tokenizer_matches_cjk_substrings() {
+        use crate::memory::fts_utils::{normalize_fts_query, FtsTokenizer};
+
+        let db = setup_db();
+        db.insert_memory(
+            "long_term", "我最喜欢的饮料是绿茶",
+            None, None, 5, None, None, None, None,
+            None, None, None,
+        ).unwrap();
+        db.insert_memory(
+            "long_term", "Andy drinks green tea every morning",
+            None, None, 5, None, None, None, None,
+            None, None, None,
+        ).unwrap();
+
+        // unicode61 indexes the whole sentence as one token, so a word inside it is missed
+        let query = normalize_fts_query("喜欢的饮料");
+        assert!(db.search_memories_fts(&query, None, 10).unwrap().is_empty());
+
+        db.update_bot_settings_full(
+            None, None, None, None, None, None, None, None, None, None, None,
+            None, None, None, None, None, None, None, None, None, None,
+            Some("trigram"), None,
+        ).unwrap();
+        db.rebuild_fts_index().unwrap();
+        assert_eq!(db.memory_fts_tokenizer().unwrap(), FtsTokenizer::Trigram);
+
+        let results = db.search_memories_fts(&query, None, 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].0.content.contains("绿茶"));
+        // Existing memories were reindexed, and triggers still feed the new table
+        assert_eq!(db.search_memories_fts(&normalize_fts_query("green tea"), None, 10).unwrap().len(), 1);
+        db.insert_memory(
+            "long_term", "用户住在东京",
+            None, None, 5, None, None, None, None,
+            None, None, None,
+        ).unwrap();
+        assert_eq!(db.search_memories_fts(&normalize_fts_query("住在东京"), None, 10).unwrap().len(), 1);
+
+        // Setting the same tokenizer again leaves the index alone
+        assert!(!db.set_memory_fts_tokenizer(FtsTokenizer::Trigram).unwrap());
+    }
+
+    #[test]
+    fn test_fts_identity_filter_includes_shared_memories() {
+        let db = setup_db();
+        db.insert_memory(
+            "long_term", "Alice keeps her seed phrase in a safe",
+            None, None, 7, Some("alice"), None, None, None,
+            None, None, None,
+        ).unwrap();
+        db.insert_memory(
+            "long_term", "Bob keeps his seed phrase in a drawer",
+            None, None, 7, Some("bob"), None, None, None,
+            None, None, None,
+        ).unwrap();
+        let shared = db.insert_memory(
+            "long_term", "Never share a seed phrase with anyone",
+            None, None, 7, Some(crate::memory::SHARED_MEMORY_IDENTITY), None, None, None,
+            None, None, None,
+        ).unwrap();
+
+        let results = db.search_memories_fts("seed", Some("alice"), 10).unwrap();
+        let owners: Vec<_> = results.iter().map(|(m, _)| m.identity_id.as_deref()).collect();
+        assert_eq!(results.len(), 2);
+        assert!(!owners.contains(&Some("bob")), "bob's memory leaked to alice: {:?}", owners);
+
+        let visible = db.list_memory_ids_visible_to("bob").unwrap();
+        assert_eq!(visible.len(), 2);
+        assert!(visible.contains(&shared));
+    }
+
+    #[test]
+    fn test_unowned_memories_are_listed_for_every_identity_but_safe_mode() {
+        let db = setup_db();
+        let unowned = db.insert_memory(
+            "long_term", "The treasury multisig lives on Base",
+            None, None, 7, None, None, None, None,
+            None, None, None,
+        ).unwrap();
+
+        assert!(db.list_memory_ids_visible_to("alice").unwrap().contains(&unowned));
+        assert_eq!(db.get_long_term_memories_visible_to("alice", 10).unwrap().len(), 1);
+
+        let safe = crate::memory::SAFE_MODE_IDENTITY;
+        assert!(db.list_memory_ids_visible_to(safe).unwrap().is_empty());
+        assert!(db.get_long_term_memories_visible_to(safe, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_find_memory_by_content_exact_match() {
+        let db = setup_db();
+        let id = db.insert_memory(
+            "long_term", "User prefers dark mode",
+            None, None, 5, Some("alice"), None, None, None,
+            None, None, None,
+        ).unwrap();
+
+        assert_eq!(db.find_memory_by_content("long_term", "  User prefers dark mode\n", Some("alice")).unwrap(), Some(id));
+        // Type, identity and content must all match
+        assert_eq!(db.find_memory_by_content("preference", "User prefers dark mode", Some("alice")).unwrap(), None);
+        assert_eq!(db.find_memory_by_content("long_term", "User prefers dark mode", None).unwrap(), None);
+        assert_eq!(db.find_memory_by_content("long_term", "User prefers light mode", Some("alice")).unwrap(), None);
+    }
 }
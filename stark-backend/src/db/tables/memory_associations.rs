@@ -2,6 +2,7 @@
 //! Manages typed connections between memories (knowledge graph)
 
 use crate::db::Database;
+use crate::memory::{SAFE_MODE_IDENTITY, SHARED_MEMORY_IDENTITY};
 use serde::{Deserialize, Serialize};
 
 /// Association record from database
@@ -181,11 +182,13 @@ impl Database {
     /// Expand from seed memory IDs to their graph neighbors.
     /// Returns (neighbor_memory_id, total_strength_x100) pairs ranked by cumulative
     /// connection strength to the seed set.  Neighbors that ARE in the seed set
-    /// are excluded so you only get new discoveries.
+    /// are excluded so you only get new discoveries. With an `identity_id`, only
+    /// neighbors visible to it (its own and shared memories) are returned.
     pub fn graph_expand_from_seeds(
         &self,
         seed_ids: &[i64],
         limit: i32,
+        identity_id: Option<&str>,
     ) -> Result<Vec<(i64, i32)>, rusqlite::Error> {
         if seed_ids.is_empty() {
             return Ok(Vec::new());
@@ -223,14 +226,20 @@ impl Database {
                  FROM memory_associations
                  WHERE target_memory_id IN ({p3})
                    AND source_memory_id NOT IN ({p4})
-             )
+             ) n
+             JOIN memories m ON m.id = n.neighbor_id
+             WHERE ?{id} IS NULL OR m.identity_id IN (?{id}, ?{shared})
+                OR (m.identity_id IS NULL AND ?{id} <> ?{safe})
              GROUP BY neighbor_id
              ORDER BY total_strength DESC
-             LIMIT ?{}",
-            4 * n + 1
+             LIMIT ?{limit}",
+            limit = 4 * n + 1,
+            id = 4 * n + 2,
+            shared = 4 * n + 3,
+            safe = 4 * n + 4,
         );
 
-        // Bind seed_ids four times (once per IN clause) + limit
+        // Bind seed_ids four times (once per IN clause) + limit + identity filter
         let mut all_params: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
         for _ in 0..4 {
             for id in &unique_seeds {
@@ -238,6 +247,9 @@ impl Database {
             }
         }
         all_params.push(Box::new(limit));
+        all_params.push(Box::new(identity_id.map(str::to_string)));
+        all_params.push(Box::new(SHARED_MEMORY_IDENTITY));
+        all_params.push(Box::new(SAFE_MODE_IDENTITY));
 
         let mut stmt = conn.prepare(&query)?;
         let results = stmt
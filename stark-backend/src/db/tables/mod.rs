@@ -10,6 +10,8 @@ mod channel_settings; // channel_settings (per-channel config)
 mod agent_settings; // agent_settings
 mod bot_settings;   // bot_settings
 mod chat_sessions;  // chat_sessions, session_messages (+ compaction)
+mod session_tags;   // session_tags, session_messages_fts (tagging + search)
+mod session_spend;  // session_spend (per-session spend ledger)
 mod identities;     // identity_links
 mod tool_configs;   // tool_configs, tool_executions
 mod skills;         // skills, skill_scripts
@@ -21,6 +23,7 @@ mod twitter_mentions; // twitter_processed_mentions (track processed tweets)
 pub mod broadcasted_transactions; // broadcasted_transactions (crypto tx history)
 pub mod impulse_nodes;  // impulse_nodes, impulse_node_connections (impulse map feature)
 pub mod telegram_chat_log; // telegram_chat_messages (passive chat log for readHistory)
+pub mod channel_messages;  // channel_messages (inbound message log for moderation exports)
 pub mod x402_payment_limits; // x402_payment_limits (per-call max amounts per token)
 pub mod kanban;          // kanban_items (kanban board task management)
 pub mod modules;         // installed_modules (plugin system registry)
@@ -31,3 +34,4 @@ pub mod memory_embeddings; // memory_embeddings (vector search)
 pub mod memory_associations; // memory_associations (knowledge graph)
 pub mod skill_embeddings;  // skill_embeddings (vector search for skill discovery)
 pub mod skill_associations; // skill_associations (skill relationship graph)
+pub mod skill_versions;    // skill_versions (prior skill definitions for rollback)
@@ -0,0 +1,120 @@
+//! Per-session spend ledger
+//!
+//! Every x402 payment, estimated AI token cost and gas fee attributed to a
+//! session is appended to `session_spend`, giving a running tab per
+//! conversation. Amounts are in their own currency and are never converted.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Result as SqliteResult;
+
+use crate::models::{SessionSpendEntry, SessionSpendTotal, SpendKind};
+use super::super::Database;
+
+impl Database {
+    /// Append an entry to a session's spend ledger
+    pub fn record_session_spend(
+        &self,
+        session_id: i64,
+        kind: SpendKind,
+        amount: f64,
+        currency: &str,
+        tokens: Option<i64>,
+        detail: Option<&str>,
+    ) -> SqliteResult<SessionSpendEntry> {
+        let conn = self.conn();
+        let now = Utc::now();
+        conn.execute(
+            "INSERT INTO session_spend (session_id, kind, amount, currency, tokens, detail, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![session_id, kind.as_str(), amount, currency, tokens, detail, now.to_rfc3339()],
+        )?;
+        Ok(SessionSpendEntry {
+            id: conn.last_insert_rowid(),
+            session_id,
+            kind,
+            amount,
+            currency: currency.to_string(),
+            tokens,
+            detail: detail.map(str::to_string),
+            created_at: now,
+        })
+    }
+
+    /// A session's ledger entries, oldest first
+    pub fn get_session_spend(&self, session_id: i64) -> SqliteResult<Vec<SessionSpendEntry>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, session_id, kind, amount, currency, tokens, detail, created_at
+             FROM session_spend WHERE session_id = ?1 ORDER BY id",
+        )?;
+        let entries = stmt.query_map([session_id], |row| {
+            let kind: String = row.get(2)?;
+            let created_at: String = row.get(7)?;
+            Ok(SessionSpendEntry {
+                id: row.get(0)?,
+                session_id: row.get(1)?,
+                kind: SpendKind::from_str(&kind).unwrap_or(SpendKind::X402),
+                amount: row.get(3)?,
+                currency: row.get(4)?,
+                tokens: row.get(5)?,
+                detail: row.get(6)?,
+                created_at: DateTime::parse_from_rfc3339(&created_at)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            })
+        })?;
+        entries.collect()
+    }
+
+    /// Totals per kind and currency for a session
+    pub fn get_session_spend_totals(&self, session_id: i64) -> SqliteResult<Vec<SessionSpendTotal>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT kind, currency, SUM(amount), COALESCE(SUM(tokens), 0), COUNT(*)
+             FROM session_spend WHERE session_id = ?1
+             GROUP BY kind, currency ORDER BY kind, currency",
+        )?;
+        let totals = stmt.query_map([session_id], |row| {
+            let kind: String = row.get(0)?;
+            Ok(SessionSpendTotal {
+                kind: SpendKind::from_str(&kind).unwrap_or(SpendKind::X402),
+                currency: row.get(1)?,
+                amount: row.get(2)?,
+                tokens: row.get(3)?,
+                entries: row.get(4)?,
+            })
+        })?;
+        totals.collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::Database;
+    use crate::models::{SessionScope, SpendKind};
+
+    #[test]
+    fn test_session_spend_totals_by_kind_and_currency() {
+        let db = Database::new(":memory:").expect("in-memory db");
+        let session = db.get_or_create_chat_session("web", 1, "web-1", SessionScope::Dm, None).unwrap();
+        let other = db.get_or_create_chat_session("web", 2, "web-2", SessionScope::Dm, None).unwrap();
+
+        db.record_session_spend(session.id, SpendKind::X402, 0.01, "USDC", None, Some("https://api.example.com")).unwrap();
+        db.record_session_spend(session.id, SpendKind::X402, 0.02, "USDC", None, None).unwrap();
+        db.record_session_spend(session.id, SpendKind::AiTokens, 0.003, "USD", Some(1200), Some("gpt")).unwrap();
+        db.record_session_spend(session.id, SpendKind::Gas, 0.0001, "ETH", None, Some("0xabc")).unwrap();
+        db.record_session_spend(other.id, SpendKind::X402, 5.0, "USDC", None, None).unwrap();
+
+        let entries = db.get_session_spend(session.id).unwrap();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[2].tokens, Some(1200));
+
+        let totals = db.get_session_spend_totals(session.id).unwrap();
+        assert_eq!(totals.len(), 3);
+        let x402 = totals.iter().find(|t| t.kind == SpendKind::X402).unwrap();
+        assert!((x402.amount - 0.03).abs() < 1e-9);
+        assert_eq!(x402.entries, 2);
+        let ai = totals.iter().find(|t| t.kind == SpendKind::AiTokens).unwrap();
+        assert_eq!(ai.tokens, 1200);
+    }
+}
@@ -0,0 +1,233 @@
+//! Session tagging and search
+//!
+//! Tags are free-form labels stored in `session_tags`. Search combines tag,
+//! channel type and date filters with a full-text match over message content
+//! via the `session_messages_fts` index.
+
+use chrono::{DateTime, Utc};
+use rusqlite::Result as SqliteResult;
+
+use crate::memory::fts_utils::all_terms_fts_query;
+use crate::models::{
+    ChatSessionResponse, MessageRole, SessionMatchSnippet, SessionSearchFilter, SessionSearchResult,
+};
+use super::super::Database;
+
+/// Snippets returned per matching session
+const SNIPPETS_PER_SESSION: i64 = 3;
+
+impl Database {
+    /// List a session's tags, alphabetically
+    pub fn get_session_tags(&self, session_id: i64) -> SqliteResult<Vec<String>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT tag FROM session_tags WHERE session_id = ?1 ORDER BY tag")?;
+        let tags = stmt.query_map([session_id], |row| row.get(0))?;
+        tags.collect()
+    }
+
+    /// Add tags to a session (already-present tags are ignored). Tags must be normalized.
+    pub fn add_session_tags(&self, session_id: i64, tags: &[String]) -> SqliteResult<()> {
+        let conn = self.conn();
+        let now = Utc::now().to_rfc3339();
+        for tag in tags {
+            conn.execute(
+                "INSERT OR IGNORE INTO session_tags (session_id, tag, created_at) VALUES (?1, ?2, ?3)",
+                rusqlite::params![session_id, tag, now],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Replace all of a session's tags. Tags must be normalized.
+    pub fn set_session_tags(&self, session_id: i64, tags: &[String]) -> SqliteResult<()> {
+        self.conn().execute("DELETE FROM session_tags WHERE session_id = ?1", [session_id])?;
+        self.add_session_tags(session_id, tags)
+    }
+
+    /// Remove one tag from a session. Returns true if it was present.
+    pub fn remove_session_tag(&self, session_id: i64, tag: &str) -> SqliteResult<bool> {
+        let deleted = self.conn().execute(
+            "DELETE FROM session_tags WHERE session_id = ?1 AND tag = ?2",
+            rusqlite::params![session_id, tag],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    /// All tags in use, with the number of sessions carrying each
+    pub fn list_session_tags(&self) -> SqliteResult<Vec<(String, i64)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT tag, COUNT(*) FROM session_tags GROUP BY tag ORDER BY COUNT(*) DESC, tag",
+        )?;
+        let tags = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        tags.collect()
+    }
+
+    /// Search sessions by tags, channel type, date range and message content.
+    /// Results are ordered by last activity (most recent first).
+    pub fn search_sessions(&self, filter: &SessionSearchFilter) -> SqliteResult<Vec<SessionSearchResult>> {
+        let fts_query = filter
+            .text
+            .as_deref()
+            .map(all_terms_fts_query)
+            .filter(|q| !q.is_empty());
+
+        let mut conditions: Vec<String> = Vec::new();
+        let mut params: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+
+        for tag in &filter.tags {
+            params.push(Box::new(tag.clone()));
+            conditions.push(format!(
+                "EXISTS (SELECT 1 FROM session_tags t WHERE t.session_id = cs.id AND t.tag = ?{})",
+                params.len()
+            ));
+        }
+        if let Some(ref channel_type) = filter.channel_type {
+            params.push(Box::new(channel_type.clone()));
+            conditions.push(format!("cs.channel_type = ?{}", params.len()));
+        }
+        if let Some(from) = filter.from {
+            params.push(Box::new(from.to_rfc3339()));
+            conditions.push(format!("cs.last_activity_at >= ?{}", params.len()));
+        }
+        if let Some(to) = filter.to {
+            params.push(Box::new(to.to_rfc3339()));
+            conditions.push(format!("cs.created_at <= ?{}", params.len()));
+        }
+        if let Some(ref q) = fts_query {
+            params.push(Box::new(q.clone()));
+            conditions.push(format!(
+                "cs.id IN (SELECT sm.session_id FROM session_messages_fts
+                           JOIN session_messages sm ON sm.id = session_messages_fts.rowid
+                           WHERE session_messages_fts MATCH ?{})",
+                params.len()
+            ));
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        params.push(Box::new(filter.limit as i64));
+        let sql = format!(
+            "SELECT cs.id FROM chat_sessions cs {} ORDER BY cs.last_activity_at DESC LIMIT ?{}",
+            where_clause,
+            params.len()
+        );
+
+        let session_ids: Vec<i64> = {
+            let conn = self.conn();
+            let mut stmt = conn.prepare(&sql)?;
+            let param_refs: Vec<&dyn rusqlite::types::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+            let ids = stmt.query_map(param_refs.as_slice(), |row| row.get(0))?;
+            ids.collect::<SqliteResult<_>>()?
+        };
+
+        let mut results = Vec::with_capacity(session_ids.len());
+        for session_id in session_ids {
+            let Some(session) = self.get_chat_session(session_id)? else {
+                continue;
+            };
+            let mut response: ChatSessionResponse = session.into();
+            response.message_count = self.count_session_messages(session_id).ok();
+            response.title = self.get_session_title(session_id)?.map(|(t, _)| t);
+            let (match_count, snippets) = match fts_query {
+                Some(ref q) => self.session_match_snippets(session_id, q)?,
+                None => (0, Vec::new()),
+            };
+            results.push(SessionSearchResult {
+                session: response,
+                tags: self.get_session_tags(session_id)?,
+                match_count,
+                snippets,
+            });
+        }
+        Ok(results)
+    }
+
+    /// Count a session's messages matching `fts_query` and excerpt the best-ranked ones
+    fn session_match_snippets(&self, session_id: i64, fts_query: &str) -> SqliteResult<(i64, Vec<SessionMatchSnippet>)> {
+        let conn = self.conn();
+        let match_count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM session_messages_fts
+             JOIN session_messages sm ON sm.id = session_messages_fts.rowid
+             WHERE session_messages_fts MATCH ?1 AND sm.session_id = ?2",
+            rusqlite::params![fts_query, session_id],
+            |row| row.get(0),
+        )?;
+
+        let mut stmt = conn.prepare(
+            "SELECT sm.id, sm.role, snippet(session_messages_fts, 0, '**', '**', '…', 16), sm.created_at
+             FROM session_messages_fts
+             JOIN session_messages sm ON sm.id = session_messages_fts.rowid
+             WHERE session_messages_fts MATCH ?1 AND sm.session_id = ?2
+             ORDER BY bm25(session_messages_fts)
+             LIMIT ?3",
+        )?;
+        let snippets = stmt
+            .query_map(rusqlite::params![fts_query, session_id, SNIPPETS_PER_SESSION], |row| {
+                let role: String = row.get(1)?;
+                let created_at: String = row.get(3)?;
+                Ok(SessionMatchSnippet {
+                    message_id: row.get(0)?,
+                    role: MessageRole::from_str(&role).unwrap_or(MessageRole::User),
+                    snippet: row.get(2)?,
+                    created_at: DateTime::parse_from_rfc3339(&created_at)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                })
+            })?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        Ok((match_count, snippets))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::db::Database;
+    use crate::models::{MessageRole, SessionScope, SessionSearchFilter};
+
+    #[test]
+    fn test_tag_and_search_sessions() {
+        let db = Database::new(":memory:").expect("in-memory db");
+        let deploy = db.get_or_create_chat_session("discord", 1, "chan-a", SessionScope::Group, None).unwrap();
+        let weather = db.get_or_create_chat_session("telegram", 2, "chat-b", SessionScope::Dm, None).unwrap();
+        db.add_session_message(deploy.id, MessageRole::User, "How do I deploy the contract to Base?", None, None, None, None).unwrap();
+        db.add_session_message(deploy.id, MessageRole::Assistant, "Run the deploy script with your API key.", None, None, None, None).unwrap();
+        db.add_session_message(weather.id, MessageRole::User, "What's the weather in Boston?", None, None, None, None).unwrap();
+
+        db.set_session_tags(deploy.id, &["web3".to_string(), "deploy".to_string()]).unwrap();
+        db.add_session_tags(deploy.id, &["deploy".to_string()]).unwrap();
+        db.add_session_tags(weather.id, &["misc".to_string()]).unwrap();
+        assert_eq!(db.get_session_tags(deploy.id).unwrap(), vec!["deploy", "web3"]);
+
+        let search = |filter: SessionSearchFilter| db.search_sessions(&SessionSearchFilter { limit: 10, ..filter }).unwrap();
+
+        // Every word must match; matched terms are highlighted in snippets
+        let results = search(SessionSearchFilter { text: Some("deploy key".into()), ..Default::default() });
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].session.id, deploy.id);
+        assert_eq!(results[0].match_count, 1);
+        assert!(results[0].snippets[0].snippet.contains("**deploy**"), "{:?}", results[0].snippets);
+
+        // Tag and channel filters combine
+        let results = search(SessionSearchFilter { tags: vec!["misc".into()], ..Default::default() });
+        assert_eq!(results.iter().map(|r| r.session.id).collect::<Vec<_>>(), vec![weather.id]);
+        assert!(search(SessionSearchFilter {
+            tags: vec!["misc".into()],
+            channel_type: Some("discord".into()),
+            ..Default::default()
+        })
+        .is_empty());
+
+        // Date range excludes sessions that started after `to`
+        let results = search(SessionSearchFilter { to: Some(chrono::Utc::now() - chrono::Duration::days(1)), ..Default::default() });
+        assert!(results.is_empty());
+        assert_eq!(search(SessionSearchFilter::default()).len(), 2);
+
+        assert!(db.remove_session_tag(deploy.id, "web3").unwrap());
+        assert_eq!(db.list_session_tags().unwrap(), vec![("deploy".to_string(), 1), ("misc".to_string(), 1)]);
+    }
+}
@@ -75,6 +75,13 @@ impl Database {
         rows.collect()
     }
 
+    /// Delete a skill's embedding so the next backfill regenerates it
+    pub fn delete_skill_embedding(&self, skill_id: i64) -> Result<bool, rusqlite::Error> {
+        let conn = self.conn();
+        let rows = conn.execute("DELETE FROM skill_embeddings WHERE skill_id = ?1", [skill_id])?;
+        Ok(rows > 0)
+    }
+
     /// Count total skill embeddings
     pub fn count_skill_embeddings(&self) -> Result<i64, rusqlite::Error> {
         let conn = self.conn();
@@ -0,0 +1,135 @@
+//! Database operations for skill_versions table
+//! Keeps prior skill definitions (body, args, scripts) so a bad edit can be rolled back
+
+use rusqlite::{Connection, Result as SqliteResult};
+
+use crate::skills::{DbSkill, DbSkillScript, DbSkillVersion};
+use super::super::Database;
+
+/// Versions kept per skill; older snapshots are pruned
+const MAX_VERSIONS_PER_SKILL: i64 = 20;
+
+/// True when two definitions differ in anything a rollback would restore
+fn definition_changed(old: &DbSkill, new: &DbSkill) -> bool {
+    // Compare maps as JSON values so key order doesn't matter
+    use serde_json::to_value;
+    old.body != new.body
+        || old.version != new.version
+        || old.description != new.description
+        || old.requires_tools != new.requires_tools
+        || old.requires_binaries != new.requires_binaries
+        || old.tags != new.tags
+        || old.subagent_type != new.subagent_type
+        || to_value(&old.arguments).ok() != to_value(&new.arguments).ok()
+        || to_value(&old.requires_api_keys).ok() != to_value(&new.requires_api_keys).ok()
+}
+
+impl Database {
+    /// Snapshot `existing` (and its scripts) before it is overwritten by `incoming`.
+    /// Runs on the caller's connection, inside `create_skill_internal`.
+    pub(crate) fn snapshot_skill_version(
+        conn: &Connection,
+        existing: &DbSkill,
+        incoming: &DbSkill,
+    ) -> SqliteResult<()> {
+        if !definition_changed(existing, incoming) {
+            return Ok(());
+        }
+
+        let scripts: Vec<DbSkillScript> = match existing.id {
+            Some(skill_id) => conn
+                .prepare(
+                    "SELECT id, skill_id, name, code, language, created_at
+                     FROM skill_scripts WHERE skill_id = ?1 ORDER BY name",
+                )?
+                .query_map([skill_id], |row| {
+                    Ok(DbSkillScript {
+                        id: row.get(0)?,
+                        skill_id: row.get(1)?,
+                        name: row.get(2)?,
+                        code: row.get(3)?,
+                        language: row.get(4)?,
+                        created_at: row.get(5)?,
+                    })
+                })?
+                .filter_map(|r| r.ok())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let skill_json = serde_json::to_string(existing).unwrap_or_default();
+        let scripts_json = serde_json::to_string(&scripts).unwrap_or_else(|_| "[]".to_string());
+        conn.execute(
+            "INSERT INTO skill_versions (skill_name, version, skill_json, scripts_json, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![
+                existing.name,
+                existing.version,
+                skill_json,
+                scripts_json,
+                chrono::Utc::now().to_rfc3339()
+            ],
+        )?;
+
+        conn.execute(
+            "DELETE FROM skill_versions WHERE skill_name = ?1 AND id NOT IN (
+                SELECT id FROM skill_versions WHERE skill_name = ?1 ORDER BY id DESC LIMIT ?2
+             )",
+            rusqlite::params![existing.name, MAX_VERSIONS_PER_SKILL],
+        )?;
+
+        log::info!(
+            "[SKILLS] Saved version {} of skill '{}' to history",
+            existing.version, existing.name
+        );
+        Ok(())
+    }
+
+    /// List prior versions of a skill, newest first
+    pub fn list_skill_versions(&self, skill_name: &str) -> SqliteResult<Vec<DbSkillVersion>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, skill_name, version, skill_json, scripts_json, created_at
+             FROM skill_versions WHERE skill_name = ?1 ORDER BY id DESC",
+        )?;
+
+        let versions = stmt
+            .query_map([skill_name], Self::row_to_skill_version)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(versions)
+    }
+
+    /// Get a single prior version of a skill
+    pub fn get_skill_version(&self, skill_name: &str, version_id: i64) -> SqliteResult<Option<DbSkillVersion>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT id, skill_name, version, skill_json, scripts_json, created_at
+             FROM skill_versions WHERE skill_name = ?1 AND id = ?2",
+        )?;
+
+        let version = stmt
+            .query_row(rusqlite::params![skill_name, version_id], Self::row_to_skill_version)
+            .ok();
+
+        Ok(version)
+    }
+
+    fn row_to_skill_version(row: &rusqlite::Row) -> rusqlite::Result<DbSkillVersion> {
+        let skill_json: String = row.get(3)?;
+        let scripts_json: String = row.get(4)?;
+        let skill: DbSkill = serde_json::from_str(&skill_json).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(3, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+
+        Ok(DbSkillVersion {
+            id: row.get(0)?,
+            skill_name: row.get(1)?,
+            version: row.get(2)?,
+            skill,
+            scripts: serde_json::from_str(&scripts_json).unwrap_or_default(),
+            created_at: row.get(5)?,
+        })
+    }
+}
@@ -91,6 +91,18 @@ impl Database {
             }
         }
 
+        // Keep the definition being replaced so it can be rolled back
+        let existing: Option<DbSkill> = conn
+            .prepare(
+                "SELECT id, name, description, body, version, author, homepage, metadata, enabled, requires_tools, requires_binaries, arguments, tags, subagent_type, requires_api_keys, created_at, updated_at
+                 FROM skills WHERE name = ?1",
+            )?
+            .query_row([&skill.name], Self::row_to_db_skill)
+            .ok();
+        if let Some(ref existing) = existing {
+            Self::snapshot_skill_version(&conn, existing, skill)?;
+        }
+
         let now = Utc::now().to_rfc3339();
         let requires_tools_json = serde_json::to_string(&skill.requires_tools).unwrap_or_default();
         let requires_binaries_json = serde_json::to_string(&skill.requires_binaries).unwrap_or_default();
@@ -351,6 +363,16 @@ impl Database {
         Ok(scripts)
     }
 
+    /// Delete a single script from a skill
+    pub fn delete_skill_script(&self, skill_id: i64, name: &str) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let rows_affected = conn.execute(
+            "DELETE FROM skill_scripts WHERE skill_id = ?1 AND name = ?2",
+            rusqlite::params![skill_id, name],
+        )?;
+        Ok(rows_affected > 0)
+    }
+
     /// Delete all scripts for a skill
     pub fn delete_skill_scripts(&self, skill_id: i64) -> SqliteResult<i64> {
         let conn = self.conn();
@@ -11,17 +11,19 @@ impl Database {
     pub fn list_special_roles(&self) -> SqliteResult<Vec<SpecialRole>> {
         let conn = self.conn();
         let mut stmt = conn.prepare(
-            "SELECT name, allowed_tools, allowed_skills, description, created_at, updated_at
+            "SELECT name, allowed_tools, allowed_skills, description, created_at, updated_at, denied_tools
              FROM special_roles ORDER BY name"
         )?;
         let roles = stmt
             .query_map([], |row| {
                 let tools_str: String = row.get(1)?;
                 let skills_str: String = row.get(2)?;
+                let denied_str: String = row.get(6)?;
                 Ok(SpecialRole {
                     name: row.get(0)?,
                     allowed_tools: serde_json::from_str(&tools_str).unwrap_or_default(),
                     allowed_skills: serde_json::from_str(&skills_str).unwrap_or_default(),
+                    denied_tools: serde_json::from_str(&denied_str).unwrap_or_default(),
                     description: row.get(3)?,
                     created_at: row.get(4)?,
                     updated_at: row.get(5)?,
@@ -36,16 +38,18 @@ impl Database {
     pub fn get_special_role(&self, name: &str) -> SqliteResult<Option<SpecialRole>> {
         let conn = self.conn();
         let result = conn.query_row(
-            "SELECT name, allowed_tools, allowed_skills, description, created_at, updated_at
+            "SELECT name, allowed_tools, allowed_skills, description, created_at, updated_at, denied_tools
              FROM special_roles WHERE name = ?1",
             [name],
             |row| {
                 let tools_str: String = row.get(1)?;
                 let skills_str: String = row.get(2)?;
+                let denied_str: String = row.get(6)?;
                 Ok(SpecialRole {
                     name: row.get(0)?,
                     allowed_tools: serde_json::from_str(&tools_str).unwrap_or_default(),
                     allowed_skills: serde_json::from_str(&skills_str).unwrap_or_default(),
+                    denied_tools: serde_json::from_str(&denied_str).unwrap_or_default(),
                     description: row.get(3)?,
                     created_at: row.get(4)?,
                     updated_at: row.get(5)?,
@@ -65,16 +69,18 @@ impl Database {
         let now = Utc::now().to_rfc3339();
         let tools_json = serde_json::to_string(&role.allowed_tools).unwrap_or_else(|_| "[]".to_string());
         let skills_json = serde_json::to_string(&role.allowed_skills).unwrap_or_else(|_| "[]".to_string());
+        let denied_json = serde_json::to_string(&role.denied_tools).unwrap_or_else(|_| "[]".to_string());
 
         conn.execute(
-            "INSERT INTO special_roles (name, allowed_tools, allowed_skills, description, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?5)
+            "INSERT INTO special_roles (name, allowed_tools, allowed_skills, denied_tools, description, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
              ON CONFLICT(name) DO UPDATE SET
                 allowed_tools = excluded.allowed_tools,
                 allowed_skills = excluded.allowed_skills,
+                denied_tools = excluded.denied_tools,
                 description = excluded.description,
                 updated_at = excluded.updated_at",
-            rusqlite::params![role.name, tools_json, skills_json, role.description, now],
+            rusqlite::params![role.name, tools_json, skills_json, denied_json, role.description, now],
         )?;
         Ok(())
     }
@@ -287,7 +293,7 @@ impl Database {
         // Build IN clause with positional params
         let placeholders: Vec<String> = (0..role_ids.len()).map(|i| format!("?{}", i + 2)).collect();
         let sql = format!(
-            "SELECT sr.name, sr.allowed_tools, sr.allowed_skills, sr.description
+            "SELECT sr.name, sr.allowed_tools, sr.allowed_skills, sr.description, sr.denied_tools
              FROM special_role_role_assignments srra
              JOIN special_roles sr ON sr.name = srra.special_role_name
              WHERE srra.channel_type = ?1 AND srra.platform_role_id IN ({})
@@ -308,16 +314,18 @@ impl Database {
             let tools_str: String = row.get(1)?;
             let skills_str: String = row.get(2)?;
             let description: Option<String> = row.get(3)?;
-            Ok((name, tools_str, skills_str, description))
+            let denied_str: String = row.get(4)?;
+            Ok((name, tools_str, skills_str, description, denied_str))
         });
 
         match result {
-            Ok((name, tools_str, skills_str, description)) => {
+            Ok((name, tools_str, skills_str, description, denied_str)) => {
                 Ok(SpecialRoleGrants {
                     role_name: Some(name),
                     description,
                     extra_tools: serde_json::from_str(&tools_str).unwrap_or_default(),
                     extra_skills: serde_json::from_str(&skills_str).unwrap_or_default(),
+                    denied_tools: serde_json::from_str(&denied_str).unwrap_or_default(),
                 })
             }
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(SpecialRoleGrants::default()),
@@ -334,7 +342,7 @@ impl Database {
     ) -> SqliteResult<SpecialRoleGrants> {
         let conn = self.conn();
         let result = conn.query_row(
-            "SELECT sr.name, sr.allowed_tools, sr.allowed_skills, sr.description
+            "SELECT sr.name, sr.allowed_tools, sr.allowed_skills, sr.description, sr.denied_tools
              FROM special_role_assignments sra
              JOIN special_roles sr ON sr.name = sra.special_role_name
              WHERE sra.channel_type = ?1 AND sra.user_id = ?2",
@@ -344,17 +352,19 @@ impl Database {
                 let tools_str: String = row.get(1)?;
                 let skills_str: String = row.get(2)?;
                 let description: Option<String> = row.get(3)?;
-                Ok((name, tools_str, skills_str, description))
+                let denied_str: String = row.get(4)?;
+                Ok((name, tools_str, skills_str, description, denied_str))
             },
         );
 
         match result {
-            Ok((name, tools_str, skills_str, description)) => {
+            Ok((name, tools_str, skills_str, description, denied_str)) => {
                 Ok(SpecialRoleGrants {
                     role_name: Some(name),
                     description,
                     extra_tools: serde_json::from_str(&tools_str).unwrap_or_default(),
                     extra_skills: serde_json::from_str(&skills_str).unwrap_or_default(),
+                    denied_tools: serde_json::from_str(&denied_str).unwrap_or_default(),
                 })
             }
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(SpecialRoleGrants::default()),
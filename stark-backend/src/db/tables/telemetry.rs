@@ -216,6 +216,72 @@ impl Database {
         Ok(())
     }
 
+    /// Record the session a rollout resolved to and the resource version it ran with
+    pub fn update_rollout_session(
+        &self,
+        rollout_id: &str,
+        session_id: i64,
+        resources_id: Option<&str>,
+    ) -> SqliteResult<()> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE rollouts SET session_id = ?1, resources_id = ?2 WHERE rollout_id = ?3",
+            rusqlite::params![session_id, resources_id, rollout_id],
+        )?;
+        Ok(())
+    }
+
+    /// Load a rollout by id. Attempts are not loaded.
+    pub fn get_rollout(&self, rollout_id: &str) -> SqliteResult<Option<crate::telemetry::rollout::Rollout>> {
+        let conn = self.conn();
+        let result = conn.query_row(
+            "SELECT rollout_id, session_id, channel_id, status, config, resources_id, created_at,
+                    completed_at, duration_ms, result, error, metadata
+             FROM rollouts WHERE rollout_id = ?1",
+            [rollout_id],
+            |row| {
+                let status: String = row.get(3)?;
+                let config: String = row.get(4)?;
+                let created_at: String = row.get(6)?;
+                let completed_at: Option<String> = row.get(7)?;
+                let metadata: String = row.get(11)?;
+                Ok(crate::telemetry::rollout::Rollout {
+                    rollout_id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    channel_id: row.get(2)?,
+                    status: serde_json::from_value(Value::String(status))
+                        .unwrap_or(crate::telemetry::RolloutStatus::Failed),
+                    config: serde_json::from_str(&config).unwrap_or_default(),
+                    attempts: Vec::new(),
+                    resources_id: row.get(5)?,
+                    created_at: DateTime::parse_from_rfc3339(&created_at)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                    completed_at: completed_at.and_then(|s| {
+                        DateTime::parse_from_rfc3339(&s).ok().map(|dt| dt.with_timezone(&Utc))
+                    }),
+                    duration_ms: row.get::<_, Option<i64>>(8)?.map(|d| d as u64),
+                    result: row.get(9)?,
+                    error: row.get(10)?,
+                    metadata: serde_json::from_str(&metadata).unwrap_or(Value::Null),
+                })
+            },
+        );
+        match result {
+            Ok(rollout) => Ok(Some(rollout)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Number of rollouts in each status
+    pub fn count_rollouts_by_status(&self) -> SqliteResult<Vec<(String, i64)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare("SELECT status, COUNT(*) FROM rollouts GROUP BY status ORDER BY status")?;
+        let counts = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        counts.collect()
+    }
+
     pub fn prune_rollouts_before(&self, before: &str) -> SqliteResult<usize> {
         let conn = self.conn();
         // Also clean up associated attempts and spans
@@ -328,6 +394,21 @@ impl Database {
         }
     }
 
+    pub fn get_resource_bundle(&self, version_id: &str) -> SqliteResult<Option<ResourceBundle>> {
+        let conn = self.conn();
+        let result = conn.query_row(
+            "SELECT version_id, label, is_active, resources, description, created_at
+             FROM resource_versions WHERE version_id = ?1",
+            [version_id],
+            Self::row_to_resource_bundle,
+        );
+        match result {
+            Ok(bundle) => Ok(Some(bundle)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
     pub fn list_resource_bundles(&self) -> SqliteResult<Vec<ResourceBundle>> {
         let conn = self.conn();
         let mut stmt = conn.prepare(
@@ -373,6 +454,7 @@ fn parse_span_type(s: &str) -> SpanType {
         "rollout" => SpanType::Rollout,
         "watchdog" => SpanType::Watchdog,
         "resource_resolution" => SpanType::ResourceResolution,
+        "hook" => SpanType::Hook,
         _ => SpanType::Annotation,
     }
 }
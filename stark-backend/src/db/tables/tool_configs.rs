@@ -164,10 +164,12 @@ impl Database {
     pub fn log_tool_execution(&self, execution: &ToolExecution) -> SqliteResult<i64> {
         let conn = self.conn();
         let params_json = serde_json::to_string(&execution.parameters).unwrap_or_default();
+        let structured_json = execution.structured_result.as_ref()
+            .and_then(|s| serde_json::to_string(s).ok());
 
         conn.execute(
-            "INSERT INTO tool_executions (channel_id, session_id, tool_name, parameters, success, result, duration_ms, executed_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            "INSERT INTO tool_executions (channel_id, session_id, tool_name, parameters, success, result, duration_ms, executed_at, structured_result)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
             rusqlite::params![
                 execution.channel_id,
                 None::<i64>, // session_id could be added if needed
@@ -176,13 +178,24 @@ impl Database {
                 execution.success as i32,
                 execution.result,
                 execution.duration_ms,
-                execution.executed_at
+                execution.executed_at,
+                structured_json
             ],
         )?;
 
         Ok(conn.last_insert_rowid())
     }
 
+    /// Logged tool executions per (tool, success)
+    pub fn count_tool_executions(&self) -> SqliteResult<Vec<(String, bool, i64)>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT tool_name, success, COUNT(*) FROM tool_executions GROUP BY tool_name, success ORDER BY tool_name, success",
+        )?;
+        let counts = stmt.query_map([], |row| Ok((row.get(0)?, row.get::<_, i32>(1)? != 0, row.get(2)?)))?;
+        counts.collect()
+    }
+
     /// Get tool execution history for a channel
     pub fn get_tool_execution_history(
         &self,
@@ -192,7 +205,7 @@ impl Database {
     ) -> SqliteResult<Vec<ToolExecution>> {
         let conn = self.conn();
         let mut stmt = conn.prepare(
-            "SELECT id, channel_id, tool_name, parameters, success, result, duration_ms, executed_at
+            "SELECT id, channel_id, tool_name, parameters, success, result, duration_ms, executed_at, structured_result
              FROM tool_executions WHERE channel_id = ?1 ORDER BY executed_at DESC LIMIT ?2 OFFSET ?3"
         )?;
 
@@ -206,6 +219,8 @@ impl Database {
                     parameters: serde_json::from_str(&params_str).unwrap_or_default(),
                     success: row.get::<_, i32>(4)? != 0,
                     result: row.get(5)?,
+                    structured_result: row.get::<_, Option<String>>(8)?
+                        .and_then(|s| serde_json::from_str(&s).ok()),
                     duration_ms: row.get(6)?,
                     executed_at: row.get(7)?,
                 })
@@ -224,7 +239,7 @@ impl Database {
     ) -> SqliteResult<Vec<ToolExecution>> {
         let conn = self.conn();
         let mut stmt = conn.prepare(
-            "SELECT id, channel_id, tool_name, parameters, success, result, duration_ms, executed_at
+            "SELECT id, channel_id, tool_name, parameters, success, result, duration_ms, executed_at, structured_result
              FROM tool_executions ORDER BY executed_at DESC LIMIT ?1 OFFSET ?2"
         )?;
 
@@ -238,6 +253,8 @@ impl Database {
                     parameters: serde_json::from_str(&params_str).unwrap_or_default(),
                     success: row.get::<_, i32>(4)? != 0,
                     result: row.get(5)?,
+                    structured_result: row.get::<_, Option<String>>(8)?
+                        .and_then(|s| serde_json::from_str(&s).ok()),
                     duration_ms: row.get(6)?,
                     executed_at: row.get(7)?,
                 })
@@ -1,4 +1,4 @@
-//! Database methods for x402_payment_limits table
+//! Database methods for x402_payment_limits and x402_payment_budgets tables
 
 use crate::db::Database;
 use rusqlite::Result as SqliteResult;
@@ -13,6 +13,22 @@ pub struct X402PaymentLimitRow {
     pub address: Option<String>,
 }
 
+/// A single rolling-window budget row.
+#[derive(Debug, Clone)]
+pub struct X402PaymentBudgetRow {
+    pub asset: String,
+    pub max_amount: String,
+    pub window_hours: u32,
+}
+
+/// A non-failed payment from `x402_payments`, used to seed the spend ledger.
+#[derive(Debug, Clone)]
+pub struct X402SpendRow {
+    pub asset: String,
+    pub amount: String,
+    pub created_at: String,
+}
+
 impl Database {
     /// Return all configured payment limits.
     pub fn get_all_x402_payment_limits(&self) -> SqliteResult<Vec<X402PaymentLimitRow>> {
@@ -65,4 +81,68 @@ impl Database {
         )?;
         Ok(affected > 0)
     }
+
+    /// Return all configured rolling-window budgets.
+    pub fn get_all_x402_payment_budgets(&self) -> SqliteResult<Vec<X402PaymentBudgetRow>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT asset, max_amount, window_hours FROM x402_payment_budgets ORDER BY asset",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(X402PaymentBudgetRow {
+                asset: row.get(0)?,
+                max_amount: row.get(1)?,
+                window_hours: row.get::<_, i64>(2)? as u32,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Upsert a rolling-window budget.
+    pub fn set_x402_payment_budget(
+        &self,
+        asset: &str,
+        max_amount: &str,
+        window_hours: u32,
+    ) -> SqliteResult<()> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO x402_payment_budgets (asset, max_amount, window_hours, updated_at)
+             VALUES (?1, ?2, ?3, datetime('now'))
+             ON CONFLICT(asset) DO UPDATE SET
+                max_amount = excluded.max_amount,
+                window_hours = excluded.window_hours,
+                updated_at = datetime('now')",
+            rusqlite::params![asset.to_uppercase(), max_amount, window_hours as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Delete a rolling-window budget.
+    pub fn delete_x402_payment_budget(&self, asset: &str) -> SqliteResult<bool> {
+        let conn = self.conn();
+        let affected = conn.execute(
+            "DELETE FROM x402_payment_budgets WHERE asset = ?1",
+            [asset.to_uppercase()],
+        )?;
+        Ok(affected > 0)
+    }
+
+    /// Return all non-failed payments made within the last `hours` hours.
+    pub fn get_x402_spends_since_hours(&self, hours: u32) -> SqliteResult<Vec<X402SpendRow>> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT asset, amount, created_at FROM x402_payments
+             WHERE status != 'failed' AND created_at >= datetime('now', ?1)
+             ORDER BY created_at",
+        )?;
+        let rows = stmt.query_map([format!("-{} hours", hours)], |row| {
+            Ok(X402SpendRow {
+                asset: row.get(0)?,
+                amount: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })?;
+        rows.collect()
+    }
 }
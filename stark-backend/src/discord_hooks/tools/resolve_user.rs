@@ -52,6 +52,7 @@ impl DiscordResolveUserTool {
                 },
                 group: ToolGroup::Messaging,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -415,6 +415,7 @@ mod tests {
             reputation: None,
             discovered_at: "2024-01-01".to_string(),
             last_updated: "2024-01-01".to_string(),
+            metadata_stale: false,
         };
 
         assert!(criteria.matches(&agent));
@@ -443,6 +444,7 @@ mod tests {
             }),
             discovered_at: "2024-01-01".to_string(),
             last_updated: "2024-01-01".to_string(),
+            metadata_stale: false,
         };
 
         index.upsert(&agent);
@@ -4,6 +4,7 @@
 
 use super::abi::identity::*;
 use super::config::Eip8004Config;
+use super::metadata_cache::{self, MetadataLookup};
 use super::types::*;
 use crate::x402::X402EvmRpc;
 use ethers::types::Address;
@@ -162,11 +163,12 @@ impl IdentityRegistry {
         let wallet = self.get_agent_wallet(agent_id).await.ok();
 
         // Fetch and parse registration file if URI is available
-        let registration = if let Some(ref uri) = uri {
-            self.fetch_registration(uri).await.ok()
-        } else {
-            None
+        let lookup = match uri {
+            Some(ref uri) => self.get_registration_cached(agent_id, uri).await.ok(),
+            None => None,
         };
+        let metadata_stale = lookup.as_ref().is_some_and(|l| l.stale);
+        let registration = lookup.map(|l| l.registration);
 
         let now = chrono::Utc::now().to_rfc3339();
 
@@ -178,9 +180,41 @@ impl IdentityRegistry {
             reputation: None, // Filled in by discovery module
             discovered_at: now.clone(),
             last_updated: now,
+            metadata_stale,
         })
     }
 
+    /// Get an agent's registration file through the metadata cache.
+    /// Fresh entries are served without a fetch; if a refetch fails and an
+    /// expired entry exists, it is returned with `stale` set.
+    pub async fn get_registration_cached(&self, agent_id: u64, uri: &str) -> Result<MetadataLookup, String> {
+        let registry = self.config.agent_registry_string();
+        let ttl = metadata_cache::metadata_ttl();
+
+        if let Some(registration) = metadata_cache::get_fresh(agent_id, &registry, uri, ttl) {
+            return Ok(MetadataLookup { registration, stale: false });
+        }
+
+        match self.fetch_registration(uri).await {
+            Ok(registration) => {
+                if !ttl.is_zero() {
+                    metadata_cache::insert(agent_id, &registry, uri, registration.clone());
+                }
+                Ok(MetadataLookup { registration, stale: false })
+            }
+            Err(e) => match metadata_cache::get_stale(agent_id, &registry, uri) {
+                Some(registration) => {
+                    log::warn!(
+                        "[eip8004] Serving stale metadata for agent {} ({}): {}",
+                        agent_id, registry, e
+                    );
+                    Ok(MetadataLookup { registration, stale: true })
+                }
+                None => Err(e),
+            },
+        }
+    }
+
     /// Fetch and parse registration file from URI
     pub async fn fetch_registration(&self, uri: &str) -> Result<RegistrationFile, String> {
         let url = self.resolve_uri(uri);
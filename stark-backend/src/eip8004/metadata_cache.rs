@@ -0,0 +1,134 @@
+//! Registration metadata cache
+//!
+//! Registration files are fetched from each agent's `registration_uri` (IPFS,
+//! Arweave or HTTP), which is slow and rate-limited. Resolved files are cached
+//! in memory keyed by (agent_id, agent_registry). When a refetch fails, the last
+//! good copy is served and flagged as stale.
+
+use super::types::RegistrationFile;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Default time-to-live for cached registration files
+const DEFAULT_TTL_SECS: u64 = 600;
+
+struct CachedRegistration {
+    uri: String,
+    registration: RegistrationFile,
+    fetched_at: Instant,
+}
+
+type CacheKey = (u64, String);
+type MetadataStore = Mutex<HashMap<CacheKey, CachedRegistration>>;
+
+static CACHE: OnceLock<MetadataStore> = OnceLock::new();
+
+fn store() -> &'static MetadataStore {
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Cache TTL, from `EIP8004_METADATA_TTL_SECS` (0 disables caching)
+pub fn metadata_ttl() -> Duration {
+    let secs = std::env::var("EIP8004_METADATA_TTL_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_TTL_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Result of a cached metadata lookup
+#[derive(Debug, Clone)]
+pub struct MetadataLookup {
+    pub registration: RegistrationFile,
+    /// True when the fetch failed and an expired cache entry was served instead
+    pub stale: bool,
+}
+
+/// Cache statistics for the API
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MetadataCacheStats {
+    pub entries: usize,
+    pub expired: usize,
+    pub ttl_secs: u64,
+}
+
+/// Get a cached registration if it was fetched from `uri` within `ttl`
+pub fn get_fresh(agent_id: u64, registry: &str, uri: &str, ttl: Duration) -> Option<RegistrationFile> {
+    let map = store().lock().unwrap();
+    map.get(&(agent_id, registry.to_string()))
+        .filter(|e| e.uri == uri && e.fetched_at.elapsed() < ttl)
+        .map(|e| e.registration.clone())
+}
+
+/// Get a cached registration for `uri` regardless of age
+pub fn get_stale(agent_id: u64, registry: &str, uri: &str) -> Option<RegistrationFile> {
+    let map = store().lock().unwrap();
+    map.get(&(agent_id, registry.to_string()))
+        .filter(|e| e.uri == uri)
+        .map(|e| e.registration.clone())
+}
+
+/// Store a freshly fetched registration
+pub fn insert(agent_id: u64, registry: &str, uri: &str, registration: RegistrationFile) {
+    let mut map = store().lock().unwrap();
+    map.insert(
+        (agent_id, registry.to_string()),
+        CachedRegistration {
+            uri: uri.to_string(),
+            registration,
+            fetched_at: Instant::now(),
+        },
+    );
+}
+
+/// Drop cached entries. With an agent id, only that agent's entries (across
+/// registries, or just `registry` if given) are removed. Returns the number removed.
+pub fn invalidate(agent_id: Option<u64>, registry: Option<&str>) -> usize {
+    let mut map = store().lock().unwrap();
+    let before = map.len();
+    map.retain(|(id, reg), _| {
+        let id_matches = agent_id.is_none_or(|a| a == *id);
+        let reg_matches = registry.is_none_or(|r| r.eq_ignore_ascii_case(reg));
+        !(id_matches && reg_matches)
+    });
+    before - map.len()
+}
+
+/// Current cache size and TTL
+pub fn stats() -> MetadataCacheStats {
+    let ttl = metadata_ttl();
+    let map = store().lock().unwrap();
+    MetadataCacheStats {
+        entries: map.len(),
+        expired: map.values().filter(|e| e.fetched_at.elapsed() >= ttl).count(),
+        ttl_secs: ttl.as_secs(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_lookup_and_invalidate() {
+        // Use agent ids no other test touches; the cache is process-wide
+        let registry = "eip155:8453:0xtest";
+        let uri = "ipfs://QmCacheTest";
+        insert(900_001, registry, uri, RegistrationFile::new("CacheBot", "cached"));
+        insert(900_002, registry, uri, RegistrationFile::new("OtherBot", "cached"));
+
+        let ttl = Duration::from_secs(60);
+        assert_eq!(get_fresh(900_001, registry, uri, ttl).unwrap().name, "CacheBot");
+        // A changed URI on-chain is a miss
+        assert!(get_fresh(900_001, registry, "ipfs://QmNew", ttl).is_none());
+        // Expired entries are only served as stale
+        assert!(get_fresh(900_001, registry, uri, Duration::ZERO).is_none());
+        assert!(get_stale(900_001, registry, uri).is_some());
+
+        assert_eq!(invalidate(Some(900_001), None), 1);
+        assert!(get_stale(900_001, registry, uri).is_none());
+        assert!(get_stale(900_002, registry, uri).is_some());
+        invalidate(Some(900_002), Some(registry));
+    }
+}
@@ -13,6 +13,8 @@ pub mod identity;
 pub mod reputation;
 pub mod discovery;
 pub mod config;
+pub mod metadata_cache;
+pub mod verification;
 
 pub use types::*;
 pub use config::Eip8004Config;
@@ -256,6 +256,9 @@ pub struct DiscoveredAgent {
     pub reputation: Option<ReputationSummary>,
     pub discovered_at: String,
     pub last_updated: String,
+    /// Registration was served from an expired cache entry because the refetch failed
+    #[serde(default)]
+    pub metadata_stale: bool,
 }
 
 impl DiscoveredAgent {
@@ -0,0 +1,345 @@
+//! Registration verification
+//!
+//! Checks that our agent's registration (services and supported-trust claims)
+//! is well-formed and consistent with on-chain registry state before it is
+//! advertised to other agents.
+
+use super::config::Eip8004Config;
+use super::identity::IdentityRegistry;
+use super::types::{RegistrationFile, ServiceEntry};
+use crate::db::sqlite::AgentIdentityRow;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// Trust models defined by EIP-8004
+pub const KNOWN_TRUST_MODELS: &[&str] = &[
+    "reputation",
+    "crypto-economic",
+    "tee-attestation",
+    "x402-payments",
+];
+
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// A single verification check
+#[derive(Debug, Clone, Serialize)]
+pub struct VerificationCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+/// Structured verification report
+#[derive(Debug, Clone, Serialize)]
+pub struct VerificationReport {
+    pub agent_id: i64,
+    pub agent_registry: String,
+    /// True when no check failed (warnings are allowed)
+    pub well_formed: bool,
+    pub passed: usize,
+    pub warnings: usize,
+    pub failed: usize,
+    pub checks: Vec<VerificationCheck>,
+}
+
+impl VerificationReport {
+    fn new(agent_id: i64, agent_registry: &str) -> Self {
+        Self {
+            agent_id,
+            agent_registry: agent_registry.to_string(),
+            well_formed: true,
+            passed: 0,
+            warnings: 0,
+            failed: 0,
+            checks: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, name: &str, status: CheckStatus, message: impl Into<String>) {
+        match status {
+            CheckStatus::Pass => self.passed += 1,
+            CheckStatus::Warn => self.warnings += 1,
+            CheckStatus::Fail => {
+                self.failed += 1;
+                self.well_formed = false;
+            }
+        }
+        self.checks.push(VerificationCheck {
+            name: name.to_string(),
+            status,
+            message: message.into(),
+        });
+    }
+
+    fn pass(&mut self, name: &str, message: impl Into<String>) {
+        self.push(name, CheckStatus::Pass, message);
+    }
+
+    fn warn(&mut self, name: &str, message: impl Into<String>) {
+        self.push(name, CheckStatus::Warn, message);
+    }
+
+    fn fail(&mut self, name: &str, message: impl Into<String>) {
+        self.push(name, CheckStatus::Fail, message);
+    }
+}
+
+/// Check the stored services and trust claims without touching the chain
+pub fn verify_local(row: &AgentIdentityRow, config: &Eip8004Config) -> VerificationReport {
+    let mut report = VerificationReport::new(row.agent_id, &row.agent_registry);
+
+    let services = match serde_json::from_str::<Vec<ServiceEntry>>(&row.services_json) {
+        Ok(s) => {
+            report.pass("services_schema", format!("{} service(s) parsed", s.len()));
+            Some(s)
+        }
+        Err(e) => {
+            report.fail("services_schema", format!("services_json is not a valid service list: {}", e));
+            None
+        }
+    };
+
+    if let Some(ref services) = services {
+        check_services(&mut report, services);
+    }
+
+    let trust = match serde_json::from_str::<Vec<String>>(&row.supported_trust_json) {
+        Ok(t) => t,
+        Err(e) => {
+            report.fail("trust_schema", format!("supported_trust_json is not a list of strings: {}", e));
+            return report;
+        }
+    };
+
+    if trust.is_empty() {
+        report.warn("trust_schema", "No trust models claimed");
+    } else {
+        report.pass("trust_schema", format!("Claims: {}", trust.join(", ")));
+    }
+
+    for claim in &trust {
+        check_trust_claim(&mut report, claim, row, services.as_deref().unwrap_or(&[]), config);
+    }
+
+    report
+}
+
+fn check_services(report: &mut VerificationReport, services: &[ServiceEntry]) {
+    let mut seen = HashSet::new();
+    for service in services {
+        let label = format!("service:{}", service.name);
+        if service.name.trim().is_empty() {
+            report.fail(&label, "Service has an empty name");
+            continue;
+        }
+        if !seen.insert(service.name.to_lowercase()) {
+            report.fail(&label, "Duplicate service name");
+            continue;
+        }
+        if !(service.endpoint.starts_with("https://") || service.endpoint.starts_with("http://")) {
+            report.fail(&label, format!("Endpoint '{}' is not an HTTP(S) URL", service.endpoint));
+        } else if service.endpoint.starts_with("http://") {
+            report.warn(&label, "Endpoint is not served over HTTPS");
+        } else if service.version.trim().is_empty() {
+            report.warn(&label, "Service has no version");
+        } else {
+            report.pass(&label, service.endpoint.clone());
+        }
+    }
+}
+
+fn check_trust_claim(
+    report: &mut VerificationReport,
+    claim: &str,
+    row: &AgentIdentityRow,
+    services: &[ServiceEntry],
+    config: &Eip8004Config,
+) {
+    let label = format!("trust:{}", claim);
+    match claim {
+        "reputation" => {
+            if config.is_reputation_deployed() {
+                report.pass(&label, format!("Reputation Registry deployed at {}", config.reputation_registry));
+            } else {
+                report.fail(&label, format!("No Reputation Registry is deployed on {}", config.chain_name));
+            }
+        }
+        "x402-payments" => {
+            if !row.x402_support {
+                report.fail(&label, "Claims x402 payments but x402Support is false");
+            } else if !services.iter().any(|s| s.name.eq_ignore_ascii_case("x402")) {
+                report.warn(&label, "x402Support is set but no 'x402' service endpoint is listed");
+            } else {
+                report.pass(&label, "x402Support set with an x402 service endpoint");
+            }
+        }
+        "crypto-economic" | "tee-attestation" => {
+            if config.is_validation_deployed() {
+                report.warn(&label, "Validation Registry deployed, but validations are not checked by this bot");
+            } else {
+                report.fail(&label, format!("No Validation Registry is deployed on {}", config.chain_name));
+            }
+        }
+        _ => {
+            report.warn(&label, format!(
+                "Unknown trust model (expected one of: {})",
+                KNOWN_TRUST_MODELS.join(", ")
+            ));
+        }
+    }
+}
+
+/// Run the local checks, then compare against on-chain registry state and
+/// the hosted registration file.
+pub async fn verify_registration(
+    row: &AgentIdentityRow,
+    config: &Eip8004Config,
+    registry: &IdentityRegistry,
+) -> VerificationReport {
+    let mut report = verify_local(row, config);
+
+    if row.agent_id <= 0 {
+        report.fail("onchain:registered", "Identity is not registered on-chain (no agent id)");
+        return report;
+    }
+    let agent_id = row.agent_id as u64;
+
+    let expected_registry = config.agent_registry_string();
+    if row.agent_registry.eq_ignore_ascii_case(&expected_registry) {
+        report.pass("onchain:registry", expected_registry);
+    } else {
+        report.fail("onchain:registry", format!(
+            "Stored registry {} does not match configured registry {}",
+            row.agent_registry, expected_registry
+        ));
+    }
+
+    match registry.get_owner(agent_id).await {
+        Ok(owner) if !owner.contains(ZERO_ADDRESS) => report.pass("onchain:owner", owner),
+        Ok(_) => report.fail("onchain:owner", format!("Agent #{} has no owner (burned or never minted)", agent_id)),
+        Err(e) => report.fail("onchain:owner", format!("Could not read owner: {}", e)),
+    }
+
+    match registry.get_agent_wallet(agent_id).await {
+        Ok(wallet) if !wallet.contains(ZERO_ADDRESS) => report.pass("onchain:wallet", wallet),
+        Ok(_) if row.x402_support => report.fail("onchain:wallet", "x402Support is set but no agent wallet is registered to receive payments"),
+        Ok(_) => report.warn("onchain:wallet", "No agent wallet registered"),
+        Err(e) => report.warn("onchain:wallet", format!("Could not read agent wallet: {}", e)),
+    }
+
+    let onchain_uri = match registry.get_agent_uri(agent_id).await {
+        Ok(uri) => uri,
+        Err(e) => {
+            report.fail("onchain:uri", format!("Could not read agent URI: {}", e));
+            return report;
+        }
+    };
+    match row.registration_uri.as_deref() {
+        Some(local) if local == onchain_uri => report.pass("onchain:uri", onchain_uri.clone()),
+        Some(local) => report.fail("onchain:uri", format!(
+            "Local registration URI {} does not match on-chain URI {}",
+            local, onchain_uri
+        )),
+        None => report.warn("onchain:uri", format!("No local registration URI; on-chain URI is {}", onchain_uri)),
+    }
+
+    // Always fetch fresh: a cached copy could hide a bad re-upload
+    match registry.fetch_registration(&onchain_uri).await {
+        Ok(hosted) => compare_hosted(&mut report, row, &hosted),
+        Err(e) => report.fail("hosted:fetch", format!("Could not fetch registration file: {}", e)),
+    }
+
+    report
+}
+
+/// Compare the hosted registration file against what we have stored locally
+fn compare_hosted(report: &mut VerificationReport, row: &AgentIdentityRow, hosted: &RegistrationFile) {
+    let local = row.to_registration_file();
+
+    let local_trust: HashSet<&str> = local.supported_trust.iter().map(|s| s.as_str()).collect();
+    let hosted_trust: HashSet<&str> = hosted.supported_trust.iter().map(|s| s.as_str()).collect();
+    if local_trust == hosted_trust {
+        report.pass("hosted:trust", "Hosted supportedTrust matches local claims");
+    } else {
+        report.fail("hosted:trust", format!(
+            "Hosted supportedTrust [{}] differs from local [{}]",
+            hosted.supported_trust.join(", "),
+            local.supported_trust.join(", ")
+        ));
+    }
+
+    let local_services: HashSet<(String, &str)> =
+        local.services.iter().map(|s| (s.name.to_lowercase(), s.endpoint.as_str())).collect();
+    let hosted_services: HashSet<(String, &str)> =
+        hosted.services.iter().map(|s| (s.name.to_lowercase(), s.endpoint.as_str())).collect();
+    if local_services == hosted_services {
+        report.pass("hosted:services", format!("{} service(s) match", hosted.services.len()));
+    } else {
+        report.fail("hosted:services", "Hosted services differ from local services; re-upload the registration file");
+    }
+
+    if hosted.x402_support == row.x402_support {
+        report.pass("hosted:x402", format!("x402Support = {}", hosted.x402_support));
+    } else {
+        report.fail("hosted:x402", format!(
+            "Hosted x402Support ({}) differs from local ({})",
+            hosted.x402_support, row.x402_support
+        ));
+    }
+
+    if !hosted.active {
+        report.warn("hosted:active", "Hosted registration is marked inactive");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(services_json: &str, supported_trust_json: &str, x402_support: bool) -> AgentIdentityRow {
+        AgentIdentityRow {
+            agent_id: 7,
+            agent_registry: "eip155:8453:0xa23a42d266653846e05d8f356a52298844537472".to_string(),
+            chain_id: 8453,
+            name: Some("TestBot".to_string()),
+            description: None,
+            image: None,
+            x402_support,
+            active: true,
+            services_json: services_json.to_string(),
+            supported_trust_json: supported_trust_json.to_string(),
+            registration_uri: None,
+        }
+    }
+
+    #[test]
+    fn test_verify_local_claims() {
+        let config = Eip8004Config::base_mainnet();
+        let services = r#"[{"name":"x402","endpoint":"https://bot.example/x402","version":"1.0"}]"#;
+
+        let report = verify_local(&row(services, r#"["x402-payments"]"#, true), &config);
+        assert!(report.well_formed, "{:?}", report.checks);
+
+        // Claiming x402 with x402Support off is a failure
+        let report = verify_local(&row(services, r#"["x402-payments"]"#, false), &config);
+        assert!(!report.well_formed);
+
+        // Mainnet config has no reputation registry deployed
+        let report = verify_local(&row(services, r#"["reputation"]"#, true), &config);
+        assert!(report.checks.iter().any(|c| c.name == "trust:reputation" && c.status == CheckStatus::Fail));
+
+        // Unknown models warn, malformed JSON fails
+        let report = verify_local(&row(services, r#"["vibes"]"#, true), &config);
+        assert!(report.well_formed && report.warnings == 1);
+        assert!(!verify_local(&row("{}", "[]", true), &config).well_formed);
+    }
+}
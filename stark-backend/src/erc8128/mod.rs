@@ -1,5 +1,6 @@
 pub mod signer;
 pub mod types;
+pub mod validation;
 
 pub use signer::Erc8128Signer;
 pub use types::Erc8128SignedHeaders;
@@ -9,6 +9,7 @@ use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use chrono::Utc;
 
 use super::types::{content_digest_sha256, Erc8128SignedHeaders};
+use super::validation::validate_request;
 use crate::wallet::WalletProvider;
 
 /// Signs outgoing HTTP requests per ERC-8128 (RFC 9421 + ERC-191).
@@ -37,6 +38,8 @@ impl Erc8128Signer {
     /// * `path`      — Path component, e.g. `"/v1/data"`
     /// * `query`     — Query string WITHOUT leading `?`, or `None`
     /// * `body`      — Request body bytes (for POST/PUT), or `None`
+    ///
+    /// Inputs are checked against the limits in `erc8128::validation` first.
     pub async fn sign_request(
         &self,
         method: &str,
@@ -45,6 +48,8 @@ impl Erc8128Signer {
         query: Option<&str>,
         body: Option<&[u8]>,
     ) -> Result<Erc8128SignedHeaders, String> {
+        validate_request(method, authority, path, query, body)?;
+
         // 1. Content-Digest (only when body is present)
         let content_digest = body.map(|b| content_digest_sha256(b));
 
@@ -265,4 +270,14 @@ mod tests {
         let decoded = BASE64.decode(b64).unwrap();
         assert_eq!(decoded.len(), 65, "ECDSA signature must be 65 bytes (r+s+v)");
     }
+
+    #[tokio::test]
+    async fn test_sign_rejects_invalid_input() {
+        let signer = Erc8128Signer::new(test_wallet(), 1);
+        let err = signer
+            .sign_request("GET", "example.com", "/a\nb", None, None)
+            .await
+            .unwrap_err();
+        assert!(err.contains("Invalid ERC-8128 request"));
+    }
 }
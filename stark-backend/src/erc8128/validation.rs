@@ -0,0 +1,213 @@
+//! Input validation for ERC-8128 request signing
+//!
+//! Every covered component ends up on its own line of the RFC 9421 signature
+//! base, so values must be size-bounded and free of whitespace and control
+//! characters (a newline in a path would inject an extra line into the base).
+
+/// Largest request body that will be digested and signed
+pub const MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+/// Host (253) + `:` + port (5), with room for IPv6 brackets
+pub const MAX_AUTHORITY_LEN: usize = 262;
+pub const MAX_PATH_LEN: usize = 8 * 1024;
+pub const MAX_QUERY_LEN: usize = 8 * 1024;
+
+const ALLOWED_METHODS: &[&str] = &["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"];
+
+/// Validate the components of a request before it is signed
+pub fn validate_request(
+    method: &str,
+    authority: &str,
+    path: &str,
+    query: Option<&str>,
+    body: Option<&[u8]>,
+) -> Result<(), String> {
+    validate_method(method)?;
+    validate_authority(authority)?;
+    validate_path(path)?;
+    if let Some(q) = query {
+        validate_query(q)?;
+    }
+    if let Some(b) = body {
+        validate_body(b)?;
+    }
+    Ok(())
+}
+
+fn validate_method(method: &str) -> Result<(), String> {
+    if ALLOWED_METHODS.iter().any(|m| m.eq_ignore_ascii_case(method)) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid ERC-8128 request: unsupported method '{}'",
+            truncate_for_error(method)
+        ))
+    }
+}
+
+fn validate_authority(authority: &str) -> Result<(), String> {
+    if authority.is_empty() {
+        return Err("Invalid ERC-8128 request: authority is empty".to_string());
+    }
+    if authority.len() > MAX_AUTHORITY_LEN {
+        return Err(format!(
+            "Invalid ERC-8128 request: authority is {} bytes (max {})",
+            authority.len(),
+            MAX_AUTHORITY_LEN
+        ));
+    }
+
+    // Split off the port, leaving IPv6 literals ("[::1]:8080") intact
+    let (host, port) = match authority.rfind(':') {
+        Some(i) if !authority[i..].contains(']') => (&authority[..i], Some(&authority[i + 1..])),
+        _ => (authority, None),
+    };
+
+    let host_ok = !host.is_empty()
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '[' | ']' | ':'));
+    if !host_ok {
+        return Err(format!(
+            "Invalid ERC-8128 request: malformed authority '{}'",
+            truncate_for_error(authority)
+        ));
+    }
+
+    if let Some(port) = port
+        && port.parse::<u16>().is_err()
+    {
+        return Err(format!(
+            "Invalid ERC-8128 request: invalid port in authority '{}'",
+            truncate_for_error(authority)
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_path(path: &str) -> Result<(), String> {
+    if !path.starts_with('/') {
+        return Err("Invalid ERC-8128 request: path must start with '/'".to_string());
+    }
+    if path.len() > MAX_PATH_LEN {
+        return Err(format!(
+            "Invalid ERC-8128 request: path is {} bytes (max {})",
+            path.len(),
+            MAX_PATH_LEN
+        ));
+    }
+    if !is_visible_ascii(path) {
+        return Err("Invalid ERC-8128 request: path contains whitespace, control or non-ASCII characters (percent-encode them)".to_string());
+    }
+    if path.contains(['?', '#']) {
+        return Err("Invalid ERC-8128 request: path must not contain a query or fragment".to_string());
+    }
+    Ok(())
+}
+
+fn validate_query(query: &str) -> Result<(), String> {
+    if query.starts_with('?') {
+        return Err("Invalid ERC-8128 request: query must not include the leading '?'".to_string());
+    }
+    if query.len() > MAX_QUERY_LEN {
+        return Err(format!(
+            "Invalid ERC-8128 request: query is {} bytes (max {})",
+            query.len(),
+            MAX_QUERY_LEN
+        ));
+    }
+    if !is_visible_ascii(query) || query.contains('#') {
+        return Err("Invalid ERC-8128 request: query contains whitespace, control, fragment or non-ASCII characters".to_string());
+    }
+    Ok(())
+}
+
+fn validate_body(body: &[u8]) -> Result<(), String> {
+    if body.len() > MAX_BODY_BYTES {
+        return Err(format!(
+            "Invalid ERC-8128 request: body is {} bytes (max {})",
+            body.len(),
+            MAX_BODY_BYTES
+        ));
+    }
+    Ok(())
+}
+
+/// Printable ASCII with no spaces (RFC 3986 characters after percent-encoding)
+fn is_visible_ascii(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Keep attacker-controlled values short in error messages
+fn truncate_for_error(s: &str) -> String {
+    const MAX: usize = 64;
+    if s.len() <= MAX {
+        return s.to_string();
+    }
+    let mut end = MAX;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...", &s[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok(method: &str, authority: &str, path: &str, query: Option<&str>) -> bool {
+        validate_request(method, authority, path, query, None).is_ok()
+    }
+
+    #[test]
+    fn test_accepts_well_formed_requests() {
+        assert!(ok("GET", "api.example.com", "/v1/data", None));
+        assert!(ok("post", "localhost:8080", "/", Some("q=test&limit=10")));
+        assert!(ok("GET", "[::1]:8080", "/a%20b", None));
+        assert!(validate_request("POST", "pay.example.com", "/charge", None, Some(b"{}")).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_truncated_inputs() {
+        assert!(!ok("", "api.example.com", "/", None));
+        assert!(!ok("GET", "", "/", None));
+        assert!(!ok("GET", "api.example.com", "", None));
+        assert!(!ok("GET", ":443", "/", None));
+        assert!(!ok("GET", "api.example.com:", "/", None));
+    }
+
+    #[test]
+    fn test_rejects_oversized_inputs() {
+        let long_host = "a".repeat(MAX_AUTHORITY_LEN + 1);
+        assert!(!ok("GET", &long_host, "/", None));
+
+        let long_path = format!("/{}", "a".repeat(MAX_PATH_LEN));
+        assert!(!ok("GET", "example.com", &long_path, None));
+
+        let long_query = "a".repeat(MAX_QUERY_LEN + 1);
+        assert!(!ok("GET", "example.com", "/", Some(&long_query)));
+
+        let body = vec![0u8; MAX_BODY_BYTES + 1];
+        let err = validate_request("POST", "example.com", "/", None, Some(&body)).unwrap_err();
+        assert!(err.contains("body is"));
+
+        // Error messages don't echo the whole oversized value back
+        let err = validate_request(&"X".repeat(10_000), "example.com", "/", None, None).unwrap_err();
+        assert!(err.len() < 200);
+    }
+
+    #[test]
+    fn test_rejects_malformed_inputs() {
+        assert!(!ok("TRACE", "example.com", "/", None));
+        assert!(!ok("GET", "exa mple.com", "/", None));
+        assert!(!ok("GET", "example.com/evil", "/", None));
+        assert!(!ok("GET", "example.com:99999", "/", None));
+        assert!(!ok("GET", "example.com", "v1/data", None));
+        // Newlines would inject lines into the signature base
+        assert!(!ok("GET", "example.com", "/a\n\"@method\": POST", None));
+        assert!(!ok("GET", "example.com", "/a?b=c", None));
+        assert!(!ok("GET", "example.com", "/", Some("?q=1")));
+        assert!(!ok("GET", "example.com", "/", Some("q=1#frag")));
+        assert!(!ok("GET", "example.com", "/caf\u{e9}", None));
+    }
+}
@@ -5,14 +5,19 @@
 //! display of execution progress (similar to Claude Code's CLI display).
 //!
 //! Also provides session lane serialization to prevent race conditions when
-//! multiple requests arrive for the same session.
+//! multiple requests arrive for the same session, records per-session
+//! spend (x402 payments, AI tokens, gas) to the spend ledger, and holds
+//! planned task lists until the user approves them.
 
 mod tracker;
 mod pending_confirmation;
+pub mod plan_approval;
 mod process_manager;
 mod session_lanes;
+pub mod spend_ledger;
 
 pub use tracker::ExecutionTracker;
 pub use pending_confirmation::{PendingConfirmation, PendingConfirmationManager};
+pub use plan_approval::{PlanApprovalManager, PlanApprovalOutcome, PlanApprovalPolicy};
 pub use process_manager::{ProcessInfo, ProcessManager, ProcessStatus};
 pub use session_lanes::{SessionLaneGuard, SessionLaneManager, SessionLaneStats};
@@ -1,8 +1,10 @@
 //! Pending confirmation tracking for tool executions
 //!
 //! Tracks tool calls that require user confirmation before execution.
-//! Used for high-risk operations like token transfers and swaps.
+//! Used for high-risk operations like token transfers and swaps, and for
+//! tools whose cost estimate needs the user's approval.
 
+use crate::tools::CostEstimate;
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -43,6 +45,9 @@ pub struct PendingConfirmation {
     pub requested_at: Option<Instant>,
     /// User who initiated the action
     pub user_id: String,
+    /// Expected cost, when confirmation was requested because of a tool's cost estimate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_cost: Option<CostEstimate>,
 }
 
 impl PendingConfirmation {
@@ -65,9 +70,15 @@ impl PendingConfirmation {
             description,
             requested_at: Some(Instant::now()),
             user_id,
+            estimated_cost: None,
         }
     }
 
+    /// Whether this confirmation covers the given tool call
+    fn matches(&self, tool_name: &str, arguments: &Value) -> bool {
+        self.tool_name == tool_name && &self.arguments == arguments
+    }
+
     /// Build a human-readable description of the pending action
     fn build_description(tool_name: &str, arguments: &Value) -> String {
         match tool_name {
@@ -156,12 +167,16 @@ pub struct PendingConfirmationManager {
     /// Map of channel_id -> pending confirmation
     /// Only one pending confirmation per channel at a time
     pending: DashMap<i64, PendingConfirmation>,
+    /// Map of channel_id -> approved cost confirmation, consumed when the
+    /// agent retries the same tool call
+    approved: DashMap<i64, PendingConfirmation>,
 }
 
 impl PendingConfirmationManager {
     pub fn new() -> Self {
         Self {
             pending: DashMap::new(),
+            approved: DashMap::new(),
         }
     }
 
@@ -204,6 +219,54 @@ impl PendingConfirmationManager {
         result
     }
 
+    /// Add a pending confirmation for a tool call with a cost estimate
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_pending_cost(
+        &self,
+        channel_id: i64,
+        session_id: i64,
+        tool_name: String,
+        tool_call_id: String,
+        arguments: Value,
+        user_id: String,
+        estimate: CostEstimate,
+    ) -> PendingConfirmation {
+        let mut confirmation = PendingConfirmation::new(
+            channel_id,
+            session_id,
+            tool_name,
+            tool_call_id,
+            arguments,
+            user_id,
+        );
+        confirmation.description = format!(
+            "Run {}: {} ({})",
+            confirmation.tool_name,
+            estimate.display(),
+            estimate.description
+        );
+        confirmation.estimated_cost = Some(estimate);
+        let result = confirmation.clone();
+        self.pending.insert(channel_id, confirmation);
+        result
+    }
+
+    /// Approve the pending confirmation for a channel so the same tool call
+    /// runs when the agent retries it. Returns the approved confirmation.
+    pub fn approve(&self, channel_id: i64) -> Option<PendingConfirmation> {
+        let mut confirmation = self.confirm(channel_id)?;
+        confirmation.requested_at = Some(Instant::now());
+        self.approved.insert(channel_id, confirmation.clone());
+        Some(confirmation)
+    }
+
+    /// Consume an approval for this exact tool call, if one was given
+    pub fn take_approval(&self, channel_id: i64, tool_name: &str, arguments: &Value) -> bool {
+        self.approved
+            .remove_if(&channel_id, |_, c| c.matches(tool_name, arguments))
+            .is_some_and(|(_, c)| !c.is_expired())
+    }
+
     /// Get pending confirmation for a channel (if not expired)
     pub fn get_pending(&self, channel_id: i64) -> Option<PendingConfirmation> {
         if let Some(entry) = self.pending.get(&channel_id) {
@@ -242,6 +305,7 @@ impl PendingConfirmationManager {
     /// Clean up expired confirmations
     pub fn cleanup_expired(&self) {
         self.pending.retain(|_, v| !v.is_expired());
+        self.approved.retain(|_, v| !v.is_expired());
     }
 }
 
@@ -262,6 +326,30 @@ mod tests {
         assert!(!PendingConfirmationManager::requires_confirmation("exec"));
     }
 
+    #[test]
+    fn test_cost_approval_covers_only_the_same_call() {
+        let manager = PendingConfirmationManager::new();
+        let args = serde_json::json!({ "url": "https://api.example.com" });
+        let estimate = CostEstimate {
+            amount: 1.0,
+            currency: "USDC".to_string(),
+            is_upper_bound: true,
+            description: "x402 payment".to_string(),
+        };
+        let pending = manager.add_pending_cost(1, 2, "x402_post".to_string(), String::new(), args.clone(), "u".to_string(), estimate);
+        assert_eq!(pending.description, "Run x402_post: up to 1.00 USDC (x402 payment)");
+
+        // Nothing is approved until the user confirms
+        assert!(!manager.take_approval(1, "x402_post", &args));
+        assert!(manager.approve(1).is_some());
+        assert!(!manager.has_pending(1));
+
+        assert!(!manager.take_approval(1, "x402_post", &serde_json::json!({ "url": "https://other" })));
+        assert!(manager.take_approval(1, "x402_post", &args));
+        // Approvals are single-use
+        assert!(!manager.take_approval(1, "x402_post", &args));
+    }
+
     #[test]
     fn test_wei_to_eth() {
         assert_eq!(PendingConfirmation::wei_to_eth("0"), "0");
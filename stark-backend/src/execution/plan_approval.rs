@@ -0,0 +1,166 @@
+//! Plan approval tracking
+//!
+//! When plan approval is on, the task list produced by the planner is shown to
+//! the user before any of it runs. The dispatch waits here until the user
+//! approves or rejects the plan (from the web UI or by replying in chat), or
+//! until the approval times out.
+
+use dashmap::DashMap;
+use serde::Serialize;
+use tokio::sync::oneshot;
+
+/// Whether a channel asks before executing a plan, and what happens if nobody answers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlanApprovalPolicy {
+    /// Execute plans without asking
+    #[default]
+    Off,
+    /// Ask; execute the plan if the approval times out
+    ProceedOnTimeout,
+    /// Ask; drop the plan if the approval times out
+    CancelOnTimeout,
+}
+
+impl PlanApprovalPolicy {
+    /// Parse the `plan_approval` channel setting ("off", "proceed" or "cancel")
+    pub fn from_str_or_default(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "proceed" => Self::ProceedOnTimeout,
+            "cancel" => Self::CancelOnTimeout,
+            _ => Self::Off,
+        }
+    }
+
+    /// Whether the plan goes ahead after the approval timed out
+    pub fn proceeds_on_timeout(&self) -> bool {
+        matches!(self, Self::ProceedOnTimeout)
+    }
+}
+
+/// How a plan approval was resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanApprovalOutcome {
+    Approved,
+    Rejected,
+    TimedOut,
+}
+
+impl PlanApprovalOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Approved => "approved",
+            Self::Rejected => "rejected",
+            Self::TimedOut => "timed_out",
+        }
+    }
+}
+
+/// Read a chat reply to a pending plan: Some(true) to approve, Some(false) to reject
+pub fn parse_reply(text: &str) -> Option<bool> {
+    let reply = text.trim().trim_start_matches('/').trim_end_matches(['.', '!']).to_lowercase();
+    match reply.as_str() {
+        "approve" | "approved" | "yes" | "y" | "ok" | "go" | "go ahead" => Some(true),
+        "reject" | "rejected" | "no" | "n" | "cancel" | "stop" => Some(false),
+        _ => None,
+    }
+}
+
+struct PendingPlan {
+    /// User whose message produced the plan; only they can answer it from chat
+    user_id: String,
+    decision: oneshot::Sender<bool>,
+}
+
+/// Plans waiting for the user's approval, one per channel
+pub struct PlanApprovalManager {
+    pending: DashMap<i64, PendingPlan>,
+}
+
+impl PlanApprovalManager {
+    pub fn new() -> Self {
+        Self {
+            pending: DashMap::new(),
+        }
+    }
+
+    /// Start waiting for a decision on a channel's plan. Replaces (and so
+    /// rejects) any plan already waiting there.
+    pub fn request(&self, channel_id: i64, user_id: &str) -> oneshot::Receiver<bool> {
+        let (tx, rx) = oneshot::channel();
+        self.pending.insert(channel_id, PendingPlan {
+            user_id: user_id.to_string(),
+            decision: tx,
+        });
+        rx
+    }
+
+    /// Approve or reject a channel's pending plan. Returns false if none is waiting.
+    pub fn resolve(&self, channel_id: i64, approved: bool) -> bool {
+        match self.pending.remove(&channel_id) {
+            Some((_, plan)) => plan.decision.send(approved).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Approve or reject a pending plan on behalf of a chat user. Only the user
+    /// who asked for the plan can answer it.
+    pub fn resolve_for_user(&self, channel_id: i64, user_id: &str, approved: bool) -> bool {
+        match self.pending.remove_if(&channel_id, |_, plan| plan.user_id == user_id) {
+            Some((_, plan)) => plan.decision.send(approved).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Stop waiting on a channel (the dispatch gave up on the approval)
+    pub fn clear(&self, channel_id: i64) {
+        self.pending.remove(&channel_id);
+    }
+
+    /// Check if a channel has a plan waiting for approval
+    pub fn has_pending(&self, channel_id: i64) -> bool {
+        self.pending.get(&channel_id).is_some_and(|plan| !plan.decision.is_closed())
+    }
+}
+
+impl Default for PlanApprovalManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_parses_setting_values() {
+        assert_eq!(PlanApprovalPolicy::from_str_or_default("proceed"), PlanApprovalPolicy::ProceedOnTimeout);
+        assert_eq!(PlanApprovalPolicy::from_str_or_default(" Cancel "), PlanApprovalPolicy::CancelOnTimeout);
+        assert_eq!(PlanApprovalPolicy::from_str_or_default("off"), PlanApprovalPolicy::Off);
+        assert_eq!(PlanApprovalPolicy::from_str_or_default(""), PlanApprovalPolicy::Off);
+    }
+
+    #[test]
+    fn replies_approve_or_reject() {
+        assert_eq!(parse_reply("Approve"), Some(true));
+        assert_eq!(parse_reply("/approve"), Some(true));
+        assert_eq!(parse_reply("yes!"), Some(true));
+        assert_eq!(parse_reply("no"), Some(false));
+        assert_eq!(parse_reply("/reject"), Some(false));
+        assert_eq!(parse_reply("can you add a step first?"), None);
+    }
+
+    #[tokio::test]
+    async fn only_the_requesting_user_answers_from_chat() {
+        let manager = PlanApprovalManager::new();
+        let rx = manager.request(7, "alice");
+        assert!(manager.has_pending(7));
+
+        assert!(!manager.resolve_for_user(7, "mallory", true));
+        assert!(manager.resolve_for_user(7, "alice", false));
+        assert!(!rx.await.unwrap());
+        assert!(!manager.has_pending(7));
+        assert!(!manager.resolve(7, true));
+    }
+}
@@ -0,0 +1,117 @@
+//! Session spend ledger recording
+//!
+//! Appends x402 payments, estimated AI token costs and gas fees to a session's
+//! ledger and broadcasts each entry with the session's updated totals, so the
+//! UI can keep a running tab for the conversation.
+
+use crate::db::Database;
+use crate::gateway::events::EventBroadcaster;
+use crate::gateway::protocol::GatewayEvent;
+use crate::models::SpendKind;
+use crate::x402::{PaymentStatus, X402PaymentInfo};
+
+/// Append an entry to a session's ledger and broadcast it. Failures are logged;
+/// the ledger never interrupts the work being paid for.
+#[allow(clippy::too_many_arguments)]
+pub fn record(
+    db: &Database,
+    broadcaster: Option<&EventBroadcaster>,
+    channel_id: i64,
+    session_id: i64,
+    kind: SpendKind,
+    amount: f64,
+    currency: &str,
+    tokens: Option<i64>,
+    detail: Option<&str>,
+) {
+    let entry = match db.record_session_spend(session_id, kind, amount, currency, tokens, detail) {
+        Ok(entry) => entry,
+        Err(e) => {
+            log::error!("[SPEND] Failed to record {} spend for session {}: {}", kind.as_str(), session_id, e);
+            return;
+        }
+    };
+    if let Some(broadcaster) = broadcaster {
+        let totals = db.get_session_spend_totals(session_id).unwrap_or_default();
+        broadcaster.broadcast(GatewayEvent::session_spend_updated(channel_id, &entry, &totals));
+    }
+}
+
+/// Record an x402 payment. Failed payments cost nothing and are skipped.
+pub fn record_x402(
+    db: &Database,
+    broadcaster: Option<&EventBroadcaster>,
+    channel_id: i64,
+    session_id: i64,
+    payment: &X402PaymentInfo,
+) {
+    if matches!(payment.status, PaymentStatus::Failed) {
+        return;
+    }
+    let amount = payment.amount_formatted.parse::<f64>().unwrap_or(0.0);
+    record(
+        db,
+        broadcaster,
+        channel_id,
+        session_id,
+        SpendKind::X402,
+        amount,
+        &payment.asset,
+        None,
+        payment.resource.as_deref(),
+    );
+}
+
+/// Estimated USD cost of an AI call from the configured per-million-token prices
+pub fn estimated_token_cost(input_tokens: i64, output_tokens: i64) -> f64 {
+    let (input_price, output_price) = crate::config::ai_token_prices_per_mtok();
+    (input_tokens as f64 * input_price + output_tokens as f64 * output_price) / 1_000_000.0
+}
+
+/// Record an AI call's estimated token usage. Calls already paid via x402 are
+/// recorded with a zero cost so their payment isn't counted twice.
+pub fn record_ai_usage(
+    db: &Database,
+    broadcaster: Option<&EventBroadcaster>,
+    channel_id: i64,
+    session_id: i64,
+    input_tokens: i64,
+    output_tokens: i64,
+    paid_via_x402: bool,
+) {
+    let cost = if paid_via_x402 { 0.0 } else { estimated_token_cost(input_tokens, output_tokens) };
+    let detail = format!("~{} in / ~{} out", input_tokens, output_tokens);
+    record(
+        db,
+        broadcaster,
+        channel_id,
+        session_id,
+        SpendKind::AiTokens,
+        cost,
+        "USD",
+        Some(input_tokens + output_tokens),
+        Some(&detail),
+    );
+}
+
+/// Gas fee in native units (e.g. ETH) from gas used and the effective price in wei
+pub fn gas_fee(gas_used: ethers::types::U256, effective_gas_price: ethers::types::U256) -> f64 {
+    let wei = gas_used.saturating_mul(effective_gas_price);
+    ethers::utils::format_units(wei, 18)
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::U256;
+
+    #[test]
+    fn gas_fee_is_in_native_units() {
+        // 21000 gas at 1 gwei
+        let fee = gas_fee(U256::from(21_000u64), U256::from(1_000_000_000u64));
+        assert!((fee - 0.000021).abs() < 1e-12);
+    }
+}
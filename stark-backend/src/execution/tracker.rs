@@ -339,6 +339,7 @@ impl ExecutionTracker {
         // Emit event with description
         self.broadcaster.broadcast(GatewayEvent::execution_started(
             channel_id,
+            chat_id,
             &execution_id,
             mode,
             &task.description,
@@ -482,7 +483,7 @@ impl ExecutionTracker {
             }
 
             // Web operations
-            "web_fetch" => {
+            "web_fetch" | "http_get" => {
                 let url = args.get("url").and_then(|v| v.as_str()).unwrap_or("");
                 let host = url.split("://")
                     .nth(1)
@@ -760,14 +761,17 @@ impl ExecutionTracker {
             }
 
             // Complete the root task
+            let mut chat_id = None;
             if let Some(mut root_task) = self.tasks.get_mut(&execution_id) {
                 root_task.complete();
                 total_metrics.duration_ms = root_task.metrics.duration_ms;
+                chat_id = root_task.chat_id.clone();
             }
 
             // Emit completion event
             self.broadcaster.broadcast(GatewayEvent::execution_completed(
                 channel_id,
+                chat_id.as_deref(),
                 &execution_id,
                 &total_metrics,
             ));
@@ -2,11 +2,11 @@
 //! This allows WebSocket connections on the same port as the HTTP server,
 //! which is required for platforms like DigitalOcean App Platform that only expose one port.
 
-use crate::channels::ChannelManager;
+use crate::channels::{ChannelManager, MessageDispatcher};
 use crate::db::Database;
-use crate::gateway::events::EventBroadcaster;
+use crate::gateway::events::{EventBroadcaster, EventFilter};
 use crate::gateway::methods;
-use crate::gateway::protocol::{ChannelIdParams, RpcError, RpcRequest, RpcResponse};
+use crate::gateway::protocol::{ChannelIdParams, GatewayEvent, RpcError, RpcRequest, RpcResponse};
 use crate::tx_queue::TxQueueManager;
 use crate::wallet::WalletProvider;
 use actix_web::{web, HttpRequest, HttpResponse};
@@ -24,14 +24,31 @@ const AUTH_TIMEOUT_SECS: u64 = 30;
 #[derive(Debug, Deserialize)]
 struct AuthParams {
     token: String,
+    /// Sequence number of the last event a reconnecting client received
+    #[serde(default)]
+    last_seq: Option<u64>,
+    /// Event names to receive (see `EventFilter`); omitted = all events
+    #[serde(default)]
+    events: Option<Vec<String>>,
 }
 
+/// Parameters for the events.subscribe RPC method
+#[derive(Debug, Deserialize)]
+struct EventsSubscribeParams {
+    #[serde(default)]
+    events: Option<Vec<String>>,
+}
+
+/// A connection's event filter, shared between the send task and RPC handling
+type SharedEventFilter = Arc<std::sync::RwLock<EventFilter>>;
+
 /// WebSocket handler for Actix-Web
 pub async fn ws_handler(
     req: HttpRequest,
     stream: web::Payload,
     db: web::Data<Arc<Database>>,
     channel_manager: web::Data<Arc<ChannelManager>>,
+    dispatcher: web::Data<Arc<MessageDispatcher>>,
     broadcaster: web::Data<Arc<EventBroadcaster>>,
     tx_queue: web::Data<Arc<TxQueueManager>>,
     wallet_provider: web::Data<Option<Arc<dyn WalletProvider>>>,
@@ -41,6 +58,7 @@ pub async fn ws_handler(
     // Spawn the WebSocket handler task
     let db = db.get_ref().clone();
     let channel_manager = channel_manager.get_ref().clone();
+    let dispatcher = dispatcher.get_ref().clone();
     let broadcaster = broadcaster.get_ref().clone();
     let tx_queue = tx_queue.get_ref().clone();
     let wallet_provider = wallet_provider.get_ref().clone();
@@ -50,6 +68,7 @@ pub async fn ws_handler(
         msg_stream,
         db,
         channel_manager,
+        dispatcher,
         broadcaster,
         tx_queue,
         wallet_provider,
@@ -63,6 +82,7 @@ async fn handle_ws_connection(
     msg_stream: actix_ws::MessageStream,
     db: Arc<Database>,
     channel_manager: Arc<ChannelManager>,
+    dispatcher: Arc<MessageDispatcher>,
     broadcaster: Arc<EventBroadcaster>,
     tx_queue: Arc<TxQueueManager>,
     wallet_provider: Option<Arc<dyn WalletProvider>>,
@@ -75,14 +95,14 @@ async fn handle_ws_connection(
         .max_continuation_size(64 * 1024);
 
     // Phase 1: Authentication required before full access
-    let authenticated = match tokio::time::timeout(
+    let auth = match tokio::time::timeout(
         Duration::from_secs(AUTH_TIMEOUT_SECS),
-        wait_for_auth(&mut session, &mut msg_stream, &db),
+        wait_for_auth(&mut session, &mut msg_stream, &db, &broadcaster),
     )
     .await
     {
-        Ok(Ok(true)) => true,
-        Ok(Ok(false)) => {
+        Ok(Ok(Some(params))) => params,
+        Ok(Ok(None)) => {
             log::warn!("Gateway client failed authentication");
             let _ = session.close(None).await;
             return;
@@ -106,11 +126,6 @@ async fn handle_ws_connection(
         }
     };
 
-    if !authenticated {
-        let _ = session.close(None).await;
-        return;
-    }
-
     log::info!("Gateway client authenticated successfully");
 
     // Phase 2: Full access after authentication
@@ -122,8 +137,38 @@ async fn handle_ws_connection(
         broadcaster.client_count()
     );
 
-    // Replay recent events so the client sees what happened before they connected
-    let recent_events = broadcaster.get_recent_events();
+    // Replay events so the client sees what happened before it connected. A
+    // reconnecting client only gets the gap since its last seen event, unless
+    // that gap is no longer buffered and it has to resync.
+    let event_filter: SharedEventFilter = Arc::new(std::sync::RwLock::new(EventFilter::new(
+        auth.events.unwrap_or_default(),
+    )));
+
+    let recent_events = match auth.last_seq {
+        Some(last_seq) => {
+            let replay = broadcaster.events_since(last_seq);
+            if replay.complete {
+                replay.events
+            } else {
+                log::info!(
+                    "Client {} missed events since seq {} that are no longer buffered (current {})",
+                    client_id,
+                    last_seq,
+                    replay.current_seq
+                );
+                let mut events = vec![GatewayEvent::events_resync_required(last_seq, replay.current_seq)];
+                events.extend(broadcaster.get_recent_events());
+                events
+            }
+        }
+        None => broadcaster.get_recent_events(),
+    };
+    // Live events already subscribed may repeat the tail of the replay
+    let replayed_through = recent_events.iter().map(|e| e.seq).max().unwrap_or(0);
+    let recent_events: Vec<GatewayEvent> = {
+        let filter = event_filter.read().unwrap();
+        recent_events.into_iter().filter(|e| filter.allows(&e.event)).collect()
+    };
     if !recent_events.is_empty() {
         log::info!(
             "Replaying {} recent events to client {}",
@@ -148,6 +193,7 @@ async fn handle_ws_connection(
     // Clone session for the send task
     let mut send_session = session.clone();
     let client_id_clone = client_id.clone();
+    let send_filter = event_filter.clone();
 
     // Task to forward messages to WebSocket
     let send_task = tokio::spawn(async move {
@@ -162,6 +208,11 @@ async fn handle_ws_connection(
                 }
                 // Forward events
                 Some(event) = event_rx.recv() => {
+                    if event.seq <= replayed_through
+                        || !send_filter.read().map(|f| f.allows(&event.event)).unwrap_or(true)
+                    {
+                        continue;
+                    }
                     let event_name = event.event.clone();
                     if let Ok(json) = serde_json::to_string(&event) {
                         if event_name == "agent.tool_call" || event_name == "tool.result" {
@@ -184,7 +235,7 @@ async fn handle_ws_connection(
         match msg_result {
             Ok(AggregatedMessage::Text(text)) => {
                 log::debug!("[DATAGRAM] <<< FROM AGENT (RPC request):\n{}", text);
-                let response = process_request(&text, &db, &channel_manager, &broadcaster, &tx_queue, &wallet_provider).await;
+                let response = process_request(&text, &db, &channel_manager, &dispatcher, &broadcaster, &tx_queue, &wallet_provider, &event_filter).await;
                 if let Ok(json) = serde_json::to_string(&response) {
                     let _ = tx.send(json).await;
                 }
@@ -217,7 +268,8 @@ async fn wait_for_auth(
     session: &mut actix_ws::Session,
     msg_stream: &mut (impl StreamExt<Item = Result<AggregatedMessage, actix_ws::ProtocolError>> + Unpin),
     db: &Arc<Database>,
-) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+    broadcaster: &Arc<EventBroadcaster>,
+) -> Result<Option<AuthParams>, Box<dyn std::error::Error + Send + Sync>> {
     while let Some(msg_result) = msg_stream.next().await {
         match msg_result {
             Ok(AggregatedMessage::Text(text)) => {
@@ -261,12 +313,15 @@ async fn wait_for_auth(
                             Ok(Some(_session)) => {
                                 let response = RpcResponse::success(
                                     request.id,
-                                    serde_json::json!({"authenticated": true}),
+                                    serde_json::json!({
+                                        "authenticated": true,
+                                        "seq": broadcaster.current_seq(),
+                                    }),
                                 );
                                 if let Ok(json) = serde_json::to_string(&response) {
                                     let _ = session.text(json).await;
                                 }
-                                return Ok(true);
+                                return Ok(Some(params));
                             }
                             Ok(None) => {
                                 let response = RpcResponse::error(
@@ -276,7 +331,7 @@ async fn wait_for_auth(
                                 if let Ok(json) = serde_json::to_string(&response) {
                                     let _ = session.text(json).await;
                                 }
-                                return Ok(false);
+                                return Ok(None);
                             }
                             Err(e) => {
                                 log::error!("Database error validating token: {}", e);
@@ -287,7 +342,7 @@ async fn wait_for_auth(
                                 if let Ok(json) = serde_json::to_string(&response) {
                                     let _ = session.text(json).await;
                                 }
-                                return Ok(false);
+                                return Ok(None);
                             }
                         }
                     }
@@ -316,7 +371,7 @@ async fn wait_for_auth(
                 let _ = session.pong(&data).await;
             }
             Ok(AggregatedMessage::Close(_)) => {
-                return Ok(false);
+                return Ok(None);
             }
             Err(e) => {
                 log::error!("WebSocket error during auth: {:?}", e);
@@ -326,16 +381,19 @@ async fn wait_for_auth(
         }
     }
 
-    Ok(false)
+    Ok(None)
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn process_request(
     text: &str,
     db: &Arc<Database>,
     channel_manager: &Arc<ChannelManager>,
+    dispatcher: &Arc<MessageDispatcher>,
     broadcaster: &Arc<EventBroadcaster>,
     tx_queue: &Arc<TxQueueManager>,
     wallet_provider: &Option<Arc<dyn WalletProvider>>,
+    event_filter: &SharedEventFilter,
 ) -> RpcResponse {
     let request: RpcRequest = match serde_json::from_str(text) {
         Ok(req) => req,
@@ -346,7 +404,7 @@ async fn process_request(
 
     let id = request.id.clone();
 
-    let result = dispatch_method(&request, db, channel_manager, broadcaster, tx_queue, wallet_provider).await;
+    let result = dispatch_method(&request, db, channel_manager, dispatcher, broadcaster, tx_queue, wallet_provider, event_filter).await;
 
     match result {
         Ok(value) => RpcResponse::success(id, value),
@@ -354,13 +412,16 @@ async fn process_request(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn dispatch_method(
     request: &RpcRequest,
     db: &Arc<Database>,
     channel_manager: &Arc<ChannelManager>,
+    dispatcher: &Arc<MessageDispatcher>,
     broadcaster: &Arc<EventBroadcaster>,
     tx_queue: &Arc<TxQueueManager>,
     wallet_provider: &Option<Arc<dyn WalletProvider>>,
+    event_filter: &SharedEventFilter,
 ) -> Result<serde_json::Value, RpcError> {
     match request.method.as_str() {
         "ping" => methods::handle_ping().await,
@@ -383,6 +444,11 @@ async fn dispatch_method(
                 .map_err(|e| RpcError::invalid_params(format!("Invalid params: {}", e)))?;
             methods::handle_channels_restart(params, db.clone(), channel_manager.clone()).await
         }
+        "channels.cancel" => {
+            let params: ChannelIdParams = serde_json::from_value(request.params.clone())
+                .map_err(|e| RpcError::invalid_params(format!("Invalid params: {}", e)))?;
+            methods::handle_channels_cancel(params, dispatcher.clone(), channel_manager.clone()).await
+        }
         "tx_queue.confirm" => {
             let params: methods::TxQueueParams = serde_json::from_value(request.params.clone())
                 .map_err(|e| RpcError::invalid_params(format!("Invalid params: {}", e)))?;
@@ -393,6 +459,14 @@ async fn dispatch_method(
                 .map_err(|e| RpcError::invalid_params(format!("Invalid params: {}", e)))?;
             methods::handle_tx_queue_deny(params, tx_queue.clone(), broadcaster.clone()).await
         }
+        "events.subscribe" => {
+            let params: EventsSubscribeParams = serde_json::from_value(request.params.clone())
+                .map_err(|e| RpcError::invalid_params(format!("Invalid params: {}", e)))?;
+            let filter = EventFilter::new(params.events.unwrap_or_default());
+            let result = serde_json::json!({ "events": filter.patterns() });
+            *event_filter.write().unwrap() = filter;
+            Ok(result)
+        }
         _ => Err(RpcError::method_not_found()),
     }
 }
@@ -1,13 +1,74 @@
 use crate::gateway::protocol::GatewayEvent;
 use dashmap::DashMap;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
-/// Max number of recent events to keep in the ring buffer for replay on connect
+/// Max number of recent events to keep per channel (and for channel-less events)
+/// for replay on connect and reconnect
 const EVENT_BUFFER_SIZE: usize = 200;
 
+/// Ring buffer of recent events for one channel
+#[derive(Default)]
+struct EventBuffer {
+    events: VecDeque<GatewayEvent>,
+    /// Sequence number of the newest event dropped from this buffer
+    evicted_through: u64,
+}
+
+/// Buffered events keyed by `data.channel_id` (None for global events)
+type EventBuffers = HashMap<Option<i64>, EventBuffer>;
+
+/// Events a reconnecting client missed
+#[derive(Debug)]
+pub struct EventReplay {
+    /// Missed events still in the buffers, oldest first
+    pub events: Vec<GatewayEvent>,
+    /// False when some missed events were already evicted (or the sequence
+    /// restarted), so the client must reload its state instead
+    pub complete: bool,
+    /// Sequence number of the newest event broadcast so far
+    pub current_seq: u64,
+}
+
+/// Per-client filter on event names, enforced by the `/ws` handler.
+///
+/// Patterns are exact names (`agent.response`) or prefixes ending in `.*`
+/// (`stream.*`); `*` or an empty list lets everything through. Gateway control
+/// events (`events.*`) are always delivered.
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    patterns: Vec<String>,
+}
+
+impl EventFilter {
+    pub fn new(patterns: Vec<String>) -> Self {
+        let patterns = patterns
+            .into_iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+        Self { patterns }
+    }
+
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Whether an event with this name should be sent to the client
+    pub fn allows(&self, event: &str) -> bool {
+        if self.patterns.is_empty() || event.starts_with("events.") {
+            return true;
+        }
+        self.patterns.iter().any(|p| match p.strip_suffix('*') {
+            Some(prefix) => event.starts_with(prefix),
+            None => p == event,
+        })
+    }
+}
+
 /// Internal commands sent to the background broadcast task.
 enum BroadcastCmd {
     /// Deliver an event to all current subscribers and buffer it for replay.
@@ -32,16 +93,18 @@ pub struct EventBroadcaster {
     /// Shared client map — used by `subscribe` / `unsubscribe` / `client_count`
     /// from any thread without going through the command channel.
     clients: Arc<DashMap<String, mpsc::Sender<GatewayEvent>>>,
-    /// Ring buffer accessible for replay on new connections.
-    recent_events: Arc<std::sync::Mutex<VecDeque<GatewayEvent>>>,
+    /// Per-channel ring buffers accessible for replay on new connections.
+    recent_events: Arc<std::sync::Mutex<EventBuffers>>,
+    /// Sequence number of the last event broadcast (assigned by the background task).
+    last_seq: Arc<AtomicU64>,
 }
 
 impl EventBroadcaster {
     pub fn new() -> Self {
         let clients: Arc<DashMap<String, mpsc::Sender<GatewayEvent>>> =
             Arc::new(DashMap::new());
-        let recent_events =
-            Arc::new(std::sync::Mutex::new(VecDeque::with_capacity(EVENT_BUFFER_SIZE)));
+        let recent_events = Arc::new(std::sync::Mutex::new(EventBuffers::new()));
+        let last_seq = Arc::new(AtomicU64::new(0));
 
         let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
 
@@ -50,12 +113,14 @@ impl EventBroadcaster {
             cmd_rx,
             clients.clone(),
             recent_events.clone(),
+            last_seq.clone(),
         ));
 
         Self {
             cmd_tx,
             clients,
             recent_events,
+            last_seq,
         }
     }
 
@@ -80,8 +145,37 @@ impl EventBroadcaster {
 
     /// Get a snapshot of recent events for replaying to newly connected clients.
     pub fn get_recent_events(&self) -> Vec<GatewayEvent> {
-        let buffer = self.recent_events.lock().unwrap();
-        buffer.iter().cloned().collect()
+        let buffers = self.recent_events.lock().unwrap();
+        let mut events: Vec<GatewayEvent> = buffers
+            .values()
+            .flat_map(|b| b.events.iter().cloned())
+            .collect();
+        events.sort_by_key(|e| e.seq);
+        let skip = events.len().saturating_sub(EVENT_BUFFER_SIZE);
+        events.split_off(skip)
+    }
+
+    /// Events broadcast after `last_seq`, for a client reconnecting after a gap.
+    pub fn events_since(&self, last_seq: u64) -> EventReplay {
+        let current_seq = self.current_seq();
+        let buffers = self.recent_events.lock().unwrap();
+        let mut events: Vec<GatewayEvent> = buffers
+            .values()
+            .flat_map(|b| b.events.iter().filter(|e| e.seq > last_seq).cloned())
+            .collect();
+        events.sort_by_key(|e| e.seq);
+        let complete =
+            last_seq <= current_seq && buffers.values().all(|b| b.evicted_through <= last_seq);
+        EventReplay {
+            events,
+            complete,
+            current_seq,
+        }
+    }
+
+    /// Sequence number of the most recently broadcast event (0 before the first).
+    pub fn current_seq(&self) -> u64 {
+        self.last_seq.load(Ordering::SeqCst)
     }
 
     /// Unsubscribe a client.
@@ -108,18 +202,29 @@ impl EventBroadcaster {
     async fn run_loop(
         mut cmd_rx: mpsc::UnboundedReceiver<BroadcastCmd>,
         clients: Arc<DashMap<String, mpsc::Sender<GatewayEvent>>>,
-        recent_events: Arc<std::sync::Mutex<VecDeque<GatewayEvent>>>,
+        recent_events: Arc<std::sync::Mutex<EventBuffers>>,
+        last_seq: Arc<AtomicU64>,
     ) {
         while let Some(cmd) = cmd_rx.recv().await {
             match cmd {
-                BroadcastCmd::Send(event) => {
-                    // Store in ring buffer for replay
-                    if let Ok(mut buffer) = recent_events.lock() {
-                        if buffer.len() >= EVENT_BUFFER_SIZE {
-                            buffer.pop_front();
+                BroadcastCmd::Send(mut event) => {
+                    // Sequence here, on the single consumer, so numbers follow delivery order
+                    event.seq = last_seq.load(Ordering::SeqCst) + 1;
+
+                    // Store in the channel's ring buffer for replay
+                    if let Ok(mut buffers) = recent_events.lock() {
+                        let channel_id = event.data.get("channel_id").and_then(|v| v.as_i64());
+                        let buffer = buffers.entry(channel_id).or_default();
+                        if buffer.events.len() >= EVENT_BUFFER_SIZE
+                            && let Some(evicted) = buffer.events.pop_front()
+                        {
+                            buffer.evicted_through = evicted.seq;
                         }
-                        buffer.push_back(event.clone());
+                        buffer.events.push_back(event.clone());
                     }
+                    // Publish the seq only once the event is buffered, so a replay
+                    // snapshot never reports a seq whose event it cannot see
+                    last_seq.store(event.seq, Ordering::SeqCst);
 
                     let event_name = event.event.clone();
 
@@ -191,3 +296,66 @@ impl Default for EventBroadcaster {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_event_filter_matches_names_and_prefixes() {
+        let filter = EventFilter::new(vec!["agent.response".into(), "tool.*".into(), " ".into()]);
+        assert!(filter.allows("agent.response"));
+        assert!(filter.allows("tool.result"));
+        assert!(!filter.allows("stream.content_delta"));
+        assert!(!filter.allows("agent.response_extra"));
+        // Control events and unfiltered clients always get through
+        assert!(filter.allows("events.resync_required"));
+        assert!(EventFilter::new(vec![]).allows("stream.content_delta"));
+        assert!(EventFilter::new(vec!["*".into()]).allows("stream.content_delta"));
+    }
+
+    async fn broadcast_and_wait(broadcaster: &EventBroadcaster, events: Vec<GatewayEvent>) {
+        let target = broadcaster.current_seq() + events.len() as u64;
+        for event in events {
+            broadcaster.broadcast(event);
+        }
+        while broadcaster.current_seq() < target {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_are_sequenced_and_replayed_since_last_seen() {
+        let broadcaster = EventBroadcaster::new();
+        broadcast_and_wait(
+            &broadcaster,
+            (0..5).map(|i| GatewayEvent::new("test.event", json!({ "channel_id": i % 2, "i": i }))).collect(),
+        )
+        .await;
+
+        let replay = broadcaster.events_since(2);
+        assert!(replay.complete);
+        assert_eq!(replay.current_seq, 5);
+        let seqs: Vec<u64> = replay.events.iter().map(|e| e.seq).collect();
+        assert_eq!(seqs, vec![3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn test_replay_reports_gap_after_eviction_or_restart() {
+        let broadcaster = EventBroadcaster::new();
+        broadcast_and_wait(
+            &broadcaster,
+            (0..EVENT_BUFFER_SIZE + 10).map(|_| GatewayEvent::new("test.event", json!({ "channel_id": 7 }))).collect(),
+        )
+        .await;
+        // A busy channel evicts its own history without touching other channels
+        broadcast_and_wait(&broadcaster, vec![GatewayEvent::new("test.event", json!({ "channel_id": 8 }))]).await;
+
+        assert!(!broadcaster.events_since(5).complete);
+        assert!(broadcaster.events_since(20).complete);
+        // A client that saw a higher seq than exists is talking to a restarted backend
+        assert!(!broadcaster.events_since(10_000).complete);
+        assert_eq!(broadcaster.get_recent_events().len(), EVENT_BUFFER_SIZE);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::channels::ChannelManager;
+use crate::channels::{ChannelManager, MessageDispatcher};
 use crate::db::Database;
 use crate::gateway::protocol::{ChannelIdParams, RpcError};
 use crate::models::ChannelResponse;
@@ -97,3 +97,19 @@ pub async fn handle_channels_restart(
         "channel_id": params.id
     }))
 }
+
+/// Abort the dispatch running on a channel
+pub async fn handle_channels_cancel(
+    params: ChannelIdParams,
+    dispatcher: Arc<MessageDispatcher>,
+    channel_manager: Arc<ChannelManager>,
+) -> Result<Value, RpcError> {
+    let dispatch_cancelled = dispatcher.cancel_dispatch(params.id).await;
+    let listener_cancelled = channel_manager.cancel_dispatch(params.id);
+
+    Ok(serde_json::json!({
+        "success": true,
+        "channel_id": params.id,
+        "cancelled": dispatch_cancelled || listener_cancelled
+    }))
+}
@@ -5,7 +5,7 @@
 use crate::gateway::events::EventBroadcaster;
 use crate::gateway::protocol::{GatewayEvent, RpcError};
 use crate::tools::rpc_config::resolve_rpc_from_network;
-use crate::tx_queue::{QueuedTxStatus, TxQueueManager};
+use crate::tx_queue::{QueuedTxStatus, SubmissionClaim, TxQueueManager};
 use crate::wallet::WalletProvider;
 use crate::x402::X402EvmRpc;
 use serde::Deserialize;
@@ -17,6 +17,10 @@ use std::time::Duration;
 pub struct TxQueueParams {
     pub uuid: String,
     pub channel_id: i64,
+    /// Client-generated token; a repeated confirm with the same token returns
+    /// the first one's result instead of broadcasting again
+    #[serde(default)]
+    pub idempotency_token: Option<String>,
 }
 
 /// Handle tx_queue.confirm RPC method
@@ -29,6 +33,48 @@ pub async fn handle_tx_queue_confirm(
 ) -> Result<Value, RpcError> {
     log::info!("[tx_queue.confirm] Confirming transaction {}", params.uuid);
 
+    let Some(token) = params.idempotency_token.clone() else {
+        return confirm_and_broadcast(&params, tx_queue, broadcaster, wallet_provider).await;
+    };
+
+    match tx_queue.claim_submission(&token, &params.uuid).map_err(|e| RpcError::new(-32000, e))? {
+        SubmissionClaim::New => {}
+        SubmissionClaim::InProgress | SubmissionClaim::Completed(_) => {
+            return existing_submission_result(&tx_queue, &params.uuid);
+        }
+    }
+
+    let result = confirm_and_broadcast(&params, tx_queue.clone(), broadcaster, wallet_provider).await;
+    match result {
+        Ok(_) => tx_queue.complete_submission(&token, &params.uuid),
+        Err(_) => tx_queue.release_submission(&token),
+    }
+    result
+}
+
+/// Report where an already-submitted confirmation got to, without broadcasting again
+fn existing_submission_result(tx_queue: &TxQueueManager, uuid: &str) -> Result<Value, RpcError> {
+    let tx = tx_queue.get(uuid)
+        .ok_or_else(|| RpcError::new(-32000, format!("Transaction {} not found", uuid)))?;
+
+    log::info!("[tx_queue.confirm] Duplicate confirm for {} (status: {}), not broadcasting again", uuid, tx.status);
+
+    Ok(json!({
+        "success": true,
+        "uuid": tx.uuid,
+        "status": tx.status.to_string(),
+        "tx_hash": tx.tx_hash,
+        "explorer_url": tx.explorer_url,
+        "duplicate": true
+    }))
+}
+
+async fn confirm_and_broadcast(
+    params: &TxQueueParams,
+    tx_queue: Arc<TxQueueManager>,
+    broadcaster: Arc<EventBroadcaster>,
+    wallet_provider: Option<Arc<dyn WalletProvider>>,
+) -> Result<Value, RpcError> {
     // Get transaction
     let tx = tx_queue.get(&params.uuid)
         .ok_or_else(|| RpcError::new(-32000, format!("Transaction {} not found", params.uuid)))?;
@@ -38,8 +84,14 @@ pub async fn handle_tx_queue_confirm(
         return Err(RpcError::new(-32000, format!("Transaction {} is not pending (status: {:?})", params.uuid, tx.status)));
     }
 
-    // Mark broadcasting
-    tx_queue.mark_broadcasting(&params.uuid);
+    // Leave the transaction pending while signing is frozen so it can be confirmed later
+    crate::wallet::ensure_signing_allowed()
+        .map_err(|e| RpcError::new(-32000, e))?;
+
+    // Mark broadcasting; fails if a concurrent confirm got there first
+    if !tx_queue.mark_broadcasting_if_pending(&params.uuid) {
+        return Err(RpcError::new(-32000, format!("Transaction {} is already being broadcast", params.uuid)));
+    }
 
     // Get wallet provider for x402 payments
     let wallet_provider = wallet_provider
@@ -34,11 +34,15 @@ pub enum EventType {
     ExecutionStopped,
     // Payment events
     X402Payment,
+    X402LowBalance,  // Wallet USDC balance is below the configured pre-flight buffer
     // Confirmation events
     ConfirmationRequired,
     ConfirmationApproved,
     ConfirmationRejected,
     ConfirmationExpired,
+    // Plan approval events
+    PlanApprovalRequired,  // Planned task list is waiting for the user's approval
+    PlanApprovalResolved,  // Plan approved, rejected or timed out
     // Transaction events
     TxPending,
     TxConfirmed,
@@ -73,6 +77,8 @@ pub enum EventType {
     TaskStatusChange,   // Individual task status change
     SessionCreated,     // New session created (for web channel gateway pattern)
     SessionComplete,    // Session marked complete (all tasks done)
+    SessionTitleUpdated, // Session got a generated title (or a new one after a topic shift)
+    SessionSpendUpdated, // New entry in the session spend ledger (x402, AI tokens, gas)
     // Cron execution events (for web channel)
     CronExecutionStartedOnChannel,  // Cron job started on web channel (main mode)
     CronExecutionStoppedOnChannel,  // Cron job stopped on web channel
@@ -82,6 +88,7 @@ pub enum EventType {
     TxQueueConfirmationRequired,  // Pending tx needs user confirmation
     TxQueueConfirmed,             // User confirmed, tx broadcast
     TxQueueDenied,                // User denied, tx deleted
+    TxQueueExpired,               // Not confirmed before the timeout, tx auto-rejected
     // Context management events
     ContextCompacting,  // Session context is being compacted to reduce token usage
     // Telemetry events
@@ -89,6 +96,8 @@ pub enum EventType {
     RolloutStatusChange, // Rollout lifecycle status changed
     // Module TUI events
     ModuleTuiInvalidate, // Module TUI dashboard needs re-render
+    // Gateway events
+    EventsResyncRequired, // Reconnecting client missed events that are no longer buffered
 }
 
 impl EventType {
@@ -117,10 +126,13 @@ impl EventType {
             Self::ExecutionCompleted => "execution.completed",
             Self::ExecutionStopped => "execution.stopped",
             Self::X402Payment => "x402.payment",
+            Self::X402LowBalance => "x402.low_balance",
             Self::ConfirmationRequired => "confirmation.required",
             Self::ConfirmationApproved => "confirmation.approved",
             Self::ConfirmationRejected => "confirmation.rejected",
             Self::ConfirmationExpired => "confirmation.expired",
+            Self::PlanApprovalRequired => "plan.approval_required",
+            Self::PlanApprovalResolved => "plan.approval_resolved",
             Self::TxPending => "tx.pending",
             Self::TxConfirmed => "tx.confirmed",
             Self::RegisterUpdate => "register.update",
@@ -147,16 +159,20 @@ impl EventType {
             Self::TaskStatusChange => "task.status_change",
             Self::SessionCreated => "session.created",
             Self::SessionComplete => "session.complete",
+            Self::SessionTitleUpdated => "session.title_updated",
+            Self::SessionSpendUpdated => "session.spend_updated",
             Self::CronExecutionStartedOnChannel => "cron.execution_started_on_channel",
             Self::CronExecutionStoppedOnChannel => "cron.execution_stopped_on_channel",
             Self::AiRetrying => "ai.retrying",
             Self::TxQueueConfirmationRequired => "tx_queue.confirmation_required",
             Self::TxQueueConfirmed => "tx_queue.confirmed",
             Self::TxQueueDenied => "tx_queue.denied",
+            Self::TxQueueExpired => "tx_queue.expired",
             Self::ContextCompacting => "context.compacting",
             Self::SpanEmitted => "telemetry.span_emitted",
             Self::RolloutStatusChange => "telemetry.rollout_status",
             Self::ModuleTuiInvalidate => "module.tui_invalidate",
+            Self::EventsResyncRequired => "events.resync_required",
         }
     }
 
@@ -185,10 +201,13 @@ impl EventType {
             "execution.completed" => Some(EventType::ExecutionCompleted),
             "execution.stopped" => Some(EventType::ExecutionStopped),
             "x402.payment" => Some(EventType::X402Payment),
+            "x402.low_balance" => Some(EventType::X402LowBalance),
             "confirmation.required" => Some(EventType::ConfirmationRequired),
             "confirmation.approved" => Some(EventType::ConfirmationApproved),
             "confirmation.rejected" => Some(EventType::ConfirmationRejected),
             "confirmation.expired" => Some(EventType::ConfirmationExpired),
+            "plan.approval_required" => Some(EventType::PlanApprovalRequired),
+            "plan.approval_resolved" => Some(EventType::PlanApprovalResolved),
             "tx.pending" => Some(EventType::TxPending),
             "tx.confirmed" => Some(EventType::TxConfirmed),
             "register.update" => Some(EventType::RegisterUpdate),
@@ -215,16 +234,19 @@ impl EventType {
             "task.status_change" => Some(EventType::TaskStatusChange),
             "session.created" => Some(EventType::SessionCreated),
             "session.complete" => Some(EventType::SessionComplete),
+            "session.spend_updated" => Some(EventType::SessionSpendUpdated),
             "cron.execution_started_on_channel" => Some(EventType::CronExecutionStartedOnChannel),
             "cron.execution_stopped_on_channel" => Some(EventType::CronExecutionStoppedOnChannel),
             "ai.retrying" => Some(EventType::AiRetrying),
             "tx_queue.confirmation_required" => Some(EventType::TxQueueConfirmationRequired),
             "tx_queue.confirmed" => Some(EventType::TxQueueConfirmed),
             "tx_queue.denied" => Some(EventType::TxQueueDenied),
+            "tx_queue.expired" => Some(EventType::TxQueueExpired),
             "context.compacting" => Some(EventType::ContextCompacting),
             "telemetry.span_emitted" => Some(EventType::SpanEmitted),
             "telemetry.rollout_status" => Some(EventType::RolloutStatusChange),
             "module.tui_invalidate" => Some(EventType::ModuleTuiInvalidate),
+            "events.resync_required" => Some(EventType::EventsResyncRequired),
             _ => None,
         }
     }
@@ -325,14 +347,57 @@ pub struct GatewayEvent {
     pub type_: String,
     pub event: String,
     pub data: Value,
+    /// Monotonic sequence number assigned by the broadcaster (0 = not yet broadcast)
+    #[serde(default)]
+    pub seq: u64,
+}
+
+/// Payload of a `tool.result` event
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ToolResultEvent<'a> {
+    pub channel_id: i64,
+    /// Platform-specific conversation ID (e.g., Discord channel snowflake)
+    pub chat_id: Option<&'a str>,
+    pub tool_name: &'a str,
+    pub success: bool,
+    pub duration_ms: i64,
+    pub content: &'a str,
+    /// Whether this is a safe mode query (affects Discord output behavior)
+    pub safe_mode: bool,
+    pub message_id: Option<&'a str>,
+    pub structured: Option<&'a crate::tools::StructuredResult>,
+}
+
+/// Payload of a `tx_queue.confirmation_required` event
+#[derive(Debug, Clone, Copy)]
+pub struct TxQueueConfirmationEvent<'a> {
+    pub channel_id: i64,
+    pub uuid: &'a str,
+    pub network: &'a str,
+    pub from: &'a str,
+    pub to: &'a str,
+    pub value: &'a str,
+    pub value_formatted: &'a str,
+    pub data: &'a str,
+    pub simulation: Option<&'a crate::tx_queue::TxSimulation>,
+    /// When the confirmation times out and the tx is auto-rejected
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl GatewayEvent {
-    pub fn new(event: impl Into<String>, data: Value) -> Self {
+    /// Events created during a dispatch carry its `trace_id` (unless the payload already has one)
+    pub fn new(event: impl Into<String>, mut data: Value) -> Self {
+        if let Some(obj) = data.as_object_mut()
+            && !obj.contains_key("trace_id")
+            && let Some(trace_id) = crate::telemetry::trace::current_trace_id()
+        {
+            obj.insert("trace_id".to_string(), Value::String(trace_id));
+        }
         Self {
             type_: "event".to_string(),
             event: event.into(),
             data,
+            seq: 0,
         }
     }
 
@@ -489,21 +554,22 @@ impl GatewayEvent {
         )
     }
 
-    /// The `chat_id` is the platform-specific conversation ID (e.g., Discord channel snowflake)
-    /// `safe_mode` indicates if this is a safe mode query (affects Discord output behavior)
-    pub fn tool_result(channel_id: i64, chat_id: Option<&str>, tool_name: &str, success: bool, duration_ms: i64, content: &str, safe_mode: bool, message_id: Option<&str>) -> Self {
+    pub fn tool_result(result: ToolResultEvent<'_>) -> Self {
         let mut data = serde_json::json!({
-            "channel_id": channel_id,
-            "chat_id": chat_id,
-            "tool_name": tool_name,
-            "success": success,
-            "duration_ms": duration_ms,
-            "content": content,
-            "safe_mode": safe_mode
+            "channel_id": result.channel_id,
+            "chat_id": result.chat_id,
+            "tool_name": result.tool_name,
+            "success": result.success,
+            "duration_ms": result.duration_ms,
+            "content": result.content,
+            "safe_mode": result.safe_mode
         });
-        if let Some(id) = message_id {
+        if let Some(id) = result.message_id {
             data["message_id"] = serde_json::json!(id);
         }
+        if let Some(structured) = result.structured {
+            data["structured"] = serde_json::json!(structured);
+        }
         Self::new(EventType::ToolResult, data)
     }
 
@@ -537,6 +603,7 @@ impl GatewayEvent {
     /// Execution started (plan mode or direct execution)
     pub fn execution_started(
         channel_id: i64,
+        chat_id: Option<&str>,
         execution_id: &str,
         mode: &str,
         description: &str,
@@ -546,6 +613,7 @@ impl GatewayEvent {
             EventType::ExecutionStarted,
             serde_json::json!({
                 "channel_id": channel_id,
+                "chat_id": chat_id,
                 "execution_id": execution_id,
                 "mode": mode,  // "plan" or "execute"
                 "description": description,
@@ -643,11 +711,17 @@ impl GatewayEvent {
     }
 
     /// Execution completed
-    pub fn execution_completed(channel_id: i64, execution_id: &str, total_metrics: &TaskMetrics) -> Self {
+    pub fn execution_completed(
+        channel_id: i64,
+        chat_id: Option<&str>,
+        execution_id: &str,
+        total_metrics: &TaskMetrics,
+    ) -> Self {
         Self::new(
             EventType::ExecutionCompleted,
             serde_json::json!({
                 "channel_id": channel_id,
+                "chat_id": chat_id,
                 "execution_id": execution_id,
                 "metrics": {
                     "tool_uses": total_metrics.tool_uses,
@@ -682,6 +756,7 @@ impl GatewayEvent {
         tool_name: &str,
         description: &str,
         parameters: &Value,
+        estimated_cost: Option<&crate::tools::CostEstimate>,
     ) -> Self {
         Self::new(
             EventType::ConfirmationRequired,
@@ -691,6 +766,7 @@ impl GatewayEvent {
                 "tool_name": tool_name,
                 "description": description,
                 "parameters": parameters,
+                "estimated_cost": estimated_cost,
                 "instructions": "Type /confirm to execute or /cancel to abort",
                 "timestamp": chrono::Utc::now().to_rfc3339()
             }),
@@ -736,6 +812,51 @@ impl GatewayEvent {
         )
     }
 
+    // =====================================================
+    // Plan Approval Events
+    // =====================================================
+
+    /// Planned task list waiting for the user's approval before it runs
+    pub fn plan_approval_required(
+        channel_id: i64,
+        chat_id: &str,
+        session_id: i64,
+        tasks: &[crate::ai::multi_agent::types::PlannerTask],
+        timeout_secs: u64,
+        proceeds_on_timeout: bool,
+    ) -> Self {
+        let tasks_json: Vec<serde_json::Value> = tasks
+            .iter()
+            .map(|t| serde_json::json!({ "id": t.id, "description": t.description }))
+            .collect();
+        Self::new(
+            EventType::PlanApprovalRequired,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "chat_id": chat_id,
+                "session_id": session_id,
+                "tasks": tasks_json,
+                "timeout_secs": timeout_secs,
+                "on_timeout": if proceeds_on_timeout { "proceed" } else { "cancel" },
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            }),
+        )
+    }
+
+    /// Plan approval resolved: "approved", "rejected" or "timed_out"
+    pub fn plan_approval_resolved(channel_id: i64, session_id: i64, outcome: &str, proceeding: bool) -> Self {
+        Self::new(
+            EventType::PlanApprovalResolved,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "session_id": session_id,
+                "outcome": outcome,
+                "proceeding": proceeding,
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            }),
+        )
+    }
+
     /// Custom event with arbitrary event name and data
     pub fn custom(event: &str, data: Value) -> Self {
         Self::new(event, data)
@@ -752,6 +873,18 @@ impl GatewayEvent {
         )
     }
 
+    /// Sent to a reconnecting client whose missed events can't all be replayed;
+    /// it should reload its state rather than trust the replay
+    pub fn events_resync_required(last_seq: u64, current_seq: u64) -> Self {
+        Self::new(
+            EventType::EventsResyncRequired,
+            serde_json::json!({
+                "last_seq": last_seq,
+                "current_seq": current_seq,
+            }),
+        )
+    }
+
     /// Transaction pending - broadcast when tx is sent but not yet mined
     pub fn tx_pending(
         channel_id: i64,
@@ -795,27 +928,20 @@ impl GatewayEvent {
     // =====================================================
 
     /// Transaction queue confirmation required - partner mode needs user approval
-    pub fn tx_queue_confirmation_required(
-        channel_id: i64,
-        uuid: &str,
-        network: &str,
-        from: &str,
-        to: &str,
-        value: &str,
-        value_formatted: &str,
-        data: &str,
-    ) -> Self {
+    pub fn tx_queue_confirmation_required(tx: TxQueueConfirmationEvent<'_>) -> Self {
         Self::new(
             EventType::TxQueueConfirmationRequired,
             serde_json::json!({
-                "channel_id": channel_id,
-                "uuid": uuid,
-                "network": network,
-                "from": from,
-                "to": to,
-                "value": value,
-                "value_formatted": value_formatted,
-                "data": data,
+                "channel_id": tx.channel_id,
+                "uuid": tx.uuid,
+                "network": tx.network,
+                "from": tx.from,
+                "to": tx.to,
+                "value": tx.value,
+                "value_formatted": tx.value_formatted,
+                "data": tx.data,
+                "simulation": tx.simulation,
+                "expires_at": tx.expires_at.map(|t| t.to_rfc3339()),
                 "timestamp": chrono::Utc::now().to_rfc3339()
             }),
         )
@@ -846,6 +972,18 @@ impl GatewayEvent {
         )
     }
 
+    /// Transaction queue expired - not confirmed in time, tx auto-rejected
+    pub fn tx_queue_expired(channel_id: i64, uuid: &str) -> Self {
+        Self::new(
+            EventType::TxQueueExpired,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "uuid": uuid,
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            }),
+        )
+    }
+
     /// x402 payment made
     pub fn x402_payment(
         channel_id: i64,
@@ -869,6 +1007,28 @@ impl GatewayEvent {
         )
     }
 
+    /// Wallet USDC balance is below the pre-flight buffer for x402 AI calls
+    pub fn x402_low_balance(
+        channel_id: i64,
+        wallet_address: &str,
+        balance: &str,
+        balance_formatted: &str,
+        buffer_formatted: &str,
+    ) -> Self {
+        Self::new(
+            EventType::X402LowBalance,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "wallet_address": wallet_address,
+                "asset": "USDC",
+                "balance": balance,
+                "balance_formatted": balance_formatted,
+                "buffer_formatted": buffer_formatted,
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            }),
+        )
+    }
+
     /// Register updated - broadcast full registry state
     pub fn register_update(
         channel_id: i64,
@@ -1259,6 +1419,37 @@ impl GatewayEvent {
         )
     }
 
+    /// Session title generated or regenerated
+    pub fn session_title_updated(channel_id: i64, session_id: i64, title: &str) -> Self {
+        Self::new(
+            EventType::SessionTitleUpdated,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "session_id": session_id,
+                "title": title,
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            }),
+        )
+    }
+
+    /// New spend ledger entry for a session, with the session's updated totals
+    pub fn session_spend_updated(
+        channel_id: i64,
+        entry: &crate::models::SessionSpendEntry,
+        totals: &[crate::models::SessionSpendTotal],
+    ) -> Self {
+        Self::new(
+            EventType::SessionSpendUpdated,
+            serde_json::json!({
+                "channel_id": channel_id,
+                "session_id": entry.session_id,
+                "entry": entry,
+                "totals": totals,
+                "timestamp": chrono::Utc::now().to_rfc3339()
+            }),
+        )
+    }
+
     // =====================================================
     // Cron Execution Events (for web channel)
     // =====================================================
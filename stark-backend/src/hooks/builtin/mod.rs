@@ -3,9 +3,12 @@
 //! This module provides hooks that are commonly needed:
 //! - Logging - Event recording and debugging
 //! - Rate limiting - Request throttling and abuse prevention
+//! - Webhooks - Delivery of lifecycle events to external systems
 
 mod logging_hook;
 mod rate_limit_hook;
+mod webhook_hook;
 
 pub use logging_hook::{LogLevel, LoggingHook};
 pub use rate_limit_hook::{RateLimitConfig, RateLimitHook};
+pub use webhook_hook::load_webhook_hooks;
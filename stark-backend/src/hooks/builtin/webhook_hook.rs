@@ -0,0 +1,312 @@
+//! Webhook hook - POSTs lifecycle events to an external URL
+//!
+//! Webhooks are loaded from `config/webhooks.ron` at startup. Each one names the
+//! events it wants; matching events are delivered as JSON in the background so
+//! a slow receiver never holds up the agent. Bodies are signed with
+//! HMAC-SHA256 when a secret is configured:
+//!
+//! ```text
+//! X-Stark-Event: session_end
+//! X-Stark-Delivery: <uuid, stable across retries>
+//! X-Stark-Signature: sha256=<hex(hmac_sha256(secret, body))>
+//! ```
+//!
+//! Network errors, 5xx and 429 responses are retried with exponential backoff.
+
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::hooks::types::{Hook, HookContext, HookEvent, HookPriority, HookResult};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Delay before the first retry; doubles on each further attempt
+const BASE_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// One entry of `config/webhooks.ron`
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookConfig {
+    /// Unique name, used in the hook id (`webhook.<id>`)
+    pub id: String,
+    /// Receiver URL (http or https)
+    pub url: String,
+    /// HMAC signing secret
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Environment variable holding the signing secret (keeps it out of the config file)
+    #[serde(default)]
+    pub secret_env: Option<String>,
+    /// Events delivered to this webhook
+    pub events: Vec<HookEvent>,
+    /// Retries after the first failed attempt
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Per-attempt request timeout
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl WebhookConfig {
+    /// The signing secret, preferring `secret_env` when it is set
+    fn resolve_secret(&self) -> Option<String> {
+        self.secret_env
+            .as_ref()
+            .and_then(|var| std::env::var(var).ok())
+            .or_else(|| self.secret.clone())
+            .filter(|s| !s.is_empty())
+    }
+}
+
+/// Load webhook hooks from `webhooks.ron` in the config directory.
+/// A missing file means no webhooks; invalid entries are skipped with an error.
+pub fn load_webhook_hooks(config_dir: &Path) -> Vec<WebhookHook> {
+    let path = config_dir.join("webhooks.ron");
+    if !path.exists() {
+        return Vec::new();
+    }
+    let configs = match std::fs::read_to_string(&path) {
+        Ok(content) => match ron::from_str::<Vec<WebhookConfig>>(&content) {
+            Ok(configs) => configs,
+            Err(e) => {
+                log::error!("[WEBHOOK] Failed to parse {:?}: {}", path, e);
+                return Vec::new();
+            }
+        },
+        Err(e) => {
+            log::error!("[WEBHOOK] Failed to read {:?}: {}", path, e);
+            return Vec::new();
+        }
+    };
+
+    configs
+        .into_iter()
+        .filter(|c| c.enabled)
+        .filter_map(|c| match WebhookHook::new(c) {
+            Ok(hook) => Some(hook),
+            Err(e) => {
+                log::error!("[WEBHOOK] {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Hex HMAC-SHA256 of `body`, as sent in `X-Stark-Signature` (after `sha256=`)
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// JSON body delivered for a hook event
+pub fn build_payload(context: &HookContext) -> Value {
+    let mut payload = json!({
+        "event": context.event.as_str(),
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+    });
+    let fields = [
+        ("channel_id", context.channel_id.map(Value::from)),
+        ("session_id", context.session_id.map(Value::from)),
+        ("tool_name", context.tool_name.clone().map(Value::from)),
+        ("error", context.error.clone().map(Value::from)),
+        ("branch", context.branch.clone().map(Value::from)),
+        ("pr_url", context.pr_url.clone().map(Value::from)),
+    ];
+    for (key, value) in fields {
+        if let Some(value) = value {
+            payload[key] = value;
+        }
+    }
+    if !context.extra.is_null() {
+        payload["data"] = context.extra.clone();
+    }
+    payload
+}
+
+/// Hook that forwards subscribed events to an external webhook
+#[derive(Clone)]
+pub struct WebhookHook {
+    hook_id: String,
+    name: String,
+    config: WebhookConfig,
+    secret: Option<String>,
+    client: reqwest::Client,
+}
+
+impl WebhookHook {
+    pub fn new(config: WebhookConfig) -> Result<Self, String> {
+        let url = url::Url::parse(&config.url)
+            .map_err(|e| format!("Webhook '{}' has an invalid URL: {}", config.id, e))?;
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(format!("Webhook '{}' must use http or https", config.id));
+        }
+        if config.events.is_empty() {
+            return Err(format!("Webhook '{}' subscribes to no events", config.id));
+        }
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .user_agent("StarkBot/1.0 (Webhook)")
+            .build()
+            .map_err(|e| format!("Webhook '{}': failed to build HTTP client: {}", config.id, e))?;
+
+        Ok(Self {
+            hook_id: format!("webhook.{}", config.id),
+            name: format!("Webhook: {}", config.id),
+            secret: config.resolve_secret(),
+            config,
+            client,
+        })
+    }
+
+    /// POST the body, retrying transient failures. Returns the final error, if any.
+    async fn deliver(&self, event: HookEvent, body: Vec<u8>) -> Result<(), String> {
+        let delivery_id = uuid::Uuid::new_v4().to_string();
+        let mut last_error = String::new();
+
+        for attempt in 0..=self.config.max_retries {
+            if attempt > 0 {
+                tokio::time::sleep(BASE_RETRY_DELAY * 2u32.pow(attempt - 1)).await;
+            }
+
+            let mut request = self
+                .client
+                .post(&self.config.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header("X-Stark-Event", event.as_str())
+                .header("X-Stark-Delivery", &delivery_id)
+                .body(body.clone());
+            if let Some(ref secret) = self.secret {
+                request = request.header("X-Stark-Signature", format!("sha256={}", sign_payload(secret, &body)));
+            }
+
+            match request.send().await {
+                Ok(resp) if resp.status().is_success() => return Ok(()),
+                Ok(resp) => {
+                    let status = resp.status();
+                    last_error = format!("HTTP {}", status);
+                    if !(status.is_server_error() || status.as_u16() == 429) {
+                        break;
+                    }
+                }
+                Err(e) => last_error = e.to_string(),
+            }
+            log::debug!(
+                "[WEBHOOK] {} delivery {} attempt {} failed: {}",
+                self.hook_id,
+                delivery_id,
+                attempt + 1,
+                last_error
+            );
+        }
+
+        Err(last_error)
+    }
+}
+
+#[async_trait]
+impl Hook for WebhookHook {
+    fn id(&self) -> &str {
+        &self.hook_id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "Delivers lifecycle events to an external webhook"
+    }
+
+    fn events(&self) -> Vec<HookEvent> {
+        self.config.events.clone()
+    }
+
+    fn priority(&self) -> HookPriority {
+        // Notify only after every other hook has had its say
+        HookPriority::Lowest
+    }
+
+    async fn execute(&self, context: &mut HookContext) -> HookResult {
+        let body = match serde_json::to_vec(&build_payload(context)) {
+            Ok(b) => b,
+            Err(e) => return HookResult::Error(format!("Failed to serialize webhook payload: {}", e)),
+        };
+
+        // Deliver in the background: retries must not stall the dispatch
+        let hook = self.clone();
+        let event = context.event;
+        tokio::spawn(async move {
+            if let Err(e) = hook.deliver(event, body).await {
+                log::warn!("[WEBHOOK] {} failed to deliver {}: {}", hook.hook_id, event.as_str(), e);
+            }
+        });
+
+        HookResult::Continue(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_matches_known_vector() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign_payload("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_payload_omits_empty_fields() {
+        let context = HookContext::new(HookEvent::OnRolloutRetry)
+            .with_channel(7, Some(42))
+            .with_extra(json!({ "attempt": 2 }));
+        let payload = build_payload(&context);
+
+        assert_eq!(payload["event"], "on_rollout_retry");
+        assert_eq!(payload["session_id"], 42);
+        assert_eq!(payload["data"]["attempt"], 2);
+        assert!(payload.get("tool_name").is_none());
+    }
+
+    #[test]
+    fn test_config_parses_and_validates() {
+        let configs: Vec<WebhookConfig> = ron::from_str(
+            r#"[(id: "ops", url: "https://example.com/hook", secret: Some("s3cret"), events: [session_end, on_rollout_retry])]"#,
+        )
+        .unwrap();
+        let hook = WebhookHook::new(configs[0].clone()).unwrap();
+        assert_eq!(hook.id(), "webhook.ops");
+        assert_eq!(hook.events(), vec![HookEvent::SessionEnd, HookEvent::OnRolloutRetry]);
+        assert_eq!(configs[0].max_retries, 3);
+
+        let shipped: Vec<WebhookConfig> =
+            ron::from_str(include_str!("../../../../config/webhooks.ron")).unwrap();
+        assert!(shipped.is_empty());
+
+        let mut bad = configs[0].clone();
+        bad.url = "ftp://example.com".into();
+        assert!(WebhookHook::new(bad).is_err());
+    }
+}
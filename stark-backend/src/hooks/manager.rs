@@ -5,14 +5,52 @@
 //! - Executing hooks in priority order
 //! - Tracking hook statistics
 //! - Managing hook configuration from database
-
-use super::types::{BoxedHook, Hook, HookConfig, HookContext, HookEvent, HookPriority, HookResult, HookStats};
+//!
+//! Each hook runs under its timeout with panics caught, so a slow or broken
+//! hook becomes a failure handled by its `HookFailurePolicy` instead of
+//! stalling or crashing the dispatch.
+
+use super::types::{
+    BoxedHook, Hook, HookConfig, HookContext, HookEvent, HookFailurePolicy, HookPriority,
+    HookResult, HookStats,
+};
+use crate::telemetry::emitter::with_active_collector;
+use crate::telemetry::SpanType;
 use dashmap::DashMap;
+use futures_util::FutureExt;
 use std::collections::HashMap;
+use std::panic::AssertUnwindSafe;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::time::timeout;
 
+/// How a single hook execution ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HookOutcome {
+    Completed,
+    TimedOut,
+    Panicked,
+}
+
+/// Best-effort text of a panic payload
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+fn result_label(result: &HookResult) -> &'static str {
+    match result {
+        HookResult::Continue(_) => "continue",
+        HookResult::Skip => "skip",
+        HookResult::Cancel(_) => "cancel",
+        HookResult::Replace(_) => "replace",
+        HookResult::Error(_) => "error",
+    }
+}
+
 /// Manager for hook registration and execution
 pub struct HookManager {
     /// Registered hooks by ID
@@ -23,8 +61,8 @@ pub struct HookManager {
     configs: DashMap<String, HookConfig>,
     /// Hook statistics
     stats: DashMap<String, HookStats>,
-    /// Whether to continue on hook errors
-    continue_on_error: bool,
+    /// Failure policy for hooks that don't set their own
+    default_failure_policy: HookFailurePolicy,
 }
 
 impl HookManager {
@@ -35,19 +73,19 @@ impl HookManager {
             hooks_by_event: DashMap::new(),
             configs: DashMap::new(),
             stats: DashMap::new(),
-            continue_on_error: true,
+            default_failure_policy: HookFailurePolicy::Continue,
         }
     }
 
     /// Create a HookManager that stops on first error
     pub fn strict() -> Self {
-        Self {
-            hooks: DashMap::new(),
-            hooks_by_event: DashMap::new(),
-            configs: DashMap::new(),
-            stats: DashMap::new(),
-            continue_on_error: false,
-        }
+        Self::new().with_failure_policy(HookFailurePolicy::Block)
+    }
+
+    /// Set the failure policy for hooks that don't override it
+    pub fn with_failure_policy(mut self, policy: HookFailurePolicy) -> Self {
+        self.default_failure_policy = policy;
+        self
     }
 
     /// Register a hook
@@ -138,6 +176,15 @@ impl HookManager {
         hook.timeout()
     }
 
+    /// Get the failure policy for a hook: config override, then the hook's own, then the default
+    fn get_failure_policy(&self, hook: &dyn Hook) -> HookFailurePolicy {
+        self.configs
+            .get(hook.id())
+            .and_then(|config| config.on_failure)
+            .or_else(|| hook.failure_policy())
+            .unwrap_or(self.default_failure_policy)
+    }
+
     /// Execute all hooks for an event
     pub async fn execute(&self, event: HookEvent, context: &mut HookContext) -> HookResult {
         // Get hook IDs for this event
@@ -167,15 +214,31 @@ impl HookManager {
                 continue;
             }
 
-            // Execute with timeout
+            // Execute with timeout, turning panics into errors
             let hook_timeout = self.get_timeout(hook.as_ref());
+            // Capture the collector now: the thread-local may differ after the await
+            let collector = with_active_collector(Arc::clone);
+            let span = collector.as_ref().map(|c| c.start_span(SpanType::Hook, hook.id()));
             let start = Instant::now();
 
-            let result = match timeout(hook_timeout, hook.execute(context)).await {
-                Ok(result) => result,
+            let (result, outcome) = match timeout(
+                hook_timeout,
+                AssertUnwindSafe(hook.execute(context)).catch_unwind(),
+            )
+            .await
+            {
+                Ok(Ok(result)) => (result, HookOutcome::Completed),
+                Ok(Err(payload)) => {
+                    let msg = panic_message(payload.as_ref());
+                    log::error!("[HOOKS] Hook {} panicked: {}", hook.id(), msg);
+                    (HookResult::Error(format!("Hook panicked: {}", msg)), HookOutcome::Panicked)
+                }
                 Err(_) => {
                     log::warn!("[HOOKS] Hook {} timed out after {:?}", hook.id(), hook_timeout);
-                    HookResult::Error(format!("Hook timed out after {:?}", hook_timeout))
+                    (
+                        HookResult::Error(format!("Hook timed out after {:?}", hook_timeout)),
+                        HookOutcome::TimedOut,
+                    )
                 }
             };
 
@@ -183,20 +246,33 @@ impl HookManager {
             let duration_ms = start.elapsed().as_millis() as u64;
             if let Some(mut stats) = self.stats.get_mut(&hook_id) {
                 stats.record_execution(duration_ms, &result);
+                match outcome {
+                    HookOutcome::TimedOut => stats.timeouts += 1,
+                    HookOutcome::Panicked => stats.panics += 1,
+                    HookOutcome::Completed => {}
+                }
             }
 
-            log::debug!(
-                "[HOOKS] Hook {} completed in {}ms with result: {:?}",
-                hook.id(),
-                duration_ms,
-                match &result {
-                    HookResult::Continue(_) => "continue",
-                    HookResult::Skip => "skip",
-                    HookResult::Cancel(_) => "cancel",
-                    HookResult::Replace(_) => "replace",
-                    HookResult::Error(_) => "error",
+            let label = match outcome {
+                HookOutcome::Completed => result_label(&result),
+                HookOutcome::TimedOut => "timeout",
+                HookOutcome::Panicked => "panic",
+            };
+            if let (Some(collector), Some(mut span)) = (collector, span) {
+                span.attributes = serde_json::json!({
+                    "hook_id": hook.id(),
+                    "event": event.as_str(),
+                    "outcome": label,
+                });
+                match (&result, outcome) {
+                    (_, HookOutcome::TimedOut) => span.timeout(),
+                    (HookResult::Error(msg), _) => span.fail(msg.clone()),
+                    _ => span.succeed(),
                 }
-            );
+                collector.record(span);
+            }
+
+            log::debug!("[HOOKS] Hook {} completed in {}ms with result: {}", hook.id(), duration_ms, label);
 
             // Handle result
             match &result {
@@ -215,12 +291,15 @@ impl HookManager {
                 HookResult::Replace(value) => {
                     final_result = HookResult::Replace(value.clone());
                 }
-                HookResult::Error(msg) => {
-                    if !self.continue_on_error {
-                        return HookResult::Error(msg.clone());
+                HookResult::Error(msg) => match self.get_failure_policy(hook.as_ref()) {
+                    HookFailurePolicy::Block => {
+                        log::warn!("[HOOKS] Hook {} failed (blocking): {}", hook.id(), msg);
+                        return HookResult::Cancel(format!("Hook '{}' failed: {}", hook.id(), msg));
                     }
-                    log::warn!("[HOOKS] Hook {} error (continuing): {}", hook.id(), msg);
-                }
+                    HookFailurePolicy::Continue => {
+                        log::warn!("[HOOKS] Hook {} error (continuing): {}", hook.id(), msg);
+                    }
+                },
             }
         }
 
@@ -337,4 +416,99 @@ mod tests {
 
         assert!(result.should_continue());
     }
+
+    enum Fault {
+        Hang,
+        Panic,
+    }
+
+    struct FaultyHook {
+        fault: Fault,
+        policy: Option<HookFailurePolicy>,
+    }
+
+    #[async_trait]
+    impl Hook for FaultyHook {
+        fn id(&self) -> &str {
+            "faulty_hook"
+        }
+
+        fn name(&self) -> &str {
+            "Faulty Hook"
+        }
+
+        fn events(&self) -> Vec<HookEvent> {
+            vec![HookEvent::BeforeToolCall]
+        }
+
+        fn timeout(&self) -> std::time::Duration {
+            std::time::Duration::from_millis(50)
+        }
+
+        fn failure_policy(&self) -> Option<HookFailurePolicy> {
+            self.policy
+        }
+
+        async fn execute(&self, _context: &mut HookContext) -> HookResult {
+            match self.fault {
+                Fault::Hang => {
+                    tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+                    HookResult::Continue(None)
+                }
+                Fault::Panic => panic!("hook exploded"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hook_timeout_continues_by_default() {
+        let manager = HookManager::new();
+        manager.register(Arc::new(FaultyHook { fault: Fault::Hang, policy: None }));
+
+        let mut context = HookContext::new(HookEvent::BeforeToolCall);
+        let result = manager.execute(HookEvent::BeforeToolCall, &mut context).await;
+
+        assert!(result.should_continue());
+        let stats = manager.get_stats("faulty_hook").unwrap();
+        assert_eq!((stats.timeouts, stats.failures), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_hook_panic_is_caught_and_blocks_when_configured() {
+        let manager = HookManager::new().with_failure_policy(HookFailurePolicy::Block);
+        manager.register(Arc::new(FaultyHook { fault: Fault::Panic, policy: None }));
+
+        let mut context = HookContext::new(HookEvent::BeforeToolCall);
+        let result = manager.execute(HookEvent::BeforeToolCall, &mut context).await;
+
+        match result {
+            HookResult::Cancel(msg) => assert!(msg.contains("hook exploded"), "{}", msg),
+            _ => panic!("expected the failing hook to cancel the operation"),
+        }
+        assert_eq!(manager.get_stats("faulty_hook").unwrap().panics, 1);
+    }
+
+    #[tokio::test]
+    async fn test_failure_policy_precedence() {
+        let manager = HookManager::new().with_failure_policy(HookFailurePolicy::Block);
+        manager.register(Arc::new(FaultyHook {
+            fault: Fault::Panic,
+            policy: Some(HookFailurePolicy::Continue),
+        }));
+
+        let mut context = HookContext::new(HookEvent::BeforeToolCall);
+        assert!(manager.execute(HookEvent::BeforeToolCall, &mut context).await.should_continue());
+
+        // A config override beats the hook's own policy
+        manager.configure(HookConfig {
+            id: "faulty_hook".to_string(),
+            enabled: true,
+            priority: None,
+            timeout_secs: None,
+            on_failure: Some(HookFailurePolicy::Block),
+            config: None,
+        });
+        let result = manager.execute(HookEvent::BeforeToolCall, &mut context).await;
+        assert!(matches!(result, HookResult::Cancel(_)));
+    }
 }
@@ -32,6 +32,4 @@ mod manager;
 mod types;
 
 pub use manager::HookManager;
-pub use types::{
-    BoxedHook, Hook, HookConfig, HookContext, HookEvent, HookPriority, HookResult, HookStats,
-};
+pub use types::{Hook, HookContext, HookEvent, HookFailurePolicy, HookResult};
@@ -121,6 +121,26 @@ impl HookResult {
     }
 }
 
+/// What happens when a hook errors, times out or panics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HookFailurePolicy {
+    /// Log the failure and keep going with the remaining hooks
+    Continue,
+    /// Fail closed: stop running hooks and cancel the operation
+    Block,
+}
+
+impl HookFailurePolicy {
+    /// Parse a policy name, falling back to `Continue`
+    pub fn from_str_or_default(s: &str) -> Self {
+        match s.trim().to_lowercase().as_str() {
+            "block" => HookFailurePolicy::Block,
+            _ => HookFailurePolicy::Continue,
+        }
+    }
+}
+
 /// Priority levels for hook execution order
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -332,6 +352,11 @@ pub trait Hook: Send + Sync {
         Duration::from_secs(5)
     }
 
+    /// Failure policy for this hook (None = the manager's default)
+    fn failure_policy(&self) -> Option<HookFailurePolicy> {
+        None
+    }
+
     /// Whether this hook is enabled
     fn enabled(&self) -> bool {
         true
@@ -352,6 +377,9 @@ pub struct HookConfig {
     pub priority: Option<HookPriority>,
     /// Timeout override in seconds
     pub timeout_secs: Option<u64>,
+    /// Failure policy override
+    #[serde(default)]
+    pub on_failure: Option<HookFailurePolicy>,
     /// Custom configuration for the hook
     pub config: Option<Value>,
 }
@@ -373,6 +401,12 @@ pub struct HookStats {
     pub avg_execution_ms: f64,
     /// Maximum execution time in milliseconds
     pub max_execution_ms: u64,
+    /// Executions cut off by the timeout (also counted as failures)
+    #[serde(default)]
+    pub timeouts: u64,
+    /// Executions that panicked (also counted as failures)
+    #[serde(default)]
+    pub panics: u64,
 }
 
 impl HookStats {
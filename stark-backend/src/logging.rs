@@ -0,0 +1,263 @@
+//! Process logger with runtime level overrides.
+//!
+//! Startup levels still come from `RUST_LOG`, but levels can be overridden per
+//! target while the instance runs (see `PUT /api/system/log-levels`). A selector
+//! is either a module path prefix (`stark_backend::channels::dispatcher`) or a
+//! log tag: an upper-case word like `DISPATCH` matches lines starting with
+//! `[DISPATCH]`. Target overrides win over tag overrides, and the most specific
+//! target wins.
+
+use log::{LevelFilter, Log, Metadata, Record};
+use serde::Serialize;
+use std::borrow::Cow;
+use std::io::Write;
+use std::sync::{OnceLock, RwLock};
+
+use crate::telemetry::trace::current_trace_id;
+
+static OVERRIDES: RwLock<Vec<LevelOverride>> = RwLock::new(Vec::new());
+
+/// Max level allowed by `RUST_LOG`, set once at startup
+static ENV_MAX_LEVEL: OnceLock<LevelFilter> = OnceLock::new();
+
+/// What a level override applies to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selector {
+    /// Module path prefix, matched on `::` boundaries
+    Target(String),
+    /// Bracketed tag at the start of the message, stored without brackets
+    Tag(String),
+}
+
+impl Selector {
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        let unbracketed = raw.strip_prefix('[').and_then(|s| s.strip_suffix(']')).unwrap_or(raw);
+        if unbracketed.is_empty() {
+            return None;
+        }
+        let is_tag = unbracketed.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            && unbracketed.chars().any(|c| c.is_ascii_uppercase());
+        if is_tag {
+            Some(Selector::Tag(unbracketed.to_string()))
+        } else if raw.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':') {
+            Some(Selector::Target(raw.to_string()))
+        } else {
+            None
+        }
+    }
+
+    fn as_string(&self) -> String {
+        match self {
+            Selector::Target(t) => t.clone(),
+            Selector::Tag(t) => format!("[{}]", t),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LevelOverride {
+    pub selector: Selector,
+    pub level: LevelFilter,
+}
+
+/// Serializable view of the current logging configuration
+#[derive(Debug, Serialize)]
+pub struct LogLevels {
+    /// Max level from RUST_LOG
+    pub default: String,
+    pub overrides: Vec<LogLevelEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogLevelEntry {
+    pub selector: String,
+    pub level: String,
+}
+
+/// Set (or with `None`, remove) the level override for a selector
+pub fn set_level(selector: Selector, level: Option<LevelFilter>) {
+    let mut overrides = OVERRIDES.write().unwrap_or_else(|e| e.into_inner());
+    overrides.retain(|o| o.selector != selector);
+    if let Some(level) = level {
+        overrides.push(LevelOverride { selector, level });
+    }
+    update_max_level(&overrides);
+}
+
+/// Drop all overrides, returning to the RUST_LOG levels
+pub fn reset_levels() {
+    let mut overrides = OVERRIDES.write().unwrap_or_else(|e| e.into_inner());
+    overrides.clear();
+    update_max_level(&overrides);
+}
+
+pub fn current_levels() -> LogLevels {
+    let overrides = OVERRIDES.read().unwrap_or_else(|e| e.into_inner());
+    LogLevels {
+        default: env_max_level().to_string().to_lowercase(),
+        overrides: overrides
+            .iter()
+            .map(|o| LogLevelEntry {
+                selector: o.selector.as_string(),
+                level: o.level.to_string().to_lowercase(),
+            })
+            .collect(),
+    }
+}
+
+fn env_max_level() -> LevelFilter {
+    ENV_MAX_LEVEL.get().copied().unwrap_or(LevelFilter::Info)
+}
+
+/// `log`'s global max level gates records before they reach the logger, so it
+/// must cover the most verbose override.
+fn update_max_level(overrides: &[LevelOverride]) {
+    let max = overrides.iter().map(|o| o.level).fold(env_max_level(), Ord::max);
+    log::set_max_level(max);
+}
+
+/// The `[TAG]` a message starts with, if any
+fn message_tag(message: &str) -> Option<&str> {
+    let rest = message.strip_prefix('[')?;
+    rest.split_once(']').map(|(tag, _)| tag)
+}
+
+/// Level override that applies to a record, if any. `message` is only
+/// rendered when a tag override could apply.
+fn override_for<'a>(
+    overrides: &[LevelOverride],
+    target: &str,
+    message: impl FnOnce() -> Cow<'a, str>,
+) -> Option<LevelFilter> {
+    let target_match = overrides
+        .iter()
+        .filter_map(|o| match &o.selector {
+            Selector::Target(t)
+                if target == t || (target.starts_with(t.as_str()) && target[t.len()..].starts_with("::")) =>
+            {
+                Some((t.len(), o.level))
+            }
+            _ => None,
+        })
+        .max_by_key(|(len, _)| *len);
+    if let Some((_, level)) = target_match {
+        return Some(level);
+    }
+
+    if !overrides.iter().any(|o| matches!(o.selector, Selector::Tag(_))) {
+        return None;
+    }
+    let message = message();
+    let tag = message_tag(&message)?;
+    overrides.iter().find_map(|o| match &o.selector {
+        Selector::Tag(t) if t == tag => Some(o.level),
+        _ => None,
+    })
+}
+
+struct StarkLogger {
+    /// Filters by RUST_LOG only (never writes)
+    env_filter: env_logger::Logger,
+    /// Writes every record it is given
+    writer: env_logger::Logger,
+}
+
+impl Log for StarkLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        let allowed = {
+            let overrides = OVERRIDES.read().unwrap_or_else(|e| e.into_inner());
+            let message = || match record.args().as_str() {
+                Some(s) => Cow::Borrowed(s),
+                None => Cow::Owned(record.args().to_string()),
+            };
+            match override_for(&overrides, record.target(), message) {
+                Some(level) => record.level() <= level,
+                None => self.env_filter.matches(record),
+            }
+        };
+        if allowed {
+            self.writer.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.writer.flush();
+    }
+}
+
+/// Install the process logger: env_logger's usual format, plus the trace id
+/// for lines logged inside a dispatch.
+pub fn init() {
+    let env_filter = env_logger::Builder::from_default_env().build();
+    // Filtering is done above, so the writer only takes RUST_LOG_STYLE from the environment
+    let writer = env_logger::Builder::new()
+        .parse_write_style(&std::env::var("RUST_LOG_STYLE").unwrap_or_default())
+        .filter_level(LevelFilter::Trace)
+        .format(|buf, record| {
+            let level_style = buf.default_level_style(record.level());
+            write!(
+                buf,
+                "[{} {level_style}{:<5}{level_style:#} {}] ",
+                buf.timestamp(),
+                record.level(),
+                record.target()
+            )?;
+            if let Some(id) = current_trace_id() {
+                write!(buf, "[trace:{}] ", id)?;
+            }
+            writeln!(buf, "{}", record.args())
+        })
+        .build();
+
+    let env_max = env_filter.filter();
+    let _ = ENV_MAX_LEVEL.set(env_max);
+    if log::set_boxed_logger(Box::new(StarkLogger { env_filter, writer })).is_ok() {
+        log::set_max_level(env_max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(selector: &str, level: LevelFilter) -> LevelOverride {
+        LevelOverride { selector: Selector::parse(selector).unwrap(), level }
+    }
+
+    #[test]
+    fn parses_targets_and_tags() {
+        assert_eq!(Selector::parse("DISPATCH"), Some(Selector::Tag("DISPATCH".to_string())));
+        assert_eq!(Selector::parse("[TX_QUEUE]"), Some(Selector::Tag("TX_QUEUE".to_string())));
+        assert_eq!(
+            Selector::parse("stark_backend::channels"),
+            Some(Selector::Target("stark_backend::channels".to_string()))
+        );
+        assert_eq!(Selector::parse(""), None);
+        assert_eq!(Selector::parse("a b"), None);
+    }
+
+    #[test]
+    fn most_specific_target_wins_then_tags() {
+        let overrides = vec![
+            level("stark_backend", LevelFilter::Warn),
+            level("stark_backend::channels", LevelFilter::Debug),
+            level("DISPATCH", LevelFilter::Trace),
+        ];
+        let msg = |m: &'static str| move || Cow::Borrowed(m);
+
+        assert_eq!(
+            override_for(&overrides, "stark_backend::channels::dispatcher", msg("[DISPATCH] x")),
+            Some(LevelFilter::Debug)
+        );
+        assert_eq!(override_for(&overrides, "stark_backend::db", msg("x")), Some(LevelFilter::Warn));
+        // Prefixes only match on module boundaries
+        assert_eq!(override_for(&overrides, "stark_backend_extra", msg("x")), None);
+        assert_eq!(override_for(&overrides, "actix_web", msg("[DISPATCH] x")), Some(LevelFilter::Trace));
+        assert_eq!(override_for(&overrides, "actix_web", msg("[OTHER] x")), None);
+    }
+}
@@ -1,4 +1,3 @@
-use actix_cors::Cors;
 use actix_files::{Files, NamedFile};
 use actix_web::{middleware::Logger, web, App, HttpServer};
 use dotenv::dotenv;
@@ -19,6 +18,7 @@ mod domain_types;
 mod execution;
 mod gateway;
 mod integrations;
+mod logging;
 mod middleware;
 mod models;
 mod notes;
@@ -48,7 +48,7 @@ use config::Config;
 use db::{ActiveSessionCache, Database};
 use execution::ExecutionTracker;
 use gateway::{events::EventBroadcaster, Gateway};
-use hooks::HookManager;
+use hooks::{Hook, HookManager};
 use scheduler::{Scheduler, SchedulerConfig};
 use skills::SkillRegistry;
 use tools::ToolRegistry;
@@ -87,8 +87,6 @@ pub struct AppState {
     pub hybrid_search: Option<Arc<memory::HybridSearchEngine>>,
     /// Concrete remote embedding generator for live URL updates
     pub remote_embedding_generator: Option<Arc<memory::embeddings::RemoteEmbeddingGenerator>>,
-    /// Bearer token for internal module-to-backend API calls (e.g. wallet signing proxy)
-    pub internal_token: String,
     /// In-memory cache for active session metadata (shared with dispatcher for admin invalidation)
     pub active_cache: Arc<ActiveSessionCache>,
 }
@@ -107,6 +105,7 @@ pub struct AppState {
 async fn auto_retrieve_from_keystore(
     db: &std::sync::Arc<db::Database>,
     wallet_provider: &std::sync::Arc<dyn wallet::WalletProvider>,
+    disk_quota: Option<&std::sync::Arc<disk_quota::DiskQuotaManager>>,
 ) {
     const MAX_RETRIES: u32 = 3;
     const INITIAL_BACKOFF_SECS: u64 = 2;
@@ -151,6 +150,14 @@ async fn auto_retrieve_from_keystore(
 
     log::info!("[Keystore] Fresh instance detected, attempting auto-retrieval for {}", wallet_address);
 
+    let auto_restore_selection = match backup::restore::RestoreSelection::parse(&config::auto_restore_sections()) {
+        Ok(selection) => selection,
+        Err(e) => {
+            log::warn!("[Keystore] {}; restoring all sections", e);
+            backup::restore::RestoreSelection::all()
+        }
+    };
+
     // Retry loop with exponential backoff
     let mut last_error = String::new();
     for attempt in 0..MAX_RETRIES {
@@ -198,7 +205,10 @@ async fn auto_retrieve_from_keystore(
                                 return;
                             }
                         };
-                        match backup::restore::restore_all(db, &mut backup_data, None, None, None).await {
+                        match backup::restore::restore_all(
+                            db, &mut backup_data, None, None, None, disk_quota,
+                            &auto_restore_selection, backup::restore::ConflictPolicy::default(),
+                        ).await {
                             Ok(restore_result) => {
                                 log::info!("[Keystore] Auto-sync: {}", restore_result.summary());
                                 let _ = db.record_auto_sync_result(
@@ -374,25 +384,43 @@ fn start_module_services(db: &Database) {
         // Pass relevant API keys + port + internal signing token to child services
         let mut envs: Vec<(String, String)> = api_key_envs.clone();
         envs.push(("MODULE_PORT".to_string(), port.to_string()));
-        // Internal token for module→backend API calls (wallet signing proxy)
-        if let Ok(token) = std::env::var("STARKBOT_INTERNAL_TOKEN") {
-            envs.push(("STARKBOT_INTERNAL_TOKEN".to_string(), token));
-        }
         // Self URL so modules can call back to the backend
         envs.push(("STARKBOT_SELF_URL".to_string(), config::self_url()));
         if let Some(ref port_var) = svc.port_env_var {
             envs.push((port_var.clone(), port.to_string()));
         }
 
-        let env_refs: Vec<(&str, &str)> = envs.iter()
-            .map(|(k, v)| (k.as_str(), v.as_str()))
-            .collect();
-
-        if let Some(ref command) = svc.command {
-            start_service_command(command, &svc.module_dir, &svc.name, port, &env_refs);
-        } else {
-            start_service_binary(&svc.binary_path, &svc.name, port, &env_refs);
-        }
+        let name = svc.name.clone();
+        let command = svc.command.clone();
+        let module_dir = svc.module_dir.clone();
+        let binary_path = svc.binary_path.clone();
+        let permissions = svc.permissions.clone();
+        modules::supervisor::spawn(&svc.name, svc.limits.clone(), move || {
+            let mut envs = envs.clone();
+            // Internal token for module→backend API calls (wallet signing proxy).
+            // The token is initialised after startup spawns, so fall back to the env value.
+            let token = Some(modules::internal_token::current())
+                .filter(|t| !t.is_empty())
+                .or_else(|| std::env::var("STARKBOT_INTERNAL_TOKEN").ok());
+            if let Some(token) = token {
+                envs.push(("STARKBOT_INTERNAL_TOKEN".to_string(), token));
+            }
+            // Per-module identity, checked by the wallet signing proxy
+            envs.push((
+                modules::module_identity::ENV_VAR.to_string(),
+                modules::module_identity::issue(&name, permissions.clone()),
+            ));
+
+            let env_refs: Vec<(&str, &str)> = envs.iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect();
+
+            if let Some(ref command) = command {
+                start_service_command(command, &module_dir, &name, port, &env_refs)
+            } else {
+                start_service_binary(&binary_path, &name, port, &env_refs)
+            }
+        });
 
         // Set env vars in parent process so manifest.service_url() resolves correctly
         // when DynamicModule makes RPC calls to this service.
@@ -428,7 +456,7 @@ fn find_free_port() -> Option<u16> {
 
 /// Start a single service binary.
 /// The caller is responsible for checking port availability before calling this.
-fn start_service_binary(exe_path: &std::path::Path, name: &str, port: u16, envs: &[(&str, &str)]) {
+fn start_service_binary(exe_path: &std::path::Path, name: &str, port: u16, envs: &[(&str, &str)]) -> Option<std::process::Child> {
     let mut cmd = std::process::Command::new(exe_path);
     cmd.stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped());
@@ -445,16 +473,18 @@ fn start_service_binary(exe_path: &std::path::Path, name: &str, port: u16, envs:
                 child.stdout.take(),
                 child.stderr.take(),
             );
+            Some(child)
         }
         Err(e) => {
             log::error!("[MODULE] Failed to start {}: {}", name, e);
+            None
         }
     }
 }
 
 /// Start a service via a shell command (e.g. "uv run service.py").
 /// The command is run from the module directory with `sh -c`.
-fn start_service_command(command: &str, cwd: &std::path::Path, name: &str, port: u16, envs: &[(&str, &str)]) {
+fn start_service_command(command: &str, cwd: &std::path::Path, name: &str, port: u16, envs: &[(&str, &str)]) -> Option<std::process::Child> {
     let mut cmd = std::process::Command::new("sh");
     cmd.arg("-c").arg(command);
     cmd.current_dir(cwd);
@@ -473,9 +503,11 @@ fn start_service_command(command: &str, cwd: &std::path::Path, name: &str, port:
                 child.stdout.take(),
                 child.stderr.take(),
             );
+            Some(child)
         }
         Err(e) => {
             log::error!("[MODULE] Failed to start {} via `{}`: {}", name, command, e);
+            None
         }
     }
 }
@@ -666,7 +698,7 @@ fn ensure_cli_gateway_channel(db: &std::sync::Arc<db::Database>, token: &str) {
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     dotenv().ok();
-    env_logger::init();
+    logging::init();
 
     // Load presets and tokens from config directory
     // Check ./config first, then ../config (for running from subdirectory)
@@ -755,6 +787,30 @@ async fn main() -> std::io::Result<()> {
         Err(e) => log::warn!("Failed to load x402 payment limits from DB: {}", e),
     }
 
+    // Load x402 rolling-window budgets and seed the spend ledger from payment history
+    match db.get_all_x402_payment_budgets() {
+        Ok(budgets) => {
+            for b in &budgets {
+                x402::payment_limits::set_budget(&b.asset, &b.max_amount, b.window_hours);
+            }
+            if !budgets.is_empty() {
+                log::info!("Loaded {} x402 payment budgets from database", budgets.len());
+            }
+        }
+        Err(e) => log::warn!("Failed to load x402 payment budgets from DB: {}", e),
+    }
+    match db.get_x402_spends_since_hours(x402::payment_limits::MAX_WINDOW_HOURS) {
+        Ok(spends) => {
+            for spend in &spends {
+                let at = chrono::NaiveDateTime::parse_from_str(&spend.created_at, "%Y-%m-%d %H:%M:%S")
+                    .map(|dt| dt.and_utc())
+                    .unwrap_or_else(|_| chrono::Utc::now());
+                x402::payment_limits::record_spend(&spend.asset, &spend.amount, at);
+            }
+        }
+        Err(e) => log::warn!("Failed to seed x402 spend ledger from DB: {}", e),
+    }
+
     // Load RPC configuration into the unified resolver so ALL codepaths
     // (tools, eip8004, x402 signer, etc.) share the same resolution logic.
     {
@@ -770,6 +826,7 @@ async fn main() -> std::io::Result<()> {
             if let Some(endpoints) = settings.custom_rpc_endpoints {
                 tools::rpc_config::set_custom_rpc_endpoints(endpoints);
             }
+            wallet::set_signing_frozen(settings.signing_frozen);
         }
     }
 
@@ -852,6 +909,8 @@ async fn main() -> std::io::Result<()> {
     } else {
         start_module_services(&db);
     }
+    // Sample module service resource usage and enforce manifest limits
+    modules::supervisor::start_monitor(config::module_monitor_interval());
 
     // Initialize Tool Registry with built-in tools + installed module tools
     log::info!("Initializing tool registry");
@@ -875,7 +934,9 @@ async fn main() -> std::io::Result<()> {
 
     // Initialize Skill Registry (disk-primary, DB is synced index)
     log::info!("Initializing skill registry");
-    let skill_registry = Arc::new(skills::create_default_registry(db.clone()));
+    let skill_registry = Arc::new(
+        skills::create_default_registry(db.clone()).with_tool_registry(tool_registry.clone()),
+    );
 
     // Sync skills from disk to database
     let skill_count = skill_registry.sync_to_db().await.unwrap_or_else(|e| {
@@ -927,8 +988,8 @@ async fn main() -> std::io::Result<()> {
         log::info!("Flash mode: initializing FlashWalletProvider (Privy embedded wallet)...");
         match wallet::FlashWalletProvider::new().await {
             Ok(provider) => {
-                log::info!("Flash wallet provider initialized: {} (mode: {})",
-                    provider.get_address(), provider.mode_name());
+                log::info!("Flash wallet provider initialized: {} (mode: {}, wallets: {})",
+                    provider.get_address(), provider.mode_name(), provider.list_wallets().len());
                 Some(Arc::new(provider) as Arc<dyn wallet::WalletProvider>)
             }
             Err(e) => {
@@ -939,10 +1000,10 @@ async fn main() -> std::io::Result<()> {
     } else if let Some(ref pk) = config.burner_wallet_private_key {
         // Standard mode - use raw private key from environment
         log::info!("Standard mode: initializing EnvWalletProvider...");
-        match wallet::EnvWalletProvider::from_private_key(pk) {
+        match wallet::EnvWalletProvider::from_private_key(pk).and_then(|p| p.with_wallets_from_env()) {
             Ok(provider) => {
-                log::info!("Wallet provider initialized: {} (mode: {})",
-                    provider.get_address(), provider.mode_name());
+                log::info!("Wallet provider initialized: {} (mode: {}, wallets: {})",
+                    provider.get_address(), provider.mode_name(), provider.list_wallets().len());
                 Some(Arc::new(provider) as Arc<dyn wallet::WalletProvider>)
             }
             Err(e) => {
@@ -955,6 +1016,11 @@ async fn main() -> std::io::Result<()> {
         None
     };
 
+    // Start the stuck-transaction monitor (needs a wallet for receipt checks and replacements)
+    if let Some(ref wp) = wallet_provider {
+        tx_queue::spawn_stuck_tx_monitor(db.clone(), tx_queue.clone(), wp.clone());
+    }
+
     // Flash mode: ECIES encryption key is now derived on-demand via
     // wallet_provider.get_encryption_key() — no startup derivation needed.
 
@@ -974,8 +1040,12 @@ async fn main() -> std::io::Result<()> {
 
     // Initialize Hook Manager
     log::info!("Initializing hook manager");
-    let hook_manager = Arc::new(HookManager::new());
-    log::info!("Hook manager initialized");
+    let hook_manager = Arc::new(HookManager::new().with_failure_policy(config::hook_failure_policy()));
+    for webhook in hooks::builtin::load_webhook_hooks(config_dir) {
+        log::info!("Registering {}", webhook.name());
+        hook_manager.register(Arc::new(webhook));
+    }
+    log::info!("Hook manager initialized with {} hooks", hook_manager.hook_count());
 
     // Initialize Tool Validator Registry
     log::info!("Initializing tool validator registry");
@@ -1002,7 +1072,7 @@ async fn main() -> std::io::Result<()> {
         Some(Arc::new(memory::HybridSearchEngine::new(
             db.clone(),
             embedding_generator.clone(),
-        )));
+        ).with_config(memory::hybrid_search::HybridSearchConfig::from_env())));
 
     // One-time migration: import QMD markdown files into the DB memories table.
     // This runs once; afterward the memory/ directory is renamed to memory.migrated/.
@@ -1034,6 +1104,7 @@ async fn main() -> std::io::Result<()> {
     // Rebuild FTS index on startup to ensure it's in sync with the memories table.
     // This is cheap (takes <100ms for typical memory counts) and guarantees search works
     // even if the index got out of sync from a restore, crash, or schema migration.
+    // It also recreates the index if the memory_fts_tokenizer setting changed.
     match db.rebuild_fts_index() {
         Ok(()) => {
             let count = db.count_memories().unwrap_or(0);
@@ -1070,6 +1141,9 @@ async fn main() -> std::io::Result<()> {
     let broadcaster = gateway.broadcaster();
     let channel_manager = gateway.channel_manager();
 
+    // Auto-reject partner-mode transactions the user doesn't confirm in time
+    tx_queue::spawn_confirmation_expiry_monitor(tx_queue.clone(), broadcaster.clone(), db.clone());
+
     // Initialize and start the scheduler
     log::info!("Initializing scheduler");
     let scheduler_config = SchedulerConfig::default();
@@ -1139,10 +1213,11 @@ async fn main() -> std::io::Result<()> {
         let db_bg = db.clone();
         let gateway_bg = gateway.clone();
         let wallet_provider_bg = wallet_provider.clone();
+        let disk_quota_bg = disk_quota.clone();
         tokio::spawn(async move {
             // Keystore auto-retrieve (works in both Standard and Flash mode via wallet provider)
             if let Some(ref wp) = wallet_provider_bg {
-                auto_retrieve_from_keystore(&db_bg, wp).await;
+                auto_retrieve_from_keystore(&db_bg, wp, disk_quota_bg.as_ref()).await;
             }
 
             // Auto-create CLI gateway channel if CLI_GATEWAY_TOKEN env var is set
@@ -1252,10 +1327,44 @@ async fn main() -> std::io::Result<()> {
                         log::error!("[SESSION_CLEANUP] Failed to clean up excess sessions: {}", e);
                     }
                 }
+                // Drop login sessions that expired over a week ago (recent ones are kept
+                // so the API can report "session_expired" rather than an unknown token)
+                if let Err(e) = db_cleanup.delete_expired_sessions(7 * 24) {
+                    log::error!("[SESSION_CLEANUP] Failed to delete expired auth sessions: {}", e);
+                }
             }
         });
     }
 
+    // Spawn broadcast history retention task — prunes finalized broadcast records by
+    // age/row count. Unconfirmed (broadcast/stuck) records are never pruned.
+    {
+        let retention_days = config::tx_history_retention_days();
+        let max_rows = config::tx_history_max_rows();
+        if retention_days.is_some() || max_rows.is_some() {
+            let db_history = db.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(6 * 3600));
+                loop {
+                    interval.tick().await;
+                    match db_history.prune_broadcast_history(retention_days, max_rows) {
+                        Ok(0) => {}
+                        Ok(count) => {
+                            log::info!("[TX_HISTORY] Pruned {} finalized broadcast record(s)", count);
+                        }
+                        Err(e) => {
+                            log::error!("[TX_HISTORY] Failed to prune broadcast history: {}", e);
+                        }
+                    }
+                }
+            });
+            log::info!(
+                "Broadcast history retention task spawned (retention_days={:?}, max_rows={:?})",
+                retention_days, max_rows
+            );
+        }
+    }
+
     // Module workers are now managed by standalone services — no workers to spawn here.
     // Keep an empty map in AppState for API compatibility.
     let module_workers = Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::<String, tokio::task::JoinHandle<()>>::new()));
@@ -1274,9 +1383,9 @@ async fn main() -> std::io::Result<()> {
         ""
     };
 
-    let dev_mode = std::env::var("STARKBOT_DEV").map(|v| v == "true" || v == "1").unwrap_or(false);
+    let dev_mode = controllers::dev::dev_mode_enabled();
     if dev_mode {
-        log::warn!("⚠️  DEV MODE ENABLED — /api/dev/chat is accessible without auth");
+        log::warn!("⚠️  DEV MODE ENABLED — /api/dev/chat and /api/dev/ws are accessible without auth");
     }
 
     log::info!("Starting StarkBot server on port {}", port);
@@ -1312,15 +1421,23 @@ async fn main() -> std::io::Result<()> {
     let dev_mode = dev_mode;
     // Internal token for module-to-backend API calls (wallet signing proxy, etc.)
     // Token is generated early in startup (before module services are spawned).
-    let internal_token = std::env::var("STARKBOT_INTERNAL_TOKEN")
-        .expect("STARKBOT_INTERNAL_TOKEN should have been set during startup");
+    modules::internal_token::init(
+        &std::env::var("STARKBOT_INTERNAL_TOKEN")
+            .expect("STARKBOT_INTERNAL_TOKEN should have been set during startup"),
+    );
+
+    let cors_policy = config::cors_policy();
+    match &cors_policy {
+        middleware::cors::CorsPolicy::Permissive => {
+            log::warn!("CORS: any origin may call the API (STARK_CORS_ALLOWED_ORIGINS=*)")
+        }
+        middleware::cors::CorsPolicy::AllowList(origins) => {
+            log::info!("CORS: same-origin plus {} allowed origin(s)", origins.len())
+        }
+    }
 
     let server = HttpServer::new(move || {
-        let cors = Cors::default()
-            .allow_any_origin()
-            .allow_any_method()
-            .allow_any_header()
-            .max_age(3600);
+        let cors = middleware::cors::build(&cors_policy);
 
         let mut app = App::new()
             .app_data(web::Data::new(AppState {
@@ -1345,16 +1462,18 @@ async fn main() -> std::io::Result<()> {
                 resource_manager: Arc::new(telemetry::ResourceManager::new(Arc::clone(&db))),
                 hybrid_search: hybrid_search_engine.clone(),
                 remote_embedding_generator: Some(Arc::clone(&remote_embedding_generator)),
-                internal_token: internal_token.clone(),
                 active_cache: disp.active_cache().clone(),
             }))
             .app_data(web::Data::new(Arc::clone(&sched)))
             // WebSocket data for /ws route
             .app_data(web::Data::new(Arc::clone(&db)))
             .app_data(web::Data::new(Arc::clone(&chan_mgr)))
+            .app_data(web::Data::new(Arc::clone(&disp)))
             .app_data(web::Data::new(Arc::clone(&bcast)))
             .app_data(web::Data::new(Arc::clone(&tx_q)))
             .app_data(web::Data::new(wallet_prov.clone()))
+            .app_data(middleware::body_limit::json_config(config::max_body_bytes()))
+            .app_data(middleware::body_limit::payload_config(config::max_body_bytes()))
             .wrap(Logger::default())
             .wrap(cors)
             .configure(controllers::health::config_routes)
@@ -1377,6 +1496,7 @@ async fn main() -> std::io::Result<()> {
             .configure(controllers::intrinsic::config)
             .configure(controllers::notes::config)
             .configure(controllers::tx_queue::config)
+            .configure(controllers::confirmation::config)
             .configure(controllers::broadcasted_transactions::config)
             .configure(controllers::impulse_map::config)
             .configure(controllers::kanban::config)
@@ -1399,6 +1519,11 @@ async fn main() -> std::io::Result<()> {
             // WebSocket Gateway route (same port as HTTP, required for single-port platforms)
             .route("/ws", web::get().to(gateway::actix_ws::ws_handler));
 
+        // Unauthenticated dev endpoints — never registered unless STARKBOT_DEV is set
+        if dev_mode {
+            app = app.configure(controllers::dev::config);
+        }
+
         // Serve static files only if frontend dist exists
         if !frontend_dist.is_empty() {
             app = app.service(
@@ -6,6 +6,12 @@ pub struct DecayConfig {
     pub half_life_days: f64,
     /// Bonus importance added when a memory was recently accessed (default: 1.0).
     pub access_boost: f64,
+    /// Scale of the reinforcement from retrieval hits (default: 1.0). A memory hit
+    /// `n` times gains `frequency_boost * ln(1 + n)` importance over its lifetime.
+    /// Set to 0.0 to disable.
+    pub frequency_boost: f64,
+    /// Reinforcement never lifts importance above this (default: 10.0).
+    pub max_importance: f64,
     /// Importance threshold below which a memory may be pruned (default: 2.0).
     pub prune_threshold: f64,
     /// Hard age limit in days — non-exempt memories older than this are pruned
@@ -20,6 +26,8 @@ impl Default for DecayConfig {
         Self {
             half_life_days: 30.0,
             access_boost: 1.0,
+            frequency_boost: 1.0,
+            max_importance: 10.0,
             prune_threshold: 2.0,
             max_age_days: 30.0,
             exempt_types: vec!["preference".to_string(), "fact".to_string()],
@@ -47,6 +55,25 @@ pub fn calculate_decayed_importance(
     }
 }
 
+/// Importance gained from retrieval hits not yet credited by a previous pass.
+///
+/// The curve is logarithmic in the total hit count, so early hits count most and
+/// each pass only adds the difference since the hits it last credited:
+/// `frequency_boost * (ln(1 + access_count) - ln(1 + already_reinforced))`.
+pub fn calculate_access_reinforcement(access_count: i64, already_reinforced: i64, config: &DecayConfig) -> f64 {
+    if access_count <= already_reinforced {
+        return 0.0;
+    }
+    let curve = |n: i64| (1.0 + n.max(0) as f64).ln();
+    config.frequency_boost * (curve(access_count) - curve(already_reinforced))
+}
+
+/// Add reinforcement to a decayed importance, capped at `max_importance`
+/// (a memory already above the cap is not lowered).
+pub fn apply_reinforcement(decayed_importance: f64, reinforcement: f64, config: &DecayConfig) -> f64 {
+    (decayed_importance + reinforcement).min(config.max_importance.max(decayed_importance))
+}
+
 /// Determine whether a memory should be pruned based on its current importance,
 /// age, and type.
 ///
@@ -68,8 +95,8 @@ pub fn should_prune(current_importance: f64, memory_type: &str, days_since_acces
 /// Run a full decay pass over all memories in the database.
 ///
 /// For each memory, calculates the decayed importance based on time since last
-/// access, updates the importance value, and optionally prunes memories that
-/// fall below the threshold.
+/// access, reinforces it with retrieval hits since the previous pass, updates the
+/// importance value, and optionally prunes memories that fall below the threshold.
 ///
 /// Returns `(updated_count, pruned_count)` on success.
 pub fn run_decay_pass(db: &Database, config: &DecayConfig) -> Result<(usize, usize), String> {
@@ -78,18 +105,21 @@ pub fn run_decay_pass(db: &Database, config: &DecayConfig) -> Result<(usize, usi
     // Fetch all memories with their current importance, type, and last access time
     let mut stmt = conn
         .prepare(
-            "SELECT id, importance, memory_type, last_accessed
+            "SELECT id, importance, memory_type, last_accessed,
+                    COALESCE(access_count, 0), COALESCE(access_count_reinforced, 0)
              FROM memories",
         )
         .map_err(|e| format!("Failed to prepare decay query: {}", e))?;
 
-    let memories: Vec<(i64, f64, String, String)> = stmt
+    let memories: Vec<(i64, f64, String, String, i64, i64)> = stmt
         .query_map([], |row| {
             Ok((
                 row.get::<_, i64>(0)?,
                 row.get::<_, f64>(1)?,
                 row.get::<_, String>(2)?,
                 row.get::<_, String>(3)?,
+                row.get::<_, i64>(4)?,
+                row.get::<_, i64>(5)?,
             ))
         })
         .map_err(|e| format!("Failed to query memories for decay: {}", e))?
@@ -100,7 +130,7 @@ pub fn run_decay_pass(db: &Database, config: &DecayConfig) -> Result<(usize, usi
     let mut updated_count: usize = 0;
     let mut pruned_count: usize = 0;
 
-    for (id, original_importance, memory_type, last_accessed) in &memories {
+    for (id, original_importance, memory_type, last_accessed, access_count, access_count_reinforced) in &memories {
         // Parse the last_accessed timestamp
         let last_access_time = chrono::DateTime::parse_from_rfc3339(last_accessed)
             .or_else(|_| {
@@ -119,6 +149,8 @@ pub fn run_decay_pass(db: &Database, config: &DecayConfig) -> Result<(usize, usi
 
         let decayed_importance =
             calculate_decayed_importance(*original_importance, days_since_access, config);
+        let reinforcement = calculate_access_reinforcement(*access_count, *access_count_reinforced, config);
+        let decayed_importance = apply_reinforcement(decayed_importance, reinforcement, config);
 
         if should_prune(decayed_importance, memory_type, days_since_access, config) {
             // Delete the memory and its related data atomically
@@ -167,10 +199,11 @@ pub fn run_decay_pass(db: &Database, config: &DecayConfig) -> Result<(usize, usi
                 decayed_importance
             );
         } else {
-            // Update the importance value (stored as real for smooth decay)
+            // Update the importance value (stored as real for smooth decay) and
+            // mark the current hits as credited
             conn.execute(
-                "UPDATE memories SET importance = ?1 WHERE id = ?2",
-                rusqlite::params![decayed_importance, id],
+                "UPDATE memories SET importance = ?1, access_count_reinforced = ?3 WHERE id = ?2",
+                rusqlite::params![decayed_importance, id, access_count],
             )
             .map_err(|e| format!("Failed to update importance for memory {}: {}", id, e))?;
 
@@ -187,3 +220,59 @@ pub fn run_decay_pass(db: &Database, config: &DecayConfig) -> Result<(usize, usi
 
     Ok((updated_count, pruned_count))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_reinforcement_curve() {
+        let config = DecayConfig::default();
+        assert_eq!(calculate_access_reinforcement(0, 0, &config), 0.0);
+        assert_eq!(calculate_access_reinforcement(5, 5, &config), 0.0);
+
+        // Diminishing returns: the first hit is worth more than the tenth
+        let first = calculate_access_reinforcement(1, 0, &config);
+        let tenth = calculate_access_reinforcement(10, 9, &config);
+        assert!(first > tenth && tenth > 0.0);
+
+        // Crediting in steps adds up to crediting all at once
+        let stepped = calculate_access_reinforcement(4, 0, &config) + calculate_access_reinforcement(10, 4, &config);
+        assert!((stepped - calculate_access_reinforcement(10, 0, &config)).abs() < 1e-9);
+
+        let disabled = DecayConfig { frequency_boost: 0.0, ..DecayConfig::default() };
+        assert_eq!(calculate_access_reinforcement(10, 0, &disabled), 0.0);
+
+        assert_eq!(apply_reinforcement(9.5, 2.0, &config), 10.0);
+        assert_eq!(apply_reinforcement(12.0, 2.0, &config), 12.0);
+    }
+
+    #[test]
+    fn test_decay_pass_reinforces_frequently_hit_memories() {
+        let db = Database::new(":memory:").unwrap();
+        let insert = |content: &str| {
+            db.insert_memory("long_term", content, None, None, 5, None, None, None, None, None, None, None)
+                .unwrap()
+        };
+        let hit = insert("The user deploys contracts on Base");
+        let cold = insert("The user once mentioned the weather");
+        for _ in 0..3 {
+            db.record_memory_hits(&[hit]).unwrap();
+        }
+        assert_eq!(db.get_memory(hit).unwrap().unwrap().access_count, 3);
+
+        let importance = |id: i64| -> f64 {
+            db.conn()
+                .query_row("SELECT importance FROM memories WHERE id = ?1", [id], |row| row.get(0))
+                .unwrap()
+        };
+        let config = DecayConfig::default();
+        run_decay_pass(&db, &config).unwrap();
+        let gap = importance(hit) - importance(cold);
+        assert!((gap - calculate_access_reinforcement(3, 0, &config)).abs() < 1e-6, "gap was {}", gap);
+
+        // Hits already credited are not counted again
+        run_decay_pass(&db, &config).unwrap();
+        assert!((importance(hit) - importance(cold) - gap).abs() < 1e-6);
+    }
+}
@@ -4,6 +4,97 @@
 //! - System prompt memory pre-injection (fallback path)
 //! - Hybrid search engine (`fts_search`)
 //! - User-facing FTS search (`search_memories_fts_user`)
+//!
+//! `all_terms_fts_query()` builds a stricter AND query for user-driven search
+//! (session search), where every typed word should appear.
+//!
+//! `FtsTokenizer` selects how the `memories_fts` index splits text into tokens.
+
+/// Tokenizer for the `memories_fts` index, chosen by the `memory_fts_tokenizer` bot setting.
+///
+/// Tradeoffs:
+/// - `Unicode61` (default): splits on whitespace and punctuation. Compact index and
+///   prefix queries work well, but scripts written without spaces (Chinese, Japanese,
+///   Thai) become one token per run of text, so a word inside a sentence can't be found.
+/// - `Porter`: unicode61 plus Porter stemming at index time, so "running" matches "runs".
+///   Only helps English; no better than unicode61 for CJK.
+/// - `Trigram`: indexes every 3-character sequence, so any substring of 3+ characters
+///   matches in any script. The index is roughly three times larger, there is no
+///   stemming, and query terms shorter than 3 characters (including many 2-character
+///   Chinese words) match nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FtsTokenizer {
+    #[default]
+    Unicode61,
+    Porter,
+    Trigram,
+}
+
+impl FtsTokenizer {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "unicode61" => Some(Self::Unicode61),
+            "porter" => Some(Self::Porter),
+            "trigram" => Some(Self::Trigram),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unicode61 => "unicode61",
+            Self::Porter => "porter",
+            Self::Trigram => "trigram",
+        }
+    }
+
+    /// Value of the FTS5 `tokenize` option, or None for SQLite's default (unicode61)
+    pub fn fts5_option(&self) -> Option<&'static str> {
+        match self {
+            Self::Unicode61 => None,
+            // remove_diacritics 2 also folds diacritics on composed characters
+            Self::Porter => Some("porter unicode61 remove_diacritics 2"),
+            Self::Trigram => Some("trigram"),
+        }
+    }
+
+    /// Tokenizer an FTS5 table was created with, from its `CREATE VIRTUAL TABLE` SQL
+    pub fn from_table_sql(sql: &str) -> Self {
+        let sql = sql.to_lowercase();
+        if !sql.contains("tokenize") {
+            Self::Unicode61
+        } else if sql.contains("trigram") {
+            Self::Trigram
+        } else if sql.contains("porter") {
+            Self::Porter
+        } else {
+            Self::Unicode61
+        }
+    }
+}
+
+/// Scripts written without spaces between words
+fn is_unspaced_script(c: char) -> bool {
+    matches!(c,
+        '\u{0E00}'..='\u{0E7F}'   // Thai
+        | '\u{3040}'..='\u{30FF}' // Hiragana, Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}' // Hangul syllables
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+    )
+}
+
+/// Overlapping 3-character slices of a word from an unspaced script, so a
+/// trigram index can match part of a sentence typed without spaces.
+/// Empty for other words and for words shorter than 3 characters.
+fn unspaced_windows(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    if chars.len() < 3 || !chars.iter().any(|c| is_unspaced_script(*c)) {
+        return Vec::new();
+    }
+    chars.windows(3).take(6).map(|w| w.iter().collect()).collect()
+}
 
 /// Normalize a raw query string into an FTS5 MATCH expression with stemming.
 ///
@@ -13,6 +104,8 @@
 /// 3. Take first 8 significant words
 /// 4. Apply `simple_stem()` to each word
 /// 5. Produce prefix-wildcard OR query: `stem1* OR original1* OR stem2*`
+/// 6. For words in scripts without spaces (CJK, Thai), also add quoted 3-character
+///    slices, which match under the trigram tokenizer
 ///
 /// Returns an empty string if no significant tokens remain.
 pub fn normalize_fts_query(query: &str) -> String {
@@ -25,17 +118,37 @@ pub fn normalize_fts_query(query: &str) -> String {
         .take(8)
         .map(|w| {
             let stemmed = simple_stem(&w);
-            if stemmed != w {
+            let mut terms = if stemmed != w {
                 // Search both the stem (prefix) and the original (prefix)
                 format!("{}* OR {}*", stemmed, w)
             } else {
                 format!("{}*", w)
+            };
+            for window in unspaced_windows(&w) {
+                terms.push_str(&format!(" OR \"{}\"", window));
             }
+            terms
         })
         .collect::<Vec<_>>()
         .join(" OR ")
 }
 
+/// Build an FTS5 MATCH expression requiring every word of `query` (as a prefix).
+///
+/// Each word is quoted so FTS5 operators and punctuation in user input are
+/// treated literally: `api key` -> `"api"* "key"*`. Stop words are kept, since
+/// the user typed them deliberately. Returns an empty string for blank input.
+pub fn all_terms_fts_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|w| !w.is_empty())
+        .take(16)
+        .map(|w| format!("\"{}\"*", w.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Lightweight English stemmer for FTS query improvement.
 /// Strips common suffixes so "hackathons" -> "hackathon", "running" -> "run", etc.
 /// Not a full Porter stemmer — just handles the most common cases that cause
@@ -144,6 +257,24 @@ mod tests {
         assert_eq!(normalize_fts_query("the a an"), "");
     }
 
+    #[test]
+    fn test_all_terms_fts_query() {
+        assert_eq!(all_terms_fts_query("the API key?"), "\"the\"* \"API\"* \"key\"*");
+        // FTS5 syntax in user input is quoted, not interpreted
+        assert_eq!(all_terms_fts_query("foo OR \"bar"), "\"foo\"* \"OR\"* \"bar\"*");
+        assert_eq!(all_terms_fts_query(" ... "), "");
+    }
+
+    #[test]
+    fn test_normalize_fts_query_adds_cjk_windows() {
+        assert_eq!(normalize_fts_query("喜欢绿茶"), "喜欢绿茶* OR \"喜欢绿\" OR \"欢绿茶\"");
+        // Too short for a trigram, and no windows for spaced scripts
+        assert_eq!(normalize_fts_query("绿茶"), "绿茶*");
+        assert_eq!(normalize_fts_query("coffee"), "coffee*");
+        assert_eq!(FtsTokenizer::from_table_sql("CREATE VIRTUAL TABLE x USING fts5(content, tokenize='trigram')"), FtsTokenizer::Trigram);
+        assert_eq!(FtsTokenizer::from_table_sql("CREATE VIRTUAL TABLE x USING fts5(content)"), FtsTokenizer::Unicode61);
+    }
+
     #[test]
     fn test_normalize_fts_query_limits_to_8_words() {
         let long_query = "alpha bravo charlie delta echo foxtrot golf hotel india juliet";
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::Duration;
@@ -8,6 +8,7 @@ use moka::sync::Cache;
 use crate::db::Database;
 use super::embeddings::EmbeddingGenerator;
 use super::vector_search;
+use super::{SAFE_MODE_IDENTITY, SHARED_MEMORY_IDENTITY};
 
 /// Hint returned at write time suggesting possible duplicates or related content.
 #[derive(Debug, Clone, serde::Serialize)]
@@ -21,6 +22,47 @@ pub struct ConsolidationHint {
     pub suggestion: String,
 }
 
+/// Tuning for the graph signal of the hybrid search engine.
+#[derive(Debug, Clone)]
+pub struct HybridSearchConfig {
+    /// Association hops to traverse from the FTS/vector hits (default: 2). 0 disables the graph signal.
+    pub graph_max_hops: usize,
+    /// Associations weaker than this are not followed (default: 0.3).
+    pub graph_min_edge_strength: f64,
+    /// Weight multiplier for each hop beyond the first (default: 0.5).
+    pub graph_hop_decay: f64,
+}
+
+impl Default for HybridSearchConfig {
+    fn default() -> Self {
+        Self {
+            graph_max_hops: 2,
+            graph_min_edge_strength: 0.3,
+            graph_hop_decay: 0.5,
+        }
+    }
+}
+
+impl HybridSearchConfig {
+    /// Defaults overridden by STARK_MEMORY_GRAPH_MAX_HOPS / STARK_MEMORY_GRAPH_MIN_EDGE_STRENGTH
+    pub fn from_env() -> Self {
+        use crate::config::env_vars;
+        let defaults = Self::default();
+        Self {
+            graph_max_hops: std::env::var(env_vars::MEMORY_GRAPH_MAX_HOPS)
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(defaults.graph_max_hops),
+            graph_min_edge_strength: std::env::var(env_vars::MEMORY_GRAPH_MIN_EDGE_STRENGTH)
+                .ok()
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .map(|v| v.clamp(0.0, 1.0))
+                .unwrap_or(defaults.graph_min_edge_strength),
+            ..defaults
+        }
+    }
+}
+
 /// Result from the hybrid search engine, combining FTS, vector, and graph signals.
 #[derive(Debug, Clone)]
 pub struct HybridSearchResult {
@@ -49,6 +91,7 @@ pub struct HybridSearchEngine {
     /// Cache of final hybrid search results keyed by query string.
     /// Short TTL — just avoids re-running the same search within a burst.
     search_cache: Cache<String, Arc<Vec<HybridSearchResult>>>,
+    config: HybridSearchConfig,
 }
 
 impl HybridSearchEngine {
@@ -72,9 +115,16 @@ impl HybridSearchEngine {
                 .max_capacity(64)
                 .time_to_live(Duration::from_secs(30))
                 .build(),
+            config: HybridSearchConfig::default(),
         }
     }
 
+    /// Replace the default graph-signal tuning.
+    pub fn with_config(mut self, config: HybridSearchConfig) -> Self {
+        self.config = config;
+        self
+    }
+
     /// Call this after writing/updating/deleting memories or embeddings
     /// to invalidate the in-memory caches.
     pub fn invalidate_caches(&self) {
@@ -90,32 +140,36 @@ impl HybridSearchEngine {
 
     /// Run a full hybrid search combining FTS5, vector similarity, and graph associations.
     /// If `agent_subtype` is provided, same-subtype memories get a 1.25x score boost.
+    /// If `identity_id` is provided, only that identity's memories and explicitly shared
+    /// ones are returned (see `memory_visible_to`); None searches all memories.
     pub async fn search(
         &self,
         query: &str,
         limit: usize,
         agent_subtype: Option<&str>,
+        identity_id: Option<&str>,
     ) -> Result<Vec<HybridSearchResult>, String> {
         // Check search result cache first
-        let cache_key = format!("{}:{}:{}", query, limit, agent_subtype.unwrap_or(""));
+        let cache_key = format!("{}:{}:{}:{}", query, limit, agent_subtype.unwrap_or(""), identity_id.unwrap_or(""));
         if let Some(cached) = self.search_cache.get(&cache_key) {
             log::debug!("[HYBRID_SEARCH] Cache hit for query: {:?}", query);
             return Ok((*cached).clone());
         }
+        let visible = self.visible_ids(identity_id)?;
 
         // 1. FTS5 search
-        let fts_results = self.fts_search(query)?;
+        let fts_results = self.fts_search(query, identity_id)?;
         log::debug!("[HYBRID_SEARCH] FTS returned {} results for query: {:?}", fts_results.len(), query);
 
         // 2. Vector search
-        let vector_results = self.vector_search(query).await;
+        let vector_results = self.vector_search(query, visible.as_ref()).await;
         log::debug!("[HYBRID_SEARCH] Vector returned {} results", vector_results.len());
 
         // 3. Graph search: expand from FTS/vector hits to find their neighbors
         let seed_ids: Vec<i64> = fts_results.iter().map(|(id, _)| *id)
             .chain(vector_results.iter().map(|(id, _)| *id))
             .collect();
-        let graph_results = self.graph_expand(&seed_ids)?;
+        let graph_results = self.graph_expand(&seed_ids, visible.as_ref())?;
         log::debug!("[HYBRID_SEARCH] Graph expanded to {} neighbors from {} seeds", graph_results.len(), seed_ids.len());
 
         // 4. RRF merge
@@ -141,27 +195,30 @@ impl HybridSearchEngine {
     /// Fast hybrid search for the chat hot-path.
     /// Tries FTS + vector + graph but caps the embedding server call at 1 second.
     /// If the embedding server doesn't respond in time, proceeds with FTS + graph only.
+    /// `identity_id` scopes results the same way as `search`.
     pub async fn search_fast(
         &self,
         query: &str,
         limit: usize,
         agent_subtype: Option<&str>,
+        identity_id: Option<&str>,
     ) -> Result<Vec<HybridSearchResult>, String> {
         // Check search result cache first
-        let cache_key = format!("fast:{}:{}:{}", query, limit, agent_subtype.unwrap_or(""));
+        let cache_key = format!("fast:{}:{}:{}:{}", query, limit, agent_subtype.unwrap_or(""), identity_id.unwrap_or(""));
         if let Some(cached) = self.search_cache.get(&cache_key) {
             log::debug!("[HYBRID_SEARCH_FAST] Cache hit for query: {:?}", query);
             return Ok((*cached).clone());
         }
+        let visible = self.visible_ids(identity_id)?;
 
         // 1. FTS5 search (local, fast)
-        let fts_results = self.fts_search(query)?;
+        let fts_results = self.fts_search(query, identity_id)?;
         log::debug!("[HYBRID_SEARCH_FAST] FTS returned {} results for query: {:?}", fts_results.len(), query);
 
         // 2. Vector search with 1s timeout on the embedding server call
         let vector_results = match tokio::time::timeout(
             Duration::from_secs(1),
-            self.vector_search(query),
+            self.vector_search(query, visible.as_ref()),
         ).await {
             Ok(results) => {
                 log::debug!("[HYBRID_SEARCH_FAST] Vector returned {} results", results.len());
@@ -177,7 +234,7 @@ impl HybridSearchEngine {
         let seed_ids: Vec<i64> = fts_results.iter().map(|(id, _)| *id)
             .chain(vector_results.iter().map(|(id, _)| *id))
             .collect();
-        let graph_results = self.graph_expand(&seed_ids)?;
+        let graph_results = self.graph_expand(&seed_ids, visible.as_ref())?;
         log::debug!("[HYBRID_SEARCH_FAST] Graph expanded to {} neighbors from {} seeds", graph_results.len(), seed_ids.len());
 
         // 4. RRF merge
@@ -202,8 +259,9 @@ impl HybridSearchEngine {
         query: &str,
         limit: usize,
         agent_subtype: Option<&str>,
+        identity_id: Option<&str>,
     ) -> Result<Vec<HybridSearchResult>, String> {
-        let fts_results = self.fts_search(query)?;
+        let fts_results = self.fts_search(query, identity_id)?;
 
         let empty_vec: Vec<(i64, f32)> = Vec::new();
         let empty_graph: Vec<(i64, i32)> = Vec::new();
@@ -221,6 +279,17 @@ impl HybridSearchEngine {
         Ok(merged)
     }
 
+    /// Memory IDs an identity may see, or None when the search is unscoped
+    fn visible_ids(&self, identity_id: Option<&str>) -> Result<Option<HashSet<i64>>, String> {
+        identity_id
+            .map(|id| {
+                self.db
+                    .list_memory_ids_visible_to(id)
+                    .map_err(|e| format!("Failed to load memories visible to identity {}: {}", id, e))
+            })
+            .transpose()
+    }
+
     /// Normalize a query string for FTS5 MATCH using shared stemming + stop-word
     /// filtering. Produces a prefix-wildcard OR query for broad matching.
     fn sanitize_fts5_query(query: &str) -> String {
@@ -228,8 +297,9 @@ impl HybridSearchEngine {
     }
 
     /// Perform FTS5 full-text search against the memories_fts table.
+    /// With an `identity_id`, only memories visible to it match.
     /// Returns (memory_id, rank) pairs.
-    fn fts_search(&self, query: &str) -> Result<Vec<(i64, f64)>, String> {
+    fn fts_search(&self, query: &str, identity_id: Option<&str>) -> Result<Vec<(i64, f64)>, String> {
         let sanitized = Self::sanitize_fts5_query(query);
         if sanitized.is_empty() {
             return Ok(Vec::new());
@@ -243,13 +313,15 @@ impl HybridSearchEngine {
                  FROM memories_fts fts
                  JOIN memories m ON m.id = fts.rowid
                  WHERE memories_fts MATCH ?1
+                   AND (?2 IS NULL OR m.identity_id IN (?2, ?3)
+                        OR (m.identity_id IS NULL AND ?2 <> ?4))
                  ORDER BY fts.rank
                  LIMIT 100",
             )
             .map_err(|e| format!("Failed to prepare FTS query: {}", e))?;
 
         let results = stmt
-            .query_map(rusqlite::params![sanitized], |row| {
+            .query_map(rusqlite::params![sanitized, identity_id, SHARED_MEMORY_IDENTITY, SAFE_MODE_IDENTITY], |row| {
                 Ok((row.get::<_, i64>(0)?, row.get::<_, f64>(1)?))
             })
             .map_err(|e| format!("Failed to execute FTS query: {}", e))?
@@ -260,9 +332,9 @@ impl HybridSearchEngine {
     }
 
     /// Perform vector similarity search by generating an embedding for the query
-    /// and comparing against all stored memory embeddings.
-    /// Returns (memory_id, similarity) pairs.
-    async fn vector_search(&self, query: &str) -> Vec<(i64, f32)> {
+    /// and comparing against all stored memory embeddings (only the `visible` ones
+    /// for a scoped search). Returns (memory_id, similarity) pairs.
+    async fn vector_search(&self, query: &str, visible: Option<&HashSet<i64>>) -> Vec<(i64, f32)> {
         let query_embedding = match self.embedding_generator.generate(query).await {
             Ok(emb) => emb,
             Err(e) => {
@@ -273,7 +345,10 @@ impl HybridSearchEngine {
 
         // Load all stored embeddings
         let candidates = match self.load_embeddings() {
-            Ok(c) => c,
+            Ok(c) => match visible {
+                Some(visible) => c.into_iter().filter(|(id, _)| visible.contains(id)).collect(),
+                None => c,
+            },
             Err(e) => {
                 log::warn!("Failed to load memory embeddings: {}", e);
                 return Vec::new();
@@ -306,70 +381,103 @@ impl HybridSearchEngine {
         Ok(embeddings)
     }
 
-    /// Expand from seed memory IDs to their graph neighbors.
-    /// Returns neighbor (memory_id, total_strength) pairs ranked by connection
-    /// strength to the seed set. This makes the graph signal query-aware rather
-    /// than just returning the globally most-connected memories.
-    fn graph_expand(&self, seed_ids: &[i64]) -> Result<Vec<(i64, i32)>, String> {
-        if seed_ids.is_empty() {
+    /// Expand from seed memory IDs through the association graph, up to
+    /// `graph_max_hops` hops, following only edges of at least `graph_min_edge_strength`.
+    ///
+    /// A memory reached from a seed gets the strength of the connecting edge; one
+    /// reached further out gets the product of the strengths along the path, times
+    /// `graph_hop_decay` for each extra hop. Weights from different paths add up.
+    /// Memories outside `visible` (for a scoped search) are neither returned nor traversed.
+    /// Returns (memory_id, weight x 100) pairs ranked by weight, seeds excluded.
+    fn graph_expand(&self, seed_ids: &[i64], visible: Option<&HashSet<i64>>) -> Result<Vec<(i64, i32)>, String> {
+        if seed_ids.is_empty() || self.config.graph_max_hops == 0 {
             return Ok(Vec::new());
         }
 
         // Deduplicate and cap seed set to avoid huge queries
-        let mut unique_seeds: Vec<i64> = seed_ids.to_vec();
-        unique_seeds.sort_unstable();
-        unique_seeds.dedup();
-        unique_seeds.truncate(50);
+        let mut frontier: HashMap<i64, f64> = HashMap::new();
+        for id in seed_ids {
+            if frontier.len() >= 50 {
+                break;
+            }
+            frontier.insert(*id, 1.0);
+        }
+        let mut visited: HashSet<i64> = frontier.keys().copied().collect();
+        let mut weights: HashMap<i64, f64> = HashMap::new();
+
+        for hop in 1..=self.config.graph_max_hops {
+            let hop_factor = if hop == 1 { 1.0 } else { self.config.graph_hop_decay };
+            let mut next: HashMap<i64, f64> = HashMap::new();
+            for (source, target, strength) in self.frontier_edges(&frontier)? {
+                for (from, to) in [(source, target), (target, source)] {
+                    if let Some(from_weight) = frontier.get(&from)
+                        && !visited.contains(&to)
+                        && visible.is_none_or(|v| v.contains(&to))
+                    {
+                        *next.entry(to).or_insert(0.0) += from_weight * strength * hop_factor;
+                    }
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
 
-        let conn = self.db.conn();
-        let n = unique_seeds.len();
+            for (id, weight) in &next {
+                *weights.entry(*id).or_insert(0.0) += weight;
+                visited.insert(*id);
+            }
+            // Only the strongest memories of this hop are expanded further
+            let mut ranked: Vec<(i64, f64)> = next.into_iter().collect();
+            ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            ranked.truncate(50);
+            frontier = ranked.into_iter().collect();
+        }
+
+        let mut results: Vec<(i64, f64)> = weights.into_iter().collect();
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal).then(a.0.cmp(&b.0)));
+        results.truncate(100);
+        Ok(results
+            .into_iter()
+            .map(|(id, weight)| (id, (weight * 100.0).round() as i32))
+            .collect())
+    }
 
-        // Build 4 sets of numbered placeholders for the IN clauses
-        let make_placeholders = |offset: usize| -> String {
+    /// Associations touching any frontier memory with at least the configured strength.
+    /// Returns (source_id, target_id, strength) triples.
+    fn frontier_edges(&self, frontier: &HashMap<i64, f64>) -> Result<Vec<(i64, i64, f64)>, String> {
+        let ids: Vec<i64> = frontier.keys().copied().collect();
+        let n = ids.len();
+        let placeholders = |offset: usize| -> String {
             (1..=n).map(|i| format!("?{}", offset + i)).collect::<Vec<_>>().join(", ")
         };
-        let p1 = make_placeholders(0);
-        let p2 = make_placeholders(n);
-        let p3 = make_placeholders(2 * n);
-        let p4 = make_placeholders(3 * n);
-
         let query = format!(
-            "SELECT neighbor_id, SUM(strength_int) as total_strength FROM (
-                 SELECT target_memory_id AS neighbor_id,
-                        CAST(strength * 100 AS INTEGER) AS strength_int
-                 FROM memory_associations
-                 WHERE source_memory_id IN ({p1})
-                   AND target_memory_id NOT IN ({p2})
-                 UNION ALL
-                 SELECT source_memory_id AS neighbor_id,
-                        CAST(strength * 100 AS INTEGER) AS strength_int
-                 FROM memory_associations
-                 WHERE target_memory_id IN ({p3})
-                   AND source_memory_id NOT IN ({p4})
-             )
-             GROUP BY neighbor_id
-             ORDER BY total_strength DESC
-             LIMIT 100"
+            "SELECT source_memory_id, target_memory_id, strength
+             FROM memory_associations
+             WHERE strength >= ?{}
+               AND (source_memory_id IN ({}) OR target_memory_id IN ({}))",
+            2 * n + 1,
+            placeholders(0),
+            placeholders(n),
         );
 
-        // Bind seed_ids four times (once per IN clause)
-        let all_params: Vec<i64> = std::iter::repeat(unique_seeds.iter().copied())
-            .take(4)
-            .flatten()
+        let mut params: Vec<rusqlite::types::Value> = ids
+            .iter()
+            .chain(ids.iter())
+            .map(|id| rusqlite::types::Value::Integer(*id))
             .collect();
+        params.push(rusqlite::types::Value::Real(self.config.graph_min_edge_strength));
 
+        let conn = self.db.conn();
         let mut stmt = conn.prepare(&query)
             .map_err(|e| format!("Failed to prepare graph expand query: {}", e))?;
-
-        let results = stmt
-            .query_map(rusqlite::params_from_iter(all_params.iter()), |row| {
-                Ok((row.get::<_, i64>(0)?, row.get::<_, i32>(1)?))
+        let edges = stmt
+            .query_map(rusqlite::params_from_iter(params.iter()), |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, f64>(2)?))
             })
             .map_err(|e| format!("Failed to execute graph expand query: {}", e))?
             .filter_map(|r| r.ok())
             .collect();
-
-        Ok(results)
+        Ok(edges)
     }
 
     /// Find consolidation hints for new content before it is stored.
@@ -617,3 +725,57 @@ impl HybridSearchEngine {
         results
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::embeddings::NullEmbeddingGenerator;
+
+    /// a -0.9- b -0.8- c -0.9- d, plus a weak a -0.1- e
+    fn graph_fixture() -> (HybridSearchEngine, [i64; 5]) {
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        let ids = ["a", "b", "c", "d", "e"].map(|name| {
+            db.insert_memory("long_term", &format!("memory {}", name), None, None, 5, None, None, None, None, None, None, None)
+                .unwrap()
+        });
+        let [a, b, c, d, e] = ids;
+        for (source, target, strength) in [(a, b, 0.9), (c, b, 0.8), (c, d, 0.9), (a, e, 0.1)] {
+            db.create_memory_association(source, target, "related", strength, None).unwrap();
+        }
+        (HybridSearchEngine::new(db, Arc::new(NullEmbeddingGenerator)), ids)
+    }
+
+    #[test]
+    fn test_graph_expand_follows_hops_by_strength() {
+        let (engine, [a, b, c, d, e]) = graph_fixture();
+
+        // Two hops (default): b directly, c through b (either edge direction), d is three hops out
+        let results = engine.graph_expand(&[a], None).unwrap();
+        assert_eq!(results, vec![(b, 90), (c, 36)]);
+        assert!(!results.iter().any(|(id, _)| *id == d || *id == e));
+
+        let engine = engine.with_config(HybridSearchConfig { graph_max_hops: 3, ..HybridSearchConfig::default() });
+        let results = engine.graph_expand(&[a], None).unwrap();
+        assert_eq!(results.last(), Some(&(d, 16)));
+
+        // Lowering the threshold lets the weak edge through; 0 hops disables expansion
+        let engine = engine.with_config(HybridSearchConfig {
+            graph_max_hops: 1,
+            graph_min_edge_strength: 0.05,
+            ..HybridSearchConfig::default()
+        });
+        assert_eq!(engine.graph_expand(&[a], None).unwrap(), vec![(b, 90), (e, 10)]);
+        let engine = engine.with_config(HybridSearchConfig { graph_max_hops: 0, ..HybridSearchConfig::default() });
+        assert!(engine.graph_expand(&[a], None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rrf_merge_includes_graph_neighbors() {
+        let (engine, [a, b, c, _, _]) = graph_fixture();
+        let graph = engine.graph_expand(&[a], None).unwrap();
+        let merged = engine.rrf_merge(&[(a, -3.0)], &[(a, 0.9)], &graph, 10);
+        let ids: Vec<i64> = merged.iter().map(|r| r.memory_id).collect();
+        assert_eq!(ids, vec![a, b, c]);
+        assert_eq!(merged[1].association_count, Some(90));
+    }
+}
@@ -10,3 +10,29 @@ pub mod vector_search;
 // Re-exports for convenience
 pub use embeddings::EmbeddingGenerator;
 pub use hybrid_search::{ConsolidationHint, HybridSearchEngine, HybridSearchResult};
+
+/// Identity that memories from safe-mode sessions are stored under
+pub const SAFE_MODE_IDENTITY: &str = "safemode";
+
+/// Memories stored under this identity are explicitly shared with every identity
+pub const SHARED_MEMORY_IDENTITY: &str = "shared";
+
+/// Whether a memory owned by `owner` may be retrieved for `requester`.
+/// No requester sees every memory; a scoped requester sees its own memories plus
+/// shared ones. Memories without an owner (written before identity scoping, or by
+/// single-user channels) count as shared, except for the safe-mode identity.
+pub fn memory_visible_to(owner: Option<&str>, requester: Option<&str>) -> bool {
+    match requester {
+        None => true,
+        Some(requester) => match owner {
+            None => requester != SAFE_MODE_IDENTITY,
+            Some(owner) => owner == requester || owner == SHARED_MEMORY_IDENTITY,
+        },
+    }
+}
+
+/// Whether a memory owned by `owner` may be deleted on behalf of `requester`.
+/// Stricter than visibility: a scoped requester can't delete shared memories.
+pub fn memory_deletable_by(owner: Option<&str>, requester: Option<&str>) -> bool {
+    requester.is_none() || owner == requester
+}
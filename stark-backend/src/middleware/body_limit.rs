@@ -0,0 +1,67 @@
+// Request body size limits.
+// The app applies STARK_MAX_BODY_BYTES to every JSON and raw-body extractor;
+// routes that need a tighter (chat) or looser (backup restore) cap override it
+// with their own app_data. Oversized bodies get a 413 before being buffered.
+
+use actix_web::error::{InternalError, JsonPayloadError};
+use actix_web::{web, HttpResponse};
+
+/// JSON extractor config capped at `limit` bytes, answering overflows with a JSON 413
+pub fn json_config(limit: usize) -> web::JsonConfig {
+    web::JsonConfig::default()
+        .limit(limit)
+        .error_handler(move |err, _req| match err {
+            JsonPayloadError::OverflowKnownLength { .. } | JsonPayloadError::Overflow { .. } => {
+                InternalError::from_response(err, too_large(limit)).into()
+            }
+            err => err.into(),
+        })
+}
+
+/// Raw body (`web::Bytes` / `String`) extractor config capped at `limit` bytes
+pub fn payload_config(limit: usize) -> web::PayloadConfig {
+    web::PayloadConfig::new(limit)
+}
+
+/// 413 response body shared by the extractors and streaming uploads
+pub fn too_large(limit: usize) -> HttpResponse {
+    HttpResponse::PayloadTooLarge().json(serde_json::json!({
+        "success": false,
+        "error": format!("Request body exceeds the {} byte limit", limit)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test, App};
+
+    async fn echo(body: web::Json<serde_json::Value>) -> HttpResponse {
+        HttpResponse::Ok().json(body.into_inner())
+    }
+
+    #[actix_web::test]
+    async fn test_route_limit_overrides_app_limit() {
+        let app = test::init_service(
+            App::new()
+                .app_data(json_config(1024))
+                .route("/default", web::post().to(echo))
+                .service(
+                    web::resource("/tight")
+                        .app_data(json_config(16))
+                        .route(web::post().to(echo)),
+                ),
+        )
+        .await;
+        let body = serde_json::json!({ "message": "more than sixteen bytes" });
+
+        let req = test::TestRequest::post().uri("/default").set_json(&body).to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+        let req = test::TestRequest::post().uri("/tight").set_json(&body).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 413);
+        let json: serde_json::Value = test::read_body_json(resp).await;
+        assert!(json["error"].as_str().unwrap().contains("16 byte limit"));
+    }
+}
@@ -0,0 +1,104 @@
+// CORS policy for the HTTP server.
+// Cross-origin requests are only allowed from the origins listed in
+// STARK_CORS_ALLOWED_ORIGINS; the bundled frontend is served from the same
+// origin and always works. Allowing any origin is an explicit opt-in ("*").
+
+use actix_cors::Cors;
+use actix_web::dev::RequestHead;
+use actix_web::http::header::{self, HeaderValue};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorsPolicy {
+    /// Any origin may call the API
+    Permissive,
+    /// Same-origin requests plus these origins (e.g. `https://app.example.com`)
+    AllowList(Vec<String>),
+}
+
+impl CorsPolicy {
+    /// Parse a comma-separated origin list; `*` anywhere in it means permissive
+    pub fn parse(value: &str) -> Self {
+        let origins: Vec<String> = value
+            .split(',')
+            .map(|o| o.trim().trim_end_matches('/').to_ascii_lowercase())
+            .filter(|o| !o.is_empty())
+            .collect();
+        if origins.iter().any(|o| o == "*") {
+            CorsPolicy::Permissive
+        } else {
+            CorsPolicy::AllowList(origins)
+        }
+    }
+
+    /// Whether a request from `origin` may be answered
+    pub fn allows(&self, origin: &HeaderValue, req: &RequestHead) -> bool {
+        let CorsPolicy::AllowList(origins) = self else {
+            return true;
+        };
+        let Ok(origin) = origin.to_str() else {
+            return false;
+        };
+        let origin = origin.to_ascii_lowercase();
+        origins.contains(&origin) || is_same_origin(&origin, req)
+    }
+}
+
+/// Whether `origin` names the host the request was sent to
+fn is_same_origin(origin: &str, req: &RequestHead) -> bool {
+    let Some(host) = req.headers().get(header::HOST).and_then(|h| h.to_str().ok()) else {
+        return false;
+    };
+    origin
+        .split_once("://")
+        .is_some_and(|(_, authority)| authority.eq_ignore_ascii_case(host))
+}
+
+/// Build the actix CORS middleware for a policy
+pub fn build(policy: &CorsPolicy) -> Cors {
+    let cors = match policy {
+        CorsPolicy::Permissive => Cors::default().allow_any_origin(),
+        CorsPolicy::AllowList(_) => {
+            let policy = policy.clone();
+            Cors::default().allowed_origin_fn(move |origin, req| policy.allows(origin, req))
+        }
+    };
+    cors.allow_any_method().allow_any_header().max_age(3600)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    fn head(host: &str) -> RequestHead {
+        TestRequest::default()
+            .insert_header((header::HOST, host))
+            .to_srv_request()
+            .head()
+            .clone()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(CorsPolicy::parse(""), CorsPolicy::AllowList(vec![]));
+        assert_eq!(CorsPolicy::parse("https://a.com, *"), CorsPolicy::Permissive);
+        assert_eq!(
+            CorsPolicy::parse(" https://A.com/ ,http://localhost:5173"),
+            CorsPolicy::AllowList(vec!["https://a.com".into(), "http://localhost:5173".into()])
+        );
+    }
+
+    #[test]
+    fn test_allow_list_accepts_listed_and_same_origin_only() {
+        let policy = CorsPolicy::parse("https://app.example.com");
+        let req = head("bot.example.com:8082");
+        let allows = |origin: &'static str| policy.allows(&HeaderValue::from_static(origin), &req);
+
+        assert!(allows("https://app.example.com"));
+        assert!(allows("http://bot.example.com:8082"));
+        assert!(!allows("https://evil.com"));
+        assert!(!allows("https://app.example.com.evil.com"));
+
+        assert!(CorsPolicy::Permissive.allows(&HeaderValue::from_static("https://evil.com"), &req));
+    }
+}
@@ -1 +1,3 @@
+pub mod body_limit;
+pub mod cors;
 pub mod session_auth;
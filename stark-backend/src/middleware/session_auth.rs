@@ -7,6 +7,7 @@ use actix_web::{HttpRequest, HttpResponse};
 use std::sync::Arc;
 
 use crate::db::Database;
+use crate::models::{SessionCheck, SESSION_EXPIRED_CODE};
 
 pub fn extract_token(req: &HttpRequest) -> Option<String> {
     req.headers()
@@ -22,9 +23,13 @@ pub async fn validate_request(db: &Arc<Database>, req: &HttpRequest) -> Result<(
         }))
     })?;
 
-    match db.validate_session(&token) {
-        Ok(Some(_)) => Ok(()),
-        Ok(None) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+    match db.check_session(&token) {
+        Ok(SessionCheck::Valid(_)) => Ok(()),
+        Ok(SessionCheck::Expired) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Session expired",
+            "code": SESSION_EXPIRED_CODE
+        }))),
+        Ok(SessionCheck::Invalid) => Err(HttpResponse::Unauthorized().json(serde_json::json!({
             "error": "Invalid or expired session"
         }))),
         Err(e) => {
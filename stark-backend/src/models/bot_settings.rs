@@ -14,6 +14,27 @@ pub const DEFAULT_WHISPER_SERVER_URL: &str = "https://whisper.defirelay.com";
 /// Default embeddings server URL
 pub const DEFAULT_EMBEDDINGS_SERVER_URL: &str = "https://embeddings.defirelay.com";
 
+/// Default gas price strategy for queued transactions
+pub const DEFAULT_GAS_STRATEGY: &str = "standard";
+
+/// Default prefix for chat commands handled by the dispatcher (/new, /reset, /think, ...)
+pub const DEFAULT_COMMAND_PREFIX: &str = "/";
+
+/// Default number of messages from the previous gateway session carried into a fresh one
+pub const DEFAULT_GATEWAY_PREVIOUS_MESSAGES: i32 = 6;
+
+/// Upper bound for `gateway_previous_messages` (each message adds up to 500 chars to the prompt)
+pub const MAX_GATEWAY_PREVIOUS_MESSAGES: i32 = 50;
+
+/// Default seconds a queued transaction waits for the user's confirmation before it is auto-rejected
+pub const DEFAULT_TX_CONFIRMATION_TIMEOUT_SECS: i32 = 300;
+
+/// Upper bound for `tx_confirmation_timeout_secs` (24 hours)
+pub const MAX_TX_CONFIRMATION_TIMEOUT_SECS: i32 = 86_400;
+
+/// Default tokenizer for the memory full-text index (see `memory::fts_utils::FtsTokenizer`)
+pub const DEFAULT_MEMORY_FTS_TOKENIZER: &str = "unicode61";
+
 /// Bot settings stored in database
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BotSettings {
@@ -65,6 +86,30 @@ pub struct BotSettings {
     /// Emergency compaction threshold
     #[serde(default = "default_emergency_threshold")]
     pub compaction_emergency_threshold: f64,
+    /// Gas price strategy for queued transactions: "slow", "standard" or "fast"
+    #[serde(default = "default_gas_strategy")]
+    pub gas_strategy: String,
+    /// Absolute max fee per gas ceiling in gwei (None = no ceiling)
+    #[serde(default)]
+    pub gas_max_fee_gwei: Option<f64>,
+    /// Prefix for chat commands (e.g. "/" or "!")
+    #[serde(default = "default_command_prefix")]
+    pub command_prefix: String,
+    /// Extra command aliases: alias -> built-in command (e.g. "clear" -> "reset")
+    #[serde(default)]
+    pub command_aliases: Option<HashMap<String, String>>,
+    /// Messages from the previous gateway session included as context in a fresh one (0 = none)
+    #[serde(default = "default_gateway_previous_messages")]
+    pub gateway_previous_messages: i32,
+    /// Tokenizer for memory full-text search: "unicode61", "porter" or "trigram"
+    #[serde(default = "default_memory_fts_tokenizer")]
+    pub memory_fts_tokenizer: String,
+    /// Kill switch: when true, transaction signing and broadcasts are refused instance-wide
+    #[serde(default)]
+    pub signing_frozen: bool,
+    /// Seconds a queued transaction waits for the user's confirmation before it is auto-rejected (0 = no limit)
+    #[serde(default = "default_tx_confirmation_timeout_secs")]
+    pub tx_confirmation_timeout_secs: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -95,6 +140,14 @@ impl Default for BotSettings {
             compaction_background_threshold: 0.80,
             compaction_aggressive_threshold: 0.85,
             compaction_emergency_threshold: 0.95,
+            gas_strategy: DEFAULT_GAS_STRATEGY.to_string(),
+            gas_max_fee_gwei: None,
+            command_prefix: DEFAULT_COMMAND_PREFIX.to_string(),
+            command_aliases: None,
+            gateway_previous_messages: DEFAULT_GATEWAY_PREVIOUS_MESSAGES,
+            memory_fts_tokenizer: DEFAULT_MEMORY_FTS_TOKENIZER.to_string(),
+            signing_frozen: false,
+            tx_confirmation_timeout_secs: DEFAULT_TX_CONFIRMATION_TIMEOUT_SECS,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -106,6 +159,35 @@ fn default_coalescing_max_wait() -> u64 { 5000 }
 fn default_background_threshold() -> f64 { 0.80 }
 fn default_aggressive_threshold() -> f64 { 0.85 }
 fn default_emergency_threshold() -> f64 { 0.95 }
+fn default_gas_strategy() -> String { DEFAULT_GAS_STRATEGY.to_string() }
+fn default_command_prefix() -> String { DEFAULT_COMMAND_PREFIX.to_string() }
+fn default_gateway_previous_messages() -> i32 { DEFAULT_GATEWAY_PREVIOUS_MESSAGES }
+fn default_memory_fts_tokenizer() -> String { DEFAULT_MEMORY_FTS_TOKENIZER.to_string() }
+fn default_tx_confirmation_timeout_secs() -> i32 { DEFAULT_TX_CONFIRMATION_TIMEOUT_SECS }
+
+/// Check a `gateway_previous_messages` value against the allowed 0..=MAX range
+pub fn validate_gateway_previous_messages(count: i32) -> Result<(), String> {
+    if (0..=MAX_GATEWAY_PREVIOUS_MESSAGES).contains(&count) {
+        Ok(())
+    } else {
+        Err(format!(
+            "gateway_previous_messages must be between 0 and {}, got {}",
+            MAX_GATEWAY_PREVIOUS_MESSAGES, count
+        ))
+    }
+}
+
+/// Check a `tx_confirmation_timeout_secs` value against the allowed 0..=MAX range
+pub fn validate_tx_confirmation_timeout_secs(secs: i32) -> Result<(), String> {
+    if (0..=MAX_TX_CONFIRMATION_TIMEOUT_SECS).contains(&secs) {
+        Ok(())
+    } else {
+        Err(format!(
+            "tx_confirmation_timeout_secs must be between 0 and {}, got {}",
+            MAX_TX_CONFIRMATION_TIMEOUT_SECS, secs
+        ))
+    }
+}
 
 /// Request type for updating bot settings
 #[derive(Debug, Clone, Deserialize)]
@@ -137,4 +219,41 @@ pub struct UpdateBotSettingsRequest {
     pub compaction_background_threshold: Option<f64>,
     pub compaction_aggressive_threshold: Option<f64>,
     pub compaction_emergency_threshold: Option<f64>,
+    /// Gas price strategy: "slow", "standard" or "fast"
+    pub gas_strategy: Option<String>,
+    /// Max fee per gas ceiling in gwei (0 or negative = remove ceiling)
+    pub gas_max_fee_gwei: Option<f64>,
+    /// Chat command prefix (empty = reset to "/")
+    pub command_prefix: Option<String>,
+    /// Command aliases: alias -> built-in command (empty map clears all aliases)
+    pub command_aliases: Option<HashMap<String, String>>,
+    /// Previous-session messages carried into fresh gateway sessions (0-50)
+    pub gateway_previous_messages: Option<i32>,
+    /// Memory FTS tokenizer: "unicode61", "porter" or "trigram" (changing it rebuilds the index)
+    pub memory_fts_tokenizer: Option<String>,
+    /// Seconds to wait for a queued transaction's confirmation before auto-rejecting it (0 = no limit)
+    pub tx_confirmation_timeout_secs: Option<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_gateway_previous_messages_bounds() {
+        assert!(validate_gateway_previous_messages(0).is_ok());
+        assert!(validate_gateway_previous_messages(DEFAULT_GATEWAY_PREVIOUS_MESSAGES).is_ok());
+        assert!(validate_gateway_previous_messages(MAX_GATEWAY_PREVIOUS_MESSAGES).is_ok());
+        assert!(validate_gateway_previous_messages(-1).is_err());
+        assert!(validate_gateway_previous_messages(MAX_GATEWAY_PREVIOUS_MESSAGES + 1).is_err());
+    }
+
+    #[test]
+    fn test_validate_tx_confirmation_timeout_bounds() {
+        assert!(validate_tx_confirmation_timeout_secs(0).is_ok());
+        assert!(validate_tx_confirmation_timeout_secs(DEFAULT_TX_CONFIRMATION_TIMEOUT_SECS).is_ok());
+        assert!(validate_tx_confirmation_timeout_secs(MAX_TX_CONFIRMATION_TIMEOUT_SECS).is_ok());
+        assert!(validate_tx_confirmation_timeout_secs(-1).is_err());
+        assert!(validate_tx_confirmation_timeout_secs(MAX_TX_CONFIRMATION_TIMEOUT_SECS + 1).is_err());
+    }
 }
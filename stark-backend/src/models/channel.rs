@@ -9,6 +9,7 @@ pub enum ChannelType {
     Discord,
     Twitter,
     ExternalChannel,
+    Matrix,
 }
 
 impl ChannelType {
@@ -19,6 +20,7 @@ impl ChannelType {
             ChannelType::Discord => "discord",
             ChannelType::Twitter => "twitter",
             ChannelType::ExternalChannel => "external_channel",
+            ChannelType::Matrix => "matrix",
         }
     }
 
@@ -29,6 +31,7 @@ impl ChannelType {
             "discord" => Some(ChannelType::Discord),
             "twitter" => Some(ChannelType::Twitter),
             "external_channel" => Some(ChannelType::ExternalChannel),
+            "matrix" => Some(ChannelType::Matrix),
             _ => None,
         }
     }
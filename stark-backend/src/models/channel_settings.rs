@@ -53,11 +53,17 @@ impl ToolOutputVerbosity {
 pub enum ChannelSettingKey {
     /// Common: Auto-start this channel when the server boots (after restore from backup)
     AutoStartOnBoot,
+    /// Common: Log this channel's messages without content and keep content out of history exports
+    PrivateMessageHistory,
+    /// Common: Show the planned task list and wait for approval before executing it
+    PlanApproval,
     /// Discord: Bot authentication token
     DiscordBotToken,
     /// Discord: Comma-separated list of Discord user IDs with admin access
     /// If empty, falls back to Discord's built-in Administrator permission
     DiscordAdminUserIds,
+    /// Discord: Reply in a thread started from the user's message
+    DiscordReplyInThread,
     /// Telegram: Bot authentication token from @BotFather
     TelegramBotToken,
     /// Slack: Bot OAuth token (xoxb-...)
@@ -84,6 +90,16 @@ pub enum ChannelSettingKey {
     ExternalChannelApiToken,
     /// External Gateway: Enable safe mode (restricts tool access for untrusted input)
     ExternalChannelSafeMode,
+    /// External Gateway: Shared secret for HMAC request signing (empty = signing not required)
+    ExternalChannelSigningSecret,
+    /// Matrix: Homeserver base URL (e.g., https://matrix.org)
+    MatrixHomeserverUrl,
+    /// Matrix: Access token for the bot account
+    MatrixAccessToken,
+    /// Matrix: Comma-separated list of Matrix user IDs with admin access
+    MatrixAdminUserIds,
+    /// Outbound: Max messages per minute sent to a single chat (0 = platform default)
+    OutboundMessagesPerMinute,
 }
 
 impl ChannelSettingKey {
@@ -91,8 +107,11 @@ impl ChannelSettingKey {
     pub fn label(&self) -> &'static str {
         match self {
             Self::AutoStartOnBoot => "Auto-Start on Boot",
+            Self::PrivateMessageHistory => "Private Message History",
+            Self::PlanApproval => "Plan Approval",
             Self::DiscordBotToken => "Bot Token",
             Self::DiscordAdminUserIds => "Admin User IDs (Optional)",
+            Self::DiscordReplyInThread => "Reply in Thread",
             Self::TelegramBotToken => "Bot Token",
             Self::SlackBotToken => "Bot Token",
             Self::SlackAppToken => "App Token (Socket Mode)",
@@ -106,6 +125,11 @@ impl ChannelSettingKey {
             Self::SlackAdminUserIds => "Admin User IDs (Optional)",
             Self::ExternalChannelApiToken => "API Token",
             Self::ExternalChannelSafeMode => "Safe Mode",
+            Self::ExternalChannelSigningSecret => "Signing Secret (Optional)",
+            Self::MatrixHomeserverUrl => "Homeserver URL",
+            Self::MatrixAccessToken => "Access Token",
+            Self::MatrixAdminUserIds => "Admin User IDs (Optional)",
+            Self::OutboundMessagesPerMinute => "Outbound Messages Per Minute",
         }
     }
 
@@ -116,6 +140,15 @@ impl ChannelSettingKey {
                 "Automatically start this channel when the server boots or restores from backup. \
                  Useful for ensuring your bot is always running after container updates."
             }
+            Self::PrivateMessageHistory => {
+                "Keep message content out of the moderation history log. Messages are still \
+                 recorded with user and timestamp, but their text is never stored or exported."
+            }
+            Self::PlanApproval => {
+                "Show the task list the agent plans for a request and wait for the requester to \
+                 approve it (reply \"approve\" or \"reject\") before any task runs. If nobody answers \
+                 in time, the plan either goes ahead or is dropped."
+            }
             Self::DiscordBotToken => {
                 "Your Discord bot token from the Discord Developer Portal. \
                  Found under Bot > Token in your application settings."
@@ -126,6 +159,11 @@ impl ChannelSettingKey {
                  If any IDs are set, ONLY those users have admin access (Discord admin role is ignored). \
                  Get your ID: enable Developer Mode in Discord settings, then right-click your username."
             }
+            Self::DiscordReplyInThread => {
+                "Start a thread from each message the bot answers and reply there, keeping busy \
+                 channels readable. Each thread keeps its own conversation context. \
+                 Messages already sent inside a thread are always answered in that thread."
+            }
             Self::TelegramBotToken => {
                 "Your Telegram bot token from @BotFather. \
                  Create a bot with /newbot and copy the token provided."
@@ -190,6 +228,32 @@ impl ChannelSettingKey {
                  tool access is restricted to a safe subset. Disable for full agent access \
                  (only if you trust the clients connecting to this channel)."
             }
+            Self::ExternalChannelSigningSecret => {
+                "When set, every request must also carry X-Stark-Timestamp (unix seconds) and \
+                 X-Stark-Signature: sha256=<hex HMAC-SHA256 of \"<timestamp>.<METHOD>.<path>.<body>\">, \
+                 where <path> includes any query string. \
+                 Requests with a bad signature or a stale timestamp are rejected, so a leaked \
+                 request cannot be tampered with or replayed later."
+            }
+            Self::MatrixHomeserverUrl => {
+                "Base URL of the homeserver the bot account lives on (e.g., https://matrix.org). \
+                 This is the client-server API URL, not the server name in user IDs."
+            }
+            Self::MatrixAccessToken => {
+                "Access token for the bot's Matrix account. In Element, find it under \
+                 Settings > Help & About > Access Token. Use a dedicated bot account — \
+                 logging out of that session invalidates the token."
+            }
+            Self::MatrixAdminUserIds => {
+                "Comma-separated Matrix user IDs (e.g., @alice:matrix.org) that have full agent access. \
+                 If left empty, all users get full access. \
+                 If any IDs are set, ONLY those users have admin access; all others use safe mode."
+            }
+            Self::OutboundMessagesPerMinute => {
+                "Maximum messages the bot sends to a single chat per minute. Replies are queued \
+                 and paced to stay under this rate; only if the queue backs up are long replies \
+                 merged into one message. Set to 0 to use the platform's default limits."
+            }
         }
     }
 
@@ -197,8 +261,11 @@ impl ChannelSettingKey {
     pub fn input_type(&self) -> SettingInputType {
         match self {
             Self::AutoStartOnBoot => SettingInputType::Toggle,
+            Self::PrivateMessageHistory => SettingInputType::Toggle,
+            Self::PlanApproval => SettingInputType::Select,
             Self::DiscordBotToken => SettingInputType::Text,
             Self::DiscordAdminUserIds => SettingInputType::Text,
+            Self::DiscordReplyInThread => SettingInputType::Toggle,
             Self::TelegramBotToken => SettingInputType::Text,
             Self::SlackBotToken => SettingInputType::Text,
             Self::SlackAppToken => SettingInputType::Text,
@@ -212,6 +279,11 @@ impl ChannelSettingKey {
             Self::SlackAdminUserIds => SettingInputType::Text,
             Self::ExternalChannelApiToken => SettingInputType::Text,
             Self::ExternalChannelSafeMode => SettingInputType::Toggle,
+            Self::ExternalChannelSigningSecret => SettingInputType::Text,
+            Self::MatrixHomeserverUrl => SettingInputType::Text,
+            Self::MatrixAccessToken => SettingInputType::Text,
+            Self::MatrixAdminUserIds => SettingInputType::Text,
+            Self::OutboundMessagesPerMinute => SettingInputType::Number,
         }
     }
 
@@ -219,8 +291,11 @@ impl ChannelSettingKey {
     pub fn placeholder(&self) -> &'static str {
         match self {
             Self::AutoStartOnBoot => "",
+            Self::PrivateMessageHistory => "",
+            Self::PlanApproval => "",
             Self::DiscordBotToken => "MTIz...abc",
             Self::DiscordAdminUserIds => "123456789012345678, 987654321098765432",
+            Self::DiscordReplyInThread => "",
             Self::TelegramBotToken => "123456:ABC-DEF...",
             Self::SlackBotToken => "xoxb-...",
             Self::SlackAppToken => "xapp-...",
@@ -234,6 +309,11 @@ impl ChannelSettingKey {
             Self::SlackAdminUserIds => "U12345678,U87654321",
             Self::ExternalChannelApiToken => "Click dice to generate a secure token",
             Self::ExternalChannelSafeMode => "",
+            Self::ExternalChannelSigningSecret => "Shared secret known only to your client",
+            Self::MatrixHomeserverUrl => "https://matrix.org",
+            Self::MatrixAccessToken => "syt_...",
+            Self::MatrixAdminUserIds => "@alice:matrix.org, @bob:example.org",
+            Self::OutboundMessagesPerMinute => "0",
         }
     }
 
@@ -248,6 +328,11 @@ impl ChannelSettingKey {
                 ("5", "5%"),
                 ("1", "1%"),
             ]),
+            Self::PlanApproval => Some(vec![
+                ("off", "Off (run plans right away)"),
+                ("proceed", "Ask, run the plan on timeout"),
+                ("cancel", "Ask, drop the plan on timeout"),
+            ]),
             _ => None,
         }
     }
@@ -256,8 +341,11 @@ impl ChannelSettingKey {
     pub fn default_value(&self) -> &'static str {
         match self {
             Self::AutoStartOnBoot => "false",
+            Self::PrivateMessageHistory => "false",
+            Self::PlanApproval => "off",
             Self::DiscordBotToken => "",
             Self::DiscordAdminUserIds => "",
+            Self::DiscordReplyInThread => "false",
             Self::TelegramBotToken => "",
             Self::SlackBotToken => "",
             Self::SlackAppToken => "",
@@ -271,12 +359,17 @@ impl ChannelSettingKey {
             Self::SlackAdminUserIds => "",
             Self::ExternalChannelApiToken => "",
             Self::ExternalChannelSafeMode => "false",
+            Self::ExternalChannelSigningSecret => "",
+            Self::MatrixHomeserverUrl => "",
+            Self::MatrixAccessToken => "",
+            Self::MatrixAdminUserIds => "",
+            Self::OutboundMessagesPerMinute => "0",
         }
     }
 
     /// Check if this setting applies to all channel types (common setting)
     pub fn is_common(&self) -> bool {
-        matches!(self, Self::AutoStartOnBoot)
+        matches!(self, Self::AutoStartOnBoot | Self::PrivateMessageHistory | Self::PlanApproval)
     }
 }
 
@@ -377,6 +470,8 @@ pub struct SettingUpdate {
 fn get_common_settings() -> Vec<ChannelSettingDefinition> {
     vec![
         ChannelSettingKey::AutoStartOnBoot.into(),
+        ChannelSettingKey::PrivateMessageHistory.into(),
+        ChannelSettingKey::PlanApproval.into(),
     ]
 }
 
@@ -388,15 +483,19 @@ pub fn get_settings_for_channel_type(channel_type: ChannelType) -> Vec<ChannelSe
         ChannelType::Discord => vec![
             ChannelSettingKey::DiscordBotToken.into(),
             ChannelSettingKey::DiscordAdminUserIds.into(),
+            ChannelSettingKey::DiscordReplyInThread.into(),
+            ChannelSettingKey::OutboundMessagesPerMinute.into(),
         ],
         ChannelType::Telegram => vec![
             ChannelSettingKey::TelegramBotToken.into(),
             ChannelSettingKey::TelegramAdminUserId.into(),
+            ChannelSettingKey::OutboundMessagesPerMinute.into(),
         ],
         ChannelType::Slack => vec![
             ChannelSettingKey::SlackBotToken.into(),
             ChannelSettingKey::SlackAppToken.into(),
             ChannelSettingKey::SlackAdminUserIds.into(),
+            ChannelSettingKey::OutboundMessagesPerMinute.into(),
         ],
         ChannelType::Twitter => vec![
             ChannelSettingKey::TwitterBotHandle.into(),
@@ -409,6 +508,13 @@ pub fn get_settings_for_channel_type(channel_type: ChannelType) -> Vec<ChannelSe
         ChannelType::ExternalChannel => vec![
             ChannelSettingKey::ExternalChannelApiToken.into(),
             ChannelSettingKey::ExternalChannelSafeMode.into(),
+            ChannelSettingKey::ExternalChannelSigningSecret.into(),
+        ],
+        ChannelType::Matrix => vec![
+            ChannelSettingKey::MatrixHomeserverUrl.into(),
+            ChannelSettingKey::MatrixAccessToken.into(),
+            ChannelSettingKey::MatrixAdminUserIds.into(),
+            ChannelSettingKey::OutboundMessagesPerMinute.into(),
         ],
     };
 
@@ -429,32 +535,46 @@ mod tests {
     #[test]
     fn test_discord_settings() {
         let settings = get_settings_for_channel_type(ChannelType::Discord);
-        // 1 common + 2 Discord-specific (bot_token, admin_user_ids)
-        assert_eq!(settings.len(), 3);
+        // 3 common + 4 Discord-specific (bot_token, admin_user_ids, reply_in_thread, outbound_messages_per_minute)
+        assert_eq!(settings.len(), 7);
         assert_eq!(settings[0].key, "auto_start_on_boot");
-        assert_eq!(settings[1].key, "discord_bot_token");
-        assert_eq!(settings[2].key, "discord_admin_user_ids");
+        assert_eq!(settings[1].key, "private_message_history");
+        assert_eq!(settings[2].key, "plan_approval");
+        assert_eq!(settings[3].key, "discord_bot_token");
+        assert_eq!(settings[4].key, "discord_admin_user_ids");
+        assert_eq!(settings[5].key, "discord_reply_in_thread");
+        assert_eq!(settings[6].key, "outbound_messages_per_minute");
     }
 
     #[test]
     fn test_telegram_settings() {
         let settings = get_settings_for_channel_type(ChannelType::Telegram);
-        // 1 common + 2 Telegram-specific (bot_token, admin_user_id)
-        assert_eq!(settings.len(), 3);
+        // 3 common + 3 Telegram-specific (bot_token, admin_user_id, outbound_messages_per_minute)
+        assert_eq!(settings.len(), 6);
         assert_eq!(settings[0].key, "auto_start_on_boot");
-        assert_eq!(settings[1].key, "telegram_bot_token");
-        assert_eq!(settings[2].key, "telegram_admin_user_id");
+        assert_eq!(settings[3].key, "telegram_bot_token");
+        assert_eq!(settings[4].key, "telegram_admin_user_id");
     }
 
     #[test]
     fn test_slack_settings() {
         let settings = get_settings_for_channel_type(ChannelType::Slack);
-        // 1 common + 3 Slack-specific (bot_token, app_token, admin_user_ids)
-        assert_eq!(settings.len(), 4);
+        // 3 common + 4 Slack-specific (bot_token, app_token, admin_user_ids, outbound_messages_per_minute)
+        assert_eq!(settings.len(), 7);
         assert_eq!(settings[0].key, "auto_start_on_boot");
-        assert_eq!(settings[1].key, "slack_bot_token");
-        assert_eq!(settings[2].key, "slack_app_token");
-        assert_eq!(settings[3].key, "slack_admin_user_ids");
+        assert_eq!(settings[3].key, "slack_bot_token");
+        assert_eq!(settings[4].key, "slack_app_token");
+        assert_eq!(settings[5].key, "slack_admin_user_ids");
+    }
+
+    #[test]
+    fn test_matrix_settings() {
+        let settings = get_settings_for_channel_type(ChannelType::Matrix);
+        // 3 common + 4 Matrix-specific (homeserver_url, access_token, admin_user_ids, outbound_messages_per_minute)
+        assert_eq!(settings.len(), 7);
+        assert_eq!(settings[3].key, "matrix_homeserver_url");
+        assert_eq!(settings[4].key, "matrix_access_token");
+        assert_eq!(settings[5].key, "matrix_admin_user_ids");
     }
 
     #[test]
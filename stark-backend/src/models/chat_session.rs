@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::session_message::MessageRole;
+
 /// Session scope determines the context type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -213,6 +215,9 @@ pub struct ChatSessionResponse {
     // Initial query (first user message) - for web sessions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub initial_query: Option<String>,
+    // Generated human-readable title
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     // Safe mode - from the channel settings
     #[serde(skip_serializing_if = "Option::is_none")]
     pub safe_mode: Option<bool>,
@@ -221,6 +226,64 @@ pub struct ChatSessionResponse {
     pub special_role_name: Option<String>,
 }
 
+/// Longest tag accepted (characters)
+pub const MAX_SESSION_TAG_LEN: usize = 64;
+/// Most tags a single session can carry
+pub const MAX_TAGS_PER_SESSION: usize = 32;
+
+/// Normalize a free-form session tag: trimmed, lowercased, inner whitespace collapsed.
+/// Returns None for empty or overlong tags.
+pub fn normalize_session_tag(tag: &str) -> Option<String> {
+    let tag = tag.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    if tag.is_empty() || tag.chars().count() > MAX_SESSION_TAG_LEN {
+        return None;
+    }
+    Some(tag)
+}
+
+/// Request body for setting or adding session tags
+#[derive(Debug, Clone, Deserialize)]
+pub struct SessionTagsRequest {
+    pub tags: Vec<String>,
+}
+
+/// Filters for session search. All set filters must match.
+#[derive(Debug, Clone, Default)]
+pub struct SessionSearchFilter {
+    /// Sessions must carry every one of these (normalized) tags
+    pub tags: Vec<String>,
+    pub channel_type: Option<String>,
+    /// Session was active at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Session started at or before this time
+    pub to: Option<DateTime<Utc>>,
+    /// Full-text query against message content
+    pub text: Option<String>,
+    pub limit: usize,
+}
+
+/// A message matching the full-text query, with the match highlighted
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionMatchSnippet {
+    pub message_id: i64,
+    pub role: MessageRole,
+    /// Excerpt around the match, matched terms wrapped in `**`
+    pub snippet: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One session in search results
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSearchResult {
+    #[serde(flatten)]
+    pub session: ChatSessionResponse,
+    pub tags: Vec<String>,
+    /// Number of messages matching the text query (0 without one)
+    pub match_count: i64,
+    /// Best-ranked matching messages
+    pub snippets: Vec<SessionMatchSnippet>,
+}
+
 impl From<ChatSession> for ChatSessionResponse {
     fn from(session: ChatSession) -> Self {
         ChatSessionResponse {
@@ -244,8 +307,76 @@ impl From<ChatSession> for ChatSessionResponse {
             compaction_id: session.compaction_id,
             completion_status: session.completion_status,
             initial_query: None,
+            title: None,
             safe_mode: if session.safe_mode { Some(true) } else { None },
             special_role_name: session.special_role_name,
         }
     }
 }
+
+/// What a session spend ledger entry paid for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpendKind {
+    /// x402 payment (AI endpoint or paid API)
+    X402,
+    /// Estimated AI token cost
+    AiTokens,
+    /// Gas for a broadcast transaction
+    Gas,
+}
+
+impl SpendKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpendKind::X402 => "x402",
+            SpendKind::AiTokens => "ai_tokens",
+            SpendKind::Gas => "gas",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "x402" => Some(SpendKind::X402),
+            "ai_tokens" => Some(SpendKind::AiTokens),
+            "gas" => Some(SpendKind::Gas),
+            _ => None,
+        }
+    }
+}
+
+/// One entry in a session's spend ledger
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSpendEntry {
+    pub id: i64,
+    pub session_id: i64,
+    pub kind: SpendKind,
+    /// Amount in `currency` units (e.g. 0.01 USDC, 0.0002 ETH)
+    pub amount: f64,
+    pub currency: String,
+    /// Estimated tokens (AI entries only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<i64>,
+    /// What was paid for: resource URL, model, tx hash...
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Running total for one kind and currency
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionSpendTotal {
+    pub kind: SpendKind,
+    pub currency: String,
+    pub amount: f64,
+    pub tokens: i64,
+    pub entries: i64,
+}
+
+/// A session's spend ledger with per-kind totals
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSpendSummary {
+    pub session_id: i64,
+    pub totals: Vec<SessionSpendTotal>,
+    pub entries: Vec<SessionSpendEntry>,
+}
@@ -12,7 +12,7 @@ pub mod session_message;
 pub mod special_role;
 
 pub use agent_settings::{AgentSettings, AgentSettingsResponse, UpdateAgentSettingsRequest, MIN_CONTEXT_TOKENS, DEFAULT_CONTEXT_TOKENS};
-pub use bot_settings::{BotSettings, UpdateBotSettingsRequest, DEFAULT_MAX_TOOL_ITERATIONS, DEFAULT_SAFE_MODE_MAX_QUERIES_PER_10MIN, DEFAULT_WHISPER_SERVER_URL, DEFAULT_EMBEDDINGS_SERVER_URL};
+pub use bot_settings::{BotSettings, UpdateBotSettingsRequest, DEFAULT_MAX_TOOL_ITERATIONS, DEFAULT_SAFE_MODE_MAX_QUERIES_PER_10MIN, DEFAULT_WHISPER_SERVER_URL, DEFAULT_EMBEDDINGS_SERVER_URL, DEFAULT_GAS_STRATEGY, DEFAULT_COMMAND_PREFIX, DEFAULT_GATEWAY_PREVIOUS_MESSAGES, MAX_GATEWAY_PREVIOUS_MESSAGES, DEFAULT_MEMORY_FTS_TOKENIZER, DEFAULT_TX_CONFIRMATION_TIMEOUT_SECS, MAX_TX_CONFIRMATION_TIMEOUT_SECS};
 pub use api_key::{ApiKey, ApiKeyResponse};
 pub use channel::{Channel, ChannelResponse, ChannelType, CreateChannelRequest, CreateSafeModeChannelRequest, UpdateChannelRequest};
 pub use channel_settings::{
@@ -24,12 +24,18 @@ pub use chat_session::{
     ChatSession, ChatSessionResponse, CompletionStatus, GetOrCreateSessionRequest, ResetPolicy,
     SessionScope, UpdateResetPolicyRequest,
 };
+pub use chat_session::{
+    normalize_session_tag, SessionMatchSnippet, SessionSearchFilter, SessionSearchResult,
+    SessionTagsRequest, MAX_TAGS_PER_SESSION,
+};
+pub use chat_session::{SessionSpendEntry, SessionSpendSummary, SessionSpendTotal, SpendKind};
 pub use identity::{
     GetOrCreateIdentityRequest, IdentityLink, IdentityResponse, LinkIdentityRequest,
     LinkedAccountInfo,
 };
-pub use session::Session;
+pub use session::{Session, SessionCheck, SESSION_EXPIRED_CODE};
 pub use session_message::{AddMessageRequest, MessageRole, SessionMessage, SessionTranscriptResponse};
+pub use session_message::SessionTranscriptExport;
 pub use cron_job::{
     CreateCronJobRequest, CronJob, CronJobResponse, CronJobRun, HeartbeatConfig,
     HeartbeatConfigResponse, JobStatus, ScheduleType, SessionMode, UpdateCronJobRequest,
@@ -7,4 +7,19 @@ pub struct Session {
     pub token: String,
     pub created_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
+    pub last_active_at: Option<DateTime<Utc>>,
+}
+
+/// Error code returned when a request carries an expired session token,
+/// so the frontend can prompt the user to sign in again
+pub const SESSION_EXPIRED_CODE: &str = "session_expired";
+
+/// Result of looking up a session token
+#[derive(Debug, Clone)]
+pub enum SessionCheck {
+    Valid(Session),
+    /// Token exists but its expiry has passed
+    Expired,
+    /// Token is unknown (never issued, logged out, or cleaned up)
+    Invalid,
 }
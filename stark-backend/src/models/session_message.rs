@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::chat_session::ChatSessionResponse;
+
 /// Message role in the conversation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -67,3 +69,156 @@ pub struct SessionTranscriptResponse {
     pub messages: Vec<SessionMessage>,
     pub total_count: i64,
 }
+
+/// Full session export for sharing or archiving (GET /api/sessions/{id}/export)
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionTranscriptExport {
+    pub session: ChatSessionResponse,
+    /// Summary of messages removed by compaction (only when requested)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compaction_summary: Option<String>,
+    pub messages: Vec<SessionMessage>,
+    /// Sum of `tokens_used` over all messages that recorded it
+    pub total_tokens: i64,
+    pub exported_at: DateTime<Utc>,
+}
+
+impl SessionTranscriptExport {
+    pub fn new(
+        session: ChatSessionResponse,
+        messages: Vec<SessionMessage>,
+        compaction_summary: Option<String>,
+    ) -> Self {
+        let total_tokens = messages.iter().filter_map(|m| m.tokens_used).map(i64::from).sum();
+        Self {
+            session,
+            compaction_summary,
+            messages,
+            total_tokens,
+            exported_at: Utc::now(),
+        }
+    }
+
+    /// Render the transcript as markdown. Message content is already markdown
+    /// (tool calls/results are stored pre-formatted), so it is emitted as-is.
+    pub fn to_markdown(&self) -> String {
+        const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S UTC";
+        let session = &self.session;
+        let mut out = match session.title {
+            Some(ref title) => format!("# Session #{}: {}\n\n", session.id, title),
+            None => format!("# Session #{} transcript\n\n", session.id),
+        };
+        out.push_str(&format!(
+            "- **Channel:** {} (chat `{}`)\n",
+            session.channel_type, session.platform_chat_id
+        ));
+        out.push_str(&format!("- **Started:** {}\n", session.created_at.format(TIME_FORMAT)));
+        out.push_str(&format!("- **Last activity:** {}\n", session.last_activity_at.format(TIME_FORMAT)));
+        out.push_str(&format!("- **Status:** {}\n", session.completion_status));
+        out.push_str(&format!(
+            "- **Messages:** {} · **Tokens:** {}\n",
+            self.messages.len(),
+            self.total_tokens
+        ));
+        out.push_str(&format!("- **Exported:** {}\n", self.exported_at.format(TIME_FORMAT)));
+
+        if let Some(summary) = self.compaction_summary.as_deref().filter(|s| !s.trim().is_empty()) {
+            out.push_str("\n## Compaction summary\n\n");
+            for line in summary.trim().lines() {
+                if line.trim().is_empty() {
+                    out.push_str(">\n");
+                } else {
+                    out.push_str(&format!("> {}\n", line));
+                }
+            }
+        }
+
+        out.push_str("\n## Messages\n");
+        for msg in &self.messages {
+            let label = match msg.role {
+                MessageRole::User => "👤 User",
+                MessageRole::Assistant => "🤖 Assistant",
+                MessageRole::System => "⚙️ System",
+                MessageRole::ToolCall => "🔧 Tool call",
+                MessageRole::ToolResult => "📋 Tool result",
+            };
+            let mut heading = label.to_string();
+            if let Some(name) = msg.user_name.as_deref().filter(|n| !n.is_empty()) {
+                heading.push_str(&format!(" — {}", name));
+            }
+            heading.push_str(&format!(" · {}", msg.created_at.format(TIME_FORMAT)));
+            if let Some(tokens) = msg.tokens_used {
+                heading.push_str(&format!(" · {} tokens", tokens));
+            }
+            out.push_str(&format!("\n### {}\n\n{}\n", heading, msg.content.trim_end()));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ChatSession, SessionScope};
+
+    fn message(id: i64, role: MessageRole, content: &str, user_name: Option<&str>, tokens: Option<i32>) -> SessionMessage {
+        SessionMessage {
+            id,
+            session_id: 7,
+            role,
+            content: content.to_string(),
+            user_id: None,
+            user_name: user_name.map(str::to_string),
+            platform_message_id: None,
+            tokens_used: tokens,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_export_to_markdown() {
+        let now = Utc::now();
+        let session: ChatSessionResponse = ChatSession {
+            id: 7,
+            session_key: "discord:1:abc".to_string(),
+            agent_id: None,
+            scope: SessionScope::Dm,
+            channel_type: "discord".to_string(),
+            channel_id: 1,
+            platform_chat_id: "abc".to_string(),
+            is_active: true,
+            reset_policy: Default::default(),
+            idle_timeout_minutes: None,
+            daily_reset_hour: None,
+            created_at: now,
+            updated_at: now,
+            last_activity_at: now,
+            expires_at: None,
+            context_tokens: 0,
+            max_context_tokens: 100_000,
+            compaction_id: None,
+            completion_status: Default::default(),
+            safe_mode: false,
+            special_role_name: None,
+        }
+        .into();
+        let messages = vec![
+            message(1, MessageRole::User, "What's the weather?", Some("alice"), Some(5)),
+            message(2, MessageRole::ToolCall, "🔧 **Tool Call:** `weather`\n```json\n{}\n```", Some("weather"), None),
+            message(3, MessageRole::Assistant, "Sunny.", None, Some(3)),
+        ];
+
+        let export = SessionTranscriptExport::new(session, messages, Some("User asked about the weather.\n\nTwice.".to_string()));
+        assert_eq!(export.total_tokens, 8);
+
+        let md = export.to_markdown();
+        assert!(md.starts_with("# Session #7 transcript"));
+        assert!(md.contains("- **Messages:** 3 · **Tokens:** 8"));
+        assert!(md.contains("## Compaction summary\n\n> User asked about the weather.\n>\n> Twice.\n"));
+        assert!(md.contains("### 👤 User — alice · "));
+        assert!(md.contains(" · 5 tokens\n\nWhat's the weather?\n"));
+        assert!(md.contains("### 🔧 Tool call — weather · "));
+        assert!(md.contains("```json\n{}\n```"));
+        assert!(md.trim_end().ends_with("Sunny."));
+    }
+}
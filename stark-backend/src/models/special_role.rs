@@ -9,6 +9,9 @@ pub struct SpecialRole {
     pub allowed_tools: Vec<String>,
     /// Individual skill names granted to this role (e.g. ["image_generation", "weather"])
     pub allowed_skills: Vec<String>,
+    /// Tool names this role never gets, even when a granted skill requires them
+    #[serde(default)]
+    pub denied_tools: Vec<String>,
     pub description: Option<String>,
     pub created_at: String,
     pub updated_at: String,
@@ -47,10 +50,18 @@ pub struct SpecialRoleGrants {
     pub extra_tools: Vec<String>,
     /// Individual skill names granted (e.g. ["image_generation"])
     pub extra_skills: Vec<String>,
+    /// Tool names denied after the grants are applied (e.g. ["web_fetch"])
+    #[serde(default)]
+    pub denied_tools: Vec<String>,
 }
 
 impl SpecialRoleGrants {
     pub fn is_empty(&self) -> bool {
-        self.extra_tools.is_empty() && self.extra_skills.is_empty()
+        self.extra_tools.is_empty() && self.extra_skills.is_empty() && self.denied_tools.is_empty()
+    }
+
+    /// Whether the role denies a tool
+    pub fn denies_tool(&self, tool_name: &str) -> bool {
+        self.denied_tools.iter().any(|t| t == tool_name)
     }
 }
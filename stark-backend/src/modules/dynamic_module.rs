@@ -226,6 +226,10 @@ impl super::Module for DynamicModule {
         self.manifest.service_url()
     }
 
+    fn health_endpoint(&self) -> String {
+        self.manifest.service.health_endpoint.clone()
+    }
+
     fn has_tools(&self) -> bool {
         !self.manifest.tools.is_empty()
     }
@@ -267,6 +271,21 @@ impl super::Module for DynamicModule {
         Some(&self.module_dir)
     }
 
+    fn permissions(&self) -> Vec<crate::modules::permissions::Permission> {
+        crate::modules::permissions::parse_declared(
+            &self.manifest.module.name,
+            &self.manifest.service.permissions,
+        )
+    }
+
+    fn rpc_methods(&self) -> Vec<crate::modules::manifest::RpcMethodInfo> {
+        self.manifest.rpc_methods()
+    }
+
+    fn resource_limits(&self) -> crate::modules::manifest::ResourceLimits {
+        self.manifest.service.limits.clone()
+    }
+
     fn skill_content(&self) -> Option<&str> {
         self.skill_content.as_deref()
     }
@@ -36,7 +36,15 @@ impl DynamicModuleTool {
                         .clone()
                         .unwrap_or_default(),
                     default: param.default.as_ref().map(toml_to_json),
-                    items: None,
+                    items: param.items.as_ref().map(|item_type| {
+                        Box::new(PropertySchema {
+                            schema_type: item_type.clone(),
+                            description: String::new(),
+                            default: None,
+                            items: None,
+                            enum_values: None,
+                        })
+                    }),
                     enum_values: param.enum_values.clone(),
                 },
             );
@@ -61,6 +69,7 @@ impl DynamicModuleTool {
                 },
                 group: manifest.tool_group(),
                 hidden: false,
+                ..Default::default()
             },
             rpc_url,
             rpc_method: manifest.rpc_method.clone(),
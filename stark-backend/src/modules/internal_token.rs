@@ -0,0 +1,103 @@
+//! Internal token for module → backend API calls.
+//!
+//! The token starts as `STARKBOT_INTERNAL_TOKEN` and can be rotated at runtime.
+//! After a rotation the previous token keeps working for a grace period, so
+//! module services that have not picked up the new value yet (or had a request
+//! in flight) are not cut off.
+
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+struct TokenState {
+    current: String,
+    /// Previous token and the moment it stops being accepted
+    previous: Option<(String, Instant)>,
+}
+
+static TOKEN: RwLock<Option<TokenState>> = RwLock::new(None);
+
+/// Set the startup token. Called once before the HTTP server starts.
+pub fn init(token: &str) {
+    *TOKEN.write().unwrap() = Some(TokenState {
+        current: token.to_string(),
+        previous: None,
+    });
+}
+
+/// The token modules should use now
+pub fn current() -> String {
+    TOKEN
+        .read()
+        .unwrap()
+        .as_ref()
+        .map(|s| s.current.clone())
+        .unwrap_or_default()
+}
+
+/// Whether `provided` is the current token, or the previous one within its grace period
+pub fn verify(provided: &str) -> bool {
+    if provided.is_empty() {
+        return false;
+    }
+    let guard = TOKEN.read().unwrap();
+    let Some(state) = guard.as_ref() else {
+        return false;
+    };
+    if constant_time_eq(provided.as_bytes(), state.current.as_bytes()) {
+        return true;
+    }
+    state.previous.as_ref().is_some_and(|(old, expires)| {
+        Instant::now() < *expires && constant_time_eq(provided.as_bytes(), old.as_bytes())
+    })
+}
+
+/// Replace the token with a fresh random one, keeping the old one valid for `grace`.
+/// Returns `(new, old)`.
+pub fn rotate(grace: Duration) -> (String, String) {
+    let mut buf = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut buf);
+    let new = hex::encode(buf);
+
+    let mut guard = TOKEN.write().unwrap();
+    let state = guard.get_or_insert_with(|| TokenState {
+        current: String::new(),
+        previous: None,
+    });
+    let old = std::mem::replace(&mut state.current, new.clone());
+    state.previous = (!old.is_empty()).then(|| (old.clone(), Instant::now() + grace));
+    (new, old)
+}
+
+/// Constant-time comparison to prevent timing attacks
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let mut diff = a.len() ^ b.len();
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= (*x ^ *y) as usize;
+    }
+    diff == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_keeps_old_token_for_grace_period() {
+        init("startup-token");
+        assert!(verify("startup-token"));
+        assert!(!verify(""));
+        assert!(!verify("startup-toke"));
+
+        let (new, old) = rotate(Duration::from_secs(60));
+        assert_eq!(old, "startup-token");
+        assert_eq!(current(), new);
+        assert!(verify(&new));
+        assert!(verify("startup-token"));
+
+        // A second rotation with no grace drops the previous token immediately
+        let (newer, _) = rotate(Duration::ZERO);
+        assert!(verify(&newer));
+        assert!(!verify(&new));
+        assert!(!verify("startup-token"));
+    }
+}
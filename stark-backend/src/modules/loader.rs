@@ -96,6 +96,8 @@ pub fn get_dynamic_service_binaries() -> Vec<DynamicServiceInfo> {
             let url_env = m.manifest_url_env_var();
             let command = m.manifest_command();
             let module_dir = m.module_dir().clone();
+            let limits = m.resource_limits();
+            let permissions = m.permissions();
             DynamicServiceInfo {
                 name,
                 default_port: port,
@@ -104,6 +106,8 @@ pub fn get_dynamic_service_binaries() -> Vec<DynamicServiceInfo> {
                 url_env_var: url_env,
                 command,
                 module_dir,
+                limits,
+                permissions,
             }
         })
         .collect()
@@ -121,4 +125,8 @@ pub struct DynamicServiceInfo {
     pub command: Option<String>,
     /// Directory containing the module — used as working directory for command.
     pub module_dir: PathBuf,
+    /// Resource caps for the service process tree
+    pub limits: super::manifest::ResourceLimits,
+    /// Internal capabilities declared in the manifest, granted to the spawned service
+    pub permissions: Vec<super::permissions::Permission>,
 }
@@ -72,12 +72,37 @@ pub struct ServiceConfig {
     /// Extra environment variables the service needs.
     #[serde(default)]
     pub env_vars: HashMap<String, EnvVarSpec>,
+    /// Internal backend capabilities the service may use (e.g. "wallet.sign").
+    /// See `modules::permissions` for the list.
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    /// Resource caps for the service process tree (`[service.limits]`)
+    #[serde(default)]
+    pub limits: ResourceLimits,
 }
 
 fn default_health_endpoint() -> String {
     "/rpc/status".to_string()
 }
 
+/// Optional resource caps for a module service. A service over its memory cap,
+/// or over its CPU cap for several samples in a row, is killed and restarted.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ResourceLimits {
+    /// Resident memory cap in MiB, across the service and its child processes
+    #[serde(default)]
+    pub max_memory_mb: Option<u64>,
+    /// CPU cap in percent of one core (e.g. 50.0, or 200.0 for two cores)
+    #[serde(default)]
+    pub max_cpu_percent: Option<f64>,
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        self.max_memory_mb.is_none() && self.max_cpu_percent.is_none()
+    }
+}
+
 /// Spec for a required/optional environment variable.
 #[derive(Debug, Clone, Deserialize)]
 pub struct EnvVarSpec {
@@ -153,6 +178,9 @@ pub struct ToolParameterManifest {
     pub enum_values: Option<Vec<String>>,
     #[serde(default)]
     pub default: Option<toml::Value>,
+    /// Element type for `array` parameters (e.g. "integer", "object")
+    #[serde(default)]
+    pub items: Option<String>,
 }
 
 fn default_param_type() -> String {
@@ -189,6 +217,18 @@ pub struct ExtEndpointManifest {
     pub x402_network: Option<String>,
 }
 
+/// A declared RPC method on a module service.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcMethodInfo {
+    /// Tool name or ext endpoint method name
+    pub name: String,
+    pub description: Option<String>,
+    /// Path on the module service (e.g. "/rpc/watchlist")
+    pub rpc_endpoint: String,
+    /// HTTP method the service expects ("GET" or "POST")
+    pub http_method: String,
+}
+
 fn default_http_methods() -> Vec<String> {
     vec!["POST".to_string()]
 }
@@ -211,6 +251,34 @@ impl ModuleManifest {
         self.ext_endpoints.iter().find(|ep| ep.method_name == method)
     }
 
+    /// RPC methods the agent may call generically (`module_rpc`): every tool and
+    /// ext endpoint the manifest declares. Undeclared service routes stay unreachable.
+    pub fn rpc_methods(&self) -> Vec<RpcMethodInfo> {
+        let tools = self.tools.iter().map(|t| RpcMethodInfo {
+            name: t.name.clone(),
+            description: Some(t.description.clone()),
+            rpc_endpoint: t.rpc_endpoint.clone(),
+            http_method: t.rpc_method.to_uppercase(),
+        });
+        let ext = self.ext_endpoints.iter().map(|ep| RpcMethodInfo {
+            name: ep.method_name.clone(),
+            description: ep.description.clone(),
+            rpc_endpoint: ep.rpc_endpoint.clone(),
+            http_method: ep
+                .http_methods
+                .first()
+                .map(|m| m.to_uppercase())
+                .unwrap_or_else(default_rpc_method),
+        });
+        let mut methods: Vec<RpcMethodInfo> = Vec::new();
+        for m in tools.chain(ext) {
+            if !methods.iter().any(|existing| existing.name == m.name) {
+                methods.push(m);
+            }
+        }
+        methods
+    }
+
     /// Build the service URL from environment variables or defaults.
     pub fn service_url(&self) -> String {
         // First check the URL env var
@@ -283,6 +351,40 @@ default_port = 9200
         assert_eq!(manifest.module.name, "test_module");
         assert_eq!(manifest.service.default_port, 9200);
         assert!(manifest.tools.is_empty());
+        assert!(manifest.service.permissions.is_empty());
+        assert!(manifest.service.limits.is_empty());
+    }
+
+    #[test]
+    fn test_parse_service_limits() {
+        let toml = r#"
+[module]
+name = "limited"
+version = "1.0.0"
+description = "A module with resource limits"
+
+[service]
+default_port = 9200
+
+[service.limits]
+max_memory_mb = 256
+max_cpu_percent = 50
+"#;
+        let manifest = ModuleManifest::from_str(toml).unwrap();
+        assert_eq!(manifest.service.limits.max_memory_mb, Some(256));
+        assert_eq!(manifest.service.limits.max_cpu_percent, Some(50.0));
+    }
+
+    #[test]
+    fn test_bundled_manifests_declare_known_permissions() {
+        let manifest =
+            ModuleManifest::from_str(include_str!("../../../modules/wallet_monitor/module.toml")).unwrap();
+        let granted = crate::modules::permissions::parse_declared(
+            &manifest.module.name,
+            &manifest.service.permissions,
+        );
+        assert_eq!(granted.len(), manifest.service.permissions.len());
+        assert!(granted.contains(&crate::modules::permissions::Permission::WalletSign));
     }
 
     #[test]
@@ -331,6 +433,8 @@ http_methods = ["GET"]
         assert_eq!(manifest.ext_endpoints.len(), 2);
         assert_eq!(manifest.ext_endpoints[0].method_name, "tell-a-joke");
         assert_eq!(manifest.ext_endpoints[0].rpc_endpoint, "/rpc/ext/tell-a-joke");
+        let methods = manifest.rpc_methods();
+        assert!(methods.iter().any(|m| m.name == "tell-a-joke" && m.rpc_endpoint == "/rpc/ext/tell-a-joke"));
         assert_eq!(manifest.ext_endpoints[0].http_methods, vec!["POST"]);
         assert_eq!(manifest.ext_endpoints[1].method_name, "list-categories");
         assert_eq!(manifest.ext_endpoints[1].http_methods, vec!["GET"]);
@@ -398,6 +502,11 @@ enum = ["add", "remove", "list"]
 type = "string"
 description = "Ethereum wallet address"
 required = false
+
+[tools.parameters.ids]
+type = "array"
+items = "integer"
+description = "Entry IDs"
 "#;
         let manifest = ModuleManifest::from_str(toml).unwrap();
         assert_eq!(manifest.module.name, "wallet_monitor");
@@ -409,7 +518,8 @@ required = false
         let tool = &manifest.tools[0];
         assert_eq!(tool.name, "wallet_watchlist");
         assert_eq!(tool.rpc_endpoint, "/rpc/watchlist");
-        assert_eq!(tool.parameters.len(), 2);
+        assert_eq!(tool.parameters.len(), 3);
+        assert_eq!(tool.parameters["ids"].items.as_deref(), Some("integer"));
         assert_eq!(tool.required_parameters(), vec!["action".to_string()]);
         assert_eq!(tool.tool_group(), crate::tools::types::ToolGroup::Finance);
     }
@@ -6,11 +6,15 @@
 
 pub mod dynamic_module;
 pub mod dynamic_tool;
+pub mod internal_token;
 pub mod loader;
 pub mod manifest;
+pub mod module_identity;
+pub mod permissions;
 pub mod port_registry;
 pub mod registry;
 pub mod service_logs;
+pub mod supervisor;
 pub mod zip_parser;
 
 use async_trait::async_trait;
@@ -79,6 +83,11 @@ pub trait Module: Send + Sync {
     /// The base URL of the running service (reads from env or falls back to default)
     fn service_url(&self) -> String;
 
+    /// Path of the service's health check (relative to `service_url`)
+    fn health_endpoint(&self) -> String {
+        "/rpc/status".to_string()
+    }
+
     /// Whether this module provides tools to the bot
     fn has_tools(&self) -> bool;
     /// Whether this module has a standalone dashboard (served by the service itself)
@@ -111,6 +120,21 @@ pub trait Module: Send + Sync {
         None
     }
 
+    /// Internal capabilities declared in the manifest (`[service] permissions`)
+    fn permissions(&self) -> Vec<permissions::Permission> {
+        Vec::new()
+    }
+
+    /// RPC methods declared in the manifest — the only ones `module_rpc` may call
+    fn rpc_methods(&self) -> Vec<manifest::RpcMethodInfo> {
+        Vec::new()
+    }
+
+    /// Resource caps for the service declared in the manifest (`[service.limits]`)
+    fn resource_limits(&self) -> manifest::ResourceLimits {
+        manifest::ResourceLimits::default()
+    }
+
     /// Optional: skill markdown content to install
     fn skill_content(&self) -> Option<&str> {
         None
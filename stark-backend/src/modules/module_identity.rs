@@ -0,0 +1,124 @@
+//! Per-module identity tokens.
+//!
+//! The shared internal token proves a caller is *some* module; it says nothing
+//! about which one. Each module service the backend spawns also gets its own
+//! random `STARKBOT_MODULE_TOKEN`, sent back as `X-Module-Token` on sensitive
+//! internal calls (wallet signing). That lets those endpoints reject processes
+//! the backend never started, attribute each call to a module, and cut off a
+//! single compromised module without rotating the token for all of them.
+//!
+//! The permissions the module's manifest declared are captured with its
+//! identity at spawn, so permission checks don't reload manifests per call.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use super::internal_token::constant_time_eq;
+use super::permissions::Permission;
+
+/// Header modules send their identity token in
+pub const HEADER: &str = "X-Module-Token";
+
+/// Env var a spawned module service receives its identity token in
+pub const ENV_VAR: &str = "STARKBOT_MODULE_TOKEN";
+
+/// An identity issued to a spawned module
+struct Issued {
+    token: String,
+    permissions: Vec<Permission>,
+}
+
+/// module name → issued identity
+static IDENTITIES: RwLock<Option<HashMap<String, Issued>>> = RwLock::new(None);
+
+/// Issue a fresh identity for a module about to be spawned with the
+/// `permissions` its manifest declares, replacing (and so revoking) any
+/// previous one.
+pub fn issue(module_name: &str, permissions: Vec<Permission>) -> String {
+    let mut buf = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut buf);
+    let token = hex::encode(buf);
+    IDENTITIES
+        .write()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(module_name.to_string(), Issued { token: token.clone(), permissions });
+    token
+}
+
+/// The module a token was issued to, if it is still valid
+pub fn identify(token: &str) -> Option<String> {
+    if token.is_empty() {
+        return None;
+    }
+    let guard = IDENTITIES.read().unwrap();
+    // Compare against every entry so timing doesn't reveal which one matched
+    let mut found = None;
+    for (name, issued) in guard.as_ref()? {
+        if constant_time_eq(token.as_bytes(), issued.token.as_bytes()) {
+            found = Some(name.clone());
+        }
+    }
+    found
+}
+
+/// Whether the module was spawned with `permission` declared
+pub fn is_granted(module_name: &str, permission: Permission) -> bool {
+    IDENTITIES
+        .read()
+        .unwrap()
+        .as_ref()
+        .and_then(|m| m.get(module_name))
+        .is_some_and(|issued| issued.permissions.contains(&permission))
+}
+
+/// Revoke a module's identity until it is next spawned. Returns false if it had none.
+pub fn revoke(module_name: &str) -> bool {
+    IDENTITIES
+        .write()
+        .unwrap()
+        .as_mut()
+        .and_then(|m| m.remove(module_name))
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_identify_revoke() {
+        let first = issue("identity_test_a", vec![]);
+        let other = issue("identity_test_b", vec![]);
+        assert_eq!(identify(&first).as_deref(), Some("identity_test_a"));
+        assert_eq!(identify(&other).as_deref(), Some("identity_test_b"));
+        assert_eq!(identify("not-a-token"), None);
+        assert_eq!(identify(""), None);
+
+        // Respawning replaces the old identity
+        let second = issue("identity_test_a", vec![]);
+        assert_eq!(identify(&first), None);
+        assert_eq!(identify(&second).as_deref(), Some("identity_test_a"));
+
+        assert!(revoke("identity_test_a"));
+        assert!(!revoke("identity_test_a"));
+        assert_eq!(identify(&second), None);
+        assert_eq!(identify(&other).as_deref(), Some("identity_test_b"));
+    }
+
+    #[test]
+    fn test_permissions_are_captured_at_issue() {
+        issue("identity_test_c", vec![Permission::WalletAddress]);
+        assert!(is_granted("identity_test_c", Permission::WalletAddress));
+        assert!(!is_granted("identity_test_c", Permission::WalletSign));
+
+        // A respawn picks up the manifest as it is then
+        issue("identity_test_c", vec![Permission::WalletSign]);
+        assert!(!is_granted("identity_test_c", Permission::WalletAddress));
+        assert!(is_granted("identity_test_c", Permission::WalletSign));
+
+        revoke("identity_test_c");
+        assert!(!is_granted("identity_test_c", Permission::WalletSign));
+        assert!(!is_granted("identity_test_never_spawned", Permission::WalletSign));
+    }
+}
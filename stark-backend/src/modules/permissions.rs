@@ -0,0 +1,120 @@
+//! Module permissions — which internal backend capabilities a module may use.
+//!
+//! A module declares what it needs in its manifest:
+//!
+//! ```toml
+//! [service]
+//! permissions = ["wallet.sign", "hooks.fire"]
+//! ```
+//!
+//! Every internal endpoint checks the caller's identity (see `module_identity`)
+//! and refuses modules that did not declare the capability it requires, so a
+//! module can only use what an operator saw it ask for.
+
+use actix_web::{HttpRequest, HttpResponse};
+use serde::Serialize;
+use strum::{AsRefStr, EnumIter, EnumString};
+
+use super::{internal_token, module_identity};
+
+/// An internal capability a module can be granted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, EnumString, AsRefStr, EnumIter)]
+pub enum Permission {
+    /// Read the bot wallet address (`GET /api/internal/wallet/address`)
+    #[strum(serialize = "wallet.address")]
+    #[serde(rename = "wallet.address")]
+    WalletAddress,
+    /// Sign messages and transactions with the bot wallet (`/api/internal/wallet/sign-*`)
+    #[strum(serialize = "wallet.sign")]
+    #[serde(rename = "wallet.sign")]
+    WalletSign,
+    /// Fire custom persona hooks (`POST /api/internal/hooks/fire`)
+    #[strum(serialize = "hooks.fire")]
+    #[serde(rename = "hooks.fire")]
+    HooksFire,
+    /// Ask dashboards to re-render the module TUI (`POST /api/internal/modules/tui-invalidate`)
+    #[strum(serialize = "tui.invalidate")]
+    #[serde(rename = "tui.invalidate")]
+    TuiInvalidate,
+    /// Read x402 spend budgets (`GET /rpc/x402/budget`)
+    #[strum(serialize = "x402.budget")]
+    #[serde(rename = "x402.budget")]
+    X402Budget,
+}
+
+/// Parse declared permission names, dropping (and logging) unknown ones
+pub fn parse_declared(module_name: &str, declared: &[String]) -> Vec<Permission> {
+    declared
+        .iter()
+        .filter_map(|name| match name.parse() {
+            Ok(p) => Some(p),
+            Err(_) => {
+                log::warn!("[MODULE] {} declares unknown permission '{}' — ignored", module_name, name);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Authenticate an internal call and check the calling module holds `permission`.
+///
+/// Requires the shared internal token (`Authorization: Bearer` or `X-Internal-Token`)
+/// and the module's own identity (`X-Module-Token`), and checks `permission`
+/// against the manifest the module was spawned with. Returns the module name.
+pub fn authorize(req: &HttpRequest, permission: Permission) -> Result<String, HttpResponse> {
+    let header = |name: &str| req.headers().get(name).and_then(|h| h.to_str().ok());
+    let token = header("X-Internal-Token")
+        .or_else(|| header("Authorization").and_then(|s| s.strip_prefix("Bearer ")))
+        .unwrap_or("")
+        .trim();
+    if !internal_token::verify(token) {
+        return Err(HttpResponse::Unauthorized().json(serde_json::json!({
+            "error": "Invalid or missing internal token"
+        })));
+    }
+
+    let Some(module) = module_identity::identify(header(module_identity::HEADER).unwrap_or("")) else {
+        log::warn!(
+            "[MODULE] Rejected {} call without a valid module identity",
+            permission.as_ref()
+        );
+        return Err(HttpResponse::Forbidden().json(serde_json::json!({
+            "error": format!("Missing, unknown or revoked {}", module_identity::HEADER)
+        })));
+    };
+
+    if !module_identity::is_granted(&module, permission) {
+        log::warn!(
+            "[MODULE] {} tried to use '{}' without declaring it",
+            module,
+            permission.as_ref()
+        );
+        return Err(HttpResponse::Forbidden().json(serde_json::json!({
+            "error": format!(
+                "Module '{}' does not declare the '{}' permission in its manifest",
+                module,
+                permission.as_ref()
+            )
+        })));
+    }
+
+    Ok(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn test_permission_names_round_trip() {
+        for p in Permission::iter() {
+            assert_eq!(p.as_ref().parse::<Permission>().unwrap(), p);
+            assert_eq!(serde_json::to_value(p).unwrap(), p.as_ref());
+        }
+        assert_eq!(
+            parse_declared("m", &["wallet.sign".into(), "memory.write".into()]),
+            vec![Permission::WalletSign]
+        );
+    }
+}
@@ -21,6 +21,14 @@ pub fn register(module_name: &str, actual_port: u16) {
     m.insert(module_name.to_string(), actual_port);
 }
 
+/// All registered modules and their runtime ports.
+pub fn all() -> Vec<(String, u16)> {
+    let map = PORT_MAP.read().unwrap();
+    map.as_ref()
+        .map(|m| m.iter().map(|(name, port)| (name.clone(), *port)).collect())
+        .unwrap_or_default()
+}
+
 /// Resolve a module name to its runtime port.
 pub fn resolve(module_name: &str) -> Option<u16> {
     let map = PORT_MAP.read().unwrap();
@@ -0,0 +1,464 @@
+//! Module service supervisor — resource usage and limit enforcement.
+//!
+//! Every module service the backend spawns is registered here with the spawn
+//! function that started it. A background monitor samples each service's
+//! process tree from `/proc` (memory and CPU, reported by `controllers::modules`)
+//! and enforces the manifest's `[service.limits]`:
+//!
+//! - on Linux with a writable cgroup v2 hierarchy, the limits are also applied
+//!   as `memory.max` / `cpu.max` at spawn, so the kernel enforces them;
+//! - otherwise (or as a backstop) a service over its memory cap, or over its
+//!   CPU cap for `CPU_STRIKES` samples in a row, is killed by the monitor.
+//!
+//! Either way a service killed for exceeding its limits is restarted, after a
+//! delay that doubles with each restart until it stays up for `STABLE_AFTER`.
+//! Killing and respawning happen outside the supervisor lock, so a slow
+//! spawn never stalls usage lookups.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::manifest::ResourceLimits;
+
+/// Spawns (or respawns) a module service
+pub type SpawnFn = Arc<dyn Fn() -> Option<Child> + Send + Sync>;
+
+/// Consecutive over-limit CPU samples before a service is killed
+const CPU_STRIKES: u32 = 3;
+
+/// Clock ticks per second for `/proc/<pid>/stat` CPU times (USER_HZ, 100 on mainstream Linux)
+const CLOCK_TICKS: f64 = 100.0;
+
+/// Parent cgroup for module services
+const CGROUP_PARENT: &str = "/sys/fs/cgroup/starkbot-modules";
+
+/// Delay before the first limit restart; doubled for each further one in a row
+const RESTART_BACKOFF_BASE: Duration = Duration::from_secs(5);
+
+/// Longest delay between limit restarts
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(300);
+
+/// Uptime after which a service's restart backoff starts over
+const STABLE_AFTER: Duration = Duration::from_secs(600);
+
+/// Current resource usage of a module service
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModuleUsage {
+    pub running: bool,
+    pub pid: Option<u32>,
+    /// Resident memory of the whole process tree, in MiB
+    pub memory_mb: Option<f64>,
+    /// CPU use over the last sample interval, in percent of one core
+    pub cpu_percent: Option<f64>,
+    pub max_memory_mb: Option<u64>,
+    pub max_cpu_percent: Option<f64>,
+    /// "cgroup" when the kernel enforces the limits, "monitor" when the backend does
+    pub enforcement: Option<&'static str>,
+    /// Restarts after being killed for exceeding a limit
+    pub limit_restarts: u32,
+    pub last_limit_violation: Option<String>,
+}
+
+struct Supervised {
+    child: Option<Child>,
+    spawn: SpawnFn,
+    limits: ResourceLimits,
+    cgroup: Option<PathBuf>,
+    /// `oom_kill` count from the cgroup's memory.events at the last sample
+    oom_kills: u64,
+    /// CPU ticks of the process tree at the last sample
+    last_cpu: Option<(u64, Instant)>,
+    cpu_strikes: u32,
+    usage: ModuleUsage,
+    /// When the current process was started
+    started_at: Instant,
+    /// Limit restarts since the service last stayed up for `STABLE_AFTER`
+    restart_streak: u32,
+    /// When a service killed for exceeding its limits is due to be respawned
+    respawn_at: Option<Instant>,
+}
+
+static SUPERVISED: Mutex<Option<HashMap<String, Supervised>>> = Mutex::new(None);
+
+/// Spawn a module service and keep it under supervision.
+/// `spawn` is kept to restart the service if it is killed for exceeding `limits`.
+pub fn spawn(
+    name: &str,
+    limits: ResourceLimits,
+    spawn: impl Fn() -> Option<Child> + Send + Sync + 'static,
+) {
+    let spawn: SpawnFn = Arc::new(spawn);
+    let Some(child) = spawn() else {
+        return;
+    };
+    let cgroup = apply_cgroup(name, child.id(), &limits);
+    let usage = ModuleUsage {
+        running: true,
+        pid: Some(child.id()),
+        max_memory_mb: limits.max_memory_mb,
+        max_cpu_percent: limits.max_cpu_percent,
+        enforcement: (!limits.is_empty()).then_some(if cgroup.is_some() { "cgroup" } else { "monitor" }),
+        ..Default::default()
+    };
+    let entry = Supervised {
+        oom_kills: cgroup.as_deref().map(read_oom_kills).unwrap_or(0),
+        child: Some(child),
+        spawn,
+        limits,
+        cgroup,
+        last_cpu: None,
+        cpu_strikes: 0,
+        usage,
+        started_at: Instant::now(),
+        restart_streak: 0,
+        respawn_at: None,
+    };
+
+    let mut guard = SUPERVISED.lock().unwrap();
+    let map = guard.get_or_insert_with(HashMap::new);
+    if let Some(mut old) = map.insert(name.to_string(), entry) {
+        // Reap the previous process if it has already exited
+        if let Some(ref mut c) = old.child {
+            let _ = c.try_wait();
+        }
+    }
+}
+
+/// Resource usage of a supervised module service
+pub fn usage(name: &str) -> Option<ModuleUsage> {
+    SUPERVISED
+        .lock()
+        .unwrap()
+        .as_ref()?
+        .get(name)
+        .map(|s| s.usage.clone())
+}
+
+/// Resource usage of every supervised module service, by module name
+pub fn usage_all() -> HashMap<String, ModuleUsage> {
+    SUPERVISED
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|map| map.iter().map(|(name, s)| (name.clone(), s.usage.clone())).collect())
+        .unwrap_or_default()
+}
+
+/// Sample all supervised services every `interval`, enforcing their limits
+pub fn start_monitor(interval: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            // /proc reads, kills and respawns block, so keep them off the async workers
+            let _ = tokio::task::spawn_blocking(monitor_pass).await;
+        }
+    });
+}
+
+/// A service due to be respawned, taken out of the supervisor lock
+struct Respawn {
+    name: String,
+    spawn: SpawnFn,
+    limits: ResourceLimits,
+}
+
+fn monitor_pass() {
+    let mut kills = Vec::new();
+    let mut respawns = Vec::new();
+    {
+        let mut guard = SUPERVISED.lock().unwrap();
+        let Some(map) = guard.as_mut() else {
+            return;
+        };
+        let now = Instant::now();
+        for (name, entry) in map.iter_mut() {
+            if let Some(reason) = sample(name, entry)
+                && let Some(child) = schedule_restart(name, entry, reason)
+            {
+                kills.push(child);
+            }
+            if entry.respawn_at.is_some_and(|at| at <= now) {
+                entry.respawn_at = None;
+                respawns.push(Respawn {
+                    name: name.clone(),
+                    spawn: entry.spawn.clone(),
+                    limits: entry.limits.clone(),
+                });
+            }
+        }
+    }
+
+    for child in kills {
+        kill_tree(child);
+    }
+    for respawn in respawns {
+        let child = (respawn.spawn)();
+        install_respawned(respawn, child);
+    }
+}
+
+/// Sample a running service and update its usage. Returns why it must be
+/// restarted if it is over a limit.
+fn sample(name: &str, entry: &mut Supervised) -> Option<String> {
+    let child = entry.child.as_mut()?;
+    let root = child.id();
+
+    if entry.restart_streak > 0 && entry.started_at.elapsed() >= STABLE_AFTER {
+        entry.restart_streak = 0;
+    }
+
+    let cgroup_oom = entry.cgroup.as_deref().map(read_oom_kills).unwrap_or(0);
+    if cgroup_oom > entry.oom_kills {
+        entry.oom_kills = cgroup_oom;
+        return Some(format!("killed by the kernel for exceeding {} MiB", entry.limits.max_memory_mb.unwrap_or(0)));
+    }
+
+    if let Ok(Some(status)) = child.try_wait() {
+        log::warn!("[MODULE] {} service exited ({})", name, status);
+        entry.child = None;
+        entry.usage = ModuleUsage {
+            running: false,
+            pid: None,
+            memory_mb: None,
+            cpu_percent: None,
+            ..entry.usage.clone()
+        };
+        return None;
+    }
+
+    let pids = process_tree(root);
+    let memory_mb = pids.iter().filter_map(|&p| rss_kb(p)).sum::<u64>() as f64 / 1024.0;
+    let ticks: u64 = pids.iter().filter_map(|&p| cpu_ticks(p)).sum();
+    let now = Instant::now();
+    let cpu_percent = entry.last_cpu.and_then(|(prev, at)| {
+        let secs = now.duration_since(at).as_secs_f64();
+        (secs > 0.0).then(|| ticks.saturating_sub(prev) as f64 / CLOCK_TICKS / secs * 100.0)
+    });
+    entry.last_cpu = Some((ticks, now));
+
+    let has_proc = Path::new("/proc").exists();
+    entry.usage.memory_mb = has_proc.then_some((memory_mb * 10.0).round() / 10.0);
+    entry.usage.cpu_percent = cpu_percent.map(|c| (c * 10.0).round() / 10.0);
+
+    if let Some(max) = entry.limits.max_memory_mb
+        && has_proc
+        && memory_mb > max as f64
+    {
+        return Some(format!("using {:.0} MiB, over its {} MiB limit", memory_mb, max));
+    }
+
+    if let (Some(max), Some(cpu)) = (entry.limits.max_cpu_percent, cpu_percent) {
+        if cpu > max {
+            entry.cpu_strikes += 1;
+            if entry.cpu_strikes >= CPU_STRIKES {
+                return Some(format!("using {:.0}% CPU, over its {}% limit", cpu, max));
+            }
+        } else {
+            entry.cpu_strikes = 0;
+        }
+    }
+    None
+}
+
+/// Backoff before the restart that follows `streak` earlier ones in a row
+fn restart_delay(streak: u32) -> Duration {
+    RESTART_BACKOFF_BASE
+        .saturating_mul(2u32.saturating_pow(streak))
+        .min(RESTART_BACKOFF_MAX)
+}
+
+/// Record a limit violation and schedule the respawn. Returns the process to
+/// kill, which the caller does after releasing the supervisor lock.
+fn schedule_restart(name: &str, entry: &mut Supervised, reason: String) -> Option<Child> {
+    let delay = restart_delay(entry.restart_streak);
+    log::warn!("[MODULE] {} service {} — restarting in {}s", name, reason, delay.as_secs());
+
+    entry.usage.limit_restarts += 1;
+    entry.usage.last_limit_violation = Some(format!("{}: {}", chrono::Utc::now().to_rfc3339(), reason));
+    entry.usage.running = false;
+    entry.usage.pid = None;
+    entry.usage.memory_mb = None;
+    entry.usage.cpu_percent = None;
+    entry.last_cpu = None;
+    entry.cpu_strikes = 0;
+    entry.restart_streak += 1;
+    entry.respawn_at = Some(Instant::now() + delay);
+    entry.child.take()
+}
+
+/// Kill a service's process tree
+fn kill_tree(mut child: Child) {
+    for pid in process_tree(child.id()).into_iter().skip(1) {
+        let _ = std::process::Command::new("kill").args(["-9", &pid.to_string()]).output();
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Put a respawned process back under supervision. If the service was
+/// re-registered by `spawn` while this one started, the newer one wins.
+fn install_respawned(respawn: Respawn, child: Option<Child>) {
+    let cgroup = child.as_ref().and_then(|c| apply_cgroup(&respawn.name, c.id(), &respawn.limits));
+    let mut guard = SUPERVISED.lock().unwrap();
+    let entry = guard
+        .as_mut()
+        .and_then(|map| map.get_mut(&respawn.name))
+        .filter(|entry| Arc::ptr_eq(&entry.spawn, &respawn.spawn));
+    match (entry, child) {
+        (Some(entry), Some(child)) => {
+            entry.oom_kills = cgroup.as_deref().map(read_oom_kills).unwrap_or(0);
+            entry.cgroup = cgroup;
+            entry.usage.running = true;
+            entry.usage.pid = Some(child.id());
+            entry.started_at = Instant::now();
+            entry.child = Some(child);
+        }
+        (Some(_), None) => {
+            log::error!("[MODULE] Failed to restart {} service", respawn.name);
+        }
+        (None, Some(child)) => {
+            drop(guard);
+            log::info!("[MODULE] {} service was restarted elsewhere meanwhile", respawn.name);
+            kill_tree(child);
+        }
+        (None, None) => {}
+    }
+}
+
+/// Best effort: put the service in its own cgroup v2 group with the limits applied.
+/// Returns the group directory, or None when cgroups aren't usable here.
+fn apply_cgroup(name: &str, pid: u32, limits: &ResourceLimits) -> Option<PathBuf> {
+    if limits.is_empty() || !Path::new("/sys/fs/cgroup/cgroup.controllers").exists() {
+        return None;
+    }
+    let parent = Path::new(CGROUP_PARENT);
+    let dir = parent.join(name);
+    let result = (|| -> std::io::Result<()> {
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(parent.join("cgroup.subtree_control"), "+memory +cpu")?;
+        if let Some(mb) = limits.max_memory_mb {
+            std::fs::write(dir.join("memory.max"), (mb * 1024 * 1024).to_string())?;
+        }
+        if let Some(pct) = limits.max_cpu_percent {
+            const PERIOD_US: u64 = 100_000;
+            let quota = ((pct / 100.0) * PERIOD_US as f64).max(1000.0) as u64;
+            std::fs::write(dir.join("cpu.max"), format!("{} {}", quota, PERIOD_US))?;
+        }
+        std::fs::write(dir.join("cgroup.procs"), pid.to_string())
+    })();
+    match result {
+        Ok(()) => {
+            log::info!("[MODULE] {} limits enforced by cgroup {}", name, dir.display());
+            Some(dir)
+        }
+        Err(e) => {
+            log::debug!("[MODULE] cgroup limits unavailable for {} ({}), monitoring instead", name, e);
+            None
+        }
+    }
+}
+
+fn read_oom_kills(cgroup: &Path) -> u64 {
+    std::fs::read_to_string(cgroup.join("memory.events"))
+        .ok()
+        .and_then(|s| {
+            s.lines()
+                .find_map(|l| l.strip_prefix("oom_kill "))
+                .and_then(|n| n.trim().parse().ok())
+        })
+        .unwrap_or(0)
+}
+
+/// Fields of `/proc/<pid>/stat` after the command name (state is index 0)
+fn stat_fields(pid: u32) -> Option<Vec<String>> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // The command name may contain spaces, so split after its closing paren
+    let rest = &stat[stat.rfind(')')? + 1..];
+    Some(rest.split_whitespace().map(str::to_string).collect())
+}
+
+/// `root` followed by all of its descendants
+fn process_tree(root: u32) -> Vec<u32> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    if let Ok(entries) = std::fs::read_dir("/proc") {
+        for entry in entries.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse::<u32>().ok()) else {
+                continue;
+            };
+            if let Some(ppid) = stat_fields(pid).and_then(|f| f.get(1)?.parse().ok()) {
+                children.entry(ppid).or_default().push(pid);
+            }
+        }
+    }
+    let mut tree = vec![root];
+    let mut i = 0;
+    while i < tree.len() {
+        if let Some(kids) = children.get(&tree[i]) {
+            tree.extend(kids);
+        }
+        i += 1;
+    }
+    tree
+}
+
+fn rss_kb(pid: u32) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    status
+        .lines()
+        .find_map(|l| l.strip_prefix("VmRSS:"))?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// utime + stime of a process, in clock ticks
+fn cpu_ticks(pid: u32) -> Option<u64> {
+    let fields = stat_fields(pid)?;
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(utime + stime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_delay_doubles_up_to_the_cap() {
+        assert_eq!(restart_delay(0), RESTART_BACKOFF_BASE);
+        assert_eq!(restart_delay(1), RESTART_BACKOFF_BASE * 2);
+        assert_eq!(restart_delay(3), RESTART_BACKOFF_BASE * 8);
+        assert_eq!(restart_delay(10), RESTART_BACKOFF_MAX);
+        assert_eq!(restart_delay(u32::MAX), RESTART_BACKOFF_MAX);
+    }
+
+    #[test]
+    fn test_process_tree_includes_children() {
+        if !Path::new("/proc").exists() {
+            return;
+        }
+        let mut child = std::process::Command::new("sh")
+            .args(["-c", "sleep 5 & wait"])
+            .spawn()
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(200));
+
+        let tree = process_tree(child.id());
+        assert_eq!(tree[0], child.id());
+        assert!(tree.len() >= 2, "sleep should be a child of sh: {:?}", tree);
+        assert!(rss_kb(child.id()).is_some());
+        assert!(cpu_ticks(child.id()).is_some());
+
+        for pid in tree.iter().skip(1) {
+            let _ = std::process::Command::new("kill").args(["-9", &pid.to_string()]).output();
+        }
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
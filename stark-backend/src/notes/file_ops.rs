@@ -25,14 +25,42 @@ pub fn slugify(title: &str) -> String {
         .join("-")
 }
 
-/// Write a note file (creates parent directories as needed)
+/// Write a note file atomically (creates parent directories as needed).
+/// Content goes to a temp file in the same directory which is then renamed
+/// over the target, so a failed write never leaves a truncated note behind.
 pub fn write_note(path: &Path, content: &str) -> io::Result<()> {
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
+    write_note_with(path, |file| file.write_all(content.as_bytes()))
+}
+
+/// Atomic write with a custom writer (lets tests inject failures)
+fn write_note_with(
+    path: &Path,
+    write: impl FnOnce(&mut fs::File) -> io::Result<()>,
+) -> io::Result<()> {
+    let parent = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(parent)?;
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "note path has no file name"))?;
+    // Hidden and without a .md extension, so list_notes never picks it up
+    let tmp_path = parent.join(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+
+    let result = (|| {
+        let mut file = fs::File::create(&tmp_path)?;
+        write(&mut file)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
     }
-    let mut file = fs::File::create(path)?;
-    file.write_all(content.as_bytes())?;
-    Ok(())
+    result
 }
 
 /// Read a note file, returning empty string if not found
@@ -129,6 +157,31 @@ mod tests {
         assert!(content.contains("Content here"));
     }
 
+    #[test]
+    fn test_write_note_failure_leaves_no_partial_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("note.md");
+
+        // Simulate the disk filling up halfway through a new note
+        let result = write_note_with(&path, |file| {
+            file.write_all(b"---\ntitle: Half")?;
+            Err(io::Error::other("No space left on device"))
+        });
+        assert!(result.is_err());
+        assert!(!path.exists());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0, "temp file left behind");
+
+        // A failed overwrite keeps the previous content intact
+        write_note(&path, "original content").unwrap();
+        let result = write_note_with(&path, |file| {
+            file.write_all(b"trunc")?;
+            Err(io::Error::other("No space left on device"))
+        });
+        assert!(result.is_err());
+        assert_eq!(read_note(&path).unwrap(), "original content");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
     #[test]
     fn test_read_note_not_found() {
         let dir = tempdir().unwrap();
@@ -16,6 +16,8 @@ pub struct NoteFrontmatter {
     pub tags: Vec<String>,
     pub aliases: Vec<String>,
     pub note_type: String, // note, idea, decision, log, reflection, todo
+    /// Any other scalar `key: value` fields (e.g. `status: draft`), keys lowercased
+    pub extra: Vec<(String, String)>,
 }
 
 /// A fully parsed note (frontmatter + body)
@@ -115,7 +117,12 @@ fn parse_frontmatter(yaml: &str) -> NoteFrontmatter {
                         fm.aliases = parse_inline_list(value);
                     }
                 }
-                _ => {}
+                _ => {
+                    // Skip nested keys and block values; only flat scalars are indexed
+                    if !line.starts_with([' ', '\t']) && !key.is_empty() && !value.is_empty() {
+                        fm.extra.push((key.to_lowercase(), unquote(value)));
+                    }
+                }
             }
         }
     }
@@ -0,0 +1,117 @@
+//! Wikilink resolution and backlink graph types
+//!
+//! Links are stored as written (normalized) and resolved at query time, so a
+//! link to a note that doesn't exist yet starts resolving once it is created,
+//! and links to a deleted note show up as broken.
+
+use super::file_ops::slugify;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Strip display text (`[[target|label]]`) and heading/block refs (`[[target#h]]`)
+pub fn normalize_link(link: &str) -> String {
+    let target = link.split('|').next().unwrap_or(link);
+    let target = target.split(['#', '^']).next().unwrap_or(target);
+    target.trim().to_string()
+}
+
+/// Resolves wikilink text to note paths using file paths, filenames, titles and aliases
+pub struct LinkResolver {
+    by_path: HashMap<String, String>,
+    by_slug: HashMap<String, String>,
+    by_name: HashMap<String, String>,
+}
+
+impl LinkResolver {
+    /// Build from `(file_path, title, aliases)` for every indexed note
+    pub fn new(notes: &[(String, String, Vec<String>)]) -> Self {
+        let mut by_path = HashMap::new();
+        let mut by_slug = HashMap::new();
+        let mut by_name = HashMap::new();
+
+        for (path, title, aliases) in notes {
+            let lower = path.to_lowercase();
+            by_path.insert(lower.trim_end_matches(".md").to_string(), path.clone());
+
+            let stem = lower.rsplit('/').next().unwrap_or(&lower).trim_end_matches(".md");
+            by_slug.entry(slugify(stem)).or_insert_with(|| path.clone());
+
+            for name in std::iter::once(title).chain(aliases.iter()) {
+                if !name.trim().is_empty() {
+                    by_name.entry(name.trim().to_lowercase()).or_insert_with(|| path.clone());
+                }
+            }
+        }
+
+        Self { by_path, by_slug, by_name }
+    }
+
+    /// Resolve link text to a note path. Tries an explicit path, then the
+    /// filename (Obsidian's default), then titles and aliases.
+    pub fn resolve(&self, link: &str) -> Option<&str> {
+        let link = normalize_link(link);
+        if link.is_empty() {
+            return None;
+        }
+        let lower = link.to_lowercase();
+        self.by_path
+            .get(lower.trim_end_matches(".md"))
+            .or_else(|| self.by_slug.get(&slugify(lower.trim_end_matches(".md"))))
+            .or_else(|| self.by_name.get(&lower))
+            .map(|s| s.as_str())
+    }
+}
+
+/// An outbound link from a note
+#[derive(Debug, Clone, Serialize)]
+pub struct OutboundLink {
+    /// Link text as written (without display text or heading)
+    pub link: String,
+    /// Resolved note path, if the target exists
+    pub file_path: Option<String>,
+    pub broken: bool,
+}
+
+/// A note linking to the queried note
+#[derive(Debug, Clone, Serialize)]
+pub struct InboundLink {
+    pub file_path: String,
+    pub title: String,
+    /// Link text used by the source note
+    pub link: String,
+}
+
+/// Inbound and outbound links for a single note
+#[derive(Debug, Clone, Serialize)]
+pub struct NoteLinkGraph {
+    pub file_path: String,
+    pub outbound: Vec<OutboundLink>,
+    pub inbound: Vec<InboundLink>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_link() {
+        assert_eq!(normalize_link("Gas Strategy|gas"), "Gas Strategy");
+        assert_eq!(normalize_link("Gas Strategy#Fast mode"), "Gas Strategy");
+        assert_eq!(normalize_link(" ideas/plan "), "ideas/plan");
+    }
+
+    #[test]
+    fn test_resolver() {
+        let resolver = LinkResolver::new(&[
+            ("gas-strategy.md".to_string(), "Gas Strategy".to_string(), vec![]),
+            ("ideas/plan.md".to_string(), "Q3 Roadmap".to_string(), vec!["Roadmap".to_string()]),
+        ]);
+        assert_eq!(resolver.resolve("Gas Strategy"), Some("gas-strategy.md"));
+        assert_eq!(resolver.resolve("gas-strategy"), Some("gas-strategy.md"));
+        assert_eq!(resolver.resolve("ideas/plan"), Some("ideas/plan.md"));
+        assert_eq!(resolver.resolve("plan"), Some("ideas/plan.md"));
+        assert_eq!(resolver.resolve("q3 roadmap"), Some("ideas/plan.md"));
+        assert_eq!(resolver.resolve("Roadmap|see here"), Some("ideas/plan.md"));
+        assert_eq!(resolver.resolve("Missing Note"), None);
+    }
+}
@@ -6,6 +6,7 @@
 
 pub mod file_ops;
 pub mod frontmatter;
+pub mod links;
 pub mod store;
 
-pub use store::NoteStore;
+pub use store::{NoteSearchFilter, NoteStore};
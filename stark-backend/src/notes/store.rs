@@ -3,6 +3,7 @@
 //! Manages a separate `.notes.db` SQLite database with an FTS5 virtual table
 //! indexing file_path, title, tags, and content for full-text search.
 
+use super::links::{normalize_link, InboundLink, LinkResolver, NoteLinkGraph, OutboundLink};
 use super::{file_ops, frontmatter};
 use crate::disk_quota::DiskQuotaManager;
 use rusqlite::{params, Connection, Result as SqliteResult};
@@ -19,6 +20,29 @@ pub struct NoteSearchResult {
     pub score: f64,
 }
 
+/// Optional filters applied to a full-text search
+#[derive(Debug, Clone, Default)]
+pub struct NoteSearchFilter {
+    /// Only notes carrying this tag (frontmatter or inline, case-insensitive)
+    pub tag: Option<String>,
+    /// Only notes with this frontmatter `type` (note, idea, decision, ...)
+    pub note_type: Option<String>,
+}
+
+/// A note matched by a frontmatter query
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct NoteFieldMatch {
+    pub file_path: String,
+    pub title: String,
+    pub tags: Vec<String>,
+    /// All indexed frontmatter fields (title, type, date, updated, custom keys)
+    pub fields: std::collections::BTreeMap<String, String>,
+}
+
+/// Snippet highlight markers; the frontend splits on these to render matches
+pub const HIGHLIGHT_START: &str = ">>>";
+pub const HIGHLIGHT_END: &str = "<<<";
+
 /// NoteStore wrapping SQLite FTS5 for markdown note indexing
 pub struct NoteStore {
     notes_dir: PathBuf,
@@ -33,6 +57,41 @@ impl NoteStore {
 
         let conn = Connection::open(db_path)?;
 
+        // The index is rebuilt from disk on startup, so an older layout
+        // (without note_type) can simply be dropped and recreated
+        let has_note_type = conn
+            .prepare("SELECT note_type FROM notes_fts LIMIT 0")
+            .is_ok();
+        if !has_note_type {
+            conn.execute("DROP TABLE IF EXISTS notes_fts", [])?;
+        }
+
+        // Frontmatter fields, one row per (note, key, value); tags are stored
+        // as repeated `tag` rows so they can be matched exactly
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS note_fields (
+                file_path TEXT NOT NULL,
+                key TEXT NOT NULL,
+                value TEXT NOT NULL,
+                PRIMARY KEY (file_path, key, value)
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_note_fields_key_value ON note_fields(key, value COLLATE NOCASE)",
+            [],
+        )?;
+
+        // [[wikilinks]] per note, stored normalized and resolved at query time
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS note_links (
+                source TEXT NOT NULL,
+                link TEXT NOT NULL,
+                PRIMARY KEY (source, link)
+            )",
+            [],
+        )?;
+
         // Create FTS5 table with richer columns than memory
         conn.execute(
             "CREATE VIRTUAL TABLE IF NOT EXISTS notes_fts USING fts5(
@@ -40,6 +99,7 @@ impl NoteStore {
                 title,
                 tags,
                 content,
+                note_type UNINDEXED,
                 tokenize='porter'
             )",
             [],
@@ -73,6 +133,8 @@ impl NoteStore {
         let conn = self.conn.lock().unwrap();
 
         conn.execute("DELETE FROM notes_fts", [])?;
+        conn.execute("DELETE FROM note_fields", [])?;
+        conn.execute("DELETE FROM note_links", [])?;
 
         let files = file_ops::list_notes(&self.notes_dir).unwrap_or_default();
 
@@ -96,9 +158,11 @@ impl NoteStore {
                     let tags = parsed.all_tags.join(", ");
 
                     conn.execute(
-                        "INSERT INTO notes_fts (file_path, title, tags, content) VALUES (?1, ?2, ?3, ?4)",
-                        params![rel_path, title, tags, parsed.body],
+                        "INSERT INTO notes_fts (file_path, title, tags, content, note_type) VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![rel_path, title, tags, parsed.body, parsed.frontmatter.note_type],
                     )?;
+                    index_fields(&conn, &rel_path, &title, &parsed)?;
+                index_links(&conn, &rel_path, &parsed)?;
                     count += 1;
                 }
             }
@@ -110,6 +174,18 @@ impl NoteStore {
 
     /// Full-text search across notes
     pub fn search(&self, query: &str, limit: i32) -> SqliteResult<Vec<NoteSearchResult>> {
+        self.search_filtered(query, &NoteSearchFilter::default(), limit)
+    }
+
+    /// Full-text search with tag / note type filters, ranked by relevance.
+    /// Snippets wrap matched terms in `HIGHLIGHT_START` / `HIGHLIGHT_END`;
+    /// title matches rank above tag matches, which rank above body matches.
+    pub fn search_filtered(
+        &self,
+        query: &str,
+        filter: &NoteSearchFilter,
+        limit: i32,
+    ) -> SqliteResult<Vec<NoteSearchResult>> {
         let conn = self.conn.lock().unwrap();
 
         let escaped_query = escape_fts5_query(query);
@@ -117,31 +193,168 @@ impl NoteStore {
             return Ok(vec![]);
         }
 
+        // Tags are stored as "a, b, c"; pad with separators for an exact match
+        let tag = filter.tag.as_deref().map(|t| format!("%, {}, %", t.trim().to_lowercase()));
+        let note_type = filter.note_type.as_deref().map(|t| t.trim().to_lowercase());
+
         let mut stmt = conn.prepare(
             "SELECT file_path, title, tags,
-                    snippet(notes_fts, 3, '>>>', '<<<', '...', 64) as snippet,
-                    bm25(notes_fts) as score
+                    snippet(notes_fts, 3, ?3, ?4, '...', 64) as snippet,
+                    bm25(notes_fts, 1.0, 10.0, 5.0, 1.0) as score
              FROM notes_fts
              WHERE notes_fts MATCH ?1
+               AND (?5 IS NULL OR (', ' || lower(tags) || ', ') LIKE ?5)
+               AND (?6 IS NULL OR lower(note_type) = ?6)
              ORDER BY score
              LIMIT ?2",
         )?;
 
         let results = stmt
-            .query_map(params![escaped_query, limit], |row| {
-                Ok(NoteSearchResult {
-                    file_path: row.get(0)?,
-                    title: row.get(1)?,
-                    tags: row.get(2)?,
-                    snippet: row.get(3)?,
-                    score: row.get(4)?,
+            .query_map(
+                params![escaped_query, limit, HIGHLIGHT_START, HIGHLIGHT_END, tag, note_type],
+                |row| {
+                    Ok(NoteSearchResult {
+                        file_path: row.get(0)?,
+                        title: row.get(1)?,
+                        tags: row.get(2)?,
+                        snippet: row.get(3)?,
+                        score: row.get(4)?,
+                    })
+                },
+            )?
+            .collect::<SqliteResult<Vec<_>>>()?;
+
+        Ok(results)
+    }
+
+    /// List notes whose frontmatter matches every `(key, value)` condition
+    /// (case-insensitive). Use the key `tag` to match one of a note's tags.
+    /// With no conditions, all notes are returned.
+    pub fn query_fields(
+        &self,
+        conditions: &[(String, String)],
+        limit: i32,
+    ) -> SqliteResult<Vec<NoteFieldMatch>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut sql_params: Vec<String> = Vec::new();
+        let sql = if conditions.is_empty() {
+            "SELECT DISTINCT file_path FROM note_fields".to_string()
+        } else {
+            conditions
+                .iter()
+                .map(|(key, value)| {
+                    sql_params.push(key.trim().to_lowercase());
+                    sql_params.push(value.trim().to_string());
+                    format!(
+                        "SELECT file_path FROM note_fields WHERE key = ?{} AND value = ?{} COLLATE NOCASE",
+                        sql_params.len() - 1,
+                        sql_params.len()
+                    )
                 })
-            })?
+                .collect::<Vec<_>>()
+                .join(" INTERSECT ")
+        };
+        let sql = format!("{} ORDER BY file_path LIMIT {}", sql, limit.max(0));
+
+        let paths: Vec<String> = conn
+            .prepare(&sql)?
+            .query_map(rusqlite::params_from_iter(sql_params.iter()), |row| row.get(0))?
             .collect::<SqliteResult<Vec<_>>>()?;
 
+        let mut fields_stmt =
+            conn.prepare("SELECT key, value FROM note_fields WHERE file_path = ?1 ORDER BY key, value")?;
+        let mut results = Vec::with_capacity(paths.len());
+        for file_path in paths {
+            let rows = fields_stmt
+                .query_map(params![file_path], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+                .collect::<SqliteResult<Vec<_>>>()?;
+
+            let mut tags = Vec::new();
+            let mut fields = std::collections::BTreeMap::new();
+            for (key, value) in rows {
+                match key.as_str() {
+                    "tag" => tags.push(value),
+                    "alias" => {}
+                    _ => {
+                        fields.insert(key, value);
+                    }
+                }
+            }
+
+            results.push(NoteFieldMatch {
+                title: fields.get("title").cloned().unwrap_or_default(),
+                file_path,
+                tags,
+                fields,
+            });
+        }
+
         Ok(results)
     }
 
+    /// Inbound and outbound [[wikilinks]] for a note. Outbound links whose
+    /// target doesn't exist are marked broken.
+    pub fn link_graph(&self, rel_path: &str) -> SqliteResult<NoteLinkGraph> {
+        let conn = self.conn.lock().unwrap();
+
+        // (file_path, title, aliases) for every indexed note
+        let mut notes: std::collections::BTreeMap<String, (String, Vec<String>)> =
+            std::collections::BTreeMap::new();
+        let mut stmt = conn.prepare(
+            "SELECT file_path, key, value FROM note_fields WHERE key IN ('title', 'alias')",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+        })?;
+        for row in rows {
+            let (path, key, value) = row?;
+            let entry = notes.entry(path).or_default();
+            if key == "title" {
+                entry.0 = value;
+            } else {
+                entry.1.push(value);
+            }
+        }
+        let titles: std::collections::HashMap<String, String> =
+            notes.iter().map(|(p, (t, _))| (p.clone(), t.clone())).collect();
+        let resolver = LinkResolver::new(
+            &notes
+                .into_iter()
+                .map(|(path, (title, aliases))| (path, title, aliases))
+                .collect::<Vec<_>>(),
+        );
+
+        let mut outbound = Vec::new();
+        let mut inbound = Vec::new();
+        let mut stmt = conn.prepare("SELECT source, link FROM note_links ORDER BY source, link")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+        for row in rows {
+            let (source, link) = row?;
+            let target = resolver.resolve(&link);
+            if source == rel_path {
+                outbound.push(OutboundLink {
+                    link: link.clone(),
+                    file_path: target.map(String::from),
+                    broken: target.is_none(),
+                });
+            }
+            if target == Some(rel_path) && source != rel_path {
+                inbound.push(InboundLink {
+                    title: titles.get(&source).cloned().unwrap_or_default(),
+                    file_path: source,
+                    link,
+                });
+            }
+        }
+
+        Ok(NoteLinkGraph {
+            file_path: rel_path.to_string(),
+            outbound,
+            inbound,
+        })
+    }
+
     /// Search notes by tag
     pub fn search_by_tag(&self, tag: &str, limit: i32) -> SqliteResult<Vec<NoteSearchResult>> {
         let conn = self.conn.lock().unwrap();
@@ -278,6 +491,13 @@ impl NoteStore {
         file_ops::write_note(&full_path, &full_content)
             .map_err(|e| format!("Failed to write note: {}", e))?;
 
+        // Update index; a note that can't be indexed is removed again so the
+        // file and index stay in sync
+        if let Err(e) = self.index_file(&full_path) {
+            let _ = std::fs::remove_file(&full_path);
+            return Err(format!("Failed to index note: {}", e));
+        }
+
         // Record write with disk quota
         if let Ok(guard) = self.disk_quota.lock() {
             if let Some(ref dq) = *guard {
@@ -285,9 +505,6 @@ impl NoteStore {
             }
         }
 
-        // Update index
-        self.index_file(&full_path).ok();
-
         Ok(rel_path)
     }
 
@@ -327,14 +544,18 @@ impl NoteStore {
         file_ops::write_note(&full_path, &full)
             .map_err(|e| format!("Failed to write note: {}", e))?;
 
+        // Restore the previous version if the index can't be updated
+        if let Err(e) = self.index_file(&full_path) {
+            let _ = file_ops::write_note(&full_path, &existing);
+            return Err(format!("Failed to index note: {}", e));
+        }
+
         if let Ok(guard) = self.disk_quota.lock() {
             if let Some(ref dq) = *guard {
                 dq.record_write(full.len() as u64);
             }
         }
 
-        self.index_file(&full_path).ok();
-
         Ok(())
     }
 
@@ -367,6 +588,14 @@ impl NoteStore {
             "DELETE FROM notes_fts WHERE file_path = ?1",
             rusqlite::params![rel_path],
         );
+        let _ = conn.execute(
+            "DELETE FROM note_fields WHERE file_path = ?1",
+            rusqlite::params![rel_path],
+        );
+        let _ = conn.execute(
+            "DELETE FROM note_links WHERE source = ?1",
+            rusqlite::params![rel_path],
+        );
 
         // Clean up empty parent directories (up to notes_dir)
         let mut parent = canonical_path.parent();
@@ -395,8 +624,10 @@ impl NoteStore {
     }
 
     /// Index or update a single file in the FTS index
+    /// All statements run in one transaction, so a failure leaves the previous entry intact.
     fn index_file(&self, file_path: &PathBuf) -> SqliteResult<()> {
         let conn = self.conn.lock().unwrap();
+        let tx = conn.unchecked_transaction()?;
 
         if let Some(rel_path) = file_ops::relative_path(&self.notes_dir, file_path) {
             if let Ok(content) = file_ops::read_note(file_path) {
@@ -411,19 +642,73 @@ impl NoteStore {
                 };
                 let tags = parsed.all_tags.join(", ");
 
-                conn.execute(
+                tx.execute(
                     "DELETE FROM notes_fts WHERE file_path = ?1",
                     params![rel_path],
                 )?;
-                conn.execute(
-                    "INSERT INTO notes_fts (file_path, title, tags, content) VALUES (?1, ?2, ?3, ?4)",
-                    params![rel_path, title, tags, parsed.body],
+                tx.execute(
+                    "INSERT INTO notes_fts (file_path, title, tags, content, note_type) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![rel_path, title, tags, parsed.body, parsed.frontmatter.note_type],
                 )?;
+                index_fields(&tx, &rel_path, &title, &parsed)?;
+                index_links(&tx, &rel_path, &parsed)?;
             }
         }
 
-        Ok(())
+        tx.commit()
+    }
+}
+
+/// Replace a note's rows in `note_fields` with its current frontmatter.
+/// Notes without frontmatter still get `title` (from the filename) and `type`.
+fn index_fields(
+    conn: &Connection,
+    rel_path: &str,
+    title: &str,
+    parsed: &frontmatter::ParsedNote,
+) -> SqliteResult<()> {
+    conn.execute("DELETE FROM note_fields WHERE file_path = ?1", params![rel_path])?;
+
+    let fm = &parsed.frontmatter;
+    let mut fields: Vec<(&str, &str)> = vec![("title", title), ("type", &fm.note_type)];
+    if let Some(ref date) = fm.date {
+        fields.push(("date", date));
+    }
+    if let Some(ref updated) = fm.updated {
+        fields.push(("updated", updated));
+    }
+    for tag in &parsed.all_tags {
+        fields.push(("tag", tag));
+    }
+    for alias in &fm.aliases {
+        fields.push(("alias", alias));
+    }
+    for (key, value) in &fm.extra {
+        fields.push((key, value));
+    }
+
+    let mut stmt = conn.prepare_cached(
+        "INSERT OR IGNORE INTO note_fields (file_path, key, value) VALUES (?1, ?2, ?3)",
+    )?;
+    for (key, value) in fields {
+        stmt.execute(params![rel_path, key, value])?;
+    }
+    Ok(())
+}
+
+/// Replace a note's rows in `note_links` with its current wikilinks
+fn index_links(conn: &Connection, rel_path: &str, parsed: &frontmatter::ParsedNote) -> SqliteResult<()> {
+    conn.execute("DELETE FROM note_links WHERE source = ?1", params![rel_path])?;
+
+    let mut stmt =
+        conn.prepare_cached("INSERT OR IGNORE INTO note_links (source, link) VALUES (?1, ?2)")?;
+    for link in &parsed.wikilinks {
+        let link = normalize_link(link);
+        if !link.is_empty() {
+            stmt.execute(params![rel_path, link])?;
+        }
     }
+    Ok(())
 }
 
 /// Escape special characters for FTS5 query
@@ -562,4 +847,161 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("already exists"));
     }
+
+    #[test]
+    fn test_note_store_search_snippets_and_filters() {
+        let dir = tempdir().unwrap();
+        let notes_dir = dir.path().join("notes");
+        let db_path = dir.path().join("test.db");
+
+        let store =
+            NoteStore::new(notes_dir.clone(), db_path.to_str().unwrap()).expect("Failed to create store");
+
+        store
+            .create_note(
+                "Gas Strategy",
+                "Use the fast strategy when the mempool is congested.",
+                &["crypto".to_string()],
+                &[],
+                "decision",
+                None,
+            )
+            .unwrap();
+        store
+            .create_note(
+                "Grocery List",
+                "Buy apples, coffee and a congested-looking cabbage.",
+                &["home".to_string()],
+                &[],
+                "todo",
+                None,
+            )
+            .unwrap();
+        store
+            .create_note("Mempool Notes", "Nothing relevant here.", &["crypto".to_string()], &[], "note", None)
+            .unwrap();
+
+        let results = store.search("congested", 10).unwrap();
+        assert_eq!(results.len(), 2);
+        let gas = results.iter().find(|r| r.title == "Gas Strategy").unwrap();
+        assert!(
+            gas.snippet.contains(">>>congested<<<"),
+            "snippet should highlight the match: {}",
+            gas.snippet
+        );
+
+        // Tag filter is exact: "crypto" only
+        let filter = NoteSearchFilter { tag: Some("Crypto".to_string()), note_type: None };
+        let results = store.search_filtered("congested", &filter, 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Gas Strategy");
+
+        // Frontmatter type filter
+        let filter = NoteSearchFilter { tag: None, note_type: Some("todo".to_string()) };
+        let results = store.search_filtered("congested", &filter, 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "Grocery List");
+
+        // Title matches outrank body matches
+        let results = store.search("mempool", 10).unwrap();
+        assert_eq!(results[0].title, "Mempool Notes");
+
+        // Limit is honored
+        assert_eq!(store.search("congested", 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_note_store_query_fields() {
+        let dir = tempdir().unwrap();
+        let notes_dir = dir.path().join("notes");
+        let db_path = dir.path().join("test.db");
+        std::fs::create_dir_all(&notes_dir).unwrap();
+
+        // Hand-written notes with custom frontmatter, plus one without any
+        std::fs::write(
+            notes_dir.join("draft-post.md"),
+            "---\ntitle: Draft Post\ntags: [blog, crypto]\nstatus: draft\n---\n\nBody",
+        )
+        .unwrap();
+        std::fs::write(
+            notes_dir.join("published-post.md"),
+            "---\ntitle: Published Post\ntags: [blog]\nstatus: \"Published\"\n---\n\nBody",
+        )
+        .unwrap();
+        std::fs::write(notes_dir.join("loose.md"), "Just some text with #blog tag").unwrap();
+
+        let store =
+            NoteStore::new(notes_dir.clone(), db_path.to_str().unwrap()).expect("Failed to create store");
+
+        let cond = |k: &str, v: &str| (k.to_string(), v.to_string());
+
+        let blog = store.query_fields(&[cond("tag", "blog")], 50).unwrap();
+        assert_eq!(blog.len(), 3);
+
+        let drafts = store.query_fields(&[cond("tag", "blog"), cond("status", "draft")], 50).unwrap();
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(drafts[0].title, "Draft Post");
+        assert_eq!(drafts[0].fields.get("status").map(String::as_str), Some("draft"));
+        assert!(drafts[0].tags.contains(&"crypto".to_string()));
+
+        // Values match case-insensitively and unquoted
+        let published = store.query_fields(&[cond("STATUS", "published")], 50).unwrap();
+        assert_eq!(published.len(), 1);
+
+        // Notes without frontmatter fall back to filename title and default type
+        let loose = store.query_fields(&[cond("title", "loose")], 50).unwrap();
+        assert_eq!(loose.len(), 1);
+        assert_eq!(loose[0].fields.get("type").map(String::as_str), Some("note"));
+
+        // Deleting a note drops its fields
+        store.delete_note("draft-post.md").unwrap();
+        assert!(store.query_fields(&[cond("status", "draft")], 50).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_note_store_link_graph() {
+        let dir = tempdir().unwrap();
+        let notes_dir = dir.path().join("notes");
+        let db_path = dir.path().join("test.db");
+
+        let store =
+            NoteStore::new(notes_dir.clone(), db_path.to_str().unwrap()).expect("Failed to create store");
+
+        let hub = store
+            .create_note(
+                "Hub",
+                "See [[Gas Strategy]], [[gas-strategy#Fast|fast mode]] and [[Not Written Yet]].",
+                &[],
+                &[],
+                "note",
+                None,
+            )
+            .unwrap();
+        let gas = store
+            .create_note("Gas Strategy", "Back to [[Hub]].", &[], &[], "note", None)
+            .unwrap();
+
+        let graph = store.link_graph(&hub).unwrap();
+        assert_eq!(graph.outbound.len(), 3);
+        let broken: Vec<_> = graph.outbound.iter().filter(|l| l.broken).map(|l| l.link.as_str()).collect();
+        assert_eq!(broken, vec!["Not Written Yet"]);
+        assert_eq!(graph.inbound.len(), 1);
+        assert_eq!(graph.inbound[0].file_path, gas);
+
+        let graph = store.link_graph(&gas).unwrap();
+        // Both link spellings in the hub resolve to the gas note
+        assert_eq!(graph.inbound.len(), 2);
+        assert!(graph.inbound.iter().all(|l| l.file_path == hub));
+
+        // Creating the missing note fixes the broken link
+        store.create_note("Not Written Yet", "Now it is.", &[], &[], "note", None).unwrap();
+        assert!(store.link_graph(&hub).unwrap().outbound.iter().all(|l| !l.broken));
+
+        // Editing the source drops stale links; deleting a target breaks them
+        store.edit_note(&gas, "No links anymore.").unwrap();
+        assert!(store.link_graph(&hub).unwrap().inbound.is_empty());
+        store.delete_note(&gas).unwrap();
+        let graph = store.link_graph(&hub).unwrap();
+        assert_eq!(graph.outbound.iter().filter(|l| l.broken).count(), 2);
+    }
 }
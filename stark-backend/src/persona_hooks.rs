@@ -81,6 +81,7 @@ fn spawn_hook_session(
         force_safe_mode: safe_mode,
         platform_role_ids: vec![],
         chat_context: None,
+        thread_id: None,
     };
 
     log::info!(
@@ -63,6 +63,8 @@ pub struct Scheduler {
     /// Wallet provider for x402 payments in scheduled tasks (heartbeats, cron jobs)
     wallet_provider: Option<Arc<dyn wallet::WalletProvider>>,
     skill_registry: Option<Arc<crate::skills::SkillRegistry>>,
+    /// When a scheduled cloud backup was last started (limits attempts to one per interval)
+    last_auto_backup_attempt: Arc<std::sync::Mutex<Option<DateTime<Utc>>>>,
 }
 
 impl Scheduler {
@@ -83,6 +85,7 @@ impl Scheduler {
             config,
             wallet_provider,
             skill_registry,
+            last_auto_backup_attempt: Arc::new(std::sync::Mutex::new(None)),
         }
     }
 
@@ -143,6 +146,9 @@ impl Scheduler {
             log::error!("Error processing heartbeats: {}", e);
         }
 
+        // Push a scheduled cloud backup when one is due
+        self.process_auto_backup();
+
         // Run periodic cleanup tasks once per hour (at minute 0, within first poll window)
         let now = Local::now();
         if now.minute() == 0 && now.second() < self.config.poll_interval_secs as u32 {
@@ -150,6 +156,39 @@ impl Scheduler {
         }
     }
 
+    /// Start a cloud backup if STARK_AUTO_BACKUP_INTERVAL_HOURS has passed since the
+    /// last backup (manual or scheduled) and since the last scheduled attempt
+    fn process_auto_backup(&self) {
+        let Some(hours) = crate::config::auto_backup_interval_hours() else {
+            return;
+        };
+        let Some(wallet_provider) = self.wallet_provider.clone() else {
+            return;
+        };
+        let now = Utc::now();
+        let due_after = now - Duration::hours(hours);
+
+        let mut last_attempt = self.last_auto_backup_attempt.lock().unwrap();
+        if last_attempt.is_some_and(|at| at > due_after) {
+            return;
+        }
+        match self.db.get_last_keystore_backup_at(&wallet_provider.get_address()) {
+            Ok(Some(at)) if at > due_after => return,
+            Ok(_) => {}
+            Err(e) => {
+                log::error!("Scheduler: Failed to read last backup time: {}", e);
+                return;
+            }
+        }
+        *last_attempt = Some(now);
+        drop(last_attempt);
+
+        let db = Arc::clone(&self.db);
+        tokio::spawn(async move {
+            let _ = crate::backup::scheduled::run_scheduled_backup(&db, &wallet_provider).await;
+        });
+    }
+
     /// Run periodic cleanup tasks (called approximately once per hour)
     fn run_periodic_cleanup(&self) {
         // Cleanup old Twitter processed mentions (keep last 30 days)
@@ -267,6 +306,7 @@ impl Scheduler {
             force_safe_mode: false,
             platform_role_ids: vec![],
             chat_context: None,
+            thread_id: None,
         };
 
         // Execute with 10-minute timeout (same as cron default)
@@ -340,6 +380,7 @@ impl Scheduler {
             config: self.config.clone(),
             wallet_provider: self.wallet_provider.clone(),
             skill_registry: self.skill_registry.clone(),
+            last_auto_backup_attempt: Arc::clone(&self.last_auto_backup_attempt),
         }
     }
 
@@ -427,6 +468,7 @@ impl Scheduler {
             force_safe_mode: false,
             platform_role_ids: vec![],
             chat_context: None,
+            thread_id: None,
         };
 
         // Execute the job with timeout
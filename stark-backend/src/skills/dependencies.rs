@@ -0,0 +1,116 @@
+//! Skill dependency validation
+//!
+//! Skills declare the tools, binaries and API keys they need. Checking these
+//! when a skill is created catches a broken skill up front instead of letting
+//! it fail on first use.
+
+use super::types::SkillApiKey;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Unmet dependencies for a skill (all empty when satisfied)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SkillDependencyReport {
+    pub missing_tools: Vec<String>,
+    pub missing_binaries: Vec<String>,
+    pub missing_api_keys: Vec<String>,
+}
+
+impl SkillDependencyReport {
+    /// Check a skill's declared dependencies.
+    /// `has_tool` / `has_api_key` are lookups into the tool registry and the
+    /// configured API keys; `None` for `has_tool` skips the tool check.
+    pub fn check(
+        requires_tools: &[String],
+        requires_binaries: &[String],
+        requires_api_keys: &HashMap<String, SkillApiKey>,
+        has_tool: Option<&dyn Fn(&str) -> bool>,
+        has_api_key: &dyn Fn(&str) -> bool,
+    ) -> Self {
+        let missing_tools = match has_tool {
+            Some(has_tool) => requires_tools.iter().filter(|t| !has_tool(t)).cloned().collect(),
+            None => Vec::new(),
+        };
+
+        let missing_binaries = requires_binaries
+            .iter()
+            .filter(|bin| which::which(bin).is_err())
+            .cloned()
+            .collect();
+
+        let mut missing_api_keys: Vec<String> = requires_api_keys
+            .keys()
+            .filter(|k| !has_api_key(k))
+            .cloned()
+            .collect();
+        missing_api_keys.sort();
+
+        Self {
+            missing_tools,
+            missing_binaries,
+            missing_api_keys,
+        }
+    }
+
+    /// True when every declared dependency is available
+    pub fn is_satisfied(&self) -> bool {
+        self.missing_tools.is_empty() && self.missing_binaries.is_empty() && self.missing_api_keys.is_empty()
+    }
+
+    /// Whether skill creation should be refused. Unknown tools always block
+    /// (the skill can never run); missing binaries and API keys only block in
+    /// strict mode, since they can be installed or configured afterwards.
+    pub fn blocks_creation(&self, strict: bool) -> bool {
+        !self.missing_tools.is_empty() || (strict && !self.is_satisfied())
+    }
+
+    /// Human-readable list of the unmet dependencies
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.missing_tools.is_empty() {
+            parts.push(format!("missing tools: {}", self.missing_tools.join(", ")));
+        }
+        if !self.missing_binaries.is_empty() {
+            parts.push(format!("missing binaries: {}", self.missing_binaries.join(", ")));
+        }
+        if !self.missing_api_keys.is_empty() {
+            parts.push(format!("missing API keys: {}", self.missing_api_keys.join(", ")));
+        }
+        parts.join("; ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dependency_report() {
+        let tools = vec!["read_file".to_string(), "no_such_tool".to_string()];
+        let binaries = vec!["definitely-not-a-real-binary-xyz".to_string()];
+        let key = SkillApiKey { description: String::new(), secret: true };
+        let mut keys = HashMap::new();
+        keys.insert("GITHUB_TOKEN".to_string(), key.clone());
+        keys.insert("MOLTX_API_KEY".to_string(), key);
+
+        let has_tool = |t: &str| t == "read_file";
+        let report = SkillDependencyReport::check(
+            &tools,
+            &binaries,
+            &keys,
+            Some(&has_tool),
+            &|k| k == "GITHUB_TOKEN",
+        );
+        assert_eq!(report.missing_tools, vec!["no_such_tool"]);
+        assert_eq!(report.missing_binaries, vec!["definitely-not-a-real-binary-xyz"]);
+        assert_eq!(report.missing_api_keys, vec!["MOLTX_API_KEY"]);
+        assert!(report.blocks_creation(false));
+        assert!(report.summary().contains("missing tools: no_such_tool"));
+
+        // Without a tool registry, only binaries/keys are checked and they only block in strict mode
+        let report = SkillDependencyReport::check(&tools, &binaries, &keys, None, &|_| true);
+        assert!(report.missing_tools.is_empty());
+        assert!(!report.blocks_creation(false));
+        assert!(report.blocks_creation(true));
+    }
+}
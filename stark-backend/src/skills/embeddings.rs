@@ -138,9 +138,18 @@ pub async fn search_skills(
     // Generate query embedding
     let query_embedding = embedding_gen.generate(query).await?;
 
-    // Load all skill embeddings
-    let candidates = db.list_skill_embeddings()
-        .map_err(|e| format!("Failed to list skill embeddings: {}", e))?;
+    // Load skill embeddings, keeping only enabled skills so disabled ones
+    // don't take up top-K slots
+    let enabled: std::collections::HashSet<i64> = db.list_enabled_skills()
+        .map_err(|e| format!("Failed to list skills: {}", e))?
+        .into_iter()
+        .filter_map(|s| s.id)
+        .collect();
+    let candidates: Vec<(i64, Vec<f32>)> = db.list_skill_embeddings()
+        .map_err(|e| format!("Failed to list skill embeddings: {}", e))?
+        .into_iter()
+        .filter(|(id, _)| enabled.contains(id))
+        .collect();
 
     if candidates.is_empty() {
         return Ok(vec![]);
@@ -1,10 +1,14 @@
+pub mod dependencies;
 pub mod embeddings;
 pub mod loader;
 pub mod registry;
 pub mod types;
+pub mod validation;
 pub mod zip_parser;
 
+pub use dependencies::SkillDependencyReport;
 pub use loader::{load_skill_from_file, load_skills_from_directory, parse_skill_file};
 pub use registry::{create_default_registry, write_skill_folder, reconstruct_skill_md, reconstruct_skill_md_from_db, delete_skill_folder, BundledSkillInfo, SkillRegistry};
-pub use types::{DbSkill, DbSkillAbi, DbSkillFlow, DbSkillPreset, DbSkillScript, Skill, SkillArgument, SkillMetadata, SkillSource};
+pub use validation::SkillValidationReport;
+pub use types::{DbSkill, DbSkillAbi, DbSkillFlow, DbSkillPreset, DbSkillScript, DbSkillVersion, Skill, SkillArgument, SkillMetadata, SkillSource};
 pub use zip_parser::{parse_skill_md, parse_skill_zip, ParsedAbi, ParsedFlow, ParsedScript, ParsedSkill};
@@ -1,8 +1,14 @@
 use crate::db::Database;
-use crate::skills::types::{DbSkill, DbSkillFlow, DbSkillScript, Skill, SkillSource};
-use crate::skills::zip_parser::{parse_skill_md, parse_skill_zip, ParsedSkill};
+use crate::memory::EmbeddingGenerator;
+use crate::skills::dependencies::SkillDependencyReport;
+use crate::skills::embeddings::{search_skills, search_skills_text};
+use crate::skills::types::{DbSkill, DbSkillFlow, DbSkillScript, Skill, SkillApiKey, SkillSource};
+use crate::skills::zip_parser::{parse_skill_md, parse_skill_zip, ParsedScript, ParsedSkill};
 use crate::skills::types::{DbSkillAbi, DbSkillPreset};
+use crate::skills::validation::{self, SkillValidationReport};
+use crate::tools::ToolRegistry;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -22,11 +28,85 @@ pub struct SkillRegistry {
     db: Arc<Database>,
     /// Path to the runtime skills directory (disk-primary store)
     skills_dir: PathBuf,
+    /// Tool registry used to validate `requires_tools` (unchecked when None)
+    tool_registry: Option<Arc<ToolRegistry>>,
 }
 
 impl SkillRegistry {
     pub fn new(db: Arc<Database>, skills_dir: PathBuf) -> Self {
-        SkillRegistry { db, skills_dir }
+        SkillRegistry { db, skills_dir, tool_registry: None }
+    }
+
+    /// Validate skill `requires_tools` against this tool registry on creation
+    pub fn with_tool_registry(mut self, tool_registry: Arc<ToolRegistry>) -> Self {
+        self.tool_registry = Some(tool_registry);
+        self
+    }
+
+    /// Check declared dependencies against the tool registry, installed
+    /// binaries and configured API keys
+    pub fn check_dependencies(
+        &self,
+        requires_tools: &[String],
+        requires_binaries: &[String],
+        requires_api_keys: &HashMap<String, SkillApiKey>,
+    ) -> SkillDependencyReport {
+        let has_tool = |name: &str| self.tool_registry.as_ref().is_some_and(|r| r.has_tool(name));
+        let has_api_key = |name: &str| {
+            matches!(self.db.get_api_key(name), Ok(Some(key)) if !key.api_key.is_empty())
+        };
+        SkillDependencyReport::check(
+            requires_tools,
+            requires_binaries,
+            requires_api_keys,
+            self.tool_registry.as_ref().map(|_| &has_tool as &dyn Fn(&str) -> bool),
+            &has_api_key,
+        )
+    }
+
+    /// Dependency report for an installed skill
+    pub fn dependency_report(&self, skill: &Skill) -> SkillDependencyReport {
+        self.check_dependencies(
+            &skill.metadata.requires_tools,
+            &skill.metadata.requires_binaries,
+            &skill.metadata.requires_api_keys,
+        )
+    }
+
+    /// Dry-run a skill: parse its scripts and resolve the tools its body uses,
+    /// without executing anything. Returns None if the skill doesn't exist.
+    pub async fn validate_skill(&self, name: &str) -> Option<SkillValidationReport> {
+        let skill = self.get(name)?;
+        let scripts = self.get_skill_scripts(name);
+        let tool_group = |tool: &str| {
+            self.tool_registry
+                .as_ref()
+                .and_then(|r| r.get(tool))
+                .map(|t| t.definition().group)
+        };
+        Some(
+            validation::validate_skill(
+                &skill.metadata.name,
+                &skill.prompt_template,
+                &skill.metadata.requires_tools,
+                &scripts,
+                &tool_group,
+            )
+            .await,
+        )
+    }
+
+    /// Validate dependencies before a skill is created. Returns an error listing
+    /// the unmet dependencies when creation is blocked; otherwise logs a warning.
+    fn validate_dependencies(&self, name: &str, report: &SkillDependencyReport) -> Result<(), String> {
+        if report.is_satisfied() {
+            return Ok(());
+        }
+        if report.blocks_creation(crate::config::skill_strict_dependencies()) {
+            return Err(format!("Skill '{}' has unmet dependencies: {}", name, report.summary()));
+        }
+        log::warn!("[SKILLS] Skill '{}' has unmet dependencies: {}", name, report.summary());
+        Ok(())
     }
 
     /// Get the runtime skills directory path
@@ -90,6 +170,35 @@ impl SkillRegistry {
         self.len() == 0
     }
 
+    /// Find the enabled skills most relevant to a natural-language task.
+    /// Embeds the task and ranks skills by cosine similarity against their
+    /// stored embeddings; falls back to keyword matching when no embedding
+    /// generator is available or nothing clears `threshold`.
+    pub async fn find_relevant_skills(
+        &self,
+        task: &str,
+        limit: usize,
+        threshold: f32,
+        embedding_gen: Option<&Arc<dyn EmbeddingGenerator + Send + Sync>>,
+    ) -> Vec<(DbSkill, f32)> {
+        if task.trim().is_empty() || limit == 0 {
+            return Vec::new();
+        }
+
+        if let Some(emb_gen) = embedding_gen {
+            match search_skills(&self.db, emb_gen, task, limit, threshold).await {
+                Ok(matches) if !matches.is_empty() => return matches,
+                Ok(_) => {}
+                Err(e) => log::warn!("[SKILL-SEARCH] Embedding search failed, falling back to text: {}", e),
+            }
+        }
+
+        search_skills_text(&self.db, task, limit).unwrap_or_else(|e| {
+            log::warn!("[SKILL-SEARCH] Text search failed: {}", e);
+            Vec::new()
+        })
+    }
+
     /// Create a skill from a parsed ZIP file — writes to disk, then syncs to DB
     pub fn create_skill_from_zip(&self, data: &[u8]) -> Result<DbSkill, String> {
         let parsed = parse_skill_zip(data)?;
@@ -163,6 +272,13 @@ impl SkillRegistry {
     }
 
     fn create_skill_from_parsed_internal(&self, parsed: ParsedSkill, force: bool) -> Result<DbSkill, String> {
+        let report = self.check_dependencies(
+            &parsed.requires_tools,
+            &parsed.requires_binaries,
+            &parsed.requires_api_keys,
+        );
+        self.validate_dependencies(&parsed.name, &report)?;
+
         // Write to disk first
         write_skill_folder(&self.skills_dir, &parsed)
             .map_err(|e| format!("Failed to write skill to disk: {}", e))?;
@@ -295,6 +411,8 @@ impl SkillRegistry {
             Some(skill_dir.to_path_buf()),
         ).await.map_err(|e| format!("Failed to load skill from {}: {}", md_path.display(), e))?;
 
+        self.validate_dependencies(&skill.metadata.name, &self.dependency_report(&skill))?;
+
         // Import into DB (handles scripts, ABIs, presets)
         self.import_file_skill(&skill)
             .map_err(|e| format!("Failed to import skill '{}': {}", skill.metadata.name, e))?;
@@ -306,6 +424,61 @@ impl SkillRegistry {
             .ok_or_else(|| "Skill not found after creation".to_string())
     }
 
+    /// Restore a prior version of a skill from its history (disk + DB).
+    /// The current definition is saved to history first, so a rollback can
+    /// itself be undone.
+    pub fn rollback_skill(&self, name: &str, version_id: i64) -> Result<DbSkill, String> {
+        let entry = self.db
+            .get_skill_version(name, version_id)
+            .map_err(|e| format!("Failed to load skill version: {}", e))?
+            .ok_or_else(|| format!("Version {} of skill '{}' not found", version_id, name))?;
+
+        let current_scripts = self.get_skill_scripts(name);
+        let skill = entry.skill;
+        let parsed = ParsedSkill {
+            name: skill.name,
+            description: skill.description,
+            body: skill.body,
+            version: skill.version,
+            author: skill.author,
+            homepage: skill.homepage,
+            metadata: skill.metadata,
+            requires_tools: skill.requires_tools,
+            requires_binaries: skill.requires_binaries,
+            arguments: skill.arguments,
+            tags: skill.tags,
+            subagent_type: skill.subagent_type,
+            requires_api_keys: skill.requires_api_keys,
+            scripts: entry.scripts.iter().map(|s| ParsedScript {
+                name: s.name.clone(),
+                code: s.code.clone(),
+                language: s.language.clone(),
+            }).collect(),
+            abis: Vec::new(),
+            presets_content: None,
+            flows: Vec::new(),
+        };
+
+        let restored = self.create_skill_from_parsed_internal(parsed, true)?;
+
+        // Drop scripts added after the restored version (DB and disk)
+        let skill_dir = self.skills_dir.join(name);
+        for script in current_scripts {
+            if entry.scripts.iter().any(|s| s.name == script.name) {
+                continue;
+            }
+            if let Err(e) = self.db.delete_skill_script(script.skill_id, &script.name) {
+                log::warn!("[SKILLS] Failed to remove script '{}' from '{}': {}", script.name, name, e);
+            }
+            for path in [skill_dir.join(&script.name), skill_dir.join("scripts").join(&script.name)] {
+                let _ = std::fs::remove_file(path);
+            }
+        }
+
+        log::info!("[SKILLS] Rolled back skill '{}' to version {} (history #{})", name, restored.version, version_id);
+        Ok(restored)
+    }
+
     /// Delete a skill from disk AND database
     pub fn delete_skill(&self, name: &str) -> Result<bool, String> {
         // Delete from disk (idempotent — safe if already removed)
@@ -982,6 +1155,110 @@ pub fn create_default_registry(db: Arc<Database>) -> SkillRegistry {
 mod tests {
     use super::*;
     use crate::skills::types::SkillMetadata;
+    use async_trait::async_trait;
+
+    /// Embeds text onto fixed "topic" axes so synonyms land close together
+    struct TopicEmbedder;
+
+    #[async_trait]
+    impl EmbeddingGenerator for TopicEmbedder {
+        async fn generate(&self, text: &str) -> Result<Vec<f32>, String> {
+            let text = text.to_lowercase();
+            let axis = |words: &[&str]| words.iter().filter(|w| text.contains(*w)).count() as f32;
+            Ok(vec![
+                axis(&["swap", "exchange", "trade", "token"]),
+                axis(&["tweet", "post", "twitter", "social"]),
+                0.1,
+            ])
+        }
+    }
 
-    // Tests would require a mock database - skipping for now
+    fn db_skill(name: &str, description: &str) -> DbSkill {
+        DbSkill {
+            id: None,
+            name: name.to_string(),
+            description: description.to_string(),
+            body: String::new(),
+            version: "1.0.0".to_string(),
+            author: None,
+            homepage: None,
+            metadata: None,
+            enabled: true,
+            requires_tools: vec![],
+            requires_binaries: vec![],
+            arguments: Default::default(),
+            tags: vec![],
+            subagent_type: None,
+            requires_api_keys: Default::default(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_find_relevant_skills_semantic() {
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        let registry = SkillRegistry::new(db.clone(), PathBuf::from("unused"));
+        let emb_gen: Arc<dyn EmbeddingGenerator + Send + Sync> = Arc::new(TopicEmbedder);
+
+        for (name, desc) in [("dex_swap", "Swap tokens on a DEX"), ("x_poster", "Post tweets")] {
+            let id = db.create_skill(&db_skill(name, desc)).unwrap();
+            let embedding = emb_gen.generate(desc).await.unwrap();
+            db.upsert_skill_embedding(id, &embedding, "test", 3).unwrap();
+        }
+
+        // No keyword overlap with "Swap tokens", but the same topic
+        let task = "exchange my ETH for USDC";
+        assert!(search_skills_text(&db, task, 5).unwrap().is_empty());
+        let matches = registry.find_relevant_skills(task, 1, 0.5, Some(&emb_gen)).await;
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].0.name, "dex_swap");
+
+        // Disabled skills are never returned
+        db.set_skill_enabled("dex_swap", false).unwrap();
+        let matches = registry.find_relevant_skills(task, 1, 0.5, Some(&emb_gen)).await;
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn test_update_history_and_rollback() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        let registry = SkillRegistry::new(db.clone(), dir.path().to_path_buf());
+
+        let v1 = db_skill("poster", "Post updates");
+        let id = db.create_skill(&v1).unwrap();
+        db.create_skill_script(&DbSkillScript {
+            id: None,
+            skill_id: id,
+            name: "post.sh".to_string(),
+            code: "echo v1".to_string(),
+            language: "bash".to_string(),
+            created_at: String::new(),
+        }).unwrap();
+
+        // Re-syncing an identical definition doesn't create history
+        db.create_skill_force(&v1).unwrap();
+        assert!(db.list_skill_versions("poster").unwrap().is_empty());
+
+        let mut v2 = db_skill("poster", "Post updates");
+        v2.body = "broken edit".to_string();
+        v2.version = "1.1.0".to_string();
+        db.create_skill_force(&v2).unwrap();
+
+        let history = db.list_skill_versions("poster").unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].version, "1.0.0");
+        assert_eq!(history[0].scripts[0].code, "echo v1");
+
+        let restored = registry.rollback_skill("poster", history[0].id).unwrap();
+        assert_eq!(restored.version, "1.0.0");
+        assert_eq!(restored.body, "");
+        assert_eq!(registry.get_skill_scripts("poster")[0].code, "echo v1");
+
+        // The rolled-back definition is itself in history
+        let history = db.list_skill_versions("poster").unwrap();
+        assert_eq!(history[0].version, "1.1.0");
+        assert!(registry.rollback_skill("poster", 9999).is_err());
+    }
 }
@@ -291,6 +291,17 @@ pub struct DbSkillScript {
     pub created_at: String,
 }
 
+/// A prior definition of a skill, captured before it was updated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DbSkillVersion {
+    pub id: i64,
+    pub skill_name: String,
+    pub version: String,
+    pub skill: DbSkill,
+    pub scripts: Vec<DbSkillScript>,
+    pub created_at: String,
+}
+
 /// Database record for skill flows (markdown flow files)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DbSkillFlow {
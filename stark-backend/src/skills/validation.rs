@@ -0,0 +1,282 @@
+//! Skill dry-run validation
+//!
+//! Checks a skill before it is enabled: scripts are parsed (never run), and
+//! tools referenced from the body are resolved against the tool registry.
+//! Tools a skill calls must be declared in `requires_tools`, since only
+//! declared tools are unlocked while the skill is active.
+
+use super::types::DbSkillScript;
+use crate::tools::types::ToolGroup;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::process::Stdio;
+use std::sync::LazyLock;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// How long a single syntax check may take
+const SYNTAX_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+// `{"tool": "name", ...}` call examples in the skill body
+static TOOL_CALL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""tool"\s*:\s*"([A-Za-z0-9_]+)""#).unwrap());
+// `"script": "name.py"` arguments to run_skill_script
+static SCRIPT_REF_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""script"\s*:\s*"([^"]+)""#).unwrap());
+// Inline code spans that look like tool names (`web_fetch`)
+static INLINE_TOOL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"`([a-z][a-z0-9]*(?:_[a-z0-9]+)+)`").unwrap());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+/// A single problem found during validation
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillValidationIssue {
+    pub severity: IssueSeverity,
+    /// unresolved_tool, disallowed_tool, missing_script, unsupported_script,
+    /// script_syntax or syntax_check_skipped
+    pub kind: String,
+    pub message: String,
+}
+
+/// Result of a skill dry-run
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillValidationReport {
+    pub skill_name: String,
+    /// True when no errors were found (warnings are allowed)
+    pub valid: bool,
+    /// Registered tools the skill body calls
+    pub referenced_tools: Vec<String>,
+    /// Scripts whose syntax was checked
+    pub scripts_checked: Vec<String>,
+    pub issues: Vec<SkillValidationIssue>,
+}
+
+impl SkillValidationReport {
+    fn new(skill_name: &str) -> Self {
+        Self {
+            skill_name: skill_name.to_string(),
+            valid: true,
+            referenced_tools: Vec::new(),
+            scripts_checked: Vec::new(),
+            issues: Vec::new(),
+        }
+    }
+
+    fn error(&mut self, kind: &str, message: impl Into<String>) {
+        self.valid = false;
+        self.issues.push(SkillValidationIssue {
+            severity: IssueSeverity::Error,
+            kind: kind.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn warn(&mut self, kind: &str, message: impl Into<String>) {
+        self.issues.push(SkillValidationIssue {
+            severity: IssueSeverity::Warning,
+            kind: kind.to_string(),
+            message: message.into(),
+        });
+    }
+}
+
+/// Validate a skill without executing anything.
+/// `tool_group` looks a tool up in the registry, returning its group if it exists.
+pub async fn validate_skill(
+    skill_name: &str,
+    body: &str,
+    requires_tools: &[String],
+    scripts: &[DbSkillScript],
+    tool_group: &(dyn Fn(&str) -> Option<ToolGroup> + Sync),
+) -> SkillValidationReport {
+    let mut report = SkillValidationReport::new(skill_name);
+    check_tools(&mut report, body, requires_tools, tool_group);
+    check_script_references(&mut report, body, scripts);
+
+    for script in scripts {
+        match check_script_syntax(&script.name, &script.code).await {
+            Ok(()) => report.scripts_checked.push(script.name.clone()),
+            Err(ScriptCheckError::Syntax(e)) => {
+                report.scripts_checked.push(script.name.clone());
+                report.error("script_syntax", format!("{}: {}", script.name, e));
+            }
+            Err(ScriptCheckError::Unsupported) => report.error(
+                "unsupported_script",
+                format!("{}: run_skill_script only runs .py, .sh and .js scripts", script.name),
+            ),
+            Err(ScriptCheckError::Skipped(reason)) => {
+                report.warn("syntax_check_skipped", format!("{}: {}", script.name, reason))
+            }
+        }
+    }
+
+    report
+}
+
+fn check_tools(
+    report: &mut SkillValidationReport,
+    body: &str,
+    requires_tools: &[String],
+    tool_group: &(dyn Fn(&str) -> Option<ToolGroup> + Sync),
+) {
+    for tool in requires_tools {
+        if tool_group(tool).is_none() {
+            report.error("unresolved_tool", format!("requires_tools lists unknown tool '{}'", tool));
+        }
+    }
+
+    // Explicit tool calls must resolve; inline code spans only count when they name a real tool
+    let mut referenced = BTreeSet::new();
+    for cap in TOOL_CALL_RE.captures_iter(body) {
+        let name = &cap[1];
+        if tool_group(name).is_some() {
+            referenced.insert(name.to_string());
+        } else if !requires_tools.iter().any(|t| t == name) {
+            report.error("unresolved_tool", format!("body calls unknown tool '{}'", name));
+        }
+    }
+    for cap in INLINE_TOOL_RE.captures_iter(body) {
+        if tool_group(&cap[1]).is_some() {
+            referenced.insert(cap[1].to_string());
+        }
+    }
+
+    for name in &referenced {
+        let declared = requires_tools.iter().any(|t| t == name);
+        if !declared && tool_group(name) != Some(ToolGroup::System) {
+            report.error(
+                "disallowed_tool",
+                format!("body uses '{}' but it is not declared in requires_tools", name),
+            );
+        }
+    }
+    report.referenced_tools = referenced.into_iter().collect();
+}
+
+fn check_script_references(report: &mut SkillValidationReport, body: &str, scripts: &[DbSkillScript]) {
+    let mut seen = BTreeSet::new();
+    for cap in SCRIPT_REF_RE.captures_iter(body) {
+        let name = cap[1].to_string();
+        if seen.insert(name.clone()) && !scripts.iter().any(|s| s.name == name) {
+            report.error("missing_script", format!("body runs script '{}' but the skill has no such script", name));
+        }
+    }
+}
+
+/// Parse a single script without running it (used by `run_skill_script` dry runs)
+pub async fn dry_run_script(name: &str, code: &str) -> Result<String, String> {
+    match check_script_syntax(name, code).await {
+        Ok(()) => Ok("script parsed successfully".to_string()),
+        Err(ScriptCheckError::Syntax(e)) => Err(e),
+        Err(ScriptCheckError::Unsupported) => Err("unsupported script extension (use .py, .sh or .js)".to_string()),
+        Err(ScriptCheckError::Skipped(reason)) => Ok(format!("resolved, but {}", reason)),
+    }
+}
+
+enum ScriptCheckError {
+    Syntax(String),
+    Unsupported,
+    Skipped(String),
+}
+
+/// Parse a script with its interpreter's syntax-only mode. The code is passed
+/// on stdin and is compiled, never run.
+async fn check_script_syntax(name: &str, code: &str) -> Result<(), ScriptCheckError> {
+    let (program, args): (&str, &[&str]) = if name.ends_with(".py") {
+        ("python3", &["-c", "import ast, sys; ast.parse(sys.stdin.read())"])
+    } else if name.ends_with(".sh") {
+        ("bash", &["-n"])
+    } else if name.ends_with(".js") {
+        ("node", &["-e", "new (require('vm').Script)(require('fs').readFileSync(0, 'utf8'))"])
+    } else {
+        return Err(ScriptCheckError::Unsupported);
+    };
+
+    if which::which(program).is_err() {
+        return Err(ScriptCheckError::Skipped(format!("{} is not installed, syntax not checked", program)));
+    }
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| ScriptCheckError::Skipped(format!("failed to start {}: {}", program, e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(code.as_bytes()).await;
+    }
+
+    let output = match tokio::time::timeout(SYNTAX_CHECK_TIMEOUT, child.wait_with_output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => return Err(ScriptCheckError::Skipped(format!("syntax check failed to run: {}", e))),
+        Err(_) => return Err(ScriptCheckError::Skipped("syntax check timed out".to_string())),
+    };
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // The last line carries the error (e.g. Python's "SyntaxError: ...")
+        let message = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("invalid syntax");
+        Err(ScriptCheckError::Syntax(message.trim().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(name: &str, code: &str) -> DbSkillScript {
+        DbSkillScript {
+            id: None,
+            skill_id: 1,
+            name: name.to_string(),
+            code: code.to_string(),
+            language: String::new(),
+            created_at: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_skill() {
+        let tool_group = |name: &str| match name {
+            "run_skill_script" | "web_fetch" => Some(ToolGroup::Web),
+            "use_skill" => Some(ToolGroup::System),
+            _ => None,
+        };
+        let body = r#"Call `use_skill` then `web_fetch`.
+{"tool": "run_skill_script", "script": "good.sh"}
+{"tool": "run_skill_script", "script": "gone.py"}
+{"tool": "no_such_tool"}"#;
+        let scripts = vec![script("good.sh", "echo hi\n"), script("tool.rb", "puts 1")];
+        let requires = vec!["run_skill_script".to_string()];
+
+        let report = validate_skill("demo", body, &requires, &scripts, &tool_group).await;
+        assert!(!report.valid);
+        assert_eq!(report.referenced_tools, vec!["run_skill_script", "use_skill", "web_fetch"]);
+
+        let kinds: Vec<&str> = report.issues.iter().map(|i| i.kind.as_str()).collect();
+        assert!(kinds.contains(&"unresolved_tool"));
+        assert!(kinds.contains(&"disallowed_tool"));
+        assert!(kinds.contains(&"missing_script"));
+        assert!(kinds.contains(&"unsupported_script"));
+        assert!(report.issues.iter().any(|i| i.message.contains("web_fetch")));
+        assert!(!report.issues.iter().any(|i| i.message.contains("use_skill")));
+
+        // A broken shell script is reported, not run
+        let scripts = vec![script("bad.sh", "if then fi (\n")];
+        let report = validate_skill("demo", "", &[], &scripts, &tool_group).await;
+        assert!(report.issues.iter().any(|i| i.kind == "script_syntax" || i.kind == "syntax_check_skipped"));
+    }
+}
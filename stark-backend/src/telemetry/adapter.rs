@@ -65,6 +65,7 @@ impl Adapter<Span, Timeline> for SpansToTimeline {
                     SpanType::Watchdog => format!("Watchdog: {}", span.name),
                     SpanType::Rollout => format!("Rollout: {}", span.name),
                     SpanType::ResourceResolution => format!("Resource: {}", span.name),
+                    SpanType::Hook => format!("Hook: {} ({})", span.name, status_label(span.status)),
                 };
 
                 TimelineEntry {
@@ -0,0 +1,118 @@
+//! Prometheus text exposition for `/metrics`.
+//!
+//! `PrometheusWriter` formats metric families; the metrics controller fills it
+//! from the telemetry store, execution tracker, tx queue and disk quota.
+
+use std::fmt::Write;
+
+/// Prometheus metric type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricKind {
+    Counter,
+    Gauge,
+}
+
+impl MetricKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+        }
+    }
+}
+
+/// One sample of a metric family: label pairs and a value
+pub type Sample<'a> = (Vec<(&'a str, String)>, f64);
+
+/// Builds a Prometheus text-format (version 0.0.4) document
+#[derive(Debug, Default)]
+pub struct PrometheusWriter {
+    out: String,
+}
+
+impl PrometheusWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write a metric family with HELP/TYPE headers and its samples.
+    /// A family with no samples still gets its headers.
+    pub fn family<'a>(
+        &mut self,
+        name: &str,
+        kind: MetricKind,
+        help: &str,
+        samples: impl IntoIterator<Item = Sample<'a>>,
+    ) {
+        let _ = writeln!(self.out, "# HELP {} {}", name, help.replace('\\', "\\\\").replace('\n', "\\n"));
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind.as_str());
+        for (labels, value) in samples {
+            self.out.push_str(name);
+            if !labels.is_empty() {
+                let labels: Vec<String> = labels
+                    .iter()
+                    .map(|(k, v)| format!("{}=\"{}\"", k, escape_label_value(v)))
+                    .collect();
+                let _ = write!(self.out, "{{{}}}", labels.join(","));
+            }
+            let _ = writeln!(self.out, " {}", format_value(value));
+        }
+    }
+
+    /// Write an unlabeled gauge
+    pub fn gauge(&mut self, name: &str, help: &str, value: f64) {
+        self.family(name, MetricKind::Gauge, help, [(Vec::new(), value)]);
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_families_and_escapes_labels() {
+        let mut w = PrometheusWriter::new();
+        w.gauge("starkbot_uptime_seconds", "Seconds since start", 12.5);
+        w.family(
+            "starkbot_tool_executions_total",
+            MetricKind::Counter,
+            "Tool executions",
+            [
+                (vec![("tool", "web_fetch".to_string()), ("success", "true".to_string())], 3.0),
+                (vec![("tool", "say \"hi\"".to_string()), ("success", "false".to_string())], 1.0),
+            ],
+        );
+        w.family("starkbot_rollouts", MetricKind::Gauge, "Rollouts", Vec::new());
+
+        assert_eq!(
+            w.finish(),
+            "# HELP starkbot_uptime_seconds Seconds since start\n\
+             # TYPE starkbot_uptime_seconds gauge\n\
+             starkbot_uptime_seconds 12.5\n\
+             # HELP starkbot_tool_executions_total Tool executions\n\
+             # TYPE starkbot_tool_executions_total counter\n\
+             starkbot_tool_executions_total{tool=\"web_fetch\",success=\"true\"} 3\n\
+             starkbot_tool_executions_total{tool=\"say \\\"hi\\\"\",success=\"false\"} 1\n\
+             # HELP starkbot_rollouts Rollouts\n\
+             # TYPE starkbot_rollouts gauge\n"
+        );
+    }
+}
@@ -13,6 +13,10 @@ pub mod watchdog;
 pub mod resource_version;
 pub mod adapter;
 pub mod store;
+pub mod metrics;
+pub mod otlp;
+pub mod trace;
+pub mod replay;
 
 // Re-export key types for convenience
 pub use span::{Span, SpanCollector, SpanGuard, SpanStatus, SpanType};
@@ -22,4 +26,6 @@ pub use reward::RewardEmitter;
 pub use watchdog::{Watchdog, WatchdogConfig, WatchdogError};
 pub use resource_version::{Resource, ResourceBundle, ResourceManager, ResourceType};
 pub use adapter::{Adapter, ExecutionSummary, SpansToSummary, SpansToTimeline, SpansToTriplets, Timeline, Triplet};
-pub use store::{RetentionPolicy, RewardStats, TelemetryStore};
+pub use store::{RetentionPolicy, RewardStats, SkillMetrics, TelemetryStore};
+pub use metrics::{MetricKind, PrometheusWriter};
+pub use replay::{RecordedDispatch, ReplayOutcome, ReplayReport};
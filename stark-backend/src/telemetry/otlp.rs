@@ -0,0 +1,301 @@
+//! Optional OTLP trace export.
+//!
+//! When `OTEL_EXPORTER_OTLP_ENDPOINT` (or `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`)
+//! is set, spans persisted by the `TelemetryStore` are also sent to an
+//! OpenTelemetry collector using OTLP/HTTP with JSON encoding. Each rollout
+//! becomes one trace: a synthetic `dispatch` root span with the rollout's spans
+//! beneath it. The in-DB telemetry is unaffected; export is fire-and-forget.
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use super::span::{Span, SpanStatus, SpanType};
+use crate::config::env_vars;
+
+static EXPORTER: LazyLock<Option<OtlpExporter>> = LazyLock::new(OtlpExporter::from_env);
+
+/// The process-wide exporter, if OTLP export is configured
+pub fn exporter() -> Option<&'static OtlpExporter> {
+    EXPORTER.as_ref()
+}
+
+pub struct OtlpExporter {
+    traces_url: String,
+    headers: Vec<(String, String)>,
+    service_name: String,
+    client: reqwest::Client,
+}
+
+impl OtlpExporter {
+    fn from_env() -> Option<Self> {
+        let traces_url = match std::env::var(env_vars::OTLP_TRACES_ENDPOINT).ok().filter(|v| !v.is_empty()) {
+            Some(url) => url,
+            None => {
+                let base = std::env::var(env_vars::OTLP_ENDPOINT).ok().filter(|v| !v.is_empty())?;
+                format!("{}/v1/traces", base.trim_end_matches('/'))
+            }
+        };
+        if let Ok(protocol) = std::env::var(env_vars::OTLP_PROTOCOL)
+            && protocol != "http/json"
+        {
+            log::warn!(
+                "[OTLP] {}={} is not supported; exporting with http/json",
+                env_vars::OTLP_PROTOCOL,
+                protocol
+            );
+        }
+        let headers = std::env::var(env_vars::OTLP_HEADERS)
+            .map(|v| parse_headers(&v))
+            .unwrap_or_default();
+        let service_name = std::env::var(env_vars::OTEL_SERVICE_NAME)
+            .ok()
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "starkbot".to_string());
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .ok()?;
+
+        log::info!("[OTLP] Exporting rollout traces to {}", traces_url);
+        Some(Self { traces_url, headers, service_name, client })
+    }
+
+    /// Send spans to the collector in the background. Failures are logged, never surfaced.
+    pub fn export(&'static self, spans: Vec<Span>) {
+        if spans.is_empty() {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let body = build_export_request(&self.service_name, &spans);
+        handle.spawn(async move {
+            let mut req = self.client.post(&self.traces_url).json(&body);
+            for (name, value) in &self.headers {
+                req = req.header(name, value);
+            }
+            match req.send().await {
+                Ok(resp) if resp.status().is_success() => {}
+                Ok(resp) => log::warn!("[OTLP] Collector rejected {} spans: HTTP {}", spans.len(), resp.status()),
+                Err(e) => log::warn!("[OTLP] Failed to export {} spans: {}", spans.len(), e),
+            }
+        });
+    }
+}
+
+/// Parse `OTEL_EXPORTER_OTLP_HEADERS` ("key1=value1,key2=value2")
+fn parse_headers(raw: &str) -> Vec<(String, String)> {
+    raw.split(',')
+        .filter_map(|pair| {
+            let (k, v) = pair.split_once('=')?;
+            let k = k.trim();
+            (!k.is_empty()).then(|| (k.to_string(), v.trim().to_string()))
+        })
+        .collect()
+}
+
+/// First `len` bytes of SHA-256(`input`), hex-encoded. Gives stable OTLP ids
+/// (16-byte trace ids, 8-byte span ids) from our UUID-based ones.
+fn derived_id(input: &str, len: usize) -> String {
+    hex::encode(&Sha256::digest(input.as_bytes())[..len])
+}
+
+fn trace_id(rollout_id: &str) -> String {
+    derived_id(rollout_id, 16)
+}
+
+fn span_id(id: &str) -> String {
+    derived_id(id, 8)
+}
+
+fn root_span_id(rollout_id: &str) -> String {
+    span_id(&format!("rollout:{}", rollout_id))
+}
+
+fn unix_nanos(t: chrono::DateTime<chrono::Utc>) -> String {
+    t.timestamp_nanos_opt().unwrap_or_default().to_string()
+}
+
+fn any_value(value: &Value) -> Value {
+    match value {
+        Value::String(s) => json!({ "stringValue": s }),
+        Value::Bool(b) => json!({ "boolValue": b }),
+        // OTLP/JSON encodes 64-bit integers as strings
+        Value::Number(n) if n.is_i64() || n.is_u64() => json!({ "intValue": n.to_string() }),
+        Value::Number(n) => json!({ "doubleValue": n.as_f64() }),
+        other => json!({ "stringValue": other.to_string() }),
+    }
+}
+
+fn key_value(key: &str, value: Value) -> Value {
+    json!({ "key": key, "value": any_value(&value) })
+}
+
+fn span_type_name(span_type: SpanType) -> Value {
+    serde_json::to_value(span_type).unwrap_or(Value::Null)
+}
+
+/// Map one of our spans to an OTLP span under the rollout's root span
+fn otlp_span(span: &Span) -> Value {
+    let mut attributes = vec![
+        key_value("starkbot.span_type", span_type_name(span.span_type)),
+        key_value("starkbot.sequence_id", json!(span.sequence_id)),
+        key_value("starkbot.attempt", json!(span.attempt_idx)),
+        key_value("starkbot.session_id", json!(span.session_id)),
+    ];
+    if let Some(attrs) = span.attributes.as_object() {
+        attributes.extend(
+            attrs
+                .iter()
+                .filter(|(_, v)| !v.is_null())
+                .map(|(k, v)| key_value(&format!("starkbot.{}", k), v.clone())),
+        );
+    }
+
+    let status = match span.status {
+        SpanStatus::Succeeded => json!({ "code": 1 }),
+        SpanStatus::Failed | SpanStatus::TimedOut => {
+            json!({ "code": 2, "message": span.error.clone().unwrap_or_default() })
+        }
+        _ => json!({ "code": 0 }),
+    };
+    let parent = match &span.parent_span_id {
+        Some(parent) => span_id(parent),
+        None => root_span_id(&span.rollout_id),
+    };
+    // LLM calls go out to a provider; everything else is in-process work
+    let kind = if span.span_type == SpanType::LlmCall { 3 } else { 1 };
+
+    json!({
+        "traceId": trace_id(&span.rollout_id),
+        "spanId": span_id(&span.span_id),
+        "parentSpanId": parent,
+        "name": format!("{} {}", span_type_name(span.span_type).as_str().unwrap_or("span"), span.name),
+        "kind": kind,
+        "startTimeUnixNano": unix_nanos(span.started_at),
+        "endTimeUnixNano": unix_nanos(span.completed_at.unwrap_or(span.started_at)),
+        "attributes": attributes,
+        "status": status,
+    })
+}
+
+/// Synthetic root span covering all of a rollout's spans. It has no status:
+/// the rollout's outcome lives in the rollouts table, not in its spans.
+fn root_span(rollout_id: &str, spans: &[&Span]) -> Value {
+    let start = spans.iter().map(|s| s.started_at).min().unwrap_or_default();
+    let end = spans
+        .iter()
+        .map(|s| s.completed_at.unwrap_or(s.started_at))
+        .max()
+        .unwrap_or(start);
+    let mut attributes = vec![key_value("starkbot.rollout_id", json!(rollout_id))];
+    if let Some(first) = spans.first() {
+        attributes.push(key_value("starkbot.session_id", json!(first.session_id)));
+    }
+    // The dispatch_start span carries the channel context
+    if let Some(attrs) = spans
+        .iter()
+        .find(|s| s.span_type == SpanType::Rollout)
+        .and_then(|s| s.attributes.as_object())
+    {
+        for key in ["channel_type", "channel_id"] {
+            if let Some(v) = attrs.get(key) {
+                attributes.push(key_value(&format!("starkbot.{}", key), v.clone()));
+            }
+        }
+    }
+
+    json!({
+        "traceId": trace_id(rollout_id),
+        "spanId": root_span_id(rollout_id),
+        "name": "dispatch",
+        "kind": 2,
+        "startTimeUnixNano": unix_nanos(start),
+        "endTimeUnixNano": unix_nanos(end),
+        "attributes": attributes,
+    })
+}
+
+/// Build an OTLP `ExportTraceServiceRequest` (JSON encoding) for a batch of spans
+pub fn build_export_request(service_name: &str, spans: &[Span]) -> Value {
+    let mut rollouts: Vec<&str> = spans.iter().map(|s| s.rollout_id.as_str()).collect();
+    rollouts.sort_unstable();
+    rollouts.dedup();
+
+    let mut otlp_spans = Vec::with_capacity(spans.len() + rollouts.len());
+    for rollout_id in rollouts {
+        let rollout_spans: Vec<&Span> = spans.iter().filter(|s| s.rollout_id == rollout_id).collect();
+        otlp_spans.push(root_span(rollout_id, &rollout_spans));
+        otlp_spans.extend(rollout_spans.into_iter().map(otlp_span));
+    }
+
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [
+                    key_value("service.name", json!(service_name)),
+                    key_value("service.version", json!(crate::controllers::health::VERSION)),
+                ]
+            },
+            "scopeSpans": [{
+                "scope": { "name": "starkbot.telemetry" },
+                "spans": otlp_spans,
+            }]
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::SpanCollector;
+
+    #[test]
+    fn rollout_becomes_root_of_its_trace() {
+        let collector = SpanCollector::new("rollout-1".to_string(), 7);
+        let mut start = collector.start_span(SpanType::Rollout, "dispatch_start");
+        start.attributes = json!({ "channel_type": "discord", "channel_id": 3 });
+        start.succeed();
+        let mut llm = collector.start_span(SpanType::LlmCall, "generate_with_tools");
+        llm.succeed();
+        let mut tool = collector.start_span(SpanType::ToolCall, "web_fetch").with_parent(llm.span_id.clone());
+        tool.attributes = json!({ "tool_name": "web_fetch", "success": false });
+        tool.fail("timeout".to_string());
+        let spans = vec![start, llm, tool];
+
+        let request = build_export_request("starkbot", &spans);
+        let otlp = request["resourceSpans"][0]["scopeSpans"][0]["spans"].as_array().unwrap();
+        assert_eq!(otlp.len(), 4);
+
+        let root = &otlp[0];
+        assert_eq!(root["name"], "dispatch");
+        assert!(root.get("parentSpanId").is_none());
+        assert_eq!(root["traceId"].as_str().unwrap().len(), 32);
+        assert!(otlp.iter().all(|s| s["traceId"] == root["traceId"]));
+        assert!(root["attributes"].as_array().unwrap().iter().any(|a| a["key"] == "starkbot.channel_type"));
+
+        // Top-level spans hang off the root; nested spans keep their parent
+        assert_eq!(otlp[2]["parentSpanId"], root["spanId"]);
+        assert_eq!(otlp[2]["kind"], 3);
+        assert_eq!(otlp[3]["parentSpanId"], otlp[2]["spanId"]);
+        assert_eq!(otlp[3]["name"], "tool_call web_fetch");
+        assert_eq!(otlp[3]["status"], json!({ "code": 2, "message": "timeout" }));
+        assert!(otlp[3]["attributes"]
+            .as_array()
+            .unwrap()
+            .contains(&json!({ "key": "starkbot.success", "value": { "boolValue": false } })));
+    }
+
+    #[test]
+    fn parses_otlp_headers() {
+        assert_eq!(
+            parse_headers("x-honeycomb-team=abc, authorization=Bearer t=1,bad"),
+            vec![
+                ("x-honeycomb-team".to_string(), "abc".to_string()),
+                ("authorization".to_string(), "Bearer t=1".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,234 @@
+//! Rollout replay for prompt iteration.
+//!
+//! Every dispatch records the user's message on its `dispatch_start` span and
+//! each AI response as an `llm_call` span. A replay re-dispatches the message
+//! with a mock client seeded with those responses while prompts resolve from
+//! another resource version, then compares the two executions.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+
+use super::adapter::{Adapter, ExecutionSummary, SpansToSummary};
+use super::resource_version::ResourceBundle;
+use super::span::{Span, SpanCollector, SpanType};
+use crate::ai::AiResponse;
+
+/// Name of the `llm_call` spans that carry a recorded AI response
+pub const LLM_RESPONSE_SPAN: &str = "generate_with_tools";
+
+/// Record an AI response as an `llm_call` span so the rollout can be replayed.
+pub fn record_llm_response(collector: &SpanCollector, response: &AiResponse, elapsed: Duration) {
+    let mut span = collector.start_span(SpanType::LlmCall, LLM_RESPONSE_SPAN);
+    span.started_at -= chrono::Duration::milliseconds(elapsed.as_millis() as i64);
+    span.attributes = json!({
+        "content": response.content,
+        "tool_calls": response.tool_calls,
+        "stop_reason": response.stop_reason,
+    });
+    span.succeed();
+    collector.record(span);
+}
+
+/// What a rollout's spans recorded about its dispatch.
+#[derive(Debug, Clone)]
+pub struct RecordedDispatch {
+    pub text: String,
+    pub user_name: String,
+    pub channel_type: String,
+    /// AI responses in the order they were returned, across all attempts
+    pub responses: Vec<AiResponse>,
+}
+
+impl RecordedDispatch {
+    /// Reconstruct the dispatch from a rollout's spans.
+    pub fn from_spans(spans: &[Span]) -> Result<Self, String> {
+        let mut sorted: Vec<&Span> = spans.iter().collect();
+        sorted.sort_by_key(|s| s.sequence_id);
+
+        let start = sorted
+            .iter()
+            .find(|s| s.span_type == SpanType::Rollout && s.name == "dispatch_start")
+            .ok_or("Rollout has no dispatch_start span")?;
+        let attr = |key: &str| start.attributes.get(key).and_then(|v| v.as_str()).map(str::to_string);
+        let text = attr("text").ok_or("Rollout did not record the user's message, so it can't be replayed")?;
+
+        let responses = sorted
+            .iter()
+            .filter(|s| s.span_type == SpanType::LlmCall && s.name == LLM_RESPONSE_SPAN)
+            .map(|s| {
+                let a = &s.attributes;
+                AiResponse {
+                    content: a.get("content").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                    tool_calls: serde_json::from_value(a.get("tool_calls").cloned().unwrap_or_default())
+                        .unwrap_or_default(),
+                    stop_reason: a.get("stop_reason").and_then(|v| v.as_str()).map(str::to_string),
+                    x402_payment: None,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            text,
+            user_name: attr("user_name").unwrap_or_else(|| "replay".to_string()),
+            channel_type: attr("channel_type").unwrap_or_else(|| "web".to_string()),
+            responses,
+        })
+    }
+}
+
+/// How one execution of a dispatch turned out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayOutcome {
+    pub succeeded: bool,
+    pub response: Option<String>,
+    pub error: Option<String>,
+    /// Tools run, in order
+    pub tool_sequence: Vec<String>,
+    pub summary: ExecutionSummary,
+}
+
+impl ReplayOutcome {
+    pub fn from_spans(spans: &[Span], response: Option<String>, error: Option<String>) -> Self {
+        let mut completions: Vec<&Span> = spans
+            .iter()
+            .filter(|s| s.span_type == SpanType::Reward && s.name == "tool_completed")
+            .collect();
+        completions.sort_by_key(|s| s.sequence_id);
+        let tool_sequence = completions
+            .iter()
+            .filter_map(|s| s.attributes.get("tool_name").and_then(|v| v.as_str()))
+            .map(str::to_string)
+            .collect();
+
+        Self {
+            succeeded: error.is_none(),
+            response,
+            error,
+            tool_sequence,
+            summary: SpansToSummary.transform(spans),
+        }
+    }
+}
+
+/// Comparison of a recorded rollout with its replay against another resource version.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayReport {
+    pub rollout_id: String,
+    pub original_version_id: Option<String>,
+    pub replay_version_id: String,
+    /// Resources whose content differs between the two versions
+    pub changed_resources: Vec<String>,
+    pub original: ReplayOutcome,
+    pub replay: ReplayOutcome,
+    /// Recorded AI responses available to the replay
+    pub recorded_responses: usize,
+    /// AI calls the replay made; more than were recorded means it ran past the recording
+    pub replayed_responses: usize,
+    pub same_tool_sequence: bool,
+    /// Replay reward minus original reward
+    pub reward_delta: f64,
+}
+
+impl ReplayReport {
+    pub fn new(
+        rollout_id: String,
+        original_version: Option<&ResourceBundle>,
+        replay_version: &ResourceBundle,
+        original: ReplayOutcome,
+        replay: ReplayOutcome,
+        recorded_responses: usize,
+        replayed_responses: usize,
+    ) -> Self {
+        Self {
+            rollout_id,
+            original_version_id: original_version.map(|b| b.version_id.clone()),
+            replay_version_id: replay_version.version_id.clone(),
+            changed_resources: changed_resources(original_version, replay_version),
+            same_tool_sequence: original.tool_sequence == replay.tool_sequence,
+            reward_delta: replay.summary.total_reward - original.summary.total_reward,
+            original,
+            replay,
+            recorded_responses,
+            replayed_responses,
+        }
+    }
+}
+
+/// Names of resources added, removed or edited between two versions
+fn changed_resources(from: Option<&ResourceBundle>, to: &ResourceBundle) -> Vec<String> {
+    let before = from.map(|b| b.resources.as_slice()).unwrap_or_default();
+    let mut changed: Vec<String> = to
+        .resources
+        .iter()
+        .filter(|r| before.iter().find(|b| b.name == r.name).is_none_or(|b| b.content != r.content))
+        .chain(before.iter().filter(|b| to.get(&b.name).is_none()))
+        .map(|r| r.name.clone())
+        .collect();
+    changed.sort();
+    changed.dedup();
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::ToolCall;
+    use crate::telemetry::{Resource, ResourceType};
+
+    fn prompt(name: &str, content: &str) -> Resource {
+        Resource {
+            name: name.to_string(),
+            resource_type: ResourceType::PromptTemplate,
+            content: content.to_string(),
+            metadata: serde_json::Value::Null,
+        }
+    }
+
+    #[test]
+    fn recorded_dispatch_round_trips_through_spans() {
+        let collector = SpanCollector::new("rollout-1".to_string(), 1);
+        let mut start = collector.start_span(SpanType::Rollout, "dispatch_start");
+        start.attributes = json!({ "text": "swap 1 eth", "user_name": "alice", "channel_type": "discord" });
+        start.succeed();
+        collector.record(start);
+
+        let call = ToolCall { id: "call_1".to_string(), name: "web3_tx".to_string(), arguments: json!({ "amount": 1 }) };
+        record_llm_response(&collector, &AiResponse::with_tools(String::new(), vec![call]), Duration::from_millis(40));
+        record_llm_response(&collector, &AiResponse::text("done".to_string()), Duration::ZERO);
+
+        let spans = collector.drain();
+        let llm = spans.iter().find(|s| s.span_type == SpanType::LlmCall).unwrap();
+        assert!(llm.duration_ms.unwrap() >= 40);
+
+        let recorded = RecordedDispatch::from_spans(&spans).unwrap();
+        assert_eq!((recorded.text.as_str(), recorded.channel_type.as_str()), ("swap 1 eth", "discord"));
+        assert_eq!(recorded.responses.len(), 2);
+        assert_eq!(recorded.responses[0].tool_calls[0].name, "web3_tx");
+        assert_eq!(recorded.responses[0].stop_reason.as_deref(), Some("tool_use"));
+        assert_eq!(recorded.responses[1].content, "done");
+    }
+
+    #[test]
+    fn dispatch_without_recorded_message_is_not_replayable() {
+        let collector = SpanCollector::new("rollout-1".to_string(), 1);
+        let mut start = collector.start_span(SpanType::Rollout, "dispatch_start");
+        start.succeed();
+        collector.record(start);
+        assert!(RecordedDispatch::from_spans(&collector.drain()).is_err());
+    }
+
+    #[test]
+    fn changed_resources_lists_added_removed_and_edited() {
+        let from = ResourceBundle::new(
+            "v1".to_string(),
+            vec![prompt("a", "same"), prompt("b", "old"), prompt("c", "gone")],
+        );
+        let to = ResourceBundle::new(
+            "v2".to_string(),
+            vec![prompt("a", "same"), prompt("b", "new"), prompt("d", "added")],
+        );
+        assert_eq!(changed_resources(Some(&from), &to), vec!["b", "c", "d"]);
+        assert_eq!(changed_resources(None, &to), vec!["a", "b", "d"]);
+    }
+}
@@ -22,7 +22,10 @@ impl RewardEmitter {
     /// - Success: +1.0
     /// - Failure: -0.5
     /// - Bonus for fast execution (< 1s): +0.2
-    pub fn tool_completed(&self, tool_name: &str, success: bool, duration_ms: u64) {
+    ///
+    /// `skill` is the skill the call ran under, if any; it is recorded as the
+    /// `skill` attribute so per-skill metrics can be aggregated later.
+    pub fn tool_completed(&self, tool_name: &str, success: bool, duration_ms: u64, skill: Option<&str>) {
         let mut value = if success { 1.0 } else { -0.5 };
 
         // Bonus for fast successful tools
@@ -38,6 +41,9 @@ impl RewardEmitter {
             "success": success,
             "duration_ms": duration_ms,
         });
+        if let Some(skill) = skill {
+            span.attributes["skill"] = json!(skill);
+        }
         span.succeed();
         self.collector.record(span);
     }
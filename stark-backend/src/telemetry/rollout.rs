@@ -2,11 +2,14 @@
 //!
 //! Each `dispatch()` becomes a `Rollout` with a formal status machine
 //! (Queuing→Preparing→Running→Succeeded/Failed). Failed executions create
-//! new `Attempt`s with configurable retry policy.
+//! new `Attempt`s with configurable retry policy. Errors are classified into
+//! categories (context overflow, timeout, rate limit, ...) and each category
+//! can have its own attempt limit and backoff.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::span::SpanCollector;
@@ -51,12 +54,55 @@ pub enum RetryCondition {
     OnContextOverflow,
 }
 
+/// Error categories that can each have their own retry policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// Context too large: retried immediately after compaction
+    ContextOverflow,
+    Timeout,
+    /// Provider rate limit (429): may carry a Retry-After
+    RateLimit,
+    /// Other LLM/provider errors (5xx)
+    LlmError,
+    ToolError,
+    Other,
+}
+
+/// Retry policy for one error category.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts (1 = no retry)
+    pub max_attempts: u32,
+    /// Base delay between retry attempts in milliseconds
+    pub retry_delay_ms: u64,
+    /// Whether to use exponential backoff for retries
+    pub exponential_backoff: bool,
+    /// Maximum retry delay (ms), also the cap on Retry-After
+    pub max_retry_delay_ms: u64,
+    /// Wait as long as the provider's Retry-After asks, when it gives one
+    #[serde(default)]
+    pub honor_retry_after: bool,
+}
+
+impl RetryPolicy {
+    /// Calculate the delay for a given attempt index (0-based).
+    pub fn delay_for_attempt(&self, attempt_idx: u32) -> u64 {
+        if self.exponential_backoff {
+            let delay = self.retry_delay_ms.saturating_mul(2u64.saturating_pow(attempt_idx));
+            delay.min(self.max_retry_delay_ms)
+        } else {
+            self.retry_delay_ms
+        }
+    }
+}
+
 /// Configuration for rollout behavior.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RolloutConfig {
     /// Maximum time for the entire rollout in seconds
     pub timeout_secs: u64,
-    /// Maximum number of attempts (1 = no retry)
+    /// Maximum number of attempts (1 = no retry) for categories without a policy
     pub max_attempts: u32,
     /// Which conditions trigger a retry
     pub retry_conditions: Vec<RetryCondition>,
@@ -66,6 +112,9 @@ pub struct RolloutConfig {
     pub exponential_backoff: bool,
     /// Maximum retry delay when using exponential backoff (ms)
     pub max_retry_delay_ms: u64,
+    /// Per-category overrides of the attempt limit and backoff above
+    #[serde(default)]
+    pub retry_policies: HashMap<ErrorCategory, RetryPolicy>,
 }
 
 impl Default for RolloutConfig {
@@ -81,30 +130,73 @@ impl Default for RolloutConfig {
             retry_delay_ms: 1000,
             exponential_backoff: true,
             max_retry_delay_ms: 30_000,
+            retry_policies: HashMap::from([
+                // Compaction is what fixes an overflow, so retry right away, once
+                (ErrorCategory::ContextOverflow, RetryPolicy {
+                    max_attempts: 2,
+                    retry_delay_ms: 0,
+                    exponential_backoff: false,
+                    max_retry_delay_ms: 0,
+                    honor_retry_after: false,
+                }),
+                // A slow provider needs time to recover
+                (ErrorCategory::Timeout, RetryPolicy {
+                    max_attempts: 3,
+                    retry_delay_ms: 5_000,
+                    exponential_backoff: true,
+                    max_retry_delay_ms: 60_000,
+                    honor_retry_after: false,
+                }),
+                (ErrorCategory::RateLimit, RetryPolicy {
+                    max_attempts: 4,
+                    retry_delay_ms: 2_000,
+                    exponential_backoff: true,
+                    max_retry_delay_ms: 60_000,
+                    honor_retry_after: true,
+                }),
+            ]),
         }
     }
 }
 
 impl RolloutConfig {
-    /// Calculate the delay for a given attempt index (0-based).
+    /// The policy for a category: its override, or the top-level settings.
+    pub fn policy_for(&self, category: ErrorCategory) -> RetryPolicy {
+        self.retry_policies.get(&category).cloned().unwrap_or(RetryPolicy {
+            max_attempts: self.max_attempts,
+            retry_delay_ms: self.retry_delay_ms,
+            exponential_backoff: self.exponential_backoff,
+            max_retry_delay_ms: self.max_retry_delay_ms,
+            honor_retry_after: true,
+        })
+    }
+
+    /// Calculate the delay for a given attempt index (0-based) with the top-level settings.
     pub fn delay_for_attempt(&self, attempt_idx: u32) -> u64 {
-        if self.exponential_backoff {
-            let delay = self.retry_delay_ms * 2u64.pow(attempt_idx);
-            delay.min(self.max_retry_delay_ms)
-        } else {
-            self.retry_delay_ms
+        self.policy_for(ErrorCategory::Other).delay_for_attempt(attempt_idx)
+    }
+
+    /// Delay before retrying after a failure, following its category's policy.
+    /// The provider's Retry-After (when the attempt got one) wins over the
+    /// computed backoff if the policy honors it.
+    pub fn retry_delay_for(&self, reason: &FailureReason, retry_after_ms: Option<u64>, attempt_idx: u32) -> u64 {
+        let policy = self.policy_for(reason.category());
+        match retry_after_ms {
+            Some(ms) if policy.honor_retry_after => ms.min(policy.max_retry_delay_ms),
+            _ => policy.delay_for_attempt(attempt_idx),
         }
     }
 
-    /// Check if a failure reason matches the retry conditions.
+    /// Check if a failure reason matches the retry conditions and its
+    /// category still has attempts left.
     pub fn should_retry(&self, reason: &FailureReason, attempt_count: u32) -> bool {
-        if attempt_count >= self.max_attempts {
+        if attempt_count >= self.policy_for(reason.category()).max_attempts {
             return false;
         }
         self.retry_conditions.iter().any(|cond| match (cond, reason) {
             (RetryCondition::OnAnyFailure, _) => true,
             (RetryCondition::OnTimeout, FailureReason::Timeout) => true,
-            (RetryCondition::OnLlmError, FailureReason::LlmError(_)) => true,
+            (RetryCondition::OnLlmError, FailureReason::LlmError(_) | FailureReason::RateLimited(_)) => true,
             (RetryCondition::OnToolError, FailureReason::ToolError(_)) => true,
             (RetryCondition::OnContextOverflow, FailureReason::ContextOverflow) => true,
             _ => false,
@@ -122,6 +214,7 @@ pub enum FailureReason {
     ContextOverflow,
     LoopDetected,
     Cancelled,
+    RateLimited(String),
     Unknown(String),
 }
 
@@ -129,7 +222,9 @@ impl FailureReason {
     /// Classify an error string into a failure reason.
     pub fn classify(error: &str) -> Self {
         let lower = error.to_lowercase();
-        if lower.contains("timed out") || lower.contains("timeout") {
+        if lower.contains("rate limit") || lower.contains("429") || lower.contains("too many requests") {
+            FailureReason::RateLimited(error.to_string())
+        } else if lower.contains("timed out") || lower.contains("timeout") {
             FailureReason::Timeout
         } else if lower.contains("context") && (lower.contains("too large") || lower.contains("overflow")) {
             FailureReason::ContextOverflow
@@ -137,12 +232,24 @@ impl FailureReason {
             FailureReason::LoopDetected
         } else if lower.contains("cancelled") || lower.contains("canceled") {
             FailureReason::Cancelled
-        } else if lower.contains("rate limit") || lower.contains("429") || lower.contains("500") || lower.contains("503") {
+        } else if lower.contains("500") || lower.contains("503") {
             FailureReason::LlmError(error.to_string())
         } else {
             FailureReason::Unknown(error.to_string())
         }
     }
+
+    /// The retry policy category this failure falls under.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            FailureReason::ContextOverflow => ErrorCategory::ContextOverflow,
+            FailureReason::Timeout => ErrorCategory::Timeout,
+            FailureReason::RateLimited(_) => ErrorCategory::RateLimit,
+            FailureReason::LlmError(_) => ErrorCategory::LlmError,
+            FailureReason::ToolError(_) => ErrorCategory::ToolError,
+            FailureReason::LoopDetected | FailureReason::Cancelled | FailureReason::Unknown(_) => ErrorCategory::Other,
+        }
+    }
 }
 
 /// A single attempt within a rollout.
@@ -168,6 +275,9 @@ pub struct Attempt {
     pub llm_calls: u32,
     /// Total tokens consumed in this attempt
     pub tokens_used: u64,
+    /// Wait the provider asked for before retrying (its Retry-After), in ms
+    #[serde(default)]
+    pub retry_after_ms: Option<u64>,
 }
 
 impl Attempt {
@@ -183,6 +293,7 @@ impl Attempt {
             tool_calls: 0,
             llm_calls: 0,
             tokens_used: 0,
+            retry_after_ms: None,
         }
     }
 
@@ -195,6 +306,8 @@ impl Attempt {
 
     pub fn fail(&mut self, reason: FailureReason, error: String) {
         let now = Utc::now();
+        // AI errors carry the provider's Retry-After as "(Retry after Ns)"
+        self.retry_after_ms = crate::channels::util::parse_retry_after(&error).map(|secs| secs.saturating_mul(1000));
         self.succeeded = false;
         self.failure_reason = Some(reason);
         self.error = Some(error);
@@ -276,6 +389,19 @@ impl Rollout {
     pub fn attempt_count(&self) -> u32 {
         self.attempts.len() as u32
     }
+
+    /// The most recent failed attempt
+    pub fn last_failure(&self) -> Option<&Attempt> {
+        self.attempts.iter().rev().find(|a| a.failure_reason.is_some())
+    }
+
+    /// Attempt limit under the policy of the most recent failure
+    pub fn max_attempts(&self) -> u32 {
+        match self.last_failure().and_then(|a| a.failure_reason.as_ref()) {
+            Some(reason) => self.config.policy_for(reason.category()).max_attempts,
+            None => self.config.max_attempts,
+        }
+    }
 }
 
 /// Manages the lifecycle of rollouts and attempts.
@@ -288,14 +414,17 @@ impl RolloutManager {
         Self { db }
     }
 
-    /// Create a new rollout and its first attempt.
+    /// Create a new rollout and its first attempt. `rollout_id` is the
+    /// dispatch's trace id, so logs and gateway events share it.
     pub fn start_rollout(
         &self,
+        rollout_id: String,
         session_id: i64,
         channel_id: i64,
         config: RolloutConfig,
     ) -> (Rollout, SpanCollector) {
         let mut rollout = Rollout::new(session_id, channel_id, config);
+        rollout.rollout_id = rollout_id;
         rollout.status = RolloutStatus::Preparing;
 
         let attempt = Attempt::new(0);
@@ -319,6 +448,19 @@ impl RolloutManager {
         }
     }
 
+    /// Attach the resolved session to the rollout, persisting it along with
+    /// the resource version the rollout runs with.
+    pub fn set_session(&self, rollout: &mut Rollout, session_id: i64) {
+        rollout.session_id = session_id;
+        if let Err(e) = self.db.update_rollout_session(
+            &rollout.rollout_id,
+            session_id,
+            rollout.resources_id.as_deref(),
+        ) {
+            log::error!("[ROLLOUT] Failed to update rollout session: {}", e);
+        }
+    }
+
     /// Mark the current attempt as succeeded and complete the rollout.
     pub fn succeed_rollout(&self, rollout: &mut Rollout, result: String) {
         if let Some(attempt) = rollout.current_attempt_mut() {
@@ -370,7 +512,7 @@ impl RolloutManager {
                 "[ROLLOUT] Retrying rollout {} (attempt {}/{}), reason: {:?}",
                 rollout.rollout_id,
                 new_idx + 1,
-                rollout.config.max_attempts,
+                rollout.max_attempts(),
                 reason
             );
 
@@ -407,10 +549,14 @@ impl RolloutManager {
         self.persist_rollout_completion(rollout);
     }
 
-    /// Get the retry delay for the current attempt.
+    /// Get the retry delay for the current attempt, per the policy of the
+    /// failure that triggered it.
     pub fn retry_delay(&self, rollout: &Rollout) -> u64 {
         let idx = rollout.attempt_count().saturating_sub(1);
-        rollout.config.delay_for_attempt(idx)
+        match rollout.last_failure().and_then(|a| Some((a.failure_reason.as_ref()?, a.retry_after_ms))) {
+            Some((reason, retry_after_ms)) => rollout.config.retry_delay_for(reason, retry_after_ms, idx),
+            None => rollout.config.delay_for_attempt(idx),
+        }
     }
 
     fn persist_rollout_completion(&self, rollout: &Rollout) {
@@ -432,3 +578,94 @@ impl RolloutManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager() -> RolloutManager {
+        RolloutManager::new(Arc::new(crate::db::Database::new(":memory:").expect("in-memory db")))
+    }
+
+    /// Fail attempts with `error` until the rollout stops retrying; returns the
+    /// number of attempts made and the delays chosen between them
+    fn run_until_exhausted(error: &str) -> (u32, Vec<u64>) {
+        let manager = manager();
+        let (mut rollout, collector) = manager.start_rollout("r".to_string(), 1, 1, RolloutConfig::default());
+        let mut delays = Vec::new();
+        while manager.fail_attempt(&mut rollout, error, &collector) {
+            delays.push(manager.retry_delay(&rollout));
+        }
+        (rollout.attempt_count(), delays)
+    }
+
+    #[test]
+    fn classifies_errors_into_categories() {
+        let category = |e: &str| FailureReason::classify(e).category();
+        assert_eq!(category("Context too large for model"), ErrorCategory::ContextOverflow);
+        assert_eq!(category("operation timed out"), ErrorCategory::Timeout);
+        assert_eq!(category("[HTTP 429] Too Many Requests (Retry after 7s)"), ErrorCategory::RateLimit);
+        assert_eq!(category("[HTTP 503] Service Unavailable"), ErrorCategory::LlmError);
+        assert_eq!(category("something odd"), ErrorCategory::Other);
+    }
+
+    #[test]
+    fn context_overflow_retries_once_immediately() {
+        let (attempts, delays) = run_until_exhausted("context overflow: input too large");
+        assert_eq!(attempts, 2);
+        assert_eq!(delays, vec![0]);
+    }
+
+    #[test]
+    fn timeouts_back_off_longer() {
+        let (attempts, delays) = run_until_exhausted("request timed out");
+        assert_eq!(attempts, 3);
+        assert_eq!(delays, vec![10_000, 20_000]);
+    }
+
+    #[test]
+    fn rate_limits_honor_retry_after() {
+        let (attempts, delays) = run_until_exhausted("[HTTP 429] rate limited (Retry after 7s)");
+        assert_eq!(attempts, 4);
+        assert_eq!(delays, vec![7_000; 3]);
+
+        // Without a Retry-After, backoff applies; a huge one is capped
+        let config = RolloutConfig::default();
+        let reason = FailureReason::classify("[HTTP 429] rate limited");
+        assert_eq!(config.retry_delay_for(&reason, None, 1), 4_000);
+        assert_eq!(config.retry_delay_for(&reason, Some(3_600_000), 1), 60_000);
+    }
+
+    #[test]
+    fn retry_after_wins_over_backoff_for_any_category() {
+        // A 503 with Retry-After falls back to the top-level policy, which honors it
+        let (attempts, delays) = run_until_exhausted("[HTTP 503] Service Unavailable (Retry after 5s)");
+        assert_eq!(attempts, 3);
+        assert_eq!(delays, vec![5_000, 5_000]);
+    }
+
+    #[test]
+    fn categories_without_a_policy_use_the_defaults() {
+        let (attempts, delays) = run_until_exhausted("[HTTP 500] Internal Server Error");
+        assert_eq!(attempts, 3);
+        assert_eq!(delays, vec![2_000, 4_000]);
+
+        // Not covered by any retry condition
+        let (attempts, _) = run_until_exhausted("something odd");
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn per_category_policies_can_be_overridden() {
+        let mut config = RolloutConfig::default();
+        config.retry_policies.insert(ErrorCategory::Timeout, RetryPolicy {
+            max_attempts: 1,
+            retry_delay_ms: 0,
+            exponential_backoff: false,
+            max_retry_delay_ms: 0,
+            honor_retry_after: false,
+        });
+        assert!(!config.should_retry(&FailureReason::Timeout, 1));
+        assert!(config.should_retry(&FailureReason::LlmError("503".to_string()), 1));
+    }
+}
@@ -30,6 +30,8 @@ pub enum SpanType {
     Watchdog,
     /// A resource version resolution
     ResourceResolution,
+    /// A lifecycle hook execution
+    Hook,
 }
 
 /// The completion status of a span.
@@ -49,6 +49,24 @@ pub struct RewardTypeStat {
     pub avg_value: f64,
 }
 
+/// Execution metrics for a single skill, aggregated from `tool_completed`
+/// reward spans tagged with the skill that was active.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SkillMetrics {
+    pub skill_name: String,
+    /// Number of `use_skill` calls for this skill
+    pub invocations: usize,
+    /// Tool calls made while the skill was active (including `use_skill`)
+    pub tool_calls: usize,
+    pub successes: usize,
+    pub failures: usize,
+    pub success_rate: f64,
+    pub avg_duration_ms: f64,
+    pub total_duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
 /// The telemetry store provides high-level persistence and query operations.
 pub struct TelemetryStore {
     db: Arc<crate::db::Database>,
@@ -86,6 +104,10 @@ impl TelemetryStore {
                 log::error!("[TELEMETRY] Failed to persist span {}: {}", span.span_id, e);
             }
         }
+
+        if let Some(exporter) = super::otlp::exporter() {
+            exporter.export(spans);
+        }
     }
 
     /// Get all spans for a rollout.
@@ -204,6 +226,74 @@ impl TelemetryStore {
         }
     }
 
+    /// Get per-skill execution metrics over a time period, busiest first.
+    pub fn get_skill_metrics(&self, since: Option<DateTime<Utc>>) -> Vec<SkillMetrics> {
+        let reward_spans = self.query_spans(
+            Some(SpanType::Reward),
+            None,
+            since,
+            None,
+        );
+
+        let mut by_skill: std::collections::HashMap<String, SkillMetrics> = std::collections::HashMap::new();
+
+        for span in &reward_spans {
+            if span.name != "tool_completed" {
+                continue;
+            }
+            let Some(skill) = span.attributes.get("skill").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let success = span.attributes.get("success")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let duration_ms = span.attributes.get("duration_ms")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            let is_invocation = span.attributes.get("tool_name")
+                .and_then(|v| v.as_str())
+                == Some("use_skill");
+
+            let entry = by_skill.entry(skill.to_string()).or_insert_with(|| SkillMetrics {
+                skill_name: skill.to_string(),
+                ..Default::default()
+            });
+            entry.tool_calls += 1;
+            if success { entry.successes += 1; } else { entry.failures += 1; }
+            if is_invocation { entry.invocations += 1; }
+            entry.total_duration_ms += duration_ms;
+            if entry.last_used_at.is_none_or(|t| span.started_at > t) {
+                entry.last_used_at = Some(span.started_at);
+            }
+        }
+
+        let mut metrics: Vec<SkillMetrics> = by_skill
+            .into_values()
+            .map(|mut m| {
+                m.success_rate = m.successes as f64 / m.tool_calls as f64;
+                m.avg_duration_ms = m.total_duration_ms as f64 / m.tool_calls as f64;
+                m
+            })
+            .collect();
+        metrics.sort_by(|a, b| {
+            b.invocations.cmp(&a.invocations)
+                .then(b.tool_calls.cmp(&a.tool_calls))
+                .then(a.skill_name.cmp(&b.skill_name))
+        });
+        metrics
+    }
+
+    /// Number of rollouts in each status (queuing, running, succeeded, ...).
+    pub fn rollout_status_counts(&self) -> Vec<(String, i64)> {
+        match self.db.count_rollouts_by_status() {
+            Ok(counts) => counts,
+            Err(e) => {
+                log::error!("[TELEMETRY] Failed to count rollouts: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
     /// Prune telemetry data older than the retention policy.
     pub fn prune(&self) {
         let span_cutoff = Utc::now() - Duration::days(self.retention.span_retention_days as i64);
@@ -228,3 +318,38 @@ impl TelemetryStore {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::RewardEmitter;
+
+    #[test]
+    fn test_skill_metrics() {
+        let db = Arc::new(crate::db::Database::new(":memory:").unwrap());
+        let store = TelemetryStore::new(db);
+        let collector = Arc::new(SpanCollector::new("rollout-1".to_string(), 1));
+        let rewards = RewardEmitter::new(collector.clone());
+
+        rewards.tool_completed("use_skill", true, 10, Some("swap"));
+        rewards.tool_completed("web3_tx", true, 300, Some("swap"));
+        rewards.tool_completed("web3_tx", false, 500, Some("swap"));
+        rewards.tool_completed("use_skill", true, 20, Some("weather"));
+        rewards.tool_completed("read_file", true, 5, None);
+        store.persist_spans(&collector);
+
+        let metrics = store.get_skill_metrics(None);
+        assert_eq!(metrics.len(), 2);
+
+        let swap = &metrics[0];
+        assert_eq!(swap.skill_name, "swap");
+        assert_eq!((swap.invocations, swap.tool_calls), (1, 3));
+        assert_eq!((swap.successes, swap.failures), (2, 1));
+        assert_eq!(swap.total_duration_ms, 810);
+        assert!((swap.avg_duration_ms - 270.0).abs() < 1e-9);
+        assert!(swap.last_used_at.is_some());
+
+        assert_eq!(metrics[1].skill_name, "weather");
+        assert_eq!(metrics[1].success_rate, 1.0);
+    }
+}
@@ -0,0 +1,79 @@
+//! Per-dispatch trace ids for log correlation.
+//!
+//! `dispatch` runs inside a trace scope whose id is also the rollout id, so a
+//! single request can be followed through logs, gateway events and telemetry
+//! with one grep. Log lines emitted inside the scope get a `[trace:<id>]`
+//! prefix and gateway events get a `trace_id` field.
+//!
+//! The id lives in a tokio task-local, so it follows the dispatch across
+//! `.await`s but not into `tokio::spawn`ed tasks — wrap those futures with
+//! `in_current_trace` to carry it over.
+
+use std::future::Future;
+
+tokio::task_local! {
+    static TRACE_ID: String;
+}
+
+/// Generate a new trace id (a UUID, so it can double as a rollout id)
+pub fn new_trace_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// Run `fut` with `trace_id` as the current trace id
+pub async fn with_trace_id<F: Future>(trace_id: String, fut: F) -> F::Output {
+    TRACE_ID.scope(trace_id, fut).await
+}
+
+/// The trace id of the current dispatch, if any
+pub fn current_trace_id() -> Option<String> {
+    TRACE_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Carry the current trace id (if any) into a future that will be spawned
+pub fn in_current_trace<F>(fut: F) -> impl Future<Output = F::Output>
+where
+    F: Future,
+{
+    let trace_id = current_trace_id();
+    async move {
+        match trace_id {
+            Some(id) => TRACE_ID.scope(id, fut).await,
+            None => fut.await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn trace_id_is_scoped_and_carried_into_spawned_tasks() {
+        assert_eq!(current_trace_id(), None);
+
+        let inner = with_trace_id("abc".to_string(), async {
+            let spawned = tokio::spawn(in_current_trace(async { current_trace_id() }));
+            let bare = tokio::spawn(async { current_trace_id() });
+            (current_trace_id(), spawned.await.unwrap(), bare.await.unwrap())
+        })
+        .await;
+
+        assert_eq!(inner, (Some("abc".to_string()), Some("abc".to_string()), None));
+        assert_eq!(current_trace_id(), None);
+    }
+
+    #[tokio::test]
+    async fn gateway_events_in_a_trace_carry_its_id() {
+        use crate::gateway::protocol::GatewayEvent;
+
+        let event = with_trace_id("abc".to_string(), async {
+            GatewayEvent::new("agent.thinking", serde_json::json!({ "channel_id": 1 }))
+        })
+        .await;
+        assert_eq!(event.data["trace_id"], "abc");
+
+        let untraced = GatewayEvent::new("agent.thinking", serde_json::json!({ "channel_id": 1 }));
+        assert!(untraced.data.get("trace_id").is_none());
+    }
+}
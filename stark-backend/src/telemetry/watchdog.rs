@@ -119,6 +119,11 @@ impl Watchdog {
         &self.config
     }
 
+    /// Get the span collector for the execution being guarded.
+    pub fn collector(&self) -> &Arc<SpanCollector> {
+        &self.collector
+    }
+
     /// Get the reward emitter for structured reward signals.
     pub fn reward_emitter(&self) -> &RewardEmitter {
         &self.reward_emitter
@@ -0,0 +1,196 @@
+//! Built-in validator for the `http_get` tool
+//!
+//! Enforces the SSRF policy before the request is made: HTTP(S) only, the host
+//! must be on the configured domain allowlist (if any), and it must not be or
+//! resolve to a private, loopback, or link-local address. The tool re-checks
+//! the same policy on every redirect hop via [`check_http_get_url`], and
+//! connects only to the addresses vetted by [`resolve_public_addrs`].
+
+use async_trait::async_trait;
+use super::traits::ToolValidator;
+use super::types::{ValidationContext, ValidationResult, ValidatorPriority};
+use crate::tools::builtin::{is_private_ip, validate_public_url};
+use std::net::{IpAddr, SocketAddr};
+
+/// Whether `host` is one of `allowed_domains` or a subdomain of one.
+/// An empty allowlist allows every host.
+pub fn domain_allowed(host: &str, allowed_domains: &[String]) -> bool {
+    if allowed_domains.is_empty() {
+        return true;
+    }
+    let host = host.trim_end_matches('.').to_lowercase();
+    allowed_domains.iter().any(|domain| {
+        host == *domain
+            || host
+                .strip_suffix(domain.as_str())
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    })
+}
+
+/// Check a URL against the http_get policy
+pub fn check_http_get_url(url: &url::Url, allowed_domains: &[String]) -> Result<(), String> {
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err(format!("Scheme '{}' is not allowed, use http or https", url.scheme()));
+    }
+    let host = url.host_str().ok_or("URL has no host")?;
+
+    // Literal IPs never go through DNS, so check them directly
+    let literal = host.trim_start_matches('[').trim_end_matches(']');
+    if let Ok(ip) = literal.parse::<IpAddr>()
+        && is_private_ip(ip)
+    {
+        return Err(format!("Access to private address '{}' is blocked", ip));
+    }
+
+    if !domain_allowed(host, allowed_domains) {
+        return Err(format!("Host '{}' is not on the http_get allowlist", host));
+    }
+
+    validate_public_url(url)
+}
+
+/// Resolve `url`'s host and check that every address it resolves to is public.
+///
+/// The request must then connect to exactly these addresses (pin them with
+/// `reqwest::ClientBuilder::resolve_to_addrs`): letting the client resolve the
+/// host again would allow a DNS-rebinding answer to differ from the vetted one.
+/// Literal IPs never go through DNS, so they return an empty list.
+pub async fn resolve_public_addrs(url: &url::Url) -> Result<Vec<SocketAddr>, String> {
+    let host = url.host_str().ok_or("URL has no host")?;
+    if host.trim_start_matches('[').trim_end_matches(']').parse::<IpAddr>().is_ok() {
+        return Ok(Vec::new());
+    }
+    let port = url.port_or_known_default().unwrap_or(80);
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("Failed to resolve '{}': {}", host, e))?
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("'{}' did not resolve to any address", host));
+    }
+    if let Some(private) = addrs.iter().find(|a| is_private_ip(a.ip())) {
+        return Err(format!(
+            "'{}' resolves to private address '{}', access blocked",
+            host,
+            private.ip()
+        ));
+    }
+    Ok(addrs)
+}
+
+/// Blocks `http_get` calls whose URL falls outside the configured policy
+pub struct HttpGetValidator {
+    allowed_domains: Vec<String>,
+}
+
+impl HttpGetValidator {
+    /// Create a validator using `STARK_HTTP_GET_ALLOWED_DOMAINS`
+    pub fn from_env() -> Self {
+        Self::new(crate::config::http_get_allowed_domains())
+    }
+
+    pub fn new(allowed_domains: Vec<String>) -> Self {
+        Self { allowed_domains }
+    }
+}
+
+#[async_trait]
+impl ToolValidator for HttpGetValidator {
+    fn id(&self) -> &str {
+        "http_get_policy"
+    }
+
+    fn name(&self) -> &str {
+        "HTTP GET Policy"
+    }
+
+    fn description(&self) -> &str {
+        "Restricts http_get to allowlisted public hosts"
+    }
+
+    fn applies_to(&self) -> Option<Vec<&str>> {
+        Some(vec!["http_get"])
+    }
+
+    fn priority(&self) -> ValidatorPriority {
+        ValidatorPriority::Critical
+    }
+
+    async fn validate(&self, ctx: &ValidationContext) -> ValidationResult {
+        let Some(raw) = ctx.tool_args.get("url").and_then(|v| v.as_str()) else {
+            return ValidationResult::Block("Missing 'url' parameter".into());
+        };
+        let url = match url::Url::parse(raw) {
+            Ok(u) => u,
+            Err(e) => return ValidationResult::Block(format!("Invalid URL: {}", e)),
+        };
+        match check_http_get_url(&url, &self.allowed_domains) {
+            Ok(()) => ValidationResult::Allow,
+            Err(reason) => ValidationResult::Block(reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::types::ToolContext;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn url(s: &str) -> url::Url {
+        url::Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_domain_allowlist_matches_subdomains_only() {
+        let allowed = vec!["example.com".to_string()];
+        assert!(domain_allowed("example.com", &allowed));
+        assert!(domain_allowed("api.example.com", &allowed));
+        assert!(domain_allowed("API.Example.com.", &allowed));
+        assert!(!domain_allowed("badexample.com", &allowed));
+        assert!(!domain_allowed("example.com.evil.io", &allowed));
+        assert!(domain_allowed("anything.io", &[]));
+    }
+
+    #[test]
+    fn test_blocks_private_and_loopback_addresses() {
+        for blocked in [
+            "http://127.0.0.1/",
+            "http://10.1.2.3:8080/admin",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/",
+            "http://[::ffff:192.168.0.1]/",
+            "http://localhost:3000/",
+        ] {
+            assert!(check_http_get_url(&url(blocked), &[]).is_err(), "{} should be blocked", blocked);
+        }
+    }
+
+    #[test]
+    fn test_blocks_non_http_schemes_and_unlisted_hosts() {
+        assert!(check_http_get_url(&url("file:///etc/passwd"), &[]).is_err());
+        assert!(check_http_get_url(&url("ftp://example.com/x"), &[]).is_err());
+        let allowed = vec!["example.com".to_string()];
+        let err = check_http_get_url(&url("https://8.8.8.8/"), &allowed).unwrap_err();
+        assert!(err.contains("allowlist"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_public_addrs() {
+        assert!(resolve_public_addrs(&url("http://localhost:8080/")).await.is_err());
+        // Literal IPs are connected to as-is
+        assert!(resolve_public_addrs(&url("https://8.8.8.8/")).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_validator_blocks_private_url() {
+        let validator = HttpGetValidator::new(vec![]);
+        let ctx = ValidationContext::new(
+            "http_get".into(),
+            json!({ "url": "http://192.168.1.1/router" }),
+            Arc::new(ToolContext::new()),
+        );
+        assert!(validator.validate(&ctx).await.is_blocked());
+    }
+}
@@ -31,7 +31,15 @@
 //! ## 2. Rust Structs (Legacy)
 //!
 //! Implement the `ToolValidator` trait directly for complex validation logic
-//! that can't be expressed in the RON DSL.
+//! that can't be expressed in the RON DSL. The `http_get` SSRF policy
+//! (`http_get.rs`) is registered this way.
+//!
+//! ## Argument Schemas
+//!
+//! Tools can set `ToolDefinition::arg_constraints` to JSON schema keywords
+//! that `input_schema` can't express; `ToolDefinition::args_schema` combines
+//! the two into one schema. The registry checks arguments against it (see `schema.rs`) before any validator runs and
+//! returns the exact failing argument so the agent can fix the call.
 //!
 //! # Condition Types (RON DSL)
 //!
@@ -42,8 +50,10 @@
 //! - `ArgContains("key", "substr")` - Argument contains substring
 //! - `ArgExists("key")` - Argument is present
 //! - `ArgMissing("key")` - Argument is not present
+//! - `AnyArgMatches("regex")` - Any string argument, at any depth, matches regex
 //! - `CredentialExists("KEY")` - API key exists and is non-empty
 //! - `CredentialMissing("KEY")` - API key does not exist
+//! - `AnyArgContainsCredential` - Any string argument contains an installed API key's value
 //! - `All([...])` - AND combinator
 //! - `Any([...])` - OR combinator
 //! - `Not(...)` - Negation
@@ -52,6 +62,8 @@ pub mod types;
 pub mod traits;
 pub mod registry;
 pub mod ron;
+pub mod schema;
+pub mod http_get;
 
 pub use types::*;
 pub use traits::*;
@@ -59,9 +71,11 @@ pub use registry::*;
 
 use std::path::Path;
 
-/// Create the default validator registry, loading RON validators from config/validators/
+/// Create the default validator registry with the built-in validators, plus RON
+/// validators loaded from config/validators/
 pub fn create_default_registry() -> ValidatorRegistry {
     let mut registry = ValidatorRegistry::new();
+    registry.register(std::sync::Arc::new(http_get::HttpGetValidator::from_env()));
 
     // Load RON validators from config directory
     // Check ./config/validators first, then ../config/validators
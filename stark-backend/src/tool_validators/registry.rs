@@ -40,9 +40,20 @@ impl ValidatorRegistry {
 
     /// Run all applicable validators against a tool call
     ///
-    /// Validators are run in priority order. The first validator to return
-    /// a Block result will stop execution and return that result.
+    /// Arguments are first checked against the tool's schema, if it declares
+    /// one. Validators then run in priority order. The first validator to
+    /// return a Block result will stop execution and return that result.
     pub async fn validate(&self, ctx: &ValidationContext) -> ValidationResult {
+        if let Some(ref schema) = ctx.args_schema
+            && let Err(e) = super::schema::validate_args(schema, &ctx.tool_args)
+        {
+            log::info!("[VALIDATOR] Schema rejected arguments for '{}': {}", ctx.tool_name, e);
+            return ValidationResult::BlockWithSuggestion {
+                reason: format!("Invalid arguments for '{}': {}.", ctx.tool_name, e),
+                suggestion: "Fix the argument and call the tool again.".to_string(),
+            };
+        }
+
         for validator in &self.validators {
             // Skip disabled validators
             if !validator.enabled() {
@@ -162,6 +173,28 @@ mod tests {
         assert_eq!(result.block_reason(), Some("Test block"));
     }
 
+    #[tokio::test]
+    async fn test_schema_checked_before_validators() {
+        let mut registry = ValidatorRegistry::new();
+        registry.register(Arc::new(AlwaysAllowValidator));
+        let schema = json!({
+            "type": "object",
+            "properties": { "count": { "type": "integer" } },
+            "required": ["count"]
+        });
+        let ctx = |args| {
+            ValidationContext::new("some_tool".into(), args, Arc::new(ToolContext::new()))
+                .with_args_schema(Some(schema.clone()))
+        };
+
+        let result = registry.validate(&ctx(json!({ "count": "three" }))).await;
+        assert_eq!(
+            result.block_reason(),
+            Some("Invalid arguments for 'some_tool': `count`: expected integer, got string.")
+        );
+        assert!(registry.validate(&ctx(json!({ "count": 3 }))).await.is_allowed());
+    }
+
     #[tokio::test]
     async fn test_validator_only_applies_to_specified_tools() {
         let mut registry = ValidatorRegistry::new();
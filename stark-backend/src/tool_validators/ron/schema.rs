@@ -71,12 +71,16 @@ pub enum Condition {
     ArgExists(String),
     /// Argument is not present
     ArgMissing(String),
+    /// Any string argument, at any depth, matches regex pattern
+    AnyArgMatches(String),
 
     // Credential checks
     /// API key/credential exists and is non-empty
     CredentialExists(String),
     /// API key/credential does not exist or is empty
     CredentialMissing(String),
+    /// Any string argument contains the value of an installed API key
+    AnyArgContainsCredential,
 
     // Combinators
     /// All conditions must match (AND)
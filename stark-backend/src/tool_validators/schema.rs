@@ -0,0 +1,208 @@
+//! Argument schema checks for tools that declare `ToolDefinition::arg_constraints`
+//!
+//! Implements the subset of JSON Schema that tool arguments need: `type`
+//! (single or list), `enum`, `const`, `required`, `properties`,
+//! `additionalProperties: false`, `items`, `minimum`/`maximum`,
+//! `minLength`/`maxLength`, `pattern` and `minItems`/`maxItems`. Errors name
+//! the offending argument path so the agent can correct the call.
+
+use regex::Regex;
+use serde_json::Value;
+
+/// Validate `args` against `schema`, returning the first violation found
+pub fn validate_args(schema: &Value, args: &Value) -> Result<(), String> {
+    check(schema, args, "")
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn matches_type(expected: &str, value: &Value) -> bool {
+    match expected {
+        "number" => value.is_number(),
+        "integer" => value.as_f64().is_some_and(|f| f.fract() == 0.0),
+        other => type_name(value) == other,
+    }
+}
+
+fn label(path: &str) -> String {
+    if path.is_empty() {
+        "arguments".to_string()
+    } else {
+        format!("`{}`", path)
+    }
+}
+
+fn check(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(());
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(|t| t.as_str()).collect(),
+            _ => vec![],
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|t| matches_type(t, value)) {
+            return Err(format!(
+                "{}: expected {}, got {}",
+                label(path),
+                allowed.join(" or "),
+                type_name(value)
+            ));
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum")
+        && !options.contains(value)
+    {
+        let options: Vec<String> = options.iter().map(|o| o.to_string()).collect();
+        return Err(format!("{}: must be one of {}", label(path), options.join(", ")));
+    }
+    if let Some(expected) = schema.get("const")
+        && expected != value
+    {
+        return Err(format!("{}: must be {}", label(path), expected));
+    }
+
+    match value {
+        Value::Number(n) => {
+            let n = n.as_f64().unwrap_or_default();
+            if let Some(min) = schema.get("minimum").and_then(|m| m.as_f64())
+                && n < min
+            {
+                return Err(format!("{}: must be >= {}", label(path), min));
+            }
+            if let Some(max) = schema.get("maximum").and_then(|m| m.as_f64())
+                && n > max
+            {
+                return Err(format!("{}: must be <= {}", label(path), max));
+            }
+        }
+        Value::String(s) => {
+            let len = s.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(|m| m.as_u64())
+                && len < min
+            {
+                return Err(format!("{}: must be at least {} characters", label(path), min));
+            }
+            if let Some(max) = schema.get("maxLength").and_then(|m| m.as_u64())
+                && len > max
+            {
+                return Err(format!("{}: must be at most {} characters", label(path), max));
+            }
+            if let Some(pattern) = schema.get("pattern").and_then(|p| p.as_str()) {
+                match Regex::new(pattern) {
+                    Ok(re) if !re.is_match(s) => {
+                        return Err(format!("{}: '{}' does not match pattern {}", label(path), s, pattern));
+                    }
+                    Ok(_) => {}
+                    Err(e) => log::warn!("[VALIDATORS] Invalid schema pattern '{}': {}", pattern, e),
+                }
+            }
+        }
+        Value::Array(items) => {
+            let len = items.len() as u64;
+            if let Some(min) = schema.get("minItems").and_then(|m| m.as_u64())
+                && len < min
+            {
+                return Err(format!("{}: needs at least {} items", label(path), min));
+            }
+            if let Some(max) = schema.get("maxItems").and_then(|m| m.as_u64())
+                && len > max
+            {
+                return Err(format!("{}: allows at most {} items", label(path), max));
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    check(item_schema, item, &format!("{}[{}]", path, i))?;
+                }
+            }
+        }
+        Value::Object(map) => {
+            let child = |key: &str| {
+                if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) }
+            };
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for key in required.iter().filter_map(|k| k.as_str()) {
+                    if map.get(key).is_none_or(|v| v.is_null()) {
+                        return Err(format!("{}: is required", label(&child(key))));
+                    }
+                }
+            }
+            let properties = schema.get("properties").and_then(|p| p.as_object());
+            for (key, item) in map {
+                match properties.and_then(|p| p.get(key)) {
+                    // Omitted optional arguments are often sent as null
+                    Some(_) if item.is_null() => {}
+                    Some(prop_schema) => check(prop_schema, item, &child(key))?,
+                    None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                        let known: Vec<&str> = properties
+                            .map(|p| p.keys().map(|k| k.as_str()).collect())
+                            .unwrap_or_default();
+                        return Err(format!(
+                            "{}: unknown argument (expected one of: {})",
+                            label(&child(key)),
+                            known.join(", ")
+                        ));
+                    }
+                    None => {}
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "amount": { "type": "string", "pattern": r"^\d+(\.\d+)?$" },
+                "decimals": { "type": "integer", "minimum": 0, "maximum": 36 },
+                "network": { "type": "string", "enum": ["base", "mainnet"] },
+                "tags": { "type": "array", "items": { "type": "string" }, "maxItems": 2 }
+            },
+            "required": ["amount"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn test_accepts_valid_arguments() {
+        assert!(validate_args(&schema(), &json!({ "amount": "1.5", "decimals": 6, "network": "base" })).is_ok());
+        // Null optionals are treated as omitted
+        assert!(validate_args(&schema(), &json!({ "amount": "1", "decimals": null })).is_ok());
+    }
+
+    #[test]
+    fn test_reports_precise_errors() {
+        let err = |args| validate_args(&schema(), &args).unwrap_err();
+
+        assert_eq!(err(json!({})), "`amount`: is required");
+        assert_eq!(err(json!({ "amount": 5 })), "`amount`: expected string, got integer");
+        assert!(err(json!({ "amount": "1,5" })).contains("does not match pattern"));
+        assert_eq!(err(json!({ "amount": "1", "decimals": 40 })), "`decimals`: must be <= 36");
+        assert_eq!(err(json!({ "amount": "1", "decimals": 1.5 })), "`decimals`: expected integer, got number");
+        assert!(err(json!({ "amount": "1", "network": "solana" })).starts_with("`network`: must be one of"));
+        assert_eq!(err(json!({ "amount": "1", "tags": ["a", 2] })), "`tags[1]`: expected string, got integer");
+        assert!(err(json!({ "amount": "1", "amout": "2" })).starts_with("`amout`: unknown argument"));
+    }
+}
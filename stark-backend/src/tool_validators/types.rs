@@ -36,6 +36,8 @@ pub struct ValidationContext {
     pub session_id: Option<i64>,
     /// Full tool context with access to credentials, DB, etc.
     pub tool_context: Arc<ToolContext>,
+    /// JSON schema the arguments must satisfy (from `ToolDefinition::args_schema`)
+    pub args_schema: Option<Value>,
 }
 
 impl ValidationContext {
@@ -51,9 +53,16 @@ impl ValidationContext {
             channel_id: tool_context.channel_id,
             session_id: tool_context.session_id,
             tool_context,
+            args_schema: None,
         }
     }
 
+    /// Set the argument schema checked before any validator runs
+    pub fn with_args_schema(mut self, schema: Option<Value>) -> Self {
+        self.args_schema = schema;
+        self
+    }
+
     /// Set channel context
     pub fn with_channel(mut self, channel_id: i64) -> Self {
         self.channel_id = Some(channel_id);
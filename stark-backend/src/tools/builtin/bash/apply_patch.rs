@@ -52,6 +52,7 @@ impl ApplyPatchTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -133,6 +133,7 @@ impl ClaudeCodeRemoteTool {
                 },
                 group: ToolGroup::Exec,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -51,6 +51,7 @@ impl DeleteFileTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -82,6 +82,7 @@ impl EditFileTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -109,6 +109,7 @@ impl ExecTool {
                 },
                 group: ToolGroup::Exec,
                 hidden: false,
+                ..Default::default()
             },
             max_timeout,
             security_mode,
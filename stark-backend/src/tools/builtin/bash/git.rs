@@ -184,6 +184,7 @@ impl GitTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -97,6 +97,7 @@ impl GlobTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
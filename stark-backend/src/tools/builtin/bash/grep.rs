@@ -119,6 +119,7 @@ impl GrepTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
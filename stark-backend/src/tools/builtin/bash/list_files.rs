@@ -122,6 +122,7 @@ impl ListFilesTool {
                 },
                 group: ToolGroup::Filesystem,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
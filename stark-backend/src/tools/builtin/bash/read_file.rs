@@ -69,6 +69,7 @@ impl ReadFileTool {
                 },
                 group: ToolGroup::Filesystem,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
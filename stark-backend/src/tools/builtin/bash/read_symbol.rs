@@ -67,6 +67,7 @@ impl ReadSymbolTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
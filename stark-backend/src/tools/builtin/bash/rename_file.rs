@@ -60,6 +60,7 @@ impl RenameFileTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
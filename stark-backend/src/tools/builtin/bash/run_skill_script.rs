@@ -80,6 +80,17 @@ impl RunSkillScriptTool {
             },
         );
 
+        properties.insert(
+            "dry_run".to_string(),
+            PropertySchema {
+                schema_type: "boolean".to_string(),
+                description: "Only check that the script resolves and parses; do not run it".to_string(),
+                default: Some(json!(false)),
+                items: None,
+                enum_values: None,
+            },
+        );
+
         RunSkillScriptTool {
             definition: ToolDefinition {
                 name: "run_skill_script".to_string(),
@@ -91,6 +102,7 @@ impl RunSkillScriptTool {
                 },
                 group: ToolGroup::Exec,
                 hidden: true,
+                ..Default::default()
             },
         }
     }
@@ -161,6 +173,8 @@ struct RunSkillScriptParams {
     args: Option<Value>,
     skill_name: Option<String>,
     timeout: Option<u64>,
+    #[serde(default)]
+    dry_run: bool,
 }
 
 #[async_trait]
@@ -292,6 +306,27 @@ impl Tool for RunSkillScriptTool {
             }
         };
 
+        // Dry run: parse the script and stop before anything executes
+        if params.dry_run {
+            let checked = match std::fs::read_to_string(&script_path) {
+                Ok(code) => crate::skills::validation::dry_run_script(&params.script, &code).await,
+                Err(e) => Err(format!("Cannot read script: {}", e)),
+            };
+            if let Some(ref tmp) = temp_file {
+                let _ = std::fs::remove_file(tmp);
+            }
+            return match checked {
+                Ok(msg) => ToolResult::success(format!(
+                    "Dry run of '{}' for skill '{}': {}",
+                    params.script, skill_name, msg
+                )),
+                Err(e) => ToolResult::error(format!(
+                    "Dry run of '{}' for skill '{}' failed: {}",
+                    params.script, skill_name, e
+                )),
+            };
+        }
+
         // 5. Build command
         let timeout_secs = params.timeout.unwrap_or(60).min(300);
 
@@ -71,6 +71,7 @@ impl WriteFileTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -161,6 +161,7 @@ impl CommitterTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
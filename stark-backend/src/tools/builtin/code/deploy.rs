@@ -179,6 +179,7 @@ impl DeployTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -57,6 +57,7 @@ impl IndexProjectTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -158,6 +158,7 @@ impl PrQualityTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -129,6 +129,7 @@ impl VerifyChangesTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
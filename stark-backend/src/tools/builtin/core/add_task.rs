@@ -58,6 +58,7 @@ impl AddTaskTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -75,6 +75,7 @@ impl AgentSendTool {
                 },
                 group: ToolGroup::Messaging,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
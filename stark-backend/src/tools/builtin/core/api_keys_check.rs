@@ -42,6 +42,7 @@ impl ApiKeysCheckTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
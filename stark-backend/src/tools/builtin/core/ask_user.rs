@@ -84,6 +84,7 @@ impl AskUserTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -1,6 +1,6 @@
 use crate::tools::registry::Tool;
 use crate::tools::types::{
-    ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult, ToolSafetyLevel,
+    StructuredResult, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult, ToolSafetyLevel,
 };
 use async_trait::async_trait;
 use serde_json::{json, Value};
@@ -24,6 +24,7 @@ impl CheckCreditBalanceTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -122,6 +123,7 @@ impl Tool for CheckCreditBalanceTool {
                                         credits
                                     ))
                                     .with_metadata(json)
+                                    .with_structured(StructuredResult::CreditBalance { credits_usd: credits })
                                 }
                                 Err(_) => ToolResult::error("Invalid response from credits service"),
                             }
@@ -39,6 +39,7 @@ impl CloudBackupTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -49,6 +49,7 @@ impl DefineTasksTool {
                 },
                 group: ToolGroup::System,
                 hidden: true,
+                ..Default::default()
             },
         }
     }
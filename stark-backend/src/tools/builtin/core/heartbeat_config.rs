@@ -128,6 +128,7 @@ impl HeartbeatConfigTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -7,7 +7,7 @@
 //! that closes the loop between on-chain state and local state.
 
 use crate::eip8004::config::Eip8004Config;
-use crate::gateway::protocol::GatewayEvent;
+use crate::gateway::protocol::{GatewayEvent, ToolResultEvent};
 use crate::tools::registry::Tool;
 use crate::tools::rpc_config::resolve_rpc_from_context;
 use crate::tools::types::{
@@ -57,6 +57,7 @@ impl IdentityPostRegisterTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -326,12 +327,13 @@ impl Tool for IdentityPostRegisterTool {
 
         // Emit tool-result event
         if let (Some(broadcaster), Some(ch_id)) = (&context.broadcaster, context.channel_id) {
-            broadcaster.broadcast(GatewayEvent::tool_result(
-                ch_id, None, "identity_post_register",
-                true, 0,
-                &format!("Agent #{} registered on-chain", registered.agent_id),
-                false, None,
-            ));
+            broadcaster.broadcast(GatewayEvent::tool_result(ToolResultEvent {
+                channel_id: ch_id,
+                tool_name: "identity_post_register",
+                success: true,
+                content: &format!("Agent #{} registered on-chain", registered.agent_id),
+                ..Default::default()
+            }));
         }
 
         let msg = format!(
@@ -7,7 +7,7 @@
 
 use crate::eip8004::config::Eip8004Config;
 use crate::eip8004::identity::IdentityRegistry;
-use crate::gateway::protocol::GatewayEvent;
+use crate::gateway::protocol::{GatewayEvent, ToolResultEvent};
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -51,6 +51,7 @@ impl ImportIdentityTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -332,12 +333,13 @@ impl Tool for ImportIdentityTool {
 
         // Emit tool-result event
         if let (Some(broadcaster), Some(ch_id)) = (&context.broadcaster, context.channel_id) {
-            broadcaster.broadcast(GatewayEvent::tool_result(
-                ch_id, None, "import_identity",
-                true, 0,
-                &format!("Agent #{} imported successfully", agent_id),
-                false, None,
-            ));
+            broadcaster.broadcast(GatewayEvent::tool_result(ToolResultEvent {
+                channel_id: ch_id,
+                tool_name: "import_identity",
+                success: true,
+                content: &format!("Agent #{} imported successfully", agent_id),
+                ..Default::default()
+            }));
         }
 
         let msg = format!(
@@ -101,6 +101,7 @@ impl ImpulseMapManageTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
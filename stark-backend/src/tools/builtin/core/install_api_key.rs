@@ -52,6 +52,7 @@ impl InstallApiKeyTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
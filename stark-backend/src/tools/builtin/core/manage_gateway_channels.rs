@@ -116,6 +116,7 @@ impl ManageGatewayChannelsTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
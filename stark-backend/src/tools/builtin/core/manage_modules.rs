@@ -90,6 +90,7 @@ impl ManageModulesTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -112,6 +112,7 @@ impl ManageSkillsTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -162,6 +162,7 @@ impl WorkstreamTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
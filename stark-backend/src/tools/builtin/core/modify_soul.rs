@@ -57,6 +57,7 @@ impl ModifySoulTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
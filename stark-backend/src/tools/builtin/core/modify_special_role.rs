@@ -83,6 +83,23 @@ impl ModifySpecialRoleTool {
             },
         );
 
+        properties.insert(
+            "denied_tools".to_string(),
+            PropertySchema {
+                schema_type: "array".to_string(),
+                description: "Tool names the role never gets, even if granted or required by a granted skill (for create_role)".to_string(),
+                default: None,
+                items: Some(Box::new(PropertySchema {
+                    schema_type: "string".to_string(),
+                    description: "Tool name".to_string(),
+                    default: None,
+                    items: None,
+                    enum_values: None,
+                })),
+                enum_values: None,
+            },
+        );
+
         properties.insert(
             "description".to_string(),
             PropertySchema {
@@ -147,7 +164,7 @@ impl ModifySpecialRoleTool {
         ModifySpecialRoleTool {
             definition: ToolDefinition {
                 name: "modify_special_role".to_string(),
-                description: "Manage special roles for enriched safe mode: create/delete roles with extra tools/skills, and assign/unassign roles to users on specific channels.".to_string(),
+                description: "Manage special roles for enriched safe mode: create/delete roles with extra tools/skills (and tools they're denied), and assign/unassign roles to users on specific channels.".to_string(),
                 input_schema: ToolInputSchema {
                     schema_type: "object".to_string(),
                     properties,
@@ -155,6 +172,7 @@ impl ModifySpecialRoleTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -172,6 +190,7 @@ struct Params {
     role_name: Option<String>,
     allowed_tools: Option<Vec<String>>,
     allowed_skills: Option<Vec<String>>,
+    denied_tools: Option<Vec<String>>,
     description: Option<String>,
     channel_type: Option<String>,
     user_id: Option<String>,
@@ -206,10 +225,15 @@ impl Tool for ModifySpecialRoleTool {
                         .iter()
                         .map(|r| {
                             format!(
-                                "- {} | tools: [{}] | skills: [{}]{}",
+                                "- {} | tools: [{}] | skills: [{}]{}{}",
                                 r.name,
                                 r.allowed_tools.join(", "),
                                 r.allowed_skills.join(", "),
+                                if r.denied_tools.is_empty() {
+                                    String::new()
+                                } else {
+                                    format!(" | denied: [{}]", r.denied_tools.join(", "))
+                                },
                                 r.description
                                     .as_deref()
                                     .map(|d| format!(" | {}", d))
@@ -246,6 +270,7 @@ impl Tool for ModifySpecialRoleTool {
                     name: name.clone(),
                     allowed_tools: params.allowed_tools.unwrap_or_default(),
                     allowed_skills: params.allowed_skills.unwrap_or_default(),
+                    denied_tools: params.denied_tools.unwrap_or_default(),
                     description: params.description,
                     created_at: String::new(),
                     updated_at: String::new(),
@@ -253,10 +278,11 @@ impl Tool for ModifySpecialRoleTool {
 
                 match db.upsert_special_role(&role) {
                     Ok(_) => ToolResult::success(format!(
-                        "Special role '{}' created/updated. Tools: [{}], Skills: [{}]",
+                        "Special role '{}' created/updated. Tools: [{}], Skills: [{}], Denied: [{}]",
                         role.name,
                         role.allowed_tools.join(", "),
-                        role.allowed_skills.join(", ")
+                        role.allowed_skills.join(", "),
+                        role.denied_tools.join(", ")
                     )),
                     Err(e) => ToolResult::error(format!("Failed to create role: {}", e)),
                 }
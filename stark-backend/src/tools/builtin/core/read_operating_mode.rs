@@ -24,6 +24,7 @@ impl ReadOperatingModeTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -65,6 +65,7 @@ impl ReadRecentTransactionsTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -44,6 +44,7 @@ impl ReadSkillTool {
                 },
                 group: ToolGroup::System,
                 hidden: true, // Only visible when a skill (e.g. starkhub) requires it
+                ..Default::default()
             },
         }
     }
@@ -63,6 +63,7 @@ impl RegisterNewIdentityTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -59,6 +59,7 @@ impl SayToUserTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -74,6 +74,7 @@ impl SetAgentSubtypeTool {
             },
             group: ToolGroup::System,
             hidden: false,
+            ..Default::default()
         }
     }
 
@@ -38,6 +38,7 @@ impl SetThemeAccentTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -86,7 +87,7 @@ impl Tool for SetThemeAccentTool {
         match db.update_bot_settings_full(
             None, None, None, None, None, None, None, None, None, None, None,
             accent_str,
-            None, None, None, None,
+            None, None, None, None, None, None, None, None, None, None, None,
         ) {
             Ok(settings) => {
                 let display_color = settings
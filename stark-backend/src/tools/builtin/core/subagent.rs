@@ -95,6 +95,7 @@ impl SpawnSubagentsTool {
                 },
                 group: ToolGroup::SubAgent,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -737,6 +738,7 @@ impl SubagentStatusTool {
                 },
                 group: ToolGroup::SubAgent,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
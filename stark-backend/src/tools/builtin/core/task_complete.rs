@@ -43,6 +43,7 @@ impl TaskFullyCompletedTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
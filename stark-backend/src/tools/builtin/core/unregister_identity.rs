@@ -5,7 +5,7 @@
 //! NFT — it only clears local state so the agent can re-import later via
 //! `import_identity`.
 
-use crate::gateway::protocol::GatewayEvent;
+use crate::gateway::protocol::{GatewayEvent, ToolResultEvent};
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -61,6 +61,7 @@ impl UnregisterIdentityTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -164,16 +165,13 @@ impl Tool for UnregisterIdentityTool {
 
         // Emit tool-result event
         if let (Some(broadcaster), Some(ch_id)) = (&context.broadcaster, context.channel_id) {
-            broadcaster.broadcast(GatewayEvent::tool_result(
-                ch_id,
-                None,
-                "unregister_identity",
-                true,
-                0,
-                &format!("Agent #{} unregistered locally", agent_id),
-                false,
-                None,
-            ));
+            broadcaster.broadcast(GatewayEvent::tool_result(ToolResultEvent {
+                channel_id: ch_id,
+                tool_name: "unregister_identity",
+                success: true,
+                content: &format!("Agent #{} unregistered locally", agent_id),
+                ..Default::default()
+            }));
         }
 
         let file_note = if file_deleted {
@@ -77,6 +77,7 @@ impl Tool for UseSkillTool {
             },
             group: ToolGroup::System,
             hidden: false,
+            ..Default::default()
         }
     }
 
@@ -110,9 +111,24 @@ impl Tool for UseSkillTool {
                             .join(", ")
                     })
                     .unwrap_or_else(|_| "unknown".to_string());
+
+                // Suggest skills that match the intent, not just the name
+                let mut suggestions = String::new();
+                if let Some(ref registry) = context.skill_registry {
+                    let task = format!("{} {}", skill_name.replace(['_', '-'], " "), input);
+                    let emb_gen = context.hybrid_search.as_ref().map(|h| h.embedding_generator());
+                    let matches = registry.find_relevant_skills(&task, 3, 0.30, emb_gen).await;
+                    if !matches.is_empty() {
+                        suggestions.push_str("\n\nClosest matches for this request:\n");
+                        for (s, _) in &matches {
+                            suggestions.push_str(&format!("- {}: {}\n", s.name, s.description));
+                        }
+                    }
+                }
+
                 return ToolResult::error(format!(
-                    "Skill '{}' not found or not enabled. Available skills: {}",
-                    skill_name, available
+                    "Skill '{}' not found or not enabled. Available skills: {}{}",
+                    skill_name, available, suggestions
                 ));
             }
             Err(e) => {
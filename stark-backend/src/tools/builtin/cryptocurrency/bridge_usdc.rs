@@ -24,7 +24,7 @@ use crate::tools::rpc_config::{resolve_rpc_from_context, ResolvedRpcConfig};
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
-use crate::tx_queue::QueuedTransaction;
+use crate::tx_queue::{GasStrategy, QueuedTransaction};
 use crate::wallet::WalletProvider;
 use crate::x402::X402EvmRpc;
 use async_trait::async_trait;
@@ -166,6 +166,7 @@ impl BridgeUsdcTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -223,6 +224,7 @@ impl BridgeUsdcTool {
     }
 
     /// Sign a transaction for queueing using WalletProvider (works in both Standard and Flash mode)
+    #[allow(clippy::too_many_arguments)]
     async fn sign_transaction_for_queue(
         chain_id: u64,
         network: &str,
@@ -231,6 +233,7 @@ impl BridgeUsdcTool {
         data: Vec<u8>,
         rpc_config: &ResolvedRpcConfig,
         wallet_provider: &Arc<dyn WalletProvider>,
+        gas_strategy: &GasStrategy,
     ) -> Result<SignedTxForQueue, String> {
         let rpc = X402EvmRpc::new_with_wallet_provider(
             wallet_provider.clone(),
@@ -254,8 +257,8 @@ impl BridgeUsdcTool {
             .map_err(|e| format!("Gas estimation failed: {}", e))?;
         let gas = gas * U256::from(130) / U256::from(100); // 30% buffer for bridge txs
 
-        // Get gas prices
-        let (max_fee, priority_fee) = rpc.estimate_eip1559_fees().await?;
+        // Get gas prices using the configured strategy
+        let (max_fee, priority_fee) = gas_strategy.estimate_fees(&rpc).await?;
 
         log::info!(
             "[bridge_usdc] Signing tx: to={:?}, value={}, data_len={}, gas={}, nonce={} on {}",
@@ -413,9 +416,9 @@ impl Tool for BridgeUsdcTool {
         };
 
         // Get wallet provider (required for signing)
-        let wallet_provider = match &context.wallet_provider {
-            Some(wp) => wp,
-            None => return ToolResult::error("Wallet not configured. Cannot bridge tokens."),
+        let wallet_provider = match context.signing_wallet() {
+            Ok(wp) => wp,
+            Err(e) => return ToolResult::error(format!("{} Cannot bridge tokens.", e)),
         };
 
         // Get wallet address from WalletProvider
@@ -527,7 +530,7 @@ impl Tool for BridgeUsdcTool {
             .as_ref()
             .map(|ct| {
                 let ct_lower = ct.to_lowercase();
-                ct_lower == "discord" || ct_lower == "telegram" || ct_lower == "slack"
+                ct_lower == "discord" || ct_lower == "telegram" || ct_lower == "slack" || ct_lower == "matrix"
             })
             .unwrap_or(false);
 
@@ -552,6 +555,7 @@ impl Tool for BridgeUsdcTool {
         // Resolve RPC config for source chain
         let network = Self::chain_to_network(&params.from_chain);
         let rpc_config = resolve_rpc_from_context(&context.extra, network);
+        let gas_strategy = GasStrategy::from_context_extra(&context.extra);
 
         let mut queued_uuids = Vec::new();
         let mut current_nonce_offset = 0u64;
@@ -584,7 +588,8 @@ impl Tool for BridgeUsdcTool {
                 approval_value,
                 approval_data,
                 &rpc_config,
-                wallet_provider,
+                &wallet_provider,
+                &gas_strategy,
             )
             .await
             {
@@ -672,7 +677,7 @@ impl Tool for BridgeUsdcTool {
             };
             let gas = gas * U256::from(130) / U256::from(100);
 
-            let (max_fee, priority_fee) = match rpc.estimate_eip1559_fees().await {
+            let (max_fee, priority_fee) = match gas_strategy.estimate_fees(&rpc).await {
                 Ok(fees) => fees,
                 Err(e) => return ToolResult::error(format!("Failed to estimate fees: {}", e)),
             };
@@ -719,7 +724,8 @@ impl Tool for BridgeUsdcTool {
                 bridge_value,
                 bridge_data,
                 &rpc_config,
-                wallet_provider,
+                &wallet_provider,
+                &gas_strategy,
             )
             .await
             {
@@ -3,13 +3,13 @@
 //! Takes a UUID from web3_tx and broadcasts the signed transaction to the network.
 
 use super::web3_tx::SendEthTool;
-use crate::gateway::protocol::GatewayEvent;
+use crate::gateway::protocol::{GatewayEvent, TxQueueConfirmationEvent};
 use crate::tools::registry::Tool;
 use crate::tools::rpc_config::resolve_rpc_from_context;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
-use crate::tx_queue::QueuedTxStatus;
+use crate::tx_queue::{QueuedTxStatus, SimulationStatus};
 use crate::x402::{TxLog, X402EvmRpc};
 use ethers::types::{H256, U256};
 use async_trait::async_trait;
@@ -60,6 +60,7 @@ impl BroadcastWeb3TxTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -82,6 +83,14 @@ fn default_uuid_register() -> String {
     "queued_tx_uuid".to_string()
 }
 
+/// How long the user has to confirm a queued transaction in partner mode (0 = no limit)
+pub(super) fn confirmation_timeout_secs(context: &ToolContext) -> i64 {
+    context.extra
+        .get("tx_confirmation_timeout_secs")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(crate::models::DEFAULT_TX_CONFIRMATION_TIMEOUT_SECS as i64)
+}
+
 #[async_trait]
 impl Tool for BroadcastWeb3TxTool {
     fn definition(&self) -> ToolDefinition {
@@ -152,18 +161,24 @@ impl Tool for BroadcastWeb3TxTool {
                 )),
             };
 
+            // Start the confirmation countdown; the tx is auto-rejected when it runs out
+            let timeout_secs = confirmation_timeout_secs(context);
+            let expires_at = tx_queue.request_confirmation(&uuid, timeout_secs, context.session_id);
+
             // Emit event to open confirmation modal
             if let (Some(broadcaster), Some(ch_id)) = (&context.broadcaster, context.channel_id) {
-                broadcaster.broadcast(GatewayEvent::tx_queue_confirmation_required(
-                    ch_id,
-                    &queued_tx.uuid,
-                    &queued_tx.network,
-                    &queued_tx.from,
-                    &queued_tx.to,
-                    &queued_tx.value,
-                    &queued_tx.format_value_eth(),
-                    &queued_tx.data,
-                ));
+                broadcaster.broadcast(GatewayEvent::tx_queue_confirmation_required(TxQueueConfirmationEvent {
+                    channel_id: ch_id,
+                    uuid: &queued_tx.uuid,
+                    network: &queued_tx.network,
+                    from: &queued_tx.from,
+                    to: &queued_tx.to,
+                    value: &queued_tx.value,
+                    value_formatted: &queued_tx.format_value_eth(),
+                    data: &queued_tx.data,
+                    simulation: queued_tx.simulation.as_ref(),
+                    expires_at,
+                }));
                 log::info!("[broadcast_web3_tx] Partner mode: emitted tx_queue.confirmation_required for {}", queued_tx.uuid);
             }
 
@@ -172,16 +187,23 @@ impl Tool for BroadcastWeb3TxTool {
                 UUID: {}\n\
                 Network: {}\n\
                 To: {}\n\
-                Value: {}\n\n\
-                The user will be prompted to confirm or deny this transaction.",
-                queued_tx.uuid, queued_tx.network, queued_tx.to, queued_tx.format_value_eth()
+                Value: {}\n\
+                Simulation: {}\n\n\
+                The user will be prompted to confirm or deny this transaction.{}",
+                queued_tx.uuid, queued_tx.network, queued_tx.to, queued_tx.format_value_eth(),
+                queued_tx.simulation.as_ref().map(|s| s.summary()).unwrap_or_else(|| "not run".to_string()),
+                expires_at
+                    .map(|t| format!(" If it is not confirmed by {} it will be rejected automatically.", t.to_rfc3339()))
+                    .unwrap_or_default()
             )).with_metadata(json!({
                 "uuid": queued_tx.uuid,
                 "status": "awaiting_confirmation",
+                "confirmation_expires_at": expires_at.map(|t| t.to_rfc3339()),
                 "network": queued_tx.network,
                 "to": queued_tx.to,
                 "value": queued_tx.value,
-                "value_formatted": queued_tx.format_value_eth()
+                "value_formatted": queued_tx.format_value_eth(),
+                "simulation": queued_tx.simulation
             }));
         }
 
@@ -209,7 +231,7 @@ impl Tool for BroadcastWeb3TxTool {
                     uuid
                 ));
             },
-            QueuedTxStatus::Broadcast | QueuedTxStatus::Confirmed => {
+            QueuedTxStatus::Broadcast | QueuedTxStatus::Confirmed | QueuedTxStatus::Stuck => {
                 let tx_hash = queued_tx.tx_hash.as_deref().unwrap_or("unknown");
                 let explorer_url = queued_tx.explorer_url.as_deref().unwrap_or("");
                 return ToolResult::error(format!(
@@ -230,6 +252,28 @@ impl Tool for BroadcastWeb3TxTool {
                     uuid
                 ));
             },
+            QueuedTxStatus::Replaced => {
+                return ToolResult::error(format!(
+                    "Transaction {} was replaced by {} (same nonce, higher gas).",
+                    uuid, queued_tx.replaced_by.as_deref().unwrap_or("a replacement")
+                ));
+            },
+        }
+
+        // Nobody confirms a rogue mode broadcast, so a tx that simulated as reverting is refused
+        if let Some(simulation) = queued_tx.simulation.as_ref()
+            && simulation.status == SimulationStatus::Reverted
+        {
+            return ToolResult::error(format!(
+                "Transaction {} was not broadcast: simulation reverted ({}). \
+                Rogue mode does not broadcast transactions that are expected to revert.",
+                uuid, simulation.error.as_deref().unwrap_or("unknown reason")
+            ));
+        }
+
+        // Leave the transaction pending while signing is frozen so it can be broadcast later
+        if let Err(e) = crate::wallet::ensure_signing_allowed() {
+            return ToolResult::error(e);
         }
 
         // Mark as broadcasting
@@ -350,6 +394,30 @@ impl Tool for BroadcastWeb3TxTool {
             log::info!("[broadcast_web3_tx] Emitted tx.confirmed event for {} (status={})", tx_hash_str, status);
         }
 
+        // Gas is spent whether the tx confirmed or reverted
+        if let (Some(db), Some(ch_id), Some(session_id), Some(gas_used), Some(price)) = (
+            &context.database,
+            context.channel_id,
+            context.session_id,
+            receipt.gas_used,
+            receipt.effective_gas_price,
+        ) {
+            let currency = queued_tx.network.parse::<crate::tools::rpc_config::Network>()
+                .map(|n| n.native_currency())
+                .unwrap_or("ETH");
+            crate::execution::spend_ledger::record(
+                db,
+                context.broadcaster.as_deref(),
+                ch_id,
+                session_id,
+                crate::models::SpendKind::Gas,
+                crate::execution::spend_ledger::gas_fee(gas_used, price),
+                currency,
+                None,
+                Some(&tx_hash_str),
+            );
+        }
+
         // Post-processing: if this was an identity_register tx and it confirmed,
         // decode the Registered event and save agent_id to the database automatically.
         let mut identity_agent_id: Option<u64> = None;
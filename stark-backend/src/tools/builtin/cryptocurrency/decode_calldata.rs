@@ -85,6 +85,7 @@ impl DecodeCalldataTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -113,6 +113,7 @@ impl Erc8128FetchTool {
                 },
                 group: ToolGroup::Web,
                 hidden: true, // Only available when a skill requires it
+                ..Default::default()
             },
         }
     }
@@ -5,7 +5,7 @@
 
 use crate::tools::registry::Tool;
 use crate::tools::types::{
-    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+    PropertySchema, StructuredResult, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
 use async_trait::async_trait;
 use serde::Deserialize;
@@ -76,6 +76,14 @@ impl FromRawAmountTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                arg_constraints: Some(json!({
+                    "properties": {
+                        "raw_amount": { "pattern": r#"^\s*"?\d+"?\s*$"# },
+                        "decimals": { "minimum": 0, "maximum": 255 },
+                        "decimals_register": { "minLength": 1 },
+                        "cache_as": { "minLength": 1 }
+                    }
+                })),
             },
         }
     }
@@ -227,7 +235,11 @@ impl Tool for FromRawAmountTool {
             "decimals": decimals,
             "human_amount": human_amount,
             "cached_in_register": params.cache_as
-        }))
+        })).with_structured(StructuredResult::TokenAmount {
+            raw_amount: params.raw_amount.clone(),
+            decimals,
+            amount: human_amount,
+        })
     }
 
     // Standard — writes human amount to context registers
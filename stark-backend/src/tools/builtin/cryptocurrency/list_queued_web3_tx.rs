@@ -2,7 +2,7 @@
 //!
 //! Shows transactions that have been signed but not yet broadcast.
 
-use crate::gateway::protocol::GatewayEvent;
+use crate::gateway::protocol::{GatewayEvent, TxQueueConfirmationEvent};
 use super::web3_tx::SendEthTool;
 use crate::tools::registry::Tool;
 use crate::tools::types::{
@@ -39,7 +39,7 @@ impl ListQueuedWeb3TxTool {
             "status".to_string(),
             PropertySchema {
                 schema_type: "string".to_string(),
-                description: "Filter by status: pending, broadcasting, broadcast, confirmed, failed, expired (optional)".to_string(),
+                description: "Filter by status: pending, broadcasting, broadcast, confirmed, failed, expired, stuck, replaced (optional)".to_string(),
                 default: None,
                 items: None,
                 enum_values: Some(vec![
@@ -49,6 +49,8 @@ impl ListQueuedWeb3TxTool {
                     "confirmed".to_string(),
                     "failed".to_string(),
                     "expired".to_string(),
+                    "stuck".to_string(),
+                    "replaced".to_string(),
                 ]),
             },
         );
@@ -86,6 +88,7 @@ impl ListQueuedWeb3TxTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -151,6 +154,9 @@ impl Tool for ListQueuedWeb3TxTool {
                     msg.push_str(&format!("Max Fee: {} ({})\n", tx.max_fee_per_gas, SendEthTool::format_gwei(&tx.max_fee_per_gas)));
                     msg.push_str(&format!("Priority Fee: {} ({})\n", tx.max_priority_fee_per_gas, SendEthTool::format_gwei(&tx.max_priority_fee_per_gas)));
                     msg.push_str(&format!("Created: {}\n", tx.created_at.format("%Y-%m-%d %H:%M:%S UTC")));
+                    if let Some(ref sim) = tx.simulation {
+                        msg.push_str(&format!("Simulation: {}\n", sim.summary()));
+                    }
 
                     if let Some(ref tx_hash) = tx.tx_hash {
                         msg.push_str(&format!("Tx Hash: {}\n", tx_hash));
@@ -197,6 +203,8 @@ impl Tool for ListQueuedWeb3TxTool {
                 "confirmed" => Some(QueuedTxStatus::Confirmed),
                 "failed" => Some(QueuedTxStatus::Failed),
                 "expired" => Some(QueuedTxStatus::Expired),
+                "stuck" => Some(QueuedTxStatus::Stuck),
+                "replaced" => Some(QueuedTxStatus::Replaced),
                 _ => None,
             }
         }).flatten();
@@ -240,6 +248,8 @@ impl Tool for ListQueuedWeb3TxTool {
                 QueuedTxStatus::Confirmed => "[CONFIRMED]",
                 QueuedTxStatus::Failed => "[FAILED]",
                 QueuedTxStatus::Expired => "[EXPIRED]",
+                QueuedTxStatus::Stuck => "[STUCK]",
+                QueuedTxStatus::Replaced => "[REPLACED]",
             };
 
             msg.push_str(&format!("{} {}\n", status_indicator, tx.uuid));
@@ -249,6 +259,9 @@ impl Tool for ListQueuedWeb3TxTool {
                 &tx.to[tx.to.len().saturating_sub(4)..]
             ));
             msg.push_str(&format!("  Value: {}\n", tx.value_formatted));
+            if let Some(ref sim) = tx.simulation {
+                msg.push_str(&format!("  Simulation: {}\n", sim.summary()));
+            }
 
             if let Some(ref tx_hash) = tx.tx_hash {
                 msg.push_str(&format!("  Hash: {}...{}\n",
@@ -290,6 +303,7 @@ impl Tool for ListQueuedWeb3TxTool {
                 "tx_hash": tx.tx_hash,
                 "explorer_url": tx.explorer_url,
                 "error": tx.error,
+                "simulation": tx.simulation,
                 "created_at": tx.created_at.to_rfc3339()
             })
         }).collect();
@@ -304,18 +318,26 @@ impl Tool for ListQueuedWeb3TxTool {
             if let Some(first_pending) = transactions.iter()
                 .find(|t| t.status == QueuedTxStatus::Pending)
             {
+                let expires_at = tx_queue.request_confirmation(
+                    &first_pending.uuid,
+                    super::broadcast_web3_tx::confirmation_timeout_secs(context),
+                    context.session_id,
+                );
+
                 // Emit event to open modal
                 if let (Some(broadcaster), Some(ch_id)) = (&context.broadcaster, context.channel_id) {
-                    broadcaster.broadcast(GatewayEvent::tx_queue_confirmation_required(
-                        ch_id,
-                        &first_pending.uuid,
-                        &first_pending.network,
-                        &first_pending.from,
-                        &first_pending.to,
-                        &first_pending.value,
-                        &first_pending.value_formatted,
-                        &first_pending.data,
-                    ));
+                    broadcaster.broadcast(GatewayEvent::tx_queue_confirmation_required(TxQueueConfirmationEvent {
+                        channel_id: ch_id,
+                        uuid: &first_pending.uuid,
+                        network: &first_pending.network,
+                        from: &first_pending.from,
+                        to: &first_pending.to,
+                        value: &first_pending.value,
+                        value_formatted: &first_pending.value_formatted,
+                        data: &first_pending.data,
+                        simulation: first_pending.simulation.as_ref(),
+                        expires_at,
+                    }));
                     log::info!("[list_queued_web3_tx] Emitted tx_queue.confirmation_required for {}", first_pending.uuid);
                 }
             }
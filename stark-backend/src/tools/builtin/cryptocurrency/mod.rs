@@ -10,6 +10,7 @@ mod verify_tx_broadcast;
 mod decode_calldata;
 mod list_queued_web3_tx;
 pub mod network_lookup;
+mod select_wallet;
 mod select_web3_network;
 mod set_address;
 mod set_nft_token_id;
@@ -39,6 +40,7 @@ pub use network_lookup::load_networks;
 pub use set_address::SetAddressTool;
 pub use set_nft_token_id::SetNftTokenIdTool;
 pub use swap_token::SwapTokenTool;
+pub use select_wallet::SelectWalletTool;
 pub use select_web3_network::SelectWeb3NetworkTool;
 pub use from_raw_amount::FromRawAmountTool;
 pub use to_raw_amount::ToRawAmountTool;
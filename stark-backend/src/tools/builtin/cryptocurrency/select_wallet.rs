@@ -0,0 +1,169 @@
+//! Select Wallet tool
+//!
+//! Lists the wallets the bot can sign with and selects one for subsequent
+//! signing operations. Only relevant when more than one wallet is configured
+//! (STARK_WALLET_KEYS in standard mode; flash mode only has its own wallet).
+//!
+//! The selected label is stored in the `wallet_label` register and picked up by
+//! `ToolContext::signing_wallet()`.
+
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::wallet::DEFAULT_WALLET_LABEL;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Select Wallet tool - chooses which wallet signs subsequent transactions
+pub struct SelectWalletTool {
+    definition: ToolDefinition,
+}
+
+impl SelectWalletTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "wallet".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Label of the wallet to sign with (e.g. 'default', 'treasury'). \
+                    Omit to list the available wallets without changing the selection."
+                    .to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        SelectWalletTool {
+            definition: ToolDefinition {
+                name: "select_wallet".to_string(),
+                description: "List the wallets available for signing, or select one by label.\n\n\
+                    The selected wallet is stored in the 'wallet_label' register and used by \
+                    subsequent signing tools (web3_tx, web3_function_call, bridge_usdc, swaps). \
+                    Select 'default' to go back to the bot's primary wallet."
+                    .to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::Finance,
+                hidden: false,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl Default for SelectWalletTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SelectWalletParams {
+    wallet: Option<String>,
+}
+
+#[async_trait]
+impl Tool for SelectWalletTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: SelectWalletParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let provider = match &context.wallet_provider {
+            Some(wp) => wp,
+            None => return ToolResult::error("Wallet not configured."),
+        };
+
+        let wallets = provider.list_wallets();
+        let current = context
+            .wallet_label()
+            .unwrap_or_else(|| DEFAULT_WALLET_LABEL.to_string());
+
+        let Some(label) = params.wallet.map(|w| w.trim().to_string()).filter(|w| !w.is_empty()) else {
+            let lines: Vec<String> = wallets
+                .iter()
+                .map(|w| {
+                    let marker = if w.label == current { " (selected)" } else { "" };
+                    format!("• {}: {}{}", w.label, w.address, marker)
+                })
+                .collect();
+            return ToolResult::success(format!("Available wallets:\n{}", lines.join("\n")))
+                .with_metadata(json!({ "wallets": wallets, "selected": current }));
+        };
+
+        let Some(address) = provider.get_address_for(&label) else {
+            let available: Vec<&str> = wallets.iter().map(|w| w.label.as_str()).collect();
+            return ToolResult::error(format!(
+                "Unknown wallet '{}'. Available wallets: {}",
+                label,
+                available.join(", ")
+            ));
+        };
+
+        context.set_register("wallet_label", json!(&label), "select_wallet");
+        context.set_register("wallet_address", json!(&address), "select_wallet");
+
+        log::info!("[select_wallet] Selected wallet: {} ({})", label, address);
+
+        ToolResult::success(format!(
+            "Selected wallet: {} ({})\n\n\
+             Subsequent transactions will be signed from this wallet.",
+            label, address
+        ))
+        .with_metadata(json!({
+            "wallet": label,
+            "address": address,
+            "register_source": "select_wallet"
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::EnvWalletProvider;
+    use std::sync::Arc;
+
+    const TEST_KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const TREASURY_KEY: &str = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+    #[tokio::test]
+    async fn test_select_wallet_sets_signing_wallet() {
+        let provider = EnvWalletProvider::from_private_key(TEST_KEY)
+            .unwrap()
+            .with_wallet("treasury", TREASURY_KEY)
+            .unwrap();
+        let mut context = ToolContext::new();
+        context.wallet_provider = Some(Arc::new(provider));
+
+        let tool = SelectWalletTool::new();
+        assert!(!tool.execute(json!({ "wallet": "missing" }), &context).await.success);
+
+        let result = tool.execute(json!({ "wallet": "treasury" }), &context).await;
+        assert!(result.success);
+        assert_eq!(
+            context.signing_wallet().unwrap().get_address(),
+            "0x70997970c51812dc3a010c7d01b50e0d17dc79c8"
+        );
+
+        tool.execute(json!({ "wallet": "default" }), &context).await;
+        assert_eq!(
+            context.signing_wallet().unwrap().get_address(),
+            "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+        );
+    }
+}
@@ -73,6 +73,7 @@ impl SelectWeb3NetworkTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
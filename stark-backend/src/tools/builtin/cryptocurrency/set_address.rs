@@ -67,6 +67,7 @@ impl SetAddressTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
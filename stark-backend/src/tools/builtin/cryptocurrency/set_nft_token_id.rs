@@ -43,6 +43,7 @@ impl SetNftTokenIdTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
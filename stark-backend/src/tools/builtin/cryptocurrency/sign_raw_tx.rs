@@ -121,6 +121,7 @@ impl SignRawTxTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: true,
+                ..Default::default()
             },
         }
     }
@@ -183,20 +184,22 @@ impl Tool for SignRawTxTool {
                     error: Some(format!("Invalid parameters: {}", e)),
                     metadata: None,
                     retry_after_secs: None,
+                    structured: None,
                 }
             }
         };
 
         // Require wallet provider
-        let wallet_provider = match &context.wallet_provider {
-            Some(wp) => wp.clone(),
-            None => {
+        let wallet_provider = match context.signing_wallet() {
+            Ok(wp) => wp,
+            Err(e) => {
                 return ToolResult {
                     success: false,
                     content: String::new(),
-                    error: Some("No wallet provider configured".to_string()),
+                    error: Some(e),
                     metadata: None,
                     retry_after_secs: None,
+                    structured: None,
                 }
             }
         };
@@ -219,6 +222,7 @@ impl Tool for SignRawTxTool {
                     error: Some(format!("Failed to create RPC client: {}", e)),
                     metadata: None,
                     retry_after_secs: None,
+                    structured: None,
                 }
             }
         };
@@ -234,6 +238,7 @@ impl Tool for SignRawTxTool {
                     error: Some(format!("Invalid wallet address: {}", from_str)),
                     metadata: None,
                     retry_after_secs: None,
+                    structured: None,
                 }
             }
         };
@@ -246,6 +251,7 @@ impl Tool for SignRawTxTool {
                     error: Some(format!("Invalid 'to' address: {}", p.to)),
                     metadata: None,
                     retry_after_secs: None,
+                    structured: None,
                 }
             }
         };
@@ -260,6 +266,7 @@ impl Tool for SignRawTxTool {
                     error: Some(format!("Invalid value: {}", p.value)),
                     metadata: None,
                     retry_after_secs: None,
+                    structured: None,
                 }
             }
         };
@@ -274,6 +281,7 @@ impl Tool for SignRawTxTool {
                     error: Some(format!("Invalid calldata hex: {}", e)),
                     metadata: None,
                     retry_after_secs: None,
+                    structured: None,
                 }
             }
         };
@@ -290,6 +298,7 @@ impl Tool for SignRawTxTool {
                         error: Some(format!("Failed to fetch nonce: {}", e)),
                         metadata: None,
                         retry_after_secs: None,
+                        structured: None,
                     }
                 }
             },
@@ -311,6 +320,7 @@ impl Tool for SignRawTxTool {
                         error: Some(format!("Failed to estimate gas fees: {}", e)),
                         metadata: None,
                         retry_after_secs: None,
+                        structured: None,
                     }
                 }
             },
@@ -353,6 +363,7 @@ impl Tool for SignRawTxTool {
                     error: Some(format!("Failed to sign transaction: {}", e)),
                     metadata: None,
                     retry_after_secs: None,
+                    structured: None,
                 }
             }
         };
@@ -383,6 +394,7 @@ impl Tool for SignRawTxTool {
             error: None,
             metadata: None,
             retry_after_secs: None,
+            structured: None,
         }
     }
 }
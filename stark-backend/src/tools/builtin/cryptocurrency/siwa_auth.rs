@@ -155,6 +155,7 @@ impl SiwaAuthTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -115,6 +115,7 @@ impl SwapTokenTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -240,9 +241,9 @@ impl Tool for SwapTokenTool {
 
         // ─── Step 4: Get wallet address ────────────────────────────────────────
 
-        let wallet_provider = match &context.wallet_provider {
-            Some(wp) => wp,
-            None => return ToolResult::error("Wallet not configured. Cannot execute swaps."),
+        let wallet_provider = match context.signing_wallet() {
+            Ok(wp) => wp,
+            Err(e) => return ToolResult::error(format!("{} Cannot execute swaps.", e)),
         };
 
         let wallet_address = wallet_provider.get_address();
@@ -273,7 +274,7 @@ impl Tool for SwapTokenTool {
                 ALLOWANCE_HOLDER,
                 &network_str,
                 context,
-                wallet_provider,
+                &wallet_provider,
             )
             .await
             {
@@ -77,6 +77,14 @@ impl ToRawAmountTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                arg_constraints: Some(json!({
+                    "properties": {
+                        "amount": { "pattern": r"^\s*(\d+(\.\d*)?|\.\d+)\s*$" },
+                        "decimals": { "minimum": 0, "maximum": 255 },
+                        "decimals_register": { "minLength": 1 },
+                        "cache_as": { "minLength": 1 }
+                    }
+                })),
             },
         }
     }
@@ -301,4 +309,20 @@ mod tests {
         assert_eq!(ToRawAmountTool::convert_to_raw(" 1 ", 18).unwrap(), "1000000000000000000");
         assert_eq!(ToRawAmountTool::convert_to_raw("  0.5  ", 18).unwrap(), "500000000000000000");
     }
+
+    #[test]
+    fn test_args_schema_matches_accepted_amounts() {
+        use crate::tool_validators::schema::validate_args;
+        let schema = ToRawAmountTool::new().definition().args_schema().unwrap();
+
+        for amount in ["1", " 0.5 ", ".25", "1."] {
+            assert!(validate_args(&schema, &json!({ "amount": amount })).is_ok(), "{}", amount);
+        }
+        assert!(validate_args(&schema, &json!({ "amount": 1.5 })).is_err());
+        assert!(validate_args(&schema, &json!({ "amount": "1,5" })).is_err());
+        assert!(validate_args(&schema, &json!({ "amount": "1", "decimals": -1 })).is_err());
+        // Types and required arguments come from input_schema
+        assert!(validate_args(&schema, &json!({ "amount": "1", "decimals": "18" })).is_err());
+        assert!(validate_args(&schema, &json!({ "decimals": 18 })).is_err());
+    }
 }
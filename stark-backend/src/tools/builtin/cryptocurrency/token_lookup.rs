@@ -6,7 +6,7 @@
 
 use crate::tools::registry::Tool;
 use crate::tools::types::{
-    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+    PropertySchema, StructuredResult, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
 use crate::tools::ToolSafetyLevel;
 use async_trait::async_trait;
@@ -139,6 +139,7 @@ impl TokenLookupTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -286,7 +287,13 @@ impl Tool for TokenLookupTool {
                     "name": token.name,
                     "network": params.network,
                     "cached_in_register": params.cache_as
-                }))
+                })).with_structured(StructuredResult::Token {
+                    symbol: params.symbol.to_uppercase(),
+                    name: token.name.clone(),
+                    address: token.address.clone(),
+                    decimals: token.decimals,
+                    network: params.network.clone(),
+                })
             }
             None => {
                 let available = Self::list_available(&params.network);
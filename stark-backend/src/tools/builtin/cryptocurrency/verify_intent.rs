@@ -12,7 +12,7 @@
 //! 4. Return `Ok(())` or `Err(reason)`
 
 use crate::ai::{AiClient, Message, MessageRole};
-use crate::gateway::protocol::GatewayEvent;
+use crate::gateway::protocol::{GatewayEvent, ToolResultEvent};
 use crate::tools::types::ToolContext;
 use serde_json::Value;
 
@@ -147,9 +147,14 @@ fn broadcast_tool_result(context: &ToolContext, result: &Result<(), String>, dur
             Ok(()) => (true, "Transaction intent verified — checks passed.".to_string()),
             Err(reason) => (false, reason.clone()),
         };
-        broadcaster.broadcast(GatewayEvent::tool_result(
-            channel_id, None, "verify_intent", success, duration_ms, &content, false, None,
-        ));
+        broadcaster.broadcast(GatewayEvent::tool_result(ToolResultEvent {
+            channel_id,
+            tool_name: "verify_intent",
+            success,
+            duration_ms,
+            content: &content,
+            ..Default::default()
+        }));
     }
 }
 
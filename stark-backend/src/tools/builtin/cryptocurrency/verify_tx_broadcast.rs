@@ -5,7 +5,7 @@
 //! the result matches the user's original intent.
 
 use crate::ai::{AiClient, Message, MessageRole};
-use crate::gateway::protocol::GatewayEvent;
+use crate::gateway::protocol::{GatewayEvent, ToolResultEvent};
 use crate::tools::registry::Tool;
 use crate::tools::rpc_config::resolve_rpc_from_context;
 use crate::tools::types::{
@@ -55,6 +55,7 @@ impl VerifyTxBroadcastTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -318,13 +319,14 @@ impl Tool for VerifyTxBroadcastTool {
 
         // Emit tool-result event
         if let (Some(broadcaster), Some(ch_id)) = (&context.broadcaster, context.channel_id) {
-            broadcaster.broadcast(GatewayEvent::tool_result(
-                ch_id, None, "verify_tx_broadcast",
-                final_status == "confirmed",
+            broadcaster.broadcast(GatewayEvent::tool_result(ToolResultEvent {
+                channel_id: ch_id,
+                tool_name: "verify_tx_broadcast",
+                success: final_status == "confirmed",
                 duration_ms,
-                &msg,
-                false, None,
-            ));
+                content: &msg,
+                ..Default::default()
+            }));
         }
 
         let verified = final_status == "confirmed" && matches!(ai_verdict, Some(Ok(())));
@@ -118,6 +118,7 @@ impl Web3FunctionCallTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
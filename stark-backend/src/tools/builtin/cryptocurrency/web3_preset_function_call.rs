@@ -68,6 +68,7 @@ impl Web3PresetFunctionCallTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
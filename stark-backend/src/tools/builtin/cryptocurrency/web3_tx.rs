@@ -17,7 +17,7 @@ use crate::tools::rpc_config::{resolve_rpc_from_context, Network, ResolvedRpcCon
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
-use crate::tx_queue::QueuedTransaction;
+use crate::tx_queue::{GasStrategy, QueuedTransaction, TxQueueManager, TxSimulation};
 use crate::wallet::WalletProvider;
 use crate::x402::X402EvmRpc;
 use async_trait::async_trait;
@@ -44,6 +44,7 @@ struct SignedTxResult {
     nonce: u64,
     signed_tx_hex: String,
     network: String,
+    simulation: TxSimulation,
 }
 
 /// Send ETH tool - native ETH transfers only
@@ -77,6 +78,7 @@ impl SendEthTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -99,6 +101,8 @@ impl SendEthTool {
         value: &str,
         rpc_config: &ResolvedRpcConfig,
         wallet_provider: &Arc<dyn WalletProvider>,
+        gas_strategy: &GasStrategy,
+        tx_queue: &TxQueueManager,
     ) -> Result<SignedTxResult, String> {
         // Create RPC client using WalletProvider for x402 payments
         let rpc = X402EvmRpc::new_with_wallet_provider(
@@ -121,14 +125,18 @@ impl SendEthTool {
         // Parse value
         let tx_value: U256 = parse_u256(value)?;
 
-        // Get nonce
-        let nonce = rpc.get_transaction_count(from_address).await?;
+        // Get nonce, skipping any already held by queued transactions from this wallet
+        let chain_nonce = rpc.get_transaction_count(from_address).await?;
+        let nonce = U256::from(tx_queue.next_nonce(network, &from_str, chain_nonce.as_u64()));
+
+        // Simulate the transfer (recipient may be a contract that rejects ETH)
+        let simulation = crate::web3::simulate_transaction(&rpc, from_address, to_address, &[], tx_value).await;
 
         // Simple ETH transfer is always 21000 gas
         let gas = U256::from(21000u64);
 
-        // Auto-estimate gas prices
-        let (max_fee, priority_fee) = rpc.estimate_eip1559_fees().await?;
+        // Estimate gas prices using the configured strategy
+        let (max_fee, priority_fee) = gas_strategy.estimate_fees(&rpc).await?;
 
         log::info!(
             "[send_eth] Signing ETH transfer: to={}, value={}, gas={}, nonce={} on {}",
@@ -169,6 +177,7 @@ impl SendEthTool {
             nonce: nonce.as_u64(),
             signed_tx_hex,
             network: network.to_string(),
+            simulation,
         })
     }
 
@@ -364,7 +373,7 @@ impl Tool for SendEthTool {
             .as_ref()
             .map(|ct| {
                 let ct_lower = ct.to_lowercase();
-                ct_lower == "discord" || ct_lower == "telegram" || ct_lower == "slack"
+                ct_lower == "discord" || ct_lower == "telegram" || ct_lower == "slack" || ct_lower == "matrix"
             })
             .unwrap_or(false);
 
@@ -386,9 +395,9 @@ impl Tool for SendEthTool {
         };
 
         // Get wallet provider (required for signing)
-        let wallet_provider = match &context.wallet_provider {
-            Some(wp) => wp,
-            None => return ToolResult::error("Wallet not configured. Cannot sign transactions."),
+        let wallet_provider = match context.signing_wallet() {
+            Ok(wp) => wp,
+            Err(e) => return ToolResult::error(format!("{} Cannot sign transactions.", e)),
         };
 
         // Resolve RPC configuration
@@ -400,7 +409,9 @@ impl Tool for SendEthTool {
             &tx_data.to,
             &tx_data.value,
             &rpc_config,
-            wallet_provider,
+            &wallet_provider,
+            &GasStrategy::from_context_extra(&context.extra),
+            tx_queue,
         ).await {
             Ok(signed) => {
                 // Verify intent before queueing
@@ -443,7 +454,8 @@ impl Tool for SendEthTool {
                     signed.nonce,
                     signed.signed_tx_hex.clone(),
                     context.channel_id,
-                );
+                )
+                .with_simulation(Some(signed.simulation.clone()));
 
                 // Queue the transaction
                 tx_queue.queue(queued_tx);
@@ -459,6 +471,7 @@ impl Tool for SendEthTool {
                 msg.push_str(&format!("To: {}\n", signed.to));
                 msg.push_str(&format!("Value: {} ({})\n", signed.value, Self::format_eth(&signed.value)));
                 msg.push_str(&format!("Nonce: {}\n", signed.nonce));
+                msg.push_str(&format!("Simulation: {}\n", signed.simulation.summary()));
                 msg.push_str("\n--- Next Steps ---\n");
                 msg.push_str("To view queued: use `list_queued_web3_tx`\n");
                 msg.push_str(&format!("To broadcast: use `broadcast_web3_tx` with uuid: {}\n", uuid));
@@ -473,7 +486,8 @@ impl Tool for SendEthTool {
                     "nonce": signed.nonce,
                     "gas_limit": signed.gas_limit,
                     "max_fee_per_gas": signed.max_fee_per_gas,
-                    "max_priority_fee_per_gas": signed.max_priority_fee_per_gas
+                    "max_priority_fee_per_gas": signed.max_priority_fee_per_gas,
+                    "simulation": signed.simulation
                 }))
             }
             Err(e) => ToolResult::error(Self::parse_rpc_error(&e, &tx_data, network.as_ref())),
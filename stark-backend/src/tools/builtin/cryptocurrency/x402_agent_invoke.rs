@@ -4,7 +4,7 @@
 
 use crate::tools::registry::Tool;
 use crate::tools::types::{
-    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+    CostEstimate, PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
 use crate::x402::X402Signer;
 use async_trait::async_trait;
@@ -78,6 +78,7 @@ impl X402AgentInvokeTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -162,6 +163,30 @@ struct AgentPaymentOption {
     extra: Option<AgentPaymentExtra>,
 }
 
+impl AgentPaymentOption {
+    /// Convert to the shared x402 type used for asset selection and signing
+    fn to_requirements(&self) -> crate::x402::PaymentRequirements {
+        crate::x402::PaymentRequirements {
+            scheme: self.scheme.clone(),
+            network: self.network.clone(),
+            max_amount_required: self.max_amount_required.clone(),
+            pay_to_address: self.pay_to.clone(),
+            asset: self.asset.clone(),
+            max_timeout_seconds: self.max_timeout_seconds.unwrap_or(300),
+            resource: self.resource.clone(),
+            description: self.description.clone(),
+            extra: self.extra.as_ref().map(|e| crate::x402::PaymentExtra {
+                token: e.token.clone(),
+                address: e.address.clone(),
+                decimals: e.decimals,
+                name: e.name.clone(),
+                version: e.version.clone(),
+                facilitator_signer: e.facilitator_signer.clone(),
+            }),
+        }
+    }
+}
+
 /// Payment payload for X-PAYMENT header (matches x402 spec)
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -218,6 +243,22 @@ impl Tool for X402AgentInvokeTool {
         self.definition.clone()
     }
 
+    /// The price is only known once the endpoint answers 402, so estimate the
+    /// per-call USDC cap
+    fn estimate_cost(&self, params: &Value, _context: &ToolContext) -> Option<CostEstimate> {
+        let target = params.get("agent_url").and_then(|v| v.as_str())?;
+        let host = url::Url::parse(target)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_else(|| target.to_string());
+        Some(CostEstimate {
+            amount: crate::x402::payment_limits::per_call_cap("USDC")?,
+            currency: "USDC".to_string(),
+            is_upper_bound: true,
+            description: format!("x402 payment to {}", host),
+        })
+    }
+
     async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
         let params: X402AgentInvokeParams = match serde_json::from_value(params) {
             Ok(p) => p,
@@ -296,22 +337,21 @@ impl Tool for X402AgentInvokeTool {
             }
         };
 
-        // Find a matching payment option for our network
-        let payment_option = payment_info
-            .accepts
-            .iter()
-            .find(|opt| {
-                opt.network == params.network ||
-                opt.network == "base" && params.network == "base" ||
-                opt.network == "base-sepolia" && params.network == "base-sepolia"
-            })
-            .or_else(|| payment_info.accepts.first());
-
-        let payment_option = match payment_option {
-            Some(opt) => opt.clone(),
-            None => return ToolResult::error("No compatible payment option found in 402 response"),
+        // Get signer
+        let signer = match self.get_signer(context) {
+            Ok(s) => s,
+            Err(e) => return ToolResult::error(e),
         };
 
+        let wallet_address = signer.address();
+
+        // Pick the payment option (asset) for our network and wallet balance
+        let payment_option =
+            match select_payment_option(&payment_info.accepts, &params.network, &wallet_address).await {
+                Ok(opt) => opt,
+                Err(e) => return ToolResult::error(e),
+            };
+
         log::info!(
             "[x402_agent] Payment required: {} units to {} on {}",
             payment_option.max_amount_required,
@@ -319,21 +359,16 @@ impl Tool for X402AgentInvokeTool {
             payment_option.network
         );
 
-        // Check payment limit before signing
-        if let Err(e) = crate::x402::payment_limits::check_payment_limit(
+        // Check the payment limit and hold the amount against the budget;
+        // the reservation is released if the payment is never sent or is rejected
+        let reservation = match crate::x402::payment_limits::reserve_spend(
             &payment_option.asset,
             &payment_option.max_amount_required,
         ) {
-            return ToolResult::error(e);
-        }
-
-        // Get signer
-        let signer = match self.get_signer(context) {
-            Ok(s) => s,
+            Ok(r) => r,
             Err(e) => return ToolResult::error(e),
         };
 
-        let wallet_address = signer.address();
         log::info!("[x402_agent] Signing payment with wallet: {}", wallet_address);
 
         // Sign the payment using EIP-3009
@@ -357,16 +392,17 @@ impl Tool for X402AgentInvokeTool {
         log::info!("[x402_agent] Payment JSON: {}", payment_json);
         log::info!("[x402_agent] Payment header (first 100 chars): {}...", &payment_header[..payment_header.len().min(100)]);
 
-        // Retry with payment
-        let paid_response = match client
+        // Retry with payment; once it's sent the reservation is kept unless the agent rejects it
+        let sent = client
             .post(&url)
             .timeout(Duration::from_secs(60))
             .header(header::CONTENT_TYPE, "application/json")
             .header("X-PAYMENT", &payment_header)
             .json(&body)
             .send()
-            .await
-        {
+            .await;
+        crate::x402::settle_sent_payment(reservation, &sent);
+        let paid_response = match sent {
             Ok(r) => r,
             Err(e) => return ToolResult::error(format!("Paid request failed: {}", e)),
         };
@@ -409,34 +445,33 @@ impl Tool for X402AgentInvokeTool {
     }
 }
 
+/// Pick the option to pay with. Options on the requested network are preferred; among
+/// them the shared asset selector applies payment limits, the default asset and balances.
+async fn select_payment_option(
+    accepts: &[AgentPaymentOption],
+    network: &str,
+    wallet_address: &str,
+) -> Result<AgentPaymentOption, String> {
+    let on_network: Vec<&AgentPaymentOption> = accepts.iter().filter(|o| o.network == network).collect();
+    let candidates = if on_network.is_empty() { accepts.iter().collect() } else { on_network };
+    let requirements: Vec<crate::x402::PaymentRequirements> =
+        candidates.iter().map(|o| o.to_requirements()).collect();
+    let selected =
+        crate::x402::asset_select::select_payment_option(&requirements, wallet_address).await?;
+    let index = requirements
+        .iter()
+        .position(|r| std::ptr::eq(r, selected))
+        .unwrap_or(0);
+    Ok(candidates[index].clone())
+}
+
 /// Sign payment using EIP-2612 (permit) or EIP-3009 (exact) based on scheme
 async fn sign_agent_payment(
     signer: &X402Signer,
     option: &AgentPaymentOption,
     x402_version: u8,
 ) -> Result<PaymentPayload, String> {
-    // Convert local extra to the x402 types extra
-    let extra = option.extra.as_ref().map(|e| crate::x402::PaymentExtra {
-        token: e.token.clone(),
-        address: e.address.clone(),
-        decimals: e.decimals,
-        name: e.name.clone(),
-        version: e.version.clone(),
-        facilitator_signer: e.facilitator_signer.clone(),
-    });
-
-    // Create payment requirements in the format the signer expects
-    let requirements = crate::x402::PaymentRequirements {
-        scheme: option.scheme.clone(),
-        network: option.network.clone(),
-        max_amount_required: option.max_amount_required.clone(),
-        pay_to_address: option.pay_to.clone(),
-        asset: option.asset.clone(),
-        max_timeout_seconds: option.max_timeout_seconds.unwrap_or(300),
-        resource: option.resource.clone(),
-        description: option.description.clone(),
-        extra,
-    };
+    let requirements = option.to_requirements();
 
     log::info!(
         "[x402_agent_invoke] Signing {} payment for {} on {}",
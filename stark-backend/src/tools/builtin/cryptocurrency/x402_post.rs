@@ -8,7 +8,7 @@
 use crate::erc8128::Erc8128Signer;
 use crate::tools::registry::Tool;
 use crate::tools::types::{
-    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+    CostEstimate, PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
 };
 use crate::x402::X402Signer;
 use async_trait::async_trait;
@@ -90,6 +90,7 @@ impl X402PostTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -235,6 +236,30 @@ struct PaymentOption {
     extra: Option<PaymentOptionExtra>,
 }
 
+impl PaymentOption {
+    /// Convert to the shared x402 type used for asset selection and signing
+    fn to_requirements(&self) -> crate::x402::PaymentRequirements {
+        crate::x402::PaymentRequirements {
+            scheme: self.scheme.clone(),
+            network: self.network.clone(),
+            max_amount_required: self.max_amount_required.clone(),
+            pay_to_address: self.pay_to.clone(),
+            asset: self.asset.clone(),
+            max_timeout_seconds: self.max_timeout_seconds.unwrap_or(300),
+            resource: self.resource.clone(),
+            description: self.description.clone(),
+            extra: self.extra.as_ref().map(|e| crate::x402::PaymentExtra {
+                token: e.token.clone(),
+                address: e.address.clone(),
+                decimals: e.decimals,
+                name: e.name.clone(),
+                version: e.version.clone(),
+                facilitator_signer: e.facilitator_signer.clone(),
+            }),
+        }
+    }
+}
+
 /// Payment payload for X-PAYMENT header
 /// This is a simplified local type - the actual payload comes from X402Signer
 #[derive(Debug, Serialize)]
@@ -292,6 +317,22 @@ impl Tool for X402PostTool {
         self.definition.clone()
     }
 
+    /// The price is only known once the endpoint answers 402, so estimate the
+    /// per-call USDC cap
+    fn estimate_cost(&self, params: &Value, _context: &ToolContext) -> Option<CostEstimate> {
+        let target = params.get("url").and_then(|v| v.as_str())?;
+        let host = url::Url::parse(target)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_else(|| target.to_string());
+        Some(CostEstimate {
+            amount: crate::x402::payment_limits::per_call_cap("USDC")?,
+            currency: "USDC".to_string(),
+            is_upper_bound: true,
+            description: format!("x402 payment to {}", host),
+        })
+    }
+
     async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
         let mut params: X402PostParams = match serde_json::from_value(params) {
             Ok(p) => p,
@@ -403,20 +444,21 @@ impl Tool for X402PostTool {
             }
         };
 
-        // Find matching payment option
-        let payment_option = payment_info
-            .accepts
-            .iter()
-            .find(|opt| opt.network == params.network)
-            .or_else(|| payment_info.accepts.first());
-
-        let payment_option = match payment_option {
-            Some(opt) => opt.clone(),
-            None => {
-                return ToolResult::error("No compatible payment option found in 402 response")
-            }
+        // Get signer
+        let signer = match self.get_signer(context) {
+            Ok(s) => s,
+            Err(e) => return ToolResult::error(e),
         };
 
+        let wallet_address = signer.address();
+
+        // Pick the payment option (asset) to use; enforces payment limits before signing
+        let payment_option =
+            match select_payment_option(&payment_info.accepts, &params.network, &wallet_address).await {
+                Ok(opt) => opt,
+                Err(e) => return ToolResult::error(e),
+            };
+
         log::info!(
             "[x402_post] Payment: {} units to {} on {}",
             payment_option.max_amount_required,
@@ -424,14 +466,6 @@ impl Tool for X402PostTool {
             payment_option.network
         );
 
-        // Get signer
-        let signer = match self.get_signer(context) {
-            Ok(s) => s,
-            Err(e) => return ToolResult::error(e),
-        };
-
-        let wallet_address = signer.address();
-
         // Sign payment
         let payment_payload =
             match sign_payment(&signer, &payment_option, payment_info.x402_version).await {
@@ -517,33 +551,33 @@ impl Tool for X402PostTool {
     }
 }
 
+/// Pick the option to pay with. Options on the requested network are preferred; among
+/// them the shared asset selector applies payment limits, the default asset and balances.
+async fn select_payment_option(
+    accepts: &[PaymentOption],
+    network: &str,
+    wallet_address: &str,
+) -> Result<PaymentOption, String> {
+    let on_network: Vec<&PaymentOption> = accepts.iter().filter(|o| o.network == network).collect();
+    let candidates = if on_network.is_empty() { accepts.iter().collect() } else { on_network };
+    let requirements: Vec<crate::x402::PaymentRequirements> =
+        candidates.iter().map(|o| o.to_requirements()).collect();
+    let selected =
+        crate::x402::asset_select::select_payment_option(&requirements, wallet_address).await?;
+    let index = requirements
+        .iter()
+        .position(|r| std::ptr::eq(r, selected))
+        .unwrap_or(0);
+    Ok(candidates[index].clone())
+}
+
 /// Sign payment using EIP-2612 (permit) or EIP-3009 (exact) based on scheme
 async fn sign_payment(
     signer: &X402Signer,
     option: &PaymentOption,
     x402_version: u8,
 ) -> Result<PaymentPayload, String> {
-    // Convert local extra to the x402 types extra
-    let extra = option.extra.as_ref().map(|e| crate::x402::PaymentExtra {
-        token: e.token.clone(),
-        address: e.address.clone(),
-        decimals: e.decimals,
-        name: e.name.clone(),
-        version: e.version.clone(),
-        facilitator_signer: e.facilitator_signer.clone(),
-    });
-
-    let requirements = crate::x402::PaymentRequirements {
-        scheme: option.scheme.clone(),
-        network: option.network.clone(),
-        max_amount_required: option.max_amount_required.clone(),
-        pay_to_address: option.pay_to.clone(),
-        asset: option.asset.clone(),
-        max_timeout_seconds: option.max_timeout_seconds.unwrap_or(300),
-        resource: option.resource.clone(),
-        description: option.description.clone(),
-        extra,
-    };
+    let requirements = option.to_requirements();
 
     log::info!(
         "[x402_post] Signing {} payment for {} on {}",
@@ -420,6 +420,7 @@ impl X402FetchTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: true,
+                ..Default::default()
             },
         }
     }
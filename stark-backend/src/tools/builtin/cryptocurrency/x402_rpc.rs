@@ -94,6 +94,7 @@ impl X402RpcTool {
                 },
                 group: ToolGroup::Finance,
                 hidden: false,
+                ..Default::default()
             },
             client: Arc::new(RwLock::new(None)),
         }
@@ -0,0 +1,291 @@
+//! Forget Memory Tool
+//!
+//! Delete a memory the agent knows is wrong, by ID or by a content match.
+//! Its embedding and associations are removed with it. Only the requesting
+//! identity's own memories can be deleted (safemode-identity ones in safe mode).
+
+use crate::memory::{memory_deletable_by, SAFE_MODE_IDENTITY};
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+};
+use crate::tools::ToolSafetyLevel;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Candidates listed when a content match is ambiguous
+const MAX_CANDIDATES: i32 = 5;
+
+/// Tool for deleting a single memory
+pub struct ForgetMemoryTool {
+    definition: ToolDefinition,
+}
+
+impl ForgetMemoryTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "id".to_string(),
+            PropertySchema {
+                schema_type: "integer".to_string(),
+                description: "ID of the memory to delete (from memory_search or memory_read results).".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "content".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Text contained in the memory to delete (case-insensitive). Must match exactly one memory; otherwise the candidates are listed so you can pick an id.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "reason".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Why the memory is being forgotten (recorded in the logs).".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        Self {
+            definition: ToolDefinition {
+                name: "forget_memory".to_string(),
+                description: "Permanently delete a memory that is wrong or outdated, by id or by a content match. Its associations and embedding are removed too. Prefer memory_merge when a memory only needs correcting.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec![],
+                },
+                group: ToolGroup::Memory,
+                hidden: false,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl Default for ForgetMemoryTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ForgetParams {
+    id: Option<i64>,
+    content: Option<String>,
+    reason: Option<String>,
+}
+
+/// Check if tool context indicates safe mode
+fn is_safe_mode(context: &ToolContext) -> bool {
+    context.extra.get("safe_mode")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+#[async_trait]
+impl Tool for ForgetMemoryTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: ForgetParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let db = match &context.database {
+            Some(db) => db,
+            None => {
+                return ToolResult::error(
+                    "Database not available. Forgetting a memory requires the database to be initialized.",
+                );
+            }
+        };
+
+        // Safe mode is sandboxed to the safemode identity, standard mode to the
+        // requesting identity
+        let identity_id: Option<&str> = if is_safe_mode(context) {
+            Some(SAFE_MODE_IDENTITY)
+        } else {
+            context.identity_id.as_deref()
+        };
+
+        let memory = match (params.id, params.content.as_deref().map(str::trim)) {
+            (Some(id), _) => match db.get_memory(id) {
+                Ok(Some(mem)) if memory_deletable_by(mem.identity_id.as_deref(), identity_id) => mem,
+                Ok(Some(_)) => {
+                    return ToolResult::error(format!(
+                        "Access denied: memory #{} is outside this session's memory scope.",
+                        id
+                    ));
+                }
+                Ok(None) => return ToolResult::error(format!("No memory found with ID {}.", id)),
+                Err(e) => return ToolResult::error(format!("Failed to read memory: {}", e)),
+            },
+            (None, Some(text)) if !text.is_empty() => {
+                let mut matches = match db.find_memories_containing(text, identity_id, MAX_CANDIDATES + 1) {
+                    Ok(m) => m,
+                    Err(e) => return ToolResult::error(format!("Failed to search memories: {}", e)),
+                };
+                match matches.len() {
+                    0 => return ToolResult::error(format!("No memory contains \"{}\".", text)),
+                    1 => matches.remove(0),
+                    n => {
+                        let listed: Vec<String> = matches
+                            .iter()
+                            .take(MAX_CANDIDATES as usize)
+                            .map(|m| {
+                                let snippet: String = m.content.chars().take(120).collect();
+                                format!("- #{} ({}): {}", m.id, m.memory_type, snippet.replace('\n', " "))
+                            })
+                            .collect();
+                        return ToolResult::error(format!(
+                            "{}{} memories contain \"{}\". Call forget_memory again with the id of the one to delete:\n{}",
+                            if n > MAX_CANDIDATES as usize { "More than " } else { "" },
+                            n.min(MAX_CANDIDATES as usize),
+                            text,
+                            listed.join("\n")
+                        ));
+                    }
+                }
+            }
+            _ => return ToolResult::error("Provide either an id or content to match."),
+        };
+
+        match db.delete_memory(memory.id) {
+            Ok(true) => {
+                log::info!(
+                    "[MEMORY_FORGET] Deleted memory #{} (type={}, identity={:?}, channel={:?}, reason={:?}): {}",
+                    memory.id,
+                    memory.memory_type,
+                    memory.identity_id,
+                    context.channel_id,
+                    params.reason,
+                    memory.content.chars().take(200).collect::<String>()
+                );
+                if let Some(ref engine) = context.hybrid_search {
+                    engine.invalidate_caches();
+                }
+                ToolResult::success(format!(
+                    "Forgot memory #{} ({}): {}",
+                    memory.id, memory.memory_type, memory.content
+                ))
+                .with_metadata(json!({
+                    "deleted_id": memory.id,
+                    "memory_type": memory.memory_type,
+                    "identity_id": memory.identity_id,
+                    "content": memory.content,
+                    "reason": params.reason
+                }))
+            }
+            Ok(false) => ToolResult::error(format!("Memory #{} was already deleted.", memory.id)),
+            Err(e) => ToolResult::error(format!("Failed to delete memory #{}: {}", memory.id, e)),
+        }
+    }
+
+    fn safety_level(&self) -> ToolSafetyLevel {
+        ToolSafetyLevel::SafeMode
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use std::sync::Arc;
+
+    fn insert(db: &Database, content: &str, identity_id: Option<&str>) -> i64 {
+        db.insert_memory("long_term", content, None, None, 5, identity_id, None, None, None, None, None, None)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_forget_memory_by_id_and_content() {
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        let wrong = insert(&db, "The user's wallet is on Solana", None);
+        let other = insert(&db, "The user likes tea", None);
+        db.create_memory_association(wrong, other, "related", 0.9, None).unwrap();
+        insert(&db, "The user likes coffee", None);
+
+        let tool = ForgetMemoryTool::new();
+        let context = ToolContext::new().with_database(db.clone());
+
+        let result = tool.execute(json!({ "id": wrong, "reason": "wrong chain" }), &context).await;
+        assert!(result.success, "{}", result.content);
+        assert!(db.get_memory(wrong).unwrap().is_none());
+        assert!(db.get_memory_associations(other).unwrap().is_empty());
+
+        // Ambiguous content lists candidates instead of deleting
+        let result = tool.execute(json!({ "content": "the user likes" }), &context).await;
+        assert!(!result.success && result.content.contains(&format!("#{}", other)));
+
+        let result = tool.execute(json!({ "content": "likes TEA" }), &context).await;
+        assert!(result.success, "{}", result.content);
+        assert!(db.get_memory(other).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_forget_memory_respects_safe_mode_scope() {
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        let operator = insert(&db, "Operator API key lives in the vault", None);
+        let shared = insert(&db, "Always answer politely", Some(crate::memory::SHARED_MEMORY_IDENTITY));
+        let sandboxed = insert(&db, "Guest asked about the vault", Some(SAFE_MODE_IDENTITY));
+
+        let tool = ForgetMemoryTool::new();
+        let mut context = ToolContext::new().with_database(db.clone());
+        context.extra.insert("safe_mode".to_string(), json!(true));
+
+        for id in [operator, shared] {
+            let result = tool.execute(json!({ "id": id }), &context).await;
+            assert!(!result.success);
+            assert!(db.get_memory(id).unwrap().is_some());
+        }
+        // Content matches only see the safemode identity's memories
+        let result = tool.execute(json!({ "content": "vault" }), &context).await;
+        assert!(result.success, "{}", result.content);
+        assert!(db.get_memory(sandboxed).unwrap().is_none());
+        assert!(db.get_memory(operator).unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_forget_memory_cannot_delete_another_identitys_memory() {
+        let db = Arc::new(Database::new(":memory:").unwrap());
+        let bobs = insert(&db, "Bob's wallet is a Ledger", Some("bob"));
+        let shared = insert(&db, "Wallets should be backed up", Some(crate::memory::SHARED_MEMORY_IDENTITY));
+        let alices = insert(&db, "Alice's wallet is a Trezor", Some("alice"));
+
+        let tool = ForgetMemoryTool::new();
+        let context = ToolContext::new().with_database(db.clone()).with_identity("alice".to_string());
+
+        for id in [bobs, shared] {
+            let result = tool.execute(json!({ "id": id }), &context).await;
+            assert!(!result.success && result.content.contains("Access denied"), "{}", result.content);
+            assert!(db.get_memory(id).unwrap().is_some());
+        }
+        let result = tool.execute(json!({ "content": "Ledger" }), &context).await;
+        assert!(!result.success);
+        assert!(db.get_memory(bobs).unwrap().is_some());
+
+        // Content matches only see alice's memories, so "wallet is" is not ambiguous
+        let result = tool.execute(json!({ "content": "wallet is" }), &context).await;
+        assert!(result.success, "{}", result.content);
+        assert!(db.get_memory(alices).unwrap().is_none());
+        assert!(db.get_memory(bobs).unwrap().is_some());
+    }
+}
@@ -0,0 +1,293 @@
+//! Generic read-only HTTP GET tool.
+//!
+//! Unlike `web_fetch`, this tool never sends a body or changes the method, and
+//! it is bounded by operator policy: an optional domain allowlist
+//! (`STARK_HTTP_GET_ALLOWED_DOMAINS`), a response size cap and a timeout.
+//! Private, loopback and link-local targets are blocked up front by the
+//! `http_get_policy` validator and again here on every redirect hop. Redirects
+//! are followed by hand so each hop connects only to the addresses its host
+//! was vetted at, rather than whatever a second DNS lookup returns.
+
+use crate::tool_validators::http_get::{check_http_get_url, resolve_public_addrs};
+use crate::tools::http_retry::{is_reqwest_error_retryable, HttpRetryManager};
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+    ToolSafetyLevel,
+};
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// Maximum redirects followed before giving up
+const MAX_REDIRECTS: usize = 5;
+
+pub struct HttpGetTool {
+    definition: ToolDefinition,
+}
+
+impl HttpGetTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "url".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "The http(s) URL to GET. Must be a public host on the configured allowlist.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "headers".to_string(),
+            PropertySchema {
+                schema_type: "object".to_string(),
+                description: "Optional request headers (e.g. {\"Accept\": \"application/json\"})".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        HttpGetTool {
+            definition: ToolDefinition {
+                name: "http_get".to_string(),
+                description: "Read-only HTTP GET against a public API. Returns the raw response body (truncated at the configured size cap). Private/internal addresses are blocked.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["url".to_string()],
+                },
+                group: ToolGroup::Web,
+                hidden: false,
+                arg_constraints: Some(json!({
+                    "properties": {
+                        "url": { "pattern": "^https?://" }
+                    },
+                    "additionalProperties": false
+                })),
+            },
+        }
+    }
+}
+
+/// Headers reqwest strips when a redirect leaves the original host
+const CROSS_HOST_SENSITIVE_HEADERS: [HeaderName; 4] = [
+    reqwest::header::AUTHORIZATION,
+    reqwest::header::COOKIE,
+    reqwest::header::PROXY_AUTHORIZATION,
+    reqwest::header::WWW_AUTHENTICATE,
+];
+
+/// Parse the user-supplied headers, rejecting any that aren't valid HTTP
+fn parse_headers(headers: Option<&HashMap<String, String>>) -> Result<HeaderMap, String> {
+    let mut map = HeaderMap::new();
+    for (key, value) in headers.into_iter().flatten() {
+        let name = HeaderName::from_bytes(key.as_bytes())
+            .map_err(|_| format!("Invalid header name '{}'", key))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|_| format!("Invalid value for header '{}'", key))?;
+        map.insert(name, value);
+    }
+    Ok(map)
+}
+
+/// Client for one request to `url`, pinned to the addresses its host was vetted at
+async fn pinned_client(url: &url::Url, context: &ToolContext) -> Result<reqwest::Client, String> {
+    let addrs = resolve_public_addrs(url).await?;
+    let mut builder = reqwest::Client::builder()
+        .timeout(crate::config::http_get_timeout())
+        .user_agent("StarkBot/1.0 (HTTP GET Tool)")
+        .redirect(reqwest::redirect::Policy::none());
+    if let Some(host) = url.host_str()
+        && !addrs.is_empty()
+    {
+        builder = builder.resolve_to_addrs(host, &addrs);
+    }
+
+    if let Some(ref proxy_url) = context.proxy_url
+        && let Ok(proxy) = reqwest::Proxy::all(proxy_url)
+    {
+        builder = builder.proxy(proxy);
+    }
+
+    builder
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
+
+impl Default for HttpGetTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HttpGetParams {
+    url: String,
+    headers: Option<HashMap<String, String>>,
+}
+
+#[async_trait]
+impl Tool for HttpGetTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: HttpGetParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let url = match url::Url::parse(&params.url) {
+            Ok(u) => u,
+            Err(e) => return ToolResult::error(format!("Invalid URL: {}", e)),
+        };
+
+        // The validator already ran, but the registry is optional — never skip the check
+        let allowed_domains = crate::config::http_get_allowed_domains();
+        if let Err(e) = check_http_get_url(&url, &allowed_domains) {
+            return ToolResult::error(e);
+        }
+
+        let mut headers = match parse_headers(params.headers.as_ref()) {
+            Ok(h) => h,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        let max_bytes = crate::config::http_get_max_response_bytes();
+        let retry_key = url.host_str().unwrap_or("unknown").to_string();
+        let retry_manager = HttpRetryManager::global();
+
+        let mut current = url.clone();
+        let mut redirects = 0;
+        let mut response = loop {
+            let client = match pinned_client(&current, context).await {
+                Ok(c) => c,
+                Err(e) => return ToolResult::error(e),
+            };
+            let response = match client.get(current.clone()).headers(headers.clone()).send().await {
+                Ok(r) => r,
+                Err(e) => {
+                    let error_msg = format!("Request failed: {}", e);
+                    if is_reqwest_error_retryable(&e) {
+                        let delay = retry_manager.record_error(&retry_key);
+                        return ToolResult::retryable_error(error_msg, delay);
+                    }
+                    return ToolResult::error(error_msg);
+                }
+            };
+
+            let location = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .filter(|_| response.status().is_redirection());
+            let Some(location) = location else {
+                break response;
+            };
+            if redirects >= MAX_REDIRECTS {
+                return ToolResult::error("Request failed: too many redirects");
+            }
+            let next = match current.join(location) {
+                Ok(u) => u,
+                Err(e) => return ToolResult::error(format!("Invalid redirect location '{}': {}", location, e)),
+            };
+            if let Err(e) = check_http_get_url(&next, &allowed_domains) {
+                return ToolResult::error(format!("Request failed: redirect blocked: {}", e));
+            }
+            if next.host_str() != current.host_str() {
+                for name in &CROSS_HOST_SENSITIVE_HEADERS {
+                    headers.remove(name);
+                }
+            }
+            redirects += 1;
+            current = next;
+        };
+
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+            .to_string();
+
+        // Stream the body so an oversized response is cut off rather than buffered
+        let mut body: Vec<u8> = Vec::new();
+        let mut truncated = false;
+        loop {
+            match response.chunk().await {
+                Ok(Some(chunk)) => {
+                    let room = max_bytes - body.len();
+                    if chunk.len() > room {
+                        body.extend_from_slice(&chunk[..room]);
+                        truncated = true;
+                        break;
+                    }
+                    body.extend_from_slice(&chunk);
+                }
+                Ok(None) => break,
+                Err(e) => return ToolResult::error(format!("Failed to read response body: {}", e)),
+            }
+        }
+        let mut text = String::from_utf8_lossy(&body).into_owned();
+        if truncated {
+            text.push_str(&format!("\n\n[Response truncated at {} bytes]", max_bytes));
+        }
+
+        let metadata = json!({
+            "url": url.as_str(),
+            "status": status.as_u16(),
+            "content_type": content_type,
+            "bytes": body.len(),
+            "truncated": truncated,
+        });
+
+        if status.is_server_error() || status.as_u16() == 429 {
+            let delay = retry_manager.record_error(&retry_key);
+            return ToolResult::retryable_error(format!("HTTP {} from {}\n{}", status, url, text), delay)
+                .with_metadata(metadata);
+        }
+        retry_manager.record_success(&retry_key);
+
+        if !status.is_success() {
+            return ToolResult::error(format!("HTTP {} from {}\n{}", status, url, text))
+                .with_metadata(metadata);
+        }
+
+        ToolResult::success(text).with_metadata(metadata)
+    }
+
+    fn safety_level(&self) -> ToolSafetyLevel {
+        ToolSafetyLevel::ReadOnly
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_invalid_headers_are_rejected() {
+        let result = HttpGetTool::new()
+            .execute(
+                json!({ "url": "https://8.8.8.8/", "headers": { "bad header": "x" } }),
+                &ToolContext::new(),
+            )
+            .await;
+        assert!(!result.success);
+        assert!(result.content.contains("Invalid header name"), "{}", result.content);
+
+        let err = parse_headers(Some(&HashMap::from([("X-Ok".to_string(), "line\nbreak".to_string())])))
+            .unwrap_err();
+        assert!(err.contains("X-Ok"));
+        assert_eq!(parse_headers(None).unwrap().len(), 0);
+    }
+}
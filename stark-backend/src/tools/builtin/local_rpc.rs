@@ -83,6 +83,7 @@ impl LocalRpcTool {
                 },
                 group: ToolGroup::System,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
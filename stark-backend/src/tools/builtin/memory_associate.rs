@@ -122,6 +122,7 @@ impl MemoryAssociateTool {
                 },
                 group: ToolGroup::Memory,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
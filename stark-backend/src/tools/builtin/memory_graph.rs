@@ -100,6 +100,7 @@ impl MemoryGraphTool {
                 },
                 group: ToolGroup::Memory,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
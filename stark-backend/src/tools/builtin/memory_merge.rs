@@ -85,6 +85,7 @@ impl MemoryMergeTool {
                 },
                 group: ToolGroup::Memory,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -3,6 +3,7 @@
 //! Read memories from the DB-backed memory system.
 //! In safe mode, access is sandboxed to the safemode identity only.
 
+use crate::memory::{memory_visible_to, SAFE_MODE_IDENTITY};
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -77,6 +78,7 @@ impl MemoryReadTool {
                 },
                 group: ToolGroup::Memory,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -104,9 +106,6 @@ fn is_safe_mode(context: &ToolContext) -> bool {
         .unwrap_or(false)
 }
 
-/// Safe mode identity
-const SAFE_MODE_IDENTITY: &str = "safemode";
-
 #[async_trait]
 impl Tool for MemoryReadTool {
     fn definition(&self) -> ToolDefinition {
@@ -141,9 +140,12 @@ impl Tool for MemoryReadTool {
         if let Some(memory_id) = params.id {
             return match db.get_memory(memory_id) {
                 Ok(Some(mem)) => {
-                    // In safe mode, block access to non-safemode memories
-                    if safe_mode && mem.identity_id.as_deref() != Some(SAFE_MODE_IDENTITY) {
-                        return ToolResult::error("Access denied: safe mode only allows reading safemode memories.");
+                    // Memories the identity can't see are reported as missing rather than denied
+                    if !memory_visible_to(mem.identity_id.as_deref(), identity_id) {
+                        return ToolResult::success(format!("No memory found with ID {}.", memory_id));
+                    }
+                    if let Err(e) = db.record_memory_hits(&[mem.id]) {
+                        log::warn!("[MEMORY_READ] Failed to record access for memory {}: {}", mem.id, e);
                     }
                     let output = format!(
                         "## Memory #{} ({})\n**Created:** {} | **Importance:** {}\n\n{}",
@@ -2,8 +2,10 @@
 //!
 //! Full-text search across DB memories using FTS5 BM25 ranking,
 //! with optional hybrid mode (FTS + vector + graph via RRF).
-//! In safe mode, results are sandboxed to the safemode identity only.
+//! Results are scoped to the requesting identity (the safemode identity in safe mode),
+//! plus shared memories.
 
+use crate::memory::SAFE_MODE_IDENTITY;
 use crate::tools::registry::Tool;
 use crate::tools::types::{
     PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
@@ -67,6 +69,7 @@ impl MemorySearchTool {
                 },
                 group: ToolGroup::Memory,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -97,9 +100,6 @@ fn is_safe_mode(context: &ToolContext) -> bool {
         .unwrap_or(false)
 }
 
-/// Safe mode identity
-const SAFE_MODE_IDENTITY: &str = "safemode";
-
 #[async_trait]
 impl Tool for MemorySearchTool {
     fn definition(&self) -> ToolDefinition {
@@ -129,12 +129,12 @@ impl Tool for MemorySearchTool {
         let safe_mode = is_safe_mode(context);
         let result_limit = params.limit.unwrap_or(10).min(50).max(1);
 
-        // Identity filter: safe mode restricts to safemode identity only;
-        // standard mode searches ALL memories (no identity filter).
+        // Identity filter: safe mode restricts to the safemode identity; standard mode
+        // to the requesting identity. Shared memories are visible to both.
         let identity_id: Option<&str> = if safe_mode {
             Some(SAFE_MODE_IDENTITY)
         } else {
-            None
+            context.identity_id.as_deref()
         };
 
         // Extract agent_subtype from tool context for search boost
@@ -146,26 +146,9 @@ impl Tool for MemorySearchTool {
         // Gracefully falls back to FTS-only if the hybrid engine is unavailable or fails.
         if params.mode == "hybrid" {
             if let Some(ref hybrid_engine) = context.hybrid_search {
-                match hybrid_engine.search(&params.query, result_limit as usize, agent_subtype.as_deref()).await {
+                // The engine scopes every signal to the identity filter
+                match hybrid_engine.search(&params.query, result_limit as usize, agent_subtype.as_deref(), identity_id).await {
                     Ok(results) => {
-                        // In safe mode, filter to safemode identity
-                        // (hybrid engine doesn't have identity filtering, so we filter here)
-                        let results: Vec<_> = if safe_mode {
-                            results.into_iter()
-                                .filter(|r| {
-                                    // Check if the memory belongs to safemode identity
-                                    if let Ok(Some(mem)) = db.get_memory(r.memory_id) {
-                                        mem.identity_id.as_deref() == Some(SAFE_MODE_IDENTITY)
-                                    } else {
-                                        false
-                                    }
-                                })
-                                .take(result_limit as usize)
-                                .collect()
-                        } else {
-                            results
-                        };
-
                         if results.is_empty() {
                             return ToolResult::success(format!(
                                 "No memories found matching: \"{}\" (hybrid mode)",
@@ -196,11 +179,16 @@ impl Tool for MemorySearchTool {
                             ));
                         }
 
+                        let memory_ids: Vec<i64> = results.iter().map(|r| r.memory_id).collect();
+                        if let Err(e) = db.record_memory_hits(&memory_ids) {
+                            log::warn!("[MEMORY_SEARCH] Failed to record memory hits: {}", e);
+                        }
+
                         return ToolResult::success(output).with_metadata(json!({
                             "query": params.query,
                             "mode": "hybrid",
                             "result_count": results.len(),
-                            "memory_ids": results.iter().map(|r| r.memory_id).collect::<Vec<_>>()
+                            "memory_ids": memory_ids
                         }));
                     }
                     Err(e) => {
@@ -252,16 +240,12 @@ impl Tool for MemorySearchTool {
                 // Graph expansion: surface memories connected to FTS hits via edges
                 let seed_ids: Vec<i64> = results.iter().map(|(m, _)| m.id).collect();
                 let graph_limit = (result_limit / 2).max(3).min(10);
-                if let Ok(neighbors) = db.graph_expand_from_seeds(&seed_ids, graph_limit) {
+                if let Ok(neighbors) = db.graph_expand_from_seeds(&seed_ids, graph_limit, identity_id) {
                     if !neighbors.is_empty() {
                         // Fetch neighbor memory details
                         let mut graph_entries = Vec::new();
                         for (neighbor_id, strength) in &neighbors {
                             if let Ok(Some(mem)) = db.get_memory(*neighbor_id) {
-                                // In safe mode, only show safemode-identity memories
-                                if safe_mode && mem.identity_id.as_deref() != Some(SAFE_MODE_IDENTITY) {
-                                    continue;
-                                }
                                 graph_entries.push((mem, *strength));
                             }
                         }
@@ -292,6 +276,10 @@ impl Tool for MemorySearchTool {
                     }
                 }
 
+                if let Err(e) = db.record_memory_hits(&all_memory_ids) {
+                    log::warn!("[MEMORY_SEARCH] Failed to record memory hits: {}", e);
+                }
+
                 ToolResult::success(output).with_metadata(json!({
                     "query": params.query,
                     "mode": "fts",
@@ -15,6 +15,8 @@ pub mod cryptocurrency;
 pub mod social_media;
 
 // Individual tools (remaining uncategorized)
+mod forget_memory;
+mod http_get;
 mod local_rpc;
 mod memory_associate;
 mod memory_graph;
@@ -23,6 +25,7 @@ mod notes;
 mod process_status;
 mod memory_read;
 mod memory_search;
+mod module_rpc;
 mod web_fetch;
 
 // Re-exports from submodules
@@ -44,7 +47,7 @@ pub use core::{
 pub use cryptocurrency::{
     load_networks, load_tokens, BridgeUsdcTool, BroadcastWeb3TxTool, DecodeCalldataTool,
     Erc8128FetchTool, FromRawAmountTool, ListQueuedWeb3TxTool,
-    SelectWeb3NetworkTool, SendEthTool, SetAddressTool, SetNftTokenIdTool, SignRawTxTool,
+    SelectWalletTool, SelectWeb3NetworkTool, SendEthTool, SetAddressTool, SetNftTokenIdTool, SignRawTxTool,
     SiwaAuthTool, SwapTokenTool, ToRawAmountTool, TokenLookupTool,
     VerifyTxBroadcastTool, Web3PresetFunctionCallTool, X402AgentInvokeTool, X402FetchTool,
     X402PostTool, X402RpcTool,
@@ -52,6 +55,8 @@ pub use cryptocurrency::{
 pub use social_media::{DiscordLookupTool, DiscordReadTool, DiscordWriteTool, FigmaTool, GithubUserTool, TelegramReadTool, TelegramWriteTool, TwitterPostTool};
 
 // Re-exports from individual tools
+pub use forget_memory::ForgetMemoryTool;
+pub use http_get::HttpGetTool;
 pub use local_rpc::LocalRpcTool;
 pub use memory_associate::MemoryAssociateTool;
 pub use memory_graph::MemoryGraphTool;
@@ -60,4 +65,6 @@ pub use notes::NotesTool;
 pub use process_status::ProcessStatusTool;
 pub use memory_read::MemoryReadTool;
 pub use memory_search::MemorySearchTool;
+pub use module_rpc::ModuleRpcTool;
 pub use web_fetch::WebFetchTool;
+pub(crate) use web_fetch::{is_private_ip, validate_public_url};
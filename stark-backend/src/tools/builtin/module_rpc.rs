@@ -0,0 +1,189 @@
+//! Module RPC tool — calls any declared RPC method of an enabled module.
+//!
+//! Agents use `module_rpc(module="wallet_monitor", method="watchlist", params={...})`.
+//! The module's service URL comes from its manifest (like `DynamicModule`), and
+//! only methods the manifest declares (tools and ext endpoints) can be called,
+//! so internal or undeclared service routes stay out of reach.
+
+use crate::modules::ModuleRegistry;
+use crate::tools::registry::Tool;
+use crate::tools::types::{
+    PropertySchema, ToolContext, ToolDefinition, ToolGroup, ToolInputSchema, ToolResult,
+    ToolSafetyLevel,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+pub struct ModuleRpcTool {
+    definition: ToolDefinition,
+}
+
+impl ModuleRpcTool {
+    pub fn new() -> Self {
+        let mut properties = HashMap::new();
+
+        properties.insert(
+            "module".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "Module name (e.g. \"wallet_monitor\"). Must be installed and enabled.".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "method".to_string(),
+            PropertySchema {
+                schema_type: "string".to_string(),
+                description: "RPC method declared in the module manifest (a tool name or ext endpoint method name)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        properties.insert(
+            "params".to_string(),
+            PropertySchema {
+                schema_type: "object".to_string(),
+                description: "JSON params (sent as the body, or as query params for GET methods)".to_string(),
+                default: None,
+                items: None,
+                enum_values: None,
+            },
+        );
+
+        ModuleRpcTool {
+            definition: ToolDefinition {
+                name: "module_rpc".to_string(),
+                description: "Call an RPC method of an enabled module by name. Only methods declared in the module's manifest can be called; the error for an unknown method lists the available ones.".to_string(),
+                input_schema: ToolInputSchema {
+                    schema_type: "object".to_string(),
+                    properties,
+                    required: vec!["module".to_string(), "method".to_string()],
+                },
+                group: ToolGroup::System,
+                hidden: false,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl Default for ModuleRpcTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ModuleRpcParams {
+    module: String,
+    method: String,
+    #[serde(default)]
+    params: Option<Value>,
+}
+
+#[async_trait]
+impl Tool for ModuleRpcTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    async fn execute(&self, params: Value, context: &ToolContext) -> ToolResult {
+        let params: ModuleRpcParams = match serde_json::from_value(params) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let registry = ModuleRegistry::new();
+        let Some(module) = registry.get(&params.module) else {
+            return ToolResult::error(format!("Unknown module '{}'", params.module));
+        };
+
+        let Some(db) = context.database.as_ref() else {
+            return ToolResult::error("Database not available to check module status");
+        };
+        if !db.is_module_enabled(&params.module).unwrap_or(false) {
+            return ToolResult::error(format!(
+                "Module '{}' is not enabled",
+                params.module
+            ));
+        }
+
+        let methods = module.rpc_methods();
+        let Some(rpc) = methods.iter().find(|m| m.name == params.method) else {
+            let available: Vec<&str> = methods.iter().map(|m| m.name.as_str()).collect();
+            return ToolResult::error(format!(
+                "Module '{}' does not declare RPC method '{}'. Available: {}",
+                params.module,
+                params.method,
+                if available.is_empty() { "(none)".to_string() } else { available.join(", ") }
+            ));
+        };
+
+        let url = format!(
+            "{}{}",
+            module.service_url().trim_end_matches('/'),
+            rpc.rpc_endpoint
+        );
+        let body = params.params.unwrap_or_else(|| Value::Object(Default::default()));
+
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(15))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+
+        let request = if rpc.http_method == "GET" {
+            let query: Vec<(String, String)> = body
+                .as_object()
+                .map(|m| {
+                    m.iter()
+                        .map(|(k, v)| (k.clone(), v.as_str().map(str::to_string).unwrap_or_else(|| v.to_string())))
+                        .collect()
+                })
+                .unwrap_or_default();
+            client.get(&url).query(&query)
+        } else {
+            client.post(&url).json(&body)
+        };
+
+        let response = match request.send().await {
+            Ok(r) => r,
+            Err(e) => {
+                return ToolResult::error(format!(
+                    "Failed to reach module '{}' at {}: {}",
+                    params.module, url, e
+                ));
+            }
+        };
+
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+
+        if !status.is_success() {
+            let truncated = if text.len() > 2000 {
+                format!("{}...", &text[..text.floor_char_boundary(2000)])
+            } else {
+                text
+            };
+            return ToolResult::error(format!(
+                "HTTP {} from {}.{}\n{}",
+                status, params.module, params.method, truncated
+            ));
+        }
+
+        match serde_json::from_str::<Value>(&text) {
+            Ok(json) => ToolResult::success(serde_json::to_string_pretty(&json).unwrap_or(text)),
+            Err(_) => ToolResult::success(text),
+        }
+    }
+
+    fn safety_level(&self) -> ToolSafetyLevel {
+        ToolSafetyLevel::Standard
+    }
+}
@@ -168,6 +168,7 @@ impl NotesTool {
                 },
                 group: ToolGroup::Memory,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
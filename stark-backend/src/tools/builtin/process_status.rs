@@ -74,6 +74,7 @@ impl ProcessStatusTool {
                 },
                 group: ToolGroup::Exec,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -66,6 +66,7 @@ impl DiscordLookupTool {
                 },
                 group: ToolGroup::Messaging,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -125,6 +125,7 @@ impl DiscordReadTool {
                 },
                 group: ToolGroup::Messaging,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -144,6 +144,7 @@ impl DiscordWriteTool {
                 },
                 group: ToolGroup::Messaging,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -162,6 +162,7 @@ EXAMPLES:
                 },
                 group: ToolGroup::Web,
                 hidden: true, // Activated by the figma skill
+                ..Default::default()
             },
         }
     }
@@ -29,6 +29,7 @@ impl GithubUserTool {
                 },
                 group: ToolGroup::Development,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
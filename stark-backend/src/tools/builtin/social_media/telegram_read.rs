@@ -79,6 +79,7 @@ impl TelegramReadTool {
                 },
                 group: ToolGroup::Messaging,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -111,6 +111,7 @@ impl TelegramWriteTool {
                 },
                 group: ToolGroup::Messaging,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
@@ -79,6 +79,7 @@ impl TwitterPostTool {
                 },
                 group: ToolGroup::Messaging,
                 hidden: false,
+                ..Default::default()
             },
         }
     }
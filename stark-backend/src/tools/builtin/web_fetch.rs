@@ -176,6 +176,7 @@ impl WebFetchTool {
                 },
                 group: ToolGroup::Web,
                 hidden: false,
+                ..Default::default()
             },
             cache: FetchCache::new(900), // 15 minute cache
         }
@@ -634,7 +635,7 @@ impl Tool for WebFetchTool {
 }
 
 /// Validate that a URL points to a public host (not private/internal)
-fn validate_public_url(url: &url::Url) -> Result<(), String> {
+pub(crate) fn validate_public_url(url: &url::Url) -> Result<(), String> {
     let host = url.host_str().ok_or("URL has no host")?;
 
     // Block localhost and common internal hostnames
@@ -680,7 +681,7 @@ fn validate_public_url(url: &url::Url) -> Result<(), String> {
 }
 
 /// Check if an IP address is private/internal
-fn is_private_ip(ip: IpAddr) -> bool {
+pub(crate) fn is_private_ip(ip: IpAddr) -> bool {
     match ip {
         IpAddr::V4(ipv4) => {
             ipv4.is_private()           // 10.x, 172.16-31.x, 192.168.x
@@ -693,8 +694,14 @@ fn is_private_ip(ip: IpAddr) -> bool {
                 || ipv4.octets()[0] == 169 && ipv4.octets()[1] == 254
         }
         IpAddr::V6(ipv6) => {
-            ipv6.is_loopback() || ipv6.is_unspecified()
-            // Note: is_unique_local() and is_unicast_link_local() are unstable
+            if let Some(ipv4) = ipv6.to_ipv4_mapped() {
+                return is_private_ip(IpAddr::V4(ipv4));
+            }
+            let first = ipv6.segments()[0];
+            ipv6.is_loopback()
+                || ipv6.is_unspecified()
+                || (first & 0xfe00) == 0xfc00 // unique local fc00::/7
+                || (first & 0xffc0) == 0xfe80 // link-local fe80::/10
         }
     }
 }
@@ -1093,5 +1100,9 @@ mod tests {
         assert!(is_private_ip("172.16.0.1".parse().unwrap()));
         assert!(!is_private_ip("8.8.8.8".parse().unwrap()));
         assert!(!is_private_ip("1.1.1.1".parse().unwrap()));
+        assert!(is_private_ip("::ffff:127.0.0.1".parse().unwrap()));
+        assert!(is_private_ip("fd00::1".parse().unwrap()));
+        assert!(is_private_ip("fe80::1".parse().unwrap()));
+        assert!(!is_private_ip("2606:4700::1111".parse().unwrap()));
     }
 }
@@ -11,8 +11,8 @@ pub use context_bank::{scan_input, ContextBank, ContextBankItem};
 pub use register::{PresetOrCustom, RegisterStore};
 pub use registry::{Tool, ToolRegistry};
 pub use types::{
-    ChannelOutputType, PropertySchema, ToolConfig, ToolContext, ToolDefinition, ToolExecution,
-    ToolGroup, ToolInputSchema, ToolProfile, ToolResult, ToolSafetyLevel, SAFE_MODE_ALLOW_LIST,
+    ChannelOutputType, CostEstimate, PropertySchema, ToolConfig, ToolContext, ToolDefinition, ToolExecution,
+    StructuredResult, ToolGroup, ToolInputSchema, ToolProfile, ToolResult, ToolSafetyLevel, SAFE_MODE_ALLOW_LIST,
 };
 
 use std::sync::Arc;
@@ -33,6 +33,7 @@ fn register_all_tools(registry: &mut ToolRegistry) {
     registry.register(Arc::new(builtin::MemoryAssociateTool::new()));
     registry.register(Arc::new(builtin::MemoryGraphTool::new()));
     registry.register(Arc::new(builtin::MemoryMergeTool::new()));
+    registry.register(Arc::new(builtin::ForgetMemoryTool::new()));
     // Notes tool (Obsidian-compatible notes with FTS5)
     registry.register(Arc::new(builtin::NotesTool::new()));
     registry.register(Arc::new(builtin::ModifySoulTool::new()));
@@ -65,8 +66,12 @@ fn register_all_tools(registry: &mut ToolRegistry) {
 
     // Web tools (shared)
     registry.register(Arc::new(builtin::WebFetchTool::new()));
+    // Read-only GET bounded by the http_get allowlist/size/timeout policy
+    registry.register(Arc::new(builtin::HttpGetTool::new()));
     // Local RPC — localhost-only HTTP for microservice APIs
     registry.register(Arc::new(builtin::LocalRpcTool::new()));
+    // Module RPC — declared methods of enabled modules, resolved via the manifest
+    registry.register(Arc::new(builtin::ModuleRpcTool::new()));
 
     // Finance tools (crypto/DeFi operations)
     registry.register(Arc::new(builtin::X402RpcTool::new()));
@@ -91,6 +96,8 @@ fn register_all_tools(registry: &mut ToolRegistry) {
     registry.register(Arc::new(builtin::VerifyTxBroadcastTool::new()));
     // Network selection for chain-specific operations
     registry.register(Arc::new(builtin::SelectWeb3NetworkTool::new()));
+    // Wallet selection when several signing wallets are configured
+    registry.register(Arc::new(builtin::SelectWalletTool::new()));
     // Cross-chain USDC bridging via Across Protocol
     registry.register(Arc::new(builtin::BridgeUsdcTool::new()));
     // ERC-8128 signed HTTP requests (Ethereum identity)
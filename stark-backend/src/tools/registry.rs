@@ -1,5 +1,5 @@
 use crate::ai::multi_agent::types;
-use crate::tools::types::{ToolConfig, ToolContext, ToolDefinition, ToolGroup, ToolProfile, ToolResult, ToolSafetyLevel};
+use crate::tools::types::{CostEstimate, ToolConfig, ToolContext, ToolDefinition, ToolGroup, ToolProfile, ToolResult, ToolSafetyLevel};
 use async_trait::async_trait;
 use parking_lot::RwLock;
 use serde_json::Value;
@@ -32,6 +32,14 @@ pub trait Tool: Send + Sync {
     fn safety_level(&self) -> ToolSafetyLevel {
         ToolSafetyLevel::Standard
     }
+
+    /// Expected cost of running the tool with these parameters, for tools that
+    /// make paid API calls or payments. When this returns a non-zero estimate,
+    /// the dispatcher asks the user to confirm before executing (outside rogue
+    /// mode). Defaults to None: no confirmation.
+    fn estimate_cost(&self, _params: &Value, _context: &ToolContext) -> Option<CostEstimate> {
+        None
+    }
 }
 
 /// Registry that holds all available tools.
@@ -313,6 +321,7 @@ mod tests {
                     input_schema: ToolInputSchema::default(),
                     group,
                     hidden: false,
+                    ..Default::default()
                 },
             }
         }
@@ -633,6 +642,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_arg_constraints_only_name_declared_arguments() {
+        let registry = crate::tools::create_default_registry();
+        let mut constrained = 0;
+        for tool in registry.list() {
+            let def = tool.definition();
+            let Some(constraints) = def.arg_constraints.as_ref() else {
+                continue;
+            };
+            constrained += 1;
+            let args = constraints
+                .get("properties")
+                .and_then(|p| p.as_object())
+                .into_iter()
+                .flat_map(|p| p.keys());
+            for arg in args {
+                assert!(
+                    def.input_schema.properties.contains_key(arg),
+                    "'{}' constrains '{}', which is not in its input_schema",
+                    def.name, arg
+                );
+            }
+            let schema = def.args_schema().unwrap();
+            assert_eq!(schema["required"], serde_json::json!(def.input_schema.required), "{}", def.name);
+        }
+        assert!(constrained > 0);
+    }
+
     #[test]
     fn test_safe_mode_specifically_blocks_twitter_post_in_real_registry() {
         // Use the REAL registry — the actual twitter_post tool with its actual group
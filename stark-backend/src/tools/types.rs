@@ -54,6 +54,33 @@ impl ToolSafetyLevel {
     }
 }
 
+/// Expected cost of running a tool, shown to the user for confirmation
+/// before the tool executes (outside rogue mode)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostEstimate {
+    /// Expected amount in `currency` units
+    pub amount: f64,
+    /// e.g. "USDC"
+    pub currency: String,
+    /// True when `amount` is a cap (e.g. the per-call payment limit) rather than a quote
+    #[serde(default)]
+    pub is_upper_bound: bool,
+    /// What the cost is for, e.g. "x402 payment to api.example.com"
+    pub description: String,
+}
+
+impl CostEstimate {
+    /// "up to 1.00 USDC" / "0.25 USDC"
+    pub fn display(&self) -> String {
+        let amount = format!("{:.2} {}", self.amount, self.currency);
+        if self.is_upper_bound {
+            format!("up to {}", amount)
+        } else {
+            amount
+        }
+    }
+}
+
 /// Tool groups for access control
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default, EnumIter)]
 #[serde(rename_all = "lowercase")]
@@ -289,7 +316,7 @@ impl Default for ToolInputSchema {
 }
 
 /// Tool definition that gets sent to the AI
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ToolDefinition {
     pub name: String,
     pub description: String,
@@ -300,6 +327,60 @@ pub struct ToolDefinition {
     /// They can only be activated when a skill declares them in `requires_tools`.
     #[serde(skip)]
     pub hidden: bool,
+    /// Optional JSON schema constraints that `input_schema` can't express
+    /// (patterns, ranges, unknown-argument rejection). `properties.<arg>` entries
+    /// are merged into that argument's schema and any other keys (such as
+    /// `additionalProperties`) are added to the top level; see `args_schema`.
+    /// Tools without constraints are not schema-checked.
+    #[serde(skip)]
+    pub arg_constraints: Option<Value>,
+}
+
+impl ToolDefinition {
+    /// JSON schema the arguments are checked against before execution:
+    /// `input_schema` (argument names, types, enums, required) with
+    /// `arg_constraints` layered on top, so the two can't disagree.
+    pub fn args_schema(&self) -> Option<Value> {
+        let Value::Object(constraints) = self.arg_constraints.as_ref()? else {
+            return None;
+        };
+        let mut schema = serde_json::to_value(&self.input_schema).ok()?;
+        let base = schema.as_object_mut()?;
+        for (key, value) in constraints {
+            match (key.as_str(), value, base.get_mut("properties")) {
+                ("properties", Value::Object(extra), Some(Value::Object(properties))) => {
+                    for (arg, arg_extra) in extra {
+                        match (properties.get_mut(arg), arg_extra) {
+                            (Some(Value::Object(prop)), Value::Object(arg_extra)) => {
+                                prop.extend(arg_extra.clone());
+                            }
+                            _ => {
+                                properties.insert(arg.clone(), arg_extra.clone());
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    base.insert(key.clone(), value.clone());
+                }
+            }
+        }
+        Some(schema)
+    }
+}
+
+/// Machine-readable form of a tool result, carried alongside the human text so
+/// API clients and the UI can render it without parsing. Serializes as
+/// `{"kind": "...", "data": {...}}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+pub enum StructuredResult {
+    /// Prepaid AI credits left on the account
+    CreditBalance { credits_usd: f64 },
+    /// Raw on-chain integer converted to a token amount
+    TokenAmount { raw_amount: String, decimals: u8, amount: String },
+    /// Token resolved from a symbol
+    Token { symbol: String, name: String, address: String, decimals: u8, network: String },
 }
 
 /// Result of tool execution
@@ -315,6 +396,10 @@ pub struct ToolResult {
     /// Used for transient network errors with exponential backoff.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retry_after_secs: Option<u64>,
+    /// Typed form of the result for machine consumers (boxed to keep `ToolResult` small,
+    /// since it is often the error side of a `Result`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub structured: Option<Box<StructuredResult>>,
 }
 
 impl ToolResult {
@@ -325,6 +410,7 @@ impl ToolResult {
             error: None,
             metadata: None,
             retry_after_secs: None,
+            structured: None,
         }
     }
 
@@ -336,6 +422,7 @@ impl ToolResult {
             error: Some(msg),
             metadata: None,
             retry_after_secs: None,
+            structured: None,
         }
     }
 
@@ -352,6 +439,7 @@ impl ToolResult {
             error: Some(msg),
             metadata: None,
             retry_after_secs: Some(retry_after_secs),
+            structured: None,
         }
     }
 
@@ -360,6 +448,11 @@ impl ToolResult {
         self
     }
 
+    pub fn with_structured(mut self, structured: StructuredResult) -> Self {
+        self.structured = Some(Box::new(structured));
+        self
+    }
+
     pub fn with_retry_after(mut self, secs: u64) -> Self {
         self.retry_after_secs = Some(secs);
         self
@@ -409,6 +502,9 @@ pub struct ToolContext {
     pub notes_store: Option<Arc<NoteStore>>,
     /// Wallet provider for signing transactions (Standard or Flash mode)
     pub wallet_provider: Option<Arc<dyn WalletProvider>>,
+    /// Label of the wallet to sign with (None = default wallet).
+    /// A `wallet_label` register set by `select_wallet` takes precedence.
+    pub selected_wallet: Option<String>,
     /// Platform-specific chat/conversation ID (e.g., Telegram chat_id)
     /// Allows tools to query data by chat without going through sessions
     pub platform_chat_id: Option<String>,
@@ -450,6 +546,7 @@ impl std::fmt::Debug for ToolContext {
             .field("selected_network", &self.selected_network)
             .field("notes_store", &self.notes_store.is_some())
             .field("wallet_provider", &self.wallet_provider.is_some())
+            .field("selected_wallet", &self.selected_wallet)
             .field("platform_chat_id", &self.platform_chat_id)
             .field("api_keys", &self.api_keys.read().ok().map(|m| m.len()))
             .field("proxy_url", &self.proxy_url)
@@ -484,6 +581,7 @@ impl Default for ToolContext {
             selected_network: None,
             notes_store: None,
             wallet_provider: None,
+            selected_wallet: None,
             platform_chat_id: None,
             api_keys: Arc::new(RwLock::new(HashMap::new())),
             proxy_url: None,
@@ -690,6 +788,29 @@ impl ToolContext {
         self
     }
 
+    /// Set the wallet label to sign with
+    pub fn with_selected_wallet(mut self, label: Option<String>) -> Self {
+        self.selected_wallet = label;
+        self
+    }
+
+    /// The wallet label selected for this operation, if any
+    pub fn wallet_label(&self) -> Option<String> {
+        self.registers
+            .get("wallet_label")
+            .and_then(|v| v.as_str().map(|s| s.to_string()))
+            .or_else(|| self.selected_wallet.clone())
+    }
+
+    /// Wallet provider narrowed to the selected wallet label
+    pub fn signing_wallet(&self) -> Result<Arc<dyn WalletProvider>, String> {
+        let provider = self
+            .wallet_provider
+            .as_ref()
+            .ok_or_else(|| "Wallet not configured.".to_string())?;
+        crate::wallet::select_wallet(provider, self.wallet_label().as_deref())
+    }
+
     /// Add a NoteStore to the context (for notes tools)
     pub fn with_notes_store(mut self, store: Arc<NoteStore>) -> Self {
         self.notes_store = Some(store);
@@ -951,6 +1072,8 @@ pub struct ToolExecution {
     pub parameters: Value,
     pub success: bool,
     pub result: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub structured_result: Option<StructuredResult>,
     pub duration_ms: Option<i64>,
     pub executed_at: String,
 }
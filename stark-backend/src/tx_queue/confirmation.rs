@@ -0,0 +1,57 @@
+//! Confirmation timeouts for partner-mode transactions
+//!
+//! A transaction queued for user confirmation gets a deadline from the
+//! `tx_confirmation_timeout_secs` bot setting. A background task rejects the
+//! ones nobody answered in time and tells the session that queued them, so the
+//! agent doesn't keep waiting on a confirmation that will never come.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::manager::TxQueueManager;
+use crate::db::Database;
+use crate::gateway::events::EventBroadcaster;
+use crate::gateway::protocol::GatewayEvent;
+use crate::models::MessageRole;
+
+/// How often to look for expired confirmations
+const EXPIRY_CHECK_INTERVAL_SECS: u64 = 5;
+
+/// Auto-reject pending transactions whose confirmation deadline has passed
+pub fn expire_unconfirmed_transactions(
+    tx_queue: &TxQueueManager,
+    broadcaster: &EventBroadcaster,
+    db: &Database,
+) {
+    for tx in tx_queue.expire_unconfirmed() {
+        if let Some(channel_id) = tx.channel_id {
+            broadcaster.broadcast(GatewayEvent::tx_queue_expired(channel_id, &tx.uuid));
+        }
+
+        if let Some(session_id) = tx.confirmation_session_id {
+            let note = format!(
+                "[Transaction {} was not confirmed by the user in time and has been rejected automatically. \
+                It was not broadcast. Queue a new transaction if the user still wants to send it.]",
+                tx.uuid
+            );
+            if let Err(e) = db.add_session_message(session_id, MessageRole::System, &note, None, None, None, None) {
+                log::error!("[TxQueue] Failed to record confirmation timeout for {}: {}", tx.uuid, e);
+            }
+        }
+    }
+}
+
+/// Spawn the background task that auto-rejects unconfirmed transactions
+pub fn spawn_confirmation_expiry_monitor(
+    tx_queue: Arc<TxQueueManager>,
+    broadcaster: Arc<EventBroadcaster>,
+    db: Arc<Database>,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(EXPIRY_CHECK_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            expire_unconfirmed_transactions(&tx_queue, &broadcaster, &db);
+        }
+    });
+}
@@ -0,0 +1,189 @@
+//! Gas price strategy for queued transactions
+//!
+//! The strategy comes from bot settings (`gas_strategy`, `gas_max_fee_gwei`)
+//! and is threaded to the signing tools through `ToolContext.extra`.
+
+use ethers::types::U256;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::models::DEFAULT_GAS_STRATEGY;
+use crate::x402::X402EvmRpc;
+
+const WEI_PER_GWEI: f64 = 1e9;
+
+/// How aggressively to price a transaction relative to the default fee estimate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GasSpeed {
+    Slow,
+    Standard,
+    Fast,
+}
+
+impl GasSpeed {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "slow" => Some(GasSpeed::Slow),
+            "standard" => Some(GasSpeed::Standard),
+            "fast" => Some(GasSpeed::Fast),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GasSpeed::Slow => "slow",
+            GasSpeed::Standard => "standard",
+            GasSpeed::Fast => "fast",
+        }
+    }
+
+    /// (max_fee, priority_fee) multipliers in percent of `estimate_eip1559_fees`
+    fn multipliers(&self) -> (u64, u64) {
+        match self {
+            GasSpeed::Slow => (90, 50),
+            GasSpeed::Standard => (100, 100),
+            GasSpeed::Fast => (140, 200),
+        }
+    }
+}
+
+/// Gas strategy applied when building queued transactions
+#[derive(Debug, Clone)]
+pub struct GasStrategy {
+    pub speed: GasSpeed,
+    /// Absolute ceiling for max_fee_per_gas, in wei
+    pub max_fee_cap: Option<U256>,
+}
+
+impl Default for GasStrategy {
+    fn default() -> Self {
+        Self {
+            speed: GasSpeed::parse(DEFAULT_GAS_STRATEGY).unwrap_or(GasSpeed::Standard),
+            max_fee_cap: None,
+        }
+    }
+}
+
+impl GasStrategy {
+    /// Build the strategy from `ToolContext.extra` (populated from bot settings)
+    pub fn from_context_extra(extra: &HashMap<String, Value>) -> Self {
+        Self::from_settings(
+            extra.get("gas_strategy").and_then(|v| v.as_str()),
+            extra.get("gas_max_fee_gwei").and_then(|v| v.as_f64()),
+        )
+    }
+
+    /// Build the strategy from the raw bot settings values
+    pub fn from_settings(speed: Option<&str>, max_fee_gwei: Option<f64>) -> Self {
+        let speed = speed.and_then(GasSpeed::parse).unwrap_or(GasSpeed::Standard);
+        let max_fee_cap = max_fee_gwei
+            .filter(|g| *g > 0.0)
+            .map(|g| U256::from((g * WEI_PER_GWEI) as u128));
+        Self { speed, max_fee_cap }
+    }
+
+    /// Apply the strategy to the default fee estimate from `estimate_eip1559_fees`
+    /// (whose priority fee is already capped at the gas price).
+    /// Returns an error if the network base fee already exceeds the ceiling.
+    pub fn apply(
+        &self,
+        base_fee: U256,
+        estimated_max_fee: U256,
+        estimated_priority: U256,
+    ) -> Result<(U256, U256), String> {
+        if let Some(cap) = self.max_fee_cap
+            && base_fee > cap
+        {
+            return Err(format!(
+                "Network base fee ({}) exceeds the configured max fee ceiling ({}). \
+                Transaction rejected to avoid overpaying; retry when gas is lower or raise the ceiling in bot settings.",
+                format_gwei(base_fee),
+                format_gwei(cap)
+            ));
+        }
+
+        let (max_fee_pct, priority_pct) = self.speed.multipliers();
+        let mut max_fee = estimated_max_fee * U256::from(max_fee_pct) / U256::from(100);
+        // Never price below the current base fee, or the tx can't be included
+        max_fee = max_fee.max(base_fee);
+        if let Some(cap) = self.max_fee_cap {
+            max_fee = max_fee.min(cap);
+        }
+
+        let priority = estimated_priority * U256::from(priority_pct) / U256::from(100);
+        let priority = priority.min(max_fee);
+
+        Ok((max_fee, priority))
+    }
+
+    /// Estimate EIP-1559 fees from the RPC and apply the strategy
+    pub async fn estimate_fees(&self, rpc: &X402EvmRpc) -> Result<(U256, U256), String> {
+        let (estimated_max_fee, estimated_priority) = rpc.estimate_eip1559_fees().await?;
+        // Only pay for the extra base-fee lookup when a ceiling needs enforcing
+        let base_fee = match self.max_fee_cap {
+            Some(_) => rpc.get_base_fee().await?,
+            None => U256::zero(),
+        };
+        let (max_fee, priority) = self.apply(base_fee, estimated_max_fee, estimated_priority)?;
+        log::debug!(
+            "[gas] strategy={} estimate={}/{} base_fee={} -> max_fee={} priority={}",
+            self.speed.as_str(), estimated_max_fee, estimated_priority, base_fee, max_fee, priority
+        );
+        Ok((max_fee, priority))
+    }
+}
+
+pub(super) fn format_gwei(wei: U256) -> String {
+    format!("{:.4} gwei", wei.as_u128() as f64 / WEI_PER_GWEI)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn gwei(g: u64) -> U256 {
+        U256::from(g) * U256::from(1_000_000_000u64)
+    }
+
+    #[test]
+    fn test_from_context_extra() {
+        let mut extra = HashMap::new();
+        assert_eq!(GasStrategy::from_context_extra(&extra).speed, GasSpeed::Standard);
+
+        extra.insert("gas_strategy".to_string(), json!("fast"));
+        extra.insert("gas_max_fee_gwei".to_string(), json!(2.5));
+        let s = GasStrategy::from_context_extra(&extra);
+        assert_eq!(s.speed, GasSpeed::Fast);
+        assert_eq!(s.max_fee_cap, Some(U256::from(2_500_000_000u64)));
+    }
+
+    #[test]
+    fn test_apply_speeds() {
+        // Standard keeps the default estimate unchanged
+        let standard = GasStrategy::default();
+        let (max_fee, prio) = standard.apply(gwei(8), gwei(11), gwei(1)).unwrap();
+        assert_eq!(max_fee, gwei(11));
+        assert_eq!(prio, gwei(1));
+
+        let fast = GasStrategy { speed: GasSpeed::Fast, max_fee_cap: None };
+        let (max_fee, prio) = fast.apply(gwei(8), gwei(10), gwei(1)).unwrap();
+        assert_eq!(max_fee, gwei(14));
+        assert_eq!(prio, gwei(2));
+
+        // The multiplied tip never exceeds the max fee
+        let (max_fee, prio) = fast.apply(gwei(8), gwei(10), gwei(9)).unwrap();
+        assert_eq!(prio, max_fee);
+    }
+
+    #[test]
+    fn test_apply_ceiling() {
+        let capped = GasStrategy { speed: GasSpeed::Fast, max_fee_cap: Some(gwei(12)) };
+        let (max_fee, _) = capped.apply(gwei(8), gwei(10), gwei(1)).unwrap();
+        assert_eq!(max_fee, gwei(12));
+
+        let err = capped.apply(gwei(13), gwei(14), gwei(1)).unwrap_err();
+        assert!(err.contains("exceeds the configured max fee ceiling"));
+    }
+}
@@ -2,16 +2,37 @@
 //!
 //! Thread-safe storage and management of queued transactions.
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-use super::types::{QueuedTransaction, QueuedTxStatus, QueuedTxSummary};
+use super::types::{QueuedTransaction, QueuedTxStatus, QueuedTxSummary, SubmissionClaim};
 use crate::db::tables::broadcasted_transactions::{
     BroadcastMode, BroadcastedTxStatus, RecordBroadcastRequest,
 };
 use crate::db::Database;
 
+/// How long a client idempotency token is remembered
+const IDEMPOTENCY_WINDOW_SECS: i64 = 600;
+
+/// How long a reserved nonce is held for a signed transaction that was never queued
+/// (e.g. one a module broadcasts itself). By then it shows up in the chain's pending nonce.
+const NONCE_RESERVATION_SECS: i64 = 120;
+
+/// (network, lowercase from) -> reserved nonces and when each was handed out
+type NonceReservations = HashMap<(String, String), Vec<(u64, DateTime<Utc>)>>;
+
+/// A broadcast submitted under a client idempotency token
+struct SubmissionRecord {
+    /// Transaction the submission acted on
+    target_uuid: String,
+    /// Transaction that was broadcast, once the submission finished
+    result_uuid: Option<String>,
+    claimed_at: DateTime<Utc>,
+}
+
 /// Manager for the transaction queue
 /// Uses DashMap for thread-safe concurrent access
 pub struct TxQueueManager {
@@ -19,6 +40,11 @@ pub struct TxQueueManager {
     transactions: DashMap<String, QueuedTransaction>,
     /// Optional database for persistent broadcast history
     db: Option<Arc<Database>>,
+    /// Client idempotency token -> submission, for deduplicating retries
+    submissions: DashMap<String, SubmissionRecord>,
+    /// Nonces handed out for signing but not yet held by a queued transaction.
+    /// Also serializes nonce allocation.
+    nonce_reservations: Mutex<NonceReservations>,
 }
 
 impl TxQueueManager {
@@ -27,6 +53,8 @@ impl TxQueueManager {
         Self {
             transactions: DashMap::new(),
             db: None,
+            submissions: DashMap::new(),
+            nonce_reservations: Mutex::new(HashMap::new()),
         }
     }
 
@@ -35,6 +63,8 @@ impl TxQueueManager {
         Self {
             transactions: DashMap::new(),
             db: Some(db),
+            submissions: DashMap::new(),
+            nonce_reservations: Mutex::new(HashMap::new()),
         }
     }
 
@@ -42,7 +72,10 @@ impl TxQueueManager {
     pub fn queue(&self, tx: QueuedTransaction) -> String {
         let uuid = tx.uuid.clone();
         log::info!("[TxQueue] Queuing transaction {} to {}", uuid, tx.to);
+        let (network, from, nonce) = (tx.network.clone(), tx.from.clone(), tx.nonce);
         self.transactions.insert(uuid.clone(), tx);
+        // The queued tx now holds its nonce itself
+        self.release_nonce(&network, &from, nonce);
         uuid
     }
 
@@ -109,6 +142,64 @@ impl TxQueueManager {
         self.update_status(uuid, QueuedTxStatus::Broadcasting)
     }
 
+    /// Move a pending transaction to broadcasting in one step, so two concurrent
+    /// submissions can't both broadcast it. Returns false if it wasn't pending.
+    pub fn mark_broadcasting_if_pending(&self, uuid: &str) -> bool {
+        match self.transactions.get_mut(uuid) {
+            Some(mut tx) if tx.status == QueuedTxStatus::Pending => {
+                log::info!("[TxQueue] Updating {} status to {:?}", uuid, QueuedTxStatus::Broadcasting);
+                tx.status = QueuedTxStatus::Broadcasting;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Claim a client idempotency token for a submission acting on `target_uuid`.
+    /// A token reused within the window reports the earlier submission instead of
+    /// starting a new one. Errors if the token was used for another transaction.
+    pub fn claim_submission(&self, token: &str, target_uuid: &str) -> Result<SubmissionClaim, String> {
+        let cutoff = Utc::now() - chrono::Duration::seconds(IDEMPOTENCY_WINDOW_SECS);
+        self.submissions.retain(|_, s| s.claimed_at > cutoff);
+
+        match self.submissions.entry(token.to_string()) {
+            Entry::Occupied(entry) => {
+                let record = entry.get();
+                if record.target_uuid != target_uuid {
+                    return Err(format!(
+                        "Idempotency token was already used for transaction {}",
+                        record.target_uuid
+                    ));
+                }
+                log::info!("[TxQueue] Duplicate submission for {} (token {})", target_uuid, token);
+                Ok(match &record.result_uuid {
+                    Some(uuid) => SubmissionClaim::Completed(uuid.clone()),
+                    None => SubmissionClaim::InProgress,
+                })
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(SubmissionRecord {
+                    target_uuid: target_uuid.to_string(),
+                    result_uuid: None,
+                    claimed_at: Utc::now(),
+                });
+                Ok(SubmissionClaim::New)
+            }
+        }
+    }
+
+    /// Record the transaction a claimed submission broadcast
+    pub fn complete_submission(&self, token: &str, result_uuid: &str) {
+        if let Some(mut record) = self.submissions.get_mut(token) {
+            record.result_uuid = Some(result_uuid.to_string());
+        }
+    }
+
+    /// Forget a claimed submission that failed, so the client can retry it
+    pub fn release_submission(&self, token: &str) {
+        self.submissions.remove(token);
+    }
+
     /// Mark transaction as broadcast with tx_hash
     /// broadcast_mode: "rogue" or "partner"
     pub fn mark_broadcast(&self, uuid: &str, tx_hash: &str, explorer_url: &str, broadcast_mode: &str) -> bool {
@@ -135,6 +226,7 @@ impl TxQueueManager {
                     tx_hash: Some(tx_hash.to_string()),
                     explorer_url: Some(explorer_url.to_string()),
                     broadcast_mode: mode,
+                    nonce: Some(tx.nonce as i64),
                 };
                 if let Err(e) = db.record_broadcast(req) {
                     log::error!("[TxQueue] Failed to persist broadcast to DB: {}", e);
@@ -197,6 +289,142 @@ impl TxQueueManager {
         }
     }
 
+    /// Start the confirmation countdown for a pending transaction. The deadline is
+    /// set once; asking again returns the existing one. Returns None when
+    /// `timeout_secs` is 0 (no limit) or the transaction isn't pending.
+    pub fn request_confirmation(&self, uuid: &str, timeout_secs: i64, session_id: Option<i64>) -> Option<DateTime<Utc>> {
+        if timeout_secs <= 0 {
+            return None;
+        }
+        let mut tx = self.transactions.get_mut(uuid)?;
+        if tx.status != QueuedTxStatus::Pending {
+            return None;
+        }
+        if tx.confirmation_session_id.is_none() {
+            tx.confirmation_session_id = session_id;
+        }
+        Some(*tx.confirmation_expires_at.get_or_insert_with(|| Utc::now() + chrono::Duration::seconds(timeout_secs)))
+    }
+
+    /// Expire pending transactions whose confirmation deadline has passed.
+    /// Returns the transactions that were expired.
+    pub fn expire_unconfirmed(&self) -> Vec<QueuedTransaction> {
+        let now = Utc::now();
+        let mut expired = Vec::new();
+        for mut entry in self.transactions.iter_mut() {
+            let tx = entry.value_mut();
+            if tx.status == QueuedTxStatus::Pending && tx.confirmation_expires_at.is_some_and(|t| t <= now) {
+                log::warn!("[TxQueue] Transaction {} was not confirmed in time, auto-rejecting", tx.uuid);
+                tx.status = QueuedTxStatus::Expired;
+                tx.error = Some("Not confirmed by the user before the confirmation timeout".to_string());
+                expired.push(tx.clone());
+            }
+        }
+        expired
+    }
+
+    /// Mark a broadcast transaction as stuck (not confirmed within the timeout)
+    pub fn mark_stuck(&self, uuid: &str) -> bool {
+        if let Some(mut tx) = self.transactions.get_mut(uuid) {
+            log::warn!("[TxQueue] Transaction {} (nonce {}) is stuck", uuid, tx.nonce);
+            tx.status = QueuedTxStatus::Stuck;
+
+            if let Some(ref db) = self.db
+                && let Err(e) = db.update_broadcast_status(uuid, BroadcastedTxStatus::Stuck, None)
+            {
+                log::error!("[TxQueue] Failed to update DB status: {}", e);
+            }
+
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Mark a transaction as replaced by a gas-bumped copy with the same nonce
+    pub fn mark_replaced(&self, uuid: &str, replaced_by: &str) -> bool {
+        if let Some(mut tx) = self.transactions.get_mut(uuid) {
+            log::info!("[TxQueue] Transaction {} replaced by {}", uuid, replaced_by);
+            tx.status = QueuedTxStatus::Replaced;
+            tx.replaced_by = Some(replaced_by.to_string());
+
+            if let Some(ref db) = self.db
+                && let Err(e) = db.mark_broadcast_replaced(uuid, replaced_by)
+            {
+                log::error!("[TxQueue] Failed to update DB status: {}", e);
+            }
+
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Next nonce to use for a new transaction from `from` on `network`.
+    /// Takes the chain's pending nonce and skips past any nonces already held by
+    /// queued-but-unconfirmed transactions, so several queued txs don't collide.
+    pub fn next_nonce(&self, network: &str, from: &str, chain_nonce: u64) -> u64 {
+        self.transactions
+            .iter()
+            .filter(|r| {
+                let tx = r.value();
+                tx.network == network
+                    && tx.from.eq_ignore_ascii_case(from)
+                    && matches!(
+                        tx.status,
+                        QueuedTxStatus::Pending
+                            | QueuedTxStatus::Broadcasting
+                            | QueuedTxStatus::Broadcast
+                            | QueuedTxStatus::Stuck
+                    )
+            })
+            .map(|r| r.value().nonce + 1)
+            .fold(chain_nonce, u64::max)
+    }
+
+    /// Reserve the next nonce for a transaction about to be signed from `from` on `network`.
+    /// Like `next_nonce`, but also skips nonces reserved by concurrent signers, so two
+    /// transactions signed at the same time never share one. The reservation is dropped
+    /// when a transaction with that nonce is queued, on `release_nonce`, once the chain's
+    /// pending nonce passes it, or after `NONCE_RESERVATION_SECS`.
+    pub fn reserve_nonce(&self, network: &str, from: &str, chain_nonce: u64) -> u64 {
+        let mut reservations = self.nonce_reservations.lock().unwrap_or_else(|e| e.into_inner());
+        let held = reservations
+            .entry((network.to_string(), from.to_lowercase()))
+            .or_default();
+        let cutoff = Utc::now() - chrono::Duration::seconds(NONCE_RESERVATION_SECS);
+        held.retain(|(nonce, at)| *nonce >= chain_nonce && *at > cutoff);
+        let nonce = held
+            .iter()
+            .map(|(nonce, _)| nonce + 1)
+            .fold(self.next_nonce(network, from, chain_nonce), u64::max);
+        held.push((nonce, Utc::now()));
+        nonce
+    }
+
+    /// Give back a reserved nonce, e.g. when signing failed
+    pub fn release_nonce(&self, network: &str, from: &str, nonce: u64) {
+        let mut reservations = self.nonce_reservations.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(held) = reservations.get_mut(&(network.to_string(), from.to_lowercase())) {
+            held.retain(|(n, _)| *n != nonce);
+        }
+    }
+
+    /// Broadcast transactions that have waited longer than `timeout_secs` without confirming.
+    /// Includes already-stuck transactions so they can be re-checked or replaced.
+    pub fn list_unconfirmed_older_than(&self, timeout_secs: i64) -> Vec<QueuedTransaction> {
+        let cutoff = Utc::now() - chrono::Duration::seconds(timeout_secs);
+        self.transactions
+            .iter()
+            .filter(|r| {
+                let tx = r.value();
+                matches!(tx.status, QueuedTxStatus::Broadcast | QueuedTxStatus::Stuck)
+                    && tx.broadcast_at.map(|t| t < cutoff).unwrap_or(false)
+            })
+            .map(|r| r.value().clone())
+            .collect()
+    }
+
     /// Get count of transactions by status
     pub fn count_by_status(&self, status: QueuedTxStatus) -> usize {
         self.transactions
@@ -223,7 +451,10 @@ impl TxQueueManager {
             .filter(|r| {
                 let tx = r.value();
                 // Only clean up terminal states (confirmed, failed, expired)
-                matches!(tx.status, QueuedTxStatus::Confirmed | QueuedTxStatus::Failed | QueuedTxStatus::Expired)
+                matches!(
+                    tx.status,
+                    QueuedTxStatus::Confirmed | QueuedTxStatus::Failed | QueuedTxStatus::Expired | QueuedTxStatus::Replaced
+                )
                     && tx.created_at < cutoff
             })
             .map(|r| r.key().clone())
@@ -332,4 +563,122 @@ mod tests {
         assert_eq!(pending.len(), 1);
         assert_eq!(pending[0].uuid, "pending-2");
     }
+
+    #[test]
+    fn test_next_nonce_skips_queued() {
+        let manager = TxQueueManager::new();
+        assert_eq!(manager.next_nonce("base", "0x1234", 5), 5);
+
+        let mut tx = create_test_tx("nonce-5");
+        tx.nonce = 5;
+        manager.queue(tx);
+        // Wallet address comparison is case-insensitive
+        assert_eq!(manager.next_nonce("base", "0X1234", 5), 6);
+        // Other networks are tracked separately
+        assert_eq!(manager.next_nonce("mainnet", "0x1234", 5), 5);
+
+        // Confirmed txs no longer hold their nonce
+        manager.mark_confirmed("nonce-5");
+        assert_eq!(manager.next_nonce("base", "0x1234", 6), 6);
+    }
+
+    #[test]
+    fn test_reserve_nonce_never_hands_out_the_same_nonce() {
+        let manager = TxQueueManager::new();
+        // Concurrent signers that never queue (module-broadcast txs) get distinct nonces
+        assert_eq!(manager.reserve_nonce("base", "0x1234", 5), 5);
+        assert_eq!(manager.reserve_nonce("base", "0X1234", 5), 6);
+
+        // A failed signing gives its nonce back
+        manager.release_nonce("base", "0x1234", 6);
+        assert_eq!(manager.reserve_nonce("base", "0x1234", 5), 6);
+
+        // Queuing a tx moves the hold from the reservation to the queue
+        let mut tx = create_test_tx("nonce-7");
+        tx.nonce = manager.reserve_nonce("base", "0x1234", 5);
+        assert_eq!(tx.nonce, 7);
+        manager.queue(tx);
+        assert_eq!(manager.reserve_nonce("base", "0x1234", 5), 8);
+
+        // Reservations the chain has moved past are dropped
+        manager.mark_confirmed("nonce-7");
+        assert_eq!(manager.reserve_nonce("base", "0x1234", 9), 9);
+    }
+
+    #[test]
+    fn test_stuck_and_replaced() {
+        let manager = TxQueueManager::new();
+        manager.queue(create_test_tx("stuck-1"));
+        manager.mark_broadcast("stuck-1", "0xhash", "https://basescan.org/tx/0xhash", "rogue");
+
+        // Freshly broadcast - not yet past the timeout
+        assert!(manager.list_unconfirmed_older_than(300).is_empty());
+        manager.transactions.get_mut("stuck-1").unwrap().broadcast_at =
+            Some(Utc::now() - chrono::Duration::seconds(600));
+        assert_eq!(manager.list_unconfirmed_older_than(300).len(), 1);
+
+        assert!(manager.mark_stuck("stuck-1"));
+        assert_eq!(manager.get("stuck-1").unwrap().status, QueuedTxStatus::Stuck);
+        assert_eq!(manager.list_unconfirmed_older_than(300).len(), 1);
+
+        assert!(manager.mark_replaced("stuck-1", "stuck-2"));
+        let tx = manager.get("stuck-1").unwrap();
+        assert_eq!(tx.status, QueuedTxStatus::Replaced);
+        assert_eq!(tx.replaced_by.as_deref(), Some("stuck-2"));
+        assert!(manager.list_unconfirmed_older_than(300).is_empty());
+    }
+
+    #[test]
+    fn test_confirmation_timeout() {
+        let manager = TxQueueManager::new();
+        manager.queue(create_test_tx("confirm-1"));
+
+        // No limit configured
+        assert!(manager.request_confirmation("confirm-1", 0, Some(9)).is_none());
+
+        let deadline = manager.request_confirmation("confirm-1", 300, Some(9)).unwrap();
+        // Asking again keeps the original deadline
+        assert_eq!(manager.request_confirmation("confirm-1", 60, Some(9)), Some(deadline));
+        assert!(manager.expire_unconfirmed().is_empty());
+
+        manager.transactions.get_mut("confirm-1").unwrap().confirmation_expires_at =
+            Some(Utc::now() - chrono::Duration::seconds(1));
+        let expired = manager.expire_unconfirmed();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].confirmation_session_id, Some(9));
+        assert_eq!(manager.get("confirm-1").unwrap().status, QueuedTxStatus::Expired);
+
+        // Already expired - not reported twice, and no new countdown
+        assert!(manager.expire_unconfirmed().is_empty());
+        assert!(manager.request_confirmation("confirm-1", 300, Some(9)).is_none());
+    }
+
+    #[test]
+    fn test_double_submit_is_deduplicated() {
+        let manager = TxQueueManager::new();
+        manager.queue(create_test_tx("double-1"));
+
+        // Both clicks arrive before the first broadcast finishes
+        assert_eq!(manager.claim_submission("token-a", "double-1"), Ok(SubmissionClaim::New));
+        assert_eq!(manager.claim_submission("token-a", "double-1"), Ok(SubmissionClaim::InProgress));
+        assert!(manager.mark_broadcasting_if_pending("double-1"));
+        assert!(!manager.mark_broadcasting_if_pending("double-1"));
+
+        // A retry after the broadcast gets the existing result
+        manager.mark_broadcast("double-1", "0xhash", "https://basescan.org/tx/0xhash", "partner");
+        manager.complete_submission("token-a", "double-1");
+        assert_eq!(
+            manager.claim_submission("token-a", "double-1"),
+            Ok(SubmissionClaim::Completed("double-1".to_string()))
+        );
+
+        // The token can't be reused for another transaction
+        manager.queue(create_test_tx("double-2"));
+        assert!(manager.claim_submission("token-a", "double-2").is_err());
+
+        // A failed submission releases its token for a retry
+        assert_eq!(manager.claim_submission("token-b", "double-2"), Ok(SubmissionClaim::New));
+        manager.release_submission("token-b");
+        assert_eq!(manager.claim_submission("token-b", "double-2"), Ok(SubmissionClaim::New));
+    }
 }
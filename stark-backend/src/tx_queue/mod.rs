@@ -6,11 +6,21 @@
 //! 1. `web3_tx` signs a transaction and queues it (returns UUID)
 //! 2. `list_queued_web3_tx` allows viewing queued transactions
 //! 3. `broadcast_web3_tx` broadcasts a transaction by UUID
+//! 4. A background monitor flags broadcast txs that don't confirm in time as
+//!    stuck; they can be replaced with a gas-bumped copy using the same nonce
+//! 5. In partner mode, a transaction the user doesn't confirm before the
+//!    configured timeout is auto-rejected and the agent is told
 //!
 //! This creates a safety layer where transactions can be reviewed before broadcast.
 
 mod types;
 mod manager;
+mod gas;
+mod replacement;
+mod confirmation;
 
-pub use types::{QueuedTransaction, QueuedTxStatus, QueuedTxSummary};
+pub use types::{QueuedTransaction, QueuedTxStatus, QueuedTxSummary, SimulationStatus, SubmissionClaim, TxSimulation};
 pub use manager::{TxQueueManager, create_tx_queue_manager};
+pub use gas::{GasSpeed, GasStrategy};
+pub use replacement::{replace_transaction, spawn_stuck_tx_monitor};
+pub use confirmation::spawn_confirmation_expiry_monitor;
@@ -0,0 +1,352 @@
+//! Stuck-transaction detection and replace-by-fee
+//!
+//! A broadcast transaction that hasn't confirmed within the stuck timeout is
+//! marked `Stuck`. It can then be rebroadcast with the same nonce and bumped
+//! fees; the original is marked `Replaced` and linked to its replacement.
+//! Either one may end up mined, so settling a replacement also checks the
+//! originals it replaced and restores the status of whichever landed.
+
+use ethers::types::transaction::eip1559::Eip1559TransactionRequest;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, H256, U256, U64};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::gas::{format_gwei, GasStrategy};
+use super::manager::TxQueueManager;
+use super::types::{QueuedTransaction, QueuedTxStatus};
+use crate::db::Database;
+use crate::tools::rpc_config::resolve_rpc_from_network;
+use crate::wallet::WalletProvider;
+use crate::web3::get_chain_id;
+use crate::x402::X402EvmRpc;
+
+/// Fee bump applied to a replacement, in percent of the original fee.
+/// Nodes require at least +10% on both fee fields to accept a replacement.
+const FEE_BUMP_PERCENT: u64 = 125;
+
+/// Smallest bump (in percent of the original fee) nodes accept for a replacement
+const MIN_FEE_BUMP_PERCENT: u64 = 110;
+
+/// How often the background monitor checks for stuck transactions
+const MONITOR_INTERVAL_SECS: u64 = 60;
+
+/// Bump a fee for replacement, never going below the current network estimate
+pub fn bump_fee(original: U256, current: U256) -> U256 {
+    let bumped = original * U256::from(FEE_BUMP_PERCENT) / U256::from(100);
+    bumped.max(current)
+}
+
+/// Fees for a replacement: the original fees bumped (or the strategy's current
+/// estimate, if higher), with max_fee clamped to the configured ceiling.
+/// Errors when the ceiling leaves no room for the minimum bump nodes accept.
+pub fn replacement_fees(
+    old_max_fee: U256,
+    old_priority: U256,
+    current_max_fee: U256,
+    current_priority: U256,
+    max_fee_cap: Option<U256>,
+) -> Result<(U256, U256), String> {
+    let mut max_fee = bump_fee(old_max_fee, current_max_fee);
+    if let Some(cap) = max_fee_cap {
+        max_fee = max_fee.min(cap);
+    }
+    let priority = bump_fee(old_priority, current_priority).min(max_fee);
+
+    let min_max_fee = old_max_fee * U256::from(MIN_FEE_BUMP_PERCENT) / U256::from(100);
+    let min_priority = old_priority * U256::from(MIN_FEE_BUMP_PERCENT) / U256::from(100);
+    if max_fee < min_max_fee || priority < min_priority {
+        return Err(format!(
+            "A valid replacement needs a max fee of at least {}, above the configured max fee ceiling ({}). \
+            Leaving the transaction stuck; raise the ceiling in bot settings to replace it.",
+            format_gwei(min_max_fee),
+            max_fee_cap.map(format_gwei).unwrap_or_default()
+        ));
+    }
+    Ok((max_fee, priority))
+}
+
+fn parse_dec(field: &str, value: &str) -> Result<U256, String> {
+    U256::from_dec_str(value).map_err(|e| format!("Invalid {} '{}': {}", field, value, e))
+}
+
+fn rpc_for(tx: &QueuedTransaction, wallet_provider: Arc<dyn WalletProvider>) -> Result<X402EvmRpc, String> {
+    let rpc_config = resolve_rpc_from_network(&tx.network);
+    X402EvmRpc::new_with_wallet_provider(
+        wallet_provider,
+        &tx.network,
+        Some(rpc_config.url.clone()),
+        rpc_config.use_x402,
+    )
+}
+
+/// Check whether a broadcast transaction has been mined, updating the queue if so.
+/// Returns true if the transaction reached a final state.
+async fn settle_if_mined(tx_queue: &TxQueueManager, tx: &QueuedTransaction, rpc: &X402EvmRpc) -> bool {
+    let Some(tx_hash) = tx.tx_hash.as_deref().and_then(|h| h.parse::<H256>().ok()) else {
+        return false;
+    };
+    match rpc.get_transaction_receipt(tx_hash).await {
+        Ok(Some(receipt)) => {
+            if receipt.status == Some(U64::from(1)) {
+                tx_queue.mark_confirmed(&tx.uuid);
+            } else {
+                tx_queue.mark_failed(&tx.uuid, "Reverted");
+            }
+            true
+        }
+        Ok(None) => false,
+        Err(e) => {
+            log::warn!("[TxQueue] Receipt check failed for {}: {}", tx.uuid, e);
+            false
+        }
+    }
+}
+
+/// Like `settle_if_mined`, but also checks the transactions `tx` replaced: only
+/// one of them can be mined with the shared nonce, and it may be an original
+/// that was marked `Replaced`. That one gets its real status back and `tx` is
+/// marked failed. Returns true if any of them was mined.
+async fn settle_replacement_chain(tx_queue: &TxQueueManager, tx: &QueuedTransaction, rpc: &X402EvmRpc) -> bool {
+    let mut candidate = Some(tx.clone());
+    while let Some(current) = candidate {
+        if settle_if_mined(tx_queue, &current, rpc).await {
+            if current.uuid != tx.uuid {
+                tx_queue.mark_failed(
+                    &tx.uuid,
+                    &format!("Not needed: the transaction it replaced ({}) was mined", current.uuid),
+                );
+            }
+            return true;
+        }
+        candidate = current.replaces.as_deref().and_then(|u| tx_queue.get(u));
+    }
+    false
+}
+
+/// Rebroadcast a stuck transaction with the same nonce and bumped fees, priced
+/// with `gas_strategy` and kept under its ceiling (the tx stays stuck otherwise).
+/// The replacement is queued as a new transaction (status Broadcast) and the
+/// original is marked `Replaced`. Returns the replacement.
+pub async fn replace_transaction(
+    tx_queue: &TxQueueManager,
+    uuid: &str,
+    wallet_provider: Arc<dyn WalletProvider>,
+    gas_strategy: &GasStrategy,
+    broadcast_mode: &str,
+) -> Result<QueuedTransaction, String> {
+    crate::wallet::ensure_signing_allowed()?;
+
+    let tx = tx_queue
+        .get(uuid)
+        .ok_or_else(|| format!("Transaction {} not found", uuid))?;
+
+    if !matches!(tx.status, QueuedTxStatus::Broadcast | QueuedTxStatus::Stuck) {
+        return Err(format!(
+            "Transaction {} cannot be replaced (status: {}). Only broadcast or stuck transactions can be replaced.",
+            uuid, tx.status
+        ));
+    }
+
+    // Sign with whichever wallet sent the original
+    let wallet_provider = crate::wallet::select_wallet_by_address(&wallet_provider, &tx.from);
+    let rpc = rpc_for(&tx, wallet_provider.clone())?;
+
+    // It (or an original it replaced) may have confirmed since it was flagged
+    if settle_replacement_chain(tx_queue, &tx, &rpc).await {
+        return Err(format!("Transaction {} has already been mined; nothing to replace", uuid));
+    }
+
+    let from: Address = tx.from.parse().map_err(|_| format!("Invalid from address: {}", tx.from))?;
+    let to: Address = tx.to.parse().map_err(|_| format!("Invalid to address: {}", tx.to))?;
+    let value = parse_dec("value", &tx.value)?;
+    let gas = parse_dec("gas_limit", &tx.gas_limit)?;
+    let old_max_fee = parse_dec("max_fee_per_gas", &tx.max_fee_per_gas)?;
+    let old_priority = parse_dec("max_priority_fee_per_gas", &tx.max_priority_fee_per_gas)?;
+    let data = hex::decode(tx.data.trim_start_matches("0x"))
+        .map_err(|e| format!("Invalid calldata: {}", e))?;
+
+    let (current_max_fee, current_priority) = gas_strategy.estimate_fees(&rpc).await?;
+    let (max_fee, priority) = replacement_fees(
+        old_max_fee,
+        old_priority,
+        current_max_fee,
+        current_priority,
+        gas_strategy.max_fee_cap,
+    )?;
+
+    log::info!(
+        "[TxQueue] Replacing {} (nonce {}): max_fee {} -> {}, priority {} -> {}",
+        uuid, tx.nonce, old_max_fee, max_fee, old_priority, priority
+    );
+
+    let request = Eip1559TransactionRequest::new()
+        .from(from)
+        .to(to)
+        .value(value)
+        .data(data)
+        .nonce(tx.nonce)
+        .gas(gas)
+        .max_fee_per_gas(max_fee)
+        .max_priority_fee_per_gas(priority)
+        .chain_id(get_chain_id(&tx.network));
+
+    let typed_tx: TypedTransaction = request.into();
+    let signature = wallet_provider
+        .sign_transaction(&typed_tx)
+        .await
+        .map_err(|e| format!("Failed to sign replacement: {}", e))?;
+    let signed = typed_tx.rlp_signed(&signature);
+
+    let tx_hash = match rpc.send_raw_transaction(&signed).await {
+        Ok(h) => h,
+        Err(e) => {
+            // The nonce was used meanwhile; settle whichever of ours was mined with it
+            if e.contains("nonce too low") && !settle_replacement_chain(tx_queue, &tx, &rpc).await {
+                tx_queue.mark_failed(uuid, "Nonce already used on-chain; replacement not needed");
+            }
+            return Err(format!("Replacement broadcast failed: {}", e));
+        }
+    };
+
+    let new_uuid = Uuid::new_v4().to_string();
+    let mut replacement = QueuedTransaction::new(
+        new_uuid.clone(),
+        tx.network.clone(),
+        tx.from.clone(),
+        tx.to.clone(),
+        tx.value.clone(),
+        tx.data.clone(),
+        tx.gas_limit.clone(),
+        max_fee.to_string(),
+        priority.to_string(),
+        tx.nonce,
+        format!("0x{}", hex::encode(&signed)),
+        tx.channel_id,
+    )
+    .with_preset(tx.preset.as_deref())
+    .with_simulation(tx.simulation.clone());
+    replacement.replaces = Some(tx.uuid.clone());
+    replacement.replacement_count = tx.replacement_count + 1;
+
+    let tx_hash_str = format!("{:?}", tx_hash);
+    let explorer_url = format!("{}/{}", replacement.get_explorer_base_url(), tx_hash_str);
+
+    tx_queue.queue(replacement);
+    tx_queue.mark_broadcast(&new_uuid, &tx_hash_str, &explorer_url, broadcast_mode);
+    tx_queue.mark_replaced(uuid, &new_uuid);
+
+    tx_queue
+        .get(&new_uuid)
+        .ok_or_else(|| "Replacement vanished from queue".to_string())
+}
+
+/// One pass of the stuck-transaction monitor
+pub async fn check_stuck_transactions(
+    tx_queue: &TxQueueManager,
+    wallet_provider: Arc<dyn WalletProvider>,
+    gas_strategy: &GasStrategy,
+    timeout_secs: i64,
+    auto_replace: bool,
+    max_replacements: u32,
+) {
+    for tx in tx_queue.list_unconfirmed_older_than(timeout_secs) {
+        let rpc = match rpc_for(&tx, wallet_provider.clone()) {
+            Ok(r) => r,
+            Err(e) => {
+                log::warn!("[TxQueue] Cannot check {}: {}", tx.uuid, e);
+                continue;
+            }
+        };
+
+        if settle_replacement_chain(tx_queue, &tx, &rpc).await {
+            continue;
+        }
+
+        if tx.status == QueuedTxStatus::Broadcast {
+            tx_queue.mark_stuck(&tx.uuid);
+        }
+
+        // Keep flagging stuck transactions while signing is frozen, but don't replace them
+        if auto_replace && tx.replacement_count < max_replacements && !crate::wallet::is_signing_frozen() {
+            match replace_transaction(tx_queue, &tx.uuid, wallet_provider.clone(), gas_strategy, "rogue").await {
+                Ok(replacement) => log::info!(
+                    "[TxQueue] Auto-replaced stuck tx {} with {} ({})",
+                    tx.uuid,
+                    replacement.uuid,
+                    replacement.tx_hash.as_deref().unwrap_or("?")
+                ),
+                Err(e) => log::warn!("[TxQueue] Auto-replace of {} failed: {}", tx.uuid, e),
+            }
+        }
+    }
+}
+
+/// Spawn the background task that flags (and optionally replaces) stuck transactions
+pub fn spawn_stuck_tx_monitor(
+    db: Arc<Database>,
+    tx_queue: Arc<TxQueueManager>,
+    wallet_provider: Arc<dyn WalletProvider>,
+) {
+    let timeout_secs = crate::config::tx_stuck_timeout_secs();
+    let auto_replace = crate::config::tx_auto_replace();
+    let max_replacements = crate::config::tx_max_replacements();
+    log::info!(
+        "[TxQueue] Stuck tx monitor started (timeout={}s, auto_replace={}, max_replacements={})",
+        timeout_secs, auto_replace, max_replacements
+    );
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(MONITOR_INTERVAL_SECS));
+        interval.tick().await; // skip immediate tick
+        loop {
+            interval.tick().await;
+            // Re-read each pass so gas settings changes apply to later replacements
+            let settings = db.get_bot_settings().unwrap_or_default();
+            let gas_strategy = GasStrategy::from_settings(Some(&settings.gas_strategy), settings.gas_max_fee_gwei);
+            check_stuck_transactions(
+                &tx_queue,
+                wallet_provider.clone(),
+                &gas_strategy,
+                timeout_secs,
+                auto_replace,
+                max_replacements,
+            )
+            .await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bump_fee() {
+        // +25% over the original when the network is calm
+        assert_eq!(bump_fee(U256::from(1000), U256::from(900)), U256::from(1250));
+        // Follows the network when it has moved past the bump
+        assert_eq!(bump_fee(U256::from(1000), U256::from(2000)), U256::from(2000));
+    }
+
+    #[test]
+    fn test_replacement_fees_respect_ceiling() {
+        // No ceiling: the usual +25% bump
+        let (max_fee, prio) =
+            replacement_fees(U256::from(1000), U256::from(100), U256::from(900), U256::from(90), None).unwrap();
+        assert_eq!((max_fee, prio), (U256::from(1250), U256::from(125)));
+
+        // The ceiling clamps the bump as long as it still clears the +10% minimum
+        let (max_fee, _) =
+            replacement_fees(U256::from(1000), U256::from(100), U256::from(900), U256::from(90), Some(U256::from(1150)))
+                .unwrap();
+        assert_eq!(max_fee, U256::from(1150));
+
+        // A ceiling below the minimum valid bump refuses the replacement
+        let err =
+            replacement_fees(U256::from(1000), U256::from(100), U256::from(900), U256::from(90), Some(U256::from(1050)))
+                .unwrap_err();
+        assert!(err.contains("Leaving the transaction stuck"));
+    }
+}
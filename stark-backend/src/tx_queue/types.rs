@@ -19,6 +19,31 @@ pub enum QueuedTxStatus {
     Failed,
     /// Transaction expired (timed out)
     Expired,
+    /// Broadcast but not confirmed within the stuck timeout (likely underpriced)
+    Stuck,
+    /// Superseded by a gas-bumped replacement with the same nonce
+    Replaced,
+}
+
+impl QueuedTxStatus {
+    pub const ALL: [QueuedTxStatus; 8] = [
+        QueuedTxStatus::Pending,
+        QueuedTxStatus::Broadcasting,
+        QueuedTxStatus::Broadcast,
+        QueuedTxStatus::Confirmed,
+        QueuedTxStatus::Failed,
+        QueuedTxStatus::Expired,
+        QueuedTxStatus::Stuck,
+        QueuedTxStatus::Replaced,
+    ];
+
+    /// Still waiting on the network (not yet confirmed, failed or superseded)
+    pub fn is_in_flight(&self) -> bool {
+        matches!(
+            self,
+            QueuedTxStatus::Pending | QueuedTxStatus::Broadcasting | QueuedTxStatus::Broadcast | QueuedTxStatus::Stuck
+        )
+    }
 }
 
 impl std::fmt::Display for QueuedTxStatus {
@@ -30,6 +55,86 @@ impl std::fmt::Display for QueuedTxStatus {
             QueuedTxStatus::Confirmed => write!(f, "confirmed"),
             QueuedTxStatus::Failed => write!(f, "failed"),
             QueuedTxStatus::Expired => write!(f, "expired"),
+            QueuedTxStatus::Stuck => write!(f, "stuck"),
+            QueuedTxStatus::Replaced => write!(f, "replaced"),
+        }
+    }
+}
+
+/// Result of claiming a client idempotency token for a broadcast submission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionClaim {
+    /// Token not seen within the window; the caller should submit
+    New,
+    /// An earlier submission with this token is still running
+    InProgress,
+    /// An earlier submission with this token finished; UUID of the transaction it broadcast
+    Completed(String),
+}
+
+/// Outcome of a pre-queue `eth_call` simulation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulationStatus {
+    /// Simulation succeeded; the transaction is expected to execute
+    Success,
+    /// Simulation reverted; broadcasting would waste gas
+    Reverted,
+    /// Simulation could not be performed (RPC error)
+    Unavailable,
+}
+
+/// Result of simulating a transaction before it was queued
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxSimulation {
+    pub status: SimulationStatus,
+    /// Hex-encoded return data (on success)
+    pub return_data: Option<String>,
+    /// Revert reason or RPC error message
+    pub error: Option<String>,
+    pub simulated_at: DateTime<Utc>,
+}
+
+impl TxSimulation {
+    pub fn success(return_data: &[u8]) -> Self {
+        Self {
+            status: SimulationStatus::Success,
+            return_data: Some(format!("0x{}", hex::encode(return_data))),
+            error: None,
+            simulated_at: Utc::now(),
+        }
+    }
+
+    pub fn reverted(reason: impl Into<String>) -> Self {
+        Self {
+            status: SimulationStatus::Reverted,
+            return_data: None,
+            error: Some(reason.into()),
+            simulated_at: Utc::now(),
+        }
+    }
+
+    pub fn unavailable(error: impl Into<String>) -> Self {
+        Self {
+            status: SimulationStatus::Unavailable,
+            return_data: None,
+            error: Some(error.into()),
+            simulated_at: Utc::now(),
+        }
+    }
+
+    /// One-line human-readable summary for tool output
+    pub fn summary(&self) -> String {
+        match self.status {
+            SimulationStatus::Success => "OK (simulated successfully)".to_string(),
+            SimulationStatus::Reverted => format!(
+                "WILL REVERT: {}",
+                self.error.as_deref().unwrap_or("unknown reason")
+            ),
+            SimulationStatus::Unavailable => format!(
+                "unavailable ({})",
+                self.error.as_deref().unwrap_or("unknown error")
+            ),
         }
     }
 }
@@ -75,6 +180,24 @@ pub struct QueuedTransaction {
     pub explorer_url: Option<String>,
     /// Preset name that created this tx (e.g. "identity_register"), for post-processing hooks
     pub preset: Option<String>,
+    /// Pre-queue simulation result, if a simulation was run
+    #[serde(default)]
+    pub simulation: Option<TxSimulation>,
+    /// UUID of the stuck transaction this one replaces (same nonce, higher gas)
+    #[serde(default)]
+    pub replaces: Option<String>,
+    /// UUID of the replacement transaction (set when status is Replaced)
+    #[serde(default)]
+    pub replaced_by: Option<String>,
+    /// How many times this nonce has been re-broadcast with bumped gas
+    #[serde(default)]
+    pub replacement_count: u32,
+    /// When a pending transaction awaiting user confirmation is auto-rejected
+    #[serde(default)]
+    pub confirmation_expires_at: Option<DateTime<Utc>>,
+    /// Session that queued the transaction, told when the confirmation expires
+    #[serde(default)]
+    pub confirmation_session_id: Option<i64>,
 }
 
 impl QueuedTransaction {
@@ -114,6 +237,12 @@ impl QueuedTransaction {
             channel_id,
             explorer_url: None,
             preset: None,
+            simulation: None,
+            replaces: None,
+            replaced_by: None,
+            replacement_count: 0,
+            confirmation_expires_at: None,
+            confirmation_session_id: None,
         }
     }
 
@@ -123,6 +252,12 @@ impl QueuedTransaction {
         self
     }
 
+    /// Attach the pre-queue simulation result
+    pub fn with_simulation(mut self, simulation: Option<TxSimulation>) -> Self {
+        self.simulation = simulation;
+        self
+    }
+
     /// Get the explorer URL for this transaction's network
     pub fn get_explorer_base_url(&self) -> &'static str {
         if self.network == "mainnet" {
@@ -164,6 +299,10 @@ pub struct QueuedTxSummary {
     pub error: Option<String>,
     pub created_at: DateTime<Utc>,
     pub broadcast_at: Option<DateTime<Utc>>,
+    pub simulation: Option<TxSimulation>,
+    pub nonce: u64,
+    pub replaces: Option<String>,
+    pub replaced_by: Option<String>,
 }
 
 impl From<&QueuedTransaction> for QueuedTxSummary {
@@ -182,6 +321,10 @@ impl From<&QueuedTransaction> for QueuedTxSummary {
             error: tx.error.clone(),
             created_at: tx.created_at,
             broadcast_at: tx.broadcast_at,
+            simulation: tx.simulation.clone(),
+            nonce: tx.nonce,
+            replaces: tx.replaces.clone(),
+            replaced_by: tx.replaced_by.clone(),
         }
     }
 }
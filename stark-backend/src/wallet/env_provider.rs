@@ -3,6 +3,9 @@
 //! Loads wallet from BURNER_WALLET_BOT_PRIVATE_KEY environment variable.
 //! This is the original Starkbot behavior - wallet is configured at deploy time.
 //! Signs transactions locally using ethers LocalWallet.
+//!
+//! Additional labelled wallets can be configured with STARK_WALLET_KEYS
+//! (`label:0xKEY,label2:0xKEY`) and selected per operation by label.
 
 use async_trait::async_trait;
 use ethers::core::k256::ecdsa::SigningKey;
@@ -10,7 +13,7 @@ use ethers::signers::{LocalWallet, Signer};
 use ethers::types::{Address, H256, Signature, U256, transaction::eip2718::TypedTransaction};
 use ethers::utils::keccak256;
 
-use super::WalletProvider;
+use super::{ensure_signing_allowed, WalletInfo, WalletProvider, DEFAULT_WALLET_LABEL};
 use crate::config::env_vars;
 
 /// Compute EIP-712 domain separator from domain object
@@ -48,12 +51,78 @@ fn compute_domain_separator(domain: &serde_json::Value) -> Result<H256, String>
     Ok(H256::from(keccak256(&encoded)))
 }
 
+/// Compute the EIP-712 digest to sign for typed data.
+/// A pre-computed `_hash` field is signed directly.
+fn typed_data_digest(typed_data: &serde_json::Value) -> Result<H256, String> {
+    // Check if there's a pre-computed hash
+    if let Some(hash_str) = typed_data.get("_hash").and_then(|v| v.as_str()) {
+        let hash_hex = hash_str.strip_prefix("0x").unwrap_or(hash_str);
+        let hash_bytes = hex::decode(hash_hex)
+            .map_err(|e| format!("Invalid hash hex: {}", e))?;
+        if hash_bytes.len() != 32 {
+            return Err("Hash must be 32 bytes".to_string());
+        }
+        return Ok(H256::from_slice(&hash_bytes));
+    }
+
+    // Otherwise, compute EIP-712 hash from the typed data
+    // This requires domain, types, primaryType, and message
+    let domain = typed_data.get("domain")
+        .ok_or("Missing 'domain' in typed data")?;
+    let _primary_type = typed_data.get("primaryType")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing 'primaryType' in typed data")?;
+    let message = typed_data.get("message")
+        .ok_or("Missing 'message' in typed data")?;
+
+    // Compute domain separator
+    let domain_separator = compute_domain_separator(domain)?;
+
+    // Compute struct hash (simplified - just hash the JSON for now)
+    // In production, this should properly encode according to EIP-712
+    let struct_hash = H256::from(keccak256(
+        serde_json::to_string(message)
+            .map_err(|e| format!("Failed to serialize message: {}", e))?
+            .as_bytes()
+    ));
+
+    // Final hash: keccak256("\x19\x01" ++ domainSeparator ++ structHash)
+    let mut to_sign = Vec::with_capacity(66);
+    to_sign.push(0x19);
+    to_sign.push(0x01);
+    to_sign.extend_from_slice(domain_separator.as_bytes());
+    to_sign.extend_from_slice(struct_hash.as_bytes());
+    Ok(H256::from(keccak256(&to_sign)))
+}
+
+/// Parse a hex private key into a LocalWallet
+fn wallet_from_key(private_key: &str) -> Result<LocalWallet, String> {
+    let key_hex = private_key.strip_prefix("0x").unwrap_or(private_key);
+
+    let key_bytes = hex::decode(key_hex)
+        .map_err(|e| format!("Invalid private key hex: {}", e))?;
+
+    let signing_key = SigningKey::from_bytes(key_bytes.as_slice().into())
+        .map_err(|e| format!("Invalid private key: {}", e))?;
+
+    Ok(LocalWallet::from(signing_key))
+}
+
+/// An additional wallet selectable by label
+struct LabeledWallet {
+    label: String,
+    wallet: LocalWallet,
+    address: String,
+}
+
 /// Wallet provider that loads from environment variable
 pub struct EnvWalletProvider {
     wallet: LocalWallet,
     address: String,
     /// Raw private key hex (without 0x prefix) — used as ECIES encryption key
     encryption_key_hex: String,
+    /// Additional labelled wallets (STARK_WALLET_KEYS)
+    additional: Vec<LabeledWallet>,
 }
 
 impl EnvWalletProvider {
@@ -64,29 +133,68 @@ impl EnvWalletProvider {
         let private_key = std::env::var(env_vars::BURNER_WALLET_PRIVATE_KEY)
             .map_err(|_| format!("{} not set", env_vars::BURNER_WALLET_PRIVATE_KEY))?;
 
-        Self::from_private_key(&private_key)
+        Self::from_private_key(&private_key)?.with_wallets_from_env()
+    }
+
+    /// Add the labelled wallets configured in STARK_WALLET_KEYS (if any)
+    pub fn with_wallets_from_env(mut self) -> Result<Self, String> {
+        if let Ok(keys) = std::env::var(env_vars::WALLET_KEYS) {
+            for entry in keys.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+                let (label, key) = entry.split_once(':').ok_or_else(|| {
+                    format!("Invalid {} entry (expected label:key)", env_vars::WALLET_KEYS)
+                })?;
+                self = self.with_wallet(label.trim(), key.trim())?;
+            }
+        }
+        Ok(self)
     }
 
     /// Create provider from a private key string
     pub fn from_private_key(private_key: &str) -> Result<Self, String> {
         let key_hex = private_key.strip_prefix("0x").unwrap_or(private_key);
-
-        let key_bytes = hex::decode(key_hex)
-            .map_err(|e| format!("Invalid private key hex: {}", e))?;
-
-        let signing_key = SigningKey::from_bytes(key_bytes.as_slice().into())
-            .map_err(|e| format!("Invalid private key: {}", e))?;
-
-        let wallet = LocalWallet::from(signing_key);
+        let wallet = wallet_from_key(private_key)?;
         let address = format!("{:?}", wallet.address()).to_lowercase();
 
         Ok(Self {
             wallet,
             address,
             encryption_key_hex: key_hex.to_string(),
+            additional: Vec::new(),
         })
     }
 
+    /// Add a labelled wallet that can be selected per operation
+    pub fn with_wallet(mut self, label: &str, private_key: &str) -> Result<Self, String> {
+        if label.is_empty() || label == DEFAULT_WALLET_LABEL {
+            return Err(format!("Invalid wallet label '{}'", label));
+        }
+        if self.additional.iter().any(|w| w.label == label) {
+            return Err(format!("Duplicate wallet label '{}'", label));
+        }
+
+        let wallet = wallet_from_key(private_key)
+            .map_err(|e| format!("Wallet '{}': {}", label, e))?;
+        let address = format!("{:?}", wallet.address()).to_lowercase();
+        self.additional.push(LabeledWallet {
+            label: label.to_string(),
+            wallet,
+            address,
+        });
+        Ok(self)
+    }
+
+    /// Look up a wallet by label (the default label is the primary wallet)
+    fn wallet_for(&self, label: &str) -> Result<&LocalWallet, String> {
+        if label == DEFAULT_WALLET_LABEL {
+            return Ok(&self.wallet);
+        }
+        self.additional
+            .iter()
+            .find(|w| w.label == label)
+            .map(|w| &w.wallet)
+            .ok_or_else(|| format!("Unknown wallet '{}'", label))
+    }
+
     /// Get the underlying LocalWallet (for internal use only)
     pub fn wallet(&self) -> &LocalWallet {
         &self.wallet
@@ -103,6 +211,7 @@ impl WalletProvider for EnvWalletProvider {
     }
 
     async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, String> {
+        ensure_signing_allowed()?;
         self.wallet
             .sign_transaction(tx)
             .await
@@ -110,6 +219,7 @@ impl WalletProvider for EnvWalletProvider {
     }
 
     async fn sign_hash(&self, hash: H256) -> Result<Signature, String> {
+        ensure_signing_allowed()?;
         self.wallet
             .sign_hash(hash)
             .map_err(|e| format!("Failed to sign hash: {}", e))
@@ -119,48 +229,7 @@ impl WalletProvider for EnvWalletProvider {
         // For EnvWalletProvider, we compute the EIP-712 hash and sign it
         // This is a simplified implementation that expects the hash to be pre-computed
         // or the typed_data to contain a "hash" field for direct signing
-
-        // Check if there's a pre-computed hash
-        if let Some(hash_str) = typed_data.get("_hash").and_then(|v| v.as_str()) {
-            let hash_hex = hash_str.strip_prefix("0x").unwrap_or(hash_str);
-            let hash_bytes = hex::decode(hash_hex)
-                .map_err(|e| format!("Invalid hash hex: {}", e))?;
-            if hash_bytes.len() != 32 {
-                return Err("Hash must be 32 bytes".to_string());
-            }
-            let hash = H256::from_slice(&hash_bytes);
-            return self.sign_hash(hash).await;
-        }
-
-        // Otherwise, compute EIP-712 hash from the typed data
-        // This requires domain, types, primaryType, and message
-        let domain = typed_data.get("domain")
-            .ok_or("Missing 'domain' in typed data")?;
-        let primary_type = typed_data.get("primaryType")
-            .and_then(|v| v.as_str())
-            .ok_or("Missing 'primaryType' in typed data")?;
-        let message = typed_data.get("message")
-            .ok_or("Missing 'message' in typed data")?;
-
-        // Compute domain separator
-        let domain_separator = compute_domain_separator(domain)?;
-
-        // Compute struct hash (simplified - just hash the JSON for now)
-        // In production, this should properly encode according to EIP-712
-        let struct_hash = H256::from(keccak256(
-            serde_json::to_string(message)
-                .map_err(|e| format!("Failed to serialize message: {}", e))?
-                .as_bytes()
-        ));
-
-        // Final hash: keccak256("\x19\x01" ++ domainSeparator ++ structHash)
-        let mut to_sign = Vec::with_capacity(66);
-        to_sign.push(0x19);
-        to_sign.push(0x01);
-        to_sign.extend_from_slice(domain_separator.as_bytes());
-        to_sign.extend_from_slice(struct_hash.as_bytes());
-        let digest = H256::from(keccak256(&to_sign));
-
+        let digest = typed_data_digest(typed_data)?;
         self.sign_hash(digest).await
     }
 
@@ -175,6 +244,45 @@ impl WalletProvider for EnvWalletProvider {
     fn mode_name(&self) -> &'static str {
         "standard"
     }
+
+    fn list_wallets(&self) -> Vec<WalletInfo> {
+        let mut wallets = vec![WalletInfo {
+            label: DEFAULT_WALLET_LABEL.to_string(),
+            address: self.address.clone(),
+        }];
+        wallets.extend(self.additional.iter().map(|w| WalletInfo {
+            label: w.label.clone(),
+            address: w.address.clone(),
+        }));
+        wallets
+    }
+
+    async fn sign_message_with(&self, label: &str, message: &[u8]) -> Result<Signature, String> {
+        self.wallet_for(label)?
+            .sign_message(message)
+            .await
+            .map_err(|e| format!("Failed to sign message: {}", e))
+    }
+
+    async fn sign_transaction_with(&self, label: &str, tx: &TypedTransaction) -> Result<Signature, String> {
+        ensure_signing_allowed()?;
+        self.wallet_for(label)?
+            .sign_transaction(tx)
+            .await
+            .map_err(|e| format!("Failed to sign transaction: {}", e))
+    }
+
+    async fn sign_hash_with(&self, label: &str, hash: H256) -> Result<Signature, String> {
+        ensure_signing_allowed()?;
+        self.wallet_for(label)?
+            .sign_hash(hash)
+            .map_err(|e| format!("Failed to sign hash: {}", e))
+    }
+
+    async fn sign_typed_data_with(&self, label: &str, typed_data: &serde_json::Value) -> Result<Signature, String> {
+        let digest = typed_data_digest(typed_data)?;
+        self.sign_hash_with(label, digest).await
+    }
 }
 
 #[cfg(test)]
@@ -217,4 +325,30 @@ mod tests {
         assert!(signature.r != ethers::types::U256::zero());
         assert!(signature.s != ethers::types::U256::zero());
     }
+
+    #[tokio::test]
+    async fn test_labelled_wallets() {
+        let test_key = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+        // Hardhat account #1
+        let treasury_key = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+        let provider = EnvWalletProvider::from_private_key(test_key)
+            .unwrap()
+            .with_wallet("treasury", treasury_key)
+            .unwrap();
+
+        let labels: Vec<String> = provider.list_wallets().into_iter().map(|w| w.label).collect();
+        assert_eq!(labels, vec!["default", "treasury"]);
+        assert_eq!(
+            provider.get_address_for("treasury").as_deref(),
+            Some("0x70997970c51812dc3a010c7d01b50e0d17dc79c8")
+        );
+        assert!(provider.get_address_for("missing").is_none());
+
+        let signature = provider.sign_message_with("treasury", b"hello").await.unwrap();
+        let recovered = signature.recover("hello").unwrap();
+        assert_eq!(format!("{:?}", recovered), "0x70997970c51812dc3a010c7d01b50e0d17dc79c8");
+
+        assert!(provider.sign_message_with("missing", b"hello").await.is_err());
+        assert!(provider.with_wallet("default", treasury_key).is_err());
+    }
 }
@@ -7,6 +7,9 @@
 //! - FLASH_KEYSTORE_URL: URL of the Flash control plane (e.g., https://flash.starkbot.io)
 //! - FLASH_TENANT_ID: Tenant identifier
 //! - FLASH_INSTANCE_TOKEN: Authentication token for this instance
+//!
+//! Only the instance's own wallet can sign: the control plane has no contract
+//! for additional labelled wallets, so selecting one fails.
 
 use async_trait::async_trait;
 use ethers::types::{H256, Signature, U256, transaction::eip2718::TypedTransaction};
@@ -15,7 +18,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use super::WalletProvider;
+use super::{ensure_signing_allowed, WalletProvider, DEFAULT_WALLET_LABEL};
 
 /// Environment variables for Flash mode
 pub mod env_vars {
@@ -446,6 +449,7 @@ impl WalletProvider for FlashWalletProvider {
     }
 
     async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, String> {
+        ensure_signing_allowed()?;
         log::debug!("Signing transaction via Flash control plane");
 
         let url = format!("{}/api/keystore/sign-transaction", self.keystore_url);
@@ -532,6 +536,7 @@ impl WalletProvider for FlashWalletProvider {
     }
 
     async fn sign_typed_data(&self, typed_data: &serde_json::Value) -> Result<Signature, String> {
+        ensure_signing_allowed()?;
         log::debug!("Signing typed data via Flash control plane");
 
         let url = format!("{}/api/keystore/sign-typed-data", self.keystore_url);
@@ -581,6 +586,37 @@ impl WalletProvider for FlashWalletProvider {
     fn mode_name(&self) -> &'static str {
         "flash"
     }
+
+    async fn sign_message_with(&self, label: &str, message: &[u8]) -> Result<Signature, String> {
+        ensure_default_wallet(label)?;
+        self.sign_message(message).await
+    }
+
+    async fn sign_transaction_with(&self, label: &str, tx: &TypedTransaction) -> Result<Signature, String> {
+        ensure_default_wallet(label)?;
+        self.sign_transaction(tx).await
+    }
+
+    async fn sign_hash_with(&self, label: &str, hash: H256) -> Result<Signature, String> {
+        ensure_default_wallet(label)?;
+        self.sign_hash(hash).await
+    }
+
+    async fn sign_typed_data_with(&self, label: &str, typed_data: &serde_json::Value) -> Result<Signature, String> {
+        ensure_default_wallet(label)?;
+        self.sign_typed_data(typed_data).await
+    }
+}
+
+/// Flash mode signs with the instance's own wallet only
+fn ensure_default_wallet(label: &str) -> Result<(), String> {
+    if label == DEFAULT_WALLET_LABEL {
+        return Ok(());
+    }
+    Err(format!(
+        "Cannot sign with wallet '{}': multiple wallets are not supported in Flash mode, only '{}'",
+        label, DEFAULT_WALLET_LABEL
+    ))
 }
 
 #[cfg(test)]
@@ -588,6 +624,13 @@ mod tests {
     use super::*;
     use ethers::utils::rlp::RlpStream;
 
+    #[test]
+    fn test_only_default_wallet_can_sign() {
+        assert!(ensure_default_wallet(DEFAULT_WALLET_LABEL).is_ok());
+        let err = ensure_default_wallet("treasury").unwrap_err();
+        assert!(err.contains("not supported in Flash mode"));
+    }
+
     #[test]
     fn test_env_vars_defined() {
         assert_eq!(env_vars::FLASH_KEYSTORE_URL, "FLASH_KEYSTORE_URL");
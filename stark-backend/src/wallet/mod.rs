@@ -11,20 +11,45 @@
 //! The mode is determined by the `STARKBOT_MODE` environment variable:
 //! - `standard` (default): Use EnvWalletProvider
 //! - `flash`: Use FlashWalletProvider
+//!
+//! Providers may hold several labelled wallets (e.g. a treasury wallet next to the
+//! bot's default wallet). The `*_with(label, ...)` methods sign from a specific one;
+//! `select_wallet` wraps a provider so existing signing code uses the chosen wallet.
+//!
+//! Transaction and typed-data signing can be frozen instance-wide (see `signing_freeze`).
 
 mod env_provider;
 mod flash_provider;
+mod selected;
+mod signing_freeze;
 
 pub use env_provider::EnvWalletProvider;
 pub use flash_provider::FlashWalletProvider;
+pub use selected::{select_wallet, select_wallet_by_address};
+pub use signing_freeze::{ensure_signing_allowed, is_signing_frozen, set_signing_frozen};
 
 use async_trait::async_trait;
 use ethers::types::{Signature, H256, transaction::eip2718::TypedTransaction};
+use serde::Serialize;
 use std::sync::Arc;
 
 /// Environment variable for mode selection
 pub const STARKBOT_MODE_ENV: &str = "STARKBOT_MODE";
 
+/// Label of the primary wallet (the one `get_address` returns)
+pub const DEFAULT_WALLET_LABEL: &str = "default";
+
+/// A wallet available to sign with
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct WalletInfo {
+    pub label: String,
+    pub address: String,
+}
+
+fn unknown_wallet(label: &str) -> String {
+    format!("Unknown wallet '{}'", label)
+}
+
 /// Trait for wallet providers - abstracts wallet access for different modes
 #[async_trait]
 pub trait WalletProvider: Send + Sync {
@@ -67,6 +92,54 @@ pub trait WalletProvider: Send + Sync {
 
     /// Get the mode name for logging
     fn mode_name(&self) -> &'static str;
+
+    /// Enumerate the wallets this provider can sign with (default wallet first)
+    fn list_wallets(&self) -> Vec<WalletInfo> {
+        vec![WalletInfo {
+            label: DEFAULT_WALLET_LABEL.to_string(),
+            address: self.get_address(),
+        }]
+    }
+
+    /// Get the address of a labelled wallet
+    fn get_address_for(&self, label: &str) -> Option<String> {
+        self.list_wallets()
+            .into_iter()
+            .find(|w| w.label == label)
+            .map(|w| w.address)
+    }
+
+    /// Sign a message with a labelled wallet
+    async fn sign_message_with(&self, label: &str, message: &[u8]) -> Result<Signature, String> {
+        if label != DEFAULT_WALLET_LABEL {
+            return Err(unknown_wallet(label));
+        }
+        self.sign_message(message).await
+    }
+
+    /// Sign a typed transaction with a labelled wallet
+    async fn sign_transaction_with(&self, label: &str, tx: &TypedTransaction) -> Result<Signature, String> {
+        if label != DEFAULT_WALLET_LABEL {
+            return Err(unknown_wallet(label));
+        }
+        self.sign_transaction(tx).await
+    }
+
+    /// Sign a raw 32-byte hash with a labelled wallet
+    async fn sign_hash_with(&self, label: &str, hash: H256) -> Result<Signature, String> {
+        if label != DEFAULT_WALLET_LABEL {
+            return Err(unknown_wallet(label));
+        }
+        self.sign_hash(hash).await
+    }
+
+    /// Sign EIP-712 typed data with a labelled wallet
+    async fn sign_typed_data_with(&self, label: &str, typed_data: &serde_json::Value) -> Result<Signature, String> {
+        if label != DEFAULT_WALLET_LABEL {
+            return Err(unknown_wallet(label));
+        }
+        self.sign_typed_data(typed_data).await
+    }
 }
 
 /// Create the appropriate wallet provider based on STARKBOT_MODE env var
@@ -84,16 +157,18 @@ pub async fn create_wallet_provider() -> Result<Arc<dyn WalletProvider>, String>
         "standard" | "env" => {
             let provider = EnvWalletProvider::from_env()?;
             log::info!(
-                "Wallet provider initialized (standard mode): {}",
-                provider.get_address()
+                "Wallet provider initialized (standard mode): {} ({} wallet(s))",
+                provider.get_address(),
+                provider.list_wallets().len()
             );
             Ok(Arc::new(provider))
         }
         "flash" | "lite" => {
             let provider = FlashWalletProvider::new().await?;
             log::info!(
-                "Wallet provider initialized (flash mode): {}",
-                provider.get_address()
+                "Wallet provider initialized (flash mode): {} ({} wallet(s))",
+                provider.get_address(),
+                provider.list_wallets().len()
             );
             Ok(Arc::new(provider))
         }
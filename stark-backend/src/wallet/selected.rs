@@ -0,0 +1,114 @@
+//! Wallet selection
+//!
+//! `SelectedWallet` pins a multi-wallet provider to one label so that code written
+//! against a single-address `WalletProvider` signs from the chosen wallet.
+
+use async_trait::async_trait;
+use ethers::types::{Signature, H256, transaction::eip2718::TypedTransaction};
+use std::sync::Arc;
+
+use super::{WalletInfo, WalletProvider, DEFAULT_WALLET_LABEL};
+
+/// A provider restricted to a single labelled wallet
+pub struct SelectedWallet {
+    inner: Arc<dyn WalletProvider>,
+    label: String,
+    address: String,
+}
+
+/// Narrow a provider to the wallet with the given label.
+/// `None` or the default label returns the provider unchanged.
+pub fn select_wallet(
+    provider: &Arc<dyn WalletProvider>,
+    label: Option<&str>,
+) -> Result<Arc<dyn WalletProvider>, String> {
+    let label = match label {
+        Some(l) if !l.is_empty() && l != DEFAULT_WALLET_LABEL => l,
+        _ => return Ok(provider.clone()),
+    };
+
+    let address = provider.get_address_for(label).ok_or_else(|| {
+        let available: Vec<String> = provider.list_wallets().into_iter().map(|w| w.label).collect();
+        format!("Unknown wallet '{}'. Available wallets: {}", label, available.join(", "))
+    })?;
+
+    Ok(Arc::new(SelectedWallet {
+        inner: provider.clone(),
+        label: label.to_string(),
+        address,
+    }))
+}
+
+/// Narrow a provider to whichever of its wallets owns `address`.
+/// Falls back to the provider unchanged if no labelled wallet matches.
+pub fn select_wallet_by_address(
+    provider: &Arc<dyn WalletProvider>,
+    address: &str,
+) -> Arc<dyn WalletProvider> {
+    let label = provider
+        .list_wallets()
+        .into_iter()
+        .find(|w| w.address.eq_ignore_ascii_case(address))
+        .map(|w| w.label);
+    select_wallet(provider, label.as_deref()).unwrap_or_else(|_| provider.clone())
+}
+
+#[async_trait]
+impl WalletProvider for SelectedWallet {
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, String> {
+        self.inner.sign_message_with(&self.label, message).await
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, String> {
+        self.inner.sign_transaction_with(&self.label, tx).await
+    }
+
+    async fn sign_hash(&self, hash: H256) -> Result<Signature, String> {
+        self.inner.sign_hash_with(&self.label, hash).await
+    }
+
+    async fn sign_typed_data(&self, typed_data: &serde_json::Value) -> Result<Signature, String> {
+        self.inner.sign_typed_data_with(&self.label, typed_data).await
+    }
+
+    fn get_address(&self) -> String {
+        self.address.clone()
+    }
+
+    /// Backups are instance-wide, so the encryption key stays the default wallet's
+    async fn get_encryption_key(&self) -> Result<String, String> {
+        self.inner.get_encryption_key().await
+    }
+
+    async fn refresh(&self) -> Result<(), String> {
+        self.inner.refresh().await
+    }
+
+    fn mode_name(&self) -> &'static str {
+        self.inner.mode_name()
+    }
+
+    fn list_wallets(&self) -> Vec<WalletInfo> {
+        self.inner.list_wallets()
+    }
+
+    fn get_address_for(&self, label: &str) -> Option<String> {
+        self.inner.get_address_for(label)
+    }
+
+    async fn sign_message_with(&self, label: &str, message: &[u8]) -> Result<Signature, String> {
+        self.inner.sign_message_with(label, message).await
+    }
+
+    async fn sign_transaction_with(&self, label: &str, tx: &TypedTransaction) -> Result<Signature, String> {
+        self.inner.sign_transaction_with(label, tx).await
+    }
+
+    async fn sign_hash_with(&self, label: &str, hash: H256) -> Result<Signature, String> {
+        self.inner.sign_hash_with(label, hash).await
+    }
+
+    async fn sign_typed_data_with(&self, label: &str, typed_data: &serde_json::Value) -> Result<Signature, String> {
+        self.inner.sign_typed_data_with(label, typed_data).await
+    }
+}
@@ -0,0 +1,41 @@
+//! Instance-wide signing freeze
+//!
+//! An operator kill switch for incidents: while frozen, wallet providers refuse to
+//! sign transactions or typed data and the tx queue refuses to broadcast, without
+//! removing the wallet. Message signing (used for service logins and the backup
+//! encryption key) keeps working. The flag is persisted in bot settings and loaded
+//! at startup; `controllers::system` toggles it at runtime.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static SIGNING_FROZEN: AtomicBool = AtomicBool::new(false);
+
+/// Error returned by every signing or broadcast attempt while signing is frozen
+const SIGNING_FROZEN_ERROR: &str =
+    "Signing is frozen: transaction signing has been disabled on this instance by an operator";
+
+/// Freeze or unfreeze signing for the whole instance
+pub fn set_signing_frozen(frozen: bool) {
+    let was = SIGNING_FROZEN.swap(frozen, Ordering::SeqCst);
+    if was != frozen {
+        if frozen {
+            log::warn!("[WALLET] Signing frozen: transaction signing and broadcasts are disabled");
+        } else {
+            log::info!("[WALLET] Signing unfrozen");
+        }
+    }
+}
+
+/// Whether signing is currently frozen
+pub fn is_signing_frozen() -> bool {
+    SIGNING_FROZEN.load(Ordering::SeqCst)
+}
+
+/// Fail with `SIGNING_FROZEN_ERROR` if signing is frozen
+pub fn ensure_signing_allowed() -> Result<(), String> {
+    if is_signing_frozen() {
+        Err(SIGNING_FROZEN_ERROR.to_string())
+    } else {
+        Ok(())
+    }
+}
@@ -7,9 +7,9 @@ use crate::tools::builtin::cryptocurrency::verify_intent::{self, TransactionInte
 use crate::tools::builtin::cryptocurrency::web3_tx::parse_u256;
 use crate::tools::rpc_config::{resolve_rpc_from_context, Network, ResolvedRpcConfig};
 use crate::tools::types::{ToolContext, ToolResult};
-use crate::tx_queue::QueuedTransaction;
+use crate::tx_queue::{GasStrategy, QueuedTransaction, SimulationStatus, TxQueueManager, TxSimulation};
 use crate::wallet::WalletProvider;
-use crate::x402::X402EvmRpc;
+use crate::x402::{SimulatedCall, X402EvmRpc};
 use ethers::abi::{Abi, Function, ParamType, Token};
 use ethers::prelude::*;
 use ethers::types::transaction::eip1559::Eip1559TransactionRequest;
@@ -37,6 +37,7 @@ pub struct SignedTxForQueue {
     pub nonce: u64,
     pub signed_tx_hex: String,
     pub network: String,
+    pub simulation: TxSimulation,
 }
 
 /// ABI file structure
@@ -351,7 +352,12 @@ pub async fn call_function(
     rpc.call(to, &calldata).await
 }
 
-/// Sign a transaction for queuing using WalletProvider
+/// Gas limit for a transaction whose simulation reverted, since it can't be estimated
+const REVERTED_TX_GAS_LIMIT: u64 = 300_000;
+
+/// Sign a transaction for queuing using WalletProvider.
+/// The result carries the simulation outcome, which may be `Reverted`.
+#[allow(clippy::too_many_arguments)]
 pub async fn sign_transaction_for_queue(
     network: &str,
     to: Address,
@@ -359,6 +365,8 @@ pub async fn sign_transaction_for_queue(
     value: U256,
     rpc_config: &ResolvedRpcConfig,
     wallet_provider: &Arc<dyn WalletProvider>,
+    gas_strategy: &GasStrategy,
+    tx_queue: &TxQueueManager,
 ) -> Result<SignedTxForQueue, String> {
     let rpc = X402EvmRpc::new_with_wallet_provider(
         wallet_provider.clone(),
@@ -373,52 +381,96 @@ pub async fn sign_transaction_for_queue(
         .map_err(|_| format!("Invalid wallet address: {}", from_str))?;
     let to_str = format!("{:?}", to);
 
-    let nonce = rpc.get_transaction_count(from_address).await?;
-
-    let gas: U256 = rpc.estimate_gas(from_address, to, &calldata, value).await?;
-    let gas = gas * U256::from(120) / U256::from(100); // 20% buffer
-
-    let (max_fee, priority_fee) = rpc.estimate_eip1559_fees().await?;
+    // Reserve a nonce no queued tx or concurrent signer holds; give it back if signing fails
+    let chain_nonce = rpc.get_transaction_count(from_address).await?;
+    let reserved = tx_queue.reserve_nonce(network, &from_str, chain_nonce.as_u64());
+    let nonce = U256::from(reserved);
+
+    let signed: Result<SignedTxForQueue, String> = async {
+        // Simulate first. A revert doesn't stop signing: it travels with the signed tx so the
+        // confirmation UI can show it, and callers that sign autonomously refuse it themselves
+        let simulation = simulate_transaction(&rpc, from_address, to, &calldata, value).await;
+
+        // Gas estimation fails for a reverting call, so fall back to a fixed limit for those
+        let gas: U256 = match rpc.estimate_gas(from_address, to, &calldata, value).await {
+            Ok(gas) => gas * U256::from(120) / U256::from(100), // 20% buffer
+            Err(e) if simulation.status == SimulationStatus::Reverted => {
+                log::warn!("[web3_function_call] Gas estimation failed for reverting tx, using {}: {}", REVERTED_TX_GAS_LIMIT, e);
+                U256::from(REVERTED_TX_GAS_LIMIT)
+            }
+            Err(e) => return Err(e),
+        };
 
-    log::info!(
-        "[web3_function_call] Signing tx for queue: to={:?}, value={}, data_len={} bytes, gas={}, nonce={} on {}",
-        to, value, calldata.len(), gas, nonce, network
-    );
+        let (max_fee, priority_fee) = gas_strategy.estimate_fees(&rpc).await?;
+
+        log::info!(
+            "[web3_function_call] Signing tx for queue: to={:?}, value={}, data_len={} bytes, gas={}, nonce={} on {}",
+            to, value, calldata.len(), gas, nonce, network
+        );
+
+        let tx = Eip1559TransactionRequest::new()
+            .from(from_address)
+            .to(to)
+            .value(value)
+            .data(calldata.clone())
+            .nonce(nonce)
+            .gas(gas)
+            .max_fee_per_gas(max_fee)
+            .max_priority_fee_per_gas(priority_fee)
+            .chain_id(chain_id);
+
+        let typed_tx: TypedTransaction = tx.into();
+        let signature = wallet_provider
+            .sign_transaction(&typed_tx)
+            .await
+            .map_err(|e| format!("Failed to sign transaction: {}", e))?;
+
+        let signed_tx = typed_tx.rlp_signed(&signature);
+        let signed_tx_hex = format!("0x{}", hex::encode(&signed_tx));
+
+        log::info!("[web3_function_call] Transaction signed for queue, nonce={}", nonce);
+
+        Ok(SignedTxForQueue {
+            from: from_str.clone(),
+            to: to_str,
+            value: value.to_string(),
+            data: format!("0x{}", hex::encode(&calldata)),
+            gas_limit: gas.to_string(),
+            max_fee_per_gas: max_fee.to_string(),
+            max_priority_fee_per_gas: priority_fee.to_string(),
+            nonce: nonce.as_u64(),
+            signed_tx_hex,
+            network: network.to_string(),
+            simulation,
+        })
+    }
+    .await;
+    if signed.is_err() {
+        tx_queue.release_nonce(network, &from_str, reserved);
+    }
+    signed
+}
 
-    let tx = Eip1559TransactionRequest::new()
-        .from(from_address)
-        .to(to)
-        .value(value)
-        .data(calldata.clone())
-        .nonce(nonce)
-        .gas(gas)
-        .max_fee_per_gas(max_fee)
-        .max_priority_fee_per_gas(priority_fee)
-        .chain_id(chain_id);
-
-    let typed_tx: TypedTransaction = tx.into();
-    let signature = wallet_provider
-        .sign_transaction(&typed_tx)
-        .await
-        .map_err(|e| format!("Failed to sign transaction: {}", e))?;
-
-    let signed_tx = typed_tx.rlp_signed(&signature);
-    let signed_tx_hex = format!("0x{}", hex::encode(&signed_tx));
-
-    log::info!("[web3_function_call] Transaction signed for queue, nonce={}", nonce);
-
-    Ok(SignedTxForQueue {
-        from: from_str,
-        to: to_str,
-        value: value.to_string(),
-        data: format!("0x{}", hex::encode(&calldata)),
-        gas_limit: gas.to_string(),
-        max_fee_per_gas: max_fee.to_string(),
-        max_priority_fee_per_gas: priority_fee.to_string(),
-        nonce: nonce.as_u64(),
-        signed_tx_hex,
-        network: network.to_string(),
-    })
+/// Simulate a transaction via `eth_call` on the resolved RPC before it is queued.
+/// RPC failures are reported as `Unavailable` rather than blocking the transaction.
+pub async fn simulate_transaction(
+    rpc: &X402EvmRpc,
+    from: Address,
+    to: Address,
+    calldata: &[u8],
+    value: U256,
+) -> TxSimulation {
+    match rpc.simulate_call(from, to, calldata, value).await {
+        Ok(SimulatedCall::Success(data)) => TxSimulation::success(&data),
+        Ok(SimulatedCall::Reverted(reason)) => {
+            log::warn!("[simulate] Transaction to {:?} would revert: {}", to, reason);
+            TxSimulation::reverted(reason)
+        }
+        Err(e) => {
+            log::warn!("[simulate] Simulation unavailable for tx to {:?}: {}", to, e);
+            TxSimulation::unavailable(e)
+        }
+    }
 }
 
 /// Try to auto-format a decoded return value using the preset's `format_decimals_register`.
@@ -596,9 +648,9 @@ pub async fn execute_resolved_call(
     }
 
     // Get wallet provider (required for signing and x402 payments)
-    let wallet_provider = match &context.wallet_provider {
-        Some(wp) => wp,
-        None => return ToolResult::error("Wallet not configured. Cannot execute web3 calls."),
+    let wallet_provider = match context.signing_wallet() {
+        Ok(wp) => wp,
+        Err(e) => return ToolResult::error(format!("{} Cannot execute web3 calls.", e)),
     };
 
     // Resolve RPC configuration from context (respects custom RPC settings)
@@ -611,7 +663,7 @@ pub async fn execute_resolved_call(
 
     if call_only {
         // Read-only call
-        match call_function(network.as_ref(), contract, calldata, &rpc_config, wallet_provider).await {
+        match call_function(network.as_ref(), contract, calldata, &rpc_config, &wallet_provider).await {
             Ok(result) => {
                 let decoded = decode_return(function, &result)
                     .unwrap_or_else(|_| json!(format!("0x{}", hex::encode(&result))));
@@ -642,7 +694,7 @@ pub async fn execute_resolved_call(
             .as_ref()
             .map(|ct| {
                 let ct_lower = ct.to_lowercase();
-                ct_lower == "discord" || ct_lower == "telegram" || ct_lower == "slack"
+                ct_lower == "discord" || ct_lower == "telegram" || ct_lower == "slack" || ct_lower == "matrix"
             })
             .unwrap_or(false);
 
@@ -671,7 +723,9 @@ pub async fn execute_resolved_call(
             calldata,
             tx_value,
             &rpc_config,
-            wallet_provider,
+            &wallet_provider,
+            &GasStrategy::from_context_extra(&context.extra),
+            tx_queue,
         ).await {
             Ok(signed) => {
                 // Verify intent before queueing
@@ -711,6 +765,7 @@ pub async fn execute_resolved_call(
                     ),
                 };
                 if let Err(reason) = verify_intent::verify_intent(&intent, context, None).await {
+                    tx_queue.release_nonce(&signed.network, &signed.from, signed.nonce);
                     return ToolResult::error(reason);
                 }
 
@@ -730,7 +785,8 @@ pub async fn execute_resolved_call(
                     signed.signed_tx_hex.clone(),
                     context.channel_id,
                 )
-                .with_preset(preset_name);
+                .with_preset(preset_name)
+                .with_simulation(Some(signed.simulation.clone()));
 
                 tx_queue.queue(queued_tx);
 
@@ -755,12 +811,14 @@ pub async fn execute_resolved_call(
                     From: {}\n\
                     To: {}\n\
                     Value: {} ({})\n\
-                    Nonce: {}\n\n\
+                    Nonce: {}\n\
+                    Simulation: {}\n\n\
                     --- Next Steps ---\n\
                     To view queued: use `list_queued_web3_tx`\n\
                     To broadcast: use `broadcast_web3_tx` with uuid: {}",
                     uuid, abi_name, function_name, signed.network, signed.from,
-                    contract_addr, signed.value, value_eth, signed.nonce, uuid
+                    contract_addr, signed.value, value_eth, signed.nonce,
+                    signed.simulation.summary(), uuid
                 )).with_metadata(json!({
                     "uuid": uuid,
                     "status": "queued",
@@ -772,7 +830,8 @@ pub async fn execute_resolved_call(
                     "to": contract_addr,
                     "value": signed.value,
                     "nonce": signed.nonce,
-                    "network": network
+                    "network": network,
+                    "simulation": signed.simulation
                 }))
             }
             Err(e) => ToolResult::error(e),
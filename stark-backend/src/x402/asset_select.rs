@@ -0,0 +1,174 @@
+//! x402 payment asset selection
+//!
+//! A 402 response may offer several `accepts` options (e.g. USDC and another
+//! stablecoin). Only options whose asset has a configured payment limit — and
+//! whose amount passes that limit and any window budget — are eligible. The
+//! configured default asset is tried first, and an option is chosen only if
+//! the wallet's on-chain balance covers the required amount.
+
+use ethers::types::U256;
+
+use super::payment_limits;
+use super::types::PaymentRequirements;
+
+/// Pick the payment option to sign from a 402 `accepts` list.
+///
+/// A single eligible option is returned without a balance lookup (there is
+/// nothing to choose between). If no option has a confirmed sufficient
+/// balance, the first option whose balance could not be checked is used;
+/// otherwise an error describing the shortfall is returned.
+pub async fn select_payment_option<'a>(
+    accepts: &'a [PaymentRequirements],
+    wallet_address: &str,
+) -> Result<&'a PaymentRequirements, String> {
+    let candidates = rank_candidates(accepts, &crate::config::x402_default_asset())?;
+    if candidates.len() == 1 {
+        return Ok(candidates[0]);
+    }
+
+    let mut unchecked: Option<&PaymentRequirements> = None;
+    let mut shortfalls: Vec<String> = Vec::new();
+
+    for option in candidates {
+        let required = match U256::from_dec_str(&option.max_amount_required) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        let token = token_address(option);
+        match super::client::check_token_balance(wallet_address, &token, network_name(&option.network)).await {
+            Ok(balance) if balance >= required => {
+                log::info!(
+                    "[X402] Selected payment asset {} (balance {} >= {})",
+                    option.asset, balance, required
+                );
+                return Ok(option);
+            }
+            Ok(balance) => {
+                shortfalls.push(format!(
+                    "{} (have {}, need {})",
+                    payment_limits::known_asset_key(&option.asset).unwrap_or_else(|| option.asset.clone()),
+                    balance,
+                    required
+                ));
+            }
+            Err(e) => {
+                log::warn!("[X402] Balance check for {} failed: {}", option.asset, e);
+                unchecked.get_or_insert(option);
+            }
+        }
+    }
+
+    unchecked.ok_or_else(|| {
+        format!(
+            "x402 payment rejected: insufficient balance in every accepted asset: {}",
+            shortfalls.join(", ")
+        )
+    })
+}
+
+/// Filter `accepts` down to options allowed by the configured payment limits
+/// and order them so the default asset comes first (other options keep the
+/// server's order). Returns the first limit error if nothing is eligible.
+pub fn rank_candidates<'a>(
+    accepts: &'a [PaymentRequirements],
+    default_asset: &str,
+) -> Result<Vec<&'a PaymentRequirements>, String> {
+    if accepts.is_empty() {
+        return Err("No payment options in 402 response".to_string());
+    }
+
+    let mut first_error: Option<String> = None;
+    let mut eligible: Vec<&PaymentRequirements> = Vec::new();
+    for option in accepts {
+        match payment_limits::check_payment_limit(&option.asset, &option.max_amount_required) {
+            Ok(()) => eligible.push(option),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    if eligible.is_empty() {
+        return Err(first_error.unwrap_or_else(|| "No eligible payment options".to_string()));
+    }
+
+    let default_key = default_asset.to_uppercase();
+    eligible.sort_by_key(|o| {
+        payment_limits::known_asset_key(&o.asset).as_deref() != Some(default_key.as_str())
+    });
+    Ok(eligible)
+}
+
+/// Contract address to query for the option's balance.
+fn token_address(option: &PaymentRequirements) -> String {
+    option
+        .extra
+        .as_ref()
+        .and_then(|e| e.address.clone())
+        .unwrap_or_else(|| option.asset.clone())
+}
+
+/// Map an x402 network identifier (e.g. "eip155:8453" or "base") to the
+/// network name used by the RPC resolver.
+fn network_name(network: &str) -> &str {
+    match network {
+        "eip155:8453" => "base",
+        "eip155:84532" => "base-sepolia",
+        "eip155:1" | "ethereum" => "mainnet",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(asset: &str, amount: &str) -> PaymentRequirements {
+        PaymentRequirements {
+            scheme: "exact".to_string(),
+            network: "eip155:8453".to_string(),
+            max_amount_required: amount.to_string(),
+            pay_to_address: "0x0000000000000000000000000000000000000001".to_string(),
+            asset: asset.to_string(),
+            max_timeout_seconds: 60,
+            resource: None,
+            description: None,
+            extra: None,
+        }
+    }
+
+    #[test]
+    fn test_rank_prefers_default_asset() {
+        payment_limits::set_limit("SELA", "1000", 6, "SELA", Some("0x00000000000000000000000000000000000005a1"));
+        payment_limits::set_limit("SELB", "1000", 6, "SELB", Some("0x00000000000000000000000000000000000005b1"));
+        let accepts = vec![
+            option("0x00000000000000000000000000000000000005a1", "10"),
+            option("0x00000000000000000000000000000000000005b1", "10"),
+        ];
+
+        let ranked = rank_candidates(&accepts, "selb").unwrap();
+        assert_eq!(ranked[0].asset, accepts[1].asset);
+        assert_eq!(ranked[1].asset, accepts[0].asset);
+    }
+
+    #[test]
+    fn test_rank_drops_assets_without_limits_or_over_limit() {
+        payment_limits::set_limit("SELC", "100", 6, "SELC", None);
+        let accepts = vec![
+            option("UNKNOWNSEL", "10"),
+            option("SELC", "500"),
+            option("SELC", "50"),
+        ];
+
+        let ranked = rank_candidates(&accepts, "USDC").unwrap();
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].max_amount_required, "50");
+    }
+
+    #[test]
+    fn test_rank_returns_limit_error_when_nothing_eligible() {
+        let accepts = vec![option("NOLIMITSEL", "10")];
+        let err = rank_candidates(&accepts, "USDC").unwrap_err();
+        assert!(err.contains("no payment limit configured"));
+    }
+}
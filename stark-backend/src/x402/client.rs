@@ -390,19 +390,22 @@ impl X402Client {
             payment_required.accepts.first().map(|a| a.pay_to_address.as_str()).unwrap_or("?")
         );
 
-        // Get the first (and typically only) payment option
-        let requirements = payment_required.accepts.first()
-            .ok_or_else(|| "No payment options in 402 response".to_string())?;
+        // Pick the payment option (asset) to use; enforces payment limits before signing
+        let requirements = super::asset_select::select_payment_option(
+            &payment_required.accepts,
+            &self.signer.address(),
+        )
+        .await?;
 
-        // Check payment limit before signing
-        super::payment_limits::check_payment_limit(
+        // Create payment info before signing
+        let payment_info = X402PaymentInfo::from_requirements(requirements);
+
+        // Hold the amount against the budget; released if anything below fails
+        let reservation = super::payment_limits::reserve_spend(
             &requirements.asset,
             &requirements.max_amount_required,
         )?;
 
-        // Create payment info before signing
-        let payment_info = X402PaymentInfo::from_requirements(requirements);
-
         // Sign the payment using V2 format (required by Kimi/AI relay)
         let payment_payload = self.signer.sign_payment_v2(requirements).await?;
         let payment_header_value = payment_payload.to_base64()?;
@@ -415,11 +418,12 @@ impl X402Client {
         );
 
         // Retry with payment
-        let paid_response = build_request()
+        let sent = build_request()
             .header("X-PAYMENT", payment_header_value)
             .send()
-            .await
-            .map_err(|e| format!("Paid request failed: {}", e))?;
+            .await;
+        let rejected = settle_sent_payment(reservation, &sent);
+        let paid_response = sent.map_err(|e| format!("Paid request failed: {}", e))?;
 
         log::info!("[X402] Payment sent, response status: {}", paid_response.status());
 
@@ -445,9 +449,15 @@ impl X402Client {
             log::info!("[X402] Payment confirmed (no tx_hash in response headers)");
             payment_info.mark_confirmed()
         } else {
-            // Payment may have failed
-            log::warn!("[X402] Payment response status: {}, keeping as pending", paid_response.status());
-            payment_info
+            log::warn!(
+                "[X402] Payment response status: {}, marking as failed ({})",
+                paid_response.status(),
+                if rejected { "rejected, budget released" } else { "may have settled, still counted" }
+            );
+            return Ok(X402Response {
+                response: paid_response,
+                payment: Some(payment_info.mark_failed()),
+            });
         };
 
         Ok(X402Response {
@@ -508,15 +518,26 @@ pub fn is_x402_endpoint(url: &str) -> bool {
 
 /// Check USDC balance on Base for a wallet address.
 /// Returns the balance in raw units (6 decimals for USDC).
-/// Used to detect insufficient funds after an x402 payment failure.
+/// Used to detect insufficient funds after an x402 payment failure and,
+/// via `check_usdc_balance_cached`, to warn before dispatching.
 pub async fn check_usdc_balance(wallet_address: &str) -> Result<ethers::types::U256, String> {
+    check_token_balance(wallet_address, super::types::USDC_ADDRESS, "base").await
+}
+
+/// Check an ERC-20 balance for a wallet address on the given network
+/// (e.g. "base"). Returns the balance in the token's raw units.
+pub async fn check_token_balance(
+    wallet_address: &str,
+    token_address: &str,
+    network: &str,
+) -> Result<ethers::types::U256, String> {
     let address: ethers::types::Address = wallet_address
         .parse()
         .map_err(|e| format!("Invalid wallet address: {}", e))?;
 
-    let usdc_address: ethers::types::Address = super::types::USDC_ADDRESS
+    let token: ethers::types::Address = token_address
         .parse()
-        .map_err(|e| format!("Invalid USDC address: {}", e))?;
+        .map_err(|e| format!("Invalid token address: {}", e))?;
 
     let call_data = super::erc20::encode_balance_of(address);
 
@@ -524,13 +545,13 @@ pub async fn check_usdc_balance(wallet_address: &str) -> Result<ethers::types::U
         "jsonrpc": "2.0",
         "method": "eth_call",
         "params": [{
-            "to": format!("{:?}", usdc_address),
+            "to": format!("{:?}", token),
             "data": format!("0x{}", hex::encode(&call_data))
         }, "latest"],
         "id": 1
     });
 
-    let resolved = crate::tools::rpc_config::resolve_rpc_readonly("base");
+    let resolved = crate::tools::rpc_config::resolve_rpc_readonly(network);
     let client = crate::http::shared_client();
     let response = client
         .post(&resolved.url)
@@ -558,18 +579,49 @@ pub async fn check_usdc_balance(wallet_address: &str) -> Result<ethers::types::U
     super::erc20::decode_balance(&bytes)
 }
 
+/// How long a fetched USDC balance is reused by `check_usdc_balance_cached`.
+const BALANCE_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Last fetched balance per wallet (lowercased address → (balance, fetched_at)).
+static BALANCE_CACHE: once_cell::sync::Lazy<Mutex<std::collections::HashMap<String, (ethers::types::U256, std::time::Instant)>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(std::collections::HashMap::new()));
+
+/// Same as `check_usdc_balance`, but reuses a recent result for up to
+/// `BALANCE_CACHE_TTL` so the pre-dispatch check doesn't hit RPC on every message.
+pub async fn check_usdc_balance_cached(wallet_address: &str) -> Result<ethers::types::U256, String> {
+    let key = wallet_address.to_lowercase();
+    let cached = BALANCE_CACHE
+        .lock()
+        .unwrap()
+        .get(&key)
+        .filter(|(_, fetched_at)| fetched_at.elapsed() < BALANCE_CACHE_TTL)
+        .map(|(balance, _)| *balance);
+    if let Some(balance) = cached {
+        return Ok(balance);
+    }
+
+    let balance = check_usdc_balance(wallet_address).await?;
+    BALANCE_CACHE
+        .lock()
+        .unwrap()
+        .insert(key, (balance, std::time::Instant::now()));
+    Ok(balance)
+}
+
 /// Parse a 402 response and sign an x402 payment, returning the X-PAYMENT header value.
 ///
 /// Tries to parse payment requirements from:
 /// 1. `payment-required` / `PAYMENT-REQUIRED` response header (base64-encoded)
 /// 2. Response body as JSON (direct `PaymentRequired` structure)
 ///
-/// Returns `(x_payment_header_value, payment_info)` on success.
+/// Returns `(x_payment_header_value, payment_info, reservation)` on success.
+/// The reservation holds the amount against the spend budget; pass it to
+/// `settle_sent_payment` once the payment has been sent, or drop it to release the amount.
 pub async fn sign_402_payment(
     response_body: &str,
     response_headers: &reqwest::header::HeaderMap,
     wallet_provider: &Arc<dyn WalletProvider>,
-) -> Result<(String, X402PaymentInfo), String> {
+) -> Result<(String, X402PaymentInfo, super::payment_limits::SpendReservation), String> {
     // Try header first (base64-encoded)
     let payment_required = if let Some(header_val) = response_headers
         .get("payment-required")
@@ -583,19 +635,19 @@ pub async fn sign_402_payment(
             .map_err(|e| format!("Failed to parse 402 payment requirements from body: {}", e))?
     };
 
-    let requirements = payment_required
-        .accepts
-        .first()
-        .ok_or_else(|| "No payment options in 402 response".to_string())?;
+    // Pick the payment option (asset) to use; enforces payment limits
+    let requirements = super::asset_select::select_payment_option(
+        &payment_required.accepts,
+        &wallet_provider.get_address(),
+    )
+    .await?;
 
-    // Check payment limit
-    super::payment_limits::check_payment_limit(
+    let payment_info = X402PaymentInfo::from_requirements(requirements);
+    let reservation = super::payment_limits::reserve_spend(
         &requirements.asset,
         &requirements.max_amount_required,
     )?;
 
-    let payment_info = X402PaymentInfo::from_requirements(requirements);
-
     // Sign the payment
     let signer = X402Signer::new(wallet_provider.clone());
     let payment_payload = signer.sign_payment_v2(requirements).await?;
@@ -608,7 +660,29 @@ pub async fn sign_402_payment(
         payment_info.pay_to
     );
 
-    Ok((header_value, payment_info))
+    Ok((header_value, payment_info, reservation))
+}
+
+/// Settle a spend reservation once the signed payment has been handed to the transport.
+///
+/// The facilitator may settle even if the request then times out or the server answers
+/// with an error, so the amount stays counted against the budget. It is released only
+/// when the server answers 402 again, i.e. it rejected the payment. Returns whether
+/// the payment was rejected.
+pub fn settle_sent_payment(
+    reservation: super::payment_limits::SpendReservation,
+    sent: &reqwest::Result<Response>,
+) -> bool {
+    match sent {
+        Ok(response) if response.status() == reqwest::StatusCode::PAYMENT_REQUIRED => {
+            drop(reservation);
+            true
+        }
+        _ => {
+            reservation.commit();
+            false
+        }
+    }
 }
 
 /// Result of an x402-aware request that may have required payment.
@@ -641,7 +715,7 @@ where
         .await
         .map_err(|e| format!("Failed to read 402 body: {}", e))?;
 
-    let (x_payment_header, payment_info) =
+    let (x_payment_header, payment_info, reservation) =
         sign_402_payment(&body_402, &response_headers, wallet_provider).await?;
 
     log::info!(
@@ -653,10 +727,9 @@ where
 
     let retry_req = build_retry_request().header("X-PAYMENT", x_payment_header);
 
-    let paid_response = retry_req
-        .send()
-        .await
-        .map_err(|e| format!("Paid request failed: {}", e))?;
+    let sent = retry_req.send().await;
+    settle_sent_payment(reservation, &sent);
+    let paid_response = sent.map_err(|e| format!("Paid request failed: {}", e))?;
 
     // Extract tx hash from response headers
     let tx_hash = paid_response
@@ -673,7 +746,10 @@ where
     } else if paid_response.status().is_success() {
         payment_info.mark_confirmed()
     } else {
-        payment_info
+        return Ok(X402RetryResult {
+            response: paid_response,
+            payment: Some(payment_info.mark_failed()),
+        });
     };
 
     Ok(X402RetryResult {
@@ -728,3 +804,68 @@ fn parse_url_parts(url: &str) -> (String, String, Option<String>) {
 
     (authority, path, query)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::x402::payment_limits::{budget_status, reserve_spend, set_budget, set_limit};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serve a single raw HTTP response on a local port and return its URL.
+    async fn serve_once(status_line: &'static str) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await;
+            let reply = format!("HTTP/1.1 {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", status_line);
+            let _ = socket.write_all(reply.as_bytes()).await;
+        });
+        format!("http://{}/", addr)
+    }
+
+    #[tokio::test]
+    async fn test_paid_request_error_keeps_budget_consumed() {
+        set_limit("SETTLEA", "1000", 0, "SETTLEA", None);
+        set_budget("SETTLEA", "1000", 24);
+
+        // Nothing listens here, so the paid request fails after it was handed to the transport
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+
+        let reservation = reserve_spend("SETTLEA", "600").unwrap();
+        let sent = Client::new().get(&url).send().await;
+        assert!(sent.is_err());
+
+        assert!(!settle_sent_payment(reservation, &sent));
+        assert_eq!(budget_status("SETTLEA").unwrap().spent, "600");
+    }
+
+    #[tokio::test]
+    async fn test_paid_request_error_status_keeps_budget_consumed() {
+        set_limit("SETTLEB", "1000", 0, "SETTLEB", None);
+        set_budget("SETTLEB", "1000", 24);
+
+        let url = serve_once("500 Internal Server Error").await;
+        let reservation = reserve_spend("SETTLEB", "600").unwrap();
+        let sent = Client::new().get(&url).send().await;
+
+        assert!(!settle_sent_payment(reservation, &sent));
+        assert_eq!(budget_status("SETTLEB").unwrap().spent, "600");
+    }
+
+    #[tokio::test]
+    async fn test_rejected_payment_releases_budget() {
+        set_limit("SETTLEC", "1000", 0, "SETTLEC", None);
+        set_budget("SETTLEC", "1000", 24);
+
+        let url = serve_once("402 Payment Required").await;
+        let reservation = reserve_spend("SETTLEC", "600").unwrap();
+        let sent = Client::new().get(&url).send().await;
+
+        assert!(settle_sent_payment(reservation, &sent));
+        assert_eq!(budget_status("SETTLEC").unwrap().spent, "0");
+    }
+}
@@ -51,6 +51,18 @@ struct JsonRpcResponse {
 struct JsonRpcError {
     code: i64,
     message: String,
+    /// Revert data for `execution reverted` errors (hex string on most nodes)
+    #[serde(default)]
+    data: Option<Value>,
+}
+
+/// Outcome of simulating a transaction with `eth_call`
+#[derive(Debug, Clone)]
+pub enum SimulatedCall {
+    /// Call succeeded; contains the raw return data
+    Success(Bytes),
+    /// Call reverted; contains the decoded (or raw) revert reason
+    Reverted(String),
 }
 
 /// Transaction receipt from eth_getTransactionReceipt
@@ -148,6 +160,18 @@ impl X402EvmRpc {
 
     /// Make a JSON-RPC call via x402 or regular HTTP depending on config
     async fn rpc_call(&self, method: &str, params: Value) -> Result<Value, String> {
+        let rpc_response = self.rpc_request(method, params).await?;
+
+        if let Some(error) = rpc_response.error {
+            return Err(format!("RPC error {}: {}", error.code, error.message));
+        }
+
+        rpc_response.result.ok_or_else(|| "RPC returned null result".to_string())
+    }
+
+    /// Send a JSON-RPC request and return the parsed response without
+    /// interpreting the `error` field (callers that need revert data use this)
+    async fn rpc_request(&self, method: &str, params: Value) -> Result<JsonRpcResponse, String> {
         let request = JsonRpcRequest {
             jsonrpc: "2.0",
             method: method.to_string(),
@@ -172,14 +196,8 @@ impl X402EvmRpc {
             return Err(format!("RPC error ({}) from {}: {}", status, url, if body.is_empty() { "empty response" } else { &body }));
         }
 
-        let rpc_response: JsonRpcResponse = serde_json::from_str(&body)
-            .map_err(|e| format!("Failed to parse RPC response: {} - body: {}", e, body))?;
-
-        if let Some(error) = rpc_response.error {
-            return Err(format!("RPC error {}: {}", error.code, error.message));
-        }
-
-        rpc_response.result.ok_or_else(|| "RPC returned null result".to_string())
+        serde_json::from_str(&body)
+            .map_err(|e| format!("Failed to parse RPC response: {} - body: {}", e, body))
     }
 
     /// Get ETH balance of an address
@@ -222,6 +240,53 @@ impl X402EvmRpc {
         Ok(Bytes::from(bytes))
     }
 
+    /// Simulate a transaction with `eth_call` from the sending wallet.
+    /// Returns `Ok(SimulatedCall::Reverted)` when the node reports a revert,
+    /// and `Err` only when the simulation itself could not be performed.
+    pub async fn simulate_call(
+        &self,
+        from: Address,
+        to: Address,
+        data: &[u8],
+        value: U256,
+    ) -> Result<SimulatedCall, String> {
+        let params = json!([
+            {
+                "from": format!("{:?}", from),
+                "to": format!("{:?}", to),
+                "data": format!("0x{}", hex::encode(data)),
+                "value": format!("0x{:x}", value)
+            },
+            "latest"
+        ]);
+
+        let rpc_response = self.rpc_request("eth_call", params).await?;
+
+        if let Some(error) = rpc_response.error {
+            let revert_data = error.data.as_ref().and_then(|d| d.as_str());
+            let is_revert = error.code == 3
+                || error.message.to_lowercase().contains("revert")
+                || revert_data.is_some();
+            if !is_revert {
+                return Err(format!("RPC error {}: {}", error.code, error.message));
+            }
+            let reason = revert_data
+                .and_then(decode_revert_reason)
+                .unwrap_or(error.message);
+            return Ok(SimulatedCall::Reverted(reason));
+        }
+
+        let hex_str = rpc_response.result
+            .as_ref()
+            .and_then(|r| r.as_str())
+            .ok_or_else(|| "Invalid eth_call response".to_string())?;
+
+        let bytes = hex::decode(hex_str.trim_start_matches("0x"))
+            .map_err(|e| format!("Failed to decode eth_call result: {}", e))?;
+
+        Ok(SimulatedCall::Success(Bytes::from(bytes)))
+    }
+
     /// Estimate gas for a transaction
     pub async fn estimate_gas(
         &self,
@@ -280,8 +345,19 @@ impl X402EvmRpc {
         Ok((max_fee, capped_priority_fee))
     }
 
+    /// Get the base fee per gas of the latest block
+    pub async fn get_base_fee(&self) -> Result<U256, String> {
+        let block = self.rpc_call("eth_getBlockByNumber", json!(["latest", false])).await?;
+        let base_fee_hex = block.get("baseFeePerGas")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| "Latest block has no baseFeePerGas".to_string())?;
+        U256::from_str_radix(base_fee_hex.trim_start_matches("0x"), 16)
+            .map_err(|e| format!("Failed to parse base fee: {}", e))
+    }
+
     /// Send a raw signed transaction
     pub async fn send_raw_transaction(&self, signed_tx: &[u8]) -> Result<H256, String> {
+        crate::wallet::ensure_signing_allowed()?;
         let params = json!([format!("0x{}", hex::encode(signed_tx))]);
 
         let result = self.rpc_call("eth_sendRawTransaction", params).await?;
@@ -350,3 +426,61 @@ impl X402EvmRpc {
         }
     }
 }
+
+/// Decode revert data returned by a failed `eth_call`.
+/// Handles `Error(string)` and `Panic(uint256)`; other custom errors are
+/// returned as their raw hex selector so the caller can still display them.
+pub fn decode_revert_reason(data: &str) -> Option<String> {
+    let bytes = hex::decode(data.trim_start_matches("0x")).ok()?;
+    if bytes.len() < 4 {
+        return None;
+    }
+    let (selector, payload) = bytes.split_at(4);
+    match selector {
+        // Error(string)
+        [0x08, 0xc3, 0x79, 0xa0] => {
+            let tokens = ethers::abi::decode(&[ethers::abi::ParamType::String], payload).ok()?;
+            tokens.into_iter().next()?.into_string()
+        }
+        // Panic(uint256)
+        [0x4e, 0x48, 0x7b, 0x71] => {
+            let tokens = ethers::abi::decode(&[ethers::abi::ParamType::Uint(256)], payload).ok()?;
+            let code = tokens.into_iter().next()?.into_uint()?;
+            Some(format!("panic code 0x{:x}", code))
+        }
+        _ => Some(format!("custom error 0x{}", hex::encode(selector))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_revert_reason_error_string() {
+        let data = format!(
+            "0x08c379a0{}",
+            hex::encode(ethers::abi::encode(&[ethers::abi::Token::String(
+                "ERC20: transfer amount exceeds balance".to_string()
+            )]))
+        );
+        assert_eq!(
+            decode_revert_reason(&data).as_deref(),
+            Some("ERC20: transfer amount exceeds balance")
+        );
+    }
+
+    #[test]
+    fn test_decode_revert_reason_panic_and_custom() {
+        let panic = format!(
+            "0x4e487b71{}",
+            hex::encode(ethers::abi::encode(&[ethers::abi::Token::Uint(U256::from(0x11))]))
+        );
+        assert_eq!(decode_revert_reason(&panic).as_deref(), Some("panic code 0x11"));
+        assert_eq!(
+            decode_revert_reason("0xe450d38c0000").as_deref(),
+            Some("custom error 0xe450d38c")
+        );
+        assert_eq!(decode_revert_reason("0x"), None);
+    }
+}
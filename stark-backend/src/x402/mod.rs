@@ -15,11 +15,12 @@ mod types;
 mod client;
 mod signer;
 mod evm_rpc;
+pub mod asset_select;
 pub mod erc20;
 pub mod payment_limits;
 pub mod verify;
 
 pub use types::*;
-pub use client::{X402Client, X402Response, X402RetryResult, PaymentMode, is_x402_endpoint, sign_402_payment, retry_with_x402_payment, check_usdc_balance};
+pub use client::{X402Client, X402Response, X402RetryResult, PaymentMode, is_x402_endpoint, sign_402_payment, retry_with_x402_payment, settle_sent_payment, check_usdc_balance, check_usdc_balance_cached};
 pub use signer::X402Signer;
-pub use evm_rpc::{TxLog, X402EvmRpc};
+pub use evm_rpc::{SimulatedCall, TxLog, X402EvmRpc};
@@ -1,13 +1,21 @@
-//! x402 Payment Limits — per-call maximum amounts
+//! x402 Payment Limits — per-call maximum amounts and rolling-window budgets
 //!
 //! Loaded from `config/x402_payment_limits.ron` at startup, then overridden
 //! by any user-configured values from the database.  The global is updated
 //! at runtime when the user changes limits via the API.
+//!
+//! Window budgets cap the cumulative spend per asset over a rolling window
+//! (e.g. 10 USDC per 24h).  Spends are tracked in an in-memory ledger that is
+//! seeded from the non-failed `x402_payments` history at startup.  A payment
+//! reserves its amount (checked and appended under one lock) before it is
+//! signed, and the reservation is released again if the payment fails.
 
-use serde::Deserialize;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 // ---------------------------------------------------------------------------
 // Types
@@ -35,11 +43,53 @@ pub struct PaymentLimit {
     pub address: Option<String>,
 }
 
+/// Cumulative spend cap for one asset over a rolling window.
+#[derive(Debug, Clone)]
+pub struct PaymentBudget {
+    /// Maximum raw-unit amount that may be spent within the window
+    pub max_amount: String,
+    /// Window length in hours (1..=MAX_WINDOW_HOURS)
+    pub window_hours: u32,
+}
+
+/// Snapshot of a budget and how much of it has been consumed.
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetStatus {
+    pub asset: String,
+    pub window_hours: u32,
+    pub max_amount: String,
+    pub spent: String,
+    pub remaining: String,
+    pub decimals: u8,
+    pub max_formatted: String,
+    pub spent_formatted: String,
+    pub remaining_formatted: String,
+}
+
+/// A single signed payment in the in-memory spend ledger.
+#[derive(Debug, Clone)]
+struct SpendRecord {
+    id: u64,
+    asset: String,
+    amount: u128,
+    at: DateTime<Utc>,
+}
+
+/// Longest supported budget window (30 days). The spend ledger never keeps
+/// records older than this.
+pub const MAX_WINDOW_HOURS: u32 = 24 * 30;
+
+/// Window used when a budget is configured without an explicit length.
+pub const DEFAULT_WINDOW_HOURS: u32 = 24;
+
 // ---------------------------------------------------------------------------
 // Global state
 // ---------------------------------------------------------------------------
 
 static LIMITS: RwLock<Option<HashMap<String, PaymentLimit>>> = RwLock::new(None);
+static BUDGETS: RwLock<Option<HashMap<String, PaymentBudget>>> = RwLock::new(None);
+static SPEND_LEDGER: RwLock<Vec<SpendRecord>> = RwLock::new(Vec::new());
+static NEXT_SPEND_ID: AtomicU64 = AtomicU64::new(1);
 
 // ---------------------------------------------------------------------------
 // Public API
@@ -126,6 +176,14 @@ pub fn get_limit(asset: &str) -> Option<PaymentLimit> {
     None
 }
 
+/// Per-call cap for an asset in whole tokens. Used as an upper-bound cost
+/// estimate before a paid request, since the real price only arrives with the 402.
+pub fn per_call_cap(asset: &str) -> Option<f64> {
+    let limit = get_limit(asset)?;
+    let raw: u128 = limit.max_amount.parse().ok()?;
+    Some(raw as f64 / 10f64.powi(limit.decimals as i32))
+}
+
 /// Update (or insert) a single limit at runtime.
 /// Called from the API controller and from the DB-restore path.
 pub fn set_limit(asset: &str, max_amount: &str, decimals: u8, display_name: &str, address: Option<&str>) {
@@ -155,6 +213,48 @@ pub fn remove_limit(asset: &str) {
 ///
 /// Returns `Ok(())` if allowed, or `Err(message)` if blocked.
 pub fn check_payment_limit(asset: &str, amount_raw: &str) -> Result<(), String> {
+    let requested = check_per_call_limit(asset, amount_raw)?;
+    check_window_budget(budget_status(asset), requested)
+}
+
+/// Check a payment like `check_payment_limit` and, if allowed, add it to the
+/// spend ledger in the same step so concurrent payments can't both fit into
+/// the last of a budget. The amount is given back when the returned
+/// reservation is dropped without being committed.
+pub fn reserve_spend(asset: &str, amount_raw: &str) -> Result<SpendReservation, String> {
+    let requested = check_per_call_limit(asset, amount_raw)?;
+    let key = resolve_asset_key(asset);
+    let mut ledger = SPEND_LEDGER.write().unwrap();
+    check_window_budget(budget_status_in(&key, &ledger), requested)?;
+    let id = push_spend(&mut ledger, key, requested, Utc::now());
+    Ok(SpendReservation { id, committed: false })
+}
+
+/// Budget held by a payment that is about to be signed and sent.
+#[must_use = "dropping a reservation releases it"]
+#[derive(Debug)]
+pub struct SpendReservation {
+    id: u64,
+    committed: bool,
+}
+
+impl SpendReservation {
+    /// Keep the amount in the ledger: the payment went out (or may have).
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for SpendReservation {
+    fn drop(&mut self) {
+        if !self.committed {
+            SPEND_LEDGER.write().unwrap().retain(|r| r.id != self.id);
+        }
+    }
+}
+
+/// Per-call limit check; returns the parsed amount.
+fn check_per_call_limit(asset: &str, amount_raw: &str) -> Result<u128, String> {
     let limit = match get_limit(asset) {
         Some(l) => l,
         None => return Err(format!(
@@ -182,9 +282,155 @@ pub fn check_payment_limit(asset: &str, amount_raw: &str) -> Result<(), String>
         ));
     }
 
+    Ok(requested)
+}
+
+// ---------------------------------------------------------------------------
+// Rolling-window budgets
+// ---------------------------------------------------------------------------
+
+/// Update (or insert) the rolling-window budget for an asset.
+pub fn set_budget(asset: &str, max_amount: &str, window_hours: u32) {
+    let mut guard = BUDGETS.write().unwrap();
+    let map = guard.get_or_insert_with(HashMap::new);
+    map.insert(
+        resolve_asset_key(asset),
+        PaymentBudget {
+            max_amount: max_amount.to_string(),
+            window_hours: window_hours.clamp(1, MAX_WINDOW_HOURS),
+        },
+    );
+}
+
+/// Remove the rolling-window budget for an asset (spending becomes uncapped
+/// apart from the per-call limit).
+pub fn remove_budget(asset: &str) {
+    let mut guard = BUDGETS.write().unwrap();
+    if let Some(map) = guard.as_mut() {
+        map.remove(&resolve_asset_key(asset));
+    }
+}
+
+/// Return all configured budgets (asset → PaymentBudget).
+pub fn get_all_budgets() -> HashMap<String, PaymentBudget> {
+    let guard = BUDGETS.read().unwrap();
+    guard.clone().unwrap_or_default()
+}
+
+/// Record a signed payment against the spend ledger.
+///
+/// `asset` may be a symbol or a contract address; it is normalised to the
+/// limit key so that both forms count against the same budget.
+pub fn record_spend(asset: &str, amount_raw: &str, at: DateTime<Utc>) {
+    let amount: u128 = match amount_raw.parse() {
+        Ok(v) => v,
+        Err(_) => {
+            log::warn!("[x402_limits] Ignoring unparseable spend amount '{}'", amount_raw);
+            return;
+        }
+    };
+    let key = resolve_asset_key(asset);
+    push_spend(&mut SPEND_LEDGER.write().unwrap(), key, amount, at);
+}
+
+/// Append to the ledger (dropping records older than the longest window)
+/// and return the new record's id.
+fn push_spend(ledger: &mut Vec<SpendRecord>, asset: String, amount: u128, at: DateTime<Utc>) -> u64 {
+    let cutoff = Utc::now() - Duration::hours(MAX_WINDOW_HOURS as i64);
+    ledger.retain(|r| r.at >= cutoff);
+    let id = NEXT_SPEND_ID.fetch_add(1, Ordering::Relaxed);
+    ledger.push(SpendRecord { id, asset, amount, at });
+    id
+}
+
+/// Current budget status for an asset, or `None` if no budget is configured.
+pub fn budget_status(asset: &str) -> Option<BudgetStatus> {
+    let key = resolve_asset_key(asset);
+    budget_status_in(&key, &SPEND_LEDGER.read().unwrap())
+}
+
+fn budget_status_in(key: &str, ledger: &[SpendRecord]) -> Option<BudgetStatus> {
+    let budget = get_all_budgets().remove(key)?;
+    let max: u128 = budget.max_amount.parse().ok()?;
+    let spent = spent_in_window(ledger, key, budget.window_hours);
+    let remaining = max.saturating_sub(spent);
+    let decimals = get_limit(key).map(|l| l.decimals).unwrap_or(6);
+    Some(BudgetStatus {
+        asset: key.to_string(),
+        window_hours: budget.window_hours,
+        max_amount: max.to_string(),
+        spent: spent.to_string(),
+        remaining: remaining.to_string(),
+        decimals,
+        max_formatted: format_amount(max, decimals),
+        spent_formatted: format_amount(spent, decimals),
+        remaining_formatted: format_amount(remaining, decimals),
+    })
+}
+
+/// Budget status for every asset that has a budget configured.
+pub fn all_budget_statuses() -> Vec<BudgetStatus> {
+    let mut keys: Vec<String> = get_all_budgets().into_keys().collect();
+    keys.sort();
+    keys.iter().filter_map(|k| budget_status(k)).collect()
+}
+
+/// Check whether a payment of `requested` raw units would push the
+/// rolling-window spend described by `status` over its budget.
+fn check_window_budget(status: Option<BudgetStatus>, requested: u128) -> Result<(), String> {
+    let status = match status {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+    let remaining: u128 = status.remaining.parse().unwrap_or(0);
+    if requested > remaining {
+        return Err(format!(
+            "x402 payment of {} {} blocked: the {}h budget of {} {} is exhausted \
+             ({} spent, {} remaining). Wait for the window to roll over or raise the budget \
+             on the Crypto Transactions page.",
+            format_amount(requested, status.decimals), status.asset,
+            status.window_hours, status.max_formatted, status.asset,
+            status.spent_formatted, status.remaining_formatted
+        ));
+    }
     Ok(())
 }
 
+fn spent_in_window(ledger: &[SpendRecord], key: &str, window_hours: u32) -> u128 {
+    let cutoff = Utc::now() - Duration::hours(window_hours as i64);
+    ledger
+        .iter()
+        .filter(|r| r.asset == key && r.at >= cutoff)
+        .map(|r| r.amount)
+        .sum()
+}
+
+/// Map a symbol or contract address to the key used in the limits map.
+/// Unknown assets fall back to their uppercased form.
+fn resolve_asset_key(asset: &str) -> String {
+    known_asset_key(asset).unwrap_or_else(|| asset.to_uppercase())
+}
+
+/// Return the limits-map key (symbol) for a symbol or contract address,
+/// or `None` if the asset has no configured limit.
+pub fn known_asset_key(asset: &str) -> Option<String> {
+    let upper = asset.to_uppercase();
+    let guard = LIMITS.read().unwrap();
+    let map = guard.as_ref()?;
+    if map.contains_key(&upper) {
+        return Some(upper);
+    }
+    if asset.starts_with("0x") || asset.starts_with("0X") {
+        let asset_lower = asset.to_lowercase();
+        for (key, limit) in map.iter() {
+            if limit.address.as_deref().map(|a| a.to_lowercase()) == Some(asset_lower.clone()) {
+                return Some(key.clone());
+            }
+        }
+    }
+    None
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -225,3 +471,90 @@ fn builtin_defaults() -> HashMap<String, PaymentLimit> {
     );
     map
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Tests share the process-wide globals, so each one uses its own asset key.
+
+    #[test]
+    fn test_budget_blocks_when_window_exhausted() {
+        set_limit("BUDGETA", "1000", 2, "BUDGETA", None);
+        set_budget("BUDGETA", "1500", 24);
+
+        assert!(check_payment_limit("BUDGETA", "1000").is_ok());
+        record_spend("BUDGETA", "1000", Utc::now());
+
+        let err = check_payment_limit("BUDGETA", "600").unwrap_err();
+        assert!(err.contains("budget"), "unexpected error: {}", err);
+        assert!(check_payment_limit("BUDGETA", "500").is_ok());
+
+        let status = budget_status("budgeta").unwrap();
+        assert_eq!(status.spent, "1000");
+        assert_eq!(status.remaining, "500");
+        assert_eq!(status.remaining_formatted, "5");
+    }
+
+    #[test]
+    fn test_spends_outside_window_are_ignored() {
+        set_limit("BUDGETB", "1000", 0, "BUDGETB", None);
+        set_budget("BUDGETB", "1000", 1);
+        record_spend("BUDGETB", "900", Utc::now() - Duration::hours(2));
+
+        assert!(check_payment_limit("BUDGETB", "1000").is_ok());
+        assert_eq!(budget_status("BUDGETB").unwrap().spent, "0");
+    }
+
+    #[test]
+    fn test_spend_by_address_counts_against_symbol_budget() {
+        let addr = "0x00000000000000000000000000000000000000b3";
+        set_limit("BUDGETC", "1000", 0, "BUDGETC", Some(addr));
+        set_budget("BUDGETC", "1000", 24);
+        record_spend(addr, "700", Utc::now());
+
+        assert_eq!(budget_status("BUDGETC").unwrap().spent, "700");
+        assert!(check_payment_limit(addr, "400").is_err());
+    }
+
+    #[test]
+    fn test_reservations_hold_budget_until_released() {
+        set_limit("BUDGETE", "1000", 0, "BUDGETE", None);
+        set_budget("BUDGETE", "1000", 24);
+
+        let first = reserve_spend("BUDGETE", "600").unwrap();
+        // The reservation already counts, so a second payment can't also fit
+        assert!(reserve_spend("BUDGETE", "600").unwrap_err().contains("budget"));
+        assert_eq!(budget_status("BUDGETE").unwrap().spent, "600");
+
+        // A failed payment gives its amount back
+        drop(first);
+        assert_eq!(budget_status("BUDGETE").unwrap().spent, "0");
+
+        reserve_spend("BUDGETE", "600").unwrap().commit();
+        assert_eq!(budget_status("BUDGETE").unwrap().spent, "600");
+        assert!(reserve_spend("BUDGETE", "400").is_ok());
+    }
+
+    #[test]
+    fn test_concurrent_reservations_never_overspend() {
+        set_limit("BUDGETF", "100", 0, "BUDGETF", None);
+        set_budget("BUDGETF", "1000", 24);
+
+        let handles: Vec<_> = (0..32)
+            .map(|_| std::thread::spawn(|| reserve_spend("BUDGETF", "100").map(|r| r.commit()).is_ok()))
+            .collect();
+        let granted = handles.into_iter().map(|h| h.join().unwrap()).filter(|ok| *ok).count();
+
+        assert_eq!(granted, 10);
+        assert_eq!(budget_status("BUDGETF").unwrap().spent, "1000");
+    }
+
+    #[test]
+    fn test_no_budget_means_only_per_call_limit() {
+        set_limit("BUDGETD", "1000", 0, "BUDGETD", None);
+        record_spend("BUDGETD", "100000", Utc::now());
+        assert!(budget_status("BUDGETD").is_none());
+        assert!(check_payment_limit("BUDGETD", "1000").is_ok());
+    }
+}
@@ -244,7 +244,8 @@ pub struct X402PaymentInfo {
     pub amount: String,
     /// Human-readable amount (e.g., "0.001234")
     pub amount_formatted: String,
-    /// Asset symbol (e.g., "USDC")
+    /// Asset symbol (e.g., "USDC"); falls back to the contract address
+    /// when the token is unknown
     pub asset: String,
     /// Address that received the payment
     pub pay_to: String,
@@ -267,7 +268,9 @@ impl X402PaymentInfo {
         Self {
             amount: req.max_amount_required.clone(),
             amount_formatted,
-            asset: req.asset.clone(),
+            asset: super::payment_limits::known_asset_key(&req.asset)
+                .or_else(|| req.extra.as_ref().and_then(|e| e.token.clone()))
+                .unwrap_or_else(|| req.asset.clone()),
             pay_to: req.pay_to_address.clone(),
             resource: req.resource.clone(),
             tx_hash: None,
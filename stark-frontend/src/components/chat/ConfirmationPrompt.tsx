@@ -2,6 +2,7 @@ import { useState } from 'react';
 import { AlertTriangle, Check, X } from 'lucide-react';
 import UnicodeSpinner from '@/components/ui/UnicodeSpinner';
 import Button from '../ui/Button';
+import type { CostEstimate } from '@/types';
 
 export interface PendingConfirmation {
   confirmation_id: string;
@@ -9,6 +10,7 @@ export interface PendingConfirmation {
   tool_name: string;
   description: string;
   parameters: Record<string, unknown>;
+  estimated_cost?: CostEstimate | null;
   timestamp: string;
 }
 
@@ -70,6 +72,12 @@ export function ConfirmationPrompt({
   const ethValue = formatValue(value);
   const isContractCall = data && data !== '0x' && data.length > 2;
 
+  // Paid tool calls carry a cost estimate instead of transaction fields
+  const cost = confirmation.estimated_cost;
+  const subject = cost ? 'Tool call' : 'Transaction';
+  const formatCost = (c: CostEstimate) =>
+    `${c.is_upper_bound ? 'up to ' : ''}${c.amount.toFixed(2)} ${c.currency}`;
+
   if (resolved) {
     return (
       <div className={`rounded-lg p-4 border ${
@@ -81,12 +89,12 @@ export function ConfirmationPrompt({
           {resolved === 'confirmed' ? (
             <>
               <Check className="w-5 h-5 text-green-400" />
-              <span className="text-green-400 font-medium">Transaction confirmed</span>
+              <span className="text-green-400 font-medium">{subject} confirmed</span>
             </>
           ) : (
             <>
               <X className="w-5 h-5 text-slate-400" />
-              <span className="text-slate-400 font-medium">Transaction cancelled</span>
+              <span className="text-slate-400 font-medium">{subject} cancelled</span>
             </>
           )}
         </div>
@@ -100,38 +108,60 @@ export function ConfirmationPrompt({
       <div className="flex items-start gap-3">
         <AlertTriangle className="w-5 h-5 text-amber-400 flex-shrink-0 mt-0.5" />
         <div className="flex-1 min-w-0">
-          <h4 className="text-amber-400 font-medium">Transaction Confirmation Required</h4>
+          <h4 className="text-amber-400 font-medium">
+            {cost ? 'Cost Confirmation Required' : 'Transaction Confirmation Required'}
+          </h4>
           <p className="text-slate-300 text-sm mt-1">{confirmation.description}</p>
         </div>
       </div>
 
-      {/* Transaction Details */}
-      <div className="bg-slate-800/50 rounded-md p-3 space-y-2 text-sm">
-        <div className="flex justify-between">
-          <span className="text-slate-400">Network:</span>
-          <span className="text-slate-200 font-mono">{network}</span>
-        </div>
-        {to && (
+      {/* Cost Details */}
+      {cost && (
+        <div className="bg-slate-800/50 rounded-md p-3 space-y-2 text-sm">
           <div className="flex justify-between">
-            <span className="text-slate-400">To:</span>
-            <span className="text-slate-200 font-mono text-xs">
-              {to.slice(0, 10)}...{to.slice(-8)}
-            </span>
+            <span className="text-slate-400">Tool:</span>
+            <span className="text-slate-200 font-mono">{confirmation.tool_name}</span>
           </div>
-        )}
-        {ethValue && (
           <div className="flex justify-between">
-            <span className="text-slate-400">Value:</span>
-            <span className="text-slate-200">{ethValue} ETH</span>
+            <span className="text-slate-400">Estimated cost:</span>
+            <span className="text-slate-200">{formatCost(cost)}</span>
           </div>
-        )}
-        {isContractCall && (
+          <div className="flex justify-between gap-4">
+            <span className="text-slate-400">For:</span>
+            <span className="text-slate-200 text-right">{cost.description}</span>
+          </div>
+        </div>
+      )}
+
+      {/* Transaction Details */}
+      {!cost && (
+        <div className="bg-slate-800/50 rounded-md p-3 space-y-2 text-sm">
           <div className="flex justify-between">
-            <span className="text-slate-400">Type:</span>
-            <span className="text-slate-200">Contract Call</span>
+            <span className="text-slate-400">Network:</span>
+            <span className="text-slate-200 font-mono">{network}</span>
           </div>
-        )}
-      </div>
+          {to && (
+            <div className="flex justify-between">
+              <span className="text-slate-400">To:</span>
+              <span className="text-slate-200 font-mono text-xs">
+                {to.slice(0, 10)}...{to.slice(-8)}
+              </span>
+            </div>
+          )}
+          {ethValue && (
+            <div className="flex justify-between">
+              <span className="text-slate-400">Value:</span>
+              <span className="text-slate-200">{ethValue} ETH</span>
+            </div>
+          )}
+          {isContractCall && (
+            <div className="flex justify-between">
+              <span className="text-slate-400">Type:</span>
+              <span className="text-slate-200">Contract Call</span>
+            </div>
+          )}
+        </div>
+      )}
 
       {/* Action Buttons */}
       <div className="flex gap-3 pt-1">
@@ -0,0 +1,82 @@
+import { useState } from 'react';
+import { ListChecks, Check, X } from 'lucide-react';
+import UnicodeSpinner from '@/components/ui/UnicodeSpinner';
+import Button from '../ui/Button';
+import type { PlanApprovalRequiredEvent } from '@/types';
+
+interface PlanApprovalPromptProps {
+  plan: PlanApprovalRequiredEvent;
+  onResolve: (approved: boolean) => Promise<void>;
+}
+
+export function PlanApprovalPrompt({ plan, onResolve }: PlanApprovalPromptProps) {
+  const [isLoading, setIsLoading] = useState<'approve' | 'reject' | null>(null);
+
+  const handle = async (approved: boolean) => {
+    setIsLoading(approved ? 'approve' : 'reject');
+    try {
+      await onResolve(approved);
+    } catch (error) {
+      console.error('Failed to resolve plan:', error);
+      setIsLoading(null);
+    }
+  };
+
+  const minutes = Math.ceil(plan.timeout_secs / 60);
+
+  return (
+    <div className="rounded-lg bg-sky-500/10 border border-sky-500/30 p-4 space-y-3">
+      {/* Header */}
+      <div className="flex items-start gap-3">
+        <ListChecks className="w-5 h-5 text-sky-400 flex-shrink-0 mt-0.5" />
+        <div className="flex-1 min-w-0">
+          <h4 className="text-sky-400 font-medium">Approve Plan</h4>
+          <p className="text-slate-300 text-sm mt-1">The agent will work through these tasks once you approve.</p>
+        </div>
+      </div>
+
+      {/* Planned Tasks */}
+      <ol className="bg-slate-800/50 rounded-md p-3 space-y-1 text-sm list-decimal list-inside">
+        {plan.tasks.map((task) => (
+          <li key={task.id} className="text-slate-200">{task.description}</li>
+        ))}
+      </ol>
+
+      {/* Action Buttons */}
+      <div className="flex gap-3 pt-1">
+        <Button
+          onClick={() => handle(true)}
+          disabled={isLoading !== null}
+          className="flex-1 bg-green-600 hover:bg-green-700 text-white"
+        >
+          {isLoading === 'approve' ? (
+            <UnicodeSpinner animation="orbit" size="sm" className="mr-2" />
+          ) : (
+            <Check className="w-4 h-4 mr-2" />
+          )}
+          Approve
+        </Button>
+        <Button
+          onClick={() => handle(false)}
+          disabled={isLoading !== null}
+          variant="secondary"
+          className="flex-1"
+        >
+          {isLoading === 'reject' ? (
+            <UnicodeSpinner animation="orbit" size="sm" className="mr-2" />
+          ) : (
+            <X className="w-4 h-4 mr-2" />
+          )}
+          Reject
+        </Button>
+      </div>
+
+      {/* Timeout notice */}
+      <p className="text-xs text-slate-500 text-center">
+        {plan.on_timeout === 'proceed'
+          ? `The plan runs automatically if not answered within ${minutes} min`
+          : `The plan is dropped if not answered within ${minutes} min`}
+      </p>
+    </div>
+  );
+}
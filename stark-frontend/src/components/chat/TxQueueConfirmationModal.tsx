@@ -57,6 +57,17 @@ export interface TxQueueTransaction {
   value_formatted: string;
   /** Hex-encoded calldata for function selector lookup */
   data?: string;
+  /** Pre-queue eth_call simulation result */
+  simulation?: TxSimulation | null;
+  /** When the transaction is auto-rejected if not confirmed (RFC 3339) */
+  expires_at?: string | null;
+}
+
+export interface TxSimulation {
+  status: 'success' | 'reverted' | 'unavailable';
+  return_data?: string | null;
+  error?: string | null;
+  simulated_at: string;
 }
 
 // Get Tenderly simulation URL
@@ -255,6 +266,20 @@ export default function TxQueueConfirmationModal({
   const [functionInfo, setFunctionInfo] = useState<FunctionInfo | null>(null);
   const [decodedFunction, setDecodedFunction] = useState<DecodedFunction | null>(null);
   const [showRawCalldata, setShowRawCalldata] = useState(false);
+  const [secondsLeft, setSecondsLeft] = useState<number | null>(null);
+
+  // Count down to the confirmation deadline
+  useEffect(() => {
+    if (!transaction?.expires_at) {
+      setSecondsLeft(null);
+      return;
+    }
+    const deadline = new Date(transaction.expires_at).getTime();
+    const tick = () => setSecondsLeft(Math.max(0, Math.round((deadline - Date.now()) / 1000)));
+    tick();
+    const timer = setInterval(tick, 1000);
+    return () => clearInterval(timer);
+  }, [transaction?.expires_at]);
 
   // Auto-clear error after 3 seconds
   useEffect(() => {
@@ -326,9 +351,11 @@ export default function TxQueueConfirmationModal({
     setIsLoading('confirm');
     setError(null);
     try {
+      // Same token for every confirm of this tx, so a double submit broadcasts once
       await getGateway().call('tx_queue.confirm', {
         uuid: transaction.uuid,
-        channel_id: channelId
+        channel_id: channelId,
+        idempotency_token: `confirm:${transaction.uuid}`
       });
       onClose();
     } catch (err) {
@@ -513,6 +540,26 @@ export default function TxQueueConfirmationModal({
             </div>
           )}
 
+          {/* Pre-queue simulation result */}
+          {transaction.simulation && (
+            <div className="flex justify-between items-start gap-3">
+              <span className="text-slate-400">Simulation</span>
+              {transaction.simulation.status === 'success' ? (
+                <span className="bg-green-600/20 text-green-400 px-2 py-0.5 rounded text-xs font-medium">
+                  Succeeded
+                </span>
+              ) : transaction.simulation.status === 'reverted' ? (
+                <span className="text-red-400 text-xs text-right break-all">
+                  Will revert: {transaction.simulation.error || 'unknown reason'}
+                </span>
+              ) : (
+                <span className="text-slate-500 text-xs text-right break-all">
+                  Unavailable{transaction.simulation.error ? `: ${transaction.simulation.error}` : ''}
+                </span>
+              )}
+            </div>
+          )}
+
           {/* Tenderly Simulation Link */}
           {transaction.from && (
             <div className="pt-2 border-t border-slate-600">
@@ -540,6 +587,12 @@ export default function TxQueueConfirmationModal({
           <div className="text-red-400 text-sm bg-red-900/20 p-2 rounded">{error}</div>
         )}
 
+        {secondsLeft !== null && (
+          <p className={`text-xs text-center ${secondsLeft <= 30 ? 'text-red-400' : 'text-slate-500'}`}>
+            Rejected automatically in {Math.floor(secondsLeft / 60)}:{String(secondsLeft % 60).padStart(2, '0')}
+          </p>
+        )}
+
         <div className="flex gap-3 pt-2">
           <Button
             onClick={handleConfirm}
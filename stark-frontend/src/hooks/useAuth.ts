@@ -1,12 +1,22 @@
 import { useState, useEffect, useCallback, useRef } from 'react';
 import { useNavigate } from 'react-router-dom';
-import { validateToken, logout as apiLogout } from '@/lib/api';
+import {
+  validateToken,
+  refreshSession,
+  storeSession,
+  clearSession,
+  logout as apiLogout,
+} from '@/lib/api';
+
+// Refresh the session when less than this much time remains
+const REFRESH_THRESHOLD_SECS = 30 * 60;
+const REFRESH_CHECK_INTERVAL_MS = 60 * 1000;
 
 interface UseAuthReturn {
   token: string | null;
   isLoading: boolean;
   isAuthenticated: boolean;
-  login: (token: string) => void;
+  login: (token: string, expiresAt?: number) => void;
   logout: () => Promise<void>;
 }
 
@@ -36,10 +46,11 @@ export function useAuth(): UseAuthReturn {
       try {
         const result = await validateToken();
         if (result.valid) {
+          storeSession(storedToken, result.expires_at);
           setToken(storedToken);
           setIsAuthenticated(true);
         } else {
-          localStorage.removeItem('stark_token');
+          clearSession();
           setToken(null);
           setIsAuthenticated(false);
         }
@@ -58,8 +69,26 @@ export function useAuth(): UseAuthReturn {
     checkAuth();
   }, []);
 
-  const login = useCallback((newToken: string) => {
-    localStorage.setItem('stark_token', newToken);
+  // Keep the session alive while the app is open
+  useEffect(() => {
+    if (!isAuthenticated) return;
+
+    const interval = setInterval(() => {
+      const expiresAt = Number(localStorage.getItem('stark_token_expires_at'));
+      if (!expiresAt) return;
+      const remaining = expiresAt - Date.now() / 1000;
+      if (remaining > REFRESH_THRESHOLD_SECS) return;
+
+      refreshSession()
+        .then((session) => setToken(session.token))
+        .catch((err) => console.error('Failed to refresh session:', err));
+    }, REFRESH_CHECK_INTERVAL_MS);
+
+    return () => clearInterval(interval);
+  }, [isAuthenticated]);
+
+  const login = useCallback((newToken: string, expiresAt?: number) => {
+    storeSession(newToken, expiresAt);
     setToken(newToken);
     setIsAuthenticated(true);
     navigate('/dashboard');
@@ -71,7 +100,7 @@ export function useAuth(): UseAuthReturn {
     } catch {
       // Ignore logout errors
     }
-    localStorage.removeItem('stark_token');
+    clearSession();
     setToken(null);
     setIsAuthenticated(false);
     navigate('/');
@@ -45,11 +45,56 @@ export async function validateAuth(
   return { token: data.token, expires_at: data.expires_at };
 }
 
-export async function validateToken(): Promise<{ valid: boolean }> {
+export async function validateToken(): Promise<{
+  valid: boolean;
+  code?: string;
+  expires_at?: number;
+}> {
   return apiFetch('/auth/validate');
 }
 
+// Store a session token and its expiry (unix seconds)
+export function storeSession(token: string, expiresAt?: number): void {
+  localStorage.setItem('stark_token', token);
+  if (expiresAt) {
+    localStorage.setItem('stark_token_expires_at', String(expiresAt));
+  } else {
+    localStorage.removeItem('stark_token_expires_at');
+  }
+}
+
+export function clearSession(): void {
+  localStorage.removeItem('stark_token');
+  localStorage.removeItem('stark_token_expires_at');
+}
+
+let refreshInFlight: Promise<{ token: string; expires_at: number }> | null = null;
+
+// Exchange the current token for a fresh one. Concurrent callers share one request,
+// since the old token is invalidated once the refresh succeeds.
+export function refreshSession(): Promise<{ token: string; expires_at: number }> {
+  if (refreshInFlight) return refreshInFlight;
+
+  refreshInFlight = (async () => {
+    const token = localStorage.getItem('stark_token');
+    const response = await fetch(`${API_BASE}/auth/refresh`, {
+      method: 'POST',
+      headers: { Authorization: `Bearer ${token}` },
+    });
+    const data = await response.json();
+    if (!response.ok || !data.success) {
+      throw new Error(data.error || 'Failed to refresh session');
+    }
+    storeSession(data.token, data.expires_at);
+    return { token: data.token, expires_at: data.expires_at };
+  })().finally(() => {
+    refreshInFlight = null;
+  });
+
+  return refreshInFlight;
+}
+
 export async function logout(): Promise<void> {
   await apiFetch('/auth/logout', { method: 'POST' });
-  localStorage.removeItem('stark_token');
+  clearSession();
 }
@@ -129,6 +129,14 @@ export async function stopChannel(id: number): Promise<ChannelInfo> {
   return response.channel;
 }
 
+// Abort the agent's current run on a channel; resolves false if nothing was running
+export async function cancelChannelDispatch(id: number): Promise<boolean> {
+  const response = await apiFetch<{ success: boolean; cancelled: boolean }>(`/channels/${id}/cancel`, {
+    method: 'POST',
+  });
+  return response.cancelled;
+}
+
 // Channel Settings API
 export async function getChannelSettingsSchema(channelType: string): Promise<ChannelSettingDefinition[]> {
   const response = await apiFetch<ChannelSettingsSchemaResponse>(`/channels/settings/schema/${channelType}`);
@@ -150,3 +158,43 @@ export async function updateChannelSettings(
   });
   return response.settings || [];
 }
+
+// Message history export (moderation)
+export interface ChannelMessage {
+  id: number;
+  channel_id: number;
+  chat_id: string;
+  chat_name: string | null;
+  user_id: string;
+  user_name: string;
+  content: string | null;
+  platform_message_id: string | null;
+  created_at: string;
+}
+
+export interface ChannelMessagesExport {
+  success: boolean;
+  channel_id: number;
+  messages: ChannelMessage[];
+  truncated: boolean;
+  content_redacted: boolean;
+  error?: string;
+}
+
+export async function exportChannelMessages(
+  channelId: number,
+  range: { from?: string; to?: string; limit?: number } = {}
+): Promise<ChannelMessagesExport> {
+  const params = new URLSearchParams();
+  if (range.from) params.set('from', range.from);
+  if (range.to) params.set('to', range.to);
+  if (range.limit) params.set('limit', String(range.limit));
+  const query = params.toString();
+  const response = await apiFetch<ChannelMessagesExport>(
+    `/channels/${channelId}/messages/export${query ? `?${query}` : ''}`
+  );
+  if (!response.success) {
+    throw new Error(response.error || 'Failed to export channel messages');
+  }
+  return response;
+}
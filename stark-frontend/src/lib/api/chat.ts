@@ -61,6 +61,13 @@ export async function cancelTransaction(channelId: number): Promise<Confirmation
   });
 }
 
+export async function resolvePlanApproval(channelId: number, approved: boolean): Promise<ConfirmationResponse> {
+  return apiFetch('/confirmation/plan', {
+    method: 'POST',
+    body: JSON.stringify({ channel_id: channelId, approved }),
+  });
+}
+
 // Execution Control API
 export interface StopExecutionResponse {
   success: boolean;
@@ -1,5 +1,8 @@
 export const API_BASE = '/api';
 
+// Error code returned with a 401 when the session token has expired
+export const SESSION_EXPIRED_CODE = 'session_expired';
+
 // Config Status API (unauthenticated)
 export interface ConfigStatus {
   login_configured: boolean;
@@ -38,9 +41,12 @@ export async function apiFetch<T>(
 
   if (!response.ok) {
     if (response.status === 401) {
+      const body = await response.json().catch(() => null);
+      const expired = body?.code === SESSION_EXPIRED_CODE;
       localStorage.removeItem('stark_token');
-      window.location.href = '/';
-      throw new Error('Unauthorized');
+      localStorage.removeItem('stark_token_expires_at');
+      window.location.href = expired ? '/?expired=1' : '/';
+      throw new Error(expired ? 'Session expired' : 'Unauthorized');
     }
     const errorText = await response.text();
     throw new Error(errorText || `HTTP ${response.status}`);
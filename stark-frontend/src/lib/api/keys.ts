@@ -85,6 +85,8 @@ export interface BackupResponse {
   has_heartbeat?: boolean;
   has_soul?: boolean;
   backup_size_bytes?: number;
+  // Restore only: per-section counts of items that already existed locally
+  conflicts?: Record<string, { overwritten: number; skipped: number; renamed: number }>;
   message?: string;
   error?: string;
 }
@@ -138,6 +140,31 @@ export async function restoreKeysFromCloud(): Promise<BackupResponse> {
   return response;
 }
 
+// Encrypted backup file, for restoring without the keystore server
+export async function downloadBackupFile(): Promise<Blob> {
+  const response = await fetch('/api/keys/backup_file', {
+    headers: {
+      Authorization: `Bearer ${localStorage.getItem('stark_token')}`,
+    },
+  });
+  if (!response.ok) {
+    const body = await response.json().catch(() => null);
+    throw new Error(body?.error || 'Failed to download backup file');
+  }
+  return response.blob();
+}
+
+export async function restoreFromBackupFile(fileContents: string): Promise<BackupResponse> {
+  const response = await apiFetch<BackupResponse>('/keys/restore_file', {
+    method: 'POST',
+    body: fileContents,
+  });
+  if (!response.success) {
+    throw new Error(response.error || 'Failed to restore backup file');
+  }
+  return response;
+}
+
 export async function previewCloudBackup(): Promise<CloudBackupPreview> {
   const response = await apiFetch<CloudBackupPreview>('/keys/cloud_preview', {
     method: 'GET',
@@ -45,7 +45,14 @@ export interface SearchResultItem {
   file_path: string;
   title: string;
   tags: string;
+  // Excerpt with matched terms wrapped in >>> and <<<
   snippet: string;
+  score: number;
+}
+
+export interface SearchNotesFilters {
+  tag?: string;
+  type?: string;
 }
 
 export interface TagItem {
@@ -80,12 +87,57 @@ export async function readNoteFile(path: string): Promise<ReadNoteResponse> {
   return apiFetch(`/notes/read?path=${encodeURIComponent(path)}`);
 }
 
-export async function searchNotes(q: string, limit?: number): Promise<SearchNotesResponse> {
+export async function searchNotes(
+  q: string,
+  limit?: number,
+  filters?: SearchNotesFilters
+): Promise<SearchNotesResponse> {
   const params = new URLSearchParams({ q });
   if (limit) params.set('limit', String(limit));
+  if (filters?.tag) params.set('tag', filters.tag);
+  if (filters?.type) params.set('type', filters.type);
   return apiFetch(`/notes/search?${params.toString()}`);
 }
 
+export interface NoteFieldMatch {
+  file_path: string;
+  title: string;
+  tags: string[];
+  fields: Record<string, string>;
+}
+
+export interface QueryNotesResponse {
+  success: boolean;
+  notes: NoteFieldMatch[];
+  error?: string;
+}
+
+// Query notes by frontmatter fields, e.g. { tag: 'blog', status: 'draft' }
+export async function queryNotes(
+  conditions: Record<string, string>,
+  limit?: number
+): Promise<QueryNotesResponse> {
+  const params = new URLSearchParams(conditions);
+  if (limit) params.set('limit', String(limit));
+  return apiFetch(`/notes/query?${params.toString()}`);
+}
+
+export interface NoteLinkGraph {
+  file_path: string;
+  outbound: { link: string; file_path: string | null; broken: boolean }[];
+  inbound: { file_path: string; title: string; link: string }[];
+}
+
+export interface NoteLinksResponse {
+  success: boolean;
+  graph?: NoteLinkGraph;
+  error?: string;
+}
+
+export async function getNoteLinks(path: string): Promise<NoteLinksResponse> {
+  return apiFetch(`/notes/links?path=${encodeURIComponent(path)}`);
+}
+
 export async function getNotesInfo(): Promise<NotesInfoResponse> {
   return apiFetch('/notes/info');
 }
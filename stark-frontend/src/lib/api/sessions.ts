@@ -12,6 +12,7 @@ export async function getSessions(): Promise<Array<{
   updated_at: string;
   message_count?: number;
   initial_query?: string;
+  title?: string;
   safe_mode?: boolean;
   scope?: string;
 }>> {
@@ -29,6 +30,7 @@ export async function getSession(id: number): Promise<{
   updated_at: string;
   message_count?: number;
   initial_query?: string;
+  title?: string;
   safe_mode?: boolean;
   scope?: string;
 }> {
@@ -31,6 +31,13 @@ export interface BotSettings {
   kanban_auto_execute: boolean;
   whisper_server_url?: string;
   embeddings_server_url?: string;
+  gas_strategy: string;
+  gas_max_fee_gwei?: number | null;
+  tx_confirmation_timeout_secs: number;
+  command_prefix: string;
+  command_aliases?: Record<string, string> | null;
+  gateway_previous_messages: number;
+  memory_fts_tokenizer: string;
   compaction_background_threshold: number;
   compaction_aggressive_threshold: number;
   compaction_emergency_threshold: number;
@@ -62,6 +69,13 @@ export async function updateBotSettings(data: {
   kanban_auto_execute?: boolean;
   whisper_server_url?: string;
   embeddings_server_url?: string;
+  gas_strategy?: string;
+  gas_max_fee_gwei?: number;
+  tx_confirmation_timeout_secs?: number;
+  command_prefix?: string;
+  command_aliases?: Record<string, string>;
+  gateway_previous_messages?: number;
+  memory_fts_tokenizer?: string;
 }): Promise<BotSettings> {
   return apiFetch('/bot-settings', {
     method: 'PUT',
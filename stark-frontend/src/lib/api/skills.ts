@@ -25,6 +25,8 @@ export interface SkillDetail {
   requires_tools: string[];
   requires_binaries: string[];
   missing_binaries: string[];
+  missing_tools: string[];
+  missing_api_keys: string[];
   tags: string[];
   arguments: Array<{ name: string; description: string; required: boolean; default?: string }>;
   prompt_template: string;
@@ -33,6 +35,12 @@ export interface SkillDetail {
   metadata?: string;
 }
 
+export interface SkillDependencyReport {
+  missing_tools: string[];
+  missing_binaries: string[];
+  missing_api_keys: string[];
+}
+
 export interface SkillDetailResponse {
   success: boolean;
   skill?: SkillDetail;
@@ -58,7 +66,8 @@ export async function updateSkillBody(name: string, body: string): Promise<void>
   });
 }
 
-export async function uploadSkill(file: File): Promise<void> {
+// Resolves to the skill's unmet dependencies when it was installed with warnings
+export async function uploadSkill(file: File): Promise<SkillDependencyReport | undefined> {
   const token = localStorage.getItem('stark_token');
   const formData = new FormData();
   formData.append('file', file);
@@ -69,9 +78,90 @@ export async function uploadSkill(file: File): Promise<void> {
     body: formData,
   });
 
-  if (!response.ok) {
-    throw new Error('Failed to upload skill');
+  const data = await response.json().catch(() => ({}));
+  if (!response.ok || !data.success) {
+    throw new Error(data.error || 'Failed to upload skill');
+  }
+  return data.dependencies;
+}
+
+export interface SkillVersionInfo {
+  id: number;
+  version: string;
+  description: string;
+  body: string;
+  arguments: string[];
+  scripts: string[];
+  created_at: string;
+}
+
+export async function getSkillVersions(name: string): Promise<SkillVersionInfo[]> {
+  const response = await apiFetch<{ success: boolean; versions: SkillVersionInfo[]; error?: string }>(
+    `/skills/${encodeURIComponent(name)}/versions`
+  );
+  if (!response.success) {
+    throw new Error(response.error || 'Failed to load skill versions');
+  }
+  return response.versions;
+}
+
+export async function rollbackSkill(name: string, versionId: number): Promise<SkillDetail> {
+  const response = await apiFetch<SkillDetailResponse>(`/skills/${encodeURIComponent(name)}/rollback`, {
+    method: 'POST',
+    body: JSON.stringify({ version_id: versionId }),
+  });
+  if (!response.success || !response.skill) {
+    throw new Error(response.error || 'Failed to roll back skill');
+  }
+  return response.skill;
+}
+
+export interface SkillValidationIssue {
+  severity: 'error' | 'warning';
+  kind: string;
+  message: string;
+}
+
+export interface SkillValidationReport {
+  skill_name: string;
+  valid: boolean;
+  referenced_tools: string[];
+  scripts_checked: string[];
+  issues: SkillValidationIssue[];
+}
+
+export async function validateSkill(name: string): Promise<SkillValidationReport> {
+  const response = await apiFetch<{ success: boolean; report?: SkillValidationReport; error?: string }>(
+    `/skills/${encodeURIComponent(name)}/validate`,
+    { method: 'POST' }
+  );
+  if (!response.success || !response.report) {
+    throw new Error(response.error || 'Failed to validate skill');
+  }
+  return response.report;
+}
+
+export interface SkillMetrics {
+  skill_name: string;
+  invocations: number;
+  tool_calls: number;
+  successes: number;
+  failures: number;
+  success_rate: number;
+  avg_duration_ms: number;
+  total_duration_ms: number;
+  last_used_at?: string;
+}
+
+export async function getSkillMetrics(sinceDays?: number): Promise<SkillMetrics[]> {
+  const query = sinceDays !== undefined ? `?since_days=${sinceDays}` : '';
+  const response = await apiFetch<{ success: boolean; metrics: SkillMetrics[]; error?: string }>(
+    `/skills/metrics${query}`
+  );
+  if (!response.success) {
+    throw new Error(response.error || 'Failed to load skill metrics');
   }
+  return response.metrics;
 }
 
 export async function deleteSkill(id: string): Promise<void> {
@@ -6,6 +6,7 @@ export interface SpecialRoleInfo {
   name: string;
   allowed_tools: string[];
   allowed_skills: string[];
+  denied_tools: string[];
   description: string | null;
   created_at: string;
   updated_at: string;
@@ -32,6 +33,7 @@ export async function createSpecialRole(role: {
   name: string;
   allowed_tools: string[];
   allowed_skills: string[];
+  denied_tools?: string[];
   description?: string;
 }): Promise<SpecialRoleInfo> {
   return apiFetch('/special-roles', {
@@ -45,6 +47,7 @@ export async function updateSpecialRole(
   update: {
     allowed_tools?: string[];
     allowed_skills?: string[];
+    denied_tools?: string[];
     description?: string | null;
   }
 ): Promise<SpecialRoleInfo> {
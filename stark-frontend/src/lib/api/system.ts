@@ -21,6 +21,15 @@ export interface SystemInfoResponse {
     percentage: number;
     breakdown: Record<string, number>;
   };
+  dispatch: {
+    max_concurrent: number | null;
+    overflow_policy: 'queue' | 'reject';
+    in_flight: number;
+    queued: number;
+    peak_in_flight: number;
+    rejected: number;
+  };
+  signing_frozen: boolean;
   uptime_secs: number;
   version: string;
 }
@@ -49,3 +58,10 @@ export async function cleanupWorkspace(): Promise<CleanupResult> {
     body: JSON.stringify({ confirm: true }),
   });
 }
+
+export async function setSigningFrozen(frozen: boolean): Promise<{ frozen: boolean }> {
+  return apiFetch('/system/signing-freeze', {
+    method: 'PUT',
+    body: JSON.stringify({ frozen }),
+  });
+}
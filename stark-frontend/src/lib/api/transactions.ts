@@ -10,7 +10,12 @@ export interface QueuedTransactionInfo {
   value_formatted: string;
   /** Hex-encoded calldata for function selector lookup */
   data: string;
-  status: 'pending' | 'broadcasting' | 'broadcast' | 'confirmed' | 'failed' | 'expired';
+  status: 'pending' | 'broadcasting' | 'broadcast' | 'confirmed' | 'failed' | 'expired' | 'stuck' | 'replaced';
+  nonce: number;
+  /** UUID of the stuck transaction this one replaces */
+  replaces?: string;
+  /** UUID of the gas-bumped transaction that replaced this one */
+  replaced_by?: string;
   tx_hash?: string;
   explorer_url?: string;
   error?: string;
@@ -25,6 +30,7 @@ export interface QueuedTransactionsResponse {
   pending_count: number;
   confirmed_count: number;
   failed_count: number;
+  stuck_count: number;
 }
 
 export interface QueuedTransactionResponse {
@@ -49,6 +55,14 @@ export async function getQueuedTransaction(uuid: string): Promise<QueuedTransact
   return apiFetch(`/tx-queue/${encodeURIComponent(uuid)}`);
 }
 
+export async function replaceQueuedTransaction(uuid: string): Promise<QueuedTransactionResponse> {
+  // One replacement per stuck tx: a repeated click returns the replacement already sent
+  return apiFetch(`/tx-queue/${encodeURIComponent(uuid)}/replace`, {
+    method: 'POST',
+    headers: { 'Idempotency-Key': `replace:${uuid}` },
+  });
+}
+
 // Broadcasted Transactions API (persistent history)
 export interface BroadcastedTransactionInfo {
   id: number;
@@ -60,7 +74,9 @@ export interface BroadcastedTransactionInfo {
   value_formatted: string;
   tx_hash?: string;
   explorer_url?: string;
-  status: 'broadcast' | 'confirmed' | 'failed';
+  status: 'broadcast' | 'confirmed' | 'failed' | 'stuck' | 'replaced';
+  nonce?: number;
+  replaced_by?: string;
   broadcast_mode: 'rogue' | 'partner';
   error?: string;
   broadcast_at: string;
@@ -89,6 +105,22 @@ export async function getBroadcastedTransactions(params?: {
   return apiFetch(`/broadcasted-transactions${query ? `?${query}` : ''}`);
 }
 
+export interface BroadcastHistoryStatsResponse {
+  success: boolean;
+  stats: {
+    total: number;
+    in_flight: number;
+    pruned_count: number;
+    last_pruned_at?: string;
+  };
+  retention_days?: number;
+  max_rows?: number;
+}
+
+export async function getBroadcastHistoryStats(): Promise<BroadcastHistoryStatsResponse> {
+  return apiFetch('/broadcasted-transactions/stats');
+}
+
 // x402 Payment Limits API
 export interface X402PaymentLimit {
   asset: string;
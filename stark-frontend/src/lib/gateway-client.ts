@@ -21,6 +21,10 @@ export class GatewayClient {
   private connectionPromise: Promise<void> | null = null;
   private connectionResolve: (() => void) | null = null;
   private authenticated = false;
+  // Sequence number of the last event received, sent on reconnect to replay the gap
+  private lastSeq = 0;
+  // Server-side event filter (names or "prefix.*"); null = all events
+  private eventFilter: string[] | null = null;
 
   constructor(url?: string) {
     if (url) {
@@ -119,7 +123,11 @@ export class GatewayClient {
       jsonrpc: '2.0',
       id,
       method: 'auth',
-      params: { token },
+      params: {
+        token,
+        ...(this.lastSeq > 0 ? { last_seq: this.lastSeq } : {}),
+        ...(this.eventFilter ? { events: this.eventFilter } : {}),
+      },
     };
 
     return new Promise((resolve, reject) => {
@@ -137,6 +145,11 @@ export class GatewayClient {
             if (message.error) {
               reject(new Error(message.error.message || 'Authentication failed'));
             } else if (message.result?.authenticated) {
+              // The backend restarted and its sequence began again: forget ours
+              // before the replayed events arrive
+              if (typeof message.result.seq === 'number' && message.result.seq < this.lastSeq) {
+                this.lastSeq = 0;
+              }
               resolve();
             } else {
               reject(new Error('Unexpected auth response'));
@@ -158,6 +171,13 @@ export class GatewayClient {
 
       // Handle server events
       if (message.type === 'event' && message.event) {
+        if (message.seq) {
+          // Replays can overlap events already seen
+          if (message.seq <= this.lastSeq) {
+            return;
+          }
+          this.lastSeq = message.seq;
+        }
         this.emitEvent(message.event, message.data);
         return;
       }
@@ -205,6 +225,14 @@ export class GatewayClient {
     });
   }
 
+  /** Only receive the given event kinds (e.g. ['agent.response', 'agent.error', 'tool.*']); null = all */
+  async setEventFilter(events: string[] | null): Promise<void> {
+    this.eventFilter = events;
+    if (this.isConnected()) {
+      await this.call('events.subscribe', { events });
+    }
+  }
+
   on(event: string, callback: EventCallback): void {
     if (event === '*') {
       this.wildcardListeners.add(callback);
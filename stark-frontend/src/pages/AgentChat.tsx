@@ -11,14 +11,15 @@ import CommandAutocomplete from '@/components/chat/CommandAutocomplete';
 import CommandMenu from '@/components/chat/CommandMenu';
 import TransactionTracker from '@/components/chat/TransactionTracker';
 import { ConfirmationPrompt } from '@/components/chat/ConfirmationPrompt';
-import TxQueueConfirmationModal, { TxQueueTransaction } from '@/components/chat/TxQueueConfirmationModal';
+import { PlanApprovalPrompt } from '@/components/chat/PlanApprovalPrompt';
+import TxQueueConfirmationModal, { TxQueueTransaction, TxSimulation } from '@/components/chat/TxQueueConfirmationModal';
 import SubagentBadge from '@/components/chat/SubagentBadge';
 import { Subagent, SubagentStatus } from '@/lib/subagent-types';
 import { useGateway } from '@/hooks/useGateway';
 import { useWallet, SUPPORTED_NETWORKS, type SupportedNetwork } from '@/hooks/useWallet';
-import { sendChatMessage, getAgentSettings, getSkills, getTools, confirmTransaction, cancelTransaction, stopExecution, listSubagents, getActiveWebSession, getSessionTranscript, getExecutionStatus, createNewWebSession, getPlannerTasks, getAgentSubtypes, AgentSubtypeInfo, transcribeAudio } from '@/lib/api';
+import { sendChatMessage, getAgentSettings, getSkills, getTools, confirmTransaction, cancelTransaction, resolvePlanApproval, stopExecution, listSubagents, getActiveWebSession, getSessionTranscript, getExecutionStatus, createNewWebSession, getPlannerTasks, getAgentSubtypes, AgentSubtypeInfo, transcribeAudio } from '@/lib/api';
 import { Command, COMMAND_DEFINITIONS, getAllCommands } from '@/lib/commands';
-import type { ChatMessage as ChatMessageType, MessageRole, SlashCommand, TrackedTransaction, TxPendingEvent, TxConfirmedEvent, PendingConfirmation, ConfirmationRequiredEvent, PlannerTask, TaskQueueUpdateEvent, TaskStatusChangeEvent } from '@/types';
+import type { ChatMessage as ChatMessageType, MessageRole, SlashCommand, TrackedTransaction, TxPendingEvent, TxConfirmedEvent, PendingConfirmation, ConfirmationRequiredEvent, PlanApprovalRequiredEvent, PlannerTask, TaskQueueUpdateEvent, TaskStatusChangeEvent } from '@/types';
 
 interface ConversationMessage {
   role: string;
@@ -113,6 +114,7 @@ export default function AgentChat() {
   const [copied, setCopied] = useState(false);
   const [trackedTxs, setTrackedTxs] = useState<TrackedTransaction[]>([]);
   const [pendingConfirmation, setPendingConfirmation] = useState<PendingConfirmation | null>(null);
+  const [pendingPlan, setPendingPlan] = useState<PlanApprovalRequiredEvent | null>(null);
   const [isRecording, setIsRecording] = useState(false);
   const [isTranscribing, setIsTranscribing] = useState(false);
   const mediaRecorderRef = useRef<MediaRecorder | null>(null);
@@ -790,6 +792,7 @@ export default function AgentChat() {
         tool_name: event.tool_name,
         description: event.description,
         parameters: event.parameters,
+        estimated_cost: event.estimated_cost,
         timestamp: event.timestamp,
       });
     };
@@ -821,6 +824,27 @@ export default function AgentChat() {
     };
   }, [on, off, dbSessionId]);
 
+  // Listen for plan approval events
+  useEffect(() => {
+    const handlePlanApprovalRequired = (data: unknown) => {
+      if (!isCurrentSessionEvent(data, dbSessionId)) return;
+      setPendingPlan(data as PlanApprovalRequiredEvent);
+    };
+
+    const handlePlanApprovalResolved = (data: unknown) => {
+      if (!isCurrentSessionEvent(data, dbSessionId)) return;
+      setPendingPlan(null);
+    };
+
+    on('plan.approval_required', handlePlanApprovalRequired);
+    on('plan.approval_resolved', handlePlanApprovalResolved);
+
+    return () => {
+      off('plan.approval_required', handlePlanApprovalRequired);
+      off('plan.approval_resolved', handlePlanApprovalResolved);
+    };
+  }, [on, off, dbSessionId]);
+
   // Listen for tx_queue confirmation events (partner mode)
   useEffect(() => {
     const handleTxQueueConfirmationRequired = (data: unknown) => {
@@ -840,6 +864,8 @@ export default function AgentChat() {
         value: string;
         value_formatted: string;
         data?: string;
+        simulation?: TxSimulation | null;
+        expires_at?: string | null;
       };
       console.log('[TxQueue] Confirmation required:', event.uuid, 'channel_id:', event.channel_id);
 
@@ -854,6 +880,8 @@ export default function AgentChat() {
           value: event.value,
           value_formatted: event.value_formatted,
           data: event.data,
+          simulation: event.simulation,
+          expires_at: event.expires_at,
         });
       } else {
         console.log('[TxQueue] Wrong channel_id, expected', WEB_CHANNEL_ID, 'got', event.channel_id);
@@ -869,11 +897,13 @@ export default function AgentChat() {
     on('tx_queue.confirmation_required', handleTxQueueConfirmationRequired);
     on('tx_queue.confirmed', handleTxQueueResolved);
     on('tx_queue.denied', handleTxQueueResolved);
+    on('tx_queue.expired', handleTxQueueResolved);
 
     return () => {
       off('tx_queue.confirmation_required', handleTxQueueConfirmationRequired);
       off('tx_queue.confirmed', handleTxQueueResolved);
       off('tx_queue.denied', handleTxQueueResolved);
+      off('tx_queue.expired', handleTxQueueResolved);
     };
   }, [on, off, dbSessionId]);
 
@@ -2016,6 +2046,22 @@ export default function AgentChat() {
         </div>
       )}
 
+      {/* Plan Approval Prompt */}
+      {pendingPlan && (
+        <div className="mx-6 mb-4">
+          <PlanApprovalPrompt
+            plan={pendingPlan}
+            onResolve={async (approved) => {
+              const result = await resolvePlanApproval(pendingPlan.channel_id, approved);
+              if (!result.success) {
+                throw new Error(result.error || 'Failed to resolve plan');
+              }
+              setPendingPlan(null);
+            }}
+          />
+        </div>
+      )}
+
       {/* Transaction Queue Confirmation Modal (Partner Mode) */}
       <TxQueueConfirmationModal
         isOpen={txQueueConfirmation !== null}
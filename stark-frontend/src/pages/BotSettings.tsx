@@ -1,5 +1,5 @@
 import { useState, useEffect, FormEvent } from 'react';
-import { Save, Bot, Server, Shield, Cloud, AlertTriangle, CheckCircle, Info, XCircle, Copy, Check, Wallet, Brain, Palette, Globe, Minimize2, Radio } from 'lucide-react';
+import { Save, Bot, Server, Shield, Cloud, AlertTriangle, CheckCircle, Info, XCircle, Copy, Check, Wallet, Brain, Palette, Globe, Minimize2, Radio, Fuel, Terminal } from 'lucide-react';
 import Card, { CardContent, CardHeader, CardTitle } from '@/components/ui/Card';
 import Button from '@/components/ui/Button';
 import Input from '@/components/ui/Input';
@@ -31,6 +31,8 @@ export default function BotSettings() {
   const [safeModeMaxQueries, setSafeModeMaxQueries] = useState(5);
   const [keystoreUrl, setKeystoreUrl] = useState('');
   const [chatSessionMemoryGeneration, setChatSessionMemoryGeneration] = useState(true);
+  const [gatewayPreviousMessages, setGatewayPreviousMessages] = useState(6);
+  const [memoryFtsTokenizer, setMemoryFtsTokenizer] = useState('unicode61');
   const [guestDashboardEnabled, setGuestDashboardEnabled] = useState(false);
   const [autoSyncStatus, setAutoSyncStatus] = useState<AutoSyncStatus | null>(null);
   const [autoSyncDismissed, setAutoSyncDismissed] = useState(false);
@@ -40,6 +42,11 @@ export default function BotSettings() {
   const [proxyUrl, setProxyUrl] = useState('');
   const [whisperServerUrl, setWhisperServerUrl] = useState('');
   const [embeddingsServerUrl, setEmbeddingsServerUrl] = useState('');
+  const [gasStrategy, setGasStrategy] = useState('standard');
+  const [gasMaxFeeGwei, setGasMaxFeeGwei] = useState('');
+  const [txConfirmationTimeoutSecs, setTxConfirmationTimeoutSecs] = useState(300);
+  const [commandPrefix, setCommandPrefix] = useState('/');
+  const [commandAliases, setCommandAliases] = useState('');
   const [servicesHealth, setServicesHealth] = useState<ServicesHealth | null>(null);
   const [servicesHealthLoading, setServicesHealthLoading] = useState(false);
   const [compactionBackgroundThreshold, setCompactionBackgroundThreshold] = useState(0.80);
@@ -132,6 +139,17 @@ export default function BotSettings() {
       setProxyUrl(data.proxy_url || '');
       setWhisperServerUrl(data.whisper_server_url || '');
       setEmbeddingsServerUrl(data.embeddings_server_url || '');
+      setGasStrategy(data.gas_strategy || 'standard');
+      setGasMaxFeeGwei(data.gas_max_fee_gwei ? String(data.gas_max_fee_gwei) : '');
+      setTxConfirmationTimeoutSecs(data.tx_confirmation_timeout_secs ?? 300);
+      setCommandPrefix(data.command_prefix || '/');
+      setGatewayPreviousMessages(data.gateway_previous_messages ?? 6);
+      setMemoryFtsTokenizer(data.memory_fts_tokenizer || 'unicode61');
+      setCommandAliases(
+        Object.entries(data.command_aliases || {})
+          .map(([alias, command]) => `${alias}=${command}`)
+          .join(', ')
+      );
       setCompactionBackgroundThreshold(data.compaction_background_threshold ?? 0.80);
       setCompactionAggressiveThreshold(data.compaction_aggressive_threshold ?? 0.85);
       setCompactionEmergencyThreshold(data.compaction_emergency_threshold ?? 0.95);
@@ -178,6 +196,13 @@ export default function BotSettings() {
         polygon: customRpcPolygon,
       } : undefined;
 
+      // "alias=command" pairs, comma separated
+      const aliases: Record<string, string> = {};
+      for (const pair of commandAliases.split(',')) {
+        const [alias, command] = pair.split('=').map(s => s.trim());
+        if (alias && command) aliases[alias] = command;
+      }
+
       const updated = await updateBotSettings({
         bot_name: botName,
         bot_email: botEmail,
@@ -186,11 +211,19 @@ export default function BotSettings() {
         safe_mode_max_queries_per_10min: safeModeMaxQueries,
         keystore_url: keystoreUrl,
         chat_session_memory_generation: chatSessionMemoryGeneration,
+        gateway_previous_messages: gatewayPreviousMessages,
+        memory_fts_tokenizer: memoryFtsTokenizer,
         guest_dashboard_enabled: guestDashboardEnabled,
         theme_accent: themeAccent || '',
         proxy_url: proxyUrl,
         whisper_server_url: whisperServerUrl,
         embeddings_server_url: embeddingsServerUrl,
+        gas_strategy: gasStrategy,
+        // 0 clears the ceiling
+        gas_max_fee_gwei: parseFloat(gasMaxFeeGwei) || 0,
+        tx_confirmation_timeout_secs: txConfirmationTimeoutSecs,
+        command_prefix: commandPrefix,
+        command_aliases: aliases,
         compaction_background_threshold: compactionBackgroundThreshold,
         compaction_aggressive_threshold: compactionAggressiveThreshold,
         compaction_emergency_threshold: compactionEmergencyThreshold,
@@ -219,6 +252,12 @@ export default function BotSettings() {
         textClass: 'text-green-300',
         borderClass: 'border-green-500/30',
       },
+      backed_up: {
+        icon: <CheckCircle className="w-5 h-5 text-green-400" />,
+        bgClass: 'bg-green-500/10',
+        textClass: 'text-green-300',
+        borderClass: 'border-green-500/30',
+      },
       no_backup: {
         icon: <Info className="w-5 h-5 text-blue-400" />,
         bgClass: 'bg-blue-500/10',
@@ -248,6 +287,8 @@ export default function BotSettings() {
           <div className="flex-1">
             <div className={`font-medium ${config.textClass}`}>
               {autoSyncStatus.status === 'success' ? 'Cloud Backup Restored' :
+               autoSyncStatus.status === 'backed_up' ? 'Scheduled Backup Complete' :
+               autoSyncStatus.status === 'backup_error' ? 'Scheduled Backup Failed' :
                autoSyncStatus.status === 'no_backup' ? 'No Cloud Backup Found' :
                autoSyncStatus.status === 'server_error' ? 'Keystore Server Unreachable' :
                'Auto-Sync Error'}
@@ -514,6 +555,36 @@ export default function BotSettings() {
               When enabled, the user's input and the bot's final response are appended to the daily memory log
               when a chat session completes. Safe mode sessions are logged under the safemode identity.
             </p>
+            <Input
+              label="Previous Messages Carried Over (0-50)"
+              type="number"
+              min={0}
+              max={50}
+              value={gatewayPreviousMessages}
+              onChange={(e) => setGatewayPreviousMessages(Math.min(50, Math.max(0, parseInt(e.target.value) || 0)))}
+            />
+            <p className="text-xs text-slate-500 -mt-2">
+              Discord, Telegram, Matrix and web chats start a fresh session for each message. This many messages
+              from the previous session are included as context. Set to 0 to disable.
+            </p>
+            <div>
+              <label className="block text-sm font-medium text-slate-300 mb-2">
+                Memory Search Tokenizer
+              </label>
+              <select
+                value={memoryFtsTokenizer}
+                onChange={(e) => setMemoryFtsTokenizer(e.target.value)}
+                className="w-full px-3 py-2 bg-slate-800 border border-slate-700 rounded-lg text-white focus:border-stark-500 focus:outline-none"
+              >
+                <option value="unicode61">Unicode (Default)</option>
+                <option value="porter">Porter (English stemming)</option>
+                <option value="trigram">Trigram (Chinese, Japanese, Korean, Thai)</option>
+              </select>
+              <p className="text-xs text-slate-500 mt-1">
+                Trigram matches any 3+ character substring in any language, at the cost of a larger index and no
+                matches for shorter search terms. Changing this rebuilds the memory search index.
+              </p>
+            </div>
           </CardContent>
         </Card>
 
@@ -587,6 +658,93 @@ export default function BotSettings() {
           </CardContent>
         </Card>
 
+        {/* Chat Commands Section */}
+        <Card>
+          <CardHeader>
+            <CardTitle className="flex items-center gap-2">
+              <Terminal className="w-5 h-5 text-stark-400" />
+              Chat Commands
+            </CardTitle>
+          </CardHeader>
+          <CardContent className="space-y-4">
+            <Input
+              label="Command Prefix"
+              value={commandPrefix}
+              onChange={(e) => setCommandPrefix(e.target.value)}
+              placeholder="/"
+              maxLength={3}
+            />
+            <p className="text-xs text-slate-500 -mt-2">
+              Prefix for chat commands like new, reset, status and think. Use e.g. <code>!</code> on platforms where
+              <code> /</code> conflicts with native commands.
+            </p>
+            <Input
+              label="Command Aliases"
+              value={commandAliases}
+              onChange={(e) => setCommandAliases(e.target.value)}
+              placeholder="clear=reset, st=status"
+            />
+            <p className="text-xs text-slate-500 -mt-2">
+              Extra names for built-in commands as <code>alias=command</code> pairs, comma separated.
+              Valid commands: new, reset, status, think.
+            </p>
+          </CardContent>
+        </Card>
+
+        {/* Gas Strategy Section */}
+        <Card>
+          <CardHeader>
+            <CardTitle className="flex items-center gap-2">
+              <Fuel className="w-5 h-5 text-stark-400" />
+              Gas Strategy
+            </CardTitle>
+          </CardHeader>
+          <CardContent className="space-y-4">
+            <div>
+              <label className="block text-sm font-medium text-slate-300 mb-1">
+                Speed
+              </label>
+              <select
+                value={gasStrategy}
+                onChange={(e) => setGasStrategy(e.target.value)}
+                className="w-full px-3 py-2 bg-slate-800 border border-slate-700 rounded-lg text-white focus:border-stark-500 focus:outline-none"
+              >
+                <option value="slow">Slow (cheapest)</option>
+                <option value="standard">Standard (Default)</option>
+                <option value="fast">Fast (higher tip)</option>
+              </select>
+              <p className="text-xs text-slate-500 mt-1">
+                Controls how transactions queued by the agent are priced relative to the current network gas price.
+              </p>
+            </div>
+            <Input
+              label="Max Fee Ceiling (gwei)"
+              type="number"
+              min={0}
+              step={0.001}
+              value={gasMaxFeeGwei}
+              onChange={(e) => setGasMaxFeeGwei(e.target.value)}
+              placeholder="No ceiling"
+            />
+            <p className="text-xs text-slate-500 -mt-2">
+              Absolute cap on max fee per gas. If the network base fee is above this ceiling the transaction
+              is rejected instead of queued. Leave empty for no ceiling.
+            </p>
+            <Input
+              label="Confirmation Timeout (seconds)"
+              type="number"
+              min={0}
+              max={86400}
+              value={txConfirmationTimeoutSecs}
+              onChange={(e) => setTxConfirmationTimeoutSecs(Math.min(86400, Math.max(0, parseInt(e.target.value) || 0)))}
+            />
+            <p className="text-xs text-slate-500 -mt-2">
+              In partner mode, a queued transaction that isn't confirmed within this time is rejected
+              automatically and the agent is told. Set to 0 to wait indefinitely.
+            </p>
+          </CardContent>
+        </Card>
+
         {/* Infrastructure Services Section */}
         <Card>
           <CardHeader>
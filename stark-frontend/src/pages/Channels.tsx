@@ -1,5 +1,5 @@
 import { useState, useEffect } from 'react';
-import { MessageSquare, Hash, Plus, Play, Square, Trash2, Save, Pencil, Twitter, AlertTriangle, Terminal, Dices, Copy, Check } from 'lucide-react';
+import { MessageSquare, Hash, Plus, Play, Square, Ban, Trash2, Save, Pencil, Twitter, AlertTriangle, Terminal, Dices, Copy, Check } from 'lucide-react';
 import Card, { CardContent, CardHeader, CardTitle } from '@/components/ui/Card';
 import Button from '@/components/ui/Button';
 import Input from '@/components/ui/Input';
@@ -11,6 +11,7 @@ import {
   deleteChannel,
   startChannel,
   stopChannel,
+  cancelChannelDispatch,
   getChannelSettings,
   getChannelSettingsSchema,
   updateChannelSettings,
@@ -27,6 +28,7 @@ const CHANNEL_TYPES = [
   { value: 'discord', label: 'Discord', icon: MessageSquare, color: 'indigo' },
   { value: 'twitter', label: 'Twitter / X', icon: Twitter, color: 'sky' },
   { value: 'external_channel', label: 'External Channel', icon: Terminal, color: 'emerald' },
+  { value: 'matrix', label: 'Matrix', icon: Hash, color: 'teal' },
 ];
 
 function getChannelHints(channelType: string): string[] {
@@ -46,6 +48,12 @@ function getChannelHints(channelType: string): string[] {
       return [
         'To use in a group, set an <strong>Admin User ID</strong> in channel settings. Only the admin gets full agent access; all other users are restricted to safe mode. Without an admin configured, all users have full unrestricted access.',
       ];
+    case 'matrix':
+      return [
+        'Set the <strong>Homeserver URL</strong> and the bot account\'s <strong>Access Token</strong> in channel settings after creation.',
+        'Invite the bot account to a room and it joins automatically. It replies to every message in direct rooms and to mentions elsewhere.',
+        'Set <strong>Admin User IDs</strong> to restrict full agent access; all other users are limited to safe mode.',
+      ];
     case 'external_channel':
       return [
         'External Channel lets programs, scripts, and CLIs chat with your agent via HTTP API.',
@@ -404,6 +412,17 @@ export default function Channels() {
     }
   };
 
+  const handleCancelRun = async (id: number) => {
+    setActionLoading(id);
+    try {
+      await cancelChannelDispatch(id);
+    } catch (e) {
+      setError(e instanceof Error ? e.message : 'Failed to cancel the current run');
+    } finally {
+      setActionLoading(null);
+    }
+  };
+
   // Toggle edit mode for a channel (opens modal with channel data + settings)
   const toggleEditMode = async (channel: ChannelInfo) => {
     if (editingId === channel.id) {
@@ -665,15 +684,27 @@ export default function Channels() {
                         {channel.running ? 'Running' : 'Stopped'}
                       </span>
                       {channel.running ? (
-                        <Button
-                          variant="secondary"
-                          size="sm"
-                          onClick={() => handleStop(channel.id)}
-                          disabled={isActionLoading}
-                        >
-                          <Square className="w-4 h-4 sm:mr-1" />
-                          <span className="hidden sm:inline">Stop</span>
-                        </Button>
+                        <>
+                          <Button
+                            variant="secondary"
+                            size="sm"
+                            onClick={() => handleCancelRun(channel.id)}
+                            disabled={isActionLoading}
+                            title="Abort the agent's current run without stopping the channel"
+                          >
+                            <Ban className="w-4 h-4 sm:mr-1" />
+                            <span className="hidden sm:inline">Cancel Run</span>
+                          </Button>
+                          <Button
+                            variant="secondary"
+                            size="sm"
+                            onClick={() => handleStop(channel.id)}
+                            disabled={isActionLoading}
+                          >
+                            <Square className="w-4 h-4 sm:mr-1" />
+                            <span className="hidden sm:inline">Stop</span>
+                          </Button>
+                        </>
                       ) : (
                         <Button
                           variant="secondary"
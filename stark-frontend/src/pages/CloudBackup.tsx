@@ -1,10 +1,10 @@
-import { useState, useEffect } from 'react';
+import { useState, useEffect, useRef } from 'react';
 import { Cloud, Upload, Download, Shield, AlertCircle, CheckCircle, X, Key, Brain, Settings, Link2, RefreshCw, Clock, AlertTriangle, Heart, MessageSquare, Sparkles, Zap, Coins, HardDrive } from 'lucide-react';
 import { JsonRpcProvider, Contract, formatUnits } from 'ethers';
 import Card, { CardContent, CardHeader, CardTitle } from '@/components/ui/Card';
 import Button from '@/components/ui/Button';
 import UnicodeSpinner from '@/components/ui/UnicodeSpinner';
-import { backupKeysToCloud, restoreKeysFromCloud, previewCloudBackup, CloudBackupPreview, getConfigStatus } from '@/lib/api';
+import { backupKeysToCloud, restoreKeysFromCloud, previewCloudBackup, CloudBackupPreview, getConfigStatus, downloadBackupFile, restoreFromBackupFile } from '@/lib/api';
 
 function formatBytes(bytes: number): string {
   if (bytes < 1024) return `${bytes} B`;
@@ -21,6 +21,8 @@ export default function CloudBackup() {
   const [confirmRestoreModalOpen, setConfirmRestoreModalOpen] = useState(false);
   const [previewData, setPreviewData] = useState<CloudBackupPreview | null>(null);
   const [noBackupWarning, setNoBackupWarning] = useState(false);
+  const [isFileBusy, setIsFileBusy] = useState(false);
+  const fileInputRef = useRef<HTMLInputElement>(null);
 
   // Backup cost in STARKBOT tokens
   const BACKUP_COST_STARKBOT = 1000;
@@ -145,6 +147,42 @@ export default function CloudBackup() {
     }
   };
 
+  const handleDownloadFile = async () => {
+    setIsFileBusy(true);
+    setMessage(null);
+    try {
+      const blob = await downloadBackupFile();
+      const url = URL.createObjectURL(blob);
+      const a = document.createElement('a');
+      a.href = url;
+      a.download = `starkbot-backup-${new Date().toISOString().slice(0, 10)}.json`;
+      a.click();
+      URL.revokeObjectURL(url);
+      setMessage({ type: 'success', text: `Backup file downloaded (${formatBytes(blob.size)})` });
+    } catch (err) {
+      setMessage({ type: 'error', text: err instanceof Error ? err.message : 'Failed to download backup file' });
+    } finally {
+      setIsFileBusy(false);
+    }
+  };
+
+  const handleRestoreFile = async (file: File) => {
+    if (!confirm(`Restore from ${file.name}? This replaces your current channels, cron jobs and settings.`)) {
+      return;
+    }
+    setIsFileBusy(true);
+    setMessage(null);
+    try {
+      const result = await restoreFromBackupFile(await file.text());
+      setMessage({ type: 'success', text: `Restore complete! ${result.message || ''}` });
+    } catch (err) {
+      setMessage({ type: 'error', text: err instanceof Error ? err.message : 'Failed to restore backup file' });
+    } finally {
+      setIsFileBusy(false);
+      if (fileInputRef.current) fileInputRef.current.value = '';
+    }
+  };
+
   return (
     <div className="p-8">
       <div className="mb-8">
@@ -250,6 +288,47 @@ export default function CloudBackup() {
                   Restore Backup
                 </Button>
               </div>
+
+              <div className="p-4 bg-slate-900/50 rounded-lg border border-slate-700">
+                <div className="mb-3">
+                  <h3 className="text-sm font-medium text-white">Backup File</h3>
+                  <p className="text-xs text-slate-400 mt-1">
+                    Save or restore an encrypted backup file without the keystore server
+                  </p>
+                </div>
+                <div className="flex gap-2">
+                  <Button
+                    variant="secondary"
+                    size="sm"
+                    onClick={handleDownloadFile}
+                    isLoading={isFileBusy}
+                    className="flex-1"
+                  >
+                    <HardDrive className="w-4 h-4 mr-2" />
+                    Download File
+                  </Button>
+                  <Button
+                    variant="secondary"
+                    size="sm"
+                    onClick={() => fileInputRef.current?.click()}
+                    disabled={isFileBusy}
+                    className="flex-1"
+                  >
+                    <Upload className="w-4 h-4 mr-2" />
+                    Restore from File
+                  </Button>
+                  <input
+                    ref={fileInputRef}
+                    type="file"
+                    accept=".json,application/json"
+                    className="hidden"
+                    onChange={(e) => {
+                      const file = e.target.files?.[0];
+                      if (file) handleRestoreFile(file);
+                    }}
+                  />
+                </div>
+              </div>
             </div>
           </CardContent>
         </Card>
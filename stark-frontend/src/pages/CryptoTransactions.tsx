@@ -1,14 +1,14 @@
 import { useState, useEffect, useCallback } from 'react';
-import { Wallet, Clock, CheckCircle, XCircle, ExternalLink, AlertCircle, History, ListTodo, Shield } from 'lucide-react';
+import { Wallet, Clock, CheckCircle, XCircle, ExternalLink, AlertCircle, History, ListTodo, Shield, RefreshCw } from 'lucide-react';
 import UnicodeSpinner from '@/components/ui/UnicodeSpinner';
 import Card, { CardContent } from '@/components/ui/Card';
 import Button from '@/components/ui/Button';
 import { useApi } from '@/hooks/useApi';
-import type { QueuedTransactionsResponse, QueuedTransactionInfo, BroadcastedTransactionsResponse, BroadcastedTransactionInfo, X402PaymentLimit } from '@/lib/api';
-import { getBroadcastedTransactions, getQueuedTransaction, getX402PaymentLimits, updateX402PaymentLimit } from '@/lib/api';
+import type { QueuedTransactionsResponse, QueuedTransactionInfo, BroadcastedTransactionsResponse, BroadcastedTransactionInfo, BroadcastHistoryStatsResponse, X402PaymentLimit } from '@/lib/api';
+import { getBroadcastedTransactions, getBroadcastHistoryStats, getQueuedTransaction, getX402PaymentLimits, replaceQueuedTransaction, updateX402PaymentLimit } from '@/lib/api';
 import TxQueueConfirmationModal, { TxQueueTransaction } from '@/components/chat/TxQueueConfirmationModal';
 
-type StatusFilter = 'all' | 'pending' | 'broadcast' | 'confirmed' | 'failed';
+type StatusFilter = 'all' | 'pending' | 'broadcast' | 'stuck' | 'confirmed' | 'failed';
 type HistoryStatusFilter = 'all' | 'broadcast' | 'confirmed' | 'failed';
 type ModeFilter = 'all' | 'rogue' | 'partner';
 type TabType = 'queue' | 'history';
@@ -20,10 +20,12 @@ export default function CryptoTransactions() {
   const [historyModeFilter, setHistoryModeFilter] = useState<ModeFilter>('all');
   const [selectedTx, setSelectedTx] = useState<TxQueueTransaction | null>(null);
   const [isModalOpen, setIsModalOpen] = useState(false);
+  const [replacingUuid, setReplacingUuid] = useState<string | null>(null);
 
   // History data state
   const [historyData, setHistoryData] = useState<BroadcastedTransactionsResponse | null>(null);
   const [historyLoading, setHistoryLoading] = useState(false);
+  const [historyStats, setHistoryStats] = useState<BroadcastHistoryStatsResponse | null>(null);
 
   // x402 Payment Limits state
   const [paymentLimits, setPaymentLimits] = useState<X402PaymentLimit[]>([]);
@@ -62,8 +64,12 @@ export default function CryptoTransactions() {
       const params: { status?: string; broadcast_mode?: string; limit?: number } = { limit: 100 };
       if (historyStatusFilter !== 'all') params.status = historyStatusFilter;
       if (historyModeFilter !== 'all') params.broadcast_mode = historyModeFilter;
-      const result = await getBroadcastedTransactions(params);
+      const [result, stats] = await Promise.all([
+        getBroadcastedTransactions(params),
+        getBroadcastHistoryStats(),
+      ]);
       setHistoryData(result);
+      setHistoryStats(stats);
     } catch (e) {
       console.error('Failed to fetch broadcast history:', e);
     } finally {
@@ -71,6 +77,22 @@ export default function CryptoTransactions() {
     }
   };
 
+  // Rebroadcast a stuck transaction with the same nonce and higher fees
+  const handleReplace = async (uuid: string) => {
+    setReplacingUuid(uuid);
+    try {
+      const result = await replaceQueuedTransaction(uuid);
+      if (!result.success) {
+        console.error('Failed to replace transaction:', result.error);
+      }
+      refetch();
+    } catch (e) {
+      console.error('Failed to replace transaction:', e);
+    } finally {
+      setReplacingUuid(null);
+    }
+  };
+
   // Poll every 5 seconds
   useEffect(() => {
     const interval = setInterval(() => {
@@ -195,6 +217,18 @@ export default function CryptoTransactions() {
             <XCircle className="w-3 h-3" /> Failed
           </span>
         );
+      case 'stuck':
+        return (
+          <span className="flex items-center gap-1 px-2 py-1 bg-orange-500/20 text-orange-400 rounded text-xs">
+            <AlertCircle className="w-3 h-3" /> Stuck
+          </span>
+        );
+      case 'replaced':
+        return (
+          <span className="flex items-center gap-1 px-2 py-1 bg-slate-500/20 text-slate-400 rounded text-xs">
+            <RefreshCw className="w-3 h-3" /> Replaced
+          </span>
+        );
       case 'expired':
         return (
           <span className="flex items-center gap-1 px-2 py-1 bg-slate-500/20 text-slate-400 rounded text-xs">
@@ -362,6 +396,13 @@ export default function CryptoTransactions() {
             >
               Broadcast
             </Button>
+            <Button
+              variant={filter === 'stuck' ? 'primary' : 'secondary'}
+              size="sm"
+              onClick={() => setFilter('stuck')}
+            >
+              Stuck
+            </Button>
             <Button
               variant={filter === 'confirmed' ? 'primary' : 'secondary'}
               size="sm"
@@ -461,6 +502,24 @@ export default function CryptoTransactions() {
                                 {tx.error}
                               </span>
                             )}
+                            {tx.status === 'stuck' && (
+                              <button
+                                className="flex items-center gap-1 mt-1 text-xs text-orange-400 hover:text-orange-300 disabled:opacity-50"
+                                disabled={replacingUuid === tx.uuid}
+                                onClick={(e) => {
+                                  e.stopPropagation();
+                                  handleReplace(tx.uuid);
+                                }}
+                              >
+                                <RefreshCw className="w-3 h-3" />
+                                {replacingUuid === tx.uuid ? 'Replacing...' : 'Speed up'}
+                              </button>
+                            )}
+                            {tx.replaced_by && (
+                              <span className="block text-xs text-slate-500 mt-1" title={tx.replaced_by}>
+                                by {shortenUuid(tx.replaced_by)}
+                              </span>
+                            )}
                           </td>
                           <td className="py-3 px-4">
                             {tx.tx_hash ? (
@@ -644,6 +703,13 @@ export default function CryptoTransactions() {
             <p><strong>Rogue Mode:</strong> Transactions broadcast autonomously by the agent.</p>
             <p><strong>Partner Mode:</strong> Transactions confirmed by you before broadcast.</p>
             <p className="mt-2">This history persists across restarts and shows all broadcasted transactions.</p>
+            {historyStats && (
+              <p className="mt-2">
+                {historyStats.stats.total} record(s) stored, {historyStats.stats.in_flight} unconfirmed.
+                {historyStats.stats.pruned_count > 0 && ` ${historyStats.stats.pruned_count} older record(s) pruned by the retention policy.`}
+                {historyStats.retention_days && ` Finalized records are kept for ${historyStats.retention_days} days.`}
+              </p>
+            )}
           </div>
         </>
       )}
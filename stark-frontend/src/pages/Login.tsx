@@ -1,7 +1,7 @@
 import { useState, useMemo, useEffect } from 'react';
 import { useNavigate, useLocation } from 'react-router-dom';
 import { BrowserProvider } from 'ethers';
-import { generateChallenge, validateAuth, getConfigStatus, ConfigStatus } from '@/lib/api';
+import { generateChallenge, validateAuth, getConfigStatus, storeSession, ConfigStatus } from '@/lib/api';
 import Button from '@/components/ui/Button';
 import Card, { CardContent } from '@/components/ui/Card';
 import UnicodeSpinner from '@/components/ui/UnicodeSpinner';
@@ -36,6 +36,7 @@ function getWalletDeepLinks() {
 
 export default function Login() {
   const [error, setError] = useState('');
+  const [sessionExpired, setSessionExpired] = useState(false);
   const [state, setState] = useState<LoginState>('idle');
   const [connectedAddress, setConnectedAddress] = useState<string | null>(null);
   const [configStatus, setConfigStatus] = useState<ConfigStatus | null>(null);
@@ -48,6 +49,10 @@ export default function Login() {
     const token = params.get('token');
     const isFlash = params.get('flash') === 'true';
 
+    if (params.get('expired') === '1') {
+      setSessionExpired(true);
+    }
+
     if (token && isFlash) {
       setState('flash');
       // Store the token and redirect to dashboard
@@ -122,7 +127,7 @@ export default function Login() {
       const result = await validateAuth(address, challenge, signature);
 
       // Store token and navigate
-      localStorage.setItem('stark_token', result.token);
+      storeSession(result.token, result.expires_at);
       navigate('/dashboard');
     } catch (err) {
       console.error('Login error:', err);
@@ -211,6 +216,12 @@ export default function Login() {
                 </div>
               )}
 
+              {sessionExpired && !error && (
+                <div className="bg-amber-500/20 border border-amber-500/50 text-amber-400 px-4 py-3 rounded-lg text-sm">
+                  Your session has expired. Please sign in again.
+                </div>
+              )}
+
               {error && (
                 <div className="bg-red-500/20 border border-red-500/50 text-red-400 px-4 py-3 rounded-lg text-sm">
                   {error}
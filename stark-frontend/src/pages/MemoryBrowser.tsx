@@ -49,6 +49,7 @@ interface MemoryItem {
   log_date?: string | null;
   source_type?: string | null;
   created_at: string;
+  access_count?: number;
 }
 
 interface ReadMemoriesResponse {
@@ -406,6 +407,7 @@ function MemoryItemView({ item }: { item: MemoryItem }) {
         {importanceBadge(item.importance)}
         {item.source_type && <span className="text-[10px] px-1 py-0.5 rounded bg-slate-700 text-slate-400">{item.source_type}</span>}
         <ModeBadge identityId={item.identity_id} />
+        {!!item.access_count && <span className="text-[10px] text-slate-500" title="Times surfaced by search or retrieval">{item.access_count} hits</span>}
         <span className="text-xs text-slate-600 ml-auto">{item.created_at.split('.')[0]}</span>
       </div>
       <div className="text-sm text-slate-300 whitespace-pre-wrap">{item.content}</div>
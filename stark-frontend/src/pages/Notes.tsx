@@ -29,6 +29,19 @@ import {
   NotesByTagGroup,
 } from '@/lib/api';
 
+// Render a search snippet, highlighting the >>>matched<<< terms
+function renderSnippet(snippet: string) {
+  return snippet.split(/(>>>.*?<<<)/g).map((part, i) =>
+    part.startsWith('>>>') && part.endsWith('<<<') ? (
+      <mark key={i} className="bg-amber-500/30 text-amber-200 rounded px-0.5">
+        {part.slice(3, -3)}
+      </mark>
+    ) : (
+      <span key={i}>{part}</span>
+    )
+  );
+}
+
 interface TreeNode {
   name: string;
   path: string;
@@ -222,7 +235,9 @@ export default function Notes() {
     }
     setIsSearching(true);
     try {
-      const res = await searchNotes(searchQuery.trim(), 20);
+      const res = await searchNotes(searchQuery.trim(), 20, {
+        tag: activeTag ?? undefined,
+      });
       if (res.success) {
         setSearchResults(res.results);
       }
@@ -703,9 +718,7 @@ export default function Notes() {
                     )}
                     {r.snippet && (
                       <div className="text-xs text-slate-400 mt-1 line-clamp-2">
-                        {r.snippet
-                          .replace(/>>>/g, '')
-                          .replace(/<<</g, '')}
+                        {renderSnippet(r.snippet)}
                       </div>
                     )}
                   </button>
@@ -19,6 +19,7 @@ interface Session {
   message_count?: number;
   completion_status?: string;
   initial_query?: string;
+  title?: string;
   safe_mode?: boolean;
   special_role_name?: string;
   scope?: string;
@@ -771,8 +772,14 @@ export default function Sessions() {
                           </p>
                         );
                       })()}
+                      {/* Generated session title */}
+                      {session.title && (
+                        <p className="text-sm text-slate-200 mt-0.5 truncate">
+                          {session.title}
+                        </p>
+                      )}
                       {/* Web session initial query */}
-                      {session.channel_type === 'web' && session.initial_query && (
+                      {!session.title && session.channel_type === 'web' && session.initial_query && (
                         <p className="text-xs text-slate-500 mt-0.5 truncate">
                           {session.initial_query}
                         </p>
@@ -73,10 +73,18 @@ export default function Skills() {
     setError(null);
 
     try {
-      await uploadSkill(file);
+      const dependencies = await uploadSkill(file);
       await loadSkills();
+      if (dependencies) {
+        const missing = [
+          ...dependencies.missing_tools,
+          ...dependencies.missing_binaries,
+          ...dependencies.missing_api_keys,
+        ];
+        setError(`Skill installed, but some dependencies are missing: ${missing.join(', ')}`);
+      }
     } catch (err) {
-      setError('Failed to upload skill');
+      setError(err instanceof Error ? err.message : 'Failed to upload skill');
     } finally {
       setIsUploading(false);
       if (fileInputRef.current) {
@@ -21,7 +21,7 @@ import {
 
 type Tab = 'roles' | 'assignments' | 'role_assignments';
 
-const CHANNEL_TYPES = ['discord', 'twitter', 'telegram', 'slack', 'external_channel'];
+const CHANNEL_TYPES = ['discord', 'twitter', 'telegram', 'slack', 'external_channel', 'matrix'];
 const MAX_ROLES = 10;
 const MAX_ASSIGNMENTS = 100;
 const MAX_ROLE_ASSIGNMENTS = 100;
@@ -41,6 +41,7 @@ export default function SpecialRoles() {
   const [editDescription, setEditDescription] = useState('');
   const [editTools, setEditTools] = useState('');
   const [editSkills, setEditSkills] = useState('');
+  const [editDeniedTools, setEditDeniedTools] = useState('');
   const [isCreating, setIsCreating] = useState(false);
   const [isSaving, setIsSaving] = useState(false);
 
@@ -92,6 +93,7 @@ export default function SpecialRoles() {
     setEditDescription(role.description || '');
     setEditTools(role.allowed_tools.join(', '));
     setEditSkills(role.allowed_skills.join(', '));
+    setEditDeniedTools(role.denied_tools.join(', '));
     setIsCreating(false);
   };
 
@@ -102,6 +104,7 @@ export default function SpecialRoles() {
     setEditDescription('');
     setEditTools('');
     setEditSkills('');
+    setEditDeniedTools('');
   };
 
   const handleCancelCreate = () => {
@@ -121,12 +124,14 @@ export default function SpecialRoles() {
     try {
       const tools = parseCommaSeparated(editTools);
       const skills = parseCommaSeparated(editSkills);
+      const deniedTools = parseCommaSeparated(editDeniedTools);
 
       if (isCreating) {
         const created = await createSpecialRole({
           name: editName,
           allowed_tools: tools,
           allowed_skills: skills,
+          denied_tools: deniedTools,
           description: editDescription || undefined,
         });
         setRoles(prev => [...prev, created]);
@@ -136,6 +141,7 @@ export default function SpecialRoles() {
         const updated = await updateSpecialRole(selectedRole, {
           allowed_tools: tools,
           allowed_skills: skills,
+          denied_tools: deniedTools,
           description: editDescription || null,
         });
         setRoles(prev => prev.map(r => r.name === updated.name ? updated : r));
@@ -167,6 +173,7 @@ export default function SpecialRoles() {
           setEditDescription('');
           setEditTools('');
           setEditSkills('');
+          setEditDeniedTools('');
         }
       }
       setSuccess('Role deleted');
@@ -334,6 +341,7 @@ export default function SpecialRoles() {
                       <div className="font-medium">{role.name}</div>
                       <div className="text-xs text-slate-500 truncate">
                         {role.allowed_tools.length} tools, {role.allowed_skills.length} skills
+                        {role.denied_tools.length > 0 && `, ${role.denied_tools.length} denied`}
                       </div>
                     </button>
                   ))}
@@ -439,6 +447,20 @@ export default function SpecialRoles() {
                           className="w-full bg-slate-900/50 border border-slate-700 rounded-lg px-3 py-2 text-sm text-white placeholder-slate-600 focus:outline-none focus:border-stark-500"
                         />
                       </div>
+
+                      <div>
+                        <label className="block text-xs text-slate-500 mb-1">
+                          Denied Tools (comma-separated)
+                          <span className="text-slate-600 ml-1">-- tools this role can never use, even if a skill requires them</span>
+                        </label>
+                        <input
+                          type="text"
+                          value={editDeniedTools}
+                          onChange={e => setEditDeniedTools(e.target.value)}
+                          placeholder="web_fetch, exec"
+                          className="w-full bg-slate-900/50 border border-slate-700 rounded-lg px-3 py-2 text-sm text-white placeholder-slate-600 focus:outline-none focus:border-stark-500"
+                        />
+                      </div>
                     </div>
                   </>
                 ) : (
@@ -685,6 +707,9 @@ export default function SpecialRoles() {
         <p>
           <strong className="text-slate-300">Allowed Skills</strong> are granted by exact skill name. When a skill is granted, its required tools are <em>automatically</em> added to the allow list &mdash; you don't need to add them separately. For example, granting the <code className="text-xs bg-slate-700 px-1 rounded">image_generation</code> skill auto-grants its required <code className="text-xs bg-slate-700 px-1 rounded">x402_preset_fetch</code> tool.
         </p>
+        <p>
+          <strong className="text-slate-300">Denied Tools</strong> are removed from the user's tool list after everything else is granted, so they win over both allowed tools and skill grants. Skills that require a denied tool are hidden from the role.
+        </p>
         <p>
           When an assigned user messages a safe-mode channel, the dispatcher enriches their session with the role's tools and skills. Unassigned users get vanilla safe mode. Sessions with enriched permissions show a badge in the session list.
         </p>
@@ -1,10 +1,10 @@
 import { useState, useEffect, useCallback } from 'react';
-import { HardDrive, Trash2, FileText, FolderOpen, File, Folder, ChevronRight, ArrowLeft } from 'lucide-react';
+import { HardDrive, Trash2, FileText, FolderOpen, File, Folder, ChevronRight, ArrowLeft, Lock, Unlock } from 'lucide-react';
 import Card, { CardContent, CardHeader, CardTitle } from '@/components/ui/Card';
 import Button from '@/components/ui/Button';
 import Modal from '@/components/ui/Modal';
 import { useApi } from '@/hooks/useApi';
-import { apiFetch, listFilesWithSizes, deleteWorkspaceFile } from '@/lib/api';
+import { apiFetch, listFilesWithSizes, deleteWorkspaceFile, setSigningFrozen } from '@/lib/api';
 import type { FileEntry } from '@/lib/api';
 
 interface SystemInfo {
@@ -16,6 +16,15 @@ interface SystemInfo {
     percentage: number;
     breakdown: Record<string, number>;
   };
+  dispatch: {
+    max_concurrent: number | null;
+    overflow_policy: 'queue' | 'reject';
+    in_flight: number;
+    queued: number;
+    peak_in_flight: number;
+    rejected: number;
+  };
+  signing_frozen: boolean;
   uptime_secs: number;
   version: string;
 }
@@ -66,6 +75,11 @@ export default function System() {
   const [wsLoading, setWsLoading] = useState(false);
   const [deleting, setDeleting] = useState(false);
 
+  // Signing freeze state
+  const [showFreezeModal, setShowFreezeModal] = useState(false);
+  const [togglingFreeze, setTogglingFreeze] = useState(false);
+  const [freezeError, setFreezeError] = useState<string | null>(null);
+
 
   const fetchWorkspaceEntries = useCallback(async (path?: string) => {
     setWsLoading(true);
@@ -144,6 +158,20 @@ export default function System() {
     }
   };
 
+  const handleToggleSigningFreeze = async (frozen: boolean) => {
+    setTogglingFreeze(true);
+    setFreezeError(null);
+    try {
+      await setSigningFrozen(frozen);
+      refetch();
+    } catch (e) {
+      setFreezeError(String(e));
+    } finally {
+      setTogglingFreeze(false);
+      setShowFreezeModal(false);
+    }
+  };
+
   const navigateToDir = (path: string) => {
     fetchWorkspaceEntries(path);
   };
@@ -170,6 +198,50 @@ export default function System() {
         <div className="text-red-400">Failed to load system info</div>
       ) : (
         <div className="space-y-6">
+          {/* Signing kill switch */}
+          <Card className={info.signing_frozen ? 'border-red-500/50' : undefined}>
+            <CardHeader>
+              <CardTitle className="flex items-center gap-2 text-base">
+                {info.signing_frozen ? (
+                  <Lock className="w-5 h-5 text-red-400" />
+                ) : (
+                  <Unlock className="w-5 h-5 text-green-400" />
+                )}
+                Transaction Signing
+              </CardTitle>
+            </CardHeader>
+            <CardContent>
+              <div className="flex items-center justify-between gap-4">
+                <p className="text-sm text-slate-400">
+                  {info.signing_frozen
+                    ? 'Signing is frozen. The wallet will not sign transactions or typed data, and queued transactions stay pending until signing is unfrozen.'
+                    : 'Signing is active. Freeze it during an incident to stop all transaction signing and broadcasts without removing the wallet.'}
+                </p>
+                {info.signing_frozen ? (
+                  <Button
+                    variant="secondary"
+                    size="sm"
+                    onClick={() => handleToggleSigningFreeze(false)}
+                    disabled={togglingFreeze}
+                  >
+                    {togglingFreeze ? 'Unfreezing...' : 'Unfreeze'}
+                  </Button>
+                ) : (
+                  <Button
+                    variant="danger"
+                    size="sm"
+                    onClick={() => setShowFreezeModal(true)}
+                  >
+                    Freeze Signing
+                  </Button>
+                )}
+              </div>
+              {freezeError && (
+                <p className="text-sm text-red-400 mt-3">Failed to update signing freeze: {freezeError}</p>
+              )}
+            </CardContent>
+          </Card>
+
           {/* Storage Overview */}
           <Card>
             <CardHeader>
@@ -410,6 +482,21 @@ export default function System() {
                   <span className="text-slate-300 text-sm">Uptime</span>
                   <span className="text-white text-sm">{formatUptime(info.uptime_secs)}</span>
                 </div>
+                <div className="flex items-center justify-between p-3 rounded-lg bg-slate-700/50">
+                  <span className="text-slate-300 text-sm">Dispatches in flight</span>
+                  <span className="text-white font-mono text-sm">
+                    {info.dispatch.in_flight} / {info.dispatch.max_concurrent ?? '∞'}
+                  </span>
+                </div>
+                <div className="flex items-center justify-between p-3 rounded-lg bg-slate-700/50">
+                  <span className="text-slate-300 text-sm">
+                    {info.dispatch.overflow_policy === 'reject' ? 'Rejected' : 'Queued'}
+                  </span>
+                  <span className="text-white font-mono text-sm">
+                    {info.dispatch.overflow_policy === 'reject' ? info.dispatch.rejected : info.dispatch.queued}
+                    <span className="text-slate-400"> (peak {info.dispatch.peak_in_flight})</span>
+                  </span>
+                </div>
               </div>
             </CardContent>
           </Card>
@@ -446,6 +533,36 @@ export default function System() {
         </div>
       </Modal>
 
+      {/* Confirm: freeze signing */}
+      <Modal
+        isOpen={showFreezeModal}
+        onClose={() => setShowFreezeModal(false)}
+        title="Freeze Signing"
+        size="sm"
+      >
+        <p className="text-slate-300 mb-6">
+          The bot will <strong className="text-red-400">refuse to sign or broadcast any transaction</strong> until
+          signing is unfrozen. The freeze stays on across restarts.
+        </p>
+        <div className="flex gap-3">
+          <Button
+            variant="ghost"
+            onClick={() => setShowFreezeModal(false)}
+            className="flex-1"
+          >
+            Cancel
+          </Button>
+          <Button
+            variant="danger"
+            onClick={() => handleToggleSigningFreeze(true)}
+            disabled={togglingFreeze}
+            className="flex-1"
+          >
+            {togglingFreeze ? 'Freezing...' : 'Freeze'}
+          </Button>
+        </div>
+      </Modal>
+
       {/* Confirm: workspace cleanup */}
       <Modal
         isOpen={showWorkspaceModal}
@@ -16,6 +16,7 @@ export interface GatewayMessage {
   type?: 'event';
   event?: string;
   data?: unknown;
+  seq?: number;
   result?: unknown;
   error?: {
     code: number;
@@ -95,12 +96,20 @@ export interface TrackedTransaction {
 }
 
 // Confirmation events
+export interface CostEstimate {
+  amount: number;
+  currency: string;
+  is_upper_bound: boolean;
+  description: string;
+}
+
 export interface ConfirmationRequiredEvent {
   channel_id: number;
   confirmation_id: string;
   tool_name: string;
   description: string;
   parameters: Record<string, unknown>;
+  estimated_cost?: CostEstimate | null;
   instructions: string;
   timestamp: string;
 }
@@ -119,6 +128,24 @@ export interface ConfirmationRejectedEvent {
   timestamp: string;
 }
 
+// Plan approval events
+export interface PlanApprovalRequiredEvent {
+  channel_id: number;
+  session_id: number;
+  tasks: { id: number; description: string }[];
+  timeout_secs: number;
+  on_timeout: 'proceed' | 'cancel';
+  timestamp: string;
+}
+
+export interface PlanApprovalResolvedEvent {
+  channel_id: number;
+  session_id: number;
+  outcome: 'approved' | 'rejected' | 'timed_out';
+  proceeding: boolean;
+  timestamp: string;
+}
+
 // Pending confirmation state
 export interface PendingConfirmation {
   confirmation_id: string;
@@ -126,6 +153,7 @@ export interface PendingConfirmation {
   tool_name: string;
   description: string;
   parameters: Record<string, unknown>;
+  estimated_cost?: CostEstimate | null;
   timestamp: string;
 }
 